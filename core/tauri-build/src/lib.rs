@@ -24,6 +24,7 @@ mod allowlist;
 mod codegen;
 /// Mobile build functions.
 pub mod mobile;
+mod permissions;
 mod static_vcruntime;
 
 #[cfg(feature = "codegen")]
@@ -273,10 +274,30 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
 
   println!("cargo:rerun-if-env-changed=TAURI_CONFIG");
   println!("cargo:rerun-if-changed=tauri.conf.json");
+  println!(
+    "cargo:rerun-if-env-changed={}",
+    tauri_utils::config::parse::ENV_VAR_NAME
+  );
+  if let Some(env) = tauri_utils::config::parse::env() {
+    for format in tauri_utils::config::parse::SUPPORTED_FORMATS {
+      println!("cargo:rerun-if-changed={}", format.into_env_file_name(&env));
+    }
+  }
+  for (name, _) in std::env::vars() {
+    if name.starts_with(tauri_utils::config::parse::OVERRIDE_VAR_PREFIX) {
+      println!("cargo:rerun-if-env-changed={name}");
+    }
+  }
   #[cfg(feature = "config-json5")]
   println!("cargo:rerun-if-changed=tauri.conf.json5");
   #[cfg(feature = "config-toml")]
   println!("cargo:rerun-if-changed=Tauri.toml");
+  if Path::new(tauri_utils::config::parse::CAPABILITIES_FOLDER_NAME).exists() {
+    println!(
+      "cargo:rerun-if-changed={}",
+      tauri_utils::config::parse::CAPABILITIES_FOLDER_NAME
+    );
+  }
 
   let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
   let mobile = target_os == "ios" || target_os == "android";
@@ -288,7 +309,7 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   )?)?;
   if let Ok(env) = std::env::var("TAURI_CONFIG") {
     let merge_config: serde_json::Value = serde_json::from_str(&env)?;
-    json_patch::merge(&mut config, &merge_config);
+    tauri_utils::config::parse::merge(&mut config, &merge_config);
   }
   let config: Config = serde_json::from_value(config)?;
 
@@ -305,9 +326,12 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   println!("cargo:rustc-env=TAURI_ANDROID_PACKAGE_PREFIX={android_package_prefix}");
 
   if let Some(project_dir) = var_os("TAURI_ANDROID_PROJECT_PATH").map(PathBuf::from) {
-    mobile::generate_gradle_files(project_dir)?;
+    mobile::generate_gradle_files(project_dir.clone())?;
+    mobile::generate_shortcut_resources(&project_dir, &config.tauri.bundle.shortcuts)?;
   }
 
+  permissions::collect_manifests()?;
+
   cfg_alias("dev", !has_feature("custom-protocol"));
 
   let ws_path = get_workspace_dir()?;
@@ -41,12 +41,16 @@ impl PluginBuilder {
   }
 
   /// Injects the mobile templates in the given path relative to the manifest root.
+  ///
+  /// Also exports the plugin permissions manifest, see [`tauri_utils::plugin`].
   pub fn run(self) -> Result<()> {
     let target_os = var("CARGO_CFG_TARGET_OS").unwrap();
     let mobile = target_os == "android" || target_os == "ios";
     crate::cfg_alias("mobile", mobile);
     crate::cfg_alias("desktop", !mobile);
 
+    crate::permissions::export_manifest()?;
+
     match target_os.as_str() {
       "android" => {
         if let Some(path) = self.android_path {
@@ -186,6 +190,17 @@ dependencies {"
     }
   }
 
+  // the modules generated with `tauri android add-module`
+  for module in ["widget"] {
+    if project_dir.join(module).join("build.gradle.kts").exists() {
+      gradle_settings.push_str(&format!("include ':{module}'"));
+      gradle_settings.push('\n');
+
+      app_build_gradle.push('\n');
+      app_build_gradle.push_str(&format!(r#"  implementation(project(":{module}"))"#));
+    }
+  }
+
   app_build_gradle.push_str("\n}");
 
   write(&gradle_settings_path, gradle_settings).context("failed to write tauri.settings.gradle")?;
@@ -198,3 +213,101 @@ dependencies {"
 
   Ok(())
 }
+
+/// Escapes the text of an XML attribute or element.
+fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+/// Escapes the text of an Android string resource, where the quotes and the leading `@` and `?` have a meaning.
+fn escape_android_string(text: &str) -> String {
+  let mut escaped = text
+    .replace('\\', "\\\\")
+    .replace('\'', "\\'")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n");
+  if escaped.starts_with('@') || escaped.starts_with('?') {
+    escaped.insert(0, '\\');
+  }
+  escape_xml(&escaped)
+}
+
+/// Generates the static shortcuts of the app, launching the main activity with the `app.tauri.SHORTCUT` action
+/// handled by the shortcuts plugin, referenced by the `android.app.shortcuts` metadata of the main activity.
+pub(crate) fn generate_shortcut_resources(
+  project_dir: &Path,
+  shortcuts: &[tauri_utils::config::ShortcutConfig],
+) -> Result<()> {
+  let res_dir = project_dir.join("app/src/main/res");
+  let shortcuts_path = res_dir.join("xml").join("tauri_shortcuts.xml");
+  let strings_path = res_dir.join("values").join("tauri_shortcuts.xml");
+
+  // the package of the main activity, which is also the application ID
+  let package = var("WRY_ANDROID_PACKAGE").context("WRY_ANDROID_PACKAGE is not set")?;
+  let package = escape_xml(&package);
+
+  let mut xml = String::from(
+    r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY. -->
+<shortcuts xmlns:android="http://schemas.android.com/apk/res/android">"#,
+  );
+  let mut strings = String::from(
+    r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY. -->
+<resources>"#,
+  );
+
+  for (i, shortcut) in shortcuts.iter().enumerate() {
+    // the labels of the static shortcuts must be string resources
+    let title = format!("tauri_shortcut_{i}_title");
+    strings.push_str(&format!(
+      "\n  <string name=\"{title}\">{}</string>",
+      escape_android_string(&shortcut.title)
+    ));
+
+    let id = escape_xml(&shortcut.id);
+    xml.push_str(&format!(
+      "\n  <shortcut\n    android:shortcutId=\"{id}\"\n    android:enabled=\"true\"\n    android:shortcutShortLabel=\"@string/{title}\""
+    ));
+    if let Some(description) = &shortcut.description {
+      let description_name = format!("tauri_shortcut_{i}_description");
+      strings.push_str(&format!(
+        "\n  <string name=\"{description_name}\">{}</string>",
+        escape_android_string(description)
+      ));
+      xml.push_str(&format!(
+        "\n    android:shortcutLongLabel=\"@string/{description_name}\""
+      ));
+    }
+    if let Some(icon) = &shortcut.icon {
+      xml.push_str(&format!(
+        "\n    android:icon=\"@drawable/{}\"",
+        escape_xml(icon)
+      ));
+    }
+    xml.push_str(&format!(
+      r#">
+    <intent
+      android:action="app.tauri.SHORTCUT"
+      android:targetPackage="{package}"
+      android:targetClass="{package}.MainActivity">
+      <extra android:name="shortcutId" android:value="{id}" />
+    </intent>
+  </shortcut>"#
+    ));
+  }
+
+  xml.push_str("\n</shortcuts>\n");
+  strings.push_str("\n</resources>\n");
+
+  create_dir_all(shortcuts_path.parent().unwrap())?;
+  write(&shortcuts_path, xml).context("failed to write the shortcuts resource")?;
+  create_dir_all(strings_path.parent().unwrap())?;
+  write(&strings_path, strings).context("failed to write the shortcut strings")?;
+
+  Ok(())
+}
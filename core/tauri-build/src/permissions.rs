@@ -0,0 +1,70 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  env::{var, var_os},
+  fs::{read_to_string, write},
+  path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use tauri_utils::plugin::{
+  PermissionManifest, PluginPermissions, PERMISSIONS_MANIFEST_FILE_NAME,
+  PLUGIN_PERMISSIONS_FILE_NAME,
+};
+
+const MANIFEST_PATH_ENV_SUFFIX: &str = "_PERMISSIONS_MANIFEST_PATH";
+
+fn read_manifest(path: &Path) -> Result<PermissionManifest> {
+  let manifest = read_to_string(path)
+    .with_context(|| format!("failed to read permissions manifest {}", path.display()))?;
+  serde_json::from_str(&manifest)
+    .with_context(|| format!("failed to parse permissions manifest {}", path.display()))
+}
+
+/// Validates the permissions manifest of the plugin being built and exposes its path to the app build script.
+///
+/// The plugin must set the `links` key of its `Cargo.toml` for the app to receive the manifest.
+pub(crate) fn export_manifest() -> Result<()> {
+  let manifest_dir = var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap();
+  let path = manifest_dir.join(PERMISSIONS_MANIFEST_FILE_NAME);
+  println!("cargo:rerun-if-changed={}", path.display());
+
+  if path.exists() {
+    read_manifest(&path)?;
+    println!("cargo:permissions_manifest_path={}", path.display());
+  }
+
+  Ok(())
+}
+
+/// Collects the permission manifests exported by the app plugins and writes them to the `OUT_DIR`,
+/// where the codegen and the CLI read them.
+pub(crate) fn collect_manifests() -> Result<()> {
+  let mut permissions = PluginPermissions::new();
+
+  for (env, value) in std::env::vars_os() {
+    let env = env.to_string_lossy();
+    if env.starts_with("DEP_") && env.ends_with(MANIFEST_PATH_ENV_SUFFIX) {
+      println!("cargo:rerun-if-env-changed={env}");
+      let links = env["DEP_".len()..env.len() - MANIFEST_PATH_ENV_SUFFIX.len()]
+        .to_lowercase()
+        .replace('_', "-");
+      let plugin = links
+        .strip_prefix("tauri-plugin-")
+        .unwrap_or(&links)
+        .to_string();
+
+      let path = PathBuf::from(value);
+      println!("cargo:rerun-if-changed={}", path.display());
+      permissions.insert(plugin, read_manifest(&path)?);
+    }
+  }
+
+  let out_path = PathBuf::from(var("OUT_DIR").unwrap()).join(PLUGIN_PERMISSIONS_FILE_NAME);
+  write(&out_path, serde_json::to_string_pretty(&permissions)?)
+    .with_context(|| format!("failed to write {}", out_path.display()))?;
+
+  Ok(())
+}
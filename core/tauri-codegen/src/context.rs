@@ -10,11 +10,12 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use sha2::{Digest, Sha256};
 
-use tauri_utils::assets::AssetKey;
+use tauri_utils::assets::{AssetKey, ASSETS_SIZE_REPORT_FILE_NAME, EXTERNAL_ASSETS_FILE_NAME};
 use tauri_utils::config::{AppUrl, Config, PatternKind, WindowUrl};
 use tauri_utils::html::{
   inject_nonce_token, parse as parse_html, serialize_node as serialize_html_node,
 };
+use tauri_utils::plugin::{PluginPermissions, PLUGIN_PERMISSIONS_FILE_NAME};
 
 use crate::embedded_assets::{AssetOptions, CspHashes, EmbeddedAssets, EmbeddedAssetsError};
 
@@ -178,7 +179,8 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
         .security
         .dangerous_disable_asset_csp_modification
         .clone(),
-    );
+    )
+    .compression(config.build.asset_compression.clone());
   let csp = if dev {
     config
       .tauri
@@ -189,7 +191,7 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
   } else {
     config.tauri.security.csp.as_ref()
   };
-  if csp.is_some() {
+  if csp.is_some() || !config.tauri.security.window_csp.is_empty() {
     options = options.with_csp();
   }
 
@@ -244,6 +246,8 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
     out_dir
   };
 
+  assets.write_size_report(&out_dir.join(ASSETS_SIZE_REPORT_FILE_NAME))?;
+
   let default_window_icon = {
     if target == Target::Windows {
       // handle default window icons for Windows targets
@@ -341,6 +345,24 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
     quote!()
   };
 
+  // the permission manifests of the app plugins, collected by `tauri-build`
+  let plugin_permissions = std::fs::read(out_dir.join(PLUGIN_PERMISSIONS_FILE_NAME))
+    .ok()
+    .and_then(|permissions| serde_json::from_slice::<PluginPermissions>(&permissions).ok())
+    .unwrap_or_default();
+  let with_plugin_permissions_code = if plugin_permissions.is_empty() {
+    quote!()
+  } else {
+    let permissions = plugin_permissions
+      .iter()
+      .map(|(plugin, manifest)| quote!(permissions.insert(#plugin.into(), #manifest);));
+    quote!({
+      let mut permissions = #root::utils::plugin::PluginPermissions::new();
+      #(#permissions)*
+      context.set_plugin_permissions(permissions);
+    })
+  };
+
   #[cfg(target_os = "macos")]
   let info_plist = if target == Target::Darwin && dev {
     let info_plist_path = config_parent.join("Info.plist");
@@ -363,6 +385,26 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
       if let Ok(build_number) = time::OffsetDateTime::now_utc().format(&format) {
         plist.insert("CFBundleVersion".into(), build_number.into());
       }
+      let local_network = &config.tauri.bundle.local_network;
+      if let Some(usage_description) = &local_network.usage_description {
+        plist.insert(
+          "NSLocalNetworkUsageDescription".into(),
+          usage_description.clone().into(),
+        );
+      }
+      if !local_network.bonjour_services.is_empty() {
+        plist.insert(
+          "NSBonjourServices".into(),
+          plist::Value::Array(
+            local_network
+              .bonjour_services
+              .iter()
+              .cloned()
+              .map(Into::into)
+              .collect(),
+          ),
+        );
+      }
     }
 
     let out_path = out_dir.join("Info.plist");
@@ -416,12 +458,40 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
         assets: ::std::sync::Arc::new(#assets),
         schema: #schema.into(),
         key: #key.into(),
-        crypto_keys: std::boxed::Box::new(::tauri::utils::pattern::isolation::Keys::new().expect("unable to generate cryptographically secure keys for Tauri \"Isolation\" Pattern")),
       })
     }
   };
 
+  // the external assets archive is written to the target directory, where the bundler picks it up
+  let mut load_external_assets_code = quote!();
+  let mut with_asset_verification_failure_code = quote!();
+  let assets = if config.tauri.external_assets.enable && !dev && target.is_desktop() {
+    let archive_path = out_dir
+      .parent()
+      .and_then(Path::parent)
+      .and_then(Path::parent)
+      .ok_or(EmbeddedAssetsError::OutDir)?
+      .join(EXTERNAL_ASSETS_FILE_NAME);
+    let digest = assets.write_archive(&archive_path)?;
+    let pubkey = match &config.tauri.external_assets.pubkey {
+      Some(pubkey) => quote!(::std::option::Option::Some(#pubkey)),
+      None => quote!(::std::option::Option::None),
+    };
+    let input_dependencies = assets.input_dependencies();
+    load_external_assets_code = quote!(
+      #input_dependencies
+      let (external_assets, asset_verification_failure) =
+        #root::external_assets::load_verified(&#package_info, #digest, #pubkey);
+    );
+    with_asset_verification_failure_code =
+      quote!(context.set_asset_verification_failure(asset_verification_failure););
+    quote!(external_assets)
+  } else {
+    quote!(#assets)
+  };
+
   Ok(quote!({
+    #load_external_assets_code
     #[allow(unused_mut, clippy::let_and_return)]
     let mut context = #root::Context::new(
       #config,
@@ -433,6 +503,8 @@ pub fn context_codegen(data: ContextData) -> Result<TokenStream, EmbeddedAssetsE
       #pattern,
     );
     #with_system_tray_icon_code
+    #with_plugin_permissions_code
+    #with_asset_verification_failure_code
     context
   }))
 }
@@ -11,9 +11,13 @@ use std::{
   fmt::Write,
   fs::File,
   path::{Path, PathBuf},
+  sync::Mutex,
+};
+use tauri_utils::config::{AssetCompressionConfig, PatternKind};
+use tauri_utils::{
+  assets::{AssetKey, AssetsSizeReport, CspHash, ExternalAssets},
+  config::DisabledCspModificationKind,
 };
-use tauri_utils::config::PatternKind;
-use tauri_utils::{assets::AssetKey, config::DisabledCspModificationKind};
 use thiserror::Error;
 use walkdir::{DirEntry, WalkDir};
 
@@ -23,9 +27,6 @@ use brotli::enc::backward_references::BrotliEncoderParams;
 /// The subdirectory inside the target directory we want to place assets.
 const TARGET_PATH: &str = "tauri-codegen-assets";
 
-/// (key, (original filepath, compressed bytes))
-type Asset = (AssetKey, (PathBuf, PathBuf));
-
 /// All possible errors while reading and compressing an [`EmbeddedAssets`] directory
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -200,6 +201,7 @@ pub struct AssetOptions {
   pub(crate) pattern: PatternKind,
   pub(crate) freeze_prototype: bool,
   pub(crate) dangerous_disable_asset_csp_modification: DisabledCspModificationKind,
+  pub(crate) compression: AssetCompressionConfig,
   #[cfg(feature = "isolation")]
   pub(crate) isolation_schema: String,
 }
@@ -212,6 +214,7 @@ impl AssetOptions {
       pattern,
       freeze_prototype: false,
       dangerous_disable_asset_csp_modification: DisabledCspModificationKind::Flag(false),
+      compression: Default::default(),
       #[cfg(feature = "isolation")]
       isolation_schema: format!("isolation-{}", uuid::Uuid::new_v4()),
     }
@@ -231,6 +234,13 @@ impl AssetOptions {
     self
   }
 
+  /// Sets the compression level and the number of threads compressing the assets.
+  #[must_use]
+  pub fn compression(mut self, compression: AssetCompressionConfig) -> Self {
+    self.compression = compression;
+    self
+  }
+
   /// Instruct the asset handler to **NOT** modify the CSP. This is **NOT** recommended.
   pub fn dangerous_disable_asset_csp_modification(
     mut self,
@@ -258,48 +268,150 @@ impl EmbeddedAssets {
     // we need to pre-compute all files now, so that we can inject data from all files into a few
     let RawEmbeddedAssets { paths, csp_hashes } = RawEmbeddedAssets::new(input.into(), options)?;
 
-    struct CompressState {
-      csp_hashes: CspHashes,
-      assets: HashMap<AssetKey, (PathBuf, PathBuf)>,
+    // we must canonicalize the base of our paths to allow long paths on windows
+    let out_dir = std::env::var("OUT_DIR")
+      .map_err(|_| EmbeddedAssetsError::OutDir)
+      .map(PathBuf::from)
+      .and_then(|p| p.canonicalize().map_err(|_| EmbeddedAssetsError::OutDir))
+      .map(|p| p.join(TARGET_PATH))?;
+
+    // make sure that our output directory is created
+    std::fs::create_dir_all(&out_dir).map_err(|_| EmbeddedAssetsError::OutDir)?;
+
+    // the caller manipulation runs on this thread since it needs the mutable CSP hashes,
+    // only the compression of the files that are not cached yet is parallelized
+    let mut csp_hashes = csp_hashes;
+    let mut assets = HashMap::new();
+    let mut pending = Vec::new();
+    for (prefix, entry) in paths {
+      let (key, input, out_path) = Self::prepare_file(
+        &prefix,
+        entry.path(),
+        &out_dir,
+        options,
+        &mut map,
+        &mut csp_hashes,
+      )?;
+      if !out_path.exists() {
+        pending.push((entry.path().to_path_buf(), input, out_path.clone()));
+      }
+      assets.insert(key, (entry.path().to_path_buf(), out_path));
     }
+    Self::write_files(pending, options)?;
 
-    let CompressState { assets, csp_hashes } = paths.into_iter().try_fold(
-      CompressState {
-        csp_hashes,
-        assets: HashMap::new(),
+    Ok(Self { assets, csp_hashes })
+  }
+
+  /// Writes the [`AssetsSizeReport`] of the embedded assets to the given path.
+  pub fn write_size_report(&self, path: &Path) -> Result<(), EmbeddedAssetsError> {
+    let file_size = |path: &Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or_default();
+    let report = self.assets.values().fold(
+      AssetsSizeReport {
+        files: self.assets.len(),
+        ..Default::default()
       },
-      move |mut state, (prefix, entry)| {
-        let (key, asset) =
-          Self::compress_file(&prefix, entry.path(), &mut map, &mut state.csp_hashes)?;
-        state.assets.insert(key, asset);
-        Result::<_, EmbeddedAssetsError>::Ok(state)
+      |mut report, (input, output)| {
+        report.size += file_size(input);
+        report.embedded_size += file_size(output);
+        report
       },
-    )?;
+    );
 
-    Ok(Self { assets, csp_hashes })
+    std::fs::write(path, serde_json::to_vec(&report).unwrap_or_default()).map_err(|error| {
+      EmbeddedAssetsError::AssetWrite {
+        path: path.to_owned(),
+        error,
+      }
+    })
   }
 
-  /// Use highest compression level for release, the fastest one for everything else
-  #[cfg(feature = "compression")]
-  fn compression_settings() -> BrotliEncoderParams {
-    let mut settings = BrotliEncoderParams::default();
+  /// Writes the assets to an [`ExternalAssets`] archive instead of embedding them, returning the hex encoded SHA-256 digest of the archive.
+  ///
+  /// The archive is only written if its content changed, to keep its modification time for the bundler.
+  pub fn write_archive(&self, path: &Path) -> Result<String, EmbeddedAssetsError> {
+    let mut archive = ExternalAssets::new(cfg!(feature = "compression"));
+    for (key, (_, output)) in &self.assets {
+      let content = std::fs::read(output).map_err(|error| EmbeddedAssetsError::AssetRead {
+        path: output.clone(),
+        error,
+      })?;
+      archive.insert(key, &content);
+    }
+    for hash in &self.csp_hashes.scripts {
+      archive.insert_csp_hash(None, CspHash::Script(hash));
+    }
+    for hash in &self.csp_hashes.styles {
+      archive.insert_csp_hash(None, CspHash::Style(hash));
+    }
+    for (html_path, hashes) in &self.csp_hashes.inline_scripts {
+      for hash in hashes {
+        archive.insert_csp_hash(Some(html_path), CspHash::Script(hash));
+      }
+    }
+    let bytes = archive.to_bytes();
+
+    if std::fs::read(path).map_or(true, |current| current != bytes) {
+      std::fs::write(path, &bytes).map_err(|error| EmbeddedAssetsError::AssetWrite {
+        path: path.to_owned(),
+        error,
+      })?;
+    }
+
+    let mut digest = String::new();
+    for b in Sha256::digest(&bytes) {
+      write!(digest, "{b:02x}").map_err(EmbeddedAssetsError::Hex)?;
+    }
+    Ok(digest)
+  }
+
+  /// The original assets as compiler dependencies, so the context is generated again when they change.
+  pub fn input_dependencies(&self) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for (input, _) in self.assets.values() {
+      let input = input.display().to_string();
+      tokens.append_all(quote!(
+        const _: &[u8] = include_bytes!(#input);
+      ));
+    }
+    tokens
+  }
 
+  /// Use the configured compression level, or the highest one for release and the fastest one for everything else
+  #[cfg(feature = "compression")]
+  fn compression_settings(options: &AssetOptions) -> BrotliEncoderParams {
     // the following compression levels are hand-picked and are not min-maxed.
     // they have a good balance of runtime vs size for the respective profile goals.
     // see the "brotli" section of this comment https://github.com/tauri-apps/tauri/issues/3571#issuecomment-1054847558
-    if cfg!(debug_assertions) {
-      settings.quality = 2
-    } else {
-      settings.quality = 9
+    let quality = match options.compression.level {
+      Some(level) => level.min(11) as i32,
+      None if cfg!(debug_assertions) => 2,
+      None => 9,
+    };
+
+    BrotliEncoderParams {
+      quality,
+      ..Default::default()
     }
+  }
 
-    settings
+  /// Identifies how the assets are written, so the cached files of another compression setting are not reused.
+  #[allow(unused_variables)]
+  fn compression_tag(options: &AssetOptions) -> String {
+    #[cfg(feature = "compression")]
+    return format!("brotli-{}", Self::compression_settings(options).quality);
+    #[cfg(not(feature = "compression"))]
+    return "raw".into();
   }
 
-  /// Compress a file and spit out the information in a [`HashMap`] friendly form.
-  fn compress_file(
+  /// Reads a file and spits out its key, its manipulated content and the path of its compressed file.
+  ///
+  /// The compressed file is named after the hash of the content and compression setting,
+  /// so it is only compressed again when the file changed.
+  fn prepare_file(
     prefix: &Path,
     path: &Path,
+    out_dir: &Path,
+    options: &AssetOptions,
     map: &mut impl FnMut(
       &AssetKey,
       &Path,
@@ -307,7 +419,7 @@ impl EmbeddedAssets {
       &mut CspHashes,
     ) -> Result<(), EmbeddedAssetsError>,
     csp_hashes: &mut CspHashes,
-  ) -> Result<Asset, EmbeddedAssetsError> {
+  ) -> Result<(AssetKey, Vec<u8>, PathBuf), EmbeddedAssetsError> {
     let mut input = std::fs::read(path).map_err(|error| EmbeddedAssetsError::AssetRead {
       path: path.to_owned(),
       error,
@@ -325,20 +437,11 @@ impl EmbeddedAssets {
     // perform any caller-requested input manipulation
     map(&key, path, &mut input, csp_hashes)?;
 
-    // we must canonicalize the base of our paths to allow long paths on windows
-    let out_dir = std::env::var("OUT_DIR")
-      .map_err(|_| EmbeddedAssetsError::OutDir)
-      .map(PathBuf::from)
-      .and_then(|p| p.canonicalize().map_err(|_| EmbeddedAssetsError::OutDir))
-      .map(|p| p.join(TARGET_PATH))?;
-
-    // make sure that our output directory is created
-    std::fs::create_dir_all(&out_dir).map_err(|_| EmbeddedAssetsError::OutDir)?;
-
     // get a hash of the input - allows for caching existing files
     let hash = {
       let mut hasher = crate::vendor::blake3_reference::Hasher::default();
       hasher.update(&input);
+      hasher.update(Self::compression_tag(options).as_bytes());
 
       let mut bytes = [0u8; 32];
       hasher.finalize(&mut bytes);
@@ -357,40 +460,101 @@ impl EmbeddedAssets {
       out_dir.join(hash)
     };
 
-    // only compress and write to the file if it doesn't already exist.
-    if !out_path.exists() {
-      #[allow(unused_mut)]
-      let mut out_file =
-        File::create(&out_path).map_err(|error| EmbeddedAssetsError::AssetWrite {
-          path: out_path.clone(),
-          error,
-        })?;
+    Ok((key, input, out_path))
+  }
 
-      #[cfg(not(feature = "compression"))]
-      {
-        use std::io::Write;
-        out_file
-          .write_all(&input)
-          .map_err(|error| EmbeddedAssetsError::AssetWrite {
-            path: path.to_owned(),
-            error,
-          })?;
+  /// Compresses the (original path, content, compressed path) files on the configured number of threads.
+  fn write_files(
+    files: Vec<(PathBuf, Vec<u8>, PathBuf)>,
+    options: &AssetOptions,
+  ) -> Result<(), EmbeddedAssetsError> {
+    let threads = options
+      .compression
+      .threads
+      .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+      .unwrap_or(1)
+      .clamp(1, files.len().max(1));
+    if threads == 1 {
+      for (path, input, out_path) in files {
+        Self::write_file(&path, input, &out_path, options)?;
       }
+      return Ok(());
+    }
 
-      #[cfg(feature = "compression")]
-      {
-        let mut input = std::io::Cursor::new(input);
-        // entirely write input to the output file path with compression
-        brotli::BrotliCompress(&mut input, &mut out_file, &Self::compression_settings()).map_err(
-          |error| EmbeddedAssetsError::AssetWrite {
-            path: path.to_owned(),
-            error,
-          },
-        )?;
-      }
+    // the largest files are compressed first so a thread doesn't start one at the end
+    let mut files = files;
+    files.sort_by_key(|(_, input, _)| std::cmp::Reverse(input.len()));
+    let files = Mutex::new(files.into_iter());
+    std::thread::scope(|scope| {
+      let workers = (0..threads)
+        .map(|_| {
+          scope.spawn(|| loop {
+            let file = files.lock().unwrap().next();
+            match file {
+              Some((path, input, out_path)) => Self::write_file(&path, input, &out_path, options)?,
+              None => return Ok(()),
+            }
+          })
+        })
+        .collect::<Vec<_>>();
+      workers
+        .into_iter()
+        .try_for_each(|worker| worker.join().expect("asset compression thread panicked"))
+    })
+  }
+
+  /// Compresses the content to the output path.
+  #[allow(unused_variables)]
+  fn write_file(
+    path: &Path,
+    input: Vec<u8>,
+    out_path: &Path,
+    options: &AssetOptions,
+  ) -> Result<(), EmbeddedAssetsError> {
+    // write to a temporary file first so an interrupted build doesn't leave a truncated asset in the cache
+    let tmp_path = out_path.with_file_name(format!(
+      "{}.{}.tmp",
+      out_path.file_name().unwrap().to_string_lossy(),
+      std::process::id()
+    ));
+    #[allow(unused_mut)]
+    let mut out_file =
+      File::create(&tmp_path).map_err(|error| EmbeddedAssetsError::AssetWrite {
+        path: tmp_path.clone(),
+        error,
+      })?;
+
+    #[cfg(not(feature = "compression"))]
+    {
+      use std::io::Write;
+      out_file
+        .write_all(&input)
+        .map_err(|error| EmbeddedAssetsError::AssetWrite {
+          path: path.to_owned(),
+          error,
+        })?;
     }
 
-    Ok((key, (path.into(), out_path)))
+    #[cfg(feature = "compression")]
+    {
+      let mut input = std::io::Cursor::new(input);
+      // entirely write input to the output file path with compression
+      brotli::BrotliCompress(
+        &mut input,
+        &mut out_file,
+        &Self::compression_settings(options),
+      )
+      .map_err(|error| EmbeddedAssetsError::AssetWrite {
+        path: path.to_owned(),
+        error,
+      })?;
+    }
+
+    drop(out_file);
+    std::fs::rename(&tmp_path, out_path).map_err(|error| EmbeddedAssetsError::AssetWrite {
+      path: out_path.to_owned(),
+      error,
+    })
   }
 }
 
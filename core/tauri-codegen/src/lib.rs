@@ -61,14 +61,19 @@ pub fn get_config(path: &Path) -> Result<(Config, PathBuf), CodegenConfigError>
   if let Ok(env) = std::env::var("TAURI_CONFIG") {
     let merge_config: serde_json::Value =
       serde_json::from_str(&env).map_err(CodegenConfigError::FormatInline)?;
-    json_patch::merge(&mut config, &merge_config);
+    tauri_utils::config::parse::merge(&mut config, &merge_config);
   }
 
   let old_cwd = std::env::current_dir().map_err(CodegenConfigError::CurrentDir)?;
   // Set working directory to where `tauri.config.json` is, so that relative paths in it are parsed correctly.
   std::env::set_current_dir(parent.clone()).map_err(CodegenConfigError::CurrentDir)?;
 
-  let config = serde_json::from_value(config)?;
+  let mut config: Config = serde_json::from_value(config)?;
+  config
+    .tauri
+    .security
+    .capabilities
+    .extend(tauri_utils::config::parse::read_capabilities(&parent)?);
 
   // Reset working directory.
   std::env::set_current_dir(old_cwd).map_err(CodegenConfigError::CurrentDir)?;
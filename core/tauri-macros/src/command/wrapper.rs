@@ -10,14 +10,18 @@ use syn::{
   ext::IdentExt,
   parse::{Parse, ParseStream},
   parse_macro_input,
+  punctuated::Punctuated,
   spanned::Spanned,
-  FnArg, ItemFn, Lit, Meta, Pat, Token, Visibility,
+  FnArg, ItemFn, Lit, LitStr, Meta, Pat, Token, Visibility,
 };
 
 struct WrapperAttributes {
   root: TokenStream2,
   execution_context: ExecutionContext,
   argument_case: ArgumentCase,
+  allowed_origins: Option<Vec<LitStr>>,
+  /// How long the responses are cached, in milliseconds.
+  cache: Option<u64>,
 }
 
 impl Parse for WrapperAttributes {
@@ -26,48 +30,75 @@ impl Parse for WrapperAttributes {
       root: quote!(::tauri),
       execution_context: ExecutionContext::Blocking,
       argument_case: ArgumentCase::Camel,
+      allowed_origins: None,
+      cache: None,
     };
 
     loop {
-      match input.parse::<Meta>() {
-        Ok(Meta::List(_)) => {}
-        Ok(Meta::NameValue(v)) => {
-          if v.path.is_ident("rename_all") {
-            if let Lit::Str(s) = v.lit {
-              wrapper_attributes.argument_case = match s.value().as_str() {
-                "snake_case" => ArgumentCase::Snake,
-                "camelCase" => ArgumentCase::Camel,
-                _ => {
-                  return Err(syn::Error::new(
+      // `allowed_origins = [..]` is not a valid `Meta` so it is parsed first
+      if input
+        .fork()
+        .parse::<Ident>()
+        .map_or(false, |i| i == "allowed_origins")
+      {
+        input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let origins = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+        wrapper_attributes.allowed_origins = Some(origins.into_iter().collect());
+      } else {
+        match input.parse::<Meta>() {
+          Ok(Meta::List(_)) => {}
+          Ok(Meta::NameValue(v)) => {
+            if v.path.is_ident("rename_all") {
+              if let Lit::Str(s) = v.lit {
+                wrapper_attributes.argument_case = match s.value().as_str() {
+                  "snake_case" => ArgumentCase::Snake,
+                  "camelCase" => ArgumentCase::Camel,
+                  _ => {
+                    return Err(syn::Error::new(
+                      s.span(),
+                      "expected \"camelCase\" or \"snake_case\"",
+                    ))
+                  }
+                };
+              }
+            } else if v.path.is_ident("cache") {
+              if let Lit::Str(s) = v.lit {
+                wrapper_attributes.cache = Some(parse_duration(&s.value()).ok_or_else(|| {
+                  syn::Error::new(
                     s.span(),
-                    "expected \"camelCase\" or \"snake_case\"",
-                  ))
-                }
-              };
+                    "expected a duration such as \"500ms\", \"5s\", \"10m\" or \"1h\"",
+                  )
+                })?);
+              }
+            } else if v.path.is_ident("root") {
+              if let Lit::Str(s) = v.lit {
+                let lit = s.value();
+
+                wrapper_attributes.root = if lit == "crate" {
+                  quote!($crate)
+                } else {
+                  let ident = Ident::new(&lit, Span::call_site());
+                  quote!(#ident)
+                };
+              }
             }
-          } else if v.path.is_ident("root") {
-            if let Lit::Str(s) = v.lit {
-              let lit = s.value();
-
-              wrapper_attributes.root = if lit == "crate" {
-                quote!($crate)
-              } else {
-                let ident = Ident::new(&lit, Span::call_site());
-                quote!(#ident)
-              };
+          }
+          Ok(Meta::Path(p)) => {
+            if p.is_ident("async") {
+              wrapper_attributes.execution_context = ExecutionContext::Async;
+            } else if p.is_ident("blocking") {
+              wrapper_attributes.execution_context = ExecutionContext::Pool;
+            } else {
+              return Err(syn::Error::new(p.span(), "expected `async` or `blocking`"));
             }
           }
-        }
-        Ok(Meta::Path(p)) => {
-          if p.is_ident("async") {
-            wrapper_attributes.execution_context = ExecutionContext::Async;
-          } else {
-            return Err(syn::Error::new(p.span(), "expected `async`"));
+          Err(_e) => {
+            break;
           }
         }
-        Err(_e) => {
-          break;
-        }
       }
 
       let lookahead = input.lookahead1();
@@ -80,10 +111,28 @@ impl Parse for WrapperAttributes {
   }
 }
 
+/// Parses a duration such as `500ms`, `5s`, `10m` or `1h` into milliseconds.
+fn parse_duration(duration: &str) -> Option<u64> {
+  let unit_start = duration.find(|c: char| !c.is_ascii_digit())?;
+  let (value, unit) = duration.split_at(unit_start);
+  let value = value.parse::<u64>().ok()?;
+  let millis = match unit {
+    "ms" => 1,
+    "s" => 1000,
+    "m" => 60 * 1000,
+    "h" => 60 * 60 * 1000,
+    _ => return None,
+  };
+  value.checked_mul(millis)
+}
+
 /// The execution context of the command.
 enum ExecutionContext {
   Async,
+  /// Runs on the thread processing the IPC message, the main thread.
   Blocking,
+  /// Runs on the blocking pool.
+  Pool,
 }
 
 /// The case of each argument name.
@@ -175,16 +224,23 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
 
   // body to the command wrapper or a `compile_error!` of an error occurred while parsing it.
   let (body, attributes) = syn::parse::<WrapperAttributes>(attributes)
-    .map(|mut attrs| {
+    .and_then(|mut attrs| {
       if function.sig.asyncness.is_some() {
+        if let ExecutionContext::Pool = attrs.execution_context {
+          return Err(syn::Error::new(
+            function.sig.asyncness.span(),
+            "async commands cannot be `blocking`",
+          ));
+        }
         attrs.execution_context = ExecutionContext::Async;
       }
-      attrs
+      Ok(attrs)
     })
     .and_then(|attrs| {
       let body = match attrs.execution_context {
         ExecutionContext::Async => body_async(&function, &invoke, &attrs),
         ExecutionContext::Blocking => body_blocking(&function, &invoke, &attrs),
+        ExecutionContext::Pool => body_pool(&function, &invoke, &attrs),
       };
       body.map(|b| (b, Some(attrs)))
     })
@@ -192,6 +248,32 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
 
   let Invoke { message, resolver } = invoke;
 
+  // reject the invoke before deserializing the arguments if the window isn't allowed to call the command
+  let origin_check = attributes
+    .as_ref()
+    .and_then(|a| a.allowed_origins.as_ref())
+    .map(|origins| {
+      quote!(
+        if let Err(err) = ensure_origin_allowed(&#message, &[#(#origins),*]) {
+          #resolver.invoke_error(err);
+          return true;
+        }
+      )
+    });
+
+  // respond with the cached response, or keep the slot where the computed response is cached
+  let cache_lookup = attributes.as_ref().and_then(|a| a.cache).map(|ttl| {
+    quote!(
+      let __tauri_cache_slot__ = match command_cache(&#message, #ttl) {
+        CacheLookup::Hit(response) => {
+          #resolver.respond(Ok(response));
+          return true;
+        }
+        CacheLookup::Miss(slot) => slot,
+      };
+    )
+  });
+
   let root = attributes
     .map(|a| a.root)
     .unwrap_or_else(|| quote!(::tauri));
@@ -213,6 +295,10 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
           #[allow(unused_variables)]
           let #root::Invoke { message: #message, resolver: #resolver } = $invoke;
 
+          #origin_check
+
+          #cache_lookup
+
           #body
       }};
     }
@@ -236,11 +322,16 @@ fn body_async(
 ) -> syn::Result<TokenStream2> {
   let Invoke { message, resolver } = invoke;
   parse_args(function, message, attributes).map(|args| {
+    let response = if attributes.cache.is_some() {
+      quote!(__tauri_cache_slot__.wrap(kind.future(result)).await)
+    } else {
+      quote!(kind.future(result).await)
+    };
     quote! {
       #resolver.respond_async_serialized(async move {
         let result = $path(#(#args?),*);
         let kind = (&result).async_kind();
-        kind.future(result).await
+        #response
       });
       return true;
     }
@@ -266,6 +357,16 @@ fn body_blocking(
     Err(err) => { #resolver.invoke_error(err); return true },
   });
 
+  // the cached commands still run on the calling thread, only their serialized response is cached
+  if attributes.cache.is_some() {
+    return Ok(quote! {
+      let result = $path(#(match #args #match_body),*);
+      let kind = (&result).async_kind();
+      #resolver.respond_async_serialized(__tauri_cache_slot__.wrap(kind.future(result)));
+      return true;
+    });
+  }
+
   Ok(quote! {
     let result = $path(#(match #args #match_body),*);
     let kind = (&result).blocking_kind();
@@ -274,6 +375,46 @@ fn body_blocking(
   })
 }
 
+/// Generates a command response running the function on the blocking pool.
+///
+/// The arguments are parsed on the pool thread, which owns the message.
+fn body_pool(
+  function: &ItemFn,
+  invoke: &Invoke,
+  attributes: &WrapperAttributes,
+) -> syn::Result<TokenStream2> {
+  let Invoke { message, resolver } = invoke;
+  let args = parse_args(function, message, attributes)?;
+
+  let match_body = quote!({
+    Ok(arg) => arg,
+    Err(err) => { #resolver.invoke_error(err); return },
+  });
+
+  let respond = if attributes.cache.is_some() {
+    quote! {
+      let kind = (&result).async_kind();
+      #resolver.respond_async_serialized(__tauri_cache_slot__.wrap(kind.future(result)));
+    }
+  } else {
+    quote! {
+      let kind = (&result).blocking_kind();
+      kind.block(result, #resolver);
+    }
+  };
+
+  Ok(quote! {
+    let __tauri_pool_resolver__ = #resolver.clone();
+    if let Err(err) = spawn_blocking(&#message.clone(), move || {
+      let result = $path(#(match #args #match_body),*);
+      #respond
+    }) {
+      __tauri_pool_resolver__.invoke_error(err);
+    }
+    return true;
+  })
+}
+
 /// Parse all arguments for the command wrapper to use from the signature of the command function.
 fn parse_args(
   function: &ItemFn,
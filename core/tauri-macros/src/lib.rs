@@ -15,6 +15,14 @@ mod context;
 
 /// Mark a function as a command handler. It creates a wrapper function with the necessary glue code.
 ///
+/// Synchronous commands run on the main thread, which processes the IPC messages and the window events,
+/// so a slow command freezes the UI until it returns. `#[command(blocking)]` runs the command on the blocking pool instead,
+/// configured with `tauri > blockingPool` in `tauri.conf.json`. The invoke is rejected when the pool queue is full.
+/// Commands using APIs that must run on the main thread, such as creating windows on macOS, must not be `blocking`.
+///
+/// `#[command(cache = "5s")]` caches the successful responses of a read-only command by window and arguments
+/// for the given duration (`ms`, `s`, `m` or `h`), see `AppHandle::invalidate_command_cache`.
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
@@ -8,7 +8,7 @@ use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandl
 use tauri_runtime::{
   http::{header::CONTENT_TYPE, Request as HttpRequest, RequestParts, Response as HttpResponse},
   menu::{AboutMetadata, CustomMenuItem, Menu, MenuEntry, MenuHash, MenuId, MenuItem, MenuUpdate},
-  monitor::Monitor,
+  monitor::{FullscreenMode, Monitor, VideoMode},
   webview::{WebviewIpcHandler, WindowBuilder, WindowBuilderBase},
   window::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
@@ -528,10 +528,51 @@ impl From<MonitorHandleWrapper> for Monitor {
       position: PhysicalPositionWrapper(monitor.0.position()).into(),
       size: PhysicalSizeWrapper(monitor.0.size()).into(),
       scale_factor: monitor.0.scale_factor(),
+      video_modes: monitor
+        .0
+        .video_modes()
+        .map(|mode| VideoMode {
+          size: PhysicalSizeWrapper(mode.size()).into(),
+          bit_depth: mode.bit_depth(),
+          refresh_rate: mode.refresh_rate(),
+        })
+        .collect(),
     }
   }
 }
 
+/// Converts the fullscreen mode to the tao one, finding the monitor among the available monitors of the window.
+fn to_wry_fullscreen(window: &Window, mode: FullscreenMode) -> Option<Fullscreen> {
+  let find_monitor = |monitor: &Monitor| {
+    window.available_monitors().find(|m| {
+      let position = m.position();
+      m.name() == monitor.name
+        && position.x == monitor.position.x
+        && position.y == monitor.position.y
+    })
+  };
+
+  match mode {
+    FullscreenMode::Borderless(monitor) => Some(Fullscreen::Borderless(
+      monitor.and_then(|monitor| find_monitor(&monitor)),
+    )),
+    FullscreenMode::Exclusive {
+      monitor,
+      video_mode,
+    } => find_monitor(&monitor)
+      .and_then(|monitor| {
+        monitor.video_modes().find(|mode| {
+          let size = mode.size();
+          size.width == video_mode.size.width
+            && size.height == video_mode.size.height
+            && mode.bit_depth() == video_mode.bit_depth
+            && mode.refresh_rate() == video_mode.refresh_rate
+        })
+      })
+      .map(Fullscreen::Exclusive),
+  }
+}
+
 pub struct PhysicalPositionWrapper<T>(pub WryPhysicalPosition<T>);
 
 impl<T> From<PhysicalPositionWrapper<T>> for PhysicalPosition<T> {
@@ -681,6 +722,7 @@ impl From<CursorIcon> for CursorIconWrapper {
 pub struct WindowBuilderWrapper {
   inner: WryWindowBuilder,
   center: bool,
+  ignore_cursor_events: bool,
   #[cfg(target_os = "macos")]
   tabbing_identifier: Option<String>,
   menu: Option<Menu>,
@@ -744,6 +786,7 @@ impl WindowBuilder for WindowBuilderWrapper {
         .always_on_top(config.always_on_top)
         .content_protected(config.content_protected)
         .skip_taskbar(config.skip_taskbar)
+        .ignore_cursor_events(config.ignore_cursor_events)
         .theme(config.theme)
         .shadow(config.shadow);
 
@@ -760,6 +803,16 @@ impl WindowBuilder for WindowBuilderWrapper {
       if config.center {
         window = window.center();
       }
+
+      if config.kiosk {
+        window = window
+          .fullscreen(true)
+          .always_on_top(true)
+          .decorations(false)
+          .resizable(false)
+          .minimizable(false)
+          .closable(false);
+      }
     }
 
     window
@@ -954,6 +1007,11 @@ impl WindowBuilder for WindowBuilderWrapper {
     self
   }
 
+  fn ignore_cursor_events(mut self, ignore: bool) -> Self {
+    self.ignore_cursor_events = ignore;
+    self
+  }
+
   #[allow(unused_variables, unused_mut)]
   fn theme(mut self, theme: Option<Theme>) -> Self {
     self.inner = self.inner.with_theme(if let Some(t) = theme {
@@ -1119,6 +1177,7 @@ pub enum WindowMessage {
   SetMaxSize(Option<Size>),
   SetPosition(Position),
   SetFullscreen(bool),
+  SetFullscreenMode(Option<FullscreenMode>),
   SetFocus,
   SetIcon(WryWindowIcon),
   SetSkipTaskbar(bool),
@@ -1580,6 +1639,13 @@ impl<T: UserEvent> Dispatch<T> for WryDispatcher<T> {
     )
   }
 
+  fn set_fullscreen_mode(&self, mode: Option<FullscreenMode>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::SetFullscreenMode(mode)),
+    )
+  }
+
   fn set_focus(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2463,6 +2529,17 @@ fn handle_user_message<T: UserEvent>(
                 window.set_fullscreen(None)
               }
             }
+            WindowMessage::SetFullscreenMode(mode) => {
+              // an unknown monitor or video mode leaves the window unchanged
+              match mode {
+                Some(mode) => {
+                  if let Some(fullscreen) = to_wry_fullscreen(&window, mode) {
+                    window.set_fullscreen(Some(fullscreen));
+                  }
+                }
+                None => window.set_fullscreen(None),
+              }
+            }
             WindowMessage::SetFocus => {
               window.set_focus();
             }
@@ -2681,6 +2758,16 @@ fn handle_event_loop<T: UserEvent>(
       callback(RunEvent::Exit);
     }
 
+    #[cfg(target_os = "macos")]
+    Event::Reopen {
+      has_visible_windows,
+      ..
+    } => {
+      callback(RunEvent::Reopen {
+        has_visible_windows,
+      });
+    }
+
     Event::MenuEvent {
       window_id,
       menu_id,
@@ -3051,6 +3138,27 @@ fn create_webview<T: UserEvent>(
   if window_builder.center {
     let _ = center_window(&window, window.inner_size());
   }
+
+  // the builder of tao cannot ignore the cursor events, so they are ignored before the window is shown
+  #[cfg(desktop)]
+  if window_builder.ignore_cursor_events {
+    let _ = window.set_ignore_cursor_events(true);
+  }
+
+  // without a compositor the transparent pixels of the window are painted black
+  #[cfg(all(target_os = "linux", debug_assertions))]
+  if is_window_transparent {
+    use gtk::prelude::GtkWindowExt;
+    if !window
+      .gtk_window()
+      .screen()
+      .map_or(true, |screen| screen.is_composited())
+    {
+      debug_eprintln!(
+        "The window `{label}` is set to be transparent but the screen is not composited, so its transparent pixels are painted black."
+      );
+    }
+  }
   let mut webview_builder = WebViewBuilder::new(window)
     .map_err(|e| Error::CreateWebview(Box::new(e)))?
     .with_url(&url)
@@ -3139,7 +3247,7 @@ fn create_webview<T: UserEvent>(
 
   #[cfg(any(debug_assertions, feature = "devtools"))]
   {
-    webview_builder = webview_builder.with_devtools(true);
+    webview_builder = webview_builder.with_devtools(!webview_attributes.kiosk);
   }
 
   #[cfg(target_os = "android")]
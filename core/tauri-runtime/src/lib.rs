@@ -307,6 +307,12 @@ pub enum RunEvent<T: UserEvent> {
   ///
   /// This event is useful as a place to put your code that should be run after all state-changing events have been handled and you want to do stuff (updating state, performing calculations, etc) that happens as the “main body” of your event loop.
   MainEventsCleared,
+  /// Emitted when the app is activated again while it is running, e.g. by clicking its dock icon.
+  #[cfg(target_os = "macos")]
+  Reopen {
+    /// Whether the app has visible windows.
+    has_visible_windows: bool,
+  },
   /// A custom event defined by the user.
   UserEvent(T),
 }
@@ -724,6 +730,9 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
   /// Updates the window fullscreen state.
   fn set_fullscreen(&self, fullscreen: bool) -> Result<()>;
 
+  /// Shows the window in fullscreen with the given mode, or leaves the fullscreen with `None`.
+  fn set_fullscreen_mode(&self, mode: Option<monitor::FullscreenMode>) -> Result<()>;
+
   /// Bring the window to front and focus.
   fn set_focus(&self) -> Result<()>;
 
@@ -16,4 +16,32 @@ pub struct Monitor {
   pub position: PhysicalPosition<i32>,
   /// Returns the scale factor that can be used to map logical pixels to physical pixels, and vice versa.
   pub scale_factor: f64,
+  /// The video modes supported by the monitor in exclusive fullscreen.
+  pub video_modes: Vec<VideoMode>,
+}
+
+/// A video mode of a monitor, which an exclusive fullscreen window switches the monitor to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+  /// The resolution of the video mode.
+  pub size: PhysicalSize<u32>,
+  /// The bit depth of the video mode, the number of bits used to represent a color.
+  pub bit_depth: u16,
+  /// The refresh rate of the video mode in hertz.
+  pub refresh_rate: u16,
+}
+
+/// How a window is shown in fullscreen.
+#[derive(Debug, Clone)]
+pub enum FullscreenMode {
+  /// A borderless window covering the given monitor, or the current monitor of the window if `None`.
+  Borderless(Option<Monitor>),
+  /// The window takes exclusive control of the monitor, switching it to the given video mode,
+  /// which must be one of the [`Monitor::video_modes`].
+  Exclusive {
+    /// The monitor of the window.
+    monitor: Monitor,
+    /// The video mode of the monitor.
+    video_mode: VideoMode,
+  },
 }
@@ -9,7 +9,7 @@ use crate::{menu::Menu, window::DetachedWindow, Icon};
 #[cfg(target_os = "macos")]
 use tauri_utils::TitleBarStyle;
 use tauri_utils::{
-  config::{WindowConfig, WindowEffectsConfig, WindowUrl},
+  config::{TitleBarOverlayConfig, WindowConfig, WindowEffectsConfig, WindowUrl},
   Theme,
 };
 
@@ -30,13 +30,17 @@ pub struct WebviewAttributes {
   pub accept_first_mouse: bool,
   pub additional_browser_args: Option<String>,
   pub window_effects: Option<WindowEffectsConfig>,
+  pub title_bar_overlay: Option<TitleBarOverlayConfig>,
   pub incognito: bool,
+  pub kiosk: bool,
+  pub process_isolation: bool,
 }
 
 impl From<&WindowConfig> for WebviewAttributes {
   fn from(config: &WindowConfig) -> Self {
     let mut builder = Self::new(config.url.clone());
     builder = builder.incognito(config.incognito);
+    builder = builder.kiosk(config.kiosk);
     builder = builder.accept_first_mouse(config.accept_first_mouse);
     if !config.file_drop_enabled {
       builder = builder.disable_file_drop_handler();
@@ -50,6 +54,9 @@ impl From<&WindowConfig> for WebviewAttributes {
     if let Some(effects) = &config.window_effects {
       builder = builder.window_effects(effects.clone());
     }
+    if let Some(overlay) = &config.title_bar_overlay {
+      builder = builder.title_bar_overlay(overlay.clone());
+    }
     builder
   }
 }
@@ -66,7 +73,10 @@ impl WebviewAttributes {
       accept_first_mouse: false,
       additional_browser_args: None,
       window_effects: None,
+      title_bar_overlay: None,
       incognito: false,
+      kiosk: false,
+      process_isolation: false,
     }
   }
 
@@ -129,12 +139,26 @@ impl WebviewAttributes {
     self
   }
 
+  /// Sets the layout of the window controls drawn over the webview.
+  #[must_use]
+  pub fn title_bar_overlay(mut self, overlay: TitleBarOverlayConfig) -> Self {
+    self.title_bar_overlay = Some(overlay);
+    self
+  }
+
   /// Enable or disable incognito mode for the WebView.
   #[must_use]
   pub fn incognito(mut self, incognito: bool) -> Self {
     self.incognito = incognito;
     self
   }
+
+  /// Whether the webview is shown in kiosk mode, disabling its devtools.
+  #[must_use]
+  pub fn kiosk(mut self, kiosk: bool) -> Self {
+    self.kiosk = kiosk;
+    self
+  }
 }
 
 /// Do **NOT** implement this trait except for use in a custom [`Runtime`](crate::Runtime).
@@ -259,6 +283,10 @@ pub trait WindowBuilder: WindowBuilderBase {
   #[must_use]
   fn skip_taskbar(self, skip: bool) -> Self;
 
+  /// Sets whether the window ignores the cursor events, letting them pass through to the windows below it.
+  #[must_use]
+  fn ignore_cursor_events(self, ignore: bool) -> Self;
+
   /// Sets whether or not the window has shadow.
   ///
   /// ## Platform-specific
@@ -7,11 +7,34 @@
 
 #[doc(hidden)]
 pub use phf;
+use serde::{Deserialize, Serialize};
 use std::{
   borrow::Cow,
+  collections::HashMap,
   path::{Component, Path},
 };
 
+/// The name of the file, inside the `OUT_DIR` of the app crate, where the codegen writes the [`AssetsSizeReport`].
+pub const ASSETS_SIZE_REPORT_FILE_NAME: &str = "tauri-assets-report.json";
+
+/// The name of the [`ExternalAssets`] archive, written to the target directory and bundled at the root of the resources directory.
+pub const EXTERNAL_ASSETS_FILE_NAME: &str = "frontend-assets.bin";
+
+/// The first bytes of an [`ExternalAssets`] archive.
+const EXTERNAL_ASSETS_MAGIC: &[u8; 8] = b"TAURIFA1";
+
+/// Size information of the embedded frontend assets.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetsSizeReport {
+  /// The number of embedded files.
+  pub files: usize,
+  /// The total size of the files, in bytes.
+  pub size: u64,
+  /// The total size of the embedded (compressed when the `compression` feature is enabled) files, in bytes.
+  pub embedded_size: u64,
+}
+
 /// Represent an asset file path in a normalized way.
 ///
 /// The following rules are enforced and added if needed:
@@ -111,6 +134,12 @@ pub trait Assets: Send + Sync + 'static {
 
   /// Gets the hashes for the CSP tag of the HTML on the given path.
   fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_>;
+
+  /// Get the brotli compressed content of the passed [`AssetKey`], if the assets are stored compressed.
+  fn get_compressed(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    let _ = key;
+    None
+  }
 }
 
 /// [`Assets`] implementation that only contains compile-time compressed and embedded assets.
@@ -163,6 +192,11 @@ impl Assets for EmbeddedAssets {
       .map(|a| Cow::Owned(a.to_vec()))
   }
 
+  #[cfg(feature = "compression")]
+  fn get_compressed(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    self.assets.get(key.as_ref()).map(|&a| Cow::Borrowed(a))
+  }
+
   fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
     Box::new(
       self
@@ -180,3 +214,201 @@ impl Assets for EmbeddedAssets {
     )
   }
 }
+
+/// A CSP hash stored in an [`ExternalAssets`] archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ArchivedCspHash {
+  Script(String),
+  Style(String),
+}
+
+impl<'a> From<&'a ArchivedCspHash> for CspHash<'a> {
+  fn from(hash: &'a ArchivedCspHash) -> Self {
+    match hash {
+      ArchivedCspHash::Script(hash) => Self::Script(hash),
+      ArchivedCspHash::Style(hash) => Self::Style(hash),
+    }
+  }
+}
+
+/// The index of an [`ExternalAssets`] archive.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalAssetsIndex {
+  /// Whether the assets are brotli compressed.
+  compressed: bool,
+  /// The offset and length of each asset in the archive data.
+  assets: HashMap<String, (usize, usize)>,
+  global_hashes: Vec<ArchivedCspHash>,
+  html_hashes: HashMap<String, Vec<ArchivedCspHash>>,
+}
+
+/// [`Assets`] implementation reading the assets from an archive file instead of the binary,
+/// used when `tauri > externalAssets > enable` is set.
+///
+/// The archive starts with a magic number and the little endian `u64` length of its JSON index,
+/// followed by the index and the content of the assets.
+#[derive(Debug, Default)]
+pub struct ExternalAssets {
+  index: ExternalAssetsIndex,
+  data: Vec<u8>,
+}
+
+impl ExternalAssets {
+  /// Creates an empty archive, where the assets added with [`Self::insert`] are already brotli compressed or not.
+  #[cfg(feature = "build")]
+  pub fn new(compressed: bool) -> Self {
+    Self {
+      index: ExternalAssetsIndex {
+        compressed,
+        ..Default::default()
+      },
+      data: Vec::new(),
+    }
+  }
+
+  /// Adds an asset to the archive.
+  #[cfg(feature = "build")]
+  pub fn insert(&mut self, key: &AssetKey, content: &[u8]) {
+    self
+      .index
+      .assets
+      .insert(key.0.clone(), (self.data.len(), content.len()));
+    self.data.extend_from_slice(content);
+  }
+
+  /// Adds a CSP hash for every HTML file, or only the given HTML file.
+  #[cfg(feature = "build")]
+  pub fn insert_csp_hash(&mut self, html_path: Option<&str>, hash: CspHash<'_>) {
+    let hash = match hash {
+      CspHash::Script(hash) => ArchivedCspHash::Script(hash.into()),
+      CspHash::Style(hash) => ArchivedCspHash::Style(hash.into()),
+    };
+    match html_path {
+      Some(path) => self
+        .index
+        .html_hashes
+        .entry(path.into())
+        .or_default()
+        .push(hash),
+      None => self.index.global_hashes.push(hash),
+    }
+  }
+
+  /// Serializes the archive.
+  #[cfg(feature = "build")]
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let index = serde_json::to_vec(&self.index).expect("failed to serialize assets index");
+    let mut bytes = Vec::with_capacity(16 + index.len() + self.data.len());
+    bytes.extend_from_slice(EXTERNAL_ASSETS_MAGIC);
+    bytes.extend_from_slice(&(index.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&index);
+    bytes.extend_from_slice(&self.data);
+    bytes
+  }
+
+  /// Parses an archive, checking that every asset is inside of it.
+  pub fn from_bytes(mut bytes: Vec<u8>) -> Option<Self> {
+    let header = bytes.get(..16)?;
+    if &header[..8] != EXTERNAL_ASSETS_MAGIC {
+      return None;
+    }
+    let index_len = usize::try_from(u64::from_le_bytes(header[8..].try_into().ok()?)).ok()?;
+    let index_end = 16usize.checked_add(index_len)?;
+    let index: ExternalAssetsIndex = serde_json::from_slice(bytes.get(16..index_end)?).ok()?;
+    let data = bytes.split_off(index_end);
+    if !index.assets.values().all(|&(offset, len)| {
+      offset
+        .checked_add(len)
+        .map_or(false, |end| end <= data.len())
+    }) {
+      return None;
+    }
+    Some(Self { index, data })
+  }
+
+  fn content(&self, key: &AssetKey) -> Option<&[u8]> {
+    self
+      .index
+      .assets
+      .get(key.as_ref())
+      .map(|&(offset, len)| &self.data[offset..offset + len])
+  }
+}
+
+impl Assets for ExternalAssets {
+  fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    let content = self.content(key)?;
+    if !self.index.compressed {
+      return Some(Cow::Borrowed(content));
+    }
+
+    #[cfg(feature = "compression")]
+    {
+      let mut content = content;
+      let mut buf = Vec::with_capacity(content.len());
+      brotli::BrotliDecompress(&mut content, &mut buf).ok()?;
+      Some(Cow::Owned(buf))
+    }
+    #[cfg(not(feature = "compression"))]
+    None
+  }
+
+  fn get_compressed(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    self
+      .index
+      .compressed
+      .then(|| self.content(key).map(Cow::Borrowed))
+      .flatten()
+  }
+
+  fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
+    Box::new(
+      self
+        .index
+        .global_hashes
+        .iter()
+        .chain(
+          self
+            .index
+            .html_hashes
+            .get(html_path.as_ref())
+            .into_iter()
+            .flatten(),
+        )
+        .map(Into::into),
+    )
+  }
+}
+
+#[cfg(all(test, feature = "build"))]
+mod tests {
+  use super::{AssetKey, Assets, CspHash, ExternalAssets};
+
+  #[test]
+  fn external_assets() {
+    let mut archive = ExternalAssets::new(false);
+    archive.insert(&AssetKey::from("index.html"), b"<html></html>");
+    archive.insert(&AssetKey::from("main.js"), b"console.log(1)");
+    archive.insert_csp_hash(None, CspHash::Style("'sha256-style'"));
+    archive.insert_csp_hash(Some("/index.html"), CspHash::Script("'sha256-script'"));
+
+    let bytes = archive.to_bytes();
+    // an asset doesn't fit in a truncated archive
+    assert!(ExternalAssets::from_bytes(bytes[..bytes.len() - 1].to_vec()).is_none());
+    assert!(ExternalAssets::from_bytes(Vec::new()).is_none());
+
+    let archive = ExternalAssets::from_bytes(bytes).unwrap();
+    assert_eq!(
+      archive.get(&AssetKey::from("main.js")).unwrap().as_ref(),
+      b"console.log(1)"
+    );
+    assert!(archive.get(&AssetKey::from("missing.js")).is_none());
+    assert!(archive.get_compressed(&AssetKey::from("main.js")).is_none());
+    let hashes = archive
+      .csp_hashes(&AssetKey::from("index.html"))
+      .map(|hash| hash.hash().to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(hashes, ["'sha256-style'", "'sha256-script'"]);
+  }
+}
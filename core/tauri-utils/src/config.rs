@@ -16,7 +16,7 @@ use heck::ToKebabCase;
 use schemars::JsonSchema;
 use semver::Version;
 use serde::{
-  de::{Deserializer, Error as DeError, Visitor},
+  de::{DeserializeOwned, Deserializer, Error as DeError, Visitor},
   Deserialize, Serialize, Serializer,
 };
 use serde_json::Value as JsonValue;
@@ -27,7 +27,7 @@ use std::{
   collections::HashMap,
   fmt::{self, Display},
   fs::read_to_string,
-  path::PathBuf,
+  path::{Path, PathBuf},
   str::FromStr,
 };
 
@@ -80,10 +80,18 @@ pub enum BundleType {
   Deb,
   /// The AppImage bundle (.appimage).
   AppImage,
+  /// The RPM bundle (.rpm).
+  Rpm,
+  /// The Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Snap package (.snap).
+  Snap,
   /// The Microsoft Installer bundle (.msi).
   Msi,
   /// The NSIS bundle (.exe).
   Nsis,
+  /// The portable Windows executable (.exe).
+  Portable,
   /// The macOS application bundle (.app).
   App,
   /// The Apple Disk Image bundle (.dmg).
@@ -100,8 +108,12 @@ impl Display for BundleType {
       match self {
         Self::Deb => "deb",
         Self::AppImage => "appimage",
+        Self::Rpm => "rpm",
+        Self::Flatpak => "flatpak",
+        Self::Snap => "snap",
         Self::Msi => "msi",
         Self::Nsis => "nsis",
+        Self::Portable => "portable",
         Self::App => "app",
         Self::Dmg => "dmg",
         Self::Updater => "updater",
@@ -128,8 +140,12 @@ impl<'de> Deserialize<'de> for BundleType {
     match s.to_lowercase().as_str() {
       "deb" => Ok(Self::Deb),
       "appimage" => Ok(Self::AppImage),
+      "rpm" => Ok(Self::Rpm),
+      "flatpak" => Ok(Self::Flatpak),
+      "snap" => Ok(Self::Snap),
       "msi" => Ok(Self::Msi),
       "nsis" => Ok(Self::Nsis),
+      "portable" => Ok(Self::Portable),
       "app" => Ok(Self::App),
       "dmg" => Ok(Self::Dmg),
       "updater" => Ok(Self::Updater),
@@ -282,6 +298,90 @@ pub struct DebConfig {
   pub desktop_template: Option<PathBuf>,
 }
 
+/// Configuration for RPM bundles.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpmConfig {
+  /// The list of RPM dependencies your application relies on.
+  ///
+  /// Each entry is either a package name or a versioned requirement such as `openssl >= 3.0`.
+  pub depends: Option<Vec<String>>,
+  /// The package release number. Defaults to `1`.
+  pub release: Option<String>,
+  /// The license of the package, e.g. `MIT`. Defaults to `Unknown`.
+  pub license: Option<String>,
+  /// The files to include on the package.
+  #[serde(default)]
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// The GPG key ID used to sign the package. The key must be available in the GPG keyring.
+  ///
+  /// Can also be set with the `TAURI_RPM_SIGNING_KEY` environment variable.
+  #[serde(alias = "signing-key")]
+  pub signing_key: Option<String>,
+}
+
+/// Configuration for Flatpak bundles.
+///
+/// The sandbox permissions (`finish-args`) are derived from the app configuration:
+/// filesystem scopes are mapped to `--filesystem` and network access is requested
+/// when the app uses the HTTP plugin or loads external URLs.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FlatpakConfig {
+  /// The runtime the app runs on. Defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The version of the runtime. Defaults to `44`.
+  #[serde(alias = "runtime-version")]
+  pub runtime_version: Option<String>,
+  /// The SDK used to build the app. Defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// Additional sandbox permissions, e.g. `--socket=pulseaudio`.
+  #[serde(default, alias = "finish-args")]
+  pub finish_args: Vec<String>,
+}
+
+/// Confinement levels of a Snap package.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum SnapConfinement {
+  /// The app runs fully sandboxed and only has access to the interfaces it plugs.
+  #[default]
+  Strict,
+  /// The app has the same access to the system as a traditionally packaged app.
+  ///
+  /// Publishing classic snaps to the Snap Store requires a manual review.
+  Classic,
+  /// Like `strict`, but sandbox violations are only logged. Meant for development only.
+  Devmode,
+}
+
+/// Configuration for Snap packages.
+///
+/// The interfaces (plugs) are derived from the app configuration:
+/// filesystem scopes are mapped to `home` and `removable-media` and `network`
+/// is plugged when the app uses the HTTP plugin or loads external URLs.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SnapConfig {
+  /// The confinement level of the snap.
+  #[serde(default)]
+  pub confinement: SnapConfinement,
+  /// The base snap that provides the run-time environment. Defaults to `core22`.
+  pub base: Option<String>,
+  /// The quality grade of the snap, either `stable` or `devel`. Defaults to `stable`.
+  pub grade: Option<String>,
+  /// Additional interfaces to plug, e.g. `audio-playback`.
+  #[serde(default)]
+  pub plugs: Vec<String>,
+}
+
 fn de_minimum_system_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
   D: Deserializer<'de>,
@@ -327,6 +427,8 @@ pub struct MacConfig {
   #[serde(alias = "signing-identity")]
   pub signing_identity: Option<String>,
   /// Provider short name for notarization.
+  ///
+  /// Deprecated, set the `APPLE_TEAM_ID` environment variable instead. Used as the team ID when it is not set.
   #[serde(alias = "provider-short-name")]
   pub provider_short_name: Option<String>,
   /// Path to the entitlements file.
@@ -436,6 +538,28 @@ pub struct WixConfig {
   /// The required dimensions are 493px × 312px.
   #[serde(alias = "dialog-image-path")]
   pub dialog_image_path: Option<PathBuf>,
+  /// Whether the MSI installs the app for all users or just the current user.
+  #[serde(default, alias = "install-mode")]
+  pub install_mode: WixInstallMode,
+  /// The name of the start menu folder that holds the app shortcut. Defaults to the product name.
+  #[serde(alias = "start-menu-folder")]
+  pub start_menu_folder: Option<String>,
+}
+
+/// Install modes for the MSI installer.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WixInstallMode {
+  /// Default mode for the MSI installer.
+  ///
+  /// Install the app in the `Program Files` folder, which requires Administrator access.
+  #[default]
+  PerMachine,
+  /// Install the app in the user's `AppData\Local\Programs` folder without requiring Administrator access.
+  ///
+  /// Note that the elevated update task is not available in this mode.
+  CurrentUser,
 }
 
 /// Configuration for the Installer bundle using NSIS.
@@ -480,6 +604,11 @@ pub struct NsisConfig {
   /// By default the OS language is selected, with a fallback to the first language in the `languages` array.
   #[serde(default, alias = "display-language-selector")]
   pub display_language_selector: bool,
+  /// The name of the start menu folder that holds the app shortcut. Defaults to the product name.
+  ///
+  /// The folder chosen by the user is remembered and reused on upgrades.
+  #[serde(alias = "start-menu-folder")]
+  pub start_menu_folder: Option<String>,
 }
 
 /// Install Modes for the NSIS installer.
@@ -503,6 +632,9 @@ pub enum NSISInstallerMode {
   /// will require Administrator access even if the user wants to install it for the current user only.
   ///
   /// Installer metadata will be saved under the `HKLM` or `HKCU` registry path based on the user's choice.
+  ///
+  /// The chosen mode is restored when upgrading, so silent and passive installs started by the updater
+  /// keep the scope of the original installation. Pass `/CurrentUser` or `/AllUsers` to the installer to override it.
   Both,
 }
 
@@ -563,6 +695,62 @@ impl Default for WebviewInstallMode {
   }
 }
 
+/// A code signing backend used to sign the Windows executables and installers
+/// instead of `signtool` with a certificate installed on the machine.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum WindowsSignConfig {
+  /// Sign with a certificate stored in Azure Key Vault using [AzureSignTool](https://github.com/vcsjones/AzureSignTool).
+  /// Only supported on Windows hosts.
+  ///
+  /// The client secret is read from the `AZURE_CLIENT_SECRET` environment variable.
+  /// If no client ID is set, the Azure managed identity is used.
+  AzureKeyVault {
+    /// The URL of the Azure Key Vault.
+    #[serde(rename = "vaultUrl", alias = "vault-url")]
+    vault_url: String,
+    /// The name of the certificate in the key vault.
+    #[serde(rename = "certificateName", alias = "certificate-name")]
+    certificate_name: String,
+    /// The client ID of the Azure application. Overwritten by the `AZURE_CLIENT_ID` environment variable.
+    #[serde(default, rename = "clientId", alias = "client-id")]
+    client_id: Option<String>,
+    /// The tenant ID of the Azure application. Overwritten by the `AZURE_TENANT_ID` environment variable.
+    #[serde(default, rename = "tenantId", alias = "tenant-id")]
+    tenant_id: Option<String>,
+  },
+  /// Sign with a key stored in a PKCS#11 token using [osslsigncode](https://github.com/mtrojnar/osslsigncode).
+  /// Supported on all hosts.
+  ///
+  /// This can be used with any HSM or cloud key management service that provides a PKCS#11 module,
+  /// e.g. [aws-kms-pkcs11](https://github.com/JackOfMostTrades/aws-kms-pkcs11) for AWS KMS.
+  /// The token PIN is read from the `TAURI_PKCS11_PIN` environment variable.
+  Pkcs11 {
+    /// Path to the PKCS#11 module library.
+    module: PathBuf,
+    /// The PKCS#11 URI of the private key, e.g. `pkcs11:token=my-token;object=my-key`.
+    key: String,
+    /// Path to the PEM or DER encoded certificate chain of the signing key.
+    #[serde(rename = "certificatePath", alias = "certificate-path")]
+    certificate_path: PathBuf,
+    /// Path to the OpenSSL PKCS#11 engine, required by osslsigncode builds without OpenSSL 3 provider support.
+    #[serde(default)]
+    engine: Option<PathBuf>,
+  },
+  /// Sign with a custom command. Supported on all hosts.
+  ///
+  /// Every `%1` in the arguments is replaced with the path of the file to sign.
+  /// The path is appended to the arguments if none of them contains `%1`.
+  Custom {
+    /// The command to run.
+    command: String,
+    /// The command arguments.
+    #[serde(default)]
+    args: Vec<String>,
+  },
+}
+
 /// Windows bundler configuration.
 ///
 /// See more: https://tauri.app/v1/api/config#windowsconfig
@@ -606,6 +794,9 @@ pub struct WindowsConfig {
   pub wix: Option<WixConfig>,
   /// Configuration for the installer generated with NSIS.
   pub nsis: Option<NsisConfig>,
+  /// A code signing backend to use instead of `signtool` and [`Self::certificate_thumbprint`],
+  /// e.g. a certificate stored in Azure Key Vault or a key stored in an HSM.
+  pub sign: Option<WindowsSignConfig>,
 }
 
 impl Default for WindowsConfig {
@@ -620,6 +811,7 @@ impl Default for WindowsConfig {
       allow_downgrades: true,
       wix: None,
       nsis: None,
+      sign: None,
     }
   }
 }
@@ -683,6 +875,53 @@ impl Default for UpdaterConfig {
   }
 }
 
+/// Localized bundle metadata for a single locale.
+///
+/// Values that are not set fall back to the ones defined on the `package` and `bundle` objects.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BundleLocalization {
+  /// The localized application name.
+  #[serde(alias = "product-name")]
+  pub product_name: Option<String>,
+  /// The localized short description.
+  #[serde(alias = "short-description")]
+  pub short_description: Option<String>,
+  /// The localized long description.
+  #[serde(alias = "long-description")]
+  pub long_description: Option<String>,
+  /// The localized copyright string.
+  pub copyright: Option<String>,
+  /// The path to the localized license file rendered on the installers that show a license.
+  pub license: Option<PathBuf>,
+}
+
+impl BundleLocalization {
+  /// Finds the localization for the given BCP-47 language tag (e.g. `pt-BR`).
+  ///
+  /// Tags are compared case insensitively and `_` is treated as `-`.
+  /// When there's no exact match, the primary language subtag is used (`pt-BR` matches `pt`).
+  pub fn find<'a>(localizations: &'a HashMap<String, Self>, locale: &str) -> Option<&'a Self> {
+    fn normalize(tag: &str) -> String {
+      tag.replace('_', "-").to_lowercase()
+    }
+
+    let locale = normalize(locale);
+    let language = locale.split('-').next().unwrap_or_default();
+    localizations
+      .iter()
+      .find(|(tag, _)| normalize(tag) == locale)
+      .or_else(|| {
+        localizations
+          .iter()
+          .find(|(tag, _)| normalize(tag) == language)
+      })
+      .map(|(_, localization)| localization)
+  }
+}
+
 /// Configuration for tauri-bundler.
 ///
 /// See more: https://tauri.app/v1/api/config#bundleconfig
@@ -694,7 +933,9 @@ pub struct BundleConfig {
   /// Whether Tauri should bundle your application or just output the executable.
   #[serde(default)]
   pub active: bool,
-  /// The bundle targets, currently supports ["deb", "appimage", "nsis", "msi", "app", "dmg", "updater"] or "all".
+  /// The bundle targets, currently supports ["deb", "rpm", "appimage", "flatpak", "snap", "nsis", "portable", "msi", "app", "dmg", "updater"] or "all".
+  ///
+  /// Note that "all" does not include the opt-in "flatpak", "snap" and "portable" targets.
   #[serde(default)]
   pub targets: BundleTarget,
   /// The application identifier in reverse domain name notation (e.g. `com.tauri.example`).
@@ -713,6 +954,12 @@ pub struct BundleConfig {
   /// Each resource is a path to a file or directory.
   /// Glob patterns are supported.
   pub resources: Option<Vec<String>>,
+  /// Whether the resources should be compressed with brotli when bundling.
+  ///
+  /// Each resource is stored with a `.br` extension and must be resolved with `PathResolver::resolve_compressed_resource`,
+  /// which decompresses it to the app cache directory on first access.
+  #[serde(default, alias = "compress-resources")]
+  pub compress_resources: bool,
   /// A copyright string associated with your application.
   pub copyright: Option<String>,
   /// The application kind.
@@ -732,6 +979,15 @@ pub struct BundleConfig {
   /// Configuration for the Debian bundle.
   #[serde(default)]
   pub deb: DebConfig,
+  /// Configuration for the RPM bundle.
+  #[serde(default)]
+  pub rpm: RpmConfig,
+  /// Configuration for the Flatpak bundle.
+  #[serde(default)]
+  pub flatpak: FlatpakConfig,
+  /// Configuration for the Snap package.
+  #[serde(default)]
+  pub snap: SnapConfig,
   /// Configuration for the macOS bundles.
   #[serde(rename = "macOS", default)]
   pub macos: MacConfig,
@@ -760,6 +1016,198 @@ pub struct BundleConfig {
   /// The updater configuration.
   #[serde(default)]
   pub updater: UpdaterConfig,
+  /// Localized bundle metadata, keyed by BCP-47 language tag (e.g. `fr`, `pt-BR`).
+  ///
+  /// Used to generate the macOS `InfoPlist.strings` files, the localized entries of the Linux desktop file
+  /// and the localized strings of each WiX language.
+  #[serde(default)]
+  pub localizations: HashMap<String, BundleLocalization>,
+  /// Declares the use of the local network on macOS and iOS, which prompt the user for the Local Network permission
+  /// the first time the app accesses a device of the local network.
+  #[serde(default, alias = "local-network")]
+  pub local_network: LocalNetworkConfig,
+  /// Push notifications configuration for Android and iOS.
+  #[serde(default)]
+  pub push: PushConfig,
+  /// The initial appearance of the status and navigation bars on Android and iOS.
+  #[serde(default, alias = "system-bars")]
+  pub system_bars: SystemBarsConfig,
+  /// In-app purchases configuration for Android and iOS.
+  #[serde(default, alias = "in-app-purchases")]
+  pub in_app_purchases: InAppPurchasesConfig,
+  /// The static shortcuts of the app, shown when long pressing the app icon on Android and iOS.
+  ///
+  /// The shortcuts set at runtime with `AppHandle::set_shortcuts` are listed after these ones.
+  #[serde(default)]
+  pub shortcuts: Vec<ShortcutConfig>,
+  /// The periodic background tasks of the app on Android and iOS.
+  #[serde(default, alias = "background-tasks")]
+  pub background_tasks: BackgroundTasksConfig,
+}
+
+impl BundleConfig {
+  /// Returns the bundle localization for the given BCP-47 language tag, see [`BundleLocalization::find`].
+  pub fn localization(&self, locale: &str) -> Option<&BundleLocalization> {
+    BundleLocalization::find(&self.localizations, locale)
+  }
+}
+
+/// Declaration of the local network usage of the app, written to the `Info.plist` of the macOS and iOS bundles.
+///
+/// See more: https://developer.apple.com/documentation/bundleresources/information_property_list/nslocalnetworkusagedescription
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LocalNetworkConfig {
+  /// Why the app accesses the local network, shown by the Local Network permission prompt.
+  /// Sets the `NSLocalNetworkUsageDescription` key.
+  #[serde(alias = "usage-description")]
+  pub usage_description: Option<String>,
+  /// The Bonjour service types the app browses or advertises, e.g. `_http._tcp`.
+  /// Sets the `NSBonjourServices` key, without which iOS fails to browse the services.
+  #[serde(default, alias = "bonjour-services")]
+  pub bonjour_services: Vec<String>,
+}
+
+/// Push notifications configuration, used by `tauri android init` and `tauri ios init`
+/// to declare the services and entitlements the push notifications require.
+///
+/// On Android, the notifications are delivered by Firebase Cloud Messaging,
+/// which requires the `google-services.json` file of the Firebase project in the `gen/android/app` directory.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PushConfig {
+  /// Whether the app receives push notifications.
+  ///
+  /// Adds the Firebase Messaging service and the `POST_NOTIFICATIONS` permission to the Android project,
+  /// and the `aps-environment` entitlement and the `remote-notification` background mode to the Xcode project.
+  #[serde(default)]
+  pub enable: bool,
+  /// The Apple Push Notification service environment the iOS app registers to.
+  #[serde(default, alias = "aps-environment")]
+  pub aps_environment: ApsEnvironment,
+}
+
+/// The Apple Push Notification service environment, set as the `aps-environment` entitlement.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum ApsEnvironment {
+  /// The sandbox environment, for the apps signed with a development provisioning profile.
+  #[default]
+  Development,
+  /// The production environment, for the apps distributed with TestFlight or the App Store.
+  Production,
+}
+
+impl ApsEnvironment {
+  /// The value of the `aps-environment` entitlement.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Development => "development",
+      Self::Production => "production",
+    }
+  }
+}
+
+/// In-app purchases configuration, used by `tauri android init` and `tauri ios init` to add the store billing libraries.
+///
+/// The products are sold through Google Play Billing on Android and the App Store on iOS.
+/// The In-App Purchase capability is enabled for all the App IDs and requires no entitlement.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InAppPurchasesConfig {
+  /// Whether the app sells in-app products and subscriptions.
+  ///
+  /// Adds the Google Play Billing library and its `BILLING` permission to the Android project,
+  /// and links the StoreKit framework in the Xcode project.
+  #[serde(default)]
+  pub enable: bool,
+}
+
+/// The periodic background tasks of the app, scheduled with `AppHandle::schedule_background_task`.
+///
+/// `tauri android init` adds the WorkManager library to the Android project, and `tauri ios init` adds the identifiers
+/// to the `BGTaskSchedulerPermittedIdentifiers` key of the `Info.plist` and enables the `fetch` and `processing` background modes.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BackgroundTasksConfig {
+  /// The identifiers of the tasks the app schedules, e.g. `com.tauri.app.sync`.
+  #[serde(default)]
+  pub identifiers: Vec<String>,
+}
+
+/// A static shortcut of the app, written to the `res/xml/tauri_shortcuts.xml` resource of the Android project
+/// when building the app, and by `tauri ios init` to the `UIApplicationShortcutItems` key of the `Info.plist`.
+///
+/// Activating the shortcut emits the `RunEvent::ShortcutActivated` event with its identifier.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ShortcutConfig {
+  /// The identifier of the shortcut, received by the app when it is activated.
+  pub id: String,
+  /// The title of the shortcut.
+  pub title: String,
+  /// The long label on Android, shown when there is enough space, or the subtitle on iOS.
+  pub description: Option<String>,
+  /// The name of a drawable resource on Android, or the name of an SF Symbol on iOS.
+  pub icon: Option<String>,
+}
+
+/// The initial appearance of the system bars on mobile, written by `tauri android init` to the theme and the main activity
+/// of the Android project, and by `tauri ios init` to the `Info.plist` of the Xcode project.
+///
+/// The app can change it at runtime with the `Window::set_status_bar_*`, `Window::set_navigation_bar_*`
+/// and `Window::set_edge_to_edge` APIs.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SystemBarsConfig {
+  /// The background color of the status bar.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS:** Unsupported at launch, the status bar is transparent and shows the webview.
+  #[serde(alias = "status-bar-color")]
+  pub status_bar_color: Option<Color>,
+  /// The style of the status bar icons.
+  #[serde(default, alias = "status-bar-style")]
+  pub status_bar_style: SystemBarStyle,
+  /// The background color of the navigation bar on Android.
+  #[serde(alias = "navigation-bar-color")]
+  pub navigation_bar_color: Option<Color>,
+  /// The style of the navigation bar icons on Android 8.1 and later.
+  #[serde(default, alias = "navigation-bar-style")]
+  pub navigation_bar_style: SystemBarStyle,
+  /// Whether the webview is laid out behind the system bars, using the whole screen.
+  /// The app must then pad its content with the safe area insets.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS:** The webview always uses the whole screen, this disables the automatic adjustment of its scroll view insets.
+  #[serde(default, alias = "edge-to-edge")]
+  pub edge_to_edge: bool,
+}
+
+/// The style of the icons of a system bar.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum SystemBarStyle {
+  /// The style of the system theme.
+  #[default]
+  Default,
+  /// Light icons, for dark backgrounds.
+  LightContent,
+  /// Dark icons, for light backgrounds.
+  DarkContent,
 }
 
 /// a tuple struct of RGBA colors. Each value has minimum of 0 and maximum of 255.
@@ -786,6 +1234,33 @@ pub struct WindowEffectsConfig {
   pub color: Option<Color>,
 }
 
+/// The layout of the window controls drawn over the webview by a window with the [`TitleBarStyle::Overlay`] title bar.
+///
+/// The bounding rectangle of the controls is available to the frontend in the `--tauri-title-bar-overlay-*`
+/// CSS variables of the document, so the page can lay out its own title bar around them.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TitleBarOverlayConfig {
+  /// The height of the title bar area in logical pixels, in which the window controls are vertically centered.
+  pub height: Option<f64>,
+  /// The position of the window controls from the top-left corner of the window in logical pixels.
+  /// Overrides the vertical centering of [`Self::height`].
+  pub inset: Option<TitleBarInset>,
+}
+
+/// A logical position from the top-left corner of the window.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TitleBarInset {
+  /// The horizontal offset.
+  pub x: f64,
+  /// The vertical offset.
+  pub y: f64,
+}
+
 /// The window configuration object.
 ///
 /// See more: https://tauri.app/v1/api/config#windowconfig
@@ -867,6 +1342,14 @@ pub struct WindowConfig {
   /// Whether the window starts as fullscreen or not.
   #[serde(default)]
   pub fullscreen: bool,
+  /// Whether the window is shown in kiosk mode, e.g. for a point of sale or a digital signage.
+  ///
+  /// A kiosk window is an undecorated, always on top and fullscreen window that cannot be closed by the user,
+  /// with the devtools, the context menu and the navigation away from the app disabled.
+  /// The system shortcuts switching or closing the apps are blocked where the operating system allows it,
+  /// and the app is relaunched if it panics.
+  #[serde(default)]
+  pub kiosk: bool,
   /// Whether the window will be initially focused or not.
   #[serde(default = "default_true")]
   pub focus: bool,
@@ -882,6 +1365,12 @@ pub struct WindowConfig {
   /// Whether the window is visible or not.
   #[serde(default = "default_true")]
   pub visible: bool,
+  /// Whether the window is preloaded: created hidden when the app starts so its page is already loaded
+  /// when it is shown, e.g. for a settings window.
+  ///
+  /// The window is hidden instead of closed when the user closes it, and is returned by `AppHandle::get_preloaded_window`.
+  #[serde(default)]
+  pub preload: bool,
   /// Whether the window should have borders and bars.
   #[serde(default = "default_true")]
   pub decorations: bool,
@@ -894,6 +1383,10 @@ pub struct WindowConfig {
   /// If `true`, hides the window icon from the taskbar on Windows and Linux.
   #[serde(default, alias = "skip-taskbar")]
   pub skip_taskbar: bool,
+  /// Whether the window ignores the cursor events, letting the clicks pass through to the windows below it,
+  /// e.g. for overlays and HUDs. Usually combined with a transparent, undecorated and always on top window.
+  #[serde(default, alias = "ignore-cursor-events")]
+  pub ignore_cursor_events: bool,
   /// The initial window theme. Defaults to the system theme. Only implemented on Windows and macOS 10.14+.
   pub theme: Option<crate::Theme>,
   /// The style of the macOS title bar.
@@ -902,6 +1395,9 @@ pub struct WindowConfig {
   /// If `true`, sets the window title to be hidden on macOS.
   #[serde(default, alias = "hidden-title")]
   pub hidden_title: bool,
+  /// The layout of the window controls drawn over the webview with the `Overlay` title bar style on macOS.
+  #[serde(alias = "title-bar-overlay")]
+  pub title_bar_overlay: Option<TitleBarOverlayConfig>,
   /// Whether clicking an inactive window also clicks through to the webview on macOS.
   #[serde(default, alias = "accept-first-mouse")]
   pub accept_first_mouse: bool,
@@ -969,17 +1465,21 @@ impl Default for WindowConfig {
       closable: true,
       title: default_title(),
       fullscreen: false,
+      kiosk: false,
       focus: false,
       transparent: false,
       maximized: false,
       visible: true,
+      preload: false,
       decorations: true,
       always_on_top: false,
       content_protected: false,
       skip_taskbar: false,
+      ignore_cursor_events: false,
       theme: None,
       title_bar_style: Default::default(),
       hidden_title: false,
+      title_bar_overlay: None,
       accept_first_mouse: false,
       tabbing_identifier: None,
       additional_browser_args: None,
@@ -1075,6 +1575,31 @@ pub enum Csp {
   DirectiveMap(HashMap<String, CspDirectiveSources>),
 }
 
+impl Csp {
+  /// Adds the sources to the given directive, converting the policy to a directive map if needed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri_utils::config::Csp;
+  /// let csp = Csp::DirectiveMap(Default::default())
+  ///   .with_directive("default-src", ["'self'"])
+  ///   .with_directive("img-src", ["'self'", "asset:"]);
+  /// ```
+  pub fn with_directive<S: AsRef<str>>(
+    self,
+    directive: impl Into<String>,
+    sources: impl IntoIterator<Item = S>,
+  ) -> Self {
+    let mut map: HashMap<String, CspDirectiveSources> = self.into();
+    let entry = map.entry(directive.into()).or_default();
+    for source in sources {
+      entry.push(source);
+    }
+    Self::DirectiveMap(map)
+  }
+}
+
 impl From<HashMap<String, CspDirectiveSources>> for Csp {
   fn from(map: HashMap<String, CspDirectiveSources>) -> Self {
     Self::DirectiveMap(map)
@@ -1186,9 +1711,18 @@ pub enum FsScope {
     #[serde(default)]
     allow: Vec<PathBuf>,
     /// A list of paths that are not allowed by this scope.
-    /// This gets precedence over the [`Self::Scope::allow`] list.
+    /// This gets precedence over the [`Self::Scope::allow`] list, see [`Self::Scope::precedence`].
     #[serde(default)]
     deny: Vec<PathBuf>,
+    /// How conflicts between the allowed and denied paths are resolved.
+    #[serde(default)]
+    precedence: ScopePrecedence,
+    /// How symbolic links on the checked paths are handled.
+    #[serde(default)]
+    symlinks: ScopeSymlinkPolicy,
+    /// The matching options of the allowed and denied paths, keyed by the path as written in the lists.
+    #[serde(default, alias = "pattern-options")]
+    pattern_options: HashMap<PathBuf, ScopePatternOptions>,
     /// Whether or not paths that contain components that start with a `.`
     /// will require that `.` appears literally in the pattern; `*`, `?`, `**`,
     /// or `[...]` will not match. This is useful because such files are
@@ -1224,6 +1758,75 @@ impl FsScope {
       Self::Scope { deny, .. } => Some(deny),
     }
   }
+
+  /// The matching options of the given allowed or forbidden path.
+  pub fn pattern_options(&self, path: &Path) -> Option<&ScopePatternOptions> {
+    match self {
+      Self::AllowedPaths(_) => None,
+      Self::Scope {
+        pattern_options, ..
+      } => pattern_options.get(path),
+    }
+  }
+
+  /// How conflicts between the allowed and denied paths are resolved.
+  pub fn precedence(&self) -> ScopePrecedence {
+    match self {
+      Self::AllowedPaths(_) => ScopePrecedence::default(),
+      Self::Scope { precedence, .. } => *precedence,
+    }
+  }
+
+  /// How symbolic links on the checked paths are handled.
+  pub fn symlinks(&self) -> ScopeSymlinkPolicy {
+    match self {
+      Self::AllowedPaths(_) => ScopeSymlinkPolicy::default(),
+      Self::Scope { symlinks, .. } => *symlinks,
+    }
+  }
+}
+
+/// The matching options of a filesystem scope path.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct ScopePatternOptions {
+  /// Whether the pattern matches the case of the path. Defaults to `false`, like the other scope patterns.
+  #[serde(alias = "case-sensitive")]
+  pub case_sensitive: Option<bool>,
+  /// The maximum number of path components matched after the pattern components without wildcards,
+  /// e.g. `$HOME/docs/**` with a depth of `1` matches `$HOME/docs/file` but not `$HOME/docs/dir/file`.
+  #[serde(alias = "max-depth")]
+  pub max_depth: Option<usize>,
+}
+
+/// How conflicts between the allowed and denied paths of a scope are resolved.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum ScopePrecedence {
+  /// A path matching a denied pattern is always forbidden.
+  #[default]
+  Deny,
+  /// The pattern with the most components without wildcards wins, so a directory can be allowed inside a denied one.
+  /// Denied patterns win ties.
+  MostSpecific,
+}
+
+/// How symbolic links on the paths checked by a scope are handled.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub enum ScopeSymlinkPolicy {
+  /// Symbolic links are resolved and the target path is checked.
+  #[default]
+  Resolve,
+  /// Paths going through a symbolic link are forbidden.
+  Deny,
+  /// The path is checked as given, without resolving symbolic links.
+  /// Paths containing `..` are never allowed since they can't be resolved.
+  Lexical,
 }
 
 /// Config for the asset custom protocol.
@@ -1239,6 +1842,13 @@ pub struct AssetProtocolConfig {
   /// Enables the asset protocol.
   #[serde(default)]
   pub enable: bool,
+  /// Persists the paths allowed and forbidden on the scope at runtime across app launches.
+  ///
+  /// The paths are stored on the app local data directory, signed with a key embedded in the app when it is built.
+  /// Set the `TAURI_SCOPE_STORE_KEY` environment variable when building the app to keep the stored paths across app updates,
+  /// otherwise a random key is generated on each clean build.
+  #[serde(default, alias = "persist-scope")]
+  pub persist_scope: bool,
 }
 
 /// Security configuration.
@@ -1261,6 +1871,12 @@ pub struct SecurityConfig {
   /// See <https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP>.
   #[serde(alias = "dev-csp")]
   pub dev_csp: Option<Csp>,
+  /// Content Security Policies that replace [`csp`](#SecurityConfig.csp) and [`dev_csp`](#SecurityConfig.devCsp)
+  /// on the windows with the given labels.
+  ///
+  /// The nonce and hash sources of the frontend assets are injected into these policies as well.
+  #[serde(default, alias = "window-csp")]
+  pub window_csp: HashMap<String, Csp>,
   /// Freeze the `Object.prototype` when using the custom protocol.
   #[serde(default, alias = "freeze-prototype")]
   pub freeze_prototype: bool,
@@ -1295,6 +1911,325 @@ pub struct SecurityConfig {
   /// Custom protocol config.
   #[serde(default, alias = "asset-protocol")]
   pub asset_protocol: AssetProtocolConfig,
+  /// The capabilities granted to the app windows.
+  ///
+  /// Capabilities can also be defined in JSON, JSON5 or TOML files on the `capabilities` folder next to the Tauri configuration file.
+  /// When at least one capability is defined, windows can only invoke the commands and plugins
+  /// granted by the capabilities that match their label.
+  #[serde(default)]
+  pub capabilities: Vec<Capability>,
+  /// Restricts commands to the given window labels and origins.
+  ///
+  /// The keys are command names, using the `plugin:<plugin-name>|<command>` format for plugin commands.
+  /// The values are window labels or origins such as `https://tauri.app`,
+  /// compared with the scheme, host and port of the window URL.
+  /// The check happens before the command arguments are deserialized.
+  ///
+  /// Commands can also be restricted with `#[tauri::command(allowed_origins = ["main", "https://tauri.app"])]`.
+  #[serde(default, alias = "command-origins")]
+  pub command_origins: HashMap<String, Vec<String>>,
+  /// Limits applied to the IPC messages sent by the windows.
+  #[serde(default, alias = "ipc-limits")]
+  pub ipc_limits: IpcLimitsConfig,
+  /// The configuration keys the frontend can read with the `getConfig()` API,
+  /// as dot-separated paths such as `package.version` or `custom.theme`.
+  ///
+  /// Nothing is exposed by default.
+  #[serde(default, alias = "config-allowlist")]
+  pub config_allowlist: Vec<String>,
+  /// Enables the `@tauri-apps/api/hid` commands, listing the gamepads and the other human interface devices and reading their input reports.
+  ///
+  /// Enabling it enables the `hid` Cargo feature.
+  #[serde(default)]
+  pub hid: bool,
+  /// The serial ports the frontend can open with `@tauri-apps/api/serial`.
+  #[serde(default)]
+  pub serial: SerialConfig,
+  /// Enables the `@tauri-apps/api/bluetooth` commands, scanning and connecting to the Bluetooth LE devices.
+  /// The user is asked the first time a window connects to a device.
+  ///
+  /// Enabling it enables the `bluetooth` Cargo feature.
+  #[serde(default)]
+  pub bluetooth: bool,
+  /// The USB devices the frontend can open with `@tauri-apps/api/usb`.
+  #[serde(default)]
+  pub usb: UsbConfig,
+  /// Enables the `@tauri-apps/api/haptics` commands, playing haptic feedback on Android and iOS.
+  ///
+  /// Enabling it enables the `haptics` Cargo feature.
+  #[serde(default)]
+  pub haptics: bool,
+  /// Enables the `@tauri-apps/api/sensors` commands, watching the motion sensors on Android and iOS.
+  ///
+  /// Enabling it enables the `sensors` Cargo feature.
+  #[serde(default)]
+  pub sensors: bool,
+  /// Enables the `@tauri-apps/api/keyring` commands, storing the passwords of the app in the credential store of the operating system.
+  ///
+  /// Enabling it enables the `keyring` Cargo feature.
+  #[serde(default)]
+  pub keyring: bool,
+  /// Enables the `@tauri-apps/api/auth` commands, authenticating the user with the prompt of the operating system.
+  ///
+  /// Enabling it enables the `auth` Cargo feature.
+  #[serde(default)]
+  pub auth: bool,
+  /// The share sheet of `@tauri-apps/api/share`.
+  #[serde(default)]
+  pub share: ShareConfig,
+  /// The TLS settings of the HTTP clients created with `tauri::api::network::client_builder` of the `network` feature, e.g. by the http and updater plugins.
+  #[serde(default)]
+  pub tls: TlsConfig,
+}
+
+/// The root certificates and the public key pins of the HTTP clients.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TlsConfig {
+  /// The path of a PEM file with additional root certificates, e.g. the certificate authority of an enterprise network.
+  ///
+  /// Relative paths are resolved from the resource directory, so the file must be bundled with `tauri > bundle > resources`.
+  #[serde(alias = "ca-bundle")]
+  pub ca_bundle: Option<PathBuf>,
+  /// Whether the root certificates built into the client are trusted.
+  /// Set it to `false` to only trust the [`ca_bundle`](#TlsConfig.caBundle) certificates.
+  #[serde(default = "default_true", alias = "builtin-roots")]
+  pub builtin_roots: bool,
+  /// The public keys the certificate chains of the hosts must contain.
+  ///
+  /// Requires the `rustls-tls` Cargo feature, the client creation fails otherwise.
+  #[serde(default)]
+  pub pins: Vec<CertificatePin>,
+}
+
+impl Default for TlsConfig {
+  fn default() -> Self {
+    Self {
+      ca_bundle: None,
+      builtin_roots: true,
+      pins: Vec::new(),
+    }
+  }
+}
+
+/// The public keys pinned for a host.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CertificatePin {
+  /// The host, e.g. `api.example.com`, or `*.example.com` for its subdomains.
+  pub host: String,
+  /// The base64 encoded SHA-256 hashes of the `SubjectPublicKeyInfo` of the allowed certificates, optionally prefixed with `sha256/`.
+  ///
+  /// A pin can match the server certificate or one of its intermediate certificates.
+  /// Include a backup key so the app keeps working when the certificate is rotated.
+  pub sha256: Vec<String>,
+}
+
+/// Configuration of the serial ports the frontend can open.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SerialConfig {
+  /// Enables the `@tauri-apps/api/serial` commands.
+  ///
+  /// Enabling it enables the `serial` Cargo feature.
+  #[serde(default)]
+  pub enable: bool,
+  /// Glob patterns of the ports the frontend can list and open, e.g. `/dev/ttyUSB*`, `/dev/cu.usbmodem*` or `COM3`.
+  ///
+  /// No port can be opened when the scope is empty.
+  #[serde(default)]
+  pub scope: Vec<String>,
+}
+
+/// Configuration of the USB devices the frontend can open.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UsbConfig {
+  /// Enables the `@tauri-apps/api/usb` commands.
+  ///
+  /// Enabling it enables the `usb` Cargo feature.
+  #[serde(default)]
+  pub enable: bool,
+  /// The devices the frontend can list and open.
+  ///
+  /// No device can be opened when the scope is empty.
+  #[serde(default)]
+  pub scope: Vec<UsbDeviceFilter>,
+}
+
+/// A USB device of the [`UsbConfig`] scope, matched by its identifiers.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UsbDeviceFilter {
+  /// The vendor identifier of the device, e.g. `9025` (`0x2341`) for Arduino.
+  pub vendor_id: u16,
+  /// The product identifier of the device. Any product of the vendor matches when it is not set.
+  pub product_id: Option<u16>,
+}
+
+impl UsbDeviceFilter {
+  /// Whether the device with the given identifiers matches the filter.
+  pub fn matches(&self, vendor_id: u16, product_id: u16) -> bool {
+    self.vendor_id == vendor_id && self.product_id.map_or(true, |id| id == product_id)
+  }
+}
+
+/// Configuration of the share sheet of the frontend.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ShareConfig {
+  /// Enables the `@tauri-apps/api/share` commands.
+  ///
+  /// Enabling it enables the `share` Cargo feature.
+  #[serde(default)]
+  pub enable: bool,
+  /// The files the frontend can share. Texts and links can always be shared.
+  #[serde(default)]
+  pub scope: FsScope,
+}
+
+/// Limits applied to the IPC messages sent by the windows,
+/// protecting the backend from a compromised webview flooding commands or sending huge payloads.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IpcLimitsConfig {
+  /// The maximum size in bytes of an IPC message, as sent by the webview.
+  /// Larger messages are dropped and logged before they are decrypted or deserialized, so their invoke never resolves.
+  #[serde(alias = "max-payload-size")]
+  pub max_payload_size: Option<usize>,
+  /// The rate limits of the commands, keyed by command name.
+  ///
+  /// Plugin commands use the `plugin:<plugin-name>|<command>` format, and `*` applies to the commands without a rate limit.
+  /// The limits are tracked separately for each window.
+  #[serde(default, alias = "rate-limits")]
+  pub rate_limits: HashMap<String, RateLimit>,
+}
+
+impl IpcLimitsConfig {
+  /// The rate limit of the given command.
+  pub fn rate_limit(&self, command: &str) -> Option<&RateLimit> {
+    self
+      .rate_limits
+      .get(command)
+      .or_else(|| self.rate_limits.get("*"))
+  }
+}
+
+/// The maximum number of times a window can invoke a command in a given interval.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RateLimit {
+  /// The maximum number of invokes in the interval.
+  pub requests: u32,
+  /// The interval in milliseconds.
+  pub interval: u64,
+}
+
+impl SecurityConfig {
+  /// Whether the window with the given label is allowed to invoke the command.
+  ///
+  /// Plugin commands use the `plugin:<plugin-name>|<command>` format.
+  /// Every command is allowed when there are no capabilities defined.
+  pub fn is_command_allowed(&self, window: &str, command: &str) -> bool {
+    self.capabilities.is_empty()
+      || self
+        .capabilities
+        .iter()
+        .filter(|c| c.matches_window(window))
+        .any(|c| c.allows_command(command))
+  }
+
+  /// The scopes of the plugin granted to the window with the given label, one for each matching capability.
+  pub fn window_scopes(&self, window: &str, plugin: &str) -> Vec<&JsonValue> {
+    self
+      .capabilities
+      .iter()
+      .filter(|c| c.matches_window(window))
+      .filter_map(|c| c.scopes.get(plugin))
+      .collect()
+  }
+}
+
+/// A set of commands and plugins granted to a set of windows.
+///
+/// ```json
+/// {
+///   "identifier": "settings",
+///   "windows": ["settings"],
+///   "commands": ["save_settings"],
+///   "plugins": ["store"],
+///   "scopes": { "fs": { "allow": ["$APPCONFIG/settings.json"] } }
+/// }
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Capability {
+  /// The capability identifier, used on error messages.
+  pub identifier: String,
+  /// A description of what the capability grants.
+  pub description: Option<String>,
+  /// The labels of the windows this capability is granted to.
+  /// `*` matches any sequence of characters, e.g. `settings-*` or `*` for every window.
+  #[serde(default)]
+  pub windows: Vec<String>,
+  /// The app commands allowed by this capability.
+  /// Plugin commands can be allowed individually with the `plugin:<plugin-name>|<command>` format.
+  #[serde(default)]
+  pub commands: Vec<String>,
+  /// The plugins whose commands are all allowed by this capability.
+  #[serde(default)]
+  pub plugins: Vec<String>,
+  /// The scopes granted to the windows, keyed by the name of the plugin reading them.
+  #[serde(default)]
+  pub scopes: HashMap<String, JsonValue>,
+}
+
+/// Whether the window label matches the pattern, where `*` matches any sequence of characters.
+fn matches_label(pattern: &str, label: &str) -> bool {
+  match pattern.split_once('*') {
+    None => pattern == label,
+    Some((prefix, rest)) => match label.strip_prefix(prefix) {
+      Some(label) => label
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(label.len()))
+        .any(|i| matches_label(rest, &label[i..])),
+      None => false,
+    },
+  }
+}
+
+impl Capability {
+  /// Whether this capability is granted to the window with the given label.
+  pub fn matches_window(&self, window: &str) -> bool {
+    self.windows.iter().any(|w| matches_label(w, window))
+  }
+
+  /// Whether this capability allows the given command.
+  pub fn allows_command(&self, command: &str) -> bool {
+    if self.commands.iter().any(|c| c == command) {
+      return true;
+    }
+    command
+      .strip_prefix("plugin:")
+      .and_then(|c| c.split('|').next())
+      .map_or(false, |plugin| self.plugins.iter().any(|p| p == plugin))
+  }
 }
 
 /// The application pattern.
@@ -1306,6 +2241,15 @@ pub enum PatternKind {
   /// Brownfield pattern.
   Brownfield,
   /// Isolation pattern. Recommended for security purposes.
+  ///
+  /// Every window, including the ones loading remote URLs and the ones created at runtime,
+  /// embeds the isolation application in a sandboxed iframe that receives the IPC messages before they are
+  /// encrypted and sent to the Rust backend. A new encryption key is generated every time a window loads the isolation application.
+  ///
+  /// The isolation application must set `window.__TAURI_ISOLATION_HOOK__` to a function
+  /// `(payload, context) => payload` (or `null` to forward the messages as is),
+  /// where `context` contains the `windowLabel` of the window and the `origin` of the page that sent the message.
+  /// The hook can modify the payload or throw to reject the message.
   Isolation {
     /// The dir containing the index.html file that contains the secure isolation application.
     dir: PathBuf,
@@ -1344,6 +2288,28 @@ pub struct TauriConfig {
   /// MacOS private API configuration. Enables the transparent background API and sets the `fullScreenEnabled` preference to `true`.
   #[serde(rename = "macOSPrivateApi", alias = "macos-private-api", default)]
   pub macos_private_api: bool,
+  /// Loads the frontend assets from an archive shipped with the app instead of embedding them in the binary.
+  #[serde(default, alias = "external-assets")]
+  pub external_assets: ExternalAssetsConfig,
+  /// The thread pool running the commands marked with `#[tauri::command(blocking)]`.
+  #[serde(default, alias = "blocking-pool")]
+  pub blocking_pool: BlockingPoolConfig,
+  /// Keeps the app running when its last window is closed, e.g. for an app living in the system tray.
+  ///
+  /// The app can start with no windows, and exits when `AppHandle::exit` is called.
+  #[serde(default, alias = "keep-running-without-windows")]
+  pub keep_running_without_windows: bool,
+  /// The Linux desktop integration.
+  #[serde(default)]
+  pub linux: LinuxConfig,
+  /// The splashscreen shown until the app calls `AppHandle::close_splashscreen`.
+  pub splashscreen: Option<SplashscreenConfig>,
+  /// Runs the webview of each window in its own processes, with its own data directory,
+  /// so the windows do not share their memory nor their crashes, e.g. for a multi-document app.
+  ///
+  /// On macOS and iOS WebKit already runs the content of each webview in its own process.
+  #[serde(default, alias = "process-isolation")]
+  pub process_isolation: bool,
 }
 
 impl TauriConfig {
@@ -1354,6 +2320,16 @@ impl TauriConfig {
       "macos-private-api",
       "isolation",
       "protocol-asset",
+      "external-assets",
+      "hid",
+      "serial",
+      "bluetooth",
+      "usb",
+      "haptics",
+      "sensors",
+      "keyring",
+      "auth",
+      "share",
     ]
   }
 
@@ -1372,11 +2348,143 @@ impl TauriConfig {
     if self.security.asset_protocol.enable {
       features.push("protocol-asset");
     }
+    if self.external_assets.enable {
+      features.push("external-assets");
+    }
+    if self.security.hid {
+      features.push("hid");
+    }
+    if self.security.serial.enable {
+      features.push("serial");
+    }
+    if self.security.bluetooth {
+      features.push("bluetooth");
+    }
+    if self.security.usb.enable {
+      features.push("usb");
+    }
+    if self.security.haptics {
+      features.push("haptics");
+    }
+    if self.security.sensors {
+      features.push("sensors");
+    }
+    if self.security.keyring {
+      features.push("keyring");
+    }
+    if self.security.auth {
+      features.push("auth");
+    }
+    if self.security.share.enable {
+      features.push("share");
+    }
     features.sort_unstable();
     features
   }
 }
 
+/// Configuration for loading the frontend assets from an external archive.
+///
+/// The `distDir` assets are written to an archive that is bundled in the resources directory,
+/// which cuts the link times of big frontends and lets a frontend-only change ship without a new binary.
+/// The archive is verified when the app starts.
+///
+/// See more: https://tauri.app/v1/api/config#externalassetsconfig
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExternalAssetsConfig {
+  /// Loads the frontend assets from the archive. Requires the `external-assets` Cargo feature.
+  #[serde(default)]
+  pub enable: bool,
+  /// The base64 encoded Ed25519 public key verifying the signature of a replaced archive.
+  ///
+  /// The archive built with the binary is checked by its SHA-256 digest.
+  /// When a public key is set, an archive whose `frontend-assets.bin.sig` file contains its base64 encoded signature
+  /// is also accepted, so the frontend can be updated without a new binary.
+  pub pubkey: Option<String>,
+}
+
+/// Configuration for the thread pool running the commands marked with `#[tauri::command(blocking)]`,
+/// which would freeze the UI if they ran on the main thread like the other synchronous commands.
+///
+/// See more: https://tauri.app/v1/api/config#blockingpoolconfig
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockingPoolConfig {
+  /// The number of threads of the pool. Defaults to the number of CPUs.
+  pub size: Option<usize>,
+  /// How many commands can wait for a free thread, the commands invoked while the queue is full are rejected.
+  /// Defaults to 1024.
+  #[serde(alias = "queue-limit")]
+  pub queue_limit: Option<usize>,
+}
+
+/// Configuration for the splashscreen, an undecorated window shown while the app starts.
+///
+/// The windows of the configuration are hidden until the app calls `AppHandle::close_splashscreen`,
+/// or `closeSplashscreen` from the `@tauri-apps/api/splashscreen` module, usually when the main window is ready.
+///
+/// See more: https://tauri.app/v1/api/config#splashscreenconfig
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SplashscreenConfig {
+  /// The HTML page of the splashscreen.
+  #[serde(default = "default_splashscreen_url")]
+  pub url: WindowUrl,
+  /// The path of an image shown instead of the HTML page, relative to the `distDir`, scaled to fit the splashscreen.
+  pub image: Option<PathBuf>,
+  /// The width of the splashscreen.
+  #[serde(default = "default_splashscreen_width")]
+  pub width: f64,
+  /// The height of the splashscreen.
+  #[serde(default = "default_splashscreen_height")]
+  pub height: f64,
+  /// Whether the splashscreen is transparent, e.g. for a rounded logo.
+  ///
+  /// Note that on `macOS` this requires the `macos-private-api` feature flag, enabled under `tauri > macOSPrivateApi`.
+  #[serde(default)]
+  pub transparent: bool,
+  /// The minimum time in milliseconds the splashscreen is shown, so it does not flash when the app starts quickly.
+  #[serde(default, alias = "min-display-time")]
+  pub min_display_time: u64,
+  /// The duration in milliseconds of the fade out of the splashscreen when it closes. Disabled with `0`.
+  #[serde(default, alias = "fade-out-duration")]
+  pub fade_out_duration: u64,
+}
+
+fn default_splashscreen_url() -> WindowUrl {
+  WindowUrl::App("splashscreen.html".into())
+}
+
+fn default_splashscreen_width() -> f64 {
+  400f64
+}
+
+fn default_splashscreen_height() -> f64 {
+  300f64
+}
+
+/// Configuration for the integration of the app with the Linux desktop environments.
+///
+/// See more: https://tauri.app/v1/api/config#linuxconfig
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LinuxConfig {
+  /// The application ID of the windows on Wayland, and the name of their `WM_CLASS` on X11,
+  /// used by the desktop environment to match the windows with the `.desktop` file of the app,
+  /// e.g. for their taskbar icon. Defaults to the name of the binary.
+  #[serde(alias = "app-id")]
+  pub app_id: Option<String>,
+}
+
 /// Install modes for the Windows update.
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -1569,20 +2677,100 @@ pub enum BeforeDevCommand {
   },
 }
 
-/// Describes a shell command to be executed when a CLI hook is triggered.
-#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+/// Describes a shell command to be executed when a CLI hook is triggered.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum HookCommand {
+  /// Run the given script with the default options.
+  Script(String),
+  /// Run the given script with custom options.
+  ScriptWithOptions {
+    /// The script to execute.
+    script: String,
+    /// The current working directory.
+    cwd: Option<String>,
+  },
+}
+
+/// The configuration of the development server of `tauri dev`.
+///
+/// Applies to the built-in server started when `devPath` is a folder,
+/// and to a server proxying the `devPath` URL when it points to a frontend dev server and HTTPS or proxy rules are configured.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DevServerConfig {
+  /// Reverse proxy rules, mapping a path prefix such as `/api` to the URL of the server handling its requests.
+  ///
+  /// The request path is appended to the upstream URL, e.g. with `{ "/api": "http://localhost:3000" }`
+  /// a request to `/api/users` is sent to `http://localhost:3000/api/users`. WebSocket connections are proxied too.
+  /// Only `http` upstreams are supported.
+  #[serde(default)]
+  pub proxy: HashMap<String, Url>,
+  /// Whether to serve the dev URL over HTTPS, for frontends requiring a secure context
+  /// (e.g. WebAuthn or `crypto.subtle` when the dev server is accessed from another device).
+  ///
+  /// The certificate is signed by a local certificate authority generated on first use
+  /// and added to the trusted certificates of the user on macOS and Windows.
+  /// On Linux and on mobile devices the CLI prints the path of the certificate authority to install.
+  #[serde(default)]
+  pub https: bool,
+}
+
+/// The configuration of the file watcher of `tauri dev`, which restarts the app when its Rust code changes.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WatcherConfig {
+  /// Additional paths to watch, relative to the `src-tauri` folder, e.g. crates outside of the Cargo workspace used as path dependencies.
+  ///
+  /// The `src-tauri` folder and the members of its Cargo workspace are always watched.
+  #[serde(default)]
+  pub paths: Vec<PathBuf>,
+  /// Patterns of the paths that must not restart the app, using the `.gitignore` syntax relative to the `src-tauri` folder.
+  ///
+  /// Applied in addition to the `.taurignore` files.
+  #[serde(default)]
+  pub ignore: Vec<String>,
+  /// How long to wait for the file changes to settle before restarting the app, in milliseconds.
+  #[serde(default = "default_watcher_debounce")]
+  pub debounce: u64,
+  /// Polls the file system for changes at the given interval in milliseconds instead of relying on file system events,
+  /// for network file systems, WSL mounts of Windows folders and some containers which don't emit them.
+  #[serde(alias = "poll-interval")]
+  pub poll_interval: Option<u64>,
+}
+
+impl Default for WatcherConfig {
+  fn default() -> Self {
+    Self {
+      paths: Vec::new(),
+      ignore: Vec::new(),
+      debounce: default_watcher_debounce(),
+      poll_interval: None,
+    }
+  }
+}
+
+fn default_watcher_debounce() -> u64 {
+  1000
+}
+
+/// The compression of the frontend assets embedded in the application binary when the `compression` feature is enabled.
+///
+/// The assets are brotli compressed in parallel, and only compressed again when their content or the compression level changed.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
-#[serde(rename_all = "camelCase", untagged)]
-pub enum HookCommand {
-  /// Run the given script with the default options.
-  Script(String),
-  /// Run the given script with custom options.
-  ScriptWithOptions {
-    /// The script to execute.
-    script: String,
-    /// The current working directory.
-    cwd: Option<String>,
-  },
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AssetCompressionConfig {
+  /// The brotli quality, from 0 (fastest) to 11 (smallest). Defaults to 2 in debug builds and 9 in release builds.
+  pub level: Option<u32>,
+  /// The number of threads compressing the assets. Defaults to the available parallelism.
+  pub threads: Option<usize>,
 }
 
 /// The Build configuration object.
@@ -1632,11 +2820,34 @@ pub struct BuildConfig {
   /// The TAURI_PLATFORM, TAURI_ARCH, TAURI_FAMILY, TAURI_PLATFORM_VERSION, TAURI_PLATFORM_TYPE and TAURI_DEBUG environment variables are set if you perform conditional compilation.
   #[serde(alias = "before-bundle-command")]
   pub before_bundle_command: Option<HookCommand>,
+  /// A shell command to run after `tauri build` bundles and signs the app.
+  ///
+  /// In addition to the `beforeBuildCommand` environment variables, TAURI_APP_PATH is set to the path of the app binary
+  /// and TAURI_BUNDLE_PATHS to the paths of the bundles and updater signatures,
+  /// separated by the platform path list separator (`:` on Linux and macOS, `;` on Windows).
+  #[serde(alias = "after-build-command")]
+  pub after_build_command: Option<HookCommand>,
+  /// The configuration of the `tauri dev` development server.
+  #[serde(default, alias = "dev-server")]
+  pub dev_server: DevServerConfig,
+  /// The configuration of the `tauri dev` file watcher.
+  #[serde(default)]
+  pub watcher: WatcherConfig,
   /// Features passed to `cargo` commands.
   pub features: Option<Vec<String>>,
   /// Whether we should inject the Tauri API on `window.__TAURI__` or not.
   #[serde(default, alias = "with-global-tauri")]
   pub with_global_tauri: bool,
+  /// Whether the frontend assets should be served brotli compressed with the `Content-Encoding: br` header
+  /// when the webview request accepts it, instead of being decompressed on each request.
+  ///
+  /// Only applies to text assets such as JavaScript, CSS, JSON and SVG files when the `compression` feature is enabled.
+  /// HTML files are always decompressed since the CSP must be injected.
+  #[serde(default, alias = "serve-compressed-assets")]
+  pub serve_compressed_assets: bool,
+  /// The compression of the embedded assets.
+  #[serde(default, alias = "asset-compression")]
+  pub asset_compression: AssetCompressionConfig,
 }
 
 impl Default for BuildConfig {
@@ -1648,8 +2859,13 @@ impl Default for BuildConfig {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_build_command: None,
+      dev_server: Default::default(),
+      watcher: Default::default(),
       features: None,
       with_global_tauri: false,
+      serve_compressed_assets: false,
+      asset_compression: Default::default(),
     }
   }
 }
@@ -1792,6 +3008,7 @@ impl PackageConfig {
 /// - [`tauri`](#tauriconfig): The Tauri config
 /// - [`build`](#buildconfig): The build configuration
 /// - [`plugins`](#pluginconfig): The plugins config
+/// - `custom`: The app configuration, see [`Config::custom`]
 ///
 /// ```json title="Example tauri.config.json file"
 /// {
@@ -1842,6 +3059,62 @@ pub struct Config {
   /// The plugins config.
   #[serde(default)]
   pub plugins: PluginConfig,
+  /// A section reserved to the app configuration, which Tauri does not read.
+  pub custom: Option<JsonValue>,
+}
+
+impl Config {
+  /// Deserializes the `custom` section of the configuration into the app configuration type.
+  ///
+  /// A missing section is deserialized from an empty object.
+  ///
+  /// ```
+  /// #[derive(serde::Deserialize)]
+  /// struct AppConfig {
+  ///   #[serde(default)]
+  ///   theme: Option<String>,
+  /// }
+  ///
+  /// let config: tauri_utils::config::Config =
+  ///   serde_json::from_str(r#"{ "custom": { "theme": "dark" } }"#).unwrap();
+  /// let app_config: AppConfig = config.custom().unwrap();
+  /// assert_eq!(app_config.theme.as_deref(), Some("dark"));
+  /// ```
+  pub fn custom<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+    match &self.custom {
+      Some(custom) => T::deserialize(custom),
+      None => T::deserialize(&JsonValue::Object(Default::default())),
+    }
+  }
+
+  /// The configuration values allowed by [`SecurityConfig::config_allowlist`], merged into a single object.
+  pub fn allowlisted(&self) -> serde_json::Result<JsonValue> {
+    let config = serde_json::to_value(self)?;
+    let mut allowlisted = JsonValue::Object(Default::default());
+
+    for key in &self.tauri.security.config_allowlist {
+      let path = key.split('.').collect::<Vec<_>>();
+      let value = path
+        .iter()
+        .try_fold(&config, |value, segment| value.get(segment));
+      if let Some(value) = value {
+        let mut target = &mut allowlisted;
+        for segment in &path[..path.len() - 1] {
+          target = target
+            .as_object_mut()
+            .unwrap()
+            .entry(*segment)
+            .or_insert_with(|| JsonValue::Object(Default::default()));
+        }
+        target
+          .as_object_mut()
+          .unwrap()
+          .insert(path[path.len() - 1].into(), value.clone());
+      }
+    }
+
+    Ok(allowlisted)
+  }
 }
 
 /// The plugin configs holds a HashMap mapping a plugin name to its configuration object.
@@ -1851,6 +3124,13 @@ pub struct Config {
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct PluginConfig(pub HashMap<String, JsonValue>);
 
+impl PluginConfig {
+  /// Deserializes the configuration of the given plugin, if it is defined.
+  pub fn get<T: DeserializeOwned>(&self, plugin: &str) -> serde_json::Result<Option<T>> {
+    self.0.get(plugin).map(T::deserialize).transpose()
+  }
+}
+
 fn default_build() -> BuildConfig {
   BuildConfig {
     runner: None,
@@ -1859,8 +3139,13 @@ fn default_build() -> BuildConfig {
     before_dev_command: None,
     before_build_command: None,
     before_bundle_command: None,
+    after_build_command: None,
+    dev_server: Default::default(),
+    watcher: Default::default(),
     features: None,
     with_global_tauri: false,
+    serve_compressed_assets: false,
+    asset_compression: Default::default(),
   }
 }
 
@@ -2073,6 +3358,24 @@ mod build {
     }
   }
 
+  impl ToTokens for TitleBarOverlayConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let height = opt_lit(self.height.as_ref());
+      let inset = opt_lit(self.inset.as_ref());
+
+      literal_struct!(tokens, TitleBarOverlayConfig, height, inset)
+    }
+  }
+
+  impl ToTokens for TitleBarInset {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let x = self.x;
+      let y = self.y;
+
+      literal_struct!(tokens, TitleBarInset, x, y)
+    }
+  }
+
   impl ToTokens for crate::TitleBarStyle {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::TitleBarStyle };
@@ -2111,6 +3414,11 @@ mod build {
         WindowEffect::UnderWindowBackground => quote! { #prefix::UnderWindowBackground},
         WindowEffect::UnderPageBackground => quote! { #prefix::UnderPageBackground},
         WindowEffect::Mica => quote! { #prefix::Mica},
+        WindowEffect::MicaDark => quote! { #prefix::MicaDark},
+        WindowEffect::MicaLight => quote! { #prefix::MicaLight},
+        WindowEffect::Tabbed => quote! { #prefix::Tabbed},
+        WindowEffect::TabbedDark => quote! { #prefix::TabbedDark},
+        WindowEffect::TabbedLight => quote! { #prefix::TabbedLight},
         WindowEffect::Blur => quote! { #prefix::Blur},
         WindowEffect::Acrylic => quote! { #prefix::Acrylic},
       })
@@ -2151,17 +3459,21 @@ mod build {
       let closable = self.closable;
       let title = str_lit(&self.title);
       let fullscreen = self.fullscreen;
+      let kiosk = self.kiosk;
       let focus = self.focus;
       let transparent = self.transparent;
       let maximized = self.maximized;
       let visible = self.visible;
+      let preload = self.preload;
       let decorations = self.decorations;
       let always_on_top = self.always_on_top;
       let content_protected = self.content_protected;
       let skip_taskbar = self.skip_taskbar;
+      let ignore_cursor_events = self.ignore_cursor_events;
       let theme = opt_lit(self.theme.as_ref());
       let title_bar_style = &self.title_bar_style;
       let hidden_title = self.hidden_title;
+      let title_bar_overlay = opt_lit(self.title_bar_overlay.as_ref());
       let accept_first_mouse = self.accept_first_mouse;
       let tabbing_identifier = opt_str_lit(self.tabbing_identifier.as_ref());
       let additional_browser_args = opt_str_lit(self.additional_browser_args.as_ref());
@@ -2191,17 +3503,21 @@ mod build {
         closable,
         title,
         fullscreen,
+        kiosk,
         focus,
         transparent,
         maximized,
         visible,
+        preload,
         decorations,
         always_on_top,
         content_protected,
         skip_taskbar,
+        ignore_cursor_events,
         theme,
         title_bar_style,
         hidden_title,
+        title_bar_overlay,
         accept_first_mouse,
         tabbing_identifier,
         additional_browser_args,
@@ -2279,6 +3595,26 @@ mod build {
     }
   }
 
+  impl ToTokens for BundleLocalization {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let product_name = opt_str_lit(self.product_name.as_ref());
+      let short_description = opt_str_lit(self.short_description.as_ref());
+      let long_description = opt_str_lit(self.long_description.as_ref());
+      let copyright = opt_str_lit(self.copyright.as_ref());
+      let license = opt_lit(self.license.as_ref().map(path_buf_lit).as_ref());
+
+      literal_struct!(
+        tokens,
+        BundleLocalization,
+        product_name,
+        short_description,
+        long_description,
+        copyright,
+        license
+      );
+    }
+  }
+
   impl ToTokens for BundleConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let identifier = str_lit(&self.identifier);
@@ -2287,18 +3623,34 @@ mod build {
       let active = self.active;
       let targets = quote!(Default::default());
       let resources = quote!(None);
+      let compress_resources = self.compress_resources;
       let copyright = quote!(None);
       let category = quote!(None);
       let short_description = quote!(None);
       let long_description = quote!(None);
       let appimage = quote!(Default::default());
       let deb = quote!(Default::default());
+      let rpm = quote!(Default::default());
+      let flatpak = quote!(Default::default());
+      let snap = quote!(Default::default());
       let macos = quote!(Default::default());
       let external_bin = opt_vec_str_lit(self.external_bin.as_ref());
       let windows = &self.windows;
       let ios = quote!(Default::default());
       let android = quote!(Default::default());
       let updater = &self.updater;
+      let local_network = quote!(Default::default());
+      let push = &self.push;
+      let system_bars = &self.system_bars;
+      let in_app_purchases = &self.in_app_purchases;
+      let shortcuts = quote!(Default::default());
+      let background_tasks = &self.background_tasks;
+      let localizations = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.localizations,
+        str_lit,
+        identity,
+      );
 
       literal_struct!(
         tokens,
@@ -2309,22 +3661,101 @@ mod build {
         icon,
         targets,
         resources,
+        compress_resources,
         copyright,
         category,
         short_description,
         long_description,
         appimage,
         deb,
+        rpm,
+        flatpak,
+        snap,
         macos,
         external_bin,
         windows,
         ios,
         android,
-        updater
+        updater,
+        localizations,
+        local_network,
+        push,
+        system_bars,
+        in_app_purchases,
+        shortcuts,
+        background_tasks
+      );
+    }
+  }
+
+  impl ToTokens for PushConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+      let aps_environment = &self.aps_environment;
+
+      literal_struct!(tokens, PushConfig, enable, aps_environment);
+    }
+  }
+
+  impl ToTokens for InAppPurchasesConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+
+      literal_struct!(tokens, InAppPurchasesConfig, enable);
+    }
+  }
+
+  impl ToTokens for BackgroundTasksConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let identifiers = vec_lit(&self.identifiers, str_lit);
+
+      literal_struct!(tokens, BackgroundTasksConfig, identifiers);
+    }
+  }
+
+  impl ToTokens for SystemBarsConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let status_bar_color = opt_lit(self.status_bar_color.as_ref());
+      let status_bar_style = &self.status_bar_style;
+      let navigation_bar_color = opt_lit(self.navigation_bar_color.as_ref());
+      let navigation_bar_style = &self.navigation_bar_style;
+      let edge_to_edge = self.edge_to_edge;
+
+      literal_struct!(
+        tokens,
+        SystemBarsConfig,
+        status_bar_color,
+        status_bar_style,
+        navigation_bar_color,
+        navigation_bar_style,
+        edge_to_edge
       );
     }
   }
 
+  impl ToTokens for SystemBarStyle {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::SystemBarStyle };
+
+      tokens.append_all(match self {
+        Self::Default => quote! { #prefix::Default },
+        Self::LightContent => quote! { #prefix::LightContent },
+        Self::DarkContent => quote! { #prefix::DarkContent },
+      })
+    }
+  }
+
+  impl ToTokens for ApsEnvironment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::ApsEnvironment };
+
+      tokens.append_all(match self {
+        Self::Development => quote! { #prefix::Development },
+        Self::Production => quote! { #prefix::Production },
+      })
+    }
+  }
+
   impl ToTokens for AppUrl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::config::AppUrl };
@@ -2346,11 +3777,16 @@ mod build {
       let dev_path = &self.dev_path;
       let dist_dir = &self.dist_dir;
       let with_global_tauri = self.with_global_tauri;
+      let serve_compressed_assets = self.serve_compressed_assets;
       let runner = quote!(None);
       let before_dev_command = quote!(None);
       let before_build_command = quote!(None);
       let before_bundle_command = quote!(None);
+      let after_build_command = quote!(None);
+      let dev_server = quote!(Default::default());
+      let watcher = quote!(Default::default());
       let features = quote!(None);
+      let asset_compression = quote!(Default::default());
 
       literal_struct!(
         tokens,
@@ -2359,10 +3795,15 @@ mod build {
         dev_path,
         dist_dir,
         with_global_tauri,
+        serve_compressed_assets,
         before_dev_command,
         before_build_command,
         before_bundle_command,
-        features
+        after_build_command,
+        dev_server,
+        watcher,
+        features,
+        asset_compression
       );
     }
   }
@@ -2459,26 +3900,171 @@ mod build {
     }
   }
 
+  impl ToTokens for Capability {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let identifier = str_lit(&self.identifier);
+      let description = opt_str_lit(self.description.as_ref());
+      let windows = vec_lit(&self.windows, str_lit);
+      let commands = vec_lit(&self.commands, str_lit);
+      let plugins = vec_lit(&self.plugins, str_lit);
+      let scopes = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.scopes,
+        str_lit,
+        json_value_lit,
+      );
+
+      literal_struct!(
+        tokens,
+        Capability,
+        identifier,
+        description,
+        windows,
+        commands,
+        plugins,
+        scopes
+      );
+    }
+  }
+
   impl ToTokens for SecurityConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let csp = opt_lit(self.csp.as_ref());
       let dev_csp = opt_lit(self.dev_csp.as_ref());
+      let window_csp = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.window_csp,
+        str_lit,
+        identity,
+      );
       let freeze_prototype = self.freeze_prototype;
       let dangerous_disable_asset_csp_modification = &self.dangerous_disable_asset_csp_modification;
       let dangerous_remote_domain_ipc_access =
         vec_lit(&self.dangerous_remote_domain_ipc_access, identity);
       let asset_protocol = &self.asset_protocol;
+      let capabilities = vec_lit(&self.capabilities, identity);
+      let command_origins = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.command_origins,
+        str_lit,
+        |origins| vec_lit(origins, str_lit),
+      );
+      let ipc_limits = &self.ipc_limits;
+      let config_allowlist = vec_lit(&self.config_allowlist, str_lit);
+      let hid = self.hid;
+      let serial = &self.serial;
+      let bluetooth = self.bluetooth;
+      let usb = &self.usb;
+      let haptics = self.haptics;
+      let sensors = self.sensors;
+      let keyring = self.keyring;
+      let auth = self.auth;
+      let share = &self.share;
+      let tls = &self.tls;
 
       literal_struct!(
         tokens,
         SecurityConfig,
         csp,
         dev_csp,
+        window_csp,
         freeze_prototype,
         dangerous_disable_asset_csp_modification,
         dangerous_remote_domain_ipc_access,
-        asset_protocol
+        asset_protocol,
+        capabilities,
+        command_origins,
+        ipc_limits,
+        config_allowlist,
+        hid,
+        serial,
+        bluetooth,
+        usb,
+        haptics,
+        sensors,
+        keyring,
+        auth,
+        share,
+        tls
+      );
+    }
+  }
+
+  impl ToTokens for TlsConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let ca_bundle = opt_lit(self.ca_bundle.as_ref().map(path_buf_lit).as_ref());
+      let builtin_roots = self.builtin_roots;
+      let pins = vec_lit(&self.pins, identity);
+
+      literal_struct!(tokens, TlsConfig, ca_bundle, builtin_roots, pins);
+    }
+  }
+
+  impl ToTokens for CertificatePin {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let host = str_lit(&self.host);
+      let sha256 = vec_lit(&self.sha256, str_lit);
+
+      literal_struct!(tokens, CertificatePin, host, sha256);
+    }
+  }
+
+  impl ToTokens for UsbConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+      let scope = vec_lit(&self.scope, identity);
+
+      literal_struct!(tokens, UsbConfig, enable, scope);
+    }
+  }
+
+  impl ToTokens for UsbDeviceFilter {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let vendor_id = self.vendor_id;
+      let product_id = opt_lit(self.product_id.as_ref());
+
+      literal_struct!(tokens, UsbDeviceFilter, vendor_id, product_id);
+    }
+  }
+
+  impl ToTokens for ShareConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+      let scope = &self.scope;
+
+      literal_struct!(tokens, ShareConfig, enable, scope);
+    }
+  }
+
+  impl ToTokens for SerialConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+      let scope = vec_lit(&self.scope, str_lit);
+
+      literal_struct!(tokens, SerialConfig, enable, scope);
+    }
+  }
+
+  impl ToTokens for IpcLimitsConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let max_payload_size = opt_lit(self.max_payload_size.as_ref());
+      let rate_limits = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.rate_limits,
+        str_lit,
+        identity,
       );
+
+      literal_struct!(tokens, IpcLimitsConfig, max_payload_size, rate_limits);
+    }
+  }
+
+  impl ToTokens for RateLimit {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let requests = self.requests;
+      let interval = self.interval;
+
+      literal_struct!(tokens, RateLimit, requests, interval);
     }
   }
 
@@ -2508,20 +4094,54 @@ mod build {
           let allowed_paths = vec_lit(allow, path_buf_lit);
           quote! { #prefix::AllowedPaths(#allowed_paths) }
         }
-        Self::Scope { allow, deny , require_literal_leading_dot} => {
+        Self::Scope { allow, deny, precedence, symlinks, pattern_options, require_literal_leading_dot } => {
           let allow = vec_lit(allow, path_buf_lit);
           let deny = vec_lit(deny, path_buf_lit);
+          let pattern_options = map_lit(quote! { ::std::collections::HashMap }, pattern_options, path_buf_lit, identity);
           let  require_literal_leading_dot = opt_lit(require_literal_leading_dot.as_ref());
-          quote! { #prefix::Scope { allow: #allow, deny: #deny, require_literal_leading_dot: #require_literal_leading_dot } }
+          quote! { #prefix::Scope { allow: #allow, deny: #deny, precedence: #precedence, symlinks: #symlinks, pattern_options: #pattern_options, require_literal_leading_dot: #require_literal_leading_dot } }
         }
       });
     }
   }
 
+  impl ToTokens for ScopePatternOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let case_sensitive = opt_lit(self.case_sensitive.as_ref());
+      let max_depth = opt_lit(self.max_depth.as_ref());
+
+      literal_struct!(tokens, ScopePatternOptions, case_sensitive, max_depth);
+    }
+  }
+
+  impl ToTokens for ScopePrecedence {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::ScopePrecedence };
+
+      tokens.append_all(match self {
+        Self::Deny => quote! { #prefix::Deny },
+        Self::MostSpecific => quote! { #prefix::MostSpecific },
+      })
+    }
+  }
+
+  impl ToTokens for ScopeSymlinkPolicy {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::ScopeSymlinkPolicy };
+
+      tokens.append_all(match self {
+        Self::Resolve => quote! { #prefix::Resolve },
+        Self::Deny => quote! { #prefix::Deny },
+        Self::Lexical => quote! { #prefix::Lexical },
+      })
+    }
+  }
+
   impl ToTokens for AssetProtocolConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let scope = &self.scope;
-      tokens.append_all(quote! { ::tauri::utils::config::AssetProtocolConfig { scope: #scope, ..Default::default() } })
+      let persist_scope = self.persist_scope;
+      tokens.append_all(quote! { ::tauri::utils::config::AssetProtocolConfig { scope: #scope, persist_scope: #persist_scope, ..Default::default() } })
     }
   }
 
@@ -2533,6 +4153,12 @@ mod build {
       let security = &self.security;
       let system_tray = opt_lit(self.system_tray.as_ref());
       let macos_private_api = self.macos_private_api;
+      let external_assets = &self.external_assets;
+      let blocking_pool = &self.blocking_pool;
+      let keep_running_without_windows = self.keep_running_without_windows;
+      let linux = &self.linux;
+      let splashscreen = opt_lit(self.splashscreen.as_ref());
+      let process_isolation = self.process_isolation;
 
       literal_struct!(
         tokens,
@@ -2542,11 +4168,67 @@ mod build {
         bundle,
         security,
         system_tray,
-        macos_private_api
+        macos_private_api,
+        external_assets,
+        blocking_pool,
+        keep_running_without_windows,
+        linux,
+        splashscreen,
+        process_isolation
+      );
+    }
+  }
+
+  impl ToTokens for ExternalAssetsConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let enable = self.enable;
+      let pubkey = opt_str_lit(self.pubkey.as_ref());
+
+      literal_struct!(tokens, ExternalAssetsConfig, enable, pubkey);
+    }
+  }
+
+  impl ToTokens for BlockingPoolConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let size = opt_lit(self.size.as_ref());
+      let queue_limit = opt_lit(self.queue_limit.as_ref());
+
+      literal_struct!(tokens, BlockingPoolConfig, size, queue_limit);
+    }
+  }
+
+  impl ToTokens for SplashscreenConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let url = &self.url;
+      let image = opt_lit(self.image.as_ref().map(path_buf_lit).as_ref());
+      let width = self.width;
+      let height = self.height;
+      let transparent = self.transparent;
+      let min_display_time = self.min_display_time;
+      let fade_out_duration = self.fade_out_duration;
+
+      literal_struct!(
+        tokens,
+        SplashscreenConfig,
+        url,
+        image,
+        width,
+        height,
+        transparent,
+        min_display_time,
+        fade_out_duration
       );
     }
   }
 
+  impl ToTokens for LinuxConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let app_id = opt_str_lit(self.app_id.as_ref());
+
+      literal_struct!(tokens, LinuxConfig, app_id);
+    }
+  }
+
   impl ToTokens for PluginConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let config = map_lit(
@@ -2575,8 +4257,9 @@ mod build {
       let tauri = &self.tauri;
       let build = &self.build;
       let plugins = &self.plugins;
+      let custom = opt_lit(self.custom.as_ref().map(json_value_lit).as_ref());
 
-      literal_struct!(tokens, Config, schema, package, tauri, build, plugins);
+      literal_struct!(tokens, Config, schema, package, tauri, build, plugins, custom);
     }
   }
 }
@@ -2612,29 +4295,61 @@ mod test {
         publisher: None,
         icon: Vec::new(),
         resources: None,
+        compress_resources: false,
         copyright: None,
         category: None,
         short_description: None,
         long_description: None,
         appimage: Default::default(),
         deb: Default::default(),
+        rpm: Default::default(),
+        flatpak: Default::default(),
+        snap: Default::default(),
         macos: Default::default(),
         external_bin: None,
         windows: Default::default(),
         ios: Default::default(),
         android: Default::default(),
         updater: Default::default(),
+        localizations: Default::default(),
+        local_network: Default::default(),
+        push: Default::default(),
+        system_bars: Default::default(),
+        in_app_purchases: Default::default(),
+        shortcuts: Vec::new(),
+        background_tasks: Default::default(),
       },
       security: SecurityConfig {
         csp: None,
         dev_csp: None,
+        window_csp: Default::default(),
         freeze_prototype: false,
         dangerous_disable_asset_csp_modification: DisabledCspModificationKind::Flag(false),
         dangerous_remote_domain_ipc_access: Vec::new(),
         asset_protocol: AssetProtocolConfig::default(),
+        capabilities: Vec::new(),
+        command_origins: Default::default(),
+        ipc_limits: Default::default(),
+        config_allowlist: Vec::new(),
+        hid: false,
+        serial: Default::default(),
+        bluetooth: false,
+        usb: Default::default(),
+        haptics: false,
+        sensors: false,
+        keyring: false,
+        auth: false,
+        share: Default::default(),
+        tls: Default::default(),
       },
       system_tray: None,
       macos_private_api: false,
+      external_assets: Default::default(),
+      blocking_pool: Default::default(),
+      keep_running_without_windows: false,
+      linux: Default::default(),
+      splashscreen: None,
+      process_isolation: false,
     };
 
     // create a build config
@@ -2647,8 +4362,13 @@ mod test {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_build_command: None,
+      dev_server: Default::default(),
+      watcher: Default::default(),
       features: None,
       with_global_tauri: false,
+      serve_compressed_assets: false,
+      asset_compression: Default::default(),
     };
 
     // test the configs
@@ -2663,4 +4383,103 @@ mod test {
     );
     assert_eq!(d_windows, tauri.windows);
   }
+
+  #[test]
+  fn allowlisted() {
+    let config: Config = serde_json::from_value(serde_json::json!({
+      "package": { "productName": "app", "version": "1.0.0" },
+      "tauri": {
+        "security": { "configAllowlist": ["package.version", "custom.theme", "custom.missing"] }
+      },
+      "custom": { "theme": "dark", "apiKey": "secret" }
+    }))
+    .unwrap();
+
+    assert_eq!(
+      config.allowlisted().unwrap(),
+      serde_json::json!({
+        "package": { "version": "1.0.0" },
+        "custom": { "theme": "dark" }
+      })
+    );
+  }
+
+  #[test]
+  fn csp_with_directive() {
+    let csp = Csp::Policy("default-src 'self'; img-src 'self'".into())
+      .with_directive("img-src", ["asset:"])
+      .with_directive("connect-src", ["ipc:", "https://api.example.com"]);
+    let map: HashMap<String, CspDirectiveSources> = csp.into();
+
+    assert_eq!(
+      Vec::<String>::from(map["default-src"].clone()),
+      vec!["'self'"]
+    );
+    assert_eq!(
+      Vec::<String>::from(map["img-src"].clone()),
+      vec!["'self'", "asset:"]
+    );
+    assert_eq!(
+      Vec::<String>::from(map["connect-src"].clone()),
+      vec!["ipc:", "https://api.example.com"]
+    );
+  }
+
+  #[test]
+  fn capabilities() {
+    let security: SecurityConfig = serde_json::from_value(serde_json::json!({
+      "capabilities": [
+        {
+          "identifier": "main",
+          "windows": ["main"],
+          "commands": ["read_file"],
+          "plugins": ["fs"],
+          "scopes": { "fs": { "allow": ["$APPDATA/**"] } }
+        },
+        {
+          "identifier": "settings",
+          "windows": ["settings-*"],
+          "commands": ["save_settings", "plugin:fs|exists"],
+          "scopes": { "fs": { "allow": ["$APPCONFIG/settings.json"] } }
+        }
+      ]
+    }))
+    .unwrap();
+
+    assert!(security.is_command_allowed("main", "read_file"));
+    assert!(security.is_command_allowed("main", "plugin:fs|read"));
+    assert!(!security.is_command_allowed("main", "save_settings"));
+
+    assert!(security.is_command_allowed("settings-general", "save_settings"));
+    assert!(security.is_command_allowed("settings-", "plugin:fs|exists"));
+    assert!(!security.is_command_allowed("settings-general", "plugin:fs|read"));
+    assert!(!security.is_command_allowed("settings", "save_settings"));
+    assert!(!security.is_command_allowed("other", "read_file"));
+
+    assert_eq!(
+      security.window_scopes("settings-general", "fs"),
+      vec![&serde_json::json!({ "allow": ["$APPCONFIG/settings.json"] })]
+    );
+    assert!(security.window_scopes("other", "fs").is_empty());
+
+    assert!(SecurityConfig::default().is_command_allowed("other", "read_file"));
+  }
+
+  #[test]
+  fn capability_window_patterns() {
+    let capability = |windows: &[&str]| Capability {
+      windows: windows.iter().map(|w| w.to_string()).collect(),
+      ..Default::default()
+    };
+
+    assert!(capability(&["*"]).matches_window("main"));
+    assert!(capability(&["*"]).matches_window(""));
+    assert!(capability(&["main"]).matches_window("main"));
+    assert!(!capability(&["main"]).matches_window("main-2"));
+    assert!(capability(&["editor-*-view"]).matches_window("editor-1-view"));
+    assert!(capability(&["editor-*-view"]).matches_window("editor--view"));
+    assert!(!capability(&["editor-*-view"]).matches_window("editor-1-view-2"));
+    assert!(capability(&["*-é"]).matches_window("ü-é"));
+    assert!(!capability(&[]).matches_window("main"));
+  }
 }
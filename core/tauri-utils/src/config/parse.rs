@@ -2,10 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::config::Config;
-use json_patch::merge;
+use crate::config::{Capability, Config};
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -26,6 +25,15 @@ pub const ENABLED_FORMATS: &[ConfigFormat] = &[
   ConfigFormat::Toml,
 ];
 
+/// The environment variable with the name of the environment-specific configuration file to merge, see [`read_from`].
+pub const ENV_VAR_NAME: &str = "TAURI_ENV";
+
+/// The prefix of the environment variables that override configuration values, see [`env_overrides`].
+pub const OVERRIDE_VAR_PREFIX: &str = "TAURI_CONFIG_";
+
+/// The key of an object that appends its array to the array it is merged into instead of replacing it, see [`merge`].
+pub const APPEND_KEY: &str = "$append";
+
 /// The available configuration formats.
 #[derive(Debug, Copy, Clone)]
 pub enum ConfigFormat {
@@ -47,6 +55,34 @@ impl ConfigFormat {
     }
   }
 
+  /// The config format of the given file, based on its extension.
+  pub fn from_path(path: &Path) -> Option<Self> {
+    match path.extension().and_then(OsStr::to_str) {
+      Some("json") => Some(Self::Json),
+      Some("json5") => Some(Self::Json5),
+      Some("toml") => Some(Self::Toml),
+      _ => None,
+    }
+  }
+
+  /// The Cargo feature of the `tauri`, `tauri-build` and `tauri-codegen` crates required to read the format.
+  pub fn cargo_feature(self) -> Option<&'static str> {
+    match self {
+      Self::Json => None,
+      Self::Json5 => Some("config-json5"),
+      Self::Toml => Some("config-toml"),
+    }
+  }
+
+  /// Maps the config format to the file name of the given environment, e.g. `tauri.staging.conf.json`.
+  pub fn into_env_file_name(self, env: &str) -> String {
+    match self {
+      Self::Json => format!("tauri.{env}.conf.json"),
+      Self::Json5 => format!("tauri.{env}.conf.json5"),
+      Self::Toml => format!("Tauri.{env}.toml"),
+    }
+  }
+
   fn into_platform_file_name(self) -> &'static str {
     match self {
       Self::Json => {
@@ -176,6 +212,12 @@ pub fn is_configuration_file(path: &Path) -> bool {
       || file_name == OsStr::new(ConfigFormat::Json.into_platform_file_name())
         || file_name == OsStr::new(ConfigFormat::Json5.into_platform_file_name())
         || file_name == OsStr::new(ConfigFormat::Toml.into_platform_file_name())
+      // environment file names
+      || env().map_or(false, |env| {
+        SUPPORTED_FORMATS
+          .iter()
+          .any(|format| file_name == OsStr::new(&format.into_env_file_name(&env)))
+      })
     })
     .unwrap_or_default()
 }
@@ -189,17 +231,228 @@ pub fn is_configuration_file(path: &Path) -> bool {
 /// - `tauri.windows.conf.json[5]` or `Tauri.windows.toml` on Windows
 /// - `tauri.android.conf.json[5]` or `Tauri.android.toml` on Android
 /// - `tauri.ios.conf.json[5]` or `Tauri.ios.toml` on iOS
-/// Merging the configurations using [JSON Merge Patch (RFC 7396)].
 ///
-/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
+/// Then, if the [`ENV_VAR_NAME`] environment variable is set, for the `tauri.{env}.conf.json[5]` or `Tauri.{env}.toml` file,
+/// which must exist. Finally it applies the [`env_overrides`].
+///
+/// The configurations are merged in that order with [`merge`].
 pub fn read_from(root_dir: PathBuf) -> Result<Value, ConfigError> {
   let mut config: Value = parse_value(root_dir.join("tauri.conf.json"))?.0;
-  if let Some((platform_config, _)) = read_platform(root_dir)? {
+  if let Some((platform_config, _)) = read_platform(root_dir.clone())? {
     merge(&mut config, &platform_config);
   }
+  if let Some(env) = env() {
+    let (env_config, _) = read_env(&root_dir, &env)?;
+    merge(&mut config, &env_config);
+  }
+  if let Some(overrides) = env_overrides() {
+    merge(&mut config, &overrides);
+  }
   Ok(config)
 }
 
+/// The environment selected by the [`ENV_VAR_NAME`] environment variable.
+pub fn env() -> Option<String> {
+  std::env::var(ENV_VAR_NAME)
+    .ok()
+    .filter(|env| !env.is_empty())
+}
+
+/// Reads the configuration file of the given environment from the root directory.
+///
+/// Check [`read_from`] for more information.
+pub fn read_env(root_dir: &Path, env: &str) -> Result<(Value, PathBuf), ConfigError> {
+  for format in SUPPORTED_FORMATS {
+    let path = root_dir.join(format.into_env_file_name(env));
+    if !path.exists() {
+      continue;
+    }
+    let raw = read_to_string(&path)?;
+    let config = match format {
+      ConfigFormat::Json => do_parse_json(&raw, &path)?,
+      #[cfg(feature = "config-json5")]
+      ConfigFormat::Json5 => do_parse_json5(&raw, &path)?,
+      #[cfg(feature = "config-toml")]
+      ConfigFormat::Toml => do_parse_toml(&raw, &path)?,
+      #[allow(unreachable_patterns)]
+      _ => {
+        return Err(ConfigError::DisabledFormat {
+          extension: path
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default(),
+          feature: match format {
+            ConfigFormat::Toml => "config-toml",
+            _ => "config-json5",
+          }
+          .into(),
+        })
+      }
+    };
+    return Ok((config, path));
+  }
+
+  Err(ConfigError::Io {
+    path: root_dir.join(ConfigFormat::Json.into_env_file_name(env)),
+    error: std::io::ErrorKind::NotFound.into(),
+  })
+}
+
+/// Reads the configuration values overridden by the `TAURI_CONFIG_*` environment variables.
+///
+/// The variable name is the path of the value, with `__` separating the objects and
+/// snake case keys mapped to camel case, so `TAURI_CONFIG_BUILD__DEV_PATH` sets `build > devPath`.
+/// Values are parsed as JSON, falling back to a string, so `TAURI_CONFIG_TAURI__BUNDLE__ACTIVE=false` sets a boolean.
+pub fn env_overrides() -> Option<Value> {
+  overrides(std::env::vars())
+}
+
+fn overrides(vars: impl Iterator<Item = (String, String)>) -> Option<Value> {
+  let mut overrides = Value::Object(Map::new());
+  let mut found = false;
+
+  for (name, value) in vars {
+    let Some(path) = name.strip_prefix(OVERRIDE_VAR_PREFIX) else {
+      continue;
+    };
+    let value = serde_json::from_str(&value).unwrap_or(Value::String(value));
+    let keys = path
+      .split("__")
+      .map(env_key_to_camel_case)
+      .collect::<Vec<_>>();
+    let mut keys = keys.into_iter().rev();
+    let Some(last) = keys.next() else {
+      continue;
+    };
+    let mut patch = Map::new();
+    patch.insert(last, value);
+    for key in keys {
+      let mut parent = Map::new();
+      parent.insert(key, Value::Object(patch));
+      patch = parent;
+    }
+    merge(&mut overrides, &Value::Object(patch));
+    found = true;
+  }
+
+  found.then_some(overrides)
+}
+
+fn env_key_to_camel_case(key: &str) -> String {
+  let mut camel_case = String::new();
+  for (i, word) in key.split('_').filter(|w| !w.is_empty()).enumerate() {
+    let word = word.to_lowercase();
+    if i == 0 {
+      camel_case.push_str(&word);
+    } else {
+      let mut chars = word.chars();
+      if let Some(first) = chars.next() {
+        camel_case.extend(first.to_uppercase());
+        camel_case.push_str(chars.as_str());
+      }
+    }
+  }
+  camel_case
+}
+
+/// Merges the `patch` configuration into `config` with [JSON Merge Patch (RFC 7396)] semantics:
+/// objects are merged recursively, `null` removes the value and any other value replaces it, including arrays.
+///
+/// To append to an array instead of replacing it, wrap the items in an [`APPEND_KEY`] object:
+///
+/// ```json
+/// { "tauri": { "windows": { "$append": [{ "label": "debug" }] } } }
+/// ```
+///
+/// [JSON Merge Patch (RFC 7396)]: https://datatracker.ietf.org/doc/html/rfc7396.
+pub fn merge(config: &mut Value, patch: &Value) {
+  let Value::Object(patch) = patch else {
+    *config = patch.clone();
+    return;
+  };
+
+  if let Some(Value::Array(items)) = patch.get(APPEND_KEY).filter(|_| patch.len() == 1) {
+    match config {
+      Value::Array(array) => array.extend(items.iter().cloned()),
+      _ => *config = Value::Array(items.clone()),
+    }
+    return;
+  }
+
+  if !config.is_object() {
+    *config = Value::Object(Map::new());
+  }
+  let map = config.as_object_mut().unwrap();
+  for (key, value) in patch {
+    if value.is_null() {
+      map.remove(key);
+    } else {
+      merge(map.entry(key.as_str()).or_insert(Value::Null), value);
+    }
+  }
+}
+
+/// The folder, next to the Tauri configuration file, that holds the capability files.
+pub const CAPABILITIES_FOLDER_NAME: &str = "capabilities";
+
+/// Reads the capabilities defined on the `capabilities` folder of the given root directory.
+///
+/// Each `.json`, `.json5` or `.toml` file defines a single [`Capability`]. Files are read in alphabetical order.
+pub fn read_capabilities(root_dir: &Path) -> Result<Vec<Capability>, ConfigError> {
+  let dir = root_dir.join(CAPABILITIES_FOLDER_NAME);
+  if !dir.is_dir() {
+    return Ok(Vec::new());
+  }
+
+  let mut paths = std::fs::read_dir(&dir)
+    .map_err(|error| ConfigError::Io {
+      path: dir.clone(),
+      error,
+    })?
+    .filter_map(|entry| entry.ok().map(|e| e.path()))
+    .filter(|path| path.is_file())
+    .collect::<Vec<_>>();
+  paths.sort();
+
+  let mut capabilities = Vec::new();
+  for path in paths {
+    let extension = path
+      .extension()
+      .map(OsStr::to_string_lossy)
+      .unwrap_or_default()
+      .to_string();
+    if !EXTENSIONS_SUPPORTED.contains(&extension.as_str()) {
+      continue;
+    }
+    let raw = read_to_string(&path)?;
+    let capability = match extension.as_str() {
+      "json" => do_parse_json(&raw, &path)?,
+      #[cfg(feature = "config-json5")]
+      "json5" => do_parse_json5(&raw, &path)?,
+      #[cfg(not(feature = "config-json5"))]
+      "json5" => {
+        return Err(ConfigError::DisabledFormat {
+          extension: ".json5".into(),
+          feature: "config-json5".into(),
+        })
+      }
+      #[cfg(feature = "config-toml")]
+      "toml" => do_parse_toml(&raw, &path)?,
+      #[cfg(not(feature = "config-toml"))]
+      "toml" => {
+        return Err(ConfigError::DisabledFormat {
+          extension: ".toml".into(),
+          feature: "config-toml".into(),
+        })
+      }
+      _ => continue,
+    };
+    capabilities.push(capability);
+  }
+
+  Ok(capabilities)
+}
+
 /// Reads the platform-specific configuration file from the given root directory if it exists.
 ///
 /// Check [`read_from`] for more information.
@@ -385,6 +638,24 @@ fn do_parse_json5<D: DeserializeOwned>(raw: &str, path: &Path) -> Result<D, Conf
   })
 }
 
+/// "Low-level" helper to parse TOML into a [`Config`].
+///
+/// `raw` should be the contents of the file that is represented by `path`. This function requires
+/// the `config-toml` feature to be enabled.
+#[cfg(feature = "config-toml")]
+pub fn parse_toml(raw: &str, path: &Path) -> Result<Config, ConfigError> {
+  do_parse_toml(raw, path)
+}
+
+/// "Low-level" helper to parse TOML into a JSON [`Value`].
+///
+/// `raw` should be the contents of the file that is represented by `path`. This function requires
+/// the `config-toml` feature to be enabled.
+#[cfg(feature = "config-toml")]
+pub fn parse_toml_value(raw: &str, path: &Path) -> Result<Value, ConfigError> {
+  do_parse_toml(raw, path)
+}
+
 #[cfg(feature = "config-toml")]
 fn do_parse_toml<D: DeserializeOwned>(raw: &str, path: &Path) -> Result<D, ConfigError> {
   ::toml::from_str(raw).map_err(|error| ConfigError::FormatToml {
@@ -400,3 +671,60 @@ fn read_to_string(path: &Path) -> Result<String, ConfigError> {
     error,
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn merges_configs() {
+    let mut config = json!({
+      "build": { "devPath": "http://localhost:1420", "beforeDevCommand": "yarn dev" },
+      "tauri": { "windows": [{ "label": "main" }], "bundle": { "targets": ["deb"] } }
+    });
+    merge(
+      &mut config,
+      &json!({
+        "build": { "beforeDevCommand": null },
+        "tauri": {
+          "windows": { "$append": [{ "label": "debug" }] },
+          "bundle": { "targets": ["appimage"] }
+        }
+      }),
+    );
+    assert_eq!(
+      config,
+      json!({
+        "build": { "devPath": "http://localhost:1420" },
+        "tauri": {
+          "windows": [{ "label": "main" }, { "label": "debug" }],
+          "bundle": { "targets": ["appimage"] }
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn reads_env_overrides() {
+    let vars = [
+      ("TAURI_CONFIG_BUILD__DEV_PATH", "http://localhost:8080"),
+      ("TAURI_CONFIG_TAURI__BUNDLE__ACTIVE", "false"),
+      (
+        "TAURI_CONFIG_TAURI__BUNDLE__IDENTIFIER",
+        "com.tauri.staging",
+      ),
+      ("TAURI_ENV", "staging"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()));
+    assert_eq!(
+      overrides(vars),
+      Some(json!({
+        "build": { "devPath": "http://localhost:8080" },
+        "tauri": { "bundle": { "active": false, "identifier": "com.tauri.staging" } }
+      }))
+    );
+    assert_eq!(overrides(std::iter::empty()), None);
+  }
+}
@@ -20,9 +20,13 @@ pub mod html;
 pub mod io;
 pub mod mime_type;
 pub mod platform;
+pub mod plugin;
 /// Prepare application resources and sidecars.
 #[cfg(feature = "resources")]
 pub mod resources;
+/// Filesystem scope pattern matching.
+#[cfg(feature = "scope")]
+pub mod scope;
 
 /// Application pattern.
 pub mod pattern;
@@ -113,6 +117,16 @@ mod window_effects {
     UnderPageBackground,
     /// **Windows 11 Only**
     Mica,
+    /// Mica effect with dark mode but only if dark mode is enabled on the system **Windows 11 Only**
+    MicaDark,
+    /// Mica effect with light mode **Windows 11 Only**
+    MicaLight,
+    /// Tabbed effect, also known as Mica Alt, that matches the system dark preference **Windows 11 Only**
+    Tabbed,
+    /// Tabbed effect with dark mode but only if dark mode is enabled on the system **Windows 11 Only**
+    TabbedDark,
+    /// Tabbed effect with light mode **Windows 11 Only**
+    TabbedLight,
     /// **Windows 7/10/11(22H1) Only**
     ///
     /// ## Notes
@@ -263,6 +277,9 @@ pub struct Env {
   /// The APPDIR environment variable.
   #[cfg(target_os = "linux")]
   pub appdir: Option<std::ffi::OsString>,
+  /// The SNAP environment variable.
+  #[cfg(target_os = "linux")]
+  pub snap: Option<std::ffi::OsString>,
   /// The command line arguments of the current process.
   pub args: Vec<String>,
 }
@@ -273,11 +290,13 @@ impl Default for Env {
     let args = std::env::args().skip(1).collect();
     #[cfg(target_os = "linux")]
     {
-      let env = Self {
+      let mut env = Self {
         #[cfg(target_os = "linux")]
         appimage: std::env::var_os("APPIMAGE"),
         #[cfg(target_os = "linux")]
         appdir: std::env::var_os("APPDIR"),
+        #[cfg(target_os = "linux")]
+        snap: std::env::var_os("SNAP"),
         args,
       };
       if env.appimage.is_some() || env.appdir.is_some() {
@@ -297,6 +316,18 @@ impl Default for Env {
           panic!("`APPDIR` or `APPIMAGE` environment variable found but this application was not detected as an AppImage; this might be a security issue.");
         }
       }
+      if let Some(snap) = &env.snap {
+        // validate that we're actually running from the snap mount point
+        let is_snap = std::env::current_exe()
+          .map(|p| p.starts_with(snap))
+          .unwrap_or(false);
+
+        // the variable is ignored so it can't redirect the resource directory
+        if !is_snap {
+          debug_eprintln!("`SNAP` environment variable found but this application was not detected as a Snap package; ignoring it.");
+          env.snap = None;
+        }
+      }
       env
     }
     #[cfg(not(target_os = "linux"))]
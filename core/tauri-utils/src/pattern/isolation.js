@@ -22,6 +22,11 @@
    */
   const aesGcmKeyRaw = new Uint8Array(__TEMPLATE_runtime_aes_gcm_key__)
 
+  /**
+   * @type {string} - Injected by Tauri during runtime
+   */
+  const windowLabel = __TEMPLATE_window_label__
+
   /**
    * @type {CryptoKey}
    */
@@ -74,15 +79,19 @@
    * @param {MessageEvent<any>} event
    */
   async function payloadHandler(event) {
-    if (!isIsolationPayload(event)) {
+    // only the window embedding this frame can send payloads, not other frames of a remote page
+    if (event.source !== window.parent || !isIsolationPayload(event)) {
       return
     }
 
     let data = event.data
 
     if (typeof window.__TAURI_ISOLATION_HOOK__ === 'function') {
+      let context = Object.create(null)
+      context.windowLabel = windowLabel
+      context.origin = event.origin
       // await even if it's not async so that we can support async ones
-      data = await window.__TAURI_ISOLATION_HOOK__(data)
+      data = await window.__TAURI_ISOLATION_HOOK__(data, Object.freeze(context))
     }
 
     const encrypted = await encrypt(data)
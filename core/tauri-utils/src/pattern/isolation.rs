@@ -141,6 +141,8 @@ pub struct IsolationJavascriptCodegen {
 pub struct IsolationJavascriptRuntime<'a> {
   /// The key used on the Rust backend and the Isolation Javascript
   pub runtime_aes_gcm_key: &'a [u8; 32],
+  /// The label of the window the isolation application is loaded on.
+  pub window_label: &'a str,
   /// The function that stringifies a IPC message.
   #[raw]
   pub stringify_ipc_message_fn: &'a str,
@@ -148,9 +150,67 @@ pub struct IsolationJavascriptRuntime<'a> {
 
 #[cfg(test)]
 mod test {
+  use super::{IsolationJavascriptRuntime, Keys, RawIsolationPayload};
+  use aes_gcm::{aead::Aead, Nonce};
+  use serialize_to_javascript::Template;
+
+  /// Encrypts the message like the isolation application does.
+  fn encrypt(keys: &Keys, message: &str) -> String {
+    let nonce = [7u8; 12];
+    let payload = keys
+      .aes_gcm()
+      .key()
+      .encrypt(Nonce::from_slice(&nonce), message.as_bytes())
+      .unwrap();
+    serde_json::json!({ "nonce": nonce, "payload": payload }).to_string()
+  }
+
   #[test]
   fn create_keys() -> Result<(), Box<dyn std::error::Error>> {
     let _ = super::Keys::new()?;
     Ok(())
   }
+
+  #[test]
+  fn decrypt() -> Result<(), Box<dyn std::error::Error>> {
+    let keys = Keys::new()?;
+    let encrypted = encrypt(&keys, r#"{"cmd":"ping"}"#);
+    assert_eq!(
+      keys.decrypt(RawIsolationPayload::try_from(encrypted.as_str())?)?,
+      r#"{"cmd":"ping"}"#
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn decrypt_with_previous_session_key() -> Result<(), Box<dyn std::error::Error>> {
+    let previous = Keys::new()?;
+    let current = Keys::new()?;
+    assert_ne!(previous.aes_gcm().raw(), current.aes_gcm().raw());
+
+    let encrypted = encrypt(&previous, r#"{"cmd":"ping"}"#);
+    assert!(matches!(
+      current.decrypt(RawIsolationPayload::try_from(encrypted.as_str())?),
+      Err(super::Error::Aes)
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn runtime_template() -> Result<(), Box<dyn std::error::Error>> {
+    let template = IsolationJavascriptRuntime {
+      runtime_aes_gcm_key: &[1; 32],
+      window_label: "main\"window",
+      stringify_ipc_message_fn: "JSON.stringify",
+    };
+    let rendered = template.render(
+      "const windowLabel = __TEMPLATE_window_label__",
+      &Default::default(),
+    )?;
+    assert_eq!(
+      rendered.into_string(),
+      r#"const windowLabel = JSON.parse('"main\\"window"')"#
+    );
+    Ok(())
+  }
 }
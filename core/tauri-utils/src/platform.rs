@@ -4,7 +4,7 @@
 
 //! Platform helper functions.
 
-use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
 
 use crate::{Env, PackageInfo};
 
@@ -145,8 +145,9 @@ pub fn target_triple() -> crate::Result<String> {
 ///
 /// On Linux, when running in an AppImage the `APPDIR` variable will be set to
 /// the mounted location of the app, and the resource dir will be
-/// `${APPDIR}/usr/lib/${exe_name}`. If not running in an AppImage, the path is
-/// `/usr/lib/${exe_name}`.  When running the app from
+/// `${APPDIR}/usr/lib/${exe_name}`. When running in a Flatpak sandbox, the path is
+/// `/app/lib/${exe_name}` and in a Snap package it is `${SNAP}/usr/lib/${exe_name}`.
+/// Otherwise the path is `/usr/lib/${exe_name}`.  When running the app from
 /// `src-tauri/target/(debug|release)/`, the path is
 /// `${exe_dir}/../lib/${exe_name}`.
 ///
@@ -176,6 +177,19 @@ pub fn resource_dir(package_info: &PackageInfo, env: &Env) -> crate::Result<Path
         .join(format!("../lib/{}", package_info.package_name()))
         .canonicalize()
         .map_err(Into::into)
+    } else if curr_dir == "/app/bin" {
+      // running from the flatpak sandbox
+      Ok(PathBuf::from(format!(
+        "/app/lib/{}",
+        package_info.package_name()
+      )))
+    } else if let Some(snap) = &env.snap {
+      let snap: &std::path::Path = snap.as_ref();
+      Ok(PathBuf::from(format!(
+        "{}/usr/lib/{}",
+        snap.display(),
+        package_info.package_name()
+      )))
     } else if let Some(appdir) = &env.appdir {
       let appdir: &std::path::Path = appdir.as_ref();
       Ok(PathBuf::from(format!(
@@ -203,6 +217,56 @@ pub fn resource_dir(package_info: &PackageInfo, env: &Env) -> crate::Result<Path
   res
 }
 
+/// The scope of the Windows installation of the app, chosen when building the MSI installer
+/// or by the user on the NSIS installers built with the `both` install mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowsInstallScope {
+  /// The app is installed for all users, e.g. in the `Program Files` folder.
+  PerMachine,
+  /// The app is installed for the current user, in the `AppData\Local` folder of the user.
+  CurrentUser,
+}
+
+impl WindowsInstallScope {
+  /// Detects the scope of the installation from the path of the app executable,
+  /// since the per-user installers install the app in the given `AppData\Local` folder.
+  pub fn from_exe_path(exe: &Path, local_app_data: &Path) -> Self {
+    // paths are case insensitive on Windows
+    let exe = PathBuf::from(exe.to_string_lossy().to_lowercase());
+    let local_app_data = PathBuf::from(local_app_data.to_string_lossy().to_lowercase());
+    if exe.starts_with(local_app_data) {
+      Self::CurrentUser
+    } else {
+      Self::PerMachine
+    }
+  }
+
+  /// Detects the scope of the installation of the running app.
+  pub fn current() -> crate::Result<Self> {
+    let exe = current_exe()?;
+    Ok(match std::env::var_os("LOCALAPPDATA") {
+      Some(local_app_data) => Self::from_exe_path(&exe, Path::new(&local_app_data)),
+      None => Self::PerMachine,
+    })
+  }
+
+  /// The `msiexec.exe` arguments installing an update with the scope of this installation.
+  pub fn msiexec_args(&self) -> &'static [&'static str] {
+    match self {
+      Self::PerMachine => &["ALLUSERS=1"],
+      Self::CurrentUser => &["ALLUSERS=2", "MSIINSTALLPERUSER=1"],
+    }
+  }
+
+  /// The NSIS installer arguments installing an update with the scope of this installation.
+  pub fn nsis_args(&self) -> &'static [&'static str] {
+    match self {
+      Self::PerMachine => &["/AllUsers"],
+      Self::CurrentUser => &["/CurrentUser"],
+    }
+  }
+}
+
 #[cfg(windows)]
 pub use windows_platform::{get_function_impl, is_windows_7, windows_version};
 
@@ -286,3 +350,45 @@ mod windows_platform {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::WindowsInstallScope;
+  use std::path::Path;
+
+  #[test]
+  fn windows_install_scope() {
+    let local_app_data = Path::new("C:/Users/tauri/AppData/Local");
+    assert_eq!(
+      WindowsInstallScope::from_exe_path(
+        Path::new("C:/Users/tauri/AppData/Local/Programs/app/app.exe"),
+        local_app_data
+      ),
+      WindowsInstallScope::CurrentUser
+    );
+    assert_eq!(
+      WindowsInstallScope::from_exe_path(
+        Path::new("c:/users/Tauri/appdata/local/app/app.exe"),
+        local_app_data
+      ),
+      WindowsInstallScope::CurrentUser
+    );
+    assert_eq!(
+      WindowsInstallScope::from_exe_path(Path::new("C:/Program Files/app/app.exe"), local_app_data),
+      WindowsInstallScope::PerMachine
+    );
+    assert_eq!(
+      WindowsInstallScope::from_exe_path(
+        Path::new("C:/Users/tauri/AppData/LocalLow/app/app.exe"),
+        local_app_data
+      ),
+      WindowsInstallScope::PerMachine
+    );
+
+    assert_eq!(
+      WindowsInstallScope::CurrentUser.msiexec_args(),
+      &["ALLUSERS=2", "MSIINSTALLPERUSER=1"]
+    );
+    assert_eq!(WindowsInstallScope::PerMachine.nsis_args(), &["/AllUsers"]);
+  }
+}
@@ -0,0 +1,170 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Permission manifests declared by plugins.
+//!
+//! A plugin declares the permissions it requires in a [`PERMISSIONS_MANIFEST_FILE_NAME`] file
+//! next to its `Cargo.toml`. `tauri-build` collects the manifests of the app plugins
+//! and the codegen embeds them in the app context.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The name of the permissions manifest file of a plugin, relative to its `Cargo.toml`.
+pub const PERMISSIONS_MANIFEST_FILE_NAME: &str = "permissions.json";
+
+/// The name of the file, inside the `OUT_DIR` of the app crate, where `tauri-build` writes the [`PluginPermissions`].
+pub const PLUGIN_PERMISSIONS_FILE_NAME: &str = "tauri-plugin-permissions.json";
+
+/// The permission manifests of the app plugins, keyed by plugin name.
+pub type PluginPermissions = BTreeMap<String, PermissionManifest>;
+
+/// The permissions declared by a plugin.
+///
+/// ```json
+/// {
+///   "permissions": [
+///     { "identifier": "read", "commands": ["read_file"] },
+///     { "identifier": "write", "description": "Writes files", "commands": ["write_file"], "optional": true }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PermissionManifest {
+  /// The permissions of the plugin.
+  #[serde(default)]
+  pub permissions: Vec<Permission>,
+}
+
+impl PermissionManifest {
+  /// The optional permission that grants the given command, if any.
+  pub fn optional_permission(&self, command: &str) -> Option<&Permission> {
+    self
+      .permissions
+      .iter()
+      .find(|p| p.optional && p.commands.iter().any(|c| c == command))
+  }
+}
+
+/// A permission declared by a plugin.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Permission {
+  /// The permission identifier.
+  pub identifier: String,
+  /// A description of what the permission grants, shown to the user on consent prompts.
+  pub description: Option<String>,
+  /// The plugin commands granted by this permission.
+  #[serde(default)]
+  pub commands: Vec<String>,
+  /// Whether the permission must be granted by the user at runtime before its commands can be invoked.
+  #[serde(default)]
+  pub optional: bool,
+}
+
+/// Returns the permissions of `current` that are not declared in `previous`, as `(plugin, permission)` pairs.
+pub fn new_permissions<'a>(
+  previous: &PluginPermissions,
+  current: &'a PluginPermissions,
+) -> Vec<(&'a str, &'a Permission)> {
+  let mut new = Vec::new();
+  for (plugin, manifest) in current {
+    for permission in &manifest.permissions {
+      let declared = previous.get(plugin).map_or(false, |m| {
+        m.permissions
+          .iter()
+          .any(|p| p.identifier == permission.identifier && p.commands == permission.commands)
+      });
+      if !declared {
+        new.push((plugin.as_str(), permission));
+      }
+    }
+  }
+  new
+}
+
+#[cfg(feature = "build")]
+mod build {
+  use super::*;
+  use proc_macro2::TokenStream;
+  use quote::{quote, ToTokens, TokenStreamExt};
+
+  impl ToTokens for PermissionManifest {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let permissions = &self.permissions;
+      tokens.append_all(quote! {
+        ::tauri::utils::plugin::PermissionManifest {
+          permissions: vec![#(#permissions),*],
+        }
+      })
+    }
+  }
+
+  impl ToTokens for Permission {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let identifier = &self.identifier;
+      let description = match &self.description {
+        Some(description) => quote!(::core::option::Option::Some(#description.into())),
+        None => quote!(::core::option::Option::None),
+      };
+      let commands = &self.commands;
+      let optional = self.optional;
+      tokens.append_all(quote! {
+        ::tauri::utils::plugin::Permission {
+          identifier: #identifier.into(),
+          description: #description,
+          commands: vec![#(#commands.into()),*],
+          optional: #optional,
+        }
+      })
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn permission(identifier: &str, commands: &[&str]) -> Permission {
+    Permission {
+      identifier: identifier.into(),
+      commands: commands.iter().map(|c| c.to_string()).collect(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn detects_new_permissions() {
+    let mut previous = PluginPermissions::new();
+    previous.insert(
+      "fs".into(),
+      PermissionManifest {
+        permissions: vec![permission("read", &["read_file"])],
+      },
+    );
+
+    let mut current = previous.clone();
+    current.get_mut("fs").unwrap().permissions = vec![
+      permission("read", &["read_file", "read_dir"]),
+      permission("write", &["write_file"]),
+    ];
+    current.insert(
+      "shell".into(),
+      PermissionManifest {
+        permissions: vec![permission("execute", &["execute"])],
+      },
+    );
+
+    let new = new_permissions(&previous, &current)
+      .into_iter()
+      .map(|(plugin, p)| (plugin, p.identifier.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      new,
+      vec![("fs", "read"), ("fs", "write"), ("shell", "execute")]
+    );
+  }
+}
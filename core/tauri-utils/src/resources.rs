@@ -21,6 +21,30 @@ pub fn resource_relpath(path: &Path) -> PathBuf {
   dest
 }
 
+/// The extension appended to the resources compressed by the bundler with `tauri > bundle > compressResources`.
+pub const COMPRESSED_RESOURCE_EXTENSION: &str = "br";
+
+/// Compresses the resource file at `src` to `dest` with brotli.
+#[cfg(feature = "compression")]
+pub fn compress_resource(src: &Path, dest: &Path) -> std::io::Result<()> {
+  let params = brotli::enc::backward_references::BrotliEncoderParams {
+    quality: 9,
+    ..Default::default()
+  };
+  let mut input = std::fs::File::open(src)?;
+  let mut output = std::fs::File::create(dest)?;
+  brotli::BrotliCompress(&mut input, &mut output, &params)?;
+  Ok(())
+}
+
+/// Decompresses a resource compressed with [`compress_resource`] at `src` to `dest`.
+#[cfg(feature = "compression")]
+pub fn decompress_resource(src: &Path, dest: &Path) -> std::io::Result<()> {
+  let mut input = std::fs::File::open(src)?;
+  let mut output = std::fs::File::create(dest)?;
+  brotli::BrotliDecompress(&mut input, &mut output)
+}
+
 /// Parses the external binaries to bundle, adding the target triple suffix to each of them.
 pub fn external_binaries(external_binaries: &[String], target_triple: &str) -> Vec<String> {
   let mut paths = Vec::new();
@@ -0,0 +1,274 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Component, Path, PathBuf};
+
+pub use glob::{MatchOptions, Pattern};
+
+use crate::config::{ScopePrecedence, ScopeSymlinkPolicy};
+
+/// A glob pattern of a filesystem scope with its matching options.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScopePattern {
+  pattern: Pattern,
+  case_sensitive: Option<bool>,
+  max_depth: Option<usize>,
+}
+
+impl From<Pattern> for ScopePattern {
+  fn from(pattern: Pattern) -> Self {
+    Self {
+      pattern,
+      case_sensitive: None,
+      max_depth: None,
+    }
+  }
+}
+
+impl ScopePattern {
+  /// Overrides the case sensitivity of the scope [`MatchOptions`] for this pattern.
+  #[must_use]
+  pub fn case_sensitive(mut self, case_sensitive: Option<bool>) -> Self {
+    self.case_sensitive = case_sensitive;
+    self
+  }
+
+  /// Limits the number of path components matched after the literal prefix of the pattern,
+  /// e.g. `$HOME/docs/**` with a depth of `1` matches `$HOME/docs/file` but not `$HOME/docs/dir/file`.
+  #[must_use]
+  pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  /// The glob pattern.
+  pub fn pattern(&self) -> &Pattern {
+    &self.pattern
+  }
+
+  /// Number of leading path components of the pattern that don't contain wildcards.
+  fn literal_prefix_len(&self) -> usize {
+    Path::new(self.pattern.as_str())
+      .components()
+      .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+      .count()
+  }
+
+  /// Whether the given path matches this pattern.
+  pub fn matches(&self, path: &Path, options: MatchOptions) -> bool {
+    let options = MatchOptions {
+      case_sensitive: self.case_sensitive.unwrap_or(options.case_sensitive),
+      ..options
+    };
+    self.pattern.matches_path_with(path, options)
+      && self.max_depth.map_or(true, |max_depth| {
+        path
+          .components()
+          .count()
+          .saturating_sub(self.literal_prefix_len())
+          <= max_depth
+      })
+  }
+}
+
+/// How a scope decides whether a path is allowed.
+#[derive(Debug, Clone, Copy)]
+pub struct ScopePolicy {
+  /// The glob options used to match the patterns.
+  pub match_options: MatchOptions,
+  /// How conflicts between allowed and forbidden patterns are resolved.
+  pub precedence: ScopePrecedence,
+  /// How symbolic links are handled.
+  pub symlinks: ScopeSymlinkPolicy,
+}
+
+/// The result of checking a path against a scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+  /// The path matches the allowed pattern.
+  Allowed(String),
+  /// The path matches the forbidden pattern.
+  Forbidden(String),
+  /// The path doesn't match any allowed pattern.
+  NotAllowed,
+  /// The path goes through a symbolic link and the scope denies them.
+  Symlink(PathBuf),
+}
+
+impl Verdict {
+  /// Whether the path is allowed.
+  pub fn is_allowed(&self) -> bool {
+    matches!(self, Self::Allowed(_))
+  }
+}
+
+/// Returns the first symbolic link found on the path or its ancestors.
+fn find_symlink(path: &Path) -> Option<PathBuf> {
+  path
+    .ancestors()
+    .find(|p| {
+      p.symlink_metadata()
+        .map_or(false, |m| m.file_type().is_symlink())
+    })
+    .map(Path::to_path_buf)
+}
+
+/// Returns the matching pattern with the longest literal prefix.
+fn most_specific<'a>(
+  patterns: impl IntoIterator<Item = &'a ScopePattern>,
+  path: &Path,
+  options: MatchOptions,
+) -> Option<&'a ScopePattern> {
+  patterns
+    .into_iter()
+    .filter(|p| p.matches(path, options))
+    .max_by_key(|p| p.literal_prefix_len())
+}
+
+/// Checks the given path against the allowed and forbidden patterns of a scope.
+pub fn check<'a>(
+  path: &Path,
+  allowed: impl IntoIterator<Item = &'a ScopePattern>,
+  forbidden: impl IntoIterator<Item = &'a ScopePattern>,
+  policy: &ScopePolicy,
+) -> Verdict {
+  let path = match policy.symlinks {
+    ScopeSymlinkPolicy::Lexical => path.to_path_buf(),
+    ScopeSymlinkPolicy::Resolve | ScopeSymlinkPolicy::Deny => {
+      if let ScopeSymlinkPolicy::Deny = policy.symlinks {
+        if let Some(symlink) = find_symlink(path) {
+          return Verdict::Symlink(symlink);
+        }
+      }
+      if path.exists() {
+        match std::fs::canonicalize(path) {
+          Ok(path) => path,
+          Err(_) => return Verdict::NotAllowed,
+        }
+      } else {
+        path.to_path_buf()
+      }
+    }
+  };
+  // `..` is only resolved for existing paths and `**` matches it, so it could escape the allowed directories
+  if path.components().any(|c| c == Component::ParentDir) {
+    return Verdict::NotAllowed;
+  }
+  let path: PathBuf = path.components().collect();
+
+  let allowed = most_specific(allowed, &path, policy.match_options);
+  let forbidden = most_specific(forbidden, &path, policy.match_options);
+
+  match (allowed, forbidden) {
+    (Some(allowed), Some(forbidden))
+      if policy.precedence == ScopePrecedence::MostSpecific
+        && allowed.literal_prefix_len() > forbidden.literal_prefix_len() =>
+    {
+      Verdict::Allowed(allowed.pattern.as_str().into())
+    }
+    (_, Some(forbidden)) => Verdict::Forbidden(forbidden.pattern.as_str().into()),
+    (Some(allowed), None) => Verdict::Allowed(allowed.pattern.as_str().into()),
+    (None, None) => Verdict::NotAllowed,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pattern(p: &str) -> ScopePattern {
+    Pattern::new(p).unwrap().into()
+  }
+
+  fn policy(precedence: ScopePrecedence) -> ScopePolicy {
+    ScopePolicy {
+      match_options: MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+      },
+      precedence,
+      symlinks: ScopeSymlinkPolicy::Lexical,
+    }
+  }
+
+  #[test]
+  fn parent_dir() {
+    let allowed = [pattern("/home/tauri/**")];
+    let policy = policy(ScopePrecedence::Deny);
+    assert_eq!(
+      check(
+        Path::new("/home/tauri/../../etc/passwd"),
+        &allowed,
+        [],
+        &policy
+      ),
+      Verdict::NotAllowed
+    );
+    assert_eq!(
+      check(
+        Path::new("/home/tauri/does-not-exist/../../../etc/passwd"),
+        &allowed,
+        [],
+        &ScopePolicy {
+          symlinks: ScopeSymlinkPolicy::Resolve,
+          ..policy
+        }
+      ),
+      Verdict::NotAllowed
+    );
+  }
+
+  #[test]
+  fn max_depth() {
+    let allowed = [pattern("/home/tauri/**").max_depth(Some(1))];
+    let policy = policy(ScopePrecedence::Deny);
+    assert!(check(Path::new("/home/tauri/file"), &allowed, [], &policy).is_allowed());
+    assert_eq!(
+      check(Path::new("/home/tauri/dir/file"), &allowed, [], &policy),
+      Verdict::NotAllowed
+    );
+  }
+
+  #[test]
+  fn case_sensitivity() {
+    let allowed = [pattern("/home/tauri/*.txt").case_sensitive(Some(false))];
+    let policy = policy(ScopePrecedence::Deny);
+    assert!(check(Path::new("/home/tauri/FILE.TXT"), &allowed, [], &policy).is_allowed());
+    assert!(!check(
+      Path::new("/home/TAURI/file.txt"),
+      &[pattern("/home/tauri/*")],
+      [],
+      &policy
+    )
+    .is_allowed());
+  }
+
+  #[test]
+  fn precedence() {
+    let allowed = [pattern("/home/tauri/secret/public/**")];
+    let forbidden = [pattern("/home/tauri/secret/**")];
+    let path = Path::new("/home/tauri/secret/public/file");
+
+    assert_eq!(
+      check(path, &allowed, &forbidden, &policy(ScopePrecedence::Deny)),
+      Verdict::Forbidden("/home/tauri/secret/**".into())
+    );
+    assert_eq!(
+      check(
+        path,
+        &allowed,
+        &forbidden,
+        &policy(ScopePrecedence::MostSpecific)
+      ),
+      Verdict::Allowed("/home/tauri/secret/public/**".into())
+    );
+    assert!(!check(
+      Path::new("/home/tauri/secret/file"),
+      &allowed,
+      &forbidden,
+      &policy(ScopePrecedence::MostSpecific)
+    )
+    .is_allowed());
+  }
+}
@@ -57,6 +57,18 @@ fn main() {
   )
   .expect("failed to write checked_features file");
 
+  // the key signing the persisted scopes, embedded in the app so it isn't stored next to them
+  if has_feature("protocol-asset") {
+    println!("cargo:rerun-if-env-changed=TAURI_SCOPE_STORE_KEY");
+    let scope_store_key_path = Path::new(&var("OUT_DIR").unwrap()).join("scope-store-key");
+    if let Ok(key) = var("TAURI_SCOPE_STORE_KEY") {
+      write(&scope_store_key_path, key).expect("failed to write the scope store key");
+    } else if !scope_store_key_path.exists() {
+      write(&scope_store_key_path, rand::random::<[u8; 32]>())
+        .expect("failed to write the scope store key");
+    }
+  }
+
   // workaround needed to prevent `STATUS_ENTRYPOINT_NOT_FOUND` error
   // see https://github.com/tauri-apps/tauri/pull/4383#issuecomment-1212221864
   let target_env = std::env::var("CARGO_CFG_TARGET_ENV");
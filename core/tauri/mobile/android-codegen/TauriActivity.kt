@@ -18,4 +18,29 @@ abstract class TauriActivity : WryActivity() {
       pluginManager.onNewIntent(intent)
     }
   }
+
+  override fun onStart() {
+    super.onStart()
+    pluginManager.onStart()
+  }
+
+  override fun onResume() {
+    super.onResume()
+    pluginManager.onResume()
+  }
+
+  override fun onPause() {
+    super.onPause()
+    pluginManager.onPause()
+  }
+
+  override fun onStop() {
+    super.onStop()
+    pluginManager.onStop()
+  }
+
+  override fun onTrimMemory(level: Int) {
+    super.onTrimMemory(level)
+    pluginManager.onTrimMemory(level)
+  }
 }
@@ -38,6 +38,12 @@ dependencies {
     implementation("androidx.core:core-ktx:1.7.0")
     implementation("androidx.appcompat:appcompat:1.6.0")
     implementation("com.google.android.material:material:1.7.0")
+    // provided by the app when the push notifications are enabled
+    compileOnly("com.google.firebase:firebase-messaging:23.1.2")
+    // provided by the app when the in-app purchases are enabled
+    compileOnly("com.android.billingclient:billing:5.2.0")
+    // provided by the app when background tasks are declared
+    compileOnly("androidx.work:work-runtime-ktx:2.8.1")
     testImplementation("junit:junit:4.13.2")
     androidTestImplementation("androidx.test.ext:junit:1.1.5")
     androidTestImplementation("androidx.test.espresso:espresso-core:3.5.1")
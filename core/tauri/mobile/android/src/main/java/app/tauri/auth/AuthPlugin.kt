@@ -0,0 +1,74 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.auth
+
+import android.annotation.SuppressLint
+import android.app.Activity
+import android.hardware.biometrics.BiometricManager
+import android.hardware.biometrics.BiometricPrompt
+import android.os.Build
+import android.os.CancellationSignal
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+
+@TauriPlugin
+class AuthPlugin(private val activity: Activity): Plugin(activity) {
+  /**
+   * Prompts the user for their biometrics or their device credential.
+   */
+  @SuppressLint("NewApi")
+  @Command
+  fun authenticate(invoke: Invoke) {
+    val reason = invoke.getString("reason") ?: return invoke.reject("Missing `reason`")
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.P) {
+      invoke.reject("The BiometricPrompt requires Android 9", "unavailable")
+      return
+    }
+    val builder = BiometricPrompt.Builder(activity)
+      .setTitle(activity.applicationInfo.loadLabel(activity.packageManager))
+      .setSubtitle(reason)
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
+      builder.setAllowedAuthenticators(
+        BiometricManager.Authenticators.BIOMETRIC_STRONG or BiometricManager.Authenticators.DEVICE_CREDENTIAL
+      )
+    } else if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
+      @Suppress("DEPRECATION")
+      builder.setDeviceCredentialAllowed(true)
+    } else {
+      // Android 9 only accepts the biometrics, the prompt needs a button to cancel it
+      builder.setNegativeButton(activity.getString(android.R.string.cancel), activity.mainExecutor) { _, _ ->
+        invoke.reject("The user cancelled the authentication", "canceled")
+      }
+    }
+    val prompt = builder.build()
+    activity.runOnUiThread {
+      prompt.authenticate(
+        CancellationSignal(),
+        activity.mainExecutor,
+        object : BiometricPrompt.AuthenticationCallback() {
+          override fun onAuthenticationSucceeded(result: BiometricPrompt.AuthenticationResult) {
+            invoke.resolve(JSObject())
+          }
+
+          override fun onAuthenticationError(errorCode: Int, errString: CharSequence) {
+            val code = when (errorCode) {
+              BiometricPrompt.BIOMETRIC_ERROR_CANCELED,
+              BiometricPrompt.BIOMETRIC_ERROR_USER_CANCELED -> "canceled"
+              BiometricPrompt.BIOMETRIC_ERROR_HW_NOT_PRESENT,
+              BiometricPrompt.BIOMETRIC_ERROR_HW_UNAVAILABLE,
+              BiometricPrompt.BIOMETRIC_ERROR_NO_BIOMETRICS,
+              BiometricPrompt.BIOMETRIC_ERROR_NO_DEVICE_CREDENTIAL -> "unavailable"
+              else -> null
+            }
+            invoke.reject(errString.toString(), code)
+          }
+        }
+      )
+    }
+  }
+}
@@ -0,0 +1,36 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.backgroundtasks
+
+import android.content.Context
+import androidx.work.Worker
+import androidx.work.WorkerParameters
+import java.util.concurrent.CountDownLatch
+import java.util.concurrent.TimeUnit
+
+class BackgroundTaskWorker(context: Context, params: WorkerParameters): Worker(context, params) {
+  override fun doWork(): Result {
+    val id = inputData.getString(ID) ?: return Result.failure()
+    // the handlers are registered by the Tauri app, which is not running when the process was started for this work
+    val plugin = BackgroundTasksPlugin.instance ?: return Result.success()
+
+    val latch = CountDownLatch(1)
+    var success = false
+    val runId = plugin.run(id) {
+      success = it
+      latch.countDown()
+    }
+    // WorkManager stops the work after 10 minutes
+    if (!latch.await(9, TimeUnit.MINUTES)) {
+      plugin.cancelRun(runId)
+      return Result.retry()
+    }
+    return if (success) Result.success() else Result.retry()
+  }
+
+  companion object {
+    internal const val ID = "id"
+  }
+}
@@ -0,0 +1,96 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.backgroundtasks
+
+import android.app.Activity
+import android.webkit.WebView
+import androidx.work.Constraints
+import androidx.work.ExistingPeriodicWorkPolicy
+import androidx.work.NetworkType
+import androidx.work.PeriodicWorkRequestBuilder
+import androidx.work.WorkManager
+import androidx.work.workDataOf
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.TimeUnit
+import java.util.concurrent.atomic.AtomicInteger
+
+@TauriPlugin
+class BackgroundTasksPlugin(private val activity: Activity): Plugin(activity) {
+  private val nextRunId = AtomicInteger()
+  // the runs waiting for the Rust handler to complete them
+  private val completions = ConcurrentHashMap<Int, (Boolean) -> Unit>()
+
+  override fun load(webView: WebView) {
+    instance = this
+  }
+
+  @Command
+  fun schedule(invoke: Invoke) {
+    val id = invoke.getString("id")
+    if (id == null) {
+      invoke.reject("id is required")
+      return
+    }
+    val constraints = Constraints.Builder()
+      .setRequiredNetworkType(if (invoke.getBoolean("requiresNetwork", false)) NetworkType.CONNECTED else NetworkType.NOT_REQUIRED)
+      .setRequiresCharging(invoke.getBoolean("requiresCharging", false))
+      .build()
+    // WorkManager clamps the interval to 15 minutes
+    val request = PeriodicWorkRequestBuilder<BackgroundTaskWorker>(invoke.getLong("intervalSecs", 0), TimeUnit.SECONDS)
+      .setConstraints(constraints)
+      .setInputData(workDataOf(BackgroundTaskWorker.ID to id))
+      .build()
+    WorkManager.getInstance(activity).enqueueUniquePeriodicWork(id, ExistingPeriodicWorkPolicy.UPDATE, request)
+    invoke.resolve()
+  }
+
+  @Command
+  fun cancel(invoke: Invoke) {
+    val id = invoke.getString("id")
+    if (id == null) {
+      invoke.reject("id is required")
+      return
+    }
+    WorkManager.getInstance(activity).cancelUniqueWork(id)
+    invoke.resolve()
+  }
+
+  @Command
+  fun complete(invoke: Invoke) {
+    val runId = invoke.getInt("runId")
+    if (runId != null) {
+      completions.remove(runId)?.invoke(invoke.getBoolean("success", false))
+    }
+    invoke.resolve()
+  }
+
+  /**
+   * Runs the handler of the task registered in Rust, calling [onComplete] once the handler completes the run.
+   */
+  internal fun run(id: String, onComplete: (Boolean) -> Unit): Int {
+    val runId = nextRunId.incrementAndGet()
+    completions[runId] = onComplete
+    handleBackgroundTask(id, runId)
+    return runId
+  }
+
+  internal fun cancelRun(runId: Int) {
+    completions.remove(runId)
+  }
+
+  private external fun handleBackgroundTask(id: String, runId: Int)
+
+  companion object {
+    /**
+     * The loaded plugin, running the tasks of [BackgroundTaskWorker].
+     */
+    @Volatile
+    internal var instance: BackgroundTasksPlugin? = null
+  }
+}
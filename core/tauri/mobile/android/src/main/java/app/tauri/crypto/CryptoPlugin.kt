@@ -0,0 +1,253 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.crypto
+
+import android.annotation.SuppressLint
+import android.annotation.TargetApi
+import android.app.Activity
+import android.content.Context
+import android.hardware.biometrics.BiometricPrompt
+import android.os.Build
+import android.os.CancellationSignal
+import android.security.keystore.KeyGenParameterSpec
+import android.security.keystore.KeyInfo
+import android.security.keystore.KeyProperties
+import android.security.keystore.StrongBoxUnavailableException
+import android.util.Base64
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import java.security.KeyFactory
+import java.security.KeyPairGenerator
+import java.security.KeyStore
+import java.security.PrivateKey
+import java.security.Signature
+import java.security.spec.ECGenParameterSpec
+import javax.crypto.AEADBadTagException
+import javax.crypto.Cipher
+import javax.crypto.KeyGenerator
+import javax.crypto.SecretKey
+import javax.crypto.spec.GCMParameterSpec
+
+private const val ANDROID_KEYSTORE = "AndroidKeyStore"
+// the prefix of the signing key aliases, so they cannot collide with the secrets key
+private const val KEY_ALIAS_PREFIX = "app.tauri.crypto.key."
+private const val SECRETS_KEY_ALIAS = "app.tauri.crypto.secrets"
+private const val SECRETS_PREFERENCES = "app.tauri.crypto.secrets"
+private const val GCM_IV_LENGTH = 12
+private const val GCM_TAG_LENGTH = 128
+
+@TauriPlugin
+class CryptoPlugin(private val activity: Activity): Plugin(activity) {
+  private val preferences = activity.getSharedPreferences(SECRETS_PREFERENCES, Context.MODE_PRIVATE)
+
+  private fun keyStore(): KeyStore {
+    val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
+    keyStore.load(null)
+    return keyStore
+  }
+
+  private fun resolveData(invoke: Invoke, data: ByteArray?) {
+    val ret = JSObject()
+    ret.put("data", data?.let { Base64.encodeToString(it, Base64.NO_WRAP) })
+    invoke.resolve(ret)
+  }
+
+  /**
+   * Runs the command, rejecting it when the Android Keystore is not available or fails.
+   */
+  private fun withKeystore(invoke: Invoke, f: () -> Unit) {
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.M) {
+      invoke.reject("The Android Keystore requires Android 6.0")
+      return
+    }
+    try {
+      f()
+    } catch (ex: Exception) {
+      invoke.reject(ex.message ?: ex.toString(), ex)
+    }
+  }
+
+  @Command
+  fun generateKey(invoke: Invoke) {
+    val alias = invoke.getString("alias") ?: return invoke.reject("Missing `alias`")
+    val hardwareBacked = invoke.getBoolean("hardwareBacked", false)
+    val requireUserAuthentication = invoke.getBoolean("requireUserAuthentication", false)
+    withKeystore(invoke) {
+      if (requireUserAuthentication && Build.VERSION.SDK_INT < Build.VERSION_CODES.P) {
+        invoke.reject("User authentication requires Android 9")
+        return@withKeystore
+      }
+      val publicKey = try {
+        generateKeyPair(alias, requireUserAuthentication, hardwareBacked)
+      } catch (ex: Exception) {
+        if (hardwareBacked && Build.VERSION.SDK_INT >= Build.VERSION_CODES.P && ex is StrongBoxUnavailableException) {
+          generateKeyPair(alias, requireUserAuthentication, false)
+        } else {
+          throw ex
+        }
+      }
+      resolveData(invoke, publicKey)
+    }
+  }
+
+  @TargetApi(Build.VERSION_CODES.M)
+  private fun generateKeyPair(alias: String, requireUserAuthentication: Boolean, strongBox: Boolean): ByteArray {
+    val builder = KeyGenParameterSpec.Builder(KEY_ALIAS_PREFIX + alias, KeyProperties.PURPOSE_SIGN)
+      .setAlgorithmParameterSpec(ECGenParameterSpec("secp256r1"))
+      .setDigests(KeyProperties.DIGEST_SHA256)
+      .setUserAuthenticationRequired(requireUserAuthentication)
+    if (strongBox && Build.VERSION.SDK_INT >= Build.VERSION_CODES.P) {
+      builder.setIsStrongBoxBacked(true)
+    }
+    val generator = KeyPairGenerator.getInstance(KeyProperties.KEY_ALGORITHM_EC, ANDROID_KEYSTORE)
+    generator.initialize(builder.build())
+    // the X.509 encoding of the public key is the DER-encoded SubjectPublicKeyInfo
+    return generator.generateKeyPair().public.encoded
+  }
+
+  @Command
+  fun publicKey(invoke: Invoke) {
+    val alias = invoke.getString("alias") ?: return invoke.reject("Missing `alias`")
+    withKeystore(invoke) {
+      resolveData(invoke, keyStore().getCertificate(KEY_ALIAS_PREFIX + alias)?.publicKey?.encoded)
+    }
+  }
+
+  @Command
+  fun sign(invoke: Invoke) {
+    val alias = invoke.getString("alias") ?: return invoke.reject("Missing `alias`")
+    val data = Base64.decode(invoke.getString("data") ?: return invoke.reject("Missing `data`"), Base64.NO_WRAP)
+    withKeystore(invoke) {
+      val key = keyStore().getKey(KEY_ALIAS_PREFIX + alias, null) as PrivateKey?
+      if (key == null) {
+        resolveData(invoke, null)
+        return@withKeystore
+      }
+      val signature = Signature.getInstance("SHA256withECDSA")
+      signature.initSign(key)
+      if (requiresUserAuthentication(key)) {
+        authenticate(invoke, signature, data)
+      } else {
+        signature.update(data)
+        resolveData(invoke, signature.sign())
+      }
+    }
+  }
+
+  @TargetApi(Build.VERSION_CODES.M)
+  private fun requiresUserAuthentication(key: PrivateKey): Boolean {
+    val factory = KeyFactory.getInstance(key.algorithm, ANDROID_KEYSTORE)
+    return factory.getKeySpec(key, KeyInfo::class.java).isUserAuthenticationRequired
+  }
+
+  /**
+   * Unlocks the signature with the biometrics of the user, the keys requiring it can only be used once per authentication.
+   */
+  @SuppressLint("NewApi")
+  private fun authenticate(invoke: Invoke, signature: Signature, data: ByteArray) {
+    val executor = activity.mainExecutor
+    val prompt = BiometricPrompt.Builder(activity)
+      .setTitle(activity.applicationInfo.loadLabel(activity.packageManager))
+      .setNegativeButton(activity.getString(android.R.string.cancel), executor) { _, _ ->
+        invoke.reject("The user cancelled the authentication")
+      }
+      .build()
+    activity.runOnUiThread {
+      prompt.authenticate(
+        BiometricPrompt.CryptoObject(signature),
+        CancellationSignal(),
+        executor,
+        object : BiometricPrompt.AuthenticationCallback() {
+          override fun onAuthenticationSucceeded(result: BiometricPrompt.AuthenticationResult) {
+            try {
+              val authenticated = result.cryptoObject.signature
+              authenticated.update(data)
+              resolveData(invoke, authenticated.sign())
+            } catch (ex: Exception) {
+              invoke.reject(ex.message ?: ex.toString(), ex)
+            }
+          }
+
+          override fun onAuthenticationError(errorCode: Int, errString: CharSequence) {
+            invoke.reject(errString.toString())
+          }
+        }
+      )
+    }
+  }
+
+  @Command
+  fun deleteKey(invoke: Invoke) {
+    val alias = invoke.getString("alias") ?: return invoke.reject("Missing `alias`")
+    withKeystore(invoke) {
+      keyStore().deleteEntry(KEY_ALIAS_PREFIX + alias)
+      resolveData(invoke, null)
+    }
+  }
+
+  @TargetApi(Build.VERSION_CODES.M)
+  private fun secretsKey(): SecretKey {
+    (keyStore().getKey(SECRETS_KEY_ALIAS, null) as SecretKey?)?.let { return it }
+    val generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES, ANDROID_KEYSTORE)
+    generator.init(
+      KeyGenParameterSpec.Builder(SECRETS_KEY_ALIAS, KeyProperties.PURPOSE_ENCRYPT or KeyProperties.PURPOSE_DECRYPT)
+        .setBlockModes(KeyProperties.BLOCK_MODE_GCM)
+        .setEncryptionPaddings(KeyProperties.ENCRYPTION_PADDING_NONE)
+        .setKeySize(256)
+        .build()
+    )
+    return generator.generateKey()
+  }
+
+  @Command
+  fun setSecret(invoke: Invoke) {
+    val key = invoke.getString("key") ?: return invoke.reject("Missing `key`")
+    val value = Base64.decode(invoke.getString("value") ?: return invoke.reject("Missing `value`"), Base64.NO_WRAP)
+    withKeystore(invoke) {
+      val cipher = Cipher.getInstance("AES/GCM/NoPadding")
+      cipher.init(Cipher.ENCRYPT_MODE, secretsKey())
+      // the random IV generated by the keystore is stored in front of the ciphertext
+      val encrypted = cipher.iv + cipher.doFinal(value)
+      preferences.edit().putString(key, Base64.encodeToString(encrypted, Base64.NO_WRAP)).apply()
+      resolveData(invoke, null)
+    }
+  }
+
+  @Command
+  fun getSecret(invoke: Invoke) {
+    val key = invoke.getString("key") ?: return invoke.reject("Missing `key`")
+    withKeystore(invoke) {
+      val encrypted = preferences.getString(key, null)?.let { Base64.decode(it, Base64.NO_WRAP) }
+      if (encrypted == null) {
+        resolveData(invoke, null)
+        return@withKeystore
+      }
+      val cipher = Cipher.getInstance("AES/GCM/NoPadding")
+      cipher.init(
+        Cipher.DECRYPT_MODE,
+        secretsKey(),
+        GCMParameterSpec(GCM_TAG_LENGTH, encrypted, 0, GCM_IV_LENGTH)
+      )
+      val value = try {
+        cipher.doFinal(encrypted, GCM_IV_LENGTH, encrypted.size - GCM_IV_LENGTH)
+      } catch (ex: AEADBadTagException) {
+        // the secret was restored from the backup of another device, whose key never left its keystore
+        preferences.edit().remove(key).apply()
+        null
+      }
+      resolveData(invoke, value)
+    }
+  }
+
+  @Command
+  fun deleteSecret(invoke: Invoke) {
+    val key = invoke.getString("key") ?: return invoke.reject("Missing `key`")
+    preferences.edit().remove(key).apply()
+    resolveData(invoke, null)
+  }
+}
@@ -0,0 +1,39 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.haptics
+
+import android.app.Activity
+import android.os.Build
+import android.view.HapticFeedbackConstants
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+
+@TauriPlugin
+class HapticsPlugin(private val activity: Activity): Plugin(activity) {
+  @Command
+  fun perform(invoke: Invoke) {
+    val feedback = when (invoke.getString("type")) {
+      "impact" -> when (invoke.getString("style")) {
+        "light" -> HapticFeedbackConstants.CLOCK_TICK
+        "heavy" -> HapticFeedbackConstants.LONG_PRESS
+        else -> HapticFeedbackConstants.VIRTUAL_KEY
+      }
+      "selection" -> HapticFeedbackConstants.CLOCK_TICK
+      "notification" -> when (invoke.getString("feedback")) {
+        "success" -> if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) HapticFeedbackConstants.CONFIRM else HapticFeedbackConstants.VIRTUAL_KEY
+        "error" -> if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) HapticFeedbackConstants.REJECT else HapticFeedbackConstants.LONG_PRESS
+        else -> HapticFeedbackConstants.LONG_PRESS
+      }
+      else -> return invoke.reject("Unknown feedback type")
+    }
+    // the feedback is played by the views, on the main thread
+    activity.runOnUiThread {
+      activity.window.decorView.performHapticFeedback(feedback)
+    }
+    invoke.resolve()
+  }
+}
@@ -0,0 +1,124 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.keyring
+
+import android.annotation.TargetApi
+import android.app.Activity
+import android.content.Context
+import android.os.Build
+import android.security.keystore.KeyGenParameterSpec
+import android.security.keystore.KeyProperties
+import android.util.Base64
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import java.security.KeyStore
+import javax.crypto.AEADBadTagException
+import javax.crypto.Cipher
+import javax.crypto.KeyGenerator
+import javax.crypto.SecretKey
+import javax.crypto.spec.GCMParameterSpec
+
+private const val ANDROID_KEYSTORE = "AndroidKeyStore"
+private const val KEY_ALIAS = "app.tauri.keyring"
+private const val PREFERENCES = "app.tauri.keyring"
+private const val GCM_IV_LENGTH = 12
+private const val GCM_TAG_LENGTH = 128
+
+@TauriPlugin
+class KeyringPlugin(private val activity: Activity): Plugin(activity) {
+  private val preferences = activity.getSharedPreferences(PREFERENCES, Context.MODE_PRIVATE)
+
+  private fun resolvePassword(invoke: Invoke, password: String?) {
+    val ret = JSObject()
+    ret.put("password", password)
+    invoke.resolve(ret)
+  }
+
+  /**
+   * The preferences key of the entry, the service cannot contain the separator since it is prefixed by the app identifier.
+   */
+  private fun entryKey(invoke: Invoke): String? {
+    val service = invoke.getString("service") ?: return null
+    val account = invoke.getString("account") ?: return null
+    return "$service/$account"
+  }
+
+  /**
+   * Runs the command, rejecting it when the Android Keystore is not available or fails.
+   */
+  private fun withKeystore(invoke: Invoke, f: () -> Unit) {
+    if (Build.VERSION.SDK_INT < Build.VERSION_CODES.M) {
+      invoke.reject("The Android Keystore requires Android 6.0")
+      return
+    }
+    try {
+      f()
+    } catch (ex: Exception) {
+      invoke.reject(ex.message ?: ex.toString(), ex)
+    }
+  }
+
+  @TargetApi(Build.VERSION_CODES.M)
+  private fun key(): SecretKey {
+    val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
+    keyStore.load(null)
+    (keyStore.getKey(KEY_ALIAS, null) as SecretKey?)?.let { return it }
+    val generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES, ANDROID_KEYSTORE)
+    generator.init(
+      KeyGenParameterSpec.Builder(KEY_ALIAS, KeyProperties.PURPOSE_ENCRYPT or KeyProperties.PURPOSE_DECRYPT)
+        .setBlockModes(KeyProperties.BLOCK_MODE_GCM)
+        .setEncryptionPaddings(KeyProperties.ENCRYPTION_PADDING_NONE)
+        .setKeySize(256)
+        .build()
+    )
+    return generator.generateKey()
+  }
+
+  @Command
+  fun setPassword(invoke: Invoke) {
+    val entry = entryKey(invoke) ?: return invoke.reject("Missing `service` or `account`")
+    val password = invoke.getString("password") ?: return invoke.reject("Missing `password`")
+    withKeystore(invoke) {
+      val cipher = Cipher.getInstance("AES/GCM/NoPadding")
+      cipher.init(Cipher.ENCRYPT_MODE, key())
+      // the random IV generated by the keystore is stored in front of the ciphertext
+      val encrypted = cipher.iv + cipher.doFinal(password.toByteArray(Charsets.UTF_8))
+      preferences.edit().putString(entry, Base64.encodeToString(encrypted, Base64.NO_WRAP)).apply()
+      resolvePassword(invoke, null)
+    }
+  }
+
+  @Command
+  fun getPassword(invoke: Invoke) {
+    val entry = entryKey(invoke) ?: return invoke.reject("Missing `service` or `account`")
+    withKeystore(invoke) {
+      val encrypted = preferences.getString(entry, null)?.let { Base64.decode(it, Base64.NO_WRAP) }
+      if (encrypted == null) {
+        resolvePassword(invoke, null)
+        return@withKeystore
+      }
+      val cipher = Cipher.getInstance("AES/GCM/NoPadding")
+      cipher.init(Cipher.DECRYPT_MODE, key(), GCMParameterSpec(GCM_TAG_LENGTH, encrypted, 0, GCM_IV_LENGTH))
+      val password = try {
+        String(cipher.doFinal(encrypted, GCM_IV_LENGTH, encrypted.size - GCM_IV_LENGTH), Charsets.UTF_8)
+      } catch (ex: AEADBadTagException) {
+        // the password was restored from the backup of another device, whose key never left its keystore
+        preferences.edit().remove(entry).apply()
+        null
+      }
+      resolvePassword(invoke, password)
+    }
+  }
+
+  @Command
+  fun deletePassword(invoke: Invoke) {
+    val entry = entryKey(invoke) ?: return invoke.reject("Missing `service` or `account`")
+    preferences.edit().remove(entry).apply()
+    resolvePassword(invoke, null)
+  }
+}
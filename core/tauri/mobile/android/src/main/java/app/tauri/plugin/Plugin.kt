@@ -37,6 +37,16 @@ abstract class Plugin(private val activity: Activity) {
    */
   open fun onNewIntent(intent: Intent) {}
 
+  /**
+   * Handle the activity being paused, e.g. when the app moves to the background
+   */
+  open fun onPause() {}
+
+  /**
+   * Handle the activity being resumed
+   */
+  open fun onResume() {}
+
   /**
    * Start activity for result with the provided Intent and resolve calling the provided callback method name.
    *
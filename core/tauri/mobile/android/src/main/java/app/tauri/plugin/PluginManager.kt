@@ -54,6 +54,32 @@ class PluginManager(val activity: AppCompatActivity) {
     }
   }
 
+  fun onStart() {
+    handleLifecycleEvent(LIFECYCLE_FOREGROUND)
+  }
+
+  fun onResume() {
+    for (plugin in plugins.values) {
+      plugin.instance.onResume()
+    }
+    handleLifecycleEvent(LIFECYCLE_ACTIVE)
+  }
+
+  fun onPause() {
+    for (plugin in plugins.values) {
+      plugin.instance.onPause()
+    }
+    handleLifecycleEvent(LIFECYCLE_INACTIVE)
+  }
+
+  fun onStop() {
+    handleLifecycleEvent(LIFECYCLE_BACKGROUND)
+  }
+
+  fun onTrimMemory(level: Int) {
+    handleTrimMemory(level)
+  }
+
   fun startActivityForResult(intent: Intent, callback: ActivityResultCallback) {
     startActivityForResultCallback = callback
     startActivityForResultLauncher.launch(intent)
@@ -131,6 +157,12 @@ class PluginManager(val activity: AppCompatActivity) {
   }
 
   companion object {
+    // the lifecycle event codes shared with the Rust code
+    private const val LIFECYCLE_FOREGROUND = 0
+    private const val LIFECYCLE_ACTIVE = 1
+    private const val LIFECYCLE_INACTIVE = 2
+    private const val LIFECYCLE_BACKGROUND = 3
+
     fun loadConfig(context: Context, plugin: String): JSObject {
       val tauriConfigJson = FsUtils.readAsset(context.assets, "tauri.conf.json")
       val tauriConfig = JSObject(tauriConfigJson)
@@ -140,4 +172,6 @@ class PluginManager(val activity: AppCompatActivity) {
   }
 
   private external fun handlePluginResponse(id: Int, success: String?, error: String?)
+  private external fun handleLifecycleEvent(event: Int)
+  private external fun handleTrimMemory(level: Int)
 }
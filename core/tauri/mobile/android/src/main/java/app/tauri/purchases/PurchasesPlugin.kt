@@ -0,0 +1,244 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.purchases
+
+import android.app.Activity
+import android.webkit.WebView
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import com.android.billingclient.api.AcknowledgePurchaseParams
+import com.android.billingclient.api.BillingClient
+import com.android.billingclient.api.BillingClientStateListener
+import com.android.billingclient.api.BillingFlowParams
+import com.android.billingclient.api.BillingResult
+import com.android.billingclient.api.ConsumeParams
+import com.android.billingclient.api.ProductDetails
+import com.android.billingclient.api.Purchase
+import com.android.billingclient.api.PurchasesUpdatedListener
+import com.android.billingclient.api.QueryProductDetailsParams
+import com.android.billingclient.api.QueryPurchasesParams
+
+@TauriPlugin
+class PurchasesPlugin(private val activity: Activity): Plugin(activity), PurchasesUpdatedListener {
+  private lateinit var client: BillingClient
+  // the product details must be queried before launching the purchase flow
+  private val products = HashMap<String, ProductDetails>()
+  // the purchase flow waiting for its result, the other updates are forwarded to Rust
+  private var pendingPurchase: Invoke? = null
+
+  override fun load(webView: WebView) {
+    client = BillingClient.newBuilder(activity)
+      .setListener(this)
+      .enablePendingPurchases()
+      .build()
+  }
+
+  /**
+   * Runs [block] once connected to Google Play, reconnecting if the service was disconnected.
+   */
+  private fun withClient(invoke: Invoke, block: () -> Unit) {
+    if (client.isReady) {
+      block()
+      return
+    }
+    client.startConnection(object : BillingClientStateListener {
+      override fun onBillingSetupFinished(result: BillingResult) {
+        if (result.responseCode == BillingClient.BillingResponseCode.OK) {
+          block()
+        } else {
+          invoke.reject("Failed to connect to Google Play: ${result.debugMessage}")
+        }
+      }
+
+      override fun onBillingServiceDisconnected() {}
+    })
+  }
+
+  @Command
+  fun getProducts(invoke: Invoke) {
+    val ids = invoke.getArray("ids", JSArray()).toList<String>()
+    val type = productType(invoke)
+    withClient(invoke) {
+      val params = QueryProductDetailsParams.newBuilder()
+        .setProductList(ids.map {
+          QueryProductDetailsParams.Product.newBuilder()
+            .setProductId(it)
+            .setProductType(type)
+            .build()
+        })
+        .build()
+      client.queryProductDetailsAsync(params) { result, details ->
+        if (result.responseCode != BillingClient.BillingResponseCode.OK) {
+          invoke.reject("Failed to query the products: ${result.debugMessage}")
+          return@queryProductDetailsAsync
+        }
+        val array = JSArray()
+        for (product in details) {
+          products[product.productId] = product
+          productToJson(product)?.let { array.put(it) }
+        }
+        val ret = JSObject()
+        ret.put("products", array)
+        invoke.resolve(ret)
+      }
+    }
+  }
+
+  @Command
+  fun purchase(invoke: Invoke) {
+    val productId = invoke.getString("productId")
+    val product = products[productId]
+    if (product == null) {
+      invoke.reject("Product $productId not found, query it with getProducts first")
+      return
+    }
+    if (pendingPurchase != null) {
+      invoke.reject("A purchase is already in progress")
+      return
+    }
+    val productParams = BillingFlowParams.ProductDetailsParams.newBuilder().setProductDetails(product)
+    product.subscriptionOfferDetails?.firstOrNull()?.let { productParams.setOfferToken(it.offerToken) }
+    val params = BillingFlowParams.newBuilder()
+      .setProductDetailsParamsList(listOf(productParams.build()))
+      .build()
+    withClient(invoke) {
+      pendingPurchase = invoke
+      val result = client.launchBillingFlow(activity, params)
+      if (result.responseCode != BillingClient.BillingResponseCode.OK) {
+        pendingPurchase = null
+        invoke.reject("Failed to launch the purchase flow: ${result.debugMessage}")
+      }
+    }
+  }
+
+  override fun onPurchasesUpdated(result: BillingResult, purchases: MutableList<Purchase>?) {
+    val invoke = pendingPurchase
+    pendingPurchase = null
+    when (result.responseCode) {
+      BillingClient.BillingResponseCode.OK -> {
+        val updates = purchases.orEmpty().mapNotNull { purchaseToJson(it) }
+        if (invoke != null) {
+          val ret = JSObject()
+          ret.put("purchase", updates.firstOrNull())
+          invoke.resolve(ret)
+        }
+        for (update in if (invoke != null) updates.drop(1) else updates) {
+          handlePurchaseEvent(update.toString())
+        }
+      }
+      // resolved without a purchase
+      BillingClient.BillingResponseCode.USER_CANCELED -> invoke?.resolve(JSObject())
+      else -> invoke?.reject("Purchase failed: ${result.debugMessage}")
+    }
+  }
+
+  @Command
+  fun finish(invoke: Invoke) {
+    val token = invoke.getString("token")
+    if (token == null) {
+      invoke.reject("token is required")
+      return
+    }
+    val consume = invoke.getBoolean("consume", false)
+    withClient(invoke) {
+      val onResult = { result: BillingResult ->
+        if (result.responseCode == BillingClient.BillingResponseCode.OK) {
+          invoke.resolve()
+        } else {
+          invoke.reject("Failed to finish the purchase: ${result.debugMessage}")
+        }
+      }
+      if (consume) {
+        val params = ConsumeParams.newBuilder().setPurchaseToken(token).build()
+        client.consumeAsync(params) { result, _ -> onResult(result) }
+      } else {
+        val params = AcknowledgePurchaseParams.newBuilder().setPurchaseToken(token).build()
+        client.acknowledgePurchase(params) { result -> onResult(result) }
+      }
+    }
+  }
+
+  @Command
+  fun restore(invoke: Invoke) {
+    withClient(invoke) {
+      val array = JSArray()
+      queryPurchases(invoke, BillingClient.ProductType.INAPP, array) {
+        queryPurchases(invoke, BillingClient.ProductType.SUBS, array) {
+          val ret = JSObject()
+          ret.put("purchases", array)
+          invoke.resolve(ret)
+        }
+      }
+    }
+  }
+
+  private fun queryPurchases(invoke: Invoke, type: String, array: JSArray, then: () -> Unit) {
+    val params = QueryPurchasesParams.newBuilder().setProductType(type).build()
+    client.queryPurchasesAsync(params) { result, purchases ->
+      if (result.responseCode != BillingClient.BillingResponseCode.OK) {
+        invoke.reject("Failed to query the purchases: ${result.debugMessage}")
+        return@queryPurchasesAsync
+      }
+      for (purchase in purchases) {
+        purchaseToJson(purchase)?.let { array.put(it) }
+      }
+      then()
+    }
+  }
+
+  private fun productType(invoke: Invoke): String {
+    return if (invoke.getString("kind") == "subscription") {
+      BillingClient.ProductType.SUBS
+    } else {
+      BillingClient.ProductType.INAPP
+    }
+  }
+
+  private fun productToJson(product: ProductDetails): JSObject? {
+    val json = JSObject()
+    json.put("id", product.productId)
+    json.put("title", product.name)
+    json.put("description", product.description)
+    if (product.productType == BillingClient.ProductType.SUBS) {
+      // the last pricing phase is the recurring price, after the free trial and introductory phases
+      val phase = product.subscriptionOfferDetails?.firstOrNull()?.pricingPhases?.pricingPhaseList?.lastOrNull() ?: return null
+      json.put("kind", "subscription")
+      json.put("price", phase.formattedPrice)
+      json.put("priceMicros", phase.priceAmountMicros)
+      json.put("currencyCode", phase.priceCurrencyCode)
+    } else {
+      val offer = product.oneTimePurchaseOfferDetails ?: return null
+      json.put("kind", "oneTime")
+      json.put("price", offer.formattedPrice)
+      json.put("priceMicros", offer.priceAmountMicros)
+      json.put("currencyCode", offer.priceCurrencyCode)
+    }
+    return json
+  }
+
+  private fun purchaseToJson(purchase: Purchase): JSObject? {
+    val state = when (purchase.purchaseState) {
+      Purchase.PurchaseState.PURCHASED -> "purchased"
+      Purchase.PurchaseState.PENDING -> "pending"
+      else -> return null
+    }
+    val json = JSObject()
+    json.put("productId", purchase.products.firstOrNull())
+    // the order ID is only assigned once the payment is completed
+    json.put("orderId", purchase.orderId.takeIf { it.isNotEmpty() })
+    json.put("token", purchase.purchaseToken)
+    json.put("receipt", purchase.purchaseToken)
+    json.put("state", state)
+    json.put("acknowledged", purchase.isAcknowledged)
+    json.put("purchaseTime", purchase.purchaseTime)
+    return json
+  }
+
+  private external fun handlePurchaseEvent(payload: String)
+}
@@ -0,0 +1,119 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.push
+
+import android.Manifest
+import android.app.Activity
+import android.content.Intent
+import android.os.Build
+import android.os.Bundle
+import android.webkit.WebView
+import app.tauri.PermissionState
+import app.tauri.annotation.Command
+import app.tauri.annotation.Permission
+import app.tauri.annotation.PermissionCallback
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import com.google.firebase.messaging.FirebaseMessaging
+import com.google.firebase.messaging.RemoteMessage
+
+@TauriPlugin(
+  permissions = [
+    Permission(strings = [Manifest.permission.POST_NOTIFICATIONS], alias = "notifications")
+  ]
+)
+class PushPlugin(private val activity: Activity): Plugin(activity) {
+  override fun load(webView: WebView) {
+    instance = this
+    // the notification tapped by the user to launch the app
+    activity.intent?.extras?.let { handleOpenedNotification(it, true) }
+  }
+
+  override fun onNewIntent(intent: Intent) {
+    intent.extras?.let { handleOpenedNotification(it, false) }
+  }
+
+  @Command
+  fun register(invoke: Invoke) {
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU && getPermissionState("notifications") != PermissionState.GRANTED) {
+      requestPermissionForAlias("notifications", invoke, "notificationsPermissionCallback")
+    } else {
+      resolveToken(invoke)
+    }
+  }
+
+  @PermissionCallback
+  fun notificationsPermissionCallback(invoke: Invoke) {
+    if (getPermissionState("notifications") == PermissionState.GRANTED) {
+      resolveToken(invoke)
+    } else {
+      invoke.reject("Permission to show notifications denied")
+    }
+  }
+
+  private fun resolveToken(invoke: Invoke) {
+    FirebaseMessaging.getInstance().token.addOnCompleteListener { task ->
+      if (task.isSuccessful) {
+        val ret = JSObject()
+        ret.put("token", task.result)
+        invoke.resolve(ret)
+      } else {
+        invoke.reject(task.exception?.message ?: "Failed to get the push token")
+      }
+    }
+  }
+
+  /**
+   * Forwards the notification data FCM adds to the extras of the activity launched by a tapped notification.
+   */
+  private fun handleOpenedNotification(extras: Bundle, coldStart: Boolean) {
+    if (!extras.containsKey("google.message_id")) {
+      return
+    }
+    val data = JSObject()
+    for (key in extras.keySet()) {
+      if (!key.startsWith("google.") && !key.startsWith("gcm.") && key != "from" && key != "collapse_key") {
+        data.put(key, extras.get(key)?.toString())
+      }
+    }
+    val notification = JSObject()
+    notification.put("type", "notification")
+    notification.put("data", data)
+    notification.put("opened", true)
+    notification.put("coldStart", coldStart)
+    handlePushEvent(notification.toString())
+  }
+
+  internal fun onMessageReceived(message: RemoteMessage) {
+    val data = JSObject()
+    for ((key, value) in message.data) {
+      data.put(key, value)
+    }
+    val notification = JSObject()
+    notification.put("type", "notification")
+    notification.put("title", message.notification?.title)
+    notification.put("body", message.notification?.body)
+    notification.put("data", data)
+    handlePushEvent(notification.toString())
+  }
+
+  internal fun onNewToken(token: String) {
+    val event = JSObject()
+    event.put("type", "token")
+    event.put("token", token)
+    handlePushEvent(event.toString())
+  }
+
+  private external fun handlePushEvent(payload: String)
+
+  companion object {
+    /**
+     * The loaded plugin, receiving the messages of [PushService].
+     */
+    internal var instance: PushPlugin? = null
+  }
+}
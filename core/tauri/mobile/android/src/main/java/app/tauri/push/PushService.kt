@@ -0,0 +1,23 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.push
+
+import com.google.firebase.messaging.FirebaseMessagingService
+import com.google.firebase.messaging.RemoteMessage
+
+/**
+ * Receives the Firebase Cloud Messaging messages, declared by `tauri android init` when the push notifications are enabled.
+ *
+ * The messages received while the app is not running are not forwarded, FCM shows their notification instead.
+ */
+class PushService : FirebaseMessagingService() {
+  override fun onMessageReceived(message: RemoteMessage) {
+    PushPlugin.instance?.onMessageReceived(message)
+  }
+
+  override fun onNewToken(token: String) {
+    PushPlugin.instance?.onNewToken(token)
+  }
+}
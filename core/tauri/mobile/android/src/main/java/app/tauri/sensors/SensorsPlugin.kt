@@ -0,0 +1,73 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.sensors
+
+import android.app.Activity
+import android.content.Context
+import android.hardware.Sensor
+import android.hardware.SensorEvent
+import android.hardware.SensorEventListener
+import android.hardware.SensorManager
+import android.os.SystemClock
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+
+@TauriPlugin
+class SensorsPlugin(private val activity: Activity): Plugin(activity) {
+  private val sensorManager = activity.getSystemService(Context.SENSOR_SERVICE) as SensorManager
+  private val listeners = mutableMapOf<Int, SensorEventListener>()
+
+  private fun sensor(invoke: Invoke): Sensor? {
+    return when (invoke.getString("sensor")) {
+      "accelerometer" -> sensorManager.getDefaultSensor(Sensor.TYPE_ACCELEROMETER)
+      "gyroscope" -> sensorManager.getDefaultSensor(Sensor.TYPE_GYROSCOPE)
+      else -> null
+    }
+  }
+
+  @Command
+  fun isAvailable(invoke: Invoke) {
+    val ret = JSObject()
+    ret.put("available", sensor(invoke) != null)
+    invoke.resolve(ret)
+  }
+
+  @Command
+  fun start(invoke: Invoke) {
+    val id = invoke.getInt("id") ?: return invoke.reject("Missing `id`")
+    // the readings of a missing sensor are never sent
+    val sensor = sensor(invoke) ?: return invoke.resolve()
+    val intervalMicros = invoke.getLong("intervalMicros", SensorManager.SENSOR_DELAY_NORMAL.toLong())
+
+    val listener = object : SensorEventListener {
+      override fun onSensorChanged(event: SensorEvent) {
+        // the event timestamp is the time since boot, in nanoseconds
+        val timestamp = System.currentTimeMillis() + (event.timestamp - SystemClock.elapsedRealtimeNanos()) / 1_000_000
+        handleSensorEvent(id, event.values[0].toDouble(), event.values[1].toDouble(), event.values[2].toDouble(), timestamp)
+      }
+
+      override fun onAccuracyChanged(sensor: Sensor, accuracy: Int) {}
+    }
+    synchronized(listeners) {
+      listeners[id] = listener
+    }
+    sensorManager.registerListener(listener, sensor, intervalMicros.toInt())
+    invoke.resolve()
+  }
+
+  @Command
+  fun stop(invoke: Invoke) {
+    val id = invoke.getInt("id") ?: return invoke.reject("Missing `id`")
+    synchronized(listeners) {
+      listeners.remove(id)
+    }?.let { sensorManager.unregisterListener(it) }
+    invoke.resolve()
+  }
+
+  private external fun handleSensorEvent(id: Int, x: Double, y: Double, z: Double, timestamp: Long)
+}
@@ -0,0 +1,83 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.share
+
+import android.app.Activity
+import android.content.ClipData
+import android.content.Intent
+import android.net.Uri
+import androidx.core.content.FileProvider
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import org.json.JSONObject
+import java.io.File
+
+@TauriPlugin
+class SharePlugin(private val activity: Activity): Plugin(activity) {
+  /**
+   * Shows the chooser of the `ACTION_SEND` targets, the files are shared with the `${applicationId}.fileprovider` provider.
+   */
+  @Command
+  fun share(invoke: Invoke) {
+    val items = invoke.getArray("items") ?: return invoke.reject("Missing `items`")
+    val texts = mutableListOf<String>()
+    val files = ArrayList<Uri>()
+    try {
+      for (i in 0 until items.length()) {
+        val item = items.get(i) as JSONObject
+        when {
+          item.has("text") -> texts.add(item.getString("text"))
+          item.has("url") -> texts.add(item.getString("url"))
+          item.has("file") -> files.add(
+            FileProvider.getUriForFile(activity, "${activity.packageName}.fileprovider", File(item.getString("file")))
+          )
+        }
+      }
+    } catch (ex: Exception) {
+      invoke.reject(ex.message ?: ex.toString(), ex)
+      return
+    }
+
+    val intent = Intent()
+    if (texts.isNotEmpty()) {
+      intent.putExtra(Intent.EXTRA_TEXT, texts.joinToString("\n"))
+    }
+    when (files.size) {
+      0 -> {
+        intent.action = Intent.ACTION_SEND
+        intent.type = "text/plain"
+      }
+      1 -> {
+        intent.action = Intent.ACTION_SEND
+        intent.type = activity.contentResolver.getType(files[0]) ?: "*/*"
+        intent.putExtra(Intent.EXTRA_STREAM, files[0])
+      }
+      else -> {
+        intent.action = Intent.ACTION_SEND_MULTIPLE
+        intent.type = "*/*"
+        intent.putParcelableArrayListExtra(Intent.EXTRA_STREAM, files)
+      }
+    }
+    if (files.isNotEmpty()) {
+      // the clip data grants the read permission of every file to the chosen target
+      val clip = ClipData.newRawUri(null, files[0])
+      files.drop(1).forEach { clip.addItem(ClipData.Item(it)) }
+      intent.clipData = clip
+      intent.addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+    }
+
+    activity.runOnUiThread {
+      try {
+        activity.startActivity(Intent.createChooser(intent, null))
+        invoke.resolve(JSObject())
+      } catch (ex: Exception) {
+        invoke.reject(ex.message ?: ex.toString(), ex)
+      }
+    }
+  }
+}
@@ -0,0 +1,81 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.shortcuts
+
+import android.app.Activity
+import android.content.Intent
+import android.webkit.WebView
+import androidx.core.content.pm.ShortcutInfoCompat
+import androidx.core.content.pm.ShortcutManagerCompat
+import androidx.core.graphics.drawable.IconCompat
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+
+/**
+ * The action of the intents launching the main activity from a shortcut,
+ * also used by the static shortcuts generated when building the app.
+ */
+private const val SHORTCUT_ACTION = "app.tauri.SHORTCUT"
+private const val SHORTCUT_ID_EXTRA = "shortcutId"
+
+@TauriPlugin
+class ShortcutsPlugin(private val activity: Activity): Plugin(activity) {
+  override fun load(webView: WebView) {
+    // the shortcut activated to launch the app
+    activity.intent?.let { handleIntent(it, true) }
+  }
+
+  override fun onNewIntent(intent: Intent) {
+    handleIntent(intent, false)
+  }
+
+  private fun handleIntent(intent: Intent, coldStart: Boolean) {
+    if (intent.action == SHORTCUT_ACTION) {
+      intent.getStringExtra(SHORTCUT_ID_EXTRA)?.let {
+        ShortcutManagerCompat.reportShortcutUsed(activity, it)
+        handleShortcutEvent(it, coldStart)
+      }
+    }
+  }
+
+  @Command
+  fun setShortcuts(invoke: Invoke) {
+    val shortcuts = invoke.getArray("shortcuts", JSArray())
+    val max = ShortcutManagerCompat.getMaxShortcutCountPerActivity(activity)
+    val infos = ArrayList<ShortcutInfoCompat>()
+    for (i in 0 until minOf(shortcuts.length(), max)) {
+      val shortcut = JSObject.fromJSONObject(shortcuts.getJSONObject(i))
+      val id = shortcut.getString("id")
+      val intent = Intent(activity, activity.javaClass)
+        .setAction(SHORTCUT_ACTION)
+        .putExtra(SHORTCUT_ID_EXTRA, id)
+      val builder = ShortcutInfoCompat.Builder(activity, id)
+        .setShortLabel(shortcut.getString("title"))
+        .setIntent(intent)
+        .setRank(i)
+      shortcut.getString("description", null)?.let { builder.setLongLabel(it) }
+      shortcut.getString("icon", null)?.let {
+        val resource = activity.resources.getIdentifier(it, "drawable", activity.packageName)
+        if (resource != 0) {
+          builder.setIcon(IconCompat.createWithResource(activity, resource))
+        }
+      }
+      infos.add(builder.build())
+    }
+
+    try {
+      ShortcutManagerCompat.setDynamicShortcuts(activity, infos)
+      invoke.resolve()
+    } catch (ex: Exception) {
+      invoke.reject(ex.message ?: "Failed to set the shortcuts", ex)
+    }
+  }
+
+  private external fun handleShortcutEvent(id: String, coldStart: Boolean)
+}
@@ -0,0 +1,126 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.systembars
+
+import android.app.Activity
+import android.content.res.Configuration
+import android.graphics.Color
+import android.os.Build
+import android.webkit.WebView
+import androidx.core.graphics.Insets
+import androidx.core.view.ViewCompat
+import androidx.core.view.WindowCompat
+import androidx.core.view.WindowInsetsCompat
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import kotlin.math.max
+
+/**
+ * Parses a `#RRGGBB` or `#RRGGBBAA` color.
+ */
+private fun parseColor(color: String): Int {
+  val hex = color.removePrefix("#")
+  val rgba = when (hex.length) {
+    6 -> hex.toLong(16) shl 8 or 0xff
+    8 -> hex.toLong(16)
+    else -> throw IllegalArgumentException("Invalid color `$color`")
+  }
+  return Color.argb(
+    (rgba and 0xff).toInt(),
+    (rgba shr 24 and 0xff).toInt(),
+    (rgba shr 16 and 0xff).toInt(),
+    (rgba shr 8 and 0xff).toInt()
+  )
+}
+
+@TauriPlugin
+class SystemBarsPlugin(private val activity: Activity): Plugin(activity) {
+  private var webView: WebView? = null
+
+  override fun load(webView: WebView) {
+    this.webView = webView
+  }
+
+  /**
+   * Whether the icons must be dark for the style, following the system theme by default.
+   */
+  private fun darkIcons(style: String): Boolean {
+    return when (style) {
+      "lightContent" -> false
+      "darkContent" -> true
+      else -> activity.resources.configuration.uiMode and Configuration.UI_MODE_NIGHT_MASK != Configuration.UI_MODE_NIGHT_YES
+    }
+  }
+
+  /**
+   * Runs the update on the main thread, resolving the invoke once it is applied.
+   */
+  private fun update(invoke: Invoke, f: () -> Unit) {
+    activity.runOnUiThread {
+      try {
+        f()
+        invoke.resolve()
+      } catch (ex: Exception) {
+        invoke.reject(ex.message ?: ex.toString(), ex)
+      }
+    }
+  }
+
+  @Command
+  fun setStatusBar(invoke: Invoke) {
+    val color = invoke.getString("color")
+    val style = invoke.getString("style")
+    update(invoke) {
+      color?.let { activity.window.statusBarColor = parseColor(it) }
+      style?.let {
+        WindowCompat.getInsetsController(activity.window, activity.window.decorView).isAppearanceLightStatusBars = darkIcons(it)
+      }
+    }
+  }
+
+  @Command
+  fun setNavigationBar(invoke: Invoke) {
+    val color = invoke.getString("color")
+    val style = invoke.getString("style")
+    update(invoke) {
+      color?.let { activity.window.navigationBarColor = parseColor(it) }
+      if (style != null && Build.VERSION.SDK_INT >= Build.VERSION_CODES.O_MR1) {
+        WindowCompat.getInsetsController(activity.window, activity.window.decorView).isAppearanceLightNavigationBars = darkIcons(style)
+      }
+    }
+  }
+
+  @Command
+  fun setEdgeToEdge(invoke: Invoke) {
+    val enable = invoke.getBoolean("enable", false)
+    update(invoke) {
+      WindowCompat.setDecorFitsSystemWindows(activity.window, !enable)
+    }
+  }
+
+  @Command
+  fun safeAreaInsets(invoke: Invoke) {
+    activity.runOnUiThread {
+      val decorView = activity.window.decorView
+      val insets = ViewCompat.getRootWindowInsets(decorView)
+        ?.getInsets(WindowInsetsCompat.Type.systemBars() or WindowInsetsCompat.Type.displayCutout())
+        ?: Insets.NONE
+      // the insets are relative to the window, the webview is only covered when it is laid out behind the bars
+      val view = webView ?: decorView
+      val location = IntArray(2)
+      view.getLocationInWindow(location)
+      val density = activity.resources.displayMetrics.density
+      val ret = JSObject()
+      ret.put("top", max(0, insets.top - location[1]) / density)
+      ret.put("right", max(0, location[0] + view.width - (decorView.width - insets.right)) / density)
+      ret.put("bottom", max(0, location[1] + view.height - (decorView.height - insets.bottom)) / density)
+      ret.put("left", max(0, insets.left - location[0]) / density)
+      invoke.resolve(ret)
+    }
+  }
+}
@@ -0,0 +1,61 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.widgets
+
+import android.app.Activity
+import android.appwidget.AppWidgetManager
+import android.content.Context
+import android.content.Intent
+import app.tauri.annotation.Command
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.Plugin
+
+// the shared preferences read by the widgets generated with `tauri android add-module widget`
+const val WIDGETS_PREFERENCES = "tauri_widgets"
+
+@TauriPlugin
+class WidgetsPlugin(private val activity: Activity): Plugin(activity) {
+  private val preferences = activity.getSharedPreferences(WIDGETS_PREFERENCES, Context.MODE_PRIVATE)
+
+  @Command
+  fun setData(invoke: Invoke) {
+    val key = invoke.getString("key")
+    if (key == null) {
+      invoke.reject("key is required")
+      return
+    }
+    preferences.edit().putString(key, invoke.getString("value")).apply()
+    invoke.resolve()
+  }
+
+  @Command
+  fun removeData(invoke: Invoke) {
+    val key = invoke.getString("key")
+    if (key == null) {
+      invoke.reject("key is required")
+      return
+    }
+    preferences.edit().remove(key).apply()
+    invoke.resolve()
+  }
+
+  @Command
+  fun reload(invoke: Invoke) {
+    val manager = AppWidgetManager.getInstance(activity)
+    // asks each widget provider of the app to update its widgets, as when their update period elapses
+    for (provider in manager.installedProviders.filter { it.provider.packageName == activity.packageName }) {
+      val ids = manager.getAppWidgetIds(provider.provider)
+      if (ids.isEmpty()) {
+        continue
+      }
+      val intent = Intent(AppWidgetManager.ACTION_APPWIDGET_UPDATE)
+        .setComponent(provider.provider)
+        .putExtra(AppWidgetManager.EXTRA_APPWIDGET_IDS, ids)
+      activity.sendBroadcast(intent)
+    }
+    invoke.resolve()
+  }
+}
@@ -0,0 +1,161 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import BackgroundTasks
+import Foundation
+
+typealias BackgroundTaskHandler = @convention(c) (UnsafePointer<CChar>, UInt32) -> Void
+
+class BackgroundTasksPlugin: Plugin {
+	static var shared: BackgroundTasksPlugin?
+	var eventHandler: BackgroundTaskHandler? {
+		didSet {
+			lock.lock()
+			let runs = pending
+			pending.removeAll()
+			lock.unlock()
+			for (id, runId) in runs {
+				send(id, runId)
+			}
+		}
+	}
+	private let lock = NSLock()
+	// the runs started before the handler is set, when the system launched the app to run a task
+	private var pending: [(String, UInt32)] = []
+	// the running `BGTask`s, which require iOS 13
+	private var tasks: [UInt32: Any] = [:]
+	private var nextRunId: UInt32 = 0
+
+	override init() {
+		super.init()
+		BackgroundTasksPlugin.shared = self
+		if #available(iOS 13.0, *) {
+			// the tasks must be registered before the app finishes launching
+			let identifiers = Bundle.main.object(forInfoDictionaryKey: "BGTaskSchedulerPermittedIdentifiers") as? [String] ?? []
+			for identifier in identifiers {
+				BGTaskScheduler.shared.register(forTaskWithIdentifier: identifier, using: nil) { [weak self] task in
+					self?.run(task)
+				}
+			}
+		}
+	}
+
+	private func optionsKey(_ id: String) -> String {
+		return "app.tauri.backgroundTasks.\(id)"
+	}
+
+	@objc func schedule(_ invoke: Invoke) {
+		guard #available(iOS 13.0, *) else {
+			invoke.unavailable("Background tasks require iOS 13")
+			return
+		}
+		guard let id = invoke.getString("id") else {
+			invoke.reject("id is required")
+			return
+		}
+		let options: [String: Any] = [
+			"intervalSecs": invoke.getDouble("intervalSecs", 0),
+			"requiresNetwork": invoke.getBool("requiresNetwork", false),
+			"requiresCharging": invoke.getBool("requiresCharging", false),
+		]
+		// the next run is scheduled by each run, so the options are persisted across the launches
+		UserDefaults.standard.set(options, forKey: optionsKey(id))
+		do {
+			try submit(id, options)
+			invoke.resolve()
+		} catch {
+			invoke.reject("Failed to schedule the task: \(error.localizedDescription)")
+		}
+	}
+
+	@objc func cancel(_ invoke: Invoke) {
+		guard #available(iOS 13.0, *) else {
+			invoke.unavailable("Background tasks require iOS 13")
+			return
+		}
+		guard let id = invoke.getString("id") else {
+			invoke.reject("id is required")
+			return
+		}
+		UserDefaults.standard.removeObject(forKey: optionsKey(id))
+		BGTaskScheduler.shared.cancel(taskRequestWithIdentifier: id)
+		invoke.resolve()
+	}
+
+	@objc func complete(_ invoke: Invoke) {
+		if #available(iOS 13.0, *), let runId = invoke.getInt("runId") {
+			complete(UInt32(runId), invoke.getBool("success", false))
+		}
+		invoke.resolve()
+	}
+
+	@available(iOS 13.0, *)
+	private func submit(_ id: String, _ options: [String: Any]) throws {
+		let requiresNetwork = options["requiresNetwork"] as? Bool ?? false
+		let requiresCharging = options["requiresCharging"] as? Bool ?? false
+		let request: BGTaskRequest
+		if requiresNetwork || requiresCharging {
+			// only the processing tasks support the constraints, and run longer
+			let processingRequest = BGProcessingTaskRequest(identifier: id)
+			processingRequest.requiresNetworkConnectivity = requiresNetwork
+			processingRequest.requiresExternalPower = requiresCharging
+			request = processingRequest
+		} else {
+			request = BGAppRefreshTaskRequest(identifier: id)
+		}
+		request.earliestBeginDate = Date(timeIntervalSinceNow: options["intervalSecs"] as? Double ?? 0)
+		try BGTaskScheduler.shared.submit(request)
+	}
+
+	@available(iOS 13.0, *)
+	private func run(_ task: BGTask) {
+		let id = task.identifier
+		// a task request runs once, the next run is scheduled right away
+		if let options = UserDefaults.standard.dictionary(forKey: optionsKey(id)) {
+			try? submit(id, options)
+		} else {
+			task.setTaskCompleted(success: true)
+			return
+		}
+
+		lock.lock()
+		nextRunId += 1
+		let runId = nextRunId
+		tasks[runId] = task
+		lock.unlock()
+		task.expirationHandler = { [weak self] in
+			self?.complete(runId, false)
+		}
+		send(id, runId)
+	}
+
+	@available(iOS 13.0, *)
+	private func complete(_ runId: UInt32, _ success: Bool) {
+		lock.lock()
+		let task = tasks.removeValue(forKey: runId) as? BGTask
+		lock.unlock()
+		task?.setTaskCompleted(success: success)
+	}
+
+	private func send(_ id: String, _ runId: UInt32) {
+		lock.lock()
+		guard let eventHandler = eventHandler else {
+			pending.append((id, runId))
+			lock.unlock()
+			return
+		}
+		lock.unlock()
+		id.withCString { eventHandler($0, runId) }
+	}
+}
+
+@_cdecl("init_background_tasks_plugin")
+func initBackgroundTasksPlugin() -> Plugin {
+	return BackgroundTasksPlugin()
+}
+
+@_cdecl("set_background_task_handler")
+func setBackgroundTaskHandler(handler: @escaping BackgroundTaskHandler) {
+	BackgroundTasksPlugin.shared?.eventHandler = handler
+}
@@ -0,0 +1,238 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import Security
+
+/// The DER header of a P-256 `SubjectPublicKeyInfo`, followed by the uncompressed point returned by the Keychain.
+private let p256PublicKeyInfoHeader: [UInt8] = [
+	0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+	0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+]
+
+class CryptoPlugin: Plugin {
+	/// The Keychain service of the secrets.
+	private let service = (Bundle.main.bundleIdentifier ?? "app") + ".tauri.crypto"
+
+	private func keyTag(_ alias: String) -> Data {
+		return "app.tauri.crypto.key.\(alias)".data(using: .utf8)!
+	}
+
+	private func resolveData(_ invoke: Invoke, _ data: Data?) {
+		if let data = data {
+			invoke.resolve(["data": data.base64EncodedString()])
+		} else {
+			invoke.resolve([:])
+		}
+	}
+
+	private func privateKey(_ alias: String) -> SecKey? {
+		let query: [String: Any] = [
+			kSecClass as String: kSecClassKey,
+			kSecAttrApplicationTag as String: keyTag(alias),
+			kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+			kSecReturnRef as String: true,
+		]
+		var item: CFTypeRef?
+		guard SecItemCopyMatching(query as CFDictionary, &item) == errSecSuccess else {
+			return nil
+		}
+		return (item as! SecKey)
+	}
+
+	private func publicKeyInfo(_ privateKey: SecKey) throws -> Data {
+		var error: Unmanaged<CFError>?
+		guard let publicKey = SecKeyCopyPublicKey(privateKey),
+			let point = SecKeyCopyExternalRepresentation(publicKey, &error) as Data?
+		else {
+			throw error?.takeRetainedValue() ?? CryptoError("Failed to export the public key")
+		}
+		return Data(p256PublicKeyInfoHeader) + point
+	}
+
+	private func deletePrivateKey(_ alias: String) {
+		let query: [String: Any] = [
+			kSecClass as String: kSecClassKey,
+			kSecAttrApplicationTag as String: keyTag(alias),
+		]
+		SecItemDelete(query as CFDictionary)
+	}
+
+	@objc func generateKey(_ invoke: Invoke) {
+		guard let alias = invoke.getString("alias") else {
+			invoke.reject("Missing `alias`")
+			return
+		}
+		let hardwareBacked = invoke.getBool("hardwareBacked", false)
+		let requireUserAuthentication = invoke.getBool("requireUserAuthentication", false)
+
+		deletePrivateKey(alias)
+		do {
+			let key: SecKey
+			do {
+				key = try createPrivateKey(alias, requireUserAuthentication, secureEnclave: hardwareBacked)
+			} catch {
+				// the simulator and the devices without a Secure Enclave fall back to the Keychain
+				guard hardwareBacked else {
+					throw error
+				}
+				key = try createPrivateKey(alias, requireUserAuthentication, secureEnclave: false)
+			}
+			resolveData(invoke, try publicKeyInfo(key))
+		} catch {
+			invoke.reject(error.localizedDescription, nil, error)
+		}
+	}
+
+	private func createPrivateKey(_ alias: String, _ requireUserAuthentication: Bool, secureEnclave: Bool) throws -> SecKey {
+		var flags: SecAccessControlCreateFlags = secureEnclave ? [.privateKeyUsage] : []
+		if requireUserAuthentication {
+			flags.insert(.userPresence)
+		}
+		var error: Unmanaged<CFError>?
+		guard let access = SecAccessControlCreateWithFlags(
+			kCFAllocatorDefault,
+			kSecAttrAccessibleWhenUnlockedThisDeviceOnly,
+			flags,
+			&error
+		) else {
+			throw error!.takeRetainedValue()
+		}
+
+		var attributes: [String: Any] = [
+			kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+			kSecAttrKeySizeInBits as String: 256,
+			kSecPrivateKeyAttrs as String: [
+				kSecAttrIsPermanent as String: true,
+				kSecAttrApplicationTag as String: keyTag(alias),
+				kSecAttrAccessControl as String: access,
+			] as [String: Any],
+		]
+		if secureEnclave {
+			attributes[kSecAttrTokenID as String] = kSecAttrTokenIDSecureEnclave
+		}
+		guard let key = SecKeyCreateRandomKey(attributes as CFDictionary, &error) else {
+			throw error!.takeRetainedValue()
+		}
+		return key
+	}
+
+	@objc func publicKey(_ invoke: Invoke) {
+		guard let alias = invoke.getString("alias") else {
+			invoke.reject("Missing `alias`")
+			return
+		}
+		guard let key = privateKey(alias) else {
+			resolveData(invoke, nil)
+			return
+		}
+		do {
+			resolveData(invoke, try publicKeyInfo(key))
+		} catch {
+			invoke.reject(error.localizedDescription, nil, error)
+		}
+	}
+
+	@objc func sign(_ invoke: Invoke) {
+		guard let alias = invoke.getString("alias") else {
+			invoke.reject("Missing `alias`")
+			return
+		}
+		guard let data = invoke.getString("data").flatMap({ Data(base64Encoded: $0) }) else {
+			invoke.reject("Missing `data`")
+			return
+		}
+		guard let key = privateKey(alias) else {
+			resolveData(invoke, nil)
+			return
+		}
+		// prompts the user to authenticate when the key requires it
+		var error: Unmanaged<CFError>?
+		guard let signature = SecKeyCreateSignature(key, .ecdsaSignatureMessageX962SHA256, data as CFData, &error) else {
+			let error = error!.takeRetainedValue()
+			invoke.reject(error.localizedDescription, nil, error)
+			return
+		}
+		resolveData(invoke, signature as Data)
+	}
+
+	@objc func deleteKey(_ invoke: Invoke) {
+		guard let alias = invoke.getString("alias") else {
+			invoke.reject("Missing `alias`")
+			return
+		}
+		deletePrivateKey(alias)
+		resolveData(invoke, nil)
+	}
+
+	private func secretQuery(_ key: String) -> [String: Any] {
+		return [
+			kSecClass as String: kSecClassGenericPassword,
+			kSecAttrService as String: service,
+			kSecAttrAccount as String: key,
+		]
+	}
+
+	@objc func setSecret(_ invoke: Invoke) {
+		guard let key = invoke.getString("key") else {
+			invoke.reject("Missing `key`")
+			return
+		}
+		guard let value = invoke.getString("value").flatMap({ Data(base64Encoded: $0) }) else {
+			invoke.reject("Missing `value`")
+			return
+		}
+		SecItemDelete(secretQuery(key) as CFDictionary)
+		var query = secretQuery(key)
+		query[kSecAttrAccessible as String] = kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly
+		query[kSecValueData as String] = value
+		let status = SecItemAdd(query as CFDictionary, nil)
+		if status == errSecSuccess {
+			resolveData(invoke, nil)
+		} else {
+			invoke.reject(SecCopyErrorMessageString(status, nil) as String? ?? "Keychain error \(status)")
+		}
+	}
+
+	@objc func getSecret(_ invoke: Invoke) {
+		guard let key = invoke.getString("key") else {
+			invoke.reject("Missing `key`")
+			return
+		}
+		var query = secretQuery(key)
+		query[kSecReturnData as String] = true
+		var item: CFTypeRef?
+		let status = SecItemCopyMatching(query as CFDictionary, &item)
+		switch status {
+		case errSecSuccess:
+			resolveData(invoke, item as? Data)
+		case errSecItemNotFound:
+			resolveData(invoke, nil)
+		default:
+			invoke.reject(SecCopyErrorMessageString(status, nil) as String? ?? "Keychain error \(status)")
+		}
+	}
+
+	@objc func deleteSecret(_ invoke: Invoke) {
+		guard let key = invoke.getString("key") else {
+			invoke.reject("Missing `key`")
+			return
+		}
+		SecItemDelete(secretQuery(key) as CFDictionary)
+		resolveData(invoke, nil)
+	}
+}
+
+struct CryptoError: LocalizedError {
+	let errorDescription: String?
+
+	init(_ message: String) {
+		errorDescription = message
+	}
+}
+
+@_cdecl("init_crypto_plugin")
+func initCryptoPlugin() -> Plugin {
+	return CryptoPlugin()
+}
@@ -0,0 +1,50 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import UIKit
+
+class HapticsPlugin: Plugin {
+	@objc func perform(_ invoke: Invoke) {
+		let type = invoke.getString("type")
+		let style = invoke.getString("style")
+		let feedback = invoke.getString("feedback")
+		// the feedback generators must be used on the main thread
+		DispatchQueue.main.async {
+			switch type {
+			case "impact":
+				let generator: UIImpactFeedbackGenerator
+				switch style {
+				case "light":
+					generator = UIImpactFeedbackGenerator(style: .light)
+				case "heavy":
+					generator = UIImpactFeedbackGenerator(style: .heavy)
+				default:
+					generator = UIImpactFeedbackGenerator(style: .medium)
+				}
+				generator.impactOccurred()
+			case "selection":
+				UISelectionFeedbackGenerator().selectionChanged()
+			case "notification":
+				let generator = UINotificationFeedbackGenerator()
+				switch feedback {
+				case "success":
+					generator.notificationOccurred(.success)
+				case "error":
+					generator.notificationOccurred(.error)
+				default:
+					generator.notificationOccurred(.warning)
+				}
+			default:
+				break
+			}
+		}
+		invoke.resolve()
+	}
+}
+
+@_cdecl("init_haptics_plugin")
+func initHapticsPlugin() -> Plugin {
+	return HapticsPlugin()
+}
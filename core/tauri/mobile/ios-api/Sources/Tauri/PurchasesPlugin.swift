@@ -0,0 +1,190 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import StoreKit
+
+typealias PurchaseEventHandler = @convention(c) (UnsafePointer<CChar>) -> Void
+
+class PurchasesPlugin: Plugin {
+	static var shared: PurchasesPlugin?
+	var eventHandler: PurchaseEventHandler?
+	// the `Product` values, which require iOS 15, queried by `getProducts`
+	private var products: [String: Any] = [:]
+	private var updates: Task<Void, Never>?
+
+	override init() {
+		super.init()
+		PurchasesPlugin.shared = self
+		if #available(iOS 15.0, *) {
+			// the transactions completed outside of the purchase flow, e.g. approved by a parent or renewed subscriptions
+			updates = Task.detached { [weak self] in
+				for await result in Transaction.updates {
+					if case .verified(let transaction) = result {
+						self?.send(purchaseJson(transaction, result.jwsRepresentation))
+					}
+				}
+			}
+		}
+	}
+
+	deinit {
+		updates?.cancel()
+	}
+
+	@objc func getProducts(_ invoke: Invoke) {
+		guard #available(iOS 15.0, *) else {
+			invoke.unavailable("In-app purchases require iOS 15")
+			return
+		}
+		let ids = invoke.getArray("ids", String.self) ?? []
+		Task {
+			do {
+				let products = try await Product.products(for: ids)
+				for product in products {
+					self.products[product.id] = product
+				}
+				invoke.resolve(["products": products.map { productJson($0) }])
+			} catch {
+				invoke.reject("Failed to query the products: \(error.localizedDescription)")
+			}
+		}
+	}
+
+	@objc func purchase(_ invoke: Invoke) {
+		guard #available(iOS 15.0, *) else {
+			invoke.unavailable("In-app purchases require iOS 15")
+			return
+		}
+		let productId = invoke.getString("productId") ?? ""
+		guard let product = products[productId] as? Product else {
+			invoke.reject("Product \(productId) not found, query it with getProducts first")
+			return
+		}
+		Task {
+			do {
+				switch try await product.purchase() {
+				case .success(let result):
+					guard case .verified(let transaction) = result else {
+						invoke.reject("The transaction could not be verified")
+						return
+					}
+					invoke.resolve(["purchase": purchaseJson(transaction, result.jwsRepresentation)])
+				case .pending:
+					// the transaction is emitted once approved
+					invoke.resolve([
+						"purchase": [
+							"productId": productId,
+							"token": "",
+							"receipt": "",
+							"state": "pending",
+							"purchaseTime": UInt64(Date().timeIntervalSince1970 * 1000),
+						]
+					])
+				default:
+					// cancelled by the user
+					invoke.resolve([:])
+				}
+			} catch {
+				invoke.reject("Purchase failed: \(error.localizedDescription)")
+			}
+		}
+	}
+
+	@objc func finish(_ invoke: Invoke) {
+		guard #available(iOS 15.0, *) else {
+			invoke.unavailable("In-app purchases require iOS 15")
+			return
+		}
+		let token = invoke.getString("token") ?? ""
+		Task {
+			for await result in Transaction.unfinished {
+				if case .verified(let transaction) = result, String(transaction.id) == token {
+					await transaction.finish()
+				}
+			}
+			invoke.resolve()
+		}
+	}
+
+	@objc func restore(_ invoke: Invoke) {
+		guard #available(iOS 15.0, *) else {
+			invoke.unavailable("In-app purchases require iOS 15")
+			return
+		}
+		Task {
+			do {
+				// asks the user to sign in to the App Store when needed
+				try await AppStore.sync()
+			} catch {
+				invoke.reject("Failed to restore the purchases: \(error.localizedDescription)")
+				return
+			}
+			var purchases: [String: JsonObject] = [:]
+			for await result in Transaction.currentEntitlements {
+				if case .verified(let transaction) = result {
+					var purchase = purchaseJson(transaction, result.jwsRepresentation)
+					purchase["acknowledged"] = true
+					purchases[String(transaction.id)] = purchase
+				}
+			}
+			for await result in Transaction.unfinished {
+				if case .verified(let transaction) = result {
+					purchases[String(transaction.id)] = purchaseJson(transaction, result.jwsRepresentation)
+				}
+			}
+			invoke.resolve(["purchases": Array(purchases.values)])
+		}
+	}
+
+	func send(_ purchase: JsonObject) {
+		guard let eventHandler = eventHandler else {
+			return
+		}
+		do {
+			let json = try JSONSerialization.data(withJSONObject: purchase)
+			String(data: json, encoding: .utf8)?.withCString { eventHandler($0) }
+		} catch {
+			Logger.error("failed to serialize the purchase: \(error)")
+		}
+	}
+}
+
+/// Converts a product to the `Product` expected by the Rust code.
+@available(iOS 15.0, *)
+private func productJson(_ product: Product) -> JsonObject {
+	return [
+		"id": product.id,
+		"kind": product.type == .autoRenewable ? "subscription" : "oneTime",
+		"title": product.displayName,
+		"description": product.description,
+		"price": product.displayPrice,
+		"priceMicros": NSDecimalNumber(decimal: product.price * 1_000_000).int64Value,
+		"currencyCode": product.priceFormatStyle.currencyCode,
+	]
+}
+
+/// Converts a verified transaction to the `Purchase` expected by the Rust code.
+@available(iOS 15.0, *)
+private func purchaseJson(_ transaction: Transaction, _ jws: String) -> JsonObject {
+	return [
+		"productId": transaction.productID,
+		"orderId": String(transaction.id),
+		"token": String(transaction.id),
+		"receipt": jws,
+		"state": "purchased",
+		"acknowledged": false,
+		"purchaseTime": UInt64(transaction.purchaseDate.timeIntervalSince1970 * 1000),
+	]
+}
+
+@_cdecl("init_purchases_plugin")
+func initPurchasesPlugin() -> Plugin {
+	return PurchasesPlugin()
+}
+
+@_cdecl("set_purchase_event_handler")
+func setPurchaseEventHandler(handler: @escaping PurchaseEventHandler) {
+	PurchasesPlugin.shared?.eventHandler = handler
+}
@@ -0,0 +1,164 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import UIKit
+import UserNotifications
+import os.log
+
+typealias PushEventHandler = @convention(c) (UnsafePointer<CChar>) -> Void
+
+class PushPlugin: Plugin, UNUserNotificationCenterDelegate {
+	static var shared: PushPlugin?
+	var eventHandler: PushEventHandler?
+	var pendingRegistration: Invoke?
+	// the notifications tapped before the app became active launched it
+	var becameActive = false
+
+	override init() {
+		super.init()
+		PushPlugin.shared = self
+		// the delegate must be set before the app finishes launching to receive the notification that launched it
+		UNUserNotificationCenter.current().delegate = self
+		NotificationCenter.default.addObserver(forName: UIApplication.didBecomeActiveNotification, object: nil, queue: .main) { [weak self] _ in
+			self?.becameActive = true
+		}
+		NotificationCenter.default.addObserver(forName: UIApplication.didFinishLaunchingNotification, object: nil, queue: .main) { [weak self] _ in
+			self?.addAppDelegateMethods()
+		}
+	}
+
+	@objc func register(_ invoke: Invoke) {
+		UNUserNotificationCenter.current().requestAuthorization(options: [.alert, .badge, .sound]) { granted, error in
+			if let error = error {
+				invoke.reject(error.localizedDescription)
+				return
+			}
+			if !granted {
+				invoke.reject("Permission to show notifications denied")
+				return
+			}
+			DispatchQueue.main.async {
+				self.pendingRegistration = invoke
+				UIApplication.shared.registerForRemoteNotifications()
+			}
+		}
+	}
+
+	/// The remote notification callbacks are only sent to the app delegate, which is owned by the event loop,
+	/// so they are added to its class.
+	private func addAppDelegateMethods() {
+		guard let delegate = UIApplication.shared.delegate else {
+			return
+		}
+		let delegateClass: AnyClass = type(of: delegate)
+
+		let didRegister: @convention(block) (AnyObject, UIApplication, Data) -> Void = { _, _, deviceToken in
+			PushPlugin.shared?.didRegister(deviceToken: deviceToken)
+		}
+		class_addMethod(
+			delegateClass,
+			#selector(UIApplicationDelegate.application(_:didRegisterForRemoteNotificationsWithDeviceToken:)),
+			imp_implementationWithBlock(didRegister),
+			"v@:@@"
+		)
+
+		let didFail: @convention(block) (AnyObject, UIApplication, NSError) -> Void = { _, _, error in
+			PushPlugin.shared?.pendingRegistration?.reject(error.localizedDescription)
+			PushPlugin.shared?.pendingRegistration = nil
+		}
+		class_addMethod(
+			delegateClass,
+			#selector(UIApplicationDelegate.application(_:didFailToRegisterForRemoteNotificationsWithError:)),
+			imp_implementationWithBlock(didFail),
+			"v@:@@"
+		)
+
+		let didReceive: @convention(block) (AnyObject, UIApplication, NSDictionary, @escaping (UIBackgroundFetchResult) -> Void) -> Void = { _, _, userInfo, completionHandler in
+			// the notifications with an alert are forwarded when they are presented or tapped
+			if let aps = userInfo["aps"] as? NSDictionary, aps["alert"] == nil {
+				PushPlugin.shared?.send(pushNotificationEvent(userInfo, opened: false, coldStart: false))
+			}
+			completionHandler(.newData)
+		}
+		class_addMethod(
+			delegateClass,
+			#selector(UIApplicationDelegate.application(_:didReceiveRemoteNotification:fetchCompletionHandler:)),
+			imp_implementationWithBlock(didReceive),
+			"v@:@@@?"
+		)
+	}
+
+	private func didRegister(deviceToken: Data) {
+		let token = deviceToken.map { String(format: "%02x", $0) }.joined()
+		pendingRegistration?.resolve(["token": token])
+		pendingRegistration = nil
+		send(["type": "token", "token": token])
+	}
+
+	func userNotificationCenter(
+		_ center: UNUserNotificationCenter,
+		willPresent notification: UNNotification,
+		withCompletionHandler completionHandler: @escaping (UNNotificationPresentationOptions) -> Void
+	) {
+		if notification.request.trigger is UNPushNotificationTrigger {
+			send(pushNotificationEvent(notification.request.content.userInfo as NSDictionary, opened: false, coldStart: false))
+		}
+		completionHandler([.badge, .sound])
+	}
+
+	func userNotificationCenter(
+		_ center: UNUserNotificationCenter,
+		didReceive response: UNNotificationResponse,
+		withCompletionHandler completionHandler: @escaping () -> Void
+	) {
+		if response.notification.request.trigger is UNPushNotificationTrigger {
+			send(pushNotificationEvent(response.notification.request.content.userInfo as NSDictionary, opened: true, coldStart: !becameActive))
+		}
+		completionHandler()
+	}
+
+	func send(_ event: [String: Any]) {
+		guard let eventHandler = eventHandler else {
+			return
+		}
+		do {
+			let json = try JSONSerialization.data(withJSONObject: event)
+			String(data: json, encoding: .utf8)?.withCString { eventHandler($0) }
+		} catch {
+			Logger.error("failed to serialize the push event: \(error)")
+		}
+	}
+}
+
+/// Converts the payload of a remote notification to the push event expected by the Rust code.
+func pushNotificationEvent(_ userInfo: NSDictionary, opened: Bool, coldStart: Bool) -> [String: Any] {
+	var event: [String: Any] = ["type": "notification", "opened": opened, "coldStart": coldStart]
+	var data: [String: Any] = [:]
+	for (key, value) in userInfo {
+		if let key = key as? String, key != "aps", JSONSerialization.isValidJSONObject([value]) {
+			data[key] = value
+		}
+	}
+	event["data"] = data
+	if let aps = userInfo["aps"] as? NSDictionary {
+		if let alert = aps["alert"] as? NSDictionary {
+			event["title"] = alert["title"]
+			event["body"] = alert["body"]
+		} else if let alert = aps["alert"] as? String {
+			event["body"] = alert
+		}
+	}
+	return event
+}
+
+@_cdecl("init_push_plugin")
+func initPushPlugin() -> Plugin {
+	return PushPlugin()
+}
+
+@_cdecl("set_push_event_handler")
+func setPushEventHandler(handler: @escaping PushEventHandler) {
+	PushPlugin.shared?.eventHandler = handler
+}
@@ -0,0 +1,109 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import CoreMotion
+import Foundation
+
+typealias SensorEventHandler = @convention(c) (UInt32, Double, Double, Double, UInt64) -> Void
+
+/// The standard gravity, CoreMotion measures the acceleration in g.
+private let standardGravity = 9.80665
+
+class SensorsPlugin: Plugin {
+	static var shared: SensorsPlugin?
+	var eventHandler: SensorEventHandler?
+	// a motion manager per watcher, so the watchers of a sensor can use different intervals
+	private var managers: [UInt32: CMMotionManager] = [:]
+	private let queue = OperationQueue()
+	private let lock = NSLock()
+
+	override init() {
+		super.init()
+		SensorsPlugin.shared = self
+	}
+
+	private func isAvailable(_ manager: CMMotionManager, _ sensor: String?) -> Bool {
+		switch sensor {
+		case "accelerometer":
+			return manager.isAccelerometerAvailable
+		case "gyroscope":
+			return manager.isGyroAvailable
+		default:
+			return false
+		}
+	}
+
+	/// Converts the time since boot of a reading to milliseconds since the Unix epoch.
+	private func timestamp(_ uptime: TimeInterval) -> UInt64 {
+		let bootTime = Date().timeIntervalSince1970 - ProcessInfo.processInfo.systemUptime
+		return UInt64((bootTime + uptime) * 1000)
+	}
+
+	@objc func isAvailable(_ invoke: Invoke) {
+		invoke.resolve(["available": isAvailable(CMMotionManager(), invoke.getString("sensor"))])
+	}
+
+	@objc func start(_ invoke: Invoke) {
+		guard let id = invoke.getInt("id").map({ UInt32($0) }) else {
+			invoke.reject("Missing `id`")
+			return
+		}
+		let sensor = invoke.getString("sensor")
+		let interval = Double(invoke.getInt("intervalMicros", 200_000)) / 1_000_000
+		let manager = CMMotionManager()
+		// the readings of a missing sensor are never sent
+		guard isAvailable(manager, sensor) else {
+			invoke.resolve()
+			return
+		}
+
+		switch sensor {
+		case "accelerometer":
+			manager.accelerometerUpdateInterval = interval
+			manager.startAccelerometerUpdates(to: queue) { [weak self] data, _ in
+				guard let self = self, let data = data else { return }
+				self.eventHandler?(
+					id,
+					data.acceleration.x * standardGravity,
+					data.acceleration.y * standardGravity,
+					data.acceleration.z * standardGravity,
+					self.timestamp(data.timestamp)
+				)
+			}
+		default:
+			manager.gyroUpdateInterval = interval
+			manager.startGyroUpdates(to: queue) { [weak self] data, _ in
+				guard let self = self, let data = data else { return }
+				self.eventHandler?(id, data.rotationRate.x, data.rotationRate.y, data.rotationRate.z, self.timestamp(data.timestamp))
+			}
+		}
+		lock.lock()
+		managers[id] = manager
+		lock.unlock()
+		invoke.resolve()
+	}
+
+	@objc func stop(_ invoke: Invoke) {
+		guard let id = invoke.getInt("id").map({ UInt32($0) }) else {
+			invoke.reject("Missing `id`")
+			return
+		}
+		lock.lock()
+		let manager = managers.removeValue(forKey: id)
+		lock.unlock()
+		manager?.stopAccelerometerUpdates()
+		manager?.stopGyroUpdates()
+		invoke.resolve()
+	}
+}
+
+@_cdecl("init_sensors_plugin")
+func initSensorsPlugin() -> Plugin {
+	return SensorsPlugin()
+}
+
+@_cdecl("set_sensor_event_handler")
+func setSensorEventHandler(handler: @escaping SensorEventHandler) {
+	SensorsPlugin.shared?.eventHandler = handler
+}
@@ -0,0 +1,39 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import UIKit
+
+class SharePlugin: Plugin {
+	@objc func share(_ invoke: Invoke) {
+		var activityItems: [Any] = []
+		for item in invoke.getArray("items", []) {
+			guard let item = item as? JSObject else { continue }
+			if let text = item["text"] as? String {
+				activityItems.append(text)
+			} else if let url = (item["url"] as? String).flatMap({ URL(string: $0) }) {
+				activityItems.append(url)
+			} else if let file = item["file"] as? String {
+				activityItems.append(URL(fileURLWithPath: file))
+			}
+		}
+		// the activity view controller must be presented on the main thread
+		DispatchQueue.main.async {
+			guard let viewController = self.manager.viewController else {
+				invoke.reject("The webview is not created yet")
+				return
+			}
+			let activityViewController = UIActivityViewController(activityItems: activityItems, applicationActivities: nil)
+			// the iPad shows the share sheet in a popover
+			UIUtils.centerPopover(rootViewController: viewController, popoverController: activityViewController)
+			viewController.present(activityViewController, animated: true)
+			invoke.resolve()
+		}
+	}
+}
+
+@_cdecl("init_share_plugin")
+func initSharePlugin() -> Plugin {
+	return SharePlugin()
+}
@@ -0,0 +1,105 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+import UIKit
+
+typealias ShortcutEventHandler = @convention(c) (UnsafePointer<CChar>, Bool) -> Void
+
+class ShortcutsPlugin: Plugin {
+	static var shared: ShortcutsPlugin?
+	var eventHandler: ShortcutEventHandler? {
+		didSet {
+			for (id, coldStart) in pending {
+				send(id, coldStart: coldStart)
+			}
+			pending.removeAll()
+		}
+	}
+	// the quick actions received before the handler is set
+	private var pending: [(String, Bool)] = []
+	// the quick action launching the app, also sent to the app delegate once launched
+	private var launchShortcut: String?
+
+	override init() {
+		super.init()
+		ShortcutsPlugin.shared = self
+		NotificationCenter.default.addObserver(forName: UIApplication.didFinishLaunchingNotification, object: nil, queue: .main) { [weak self] notification in
+			if let item = notification.userInfo?[UIApplication.LaunchOptionsKey.shortcutItem] as? UIApplicationShortcutItem {
+				self?.launchShortcut = item.type
+			}
+			self?.addAppDelegateMethod()
+		}
+	}
+
+	@objc func setShortcuts(_ invoke: Invoke) {
+		let shortcuts = invoke.getArray("shortcuts", JSObject.self) ?? []
+		let items = shortcuts.compactMap { shortcut -> UIApplicationShortcutItem? in
+			guard let id = shortcut["id"] as? String, let title = shortcut["title"] as? String else {
+				return nil
+			}
+			var icon: UIApplicationShortcutIcon?
+			if let name = shortcut["icon"] as? String {
+				if #available(iOS 13.0, *) {
+					icon = UIApplicationShortcutIcon(systemImageName: name)
+				} else {
+					icon = UIApplicationShortcutIcon(templateImageName: name)
+				}
+			}
+			return UIApplicationShortcutItem(
+				type: id,
+				localizedTitle: title,
+				localizedSubtitle: shortcut["description"] as? String,
+				icon: icon,
+				userInfo: nil
+			)
+		}
+		DispatchQueue.main.async {
+			UIApplication.shared.shortcutItems = items
+			invoke.resolve()
+		}
+	}
+
+	/// The quick actions are only sent to the app delegate, which is owned by the event loop,
+	/// so the callback is added to its class.
+	private func addAppDelegateMethod() {
+		guard let delegate = UIApplication.shared.delegate else {
+			return
+		}
+		let performAction: @convention(block) (AnyObject, UIApplication, UIApplicationShortcutItem, @escaping (Bool) -> Void) -> Void = { _, _, item, completionHandler in
+			guard let plugin = ShortcutsPlugin.shared else {
+				completionHandler(false)
+				return
+			}
+			let coldStart = plugin.launchShortcut == item.type
+			plugin.launchShortcut = nil
+			plugin.send(item.type, coldStart: coldStart)
+			completionHandler(true)
+		}
+		class_addMethod(
+			type(of: delegate),
+			#selector(UIApplicationDelegate.application(_:performActionFor:completionHandler:)),
+			imp_implementationWithBlock(performAction),
+			"v@:@@@?"
+		)
+	}
+
+	func send(_ id: String, coldStart: Bool) {
+		guard let eventHandler = eventHandler else {
+			pending.append((id, coldStart))
+			return
+		}
+		id.withCString { eventHandler($0, coldStart) }
+	}
+}
+
+@_cdecl("init_shortcuts_plugin")
+func initShortcutsPlugin() -> Plugin {
+	return ShortcutsPlugin()
+}
+
+@_cdecl("set_shortcut_event_handler")
+func setShortcutEventHandler(handler: @escaping ShortcutEventHandler) {
+	ShortcutsPlugin.shared?.eventHandler = handler
+}
@@ -0,0 +1,112 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import UIKit
+import WebKit
+
+/// Parses a `#RRGGBB` or `#RRGGBBAA` color.
+private func parseColor(_ color: String) -> UIColor? {
+	let hex = color.hasPrefix("#") ? String(color.dropFirst()) : color
+	guard hex.count == 6 || hex.count == 8, var rgba = UInt64(hex, radix: 16) else {
+		return nil
+	}
+	if hex.count == 6 {
+		rgba = rgba << 8 | 0xff
+	}
+	return UIColor(
+		red: CGFloat(rgba >> 24 & 0xff) / 255,
+		green: CGFloat(rgba >> 16 & 0xff) / 255,
+		blue: CGFloat(rgba >> 8 & 0xff) / 255,
+		alpha: CGFloat(rgba & 0xff) / 255
+	)
+}
+
+/// Runs the closure on the main thread, directly when the plugin is invoked from it.
+private func onMainThread(_ f: @escaping () -> Void) {
+	if Thread.isMainThread {
+		f()
+	} else {
+		DispatchQueue.main.async(execute: f)
+	}
+}
+
+class SystemBarsPlugin: Plugin {
+	private weak var webview: WKWebView?
+	/// The view drawing the status bar color, the status bar itself is transparent.
+	private var statusBarBackground: UIView?
+
+	override func load(webview: WKWebView) {
+		self.webview = webview
+	}
+
+	@objc func setStatusBar(_ invoke: Invoke) {
+		let color = invoke.getString("color")
+		let style = invoke.getString("style")
+		if let color = color, parseColor(color) == nil {
+			invoke.reject("Invalid color `\(color)`")
+			return
+		}
+		onMainThread {
+			if let color = color.flatMap(parseColor) {
+				self.setStatusBarColor(color)
+			}
+			if let style = style {
+				// the app controls the status bar, with `UIViewControllerBasedStatusBarAppearance` set to false
+				switch style {
+				case "lightContent":
+					UIApplication.shared.statusBarStyle = .lightContent
+				case "darkContent":
+					UIApplication.shared.statusBarStyle = .darkContent
+				default:
+					UIApplication.shared.statusBarStyle = .default
+				}
+			}
+			invoke.resolve()
+		}
+	}
+
+	private func setStatusBarColor(_ color: UIColor) {
+		guard let window = webview?.window, let statusBarManager = window.windowScene?.statusBarManager else {
+			return
+		}
+		let background = statusBarBackground ?? UIView()
+		background.frame = statusBarManager.statusBarFrame
+		background.autoresizingMask = [.flexibleWidth, .flexibleBottomMargin]
+		background.backgroundColor = color
+		if background.superview == nil {
+			window.addSubview(background)
+		}
+		statusBarBackground = background
+	}
+
+	@objc func setNavigationBar(_ invoke: Invoke) {
+		// iOS has no navigation bar, only the home indicator
+		invoke.resolve()
+	}
+
+	@objc func setEdgeToEdge(_ invoke: Invoke) {
+		let enable = invoke.getBool("enable", false)
+		onMainThread {
+			self.webview?.scrollView.contentInsetAdjustmentBehavior = enable ? .never : .automatic
+			invoke.resolve()
+		}
+	}
+
+	@objc func safeAreaInsets(_ invoke: Invoke) {
+		onMainThread {
+			let insets = self.webview?.safeAreaInsets ?? .zero
+			invoke.resolve([
+				"top": insets.top,
+				"right": insets.right,
+				"bottom": insets.bottom,
+				"left": insets.left,
+			])
+		}
+	}
+}
+
+@_cdecl("init_system_bars_plugin")
+func initSystemBarsPlugin() -> Plugin {
+	return SystemBarsPlugin()
+}
@@ -108,6 +108,23 @@ func onWebviewCreated(webview: WKWebView, viewController: UIViewController) {
 	PluginManager.shared.onWebviewCreated(webview)
 }
 
+@_cdecl("observe_lifecycle_events")
+func observeLifecycleEvents(callback: @escaping @convention(c) (Int32) -> Void) {
+	// the event codes shared with the Rust code
+	let events: [(Notification.Name, Int32)] = [
+		(UIApplication.willEnterForegroundNotification, 0),
+		(UIApplication.didBecomeActiveNotification, 1),
+		(UIApplication.willResignActiveNotification, 2),
+		(UIApplication.didEnterBackgroundNotification, 3),
+		(UIApplication.didReceiveMemoryWarningNotification, 4),
+	]
+	for (name, event) in events {
+		NotificationCenter.default.addObserver(forName: name, object: nil, queue: .main) { _ in
+			callback(event)
+		}
+	}
+}
+
 @_cdecl("post_ipc_message")
 func postIpcMessage(webview: WKWebView, name: SRString, command: SRString, data: NSDictionary, callback: UInt64, error: UInt64) {
 	let invoke = Invoke(command: command.toString(), callback: callback, error: error, sendResponse: { (fn: UInt64, payload: JsonValue?) -> Void in
@@ -0,0 +1,62 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import Foundation
+#if canImport(WidgetKit)
+import WidgetKit
+#endif
+
+class WidgetsPlugin: Plugin {
+	/// The user defaults of the App Group shared with the widget extension generated with `tauri ios add-extension widget`.
+	private func defaults(_ invoke: Invoke) -> UserDefaults? {
+		let appGroup = invoke.getString("appGroup") ?? ""
+		guard let defaults = UserDefaults(suiteName: appGroup) else {
+			invoke.reject("Failed to open the user defaults of the \(appGroup) App Group")
+			return nil
+		}
+		return defaults
+	}
+
+	@objc func setData(_ invoke: Invoke) {
+		guard let key = invoke.getString("key") else {
+			invoke.reject("key is required")
+			return
+		}
+		guard let defaults = defaults(invoke) else {
+			return
+		}
+		defaults.set(invoke.getString("value"), forKey: key)
+		invoke.resolve()
+	}
+
+	@objc func removeData(_ invoke: Invoke) {
+		guard let key = invoke.getString("key") else {
+			invoke.reject("key is required")
+			return
+		}
+		guard let defaults = defaults(invoke) else {
+			return
+		}
+		defaults.removeObject(forKey: key)
+		invoke.resolve()
+	}
+
+	@objc func reload(_ invoke: Invoke) {
+		#if canImport(WidgetKit)
+		guard #available(iOS 14.0, macOS 11.0, *) else {
+			invoke.unavailable("Widgets require iOS 14")
+			return
+		}
+		WidgetCenter.shared.reloadAllTimelines()
+		invoke.resolve()
+		#else
+		invoke.unavailable("Widgets are not supported")
+		#endif
+	}
+}
+
+@_cdecl("init_widgets_plugin")
+func initWidgetsPlugin() -> Plugin {
+	return WidgetsPlugin()
+}
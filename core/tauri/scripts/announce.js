@@ -0,0 +1,26 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  const live = __TEMPLATE_live__
+  const id = `__TAURI_ANNOUNCER_${live}__`
+  let region = document.getElementById(id)
+  if (!region) {
+    region = document.createElement('div')
+    region.id = id
+    region.setAttribute('role', live === 'assertive' ? 'alert' : 'status')
+    region.setAttribute('aria-live', live)
+    region.setAttribute('aria-atomic', 'true')
+    // visually hidden but still read by screen readers
+    region.style.cssText =
+      'position:absolute;width:1px;height:1px;margin:-1px;padding:0;border:0;overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap'
+    document.body.appendChild(region)
+  }
+
+  // the region is cleared first so the same text is announced again
+  region.textContent = ''
+  window.setTimeout(function () {
+    region.textContent = __TEMPLATE_text__
+  }, 100)
+})()
@@ -1,2 +1,2 @@
-"use strict";var __TAURI_IIFE__=(()=>{var m=Object.defineProperty;var C=Object.getOwnPropertyDescriptor;var E=Object.getOwnPropertyNames;var O=Object.prototype.hasOwnProperty;var p=(n,e)=>{for(var i in e)m(n,i,{get:e[i],enumerable:!0})},W=(n,e,i,o)=>{if(e&&typeof e=="object"||typeof e=="function")for(let a of E(e))!O.call(n,a)&&a!==i&&m(n,a,{get:()=>e[a],enumerable:!(o=C(e,a))||o.enumerable});return n};var N=n=>W(m({},"__esModule",{value:!0}),n);var P=(n,e,i)=>{if(!e.has(n))throw TypeError("Cannot "+i)};var _=(n,e,i)=>(P(n,e,"read from private field"),i?i.call(n):e.get(n)),D=(n,e,i)=>{if(e.has(n))throw TypeError("Cannot add the same private member more than once");e instanceof WeakSet?e.add(n):e.set(n,i)},w=(n,e,i,o)=>(P(n,e,"write to private field"),o?o.call(n,i):e.set(n,i),i);var hn={};p(hn,{event:()=>f,invoke:()=>fn,path:()=>h,tauri:()=>y});var f={};p(f,{TauriEvent:()=>b,emit:()=>F,listen:()=>I,once:()=>U});var y={};p(y,{Channel:()=>l,PluginListener:()=>g,addPluginListener:()=>L,convertFileSrc:()=>k,invoke:()=>t,transformCallback:()=>u});function T(){return window.crypto.getRandomValues(new Uint32Array(1))[0]}function u(n,e=!1){let i=T(),o=`_${i}`;return Object.defineProperty(window,o,{value:a=>(e&&Reflect.deleteProperty(window,o),n?.(a)),writable:!1,configurable:!0}),i}var c,l=class{constructor(){this.__TAURI_CHANNEL_MARKER__=!0;D(this,c,()=>{});this.id=u(e=>{_(this,c).call(this,e)})}set onmessage(e){w(this,c,e)}get onmessage(){return _(this,c)}toJSON(){return`__CHANNEL__:${this.id}`}};c=new WeakMap;var g=class{constructor(e,i,o){this.plugin=e,this.event=i,this.channelId=o}async unregister(){return t(`plugin:${this.plugin}|remove_listener`,{event:this.event,channelId:this.channelId})}};async function L(n,e,i){let o=new l;return o.onmessage=i,t(`plugin:${n}|register_listener`,{event:e,handler:o}).then(()=>new g(n,e,o.id))}async function t(n,e={}){return new Promise((i,o)=>{let a=u(d=>{i(d),Reflect.deleteProperty(window,`_${v}`)},!0),v=u(d=>{o(d),Reflect.deleteProperty(window,`_${a}`)},!0);window.__TAURI_IPC__({cmd:n,callback:a,error:v,...e})})}function k(n,e="asset"){let i=encodeURIComponent(n);return navigator.userAgent.includes("Windows")?`https://${e}.localhost/${i}`:`${e}://localhost/${i}`}var b=(s=>(s.WINDOW_RESIZED="tauri://resize",s.WINDOW_MOVED="tauri://move",s.WINDOW_CLOSE_REQUESTED="tauri://close-requested",s.WINDOW_CREATED="tauri://window-created",s.WINDOW_DESTROYED="tauri://destroyed",s.WINDOW_FOCUS="tauri://focus",s.WINDOW_BLUR="tauri://blur",s.WINDOW_SCALE_FACTOR_CHANGED="tauri://scale-change",s.WINDOW_THEME_CHANGED="tauri://theme-changed",s.WINDOW_FILE_DROP="tauri://file-drop",s.WINDOW_FILE_DROP_HOVER="tauri://file-drop-hover",s.WINDOW_FILE_DROP_CANCELLED="tauri://file-drop-cancelled",s.MENU="tauri://menu",s))(b||{});async function A(n,e){await t("plugin:event|unlisten",{event:n,eventId:e})}async function I(n,e,i){return t("plugin:event|listen",{event:n,windowLabel:i?.target,handler:u(e)}).then(o=>async()=>A(n,o))}async function U(n,e,i){return I(n,o=>{e(o),A(n,o.id).catch(()=>{})},i)}async function F(n,e,i){await t("plugin:event|emit",{event:n,windowLabel:i?.target,payload:e})}var h={};p(h,{BaseDirectory:()=>R,appCacheDir:()=>S,appConfigDir:()=>x,appDataDir:()=>$,appLocalDataDir:()=>H,appLogDir:()=>sn,audioDir:()=>M,basename:()=>_n,cacheDir:()=>V,configDir:()=>j,dataDir:()=>z,delimiter:()=>un,desktopDir:()=>G,dirname:()=>dn,documentDir:()=>q,downloadDir:()=>J,executableDir:()=>K,extname:()=>mn,fontDir:()=>Q,homeDir:()=>Y,isAbsolute:()=>yn,join:()=>gn,localDataDir:()=>Z,normalize:()=>ln,pictureDir:()=>X,publicDir:()=>B,resolve:()=>pn,resolveResource:()=>en,resourceDir:()=>nn,runtimeDir:()=>rn,sep:()=>cn,tempDir:()=>an,templateDir:()=>tn,videoDir:()=>on});var R=(r=>(r[r.Audio=1]="Audio",r[r.Cache=2]="Cache",r[r.Config=3]="Config",r[r.Data=4]="Data",r[r.LocalData=5]="LocalData",r[r.Document=6]="Document",r[r.Download=7]="Download",r[r.Picture=8]="Picture",r[r.Public=9]="Public",r[r.Video=10]="Video",r[r.Resource=11]="Resource",r[r.Temp=12]="Temp",r[r.AppConfig=13]="AppConfig",r[r.AppData=14]="AppData",r[r.AppLocalData=15]="AppLocalData",r[r.AppCache=16]="AppCache",r[r.AppLog=17]="AppLog",r[r.Desktop=18]="Desktop",r[r.Executable=19]="Executable",r[r.Font=20]="Font",r[r.Home=21]="Home",r[r.Runtime=22]="Runtime",r[r.Template=23]="Template",r))(R||{});async function x(){return t("plugin:path|resolve_directory",{directory:13})}async function $(){return t("plugin:path|resolve_directory",{directory:14})}async function H(){return t("plugin:path|resolve_directory",{directory:15})}async function S(){return t("plugin:path|resolve_directory",{directory:16})}async function M(){return t("plugin:path|resolve_directory",{directory:1})}async function V(){return t("plugin:path|resolve_directory",{directory:2})}async function j(){return t("plugin:path|resolve_directory",{directory:3})}async function z(){return t("plugin:path|resolve_directory",{directory:4})}async function G(){return t("plugin:path|resolve_directory",{directory:18})}async function q(){return t("plugin:path|resolve_directory",{directory:6})}async function J(){return t("plugin:path|resolve_directory",{directory:7})}async function K(){return t("plugin:path|resolve_directory",{directory:19})}async function Q(){return t("plugin:path|resolve_directory",{directory:20})}async function Y(){return t("plugin:path|resolve_directory",{directory:21})}async function Z(){return t("plugin:path|resolve_directory",{directory:5})}async function X(){return t("plugin:path|resolve_directory",{directory:8})}async function B(){return t("plugin:path|resolve_directory",{directory:9})}async function nn(){return t("plugin:path|resolve_directory",{directory:11})}async function en(n){return t("plugin:path|resolve_directory",{directory:11,path:n})}async function rn(){return t("plugin:path|resolve_directory",{directory:22})}async function tn(){return t("plugin:path|resolve_directory",{directory:23})}async function on(){return t("plugin:path|resolve_directory",{directory:10})}async function sn(){return t("plugin:path|resolve_directory",{directory:17})}async function an(n){return t("plugin:path|resolve_directory",{directory:12})}function cn(){return window.__TAURI__.path.__sep}function un(){return window.__TAURI__.path.__delimiter}async function pn(...n){return t("plugin:path|resolve",{paths:n})}async function ln(n){return t("plugin:path|normalize",{path:n})}async function gn(...n){return t("plugin:path|join",{paths:n})}async function dn(n){return t("plugin:path|dirname",{path:n})}async function mn(n){return t("plugin:path|extname",{path:n})}async function _n(n,e){return t("plugin:path|basename",{path:n,ext:e})}async function yn(n){return t("plugin:path|isAbsolute",{path:n})}var fn=t;return N(hn);})();
+"use strict";var __TAURI_IIFE__=(()=>{var __defProp=Object.defineProperty;var __getOwnPropDesc=Object.getOwnPropertyDescriptor;var __getOwnPropNames=Object.getOwnPropertyNames;var __hasOwnProp=Object.prototype.hasOwnProperty;var __export=(target,all)=>{for(var name in all)__defProp(target,name,{get:all[name],enumerable:!0})},__copyProps=(to,from,except,desc)=>{if(from&&typeof from=="object"||typeof from=="function")for(let key of __getOwnPropNames(from))!__hasOwnProp.call(to,key)&&key!==except&&__defProp(to,key,{get:()=>from[key],enumerable:!(desc=__getOwnPropDesc(from,key))||desc.enumerable});return to};var __toCommonJS=mod=>__copyProps(__defProp({},"__esModule",{value:!0}),mod);var __accessCheck=(obj,member,msg)=>{if(!member.has(obj))throw TypeError("Cannot "+msg)};var __privateGet=(obj,member,getter)=>(__accessCheck(obj,member,"read from private field"),getter?getter.call(obj):member.get(obj)),__privateAdd=(obj,member,value)=>{if(member.has(obj))throw TypeError("Cannot add the same private member more than once");member instanceof WeakSet?member.add(obj):member.set(obj,value)},__privateSet=(obj,member,value,setter)=>(__accessCheck(obj,member,"write to private field"),setter?setter.call(obj,value):member.set(obj,value),value);var index_exports={};__export(index_exports,{auth:()=>auth_exports,bluetooth:()=>bluetooth_exports,config:()=>config_exports,event:()=>event_exports,haptics:()=>haptics_exports,hid:()=>hid_exports,hotRestart:()=>hotRestart_exports,invoke:()=>invoke2,keyring:()=>keyring_exports,path:()=>path_exports,purchases:()=>purchases_exports,push:()=>push_exports,sensors:()=>sensors_exports,serial:()=>serial_exports,share:()=>share_exports,shortcuts:()=>shortcuts_exports,splashscreen:()=>splashscreen_exports,systemBars:()=>systemBars_exports,tauri:()=>tauri_exports,titleBar:()=>titleBar_exports,usb:()=>usb_exports,widgets:()=>widgets_exports});var tauri_exports={};__export(tauri_exports,{Channel:()=>Channel,PluginListener:()=>PluginListener,addPluginListener:()=>addPluginListener,convertFileSrc:()=>convertFileSrc,invoke:()=>invoke,readSharedBuffer:()=>readSharedBuffer,transformCallback:()=>transformCallback});var auth_exports={};__export(auth_exports,{authenticate:()=>authenticate});var bluetooth_exports={};__export(bluetooth_exports,{Connection:()=>Connection,scan:()=>scan});var config_exports={};__export(config_exports,{getConfig:()=>getConfig});var event_exports={};__export(event_exports,{TauriEvent:()=>TauriEvent,emit:()=>emit,listen:()=>listen,once:()=>once});var haptics_exports={};__export(haptics_exports,{impactFeedback:()=>impactFeedback,notificationFeedback:()=>notificationFeedback,selectionFeedback:()=>selectionFeedback});var hid_exports={};__export(hid_exports,{devices:()=>devices,isGamepad:()=>isGamepad,listen:()=>listen2,write:()=>write});var hotRestart_exports={};__export(hotRestart_exports,{onBeforeHotRestart:()=>onBeforeHotRestart,onHotRestart:()=>onHotRestart});var keyring_exports={};__export(keyring_exports,{deletePassword:()=>deletePassword,getPassword:()=>getPassword,setPassword:()=>setPassword});var path_exports={};__export(path_exports,{BaseDirectory:()=>BaseDirectory,appCacheDir:()=>appCacheDir,appConfigDir:()=>appConfigDir,appDataDir:()=>appDataDir,appLocalDataDir:()=>appLocalDataDir,appLogDir:()=>appLogDir,audioDir:()=>audioDir,basename:()=>basename,cacheDir:()=>cacheDir,configDir:()=>configDir,dataDir:()=>dataDir,delimiter:()=>delimiter,desktopDir:()=>desktopDir,dirname:()=>dirname,documentDir:()=>documentDir,downloadDir:()=>downloadDir,executableDir:()=>executableDir,extname:()=>extname,fontDir:()=>fontDir,homeDir:()=>homeDir,isAbsolute:()=>isAbsolute,join:()=>join,localDataDir:()=>localDataDir,normalize:()=>normalize,pictureDir:()=>pictureDir,publicDir:()=>publicDir,resolve:()=>resolve,resolveResource:()=>resolveResource,resourceDir:()=>resourceDir,runtimeDir:()=>runtimeDir,sep:()=>sep,tempDir:()=>tempDir,templateDir:()=>templateDir,videoDir:()=>videoDir});var purchases_exports={};__export(purchases_exports,{finishPurchase:()=>finishPurchase,getProducts:()=>getProducts,onPurchaseUpdated:()=>onPurchaseUpdated,purchase:()=>purchase,restorePurchases:()=>restorePurchases});var push_exports={};__export(push_exports,{getLaunchNotification:()=>getLaunchNotification,onPushNotification:()=>onPushNotification,onPushTokenChanged:()=>onPushTokenChanged,registerForPushNotifications:()=>registerForPushNotifications});var sensors_exports={};__export(sensors_exports,{isSensorAvailable:()=>isSensorAvailable,watchSensor:()=>watchSensor});var serial_exports={};__export(serial_exports,{SerialPort:()=>SerialPort,availablePorts:()=>availablePorts});var share_exports={};__export(share_exports,{share:()=>share});var shortcuts_exports={};__export(shortcuts_exports,{getLaunchShortcut:()=>getLaunchShortcut,onShortcutActivated:()=>onShortcutActivated,setShortcuts:()=>setShortcuts});var splashscreen_exports={};__export(splashscreen_exports,{closeSplashscreen:()=>closeSplashscreen});var systemBars_exports={};__export(systemBars_exports,{safeAreaInsets:()=>safeAreaInsets,setEdgeToEdge:()=>setEdgeToEdge,setNavigationBar:()=>setNavigationBar,setStatusBar:()=>setStatusBar});var titleBar_exports={};__export(titleBar_exports,{onOverlayRectChanged:()=>onOverlayRectChanged,overlayRect:()=>overlayRect});var usb_exports={};__export(usb_exports,{UsbDevice:()=>UsbDevice,devices:()=>devices2});var widgets_exports={};__export(widgets_exports,{reloadWidgets:()=>reloadWidgets,removeWidgetData:()=>removeWidgetData,setWidgetData:()=>setWidgetData});function uid(){return window.crypto.getRandomValues(new Uint32Array(1))[0]}function transformCallback(callback,once=!1){const identifier=uid();const prop=`_${identifier}`;Object.defineProperty(window,prop,{value:(result)=>{if(once){Reflect.deleteProperty(window,prop)}return callback?.(result)},writable:!1,configurable:!0});return identifier}var _onmessage;var Channel=class{constructor(){this.__TAURI_CHANNEL_MARKER__=!0;__privateAdd(this,_onmessage,()=>{});this.id=transformCallback((response)=>{__privateGet(this,_onmessage).call(this,response)})}set onmessage(handler){__privateSet(this,_onmessage,handler)}get onmessage(){return __privateGet(this,_onmessage)}toJSON(){return`__CHANNEL__:${this.id}`}};_onmessage=new WeakMap;var PluginListener=class{constructor(plugin,event,channelId){this.plugin=plugin;this.event=event;this.channelId=channelId}async unregister(){return invoke(`plugin:${this.plugin}|remove_listener`,{event:this.event,channelId:this.channelId})}};async function addPluginListener(plugin,event,cb){const handler=new Channel();handler.onmessage=cb;return invoke(`plugin:${plugin}|register_listener`,{event,handler}).then(()=>new PluginListener(plugin,event,handler.id))}async function invoke(cmd,args={},options){return new Promise((resolve,reject)=>{const signal=options?.signal;if(signal?.aborted){reject(signal.reason);return}const cancellationId=signal?uid():undefined;const onAbort=()=>{Reflect.deleteProperty(window,`_${callback}`);Reflect.deleteProperty(window,`_${error}`);reject(signal?.reason);void invoke("plugin:ipc|cancel",{id:cancellationId})};const callback=transformCallback((e)=>{signal?.removeEventListener("abort",onAbort);resolve(e);Reflect.deleteProperty(window,`_${error}`)},!0);const error=transformCallback((e)=>{signal?.removeEventListener("abort",onAbort);reject(e);Reflect.deleteProperty(window,`_${callback}`)},!0);signal?.addEventListener("abort",onAbort,{once:!0});window.__TAURI_IPC__({cmd,callback,error,...(cancellationId===undefined?{}:{__TAURI_CANCELLATION_ID__:cancellationId}),...args})})}function convertFileSrc(filePath,protocol="asset"){const path=encodeURIComponent(filePath);return navigator.userAgent.includes("Windows")?`https://${protocol}.localhost/${path}`:`${protocol}://localhost/${path}`}async function readSharedBuffer(url,range){const response=await fetch(url,{headers:range?{Range:`bytes=${range.start}-${range.end}`}:{}});if(!response.ok){throw new Error(`the shared buffer ${url} is not available (${response.status})`)}return response.arrayBuffer()}async function authenticate(reason){return invoke("plugin:auth|authenticate",{reason})}async function scan(options={}){return invoke("plugin:bluetooth|scan",{...options})}var Connection=class{constructor(id){this.id=id}static async connect(id){await invoke("plugin:bluetooth|connect",{id});return new Connection(id)}async read(service,characteristic){return Uint8Array.from(await invoke("plugin:bluetooth|read",{id:this.id,service,characteristic}))}async write(service,characteristic,data,options={}){return invoke("plugin:bluetooth|write",{id:this.id,service,characteristic,data:Array.from(data),withResponse:options.withResponse})}async subscribe(service,characteristic,handler){const onValue=new Channel();onValue.onmessage=(value)=>handler(Uint8Array.from(value));return invoke("plugin:bluetooth|subscribe",{id:this.id,service,characteristic,onValue})}async disconnect(){return invoke("plugin:bluetooth|disconnect",{id:this.id})}};async function getConfig(){return invoke("plugin:config|get_config")}var TauriEvent=(TauriEvent=>(TauriEvent.WINDOW_RESIZED="tauri://resize",TauriEvent.WINDOW_MOVED="tauri://move",TauriEvent.WINDOW_CLOSE_REQUESTED="tauri://close-requested",TauriEvent.WINDOW_CREATED="tauri://window-created",TauriEvent.WINDOW_DESTROYED="tauri://destroyed",TauriEvent.WINDOW_FOCUS="tauri://focus",TauriEvent.WINDOW_BLUR="tauri://blur",TauriEvent.WINDOW_SCALE_FACTOR_CHANGED="tauri://scale-change",TauriEvent.WINDOW_THEME_CHANGED="tauri://theme-changed",TauriEvent.WINDOW_FILE_DROP="tauri://file-drop",TauriEvent.WINDOW_FILE_DROP_HOVER="tauri://file-drop-hover",TauriEvent.WINDOW_FILE_DROP_CANCELLED="tauri://file-drop-cancelled",TauriEvent.MENU="tauri://menu",TauriEvent.BEFORE_HOT_RESTART="tauri://before-hot-restart",TauriEvent.MEMORY_PRESSURE="tauri://memory-pressure",TauriEvent.LIFECYCLE="tauri://lifecycle",TauriEvent.PUSH="tauri://push",TauriEvent.PURCHASE="tauri://purchase",TauriEvent.SHORTCUT="tauri://shortcut",TauriEvent))(TauriEvent||{});async function _unlisten(event,eventId){await invoke("plugin:event|unlisten",{event,eventId})}async function listen(event,handler,options){return invoke("plugin:event|listen",{event,windowLabel:options?.target,handler:transformCallback(handler)}).then((eventId)=>{return async()=>_unlisten(event,eventId)})}async function once(event,handler,options){return listen(event,(eventData)=>{handler(eventData);_unlisten(event,eventData.id).catch(()=>{})},options)}async function emit(event,payload,options){await invoke("plugin:event|emit",{event,windowLabel:options?.target,payload})}async function impactFeedback(style="medium"){return invoke("plugin:haptics|impact",{style})}async function selectionFeedback(){return invoke("plugin:haptics|selection")}async function notificationFeedback(feedback){return invoke("plugin:haptics|notification",{feedback})}async function devices(){return invoke("plugin:hid|devices")}function isGamepad(device){return(device.usagePage===0x01&&[0x04,0x05,0x08].includes(device.usage))}async function listen2(path,handler){const onReport=new Channel();onReport.onmessage=(report)=>handler(Uint8Array.from(report));const id=await invoke("plugin:hid|listen",{path,onReport});return async()=>invoke("plugin:hid|unlisten",{id})}async function write(path,report){return invoke("plugin:hid|write",{path,report:Array.from(report)})}async function onBeforeHotRestart(handler){return listen(TauriEvent.BEFORE_HOT_RESTART,async()=>{const state=await handler();await invoke("plugin:hot-restart|save",{state:state??null})})}function onHotRestart(handler){const state=window.__TAURI_HOT_RESTART__?.[window.__TAURI_METADATA__.__currentWindow.label];if(state){void Promise.resolve().then(()=>{handler(state)})}}async function setPassword(service,account,password){return invoke("plugin:keyring|set_password",{service,account,password})}async function getPassword(service,account){return invoke("plugin:keyring|get_password",{service,account})}async function deletePassword(service,account){return invoke("plugin:keyring|delete_password",{service,account})}var BaseDirectory=(BaseDirectory=>(BaseDirectory[BaseDirectory.Audio=1]="Audio",BaseDirectory[BaseDirectory.Cache=2]="Cache",BaseDirectory[BaseDirectory.Config=3]="Config",BaseDirectory[BaseDirectory.Data=4]="Data",BaseDirectory[BaseDirectory.LocalData=5]="LocalData",BaseDirectory[BaseDirectory.Document=6]="Document",BaseDirectory[BaseDirectory.Download=7]="Download",BaseDirectory[BaseDirectory.Picture=8]="Picture",BaseDirectory[BaseDirectory.Public=9]="Public",BaseDirectory[BaseDirectory.Video=10]="Video",BaseDirectory[BaseDirectory.Resource=11]="Resource",BaseDirectory[BaseDirectory.Temp=12]="Temp",BaseDirectory[BaseDirectory.AppConfig=13]="AppConfig",BaseDirectory[BaseDirectory.AppData=14]="AppData",BaseDirectory[BaseDirectory.AppLocalData=15]="AppLocalData",BaseDirectory[BaseDirectory.AppCache=16]="AppCache",BaseDirectory[BaseDirectory.AppLog=17]="AppLog",BaseDirectory[BaseDirectory.Desktop=18]="Desktop",BaseDirectory[BaseDirectory.Executable=19]="Executable",BaseDirectory[BaseDirectory.Font=20]="Font",BaseDirectory[BaseDirectory.Home=21]="Home",BaseDirectory[BaseDirectory.Runtime=22]="Runtime",BaseDirectory[BaseDirectory.Template=23]="Template",BaseDirectory))(BaseDirectory||{});async function appConfigDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.AppConfig})}async function appDataDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.AppData})}async function appLocalDataDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.AppLocalData})}async function appCacheDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.AppCache})}async function audioDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Audio})}async function cacheDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Cache})}async function configDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Config})}async function dataDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Data})}async function desktopDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Desktop})}async function documentDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Document})}async function downloadDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Download})}async function executableDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Executable})}async function fontDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Font})}async function homeDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Home})}async function localDataDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.LocalData})}async function pictureDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Picture})}async function publicDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Public})}async function resourceDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Resource})}async function resolveResource(resourcePath){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Resource,path:resourcePath})}async function runtimeDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Runtime})}async function templateDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Template})}async function videoDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Video})}async function appLogDir(){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.AppLog})}async function tempDir(path){return invoke("plugin:path|resolve_directory",{directory:BaseDirectory.Temp})}function sep(){return window.__TAURI__.path.__sep}function delimiter(){return window.__TAURI__.path.__delimiter}async function resolve(...paths){return invoke("plugin:path|resolve",{paths})}async function normalize(path){return invoke("plugin:path|normalize",{path})}async function join(...paths){return invoke("plugin:path|join",{paths})}async function dirname(path){return invoke("plugin:path|dirname",{path})}async function extname(path){return invoke("plugin:path|extname",{path})}async function basename(path,ext){return invoke("plugin:path|basename",{path,ext})}async function isAbsolute(path){return invoke("plugin:path|isAbsolute",{path})}async function getProducts(ids,kind="oneTime"){return invoke("plugin:purchases|get_products",{ids,kind})}async function purchase(productId,kind="oneTime"){return invoke("plugin:purchases|purchase",{productId,kind})}async function finishPurchase(purchase,consume=!1){return invoke("plugin:purchases|finish",{purchase,consume})}async function restorePurchases(){return invoke("plugin:purchases|restore")}async function onPurchaseUpdated(handler){return listen(TauriEvent.PURCHASE,({payload})=>{handler(payload)})}async function registerForPushNotifications(){return invoke("plugin:push|register")}async function getLaunchNotification(){return invoke("plugin:push|launch_notification")}async function onPushNotification(handler){return listen(TauriEvent.PUSH,({payload})=>{if(payload.type==="notification"){handler(payload)}})}async function onPushTokenChanged(handler){return listen(TauriEvent.PUSH,({payload})=>{if(payload.type==="token"){handler(payload.token)}})}async function isSensorAvailable(sensor){return invoke("plugin:sensors|is_available",{sensor})}async function watchSensor(sensor,interval,handler){const onReading=new Channel();onReading.onmessage=handler;const id=await invoke("plugin:sensors|watch",{sensor,interval,onReading});return async()=>invoke("plugin:sensors|unwatch",{id})}async function availablePorts(){return invoke("plugin:serial|available")}var _id;var SerialPort=class{constructor(path,id){__privateAdd(this,_id);this.path=path;__privateSet(this,_id,id)}static async open(path,options,handler){const onEvent=new Channel();onEvent.onmessage=(event)=>{handler(event.type==="data"?{type:"data",data:Uint8Array.from(event.data)}:event)};const id=await invoke("plugin:serial|open",{path,options,onEvent});return new SerialPort(path,id)}async write(data){return invoke("plugin:serial|write",{id:__privateGet(this,_id),data:Array.from(data)})}async close(){return invoke("plugin:serial|close",{id:__privateGet(this,_id)})}};_id=new WeakMap;async function share(items){return invoke("plugin:share|share",{items})}async function setShortcuts(shortcuts){return invoke("plugin:shortcuts|set_shortcuts",{shortcuts})}async function getLaunchShortcut(){return invoke("plugin:shortcuts|launch_shortcut")}async function onShortcutActivated(handler){return listen(TauriEvent.SHORTCUT,({payload})=>{handler(payload)})}async function closeSplashscreen(){return invoke("plugin:splashscreen|close")}async function setStatusBar(bar){return invoke("plugin:system-bars|set_status_bar",{bar})}async function setNavigationBar(bar){return invoke("plugin:system-bars|set_navigation_bar",{bar})}async function setEdgeToEdge(enable){return invoke("plugin:system-bars|set_edge_to_edge",{enable})}async function safeAreaInsets(){return invoke("plugin:system-bars|safe_area_insets")}async function overlayRect(){return invoke("plugin:title-bar|overlay_rect")}function onOverlayRectChanged(handler){const listener=(event)=>{handler((event).detail)};window.addEventListener("tauri://title-bar-overlay",listener);return()=>window.removeEventListener("tauri://title-bar-overlay",listener)}async function devices2(){return invoke("plugin:usb|devices")}function setupPacket(setup){const types=["standard","class","vendor"];const recipients=["device","interface","endpoint","other"];return{requestType:(types.indexOf(setup.requestType)<<5)|recipients.indexOf(setup.recipient),request:setup.request,value:setup.value,index:setup.index}}var _id;var UsbDevice=class{constructor(path,id){__privateAdd(this,_id);this.path=path;__privateSet(this,_id,id)}static async open(path){const id=await invoke("plugin:usb|open",{path});return new UsbDevice(path,id)}async claimInterface(interfaceNumber){return invoke("plugin:usb|claim_interface",{id:__privateGet(this,_id),interface:interfaceNumber})}async releaseInterface(interfaceNumber){return invoke("plugin:usb|release_interface",{id:__privateGet(this,_id),interface:interfaceNumber})}async transferIn(endpoint,length,options={}){return Uint8Array.from(await invoke("plugin:usb|transfer_in",{id:__privateGet(this,_id),endpoint,length,timeout:options.timeout}))}async transferOut(endpoint,data,options={}){return invoke("plugin:usb|transfer_out",{id:__privateGet(this,_id),endpoint,data:Array.from(data),timeout:options.timeout})}async controlTransferIn(setup,length,options={}){return Uint8Array.from(await invoke("plugin:usb|control_transfer_in",{id:__privateGet(this,_id),setup:setupPacket(setup),length,timeout:options.timeout}))}async controlTransferOut(setup,data=[],options={}){return invoke("plugin:usb|control_transfer_out",{id:__privateGet(this,_id),setup:setupPacket(setup),data:Array.from(data),timeout:options.timeout})}async close(){return invoke("plugin:usb|close",{id:__privateGet(this,_id)})}};_id=new WeakMap;async function setWidgetData(key,data){return invoke("plugin:widgets|set_data",{key,data})}async function removeWidgetData(key){return invoke("plugin:widgets|remove_data",{key})}async function reloadWidgets(){return invoke("plugin:widgets|reload")}const invoke2=tauri_exports.invoke;return __toCommonJS(index_exports);})();
 window.__TAURI__ = __TAURI_IIFE__
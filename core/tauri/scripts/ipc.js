@@ -131,6 +131,12 @@
     window.addEventListener(
       'message',
       (event) => {
+        // ignore messages that weren't sent by the isolation frame, e.g. from other frames of a remote page
+        const frame = document.querySelector('iframe#__tauri_isolation__')
+        if (!frame || event.source !== frame.contentWindow) {
+          return
+        }
+
         // watch for the isolation frame being ready and flush any queued messages
         if (event.data === '__TAURI_ISOLATION_READY__') {
           isolation.ready = true
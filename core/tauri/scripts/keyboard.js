@@ -0,0 +1,43 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  function send(event) {
+    window.__TAURI_INVOKE__('plugin:keyboard|emit', { event })
+  }
+
+  function onKey(e) {
+    send({
+      type: e.type === 'keydown' ? 'keyDown' : 'keyUp',
+      key: e.key,
+      code: e.code,
+      location: e.location,
+      repeat: e.repeat,
+      // the key events of a composition are sent too, with `isComposing` set
+      isComposing: e.isComposing || e.keyCode === 229,
+      shiftKey: e.shiftKey,
+      ctrlKey: e.ctrlKey,
+      altKey: e.altKey,
+      metaKey: e.metaKey
+    })
+  }
+
+  function onComposition(e) {
+    send({
+      type: {
+        compositionstart: 'compositionStart',
+        compositionupdate: 'compositionUpdate',
+        compositionend: 'compositionEnd'
+      }[e.type],
+      data: e.data || ''
+    })
+  }
+
+  // the capture phase receives the events before the page handlers can stop them
+  window.addEventListener('keydown', onKey, true)
+  window.addEventListener('keyup', onKey, true)
+  window.addEventListener('compositionstart', onComposition, true)
+  window.addEventListener('compositionupdate', onComposition, true)
+  window.addEventListener('compositionend', onComposition, true)
+})()
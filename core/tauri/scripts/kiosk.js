@@ -0,0 +1,33 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  window.addEventListener(
+    'contextmenu',
+    function (event) {
+      event.preventDefault()
+    },
+    true
+  )
+
+  // the reload, devtools, print and find shortcuts of the webviews
+  window.addEventListener(
+    'keydown',
+    function (event) {
+      var key = event.key.toLowerCase()
+      var modifier = event.ctrlKey || event.metaKey
+      var blocked =
+        key === 'f5' ||
+        key === 'f7' ||
+        key === 'f12' ||
+        (modifier && ['r', 'p', 'f', 'g', 'u'].indexOf(key) !== -1) ||
+        (modifier && event.shiftKey && ['i', 'j', 'c'].indexOf(key) !== -1)
+      if (blocked) {
+        event.preventDefault()
+        event.stopImmediatePropagation()
+      }
+    },
+    true
+  )
+})()
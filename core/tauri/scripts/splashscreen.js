@@ -0,0 +1,33 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  function fitImage() {
+    if (document.contentType.indexOf('image/') !== 0) {
+      return
+    }
+    var style = document.createElement('style')
+    style.textContent =
+      'html, body { margin: 0; width: 100%; height: 100%; overflow: hidden; background: transparent; }' +
+      'body { display: flex; align-items: center; justify-content: center; }' +
+      'img { width: 100%; height: 100%; object-fit: contain; }'
+    document.head.appendChild(style)
+  }
+
+  function fadeOut(duration) {
+    var root = document.documentElement
+    root.style.transition = 'opacity ' + duration + 'ms ease-out'
+    root.style.opacity = '0'
+  }
+
+  Object.defineProperty(window, '__TAURI_SPLASHSCREEN__', {
+    value: { fadeOut: fadeOut }
+  })
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', fitImage)
+  } else {
+    fitImage()
+  }
+})()
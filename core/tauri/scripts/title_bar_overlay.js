@@ -0,0 +1,38 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  function update(rect) {
+    var style = document.documentElement.style
+    var properties = ['x', 'y', 'width', 'height']
+    for (var i = 0; i < properties.length; i++) {
+      var name = '--tauri-title-bar-overlay-' + properties[i]
+      if (rect) {
+        style.setProperty(name, rect[properties[i]] + 'px')
+      } else {
+        style.removeProperty(name)
+      }
+    }
+    window.dispatchEvent(
+      new CustomEvent('tauri://title-bar-overlay', { detail: rect })
+    )
+  }
+
+  Object.defineProperty(window, '__TAURI_TITLE_BAR_OVERLAY__', {
+    value: { update: update }
+  })
+
+  function load() {
+    window
+      .__TAURI_INVOKE__('plugin:title-bar|overlay_rect')
+      .then(update)
+      .catch(function () {})
+  }
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', load)
+  } else {
+    load()
+  }
+})()
@@ -0,0 +1,102 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Screen reader announcements, made with ARIA live regions of the webview which the
+//! webview exposes to the platform accessibility APIs.
+
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+
+/// How a screen reader reads an announcement made with [`crate::Window::announce`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AnnouncementPriority {
+  /// Read when the user is idle, e.g. a completed download.
+  #[default]
+  Polite,
+  /// Read immediately, interrupting the current speech, e.g. an error.
+  Assertive,
+}
+
+impl AnnouncementPriority {
+  fn live(self) -> &'static str {
+    match self {
+      Self::Polite => "polite",
+      Self::Assertive => "assertive",
+    }
+  }
+}
+
+#[derive(Template)]
+#[default_template("../scripts/announce.js")]
+struct AnnounceJavascript<'a> {
+  text: &'a str,
+  live: &'a str,
+}
+
+/// The script writing the text to the live region of the priority, created on the first announcement of the page.
+pub(crate) fn announce_script(text: &str, priority: AnnouncementPriority) -> crate::Result<String> {
+  AnnounceJavascript {
+    text,
+    live: priority.live(),
+  }
+  .render_default(&Default::default())
+  .map(|js| js.into_string())
+  .map_err(Into::into)
+}
+
+/// Whether a screen reader is running: Narrator or a third-party one on Windows, VoiceOver on macOS
+/// and the screen reader enabled in the GNOME accessibility settings on Linux.
+pub(crate) fn screen_reader_active() -> bool {
+  #[cfg(windows)]
+  return unsafe {
+    use windows::Win32::{
+      Foundation::BOOL,
+      UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETSCREENREADER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+      },
+    };
+
+    let mut active = BOOL(0);
+    SystemParametersInfoW(
+      SPI_GETSCREENREADER,
+      0,
+      Some(&mut active as *mut BOOL as *const _),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+    .as_bool()
+      && active.as_bool()
+  };
+
+  #[cfg(target_os = "macos")]
+  return unsafe {
+    use cocoa::base::{id, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+    let active: BOOL = msg_send![workspace, isVoiceOverEnabled];
+    active != NO
+  };
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  return {
+    use gtk::gio::{prelude::SettingsExt, Settings, SettingsSchemaSource};
+
+    const SCHEMA: &str = "org.gnome.desktop.a11y.applications";
+    // `Settings::new` aborts when the schema is not installed
+    SettingsSchemaSource::default()
+      .and_then(|source| source.lookup(SCHEMA, true))
+      .map_or(false, |_| {
+        Settings::new(SCHEMA).boolean("screen-reader-enabled")
+      })
+  };
+
+  #[cfg(mobile)]
+  return false;
+}
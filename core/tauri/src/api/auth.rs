@@ -0,0 +1,326 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Local authentication of the user with the prompt of the operating system, e.g. to unlock the app.
+//!
+//! The functions block until the user completes the prompt, so they must not be called on the main thread.
+//!
+//! ## Platform-specific
+//!
+//! - **macOS / iOS**: Touch ID, Face ID or the password of the user with the LocalAuthentication framework.
+//! - **Windows**: Windows Hello, i.e. the biometrics or the PIN of the user.
+//! - **Linux**: Polkit, checking the `<identifier>.authenticate` action of the app for the calling process.
+//!   The app must install the action, e.g. in `/usr/share/polkit-1/actions/<identifier>.policy`, with the `auth_self` defaults
+//!   so the user is asked for their own password on every check. The prompt shows the message of the action instead of the reason.
+//! - **Android**: The biometrics or the device credential of the user with the BiometricPrompt, requires Android 9.
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+/// Errors of the authentication functions.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The device cannot authenticate the user, e.g. when no biometrics or password are enrolled.
+  #[error("authentication is not available: {0}")]
+  Unavailable(String),
+  /// The user or the system canceled the prompt.
+  #[error("the authentication was canceled")]
+  Canceled,
+  /// The user could not be authenticated.
+  #[error("authentication failed: {0}")]
+  Failed(String),
+}
+
+/// The native authentication plugin.
+#[cfg(target_os = "android")]
+struct AuthPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Prompts the user to authenticate, returning once the operating system verified the user.
+///
+/// The `reason` is shown on the prompt, e.g. `unlock your notes`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[tauri::command]
+/// async fn unlock(app: tauri::AppHandle) -> Result<(), String> {
+///   tauri::api::auth::authenticate(&app, "unlock your notes").map_err(|e| e.to_string())
+/// }
+/// ```
+pub fn authenticate<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  reason: &str,
+) -> crate::api::Result<()> {
+  platform::authenticate(manager, reason).map_err(Into::into)
+}
+
+/// Prompts the user to authenticate, then reads the password of the account from the [`keyring`](crate::api::keyring).
+///
+/// The password is only read once the operating system verified the user.
+#[cfg(feature = "keyring")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "keyring")))]
+pub fn authenticate_password<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  reason: &str,
+  service: &str,
+  account: &str,
+) -> crate::api::Result<Option<String>> {
+  authenticate(manager, reason)?;
+  crate::api::keyring::get_password(manager, service, account)
+}
+
+mod commands {
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn authenticate<R: Runtime>(
+    app: AppHandle<R>,
+    reason: String,
+  ) -> crate::Result<()> {
+    super::authenticate(&app, &reason).map_err(Into::into)
+  }
+}
+
+/// The authentication plugin, exposing the commands of `@tauri-apps/api/auth` when the `tauri > security > auth` allowlist enables them.
+pub(crate) fn init<R: Runtime>(commands: bool) -> TauriPlugin<R> {
+  let mut builder = Builder::new("auth");
+  if commands {
+    builder = builder.invoke_handler(crate::generate_handler![commands::authenticate]);
+  }
+  builder
+    .setup(|app, _api| {
+      #[cfg(target_os = "android")]
+      app.manage(AuthPlugin(
+        _api.register_android_plugin("app.tauri.auth", "AuthPlugin")?,
+      ));
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use block::ConcreteBlock;
+  use cocoa::base::{id, nil, BOOL, YES};
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use std::{ffi::CStr, os::raw::c_void, sync::mpsc::channel};
+
+  #[link(name = "LocalAuthentication", kind = "framework")]
+  extern "C" {}
+
+  /// `LAPolicyDeviceOwnerAuthentication`, the biometrics or the password of the user.
+  const DEVICE_OWNER_AUTHENTICATION: isize = 2;
+
+  const LA_ERROR_USER_CANCEL: isize = -2;
+  const LA_ERROR_SYSTEM_CANCEL: isize = -4;
+  const LA_ERROR_PASSCODE_NOT_SET: isize = -5;
+  const LA_ERROR_BIOMETRY_NOT_AVAILABLE: isize = -6;
+  const LA_ERROR_BIOMETRY_NOT_ENROLLED: isize = -7;
+  const LA_ERROR_APP_CANCEL: isize = -9;
+
+  /// `NSUTF8StringEncoding`
+  const UTF8_ENCODING: usize = 4;
+
+  pub fn authenticate<R: Runtime, M: Manager<R>>(_manager: &M, reason: &str) -> Result<(), Error> {
+    let (tx, rx) = channel();
+    unsafe {
+      let context: id = msg_send![class!(LAContext), new];
+      let localized_reason: id = msg_send![class!(NSString), alloc];
+      let localized_reason: id = msg_send![
+        localized_reason,
+        initWithBytes: reason.as_ptr() as *const c_void
+        length: reason.len()
+        encoding: UTF8_ENCODING
+      ];
+      // the reply is called on a private queue of the framework
+      let reply = ConcreteBlock::new(move |success: BOOL, error: id| {
+        let result = if success == YES {
+          Ok(())
+        } else {
+          Err(error_of(error))
+        };
+        let _ = tx.send(result);
+      })
+      .copy();
+      let _: () = msg_send![
+        context,
+        evaluatePolicy: DEVICE_OWNER_AUTHENTICATION
+        localizedReason: localized_reason
+        reply: &*reply
+      ];
+      let _: () = msg_send![localized_reason, release];
+      let result = rx
+        .recv()
+        .unwrap_or_else(|_| Err(Error::Failed("the prompt was closed".into())));
+      let _: () = msg_send![context, release];
+      result
+    }
+  }
+
+  fn error_of(error: id) -> Error {
+    if error == nil {
+      return Error::Failed("unknown error".into());
+    }
+    unsafe {
+      let code: isize = msg_send![error, code];
+      match code {
+        LA_ERROR_USER_CANCEL | LA_ERROR_SYSTEM_CANCEL | LA_ERROR_APP_CANCEL => Error::Canceled,
+        code => {
+          let description: id = msg_send![error, localizedDescription];
+          let utf8: *const std::os::raw::c_char = msg_send![description, UTF8String];
+          let description = CStr::from_ptr(utf8).to_string_lossy().into_owned();
+          match code {
+            LA_ERROR_PASSCODE_NOT_SET
+            | LA_ERROR_BIOMETRY_NOT_AVAILABLE
+            | LA_ERROR_BIOMETRY_NOT_ENROLLED => Error::Unavailable(description),
+            _ => Error::Failed(description),
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use windows::{
+    core::HSTRING,
+    Security::Credentials::UI::{UserConsentVerificationResult, UserConsentVerifier},
+  };
+
+  pub fn authenticate<R: Runtime, M: Manager<R>>(_manager: &M, reason: &str) -> Result<(), Error> {
+    let result = UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))
+      .and_then(|operation| operation.get())
+      .map_err(|e| Error::Unavailable(e.message().to_string()))?;
+    match result {
+      UserConsentVerificationResult::Verified => Ok(()),
+      UserConsentVerificationResult::Canceled => Err(Error::Canceled),
+      UserConsentVerificationResult::DeviceNotPresent => {
+        Err(Error::Unavailable("no Windows Hello device".into()))
+      }
+      UserConsentVerificationResult::NotConfiguredForUser => Err(Error::Unavailable(
+        "Windows Hello is not configured for the user".into(),
+      )),
+      UserConsentVerificationResult::DisabledByPolicy => Err(Error::Unavailable(
+        "Windows Hello is disabled by the group policy".into(),
+      )),
+      UserConsentVerificationResult::DeviceBusy => {
+        Err(Error::Failed("the Windows Hello device is busy".into()))
+      }
+      UserConsentVerificationResult::RetriesExhausted => {
+        Err(Error::Failed("too many failed attempts".into()))
+      }
+      _ => Err(Error::Failed("unknown verification result".into())),
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use gtk::{
+    gio::{self, BusType, Cancellable, DBusCallFlags},
+    glib::ToVariant,
+  };
+
+  use std::collections::HashMap;
+
+  /// `AllowUserInteraction` flag of `CheckAuthorization`.
+  const ALLOW_USER_INTERACTION: u32 = 1;
+
+  pub fn authenticate<R: Runtime, M: Manager<R>>(manager: &M, _reason: &str) -> Result<(), Error> {
+    let connection = gio::bus_get_sync(BusType::System, Cancellable::NONE)
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    let name = connection
+      .unique_name()
+      .ok_or_else(|| Error::Unavailable("the system bus connection has no name".into()))?;
+
+    // the subject is this process, identified by its connection to the system bus
+    let subject = (
+      "system-bus-name",
+      HashMap::from([("name", name.to_variant())]),
+    );
+    let action = format!("{}.authenticate", manager.config().tauri.bundle.identifier);
+    // polkit ignores the `polkit.message` detail of unprivileged processes, the prompt shows the message of the action
+    let details = HashMap::<&str, &str>::new();
+    let parameters = (subject, action, details, ALLOW_USER_INTERACTION, "").to_variant();
+
+    let reply = connection
+      .call_sync(
+        Some("org.freedesktop.PolicyKit1"),
+        "/org/freedesktop/PolicyKit1/Authority",
+        "org.freedesktop.PolicyKit1.Authority",
+        "CheckAuthorization",
+        Some(&parameters),
+        None,
+        DBusCallFlags::ALLOW_INTERACTIVE_AUTHORIZATION,
+        // the user can take a while to complete the prompt
+        i32::MAX,
+        Cancellable::NONE,
+      )
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    let (authorized, _challenge, details): (bool, bool, HashMap<String, String>) = reply
+      .child_value(0)
+      .get()
+      .ok_or_else(|| Error::Failed("invalid polkit reply".into()))?;
+
+    if authorized {
+      Ok(())
+    } else if details.contains_key("polkit.dismissed") {
+      Err(Error::Canceled)
+    } else {
+      Err(Error::Failed("the user is not authorized".into()))
+    }
+  }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+  use super::{AuthPlugin, Error};
+  use crate::{plugin::mobile::PluginInvokeError, Manager, Runtime};
+
+  #[derive(serde::Serialize)]
+  struct AuthenticatePayload<'a> {
+    reason: &'a str,
+  }
+
+  pub fn authenticate<R: Runtime, M: Manager<R>>(manager: &M, reason: &str) -> Result<(), Error> {
+    let plugin = manager
+      .try_state::<AuthPlugin<R>>()
+      .ok_or_else(|| Error::Unavailable("the auth plugin is not registered".into()))?;
+    plugin
+      .0
+      .run_mobile_plugin::<serde::de::IgnoredAny>("authenticate", AuthenticatePayload { reason })
+      .map_err(|e| match e {
+        PluginInvokeError::InvokeRejected(response) => match response.code.as_deref() {
+          Some("canceled") => Error::Canceled,
+          Some("unavailable") => {
+            Error::Unavailable(response.message.unwrap_or_else(|| response.to_string()))
+          }
+          _ => Error::Failed(response.message.unwrap_or_else(|| response.to_string())),
+        },
+        e => Error::Failed(e.to_string()),
+      })
+      .map(|_| ())
+  }
+}
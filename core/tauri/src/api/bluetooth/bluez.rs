@@ -0,0 +1,306 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The BlueZ Bluetooth LE backend, over the system D-Bus.
+
+use super::{NotificationHandler, Peripheral};
+
+use gtk::{
+  gio::{self, BusType, DBusCallFlags, DBusConnection, DBusSignalFlags, SignalSubscriptionId},
+  glib::{self, variant::ObjectPath, ToVariant, Variant, VariantTy},
+};
+use uuid::Uuid;
+
+use std::{
+  collections::HashMap,
+  io,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+const BLUEZ: &str = "org.bluez";
+const ADAPTER: &str = "org.bluez.Adapter1";
+const DEVICE: &str = "org.bluez.Device1";
+const SERVICE: &str = "org.bluez.GattService1";
+const CHARACTERISTIC: &str = "org.bluez.GattCharacteristic1";
+const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+
+/// How long [`Connection::connect`] waits for the services of the device to be discovered.
+const SERVICES_RESOLVED_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The interfaces and their properties of each BlueZ object.
+type Objects = HashMap<ObjectPath, HashMap<String, HashMap<String, Variant>>>;
+
+fn error(e: glib::Error) -> io::Error {
+  io::Error::new(io::ErrorKind::Other, e.message().to_string())
+}
+
+fn system_bus() -> io::Result<DBusConnection> {
+  gio::bus_get_sync(BusType::System, gio::Cancellable::NONE).map_err(error)
+}
+
+fn call(
+  bus: &DBusConnection,
+  path: &str,
+  interface: &str,
+  method: &str,
+  parameters: Option<Variant>,
+) -> io::Result<Variant> {
+  bus
+    .call_sync(
+      Some(BLUEZ),
+      path,
+      interface,
+      method,
+      parameters.as_ref(),
+      None,
+      DBusCallFlags::NONE,
+      -1,
+      gio::Cancellable::NONE,
+    )
+    .map_err(error)
+}
+
+fn managed_objects(bus: &DBusConnection) -> io::Result<Objects> {
+  let reply = bus
+    .call_sync(
+      Some(BLUEZ),
+      "/",
+      "org.freedesktop.DBus.ObjectManager",
+      "GetManagedObjects",
+      None,
+      VariantTy::new("(a{oa{sa{sv}}})").ok(),
+      DBusCallFlags::NONE,
+      -1,
+      gio::Cancellable::NONE,
+    )
+    .map_err(error)?;
+  Ok(reply.child_value(0).get().unwrap_or_default())
+}
+
+fn property<T: glib::FromVariant>(properties: &HashMap<String, Variant>, name: &str) -> Option<T> {
+  properties.get(name).and_then(|value| value.get())
+}
+
+fn uuid_property(properties: &HashMap<String, Variant>) -> Option<Uuid> {
+  property::<String>(properties, "UUID").and_then(|uuid| uuid.parse().ok())
+}
+
+/// The interfaces of the objects implementing `interface`.
+fn objects_of<'a>(
+  objects: &'a Objects,
+  interface: &'a str,
+) -> impl Iterator<Item = (&'a ObjectPath, &'a HashMap<String, Variant>)> + 'a {
+  objects.iter().filter_map(move |(path, interfaces)| {
+    interfaces
+      .get(interface)
+      .map(|properties| (path, properties))
+  })
+}
+
+fn adapter(objects: &Objects) -> io::Result<String> {
+  objects_of(objects, ADAPTER)
+    .map(|(path, _)| path.to_string())
+    .min()
+    .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no Bluetooth adapter was found"))
+}
+
+pub(super) fn scan(timeout: Duration) -> io::Result<Vec<Peripheral>> {
+  let bus = system_bus()?;
+  let adapter = adapter(&managed_objects(&bus)?)?;
+
+  let mut filter = HashMap::new();
+  filter.insert("Transport", "le".to_variant());
+  call(
+    &bus,
+    &adapter,
+    ADAPTER,
+    "SetDiscoveryFilter",
+    Some((filter,).to_variant()),
+  )?;
+  call(&bus, &adapter, ADAPTER, "StartDiscovery", None)?;
+  std::thread::sleep(timeout);
+  let objects = managed_objects(&bus);
+  call(&bus, &adapter, ADAPTER, "StopDiscovery", None)?;
+
+  let peripherals = objects_of(&objects?, DEVICE)
+    .filter(|(path, _)| path.starts_with(&adapter))
+    .filter_map(|(_, properties)| {
+      // the known devices which are not advertising have no RSSI
+      let rssi = property::<i16>(properties, "RSSI")?;
+      Some(Peripheral {
+        id: property(properties, "Address")?,
+        name: property(properties, "Name"),
+        rssi: Some(rssi),
+        services: property::<Vec<String>>(properties, "UUIDs")
+          .unwrap_or_default()
+          .iter()
+          .filter_map(|uuid| uuid.parse().ok())
+          .collect(),
+      })
+    })
+    .collect();
+  Ok(peripherals)
+}
+
+pub(super) struct Connection {
+  bus: DBusConnection,
+  path: String,
+  subscriptions: Arc<Mutex<Vec<SignalSubscriptionId>>>,
+}
+
+impl Connection {
+  pub(super) fn connect(id: &str) -> io::Result<Self> {
+    let bus = system_bus()?;
+    let objects = managed_objects(&bus)?;
+    let path = objects_of(&objects, DEVICE)
+      .find(|(_, properties)| property::<String>(properties, "Address").as_deref() == Some(id))
+      .map(|(path, _)| path.to_string())
+      .ok_or_else(|| {
+        io::Error::new(
+          io::ErrorKind::NotFound,
+          format!("the Bluetooth device `{id}` was not found, it must be scanned first"),
+        )
+      })?;
+    call(&bus, &path, DEVICE, "Connect", None)?;
+
+    // the GATT objects are exported once the services are resolved
+    let started = Instant::now();
+    loop {
+      let resolved = call(
+        &bus,
+        &path,
+        PROPERTIES,
+        "Get",
+        Some((DEVICE, "ServicesResolved").to_variant()),
+      )?
+      .child_value(0)
+      .as_variant()
+      .and_then(|value| value.get::<bool>())
+      .unwrap_or(false);
+      if resolved {
+        break;
+      }
+      if started.elapsed() > SERVICES_RESOLVED_TIMEOUT {
+        let _ = call(&bus, &path, DEVICE, "Disconnect", None);
+        return Err(io::Error::new(
+          io::ErrorKind::TimedOut,
+          "the services of the device were not resolved",
+        ));
+      }
+      std::thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(Self {
+      bus,
+      path,
+      subscriptions: Default::default(),
+    })
+  }
+
+  fn characteristic(&self, service: Uuid, characteristic: Uuid) -> io::Result<String> {
+    let objects = managed_objects(&self.bus)?;
+    let device_prefix = format!("{}/", self.path);
+    let service_path = objects_of(&objects, SERVICE)
+      .find(|(path, properties)| {
+        path.starts_with(&device_prefix) && uuid_property(properties) == Some(service)
+      })
+      .map(|(path, _)| path.clone());
+    service_path
+      .and_then(|service_path| {
+        objects_of(&objects, CHARACTERISTIC)
+          .find(|(_, properties)| {
+            property::<ObjectPath>(properties, "Service").as_ref() == Some(&service_path)
+              && uuid_property(properties) == Some(characteristic)
+          })
+          .map(|(path, _)| path.to_string())
+      })
+      .ok_or_else(|| {
+        io::Error::new(
+          io::ErrorKind::NotFound,
+          format!("the characteristic {characteristic} of the service {service} was not found"),
+        )
+      })
+  }
+
+  pub(super) fn read(&self, service: Uuid, characteristic: Uuid) -> io::Result<Vec<u8>> {
+    let path = self.characteristic(service, characteristic)?;
+    let options = HashMap::<String, Variant>::new();
+    let reply = call(
+      &self.bus,
+      &path,
+      CHARACTERISTIC,
+      "ReadValue",
+      Some((options,).to_variant()),
+    )?;
+    Ok(reply.child_value(0).get().unwrap_or_default())
+  }
+
+  pub(super) fn write(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    data: &[u8],
+    with_response: bool,
+  ) -> io::Result<()> {
+    let path = self.characteristic(service, characteristic)?;
+    let mut options = HashMap::new();
+    options.insert(
+      "type",
+      if with_response { "request" } else { "command" }.to_variant(),
+    );
+    call(
+      &self.bus,
+      &path,
+      CHARACTERISTIC,
+      "WriteValue",
+      Some((data.to_vec(), options).to_variant()),
+    )
+    .map(|_| ())
+  }
+
+  pub(super) fn subscribe(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    handler: NotificationHandler,
+  ) -> io::Result<()> {
+    let path = self.characteristic(service, characteristic)?;
+
+    // the signals are dispatched on the main context, which the event loop runs
+    let bus = self.bus.clone();
+    let subscriptions = self.subscriptions.clone();
+    let path_ = path.clone();
+    glib::MainContext::default().invoke(move || {
+      let id = bus.signal_subscribe(
+        Some(BLUEZ),
+        Some(PROPERTIES),
+        Some("PropertiesChanged"),
+        Some(&path_),
+        Some(CHARACTERISTIC),
+        DBusSignalFlags::NONE,
+        move |_, _, _, _, _, parameters| {
+          let changed = parameters.child_value(1).get::<HashMap<String, Variant>>();
+          if let Some(value) = changed.and_then(|changed| property::<Vec<u8>>(&changed, "Value")) {
+            handler(&value);
+          }
+        },
+      );
+      subscriptions.lock().unwrap().push(id);
+    });
+
+    call(&self.bus, &path, CHARACTERISTIC, "StartNotify", None).map(|_| ())
+  }
+
+  pub(super) fn disconnect(&self) -> io::Result<()> {
+    let bus = self.bus.clone();
+    let subscriptions = self.subscriptions.clone();
+    glib::MainContext::default().invoke(move || {
+      for id in subscriptions.lock().unwrap().drain(..) {
+        bus.signal_unsubscribe(id);
+      }
+    });
+    call(&self.bus, &self.path, DEVICE, "Disconnect", None).map(|_| ())
+  }
+}
@@ -0,0 +1,696 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The CoreBluetooth Bluetooth LE backend.
+//!
+//! The central manager and the peripherals call their delegate on a dedicated dispatch queue.
+//! The functions send their messages on the same queue, then wait for the delegate to store the result in [`SHARED`].
+
+use super::{parse_uuid, NotificationHandler, Peripheral};
+
+use cocoa::base::{id, nil};
+use objc::{
+  class,
+  declare::ClassDecl,
+  msg_send,
+  runtime::{Object, Sel, YES},
+  sel, sel_impl,
+};
+use once_cell::sync::{Lazy, OnceCell};
+use uuid::Uuid;
+
+use std::{
+  collections::HashMap,
+  ffi::{c_void, CStr},
+  io,
+  os::raw::c_char,
+  sync::{Arc, Condvar, Mutex, Once},
+  time::{Duration, Instant},
+};
+
+/// How long the functions wait for CoreBluetooth, e.g. for a device to connect.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+const CB_MANAGER_STATE_RESETTING: isize = 1;
+const CB_MANAGER_STATE_UNSUPPORTED: isize = 2;
+const CB_MANAGER_STATE_UNAUTHORIZED: isize = 3;
+const CB_MANAGER_STATE_POWERED_OFF: isize = 4;
+const CB_MANAGER_STATE_POWERED_ON: isize = 5;
+
+const CB_CHARACTERISTIC_WRITE_WITH_RESPONSE: isize = 0;
+const CB_CHARACTERISTIC_WRITE_WITHOUT_RESPONSE: isize = 1;
+
+#[link(name = "CoreBluetooth", kind = "framework")]
+extern "C" {
+  static CBAdvertisementDataLocalNameKey: id;
+  static CBAdvertisementDataServiceUUIDsKey: id;
+}
+
+extern "C" {
+  fn dispatch_queue_create(label: *const c_char, attr: *mut c_void) -> *mut c_void;
+  fn dispatch_sync_f(queue: *mut c_void, context: *mut c_void, work: extern "C" fn(*mut c_void));
+}
+
+/// A retained Objective-C object, only messaged on the queue of the central manager.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Handle(id);
+
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+type SharedHandler = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+/// The state updated by the delegate. The characteristics are keyed by their address.
+#[derive(Default)]
+struct State {
+  manager_state: Option<isize>,
+  scanning: bool,
+  peripherals: HashMap<String, Handle>,
+  scanned: HashMap<String, Peripheral>,
+  /// The connections being established have no result yet.
+  connections: HashMap<String, Option<Result<(), String>>>,
+  /// The number of services of each peripheral whose characteristics are not discovered yet.
+  pending_services: HashMap<String, usize>,
+  reads: HashMap<usize, Option<Result<Vec<u8>, String>>>,
+  writes: HashMap<usize, Option<Result<(), String>>>,
+  subscriptions: HashMap<usize, Option<Result<(), String>>>,
+  handlers: HashMap<usize, SharedHandler>,
+}
+
+struct Shared {
+  state: Mutex<State>,
+  changed: Condvar,
+}
+
+static SHARED: Lazy<Shared> = Lazy::new(|| Shared {
+  state: Default::default(),
+  changed: Condvar::new(),
+});
+
+struct Central {
+  manager: Handle,
+  queue: Handle,
+}
+
+static CENTRAL: OnceCell<Central> = OnceCell::new();
+
+impl Central {
+  /// Runs `f` on the queue of the central manager and returns its result.
+  fn sync<T, F: FnOnce(id) -> T>(&self, f: F) -> T {
+    extern "C" fn work<T, F: FnOnce(id) -> T>(context: *mut c_void) {
+      let context = unsafe { &mut *(context as *mut (Option<F>, id, Option<T>)) };
+      if let Some(f) = context.0.take() {
+        context.2.replace(f(context.1));
+      }
+    }
+
+    let mut context = (Some(f), self.manager.0, None);
+    unsafe {
+      dispatch_sync_f(
+        self.queue.0 as *mut c_void,
+        &mut context as *mut (Option<F>, id, Option<T>) as *mut c_void,
+        work::<T, F>,
+      );
+    }
+    context
+      .2
+      .expect("the dispatch queue did not run the function")
+  }
+}
+
+/// Waits until `f` returns a value, which it usually takes out of the state.
+fn wait<T>(mut f: impl FnMut(&mut State) -> Option<T>) -> io::Result<T> {
+  let deadline = Instant::now() + TIMEOUT;
+  let mut state = SHARED.state.lock().unwrap();
+  loop {
+    if let Some(value) = f(&mut state) {
+      return Ok(value);
+    }
+    let now = Instant::now();
+    if now >= deadline {
+      return Err(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "CoreBluetooth did not respond in time",
+      ));
+    }
+    state = SHARED
+      .changed
+      .wait_timeout(state, deadline - now)
+      .unwrap()
+      .0;
+  }
+}
+
+/// Updates the state from the delegate and wakes the waiting functions.
+fn update(f: impl FnOnce(&mut State)) {
+  f(&mut SHARED.state.lock().unwrap());
+  SHARED.changed.notify_all();
+}
+
+fn error(e: String) -> io::Error {
+  io::Error::new(io::ErrorKind::Other, e)
+}
+
+unsafe fn to_string(string: id) -> Option<String> {
+  if string == nil {
+    return None;
+  }
+  let bytes: *const c_char = msg_send![string, UTF8String];
+  if bytes.is_null() {
+    None
+  } else {
+    Some(CStr::from_ptr(bytes).to_string_lossy().into_owned())
+  }
+}
+
+unsafe fn to_result(error: id) -> Result<(), String> {
+  if error == nil {
+    Ok(())
+  } else {
+    let description: id = msg_send![error, localizedDescription];
+    Err(to_string(description).unwrap_or_else(|| "unknown CoreBluetooth error".into()))
+  }
+}
+
+unsafe fn to_uuid(cb_uuid: id) -> Option<Uuid> {
+  let string: id = msg_send![cb_uuid, UUIDString];
+  to_string(string).and_then(|uuid| parse_uuid(&uuid).ok())
+}
+
+unsafe fn to_bytes(data: id) -> Vec<u8> {
+  if data == nil {
+    return Vec::new();
+  }
+  let length: usize = msg_send![data, length];
+  let bytes: *const u8 = msg_send![data, bytes];
+  if bytes.is_null() || length == 0 {
+    Vec::new()
+  } else {
+    std::slice::from_raw_parts(bytes, length).to_vec()
+  }
+}
+
+unsafe fn peripheral_id(peripheral: id) -> String {
+  let identifier: id = msg_send![peripheral, identifier];
+  let string: id = msg_send![identifier, UUIDString];
+  to_string(string).unwrap_or_default()
+}
+
+/// The objects of an `NSArray`.
+unsafe fn objects(array: id) -> Vec<id> {
+  if array == nil {
+    return Vec::new();
+  }
+  let count: usize = msg_send![array, count];
+  (0..count)
+    .map(|i| msg_send![array, objectAtIndex: i])
+    .collect()
+}
+
+extern "C" fn did_update_state(_: &Object, _: Sel, manager: id) {
+  let manager_state: isize = unsafe { msg_send![manager, state] };
+  update(|state| {
+    state.manager_state.replace(manager_state);
+  });
+}
+
+extern "C" fn did_discover_peripheral(
+  _: &Object,
+  _: Sel,
+  _manager: id,
+  peripheral: id,
+  advertisement_data: id,
+  rssi: id,
+) {
+  unsafe {
+    let id = peripheral_id(peripheral);
+    let advertised_name: id =
+      msg_send![advertisement_data, objectForKey: CBAdvertisementDataLocalNameKey];
+    let name = to_string(advertised_name).or_else(|| to_string(msg_send![peripheral, name]));
+    let rssi: isize = msg_send![rssi, integerValue];
+    let services: id =
+      msg_send![advertisement_data, objectForKey: CBAdvertisementDataServiceUUIDsKey];
+    let services: Vec<Uuid> = objects(services)
+      .into_iter()
+      .filter_map(|uuid| to_uuid(uuid))
+      .collect();
+
+    update(|state| {
+      if !state.peripherals.contains_key(&id) {
+        let _: id = msg_send![peripheral, retain];
+        state.peripherals.insert(id.clone(), Handle(peripheral));
+      }
+      if state.scanning {
+        let scanned = state
+          .scanned
+          .entry(id.clone())
+          .or_insert_with(|| Peripheral {
+            id,
+            name: None,
+            rssi: None,
+            services: Vec::new(),
+          });
+        if name.is_some() {
+          scanned.name = name;
+        }
+        // 127 is reported when the RSSI is unavailable
+        if rssi != 127 {
+          scanned.rssi.replace(rssi as i16);
+        }
+        for service in services {
+          if !scanned.services.contains(&service) {
+            scanned.services.push(service);
+          }
+        }
+      }
+    });
+  }
+}
+
+extern "C" fn did_connect_peripheral(this: &Object, _: Sel, _manager: id, peripheral: id) {
+  unsafe {
+    let _: () = msg_send![peripheral, setDelegate: this as *const Object as id];
+    let _: () = msg_send![peripheral, discoverServices: nil];
+  }
+}
+
+extern "C" fn did_fail_to_connect_peripheral(
+  _: &Object,
+  _: Sel,
+  _manager: id,
+  peripheral: id,
+  error: id,
+) {
+  let (id, result) = unsafe { (peripheral_id(peripheral), to_result(error)) };
+  update(|state| {
+    state.connections.insert(
+      id,
+      Some(result.and(Err("failed to connect to the device".into()))),
+    );
+  });
+}
+
+extern "C" fn did_disconnect_peripheral(
+  _: &Object,
+  _: Sel,
+  _manager: id,
+  peripheral: id,
+  _error: id,
+) {
+  let id = unsafe { peripheral_id(peripheral) };
+  update(|state| {
+    state.connections.remove(&id);
+    state.pending_services.remove(&id);
+  });
+}
+
+extern "C" fn did_discover_services(_: &Object, _: Sel, peripheral: id, error: id) {
+  unsafe {
+    let id = peripheral_id(peripheral);
+    if let Err(e) = to_result(error) {
+      update(|state| {
+        state.connections.insert(id, Some(Err(e)));
+      });
+      return;
+    }
+
+    let services = objects(msg_send![peripheral, services]);
+    update(|state| {
+      if services.is_empty() {
+        state.connections.insert(id, Some(Ok(())));
+      } else {
+        state.pending_services.insert(id, services.len());
+      }
+    });
+    for service in services {
+      let _: () = msg_send![peripheral, discoverCharacteristics: nil forService: service];
+    }
+  }
+}
+
+extern "C" fn did_discover_characteristics(
+  _: &Object,
+  _: Sel,
+  peripheral: id,
+  _service: id,
+  _error: id,
+) {
+  let id = unsafe { peripheral_id(peripheral) };
+  update(|state| {
+    if let Some(pending) = state.pending_services.get_mut(&id) {
+      *pending -= 1;
+      if *pending == 0 {
+        state.pending_services.remove(&id);
+        state.connections.insert(id, Some(Ok(())));
+      }
+    }
+  });
+}
+
+extern "C" fn did_update_value(_: &Object, _: Sel, _peripheral: id, characteristic: id, error: id) {
+  let key = characteristic as usize;
+  let value = unsafe { to_result(error).map(|()| to_bytes(msg_send![characteristic, value])) };
+  let mut state = SHARED.state.lock().unwrap();
+  // a pending read takes the value, the notifications and the reads share this callback
+  if let Some(read) = state.reads.get_mut(&key).filter(|read| read.is_none()) {
+    read.replace(value);
+    drop(state);
+    SHARED.changed.notify_all();
+    return;
+  }
+  let handler = state.handlers.get(&key).cloned();
+  drop(state);
+  if let (Some(handler), Ok(value)) = (handler, value) {
+    handler(&value);
+  }
+}
+
+extern "C" fn did_write_value(_: &Object, _: Sel, _peripheral: id, characteristic: id, error: id) {
+  let result = unsafe { to_result(error) };
+  update(|state| {
+    if let Some(write) = state.writes.get_mut(&(characteristic as usize)) {
+      write.replace(result);
+    }
+  });
+}
+
+extern "C" fn did_update_notification_state(
+  _: &Object,
+  _: Sel,
+  _peripheral: id,
+  characteristic: id,
+  error: id,
+) {
+  let result = unsafe { to_result(error) };
+  update(|state| {
+    if let Some(subscription) = state.subscriptions.get_mut(&(characteristic as usize)) {
+      subscription.replace(result);
+    }
+  });
+}
+
+fn delegate_class() -> &'static objc::runtime::Class {
+  static REGISTER: Once = Once::new();
+  REGISTER.call_once(|| unsafe {
+    let mut decl = ClassDecl::new("TauriBluetoothDelegate", class!(NSObject))
+      .expect("failed to declare the Bluetooth delegate class");
+    decl.add_method(
+      sel!(centralManagerDidUpdateState:),
+      did_update_state as extern "C" fn(&Object, Sel, id),
+    );
+    decl.add_method(
+      sel!(centralManager:didDiscoverPeripheral:advertisementData:RSSI:),
+      did_discover_peripheral as extern "C" fn(&Object, Sel, id, id, id, id),
+    );
+    decl.add_method(
+      sel!(centralManager:didConnectPeripheral:),
+      did_connect_peripheral as extern "C" fn(&Object, Sel, id, id),
+    );
+    decl.add_method(
+      sel!(centralManager:didFailToConnectPeripheral:error:),
+      did_fail_to_connect_peripheral as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.add_method(
+      sel!(centralManager:didDisconnectPeripheral:error:),
+      did_disconnect_peripheral as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.add_method(
+      sel!(peripheral:didDiscoverServices:),
+      did_discover_services as extern "C" fn(&Object, Sel, id, id),
+    );
+    decl.add_method(
+      sel!(peripheral:didDiscoverCharacteristicsForService:error:),
+      did_discover_characteristics as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.add_method(
+      sel!(peripheral:didUpdateValueForCharacteristic:error:),
+      did_update_value as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.add_method(
+      sel!(peripheral:didWriteValueForCharacteristic:error:),
+      did_write_value as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.add_method(
+      sel!(peripheral:didUpdateNotificationStateForCharacteristic:error:),
+      did_update_notification_state as extern "C" fn(&Object, Sel, id, id, id),
+    );
+    decl.register();
+  });
+  class!(TauriBluetoothDelegate)
+}
+
+/// The central manager, created on first use, once Bluetooth is powered on.
+fn central() -> io::Result<&'static Central> {
+  let central = CENTRAL.get_or_init(|| unsafe {
+    let queue = dispatch_queue_create(
+      b"app.tauri.bluetooth\0".as_ptr() as *const c_char,
+      std::ptr::null_mut(),
+    );
+    let delegate: id = msg_send![delegate_class(), new];
+    let manager: id = msg_send![class!(CBCentralManager), alloc];
+    let manager: id = msg_send![manager, initWithDelegate: delegate queue: queue];
+    Central {
+      manager: Handle(manager),
+      queue: Handle(queue as id),
+    }
+  });
+
+  let manager_state = wait(|state| {
+    state
+      .manager_state
+      .filter(|manager_state| *manager_state > CB_MANAGER_STATE_RESETTING)
+  })?;
+  match manager_state {
+    CB_MANAGER_STATE_POWERED_ON => Ok(central),
+    CB_MANAGER_STATE_UNAUTHORIZED => Err(io::Error::new(
+      io::ErrorKind::PermissionDenied,
+      "the app is not allowed to use Bluetooth",
+    )),
+    CB_MANAGER_STATE_UNSUPPORTED => Err(io::Error::new(
+      io::ErrorKind::Unsupported,
+      "Bluetooth LE is not supported",
+    )),
+    CB_MANAGER_STATE_POWERED_OFF => Err(error("Bluetooth is turned off".into())),
+    _ => Err(error("Bluetooth is unavailable".into())),
+  }
+}
+
+pub(super) fn scan(timeout: Duration) -> io::Result<Vec<Peripheral>> {
+  let central = central()?;
+  central.sync(|manager| unsafe {
+    {
+      let mut state = SHARED.state.lock().unwrap();
+      state.scanned.clear();
+      state.scanning = true;
+    }
+    let _: () = msg_send![manager, scanForPeripheralsWithServices: nil options: nil];
+  });
+  std::thread::sleep(timeout);
+  Ok(central.sync(|manager| unsafe {
+    let _: () = msg_send![manager, stopScan];
+    let mut state = SHARED.state.lock().unwrap();
+    state.scanning = false;
+    std::mem::take(&mut state.scanned).into_values().collect()
+  }))
+}
+
+pub(super) struct Connection {
+  id: String,
+  peripheral: Handle,
+  subscriptions: Mutex<Vec<usize>>,
+}
+
+impl Connection {
+  pub(super) fn connect(id: &str) -> io::Result<Self> {
+    let central = central()?;
+    let peripheral = SHARED
+      .state
+      .lock()
+      .unwrap()
+      .peripherals
+      .get(id)
+      .copied()
+      .ok_or_else(|| {
+        io::Error::new(
+          io::ErrorKind::NotFound,
+          format!("the Bluetooth device `{id}` was not found, it must be scanned first"),
+        )
+      })?;
+
+    central.sync(|manager| unsafe {
+      SHARED
+        .state
+        .lock()
+        .unwrap()
+        .connections
+        .insert(id.into(), None);
+      let _: () = msg_send![manager, connectPeripheral: peripheral.0 options: nil];
+    });
+    let result = wait(|state| state.connections.get(id).cloned().flatten());
+
+    match result {
+      Ok(Ok(())) => Ok(Self {
+        id: id.into(),
+        peripheral,
+        subscriptions: Default::default(),
+      }),
+      result => {
+        central.sync(|manager| unsafe {
+          let _: () = msg_send![manager, cancelPeripheralConnection: peripheral.0];
+          SHARED.state.lock().unwrap().connections.remove(id);
+        });
+        Err(result.and_then(|result| result.map_err(error)).unwrap_err())
+      }
+    }
+  }
+
+  fn characteristic(&self, service: Uuid, characteristic: Uuid) -> io::Result<Handle> {
+    let peripheral = self.peripheral;
+    let found = central()?.sync(|_| unsafe {
+      for s in objects(msg_send![peripheral.0, services]) {
+        if to_uuid(msg_send![s, UUID]) != Some(service) {
+          continue;
+        }
+        for c in objects(msg_send![s, characteristics]) {
+          if to_uuid(msg_send![c, UUID]) == Some(characteristic) {
+            return Some(Handle(c));
+          }
+        }
+      }
+      None
+    });
+    found.ok_or_else(|| {
+      io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("the characteristic {characteristic} of the service {service} was not found"),
+      )
+    })
+  }
+
+  fn check_connected(&self) -> io::Result<()> {
+    if SHARED
+      .state
+      .lock()
+      .unwrap()
+      .connections
+      .contains_key(&self.id)
+    {
+      Ok(())
+    } else {
+      Err(io::Error::new(
+        io::ErrorKind::NotConnected,
+        "the Bluetooth device is disconnected",
+      ))
+    }
+  }
+
+  pub(super) fn read(&self, service: Uuid, characteristic: Uuid) -> io::Result<Vec<u8>> {
+    self.check_connected()?;
+    let peripheral = self.peripheral;
+    let c = self.characteristic(service, characteristic)?;
+    let key = c.0 as usize;
+    central()?.sync(|_| unsafe {
+      SHARED.state.lock().unwrap().reads.insert(key, None);
+      let _: () = msg_send![peripheral.0, readValueForCharacteristic: c.0];
+    });
+    let result = wait(|state| match state.reads.get(&key) {
+      Some(Some(_)) => state.reads.remove(&key).flatten(),
+      _ => None,
+    });
+    if result.is_err() {
+      SHARED.state.lock().unwrap().reads.remove(&key);
+    }
+    result?.map_err(error)
+  }
+
+  pub(super) fn write(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    data: &[u8],
+    with_response: bool,
+  ) -> io::Result<()> {
+    self.check_connected()?;
+    let peripheral = self.peripheral;
+    let c = self.characteristic(service, characteristic)?;
+    let key = c.0 as usize;
+    central()?.sync(|_| unsafe {
+      let data: id =
+        msg_send![class!(NSData), dataWithBytes: data.as_ptr() as *const c_void length: data.len()];
+      let write_type = if with_response {
+        SHARED.state.lock().unwrap().writes.insert(key, None);
+        CB_CHARACTERISTIC_WRITE_WITH_RESPONSE
+      } else {
+        CB_CHARACTERISTIC_WRITE_WITHOUT_RESPONSE
+      };
+      let _: () = msg_send![peripheral.0, writeValue: data forCharacteristic: c.0 type: write_type];
+    });
+    if !with_response {
+      return Ok(());
+    }
+
+    let result = wait(|state| match state.writes.get(&key) {
+      Some(Some(_)) => state.writes.remove(&key).flatten(),
+      _ => None,
+    });
+    if result.is_err() {
+      SHARED.state.lock().unwrap().writes.remove(&key);
+    }
+    result?.map_err(error)
+  }
+
+  pub(super) fn subscribe(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    handler: NotificationHandler,
+  ) -> io::Result<()> {
+    self.check_connected()?;
+    let peripheral = self.peripheral;
+    let c = self.characteristic(service, characteristic)?;
+    let key = c.0 as usize;
+    central()?.sync(|_| unsafe {
+      {
+        let mut state = SHARED.state.lock().unwrap();
+        state.handlers.insert(key, Arc::from(handler));
+        state.subscriptions.insert(key, None);
+      }
+      let _: () = msg_send![peripheral.0, setNotifyValue: YES forCharacteristic: c.0];
+    });
+
+    let result = wait(|state| match state.subscriptions.get(&key) {
+      Some(Some(_)) => state.subscriptions.remove(&key).flatten(),
+      _ => None,
+    })
+    .and_then(|result| result.map_err(error));
+    match result {
+      Ok(()) => {
+        self.subscriptions.lock().unwrap().push(key);
+        Ok(())
+      }
+      Err(e) => {
+        let mut state = SHARED.state.lock().unwrap();
+        state.handlers.remove(&key);
+        state.subscriptions.remove(&key);
+        Err(e)
+      }
+    }
+  }
+
+  pub(super) fn disconnect(&self) -> io::Result<()> {
+    let peripheral = self.peripheral;
+    let subscriptions = std::mem::take(&mut *self.subscriptions.lock().unwrap());
+    central()?.sync(|manager| unsafe {
+      {
+        let mut state = SHARED.state.lock().unwrap();
+        for key in subscriptions {
+          state.handlers.remove(&key);
+        }
+        state.connections.remove(&self.id);
+      }
+      let _: () = msg_send![manager, cancelPeripheralConnection: peripheral.0];
+    });
+    Ok(())
+  }
+}
@@ -0,0 +1,574 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Bluetooth LE devices: scanning, connecting and reading, writing and subscribing to the characteristics of their GATT services,
+//! since Web Bluetooth is not available in the platform webviews.
+//!
+//! The frontend uses `@tauri-apps/api/bluetooth`. The first time a window connects to a device, the user is asked with a native dialog,
+//! the answer is remembered by the [`BluetoothScope`] until the app exits. The app can allow devices itself with [`BluetoothScope::allow`].
+//!
+//! The functions block until the device responds, they must not be called on the main thread.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux**: Uses BlueZ over the system D-Bus.
+//! - **macOS**: Uses CoreBluetooth, the `Info.plist` must have a `NSBluetoothAlwaysUsageDescription`. The device identifiers are generated by macOS for each app.
+//! - **Windows**: Uses the WinRT Bluetooth APIs, the device identifiers are the Bluetooth addresses.
+//! - **Android / iOS**: Unsupported, the functions fail with [`std::io::ErrorKind::Unsupported`].
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod bluez;
+#[cfg(target_os = "macos")]
+mod core_bluetooth;
+#[cfg(windows)]
+mod winrt;
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+use bluez as platform;
+#[cfg(target_os = "macos")]
+use core_bluetooth as platform;
+#[cfg(mobile)]
+use unsupported as platform;
+#[cfg(windows)]
+use winrt as platform;
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime, Window,
+};
+
+use serde::{Serialize, Serializer};
+pub use uuid::Uuid;
+
+use std::{
+  collections::HashMap,
+  io,
+  sync::{mpsc, Mutex},
+  time::Duration,
+};
+
+/// The base of the 16-bit and 32-bit UUIDs assigned by the Bluetooth SIG.
+const BASE_UUID: u128 = 0x00000000_0000_1000_8000_00805f9b34fb;
+
+/// The handler of the values notified for a characteristic.
+type NotificationHandler = Box<dyn Fn(&[u8]) + Send + Sync>;
+
+/// A Bluetooth LE device found by [`scan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Peripheral {
+  /// The identifier the device is connected with.
+  pub id: String,
+  /// The name the device advertises.
+  pub name: Option<String>,
+  /// The signal strength of the advertisement, in dBm.
+  pub rssi: Option<i16>,
+  /// The services the device advertises. Devices usually advertise a few of their services only.
+  #[serde(serialize_with = "serialize_uuids")]
+  pub services: Vec<Uuid>,
+}
+
+fn serialize_uuids<S: Serializer>(uuids: &[Uuid], serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_seq(uuids.iter().map(Uuid::to_string))
+}
+
+/// Parses a UUID, expanding the 16-bit and 32-bit UUIDs assigned by the Bluetooth SIG, e.g. `180d` for the heart rate service.
+pub fn parse_uuid(uuid: &str) -> crate::api::Result<Uuid> {
+  let invalid = || {
+    io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("`{uuid}` is not a valid Bluetooth UUID"),
+    )
+  };
+  match uuid.len() {
+    4 | 8 if uuid.bytes().all(|b| b.is_ascii_hexdigit()) => u32::from_str_radix(uuid, 16)
+      .map(|short| Uuid::from_u128(BASE_UUID | (u128::from(short) << 96)))
+      .map_err(|_| invalid().into()),
+    _ => Uuid::parse_str(uuid).map_err(|_| invalid().into()),
+  }
+}
+
+/// Scans for the advertising devices during `timeout`.
+///
+/// When `services` is not empty, only the devices advertising one of the services are returned.
+pub fn scan(timeout: Duration, services: &[Uuid]) -> crate::api::Result<Vec<Peripheral>> {
+  let mut peripherals = platform::scan(timeout)?;
+  if !services.is_empty() {
+    peripherals.retain(|peripheral| {
+      peripheral
+        .services
+        .iter()
+        .any(|service| services.contains(service))
+    });
+  }
+  peripherals.sort_by_key(|p| std::cmp::Reverse(p.rssi));
+  Ok(peripherals)
+}
+
+/// A connection to a Bluetooth LE device, which stays connected until [`Self::disconnect`].
+pub struct Connection {
+  id: String,
+  inner: platform::Connection,
+}
+
+impl std::fmt::Debug for Connection {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Connection").field("id", &self.id).finish()
+  }
+}
+
+impl Connection {
+  /// Connects to a device found by [`scan`] and discovers its services.
+  pub fn connect(id: &str) -> crate::api::Result<Self> {
+    Ok(Self {
+      id: id.into(),
+      inner: platform::Connection::connect(id)?,
+    })
+  }
+
+  /// The identifier of the device.
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+
+  /// Reads the value of a characteristic.
+  pub fn read(&self, service: Uuid, characteristic: Uuid) -> crate::api::Result<Vec<u8>> {
+    self.inner.read(service, characteristic).map_err(Into::into)
+  }
+
+  /// Writes the value of a characteristic, waiting for the device to acknowledge it when `with_response` is `true`.
+  pub fn write(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    data: &[u8],
+    with_response: bool,
+  ) -> crate::api::Result<()> {
+    self
+      .inner
+      .write(service, characteristic, data, with_response)
+      .map_err(Into::into)
+  }
+
+  /// Calls `handler` with the values the device notifies or indicates for a characteristic.
+  ///
+  /// The handler runs on the thread of the platform Bluetooth callbacks, it must not block.
+  pub fn subscribe<F: Fn(&[u8]) + Send + Sync + 'static>(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    handler: F,
+  ) -> crate::api::Result<()> {
+    self
+      .inner
+      .subscribe(service, characteristic, Box::new(handler))
+      .map_err(Into::into)
+  }
+
+  /// Unsubscribes from the characteristics and disconnects from the device.
+  pub fn disconnect(&self) -> crate::api::Result<()> {
+    self.inner.disconnect().map_err(Into::into)
+  }
+}
+
+#[cfg(mobile)]
+mod unsupported {
+  use super::{NotificationHandler, Peripheral};
+  use std::{io, time::Duration};
+  use uuid::Uuid;
+
+  fn unsupported() -> io::Error {
+    io::Error::new(
+      io::ErrorKind::Unsupported,
+      "Bluetooth LE is not supported on this platform",
+    )
+  }
+
+  pub(super) fn scan(_timeout: Duration) -> io::Result<Vec<Peripheral>> {
+    Err(unsupported())
+  }
+
+  pub(super) enum Connection {}
+
+  impl Connection {
+    pub(super) fn connect(_id: &str) -> io::Result<Self> {
+      Err(unsupported())
+    }
+
+    pub(super) fn read(&self, _service: Uuid, _characteristic: Uuid) -> io::Result<Vec<u8>> {
+      match *self {}
+    }
+
+    pub(super) fn write(
+      &self,
+      _service: Uuid,
+      _characteristic: Uuid,
+      _data: &[u8],
+      _with_response: bool,
+    ) -> io::Result<()> {
+      match *self {}
+    }
+
+    pub(super) fn subscribe(
+      &self,
+      _service: Uuid,
+      _characteristic: Uuid,
+      _handler: NotificationHandler,
+    ) -> io::Result<()> {
+      match *self {}
+    }
+
+    pub(super) fn disconnect(&self) -> io::Result<()> {
+      match *self {}
+    }
+  }
+}
+
+/// The Bluetooth devices the frontend is allowed to connect to, managed by the app.
+///
+/// The devices the user allowed or denied with the connection prompt are remembered until the app exits.
+#[derive(Debug, Default)]
+pub struct BluetoothScope {
+  devices: Mutex<HashMap<String, bool>>,
+}
+
+impl BluetoothScope {
+  /// Allows the frontend to connect to the device without prompting the user.
+  pub fn allow(&self, id: impl Into<String>) {
+    self.devices.lock().unwrap().insert(id.into(), true);
+  }
+
+  /// Forbids the frontend to connect to the device without prompting the user.
+  pub fn forbid(&self, id: impl Into<String>) {
+    self.devices.lock().unwrap().insert(id.into(), false);
+  }
+
+  /// Whether the device is allowed, `None` if the user was not asked yet.
+  pub fn is_allowed(&self, id: &str) -> Option<bool> {
+    self.devices.lock().unwrap().get(id).copied()
+  }
+}
+
+/// Asks the user whether the window may connect to the device, with a native dialog on the main thread.
+fn prompt<R: Runtime>(window: &Window<R>, device: &str) -> bool {
+  let title = window.app_handle().package_info().name.clone();
+  let message = format!("{title} wants to connect to the Bluetooth device “{device}”.");
+  let (tx, rx) = mpsc::channel();
+  let window_ = window.clone();
+  let dispatched = window.run_on_main_thread(move || {
+    let _ = tx.send(show_prompt(&window_, &title, &message));
+  });
+  dispatched.is_ok() && rx.recv().unwrap_or(false)
+}
+
+#[allow(unused_variables)]
+fn show_prompt<R: Runtime>(window: &Window<R>, title: &str, message: &str) -> bool {
+  #[cfg(windows)]
+  return unsafe {
+    use windows::{
+      core::HSTRING,
+      Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONQUESTION, MB_YESNO},
+    };
+    MessageBoxW(
+      window.hwnd().unwrap_or_default(),
+      &HSTRING::from(message),
+      &HSTRING::from(title),
+      MB_YESNO | MB_ICONQUESTION,
+    ) == IDYES
+  };
+
+  #[cfg(target_os = "macos")]
+  return unsafe {
+    use cocoa::{
+      base::{id, nil},
+      foundation::NSString,
+    };
+    use objc::{class, msg_send, sel, sel_impl};
+    const NS_ALERT_FIRST_BUTTON_RETURN: isize = 1000;
+
+    let alert: id = msg_send![class!(NSAlert), new];
+    let _: () = msg_send![alert, setMessageText: NSString::alloc(nil).init_str(title)];
+    let _: () = msg_send![alert, setInformativeText: NSString::alloc(nil).init_str(message)];
+    let _: id = msg_send![alert, addButtonWithTitle: NSString::alloc(nil).init_str("Allow")];
+    let _: id = msg_send![alert, addButtonWithTitle: NSString::alloc(nil).init_str("Don't Allow")];
+    let response: isize = msg_send![alert, runModal];
+    let _: () = msg_send![alert, release];
+    response == NS_ALERT_FIRST_BUTTON_RETURN
+  };
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  return {
+    use gtk::prelude::*;
+    let parent = window.gtk_window().ok();
+    let dialog = gtk::MessageDialog::new(
+      parent.as_ref(),
+      gtk::DialogFlags::MODAL,
+      gtk::MessageType::Question,
+      gtk::ButtonsType::YesNo,
+      message,
+    );
+    dialog.set_title(title);
+    let allowed = dialog.run() == gtk::ResponseType::Yes;
+    dialog.close();
+    allowed
+  };
+
+  #[cfg(mobile)]
+  false
+}
+
+mod commands {
+  use super::{parse_uuid, prompt, scan as scan_devices, BluetoothScope, Connection, Peripheral};
+  use crate::{api::ipc::Channel, command, Runtime, State, Window};
+  use std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+  };
+
+  /// How long a scan lasts when the frontend does not set its timeout.
+  const DEFAULT_SCAN_TIMEOUT: Duration = Duration::from_secs(5);
+
+  /// The names of the scanned devices, shown in the prompt, and the connections of the frontend.
+  #[derive(Default)]
+  pub(super) struct Devices {
+    names: Mutex<HashMap<String, String>>,
+    connections: Mutex<HashMap<String, Arc<Connection>>>,
+  }
+
+  impl Devices {
+    fn connection(&self, id: &str) -> crate::Result<Arc<Connection>> {
+      self
+        .connections
+        .lock()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .ok_or_else(|| {
+          crate::Error::FailedToExecuteApi(
+            io::Error::new(
+              ErrorKind::NotConnected,
+              format!("the Bluetooth device `{id}` is not connected"),
+            )
+            .into(),
+          )
+        })
+    }
+  }
+
+  fn parse_uuids(service: &str, characteristic: &str) -> crate::Result<(super::Uuid, super::Uuid)> {
+    Ok((parse_uuid(service)?, parse_uuid(characteristic)?))
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn scan(
+    devices: State<'_, Devices>,
+    timeout: Option<u64>,
+    services: Option<Vec<String>>,
+  ) -> crate::Result<Vec<Peripheral>> {
+    let services = services
+      .unwrap_or_default()
+      .iter()
+      .map(|service| parse_uuid(service))
+      .collect::<crate::api::Result<Vec<_>>>()?;
+    let timeout = timeout.map_or(DEFAULT_SCAN_TIMEOUT, Duration::from_millis);
+    let peripherals = scan_devices(timeout, &services)?;
+
+    let mut names = devices.names.lock().unwrap();
+    for peripheral in &peripherals {
+      if let Some(name) = &peripheral.name {
+        names.insert(peripheral.id.clone(), name.clone());
+      }
+    }
+    Ok(peripherals)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn connect<R: Runtime>(
+    window: Window<R>,
+    scope: State<'_, BluetoothScope>,
+    devices: State<'_, Devices>,
+    id: String,
+  ) -> crate::Result<()> {
+    let allowed = match scope.is_allowed(&id) {
+      Some(allowed) => allowed,
+      None => {
+        let name = devices.names.lock().unwrap().get(&id).cloned();
+        let allowed = prompt(&window, name.as_deref().unwrap_or(&id));
+        if allowed {
+          scope.allow(id.clone());
+        } else {
+          scope.forbid(id.clone());
+        }
+        allowed
+      }
+    };
+    if !allowed {
+      return Err(crate::Error::FailedToExecuteApi(
+        io::Error::new(
+          ErrorKind::PermissionDenied,
+          format!("the connection to the Bluetooth device `{id}` was denied"),
+        )
+        .into(),
+      ));
+    }
+
+    let connected = devices.connections.lock().unwrap().contains_key(&id);
+    if !connected {
+      let connection = Connection::connect(&id)?;
+      devices
+        .connections
+        .lock()
+        .unwrap()
+        .entry(id)
+        .or_insert_with(|| Arc::new(connection));
+    }
+    Ok(())
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn read(
+    devices: State<'_, Devices>,
+    id: String,
+    service: String,
+    characteristic: String,
+  ) -> crate::Result<Vec<u8>> {
+    let (service, characteristic) = parse_uuids(&service, &characteristic)?;
+    devices
+      .connection(&id)?
+      .read(service, characteristic)
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn write(
+    devices: State<'_, Devices>,
+    id: String,
+    service: String,
+    characteristic: String,
+    data: Vec<u8>,
+    with_response: Option<bool>,
+  ) -> crate::Result<()> {
+    let (service, characteristic) = parse_uuids(&service, &characteristic)?;
+    devices
+      .connection(&id)?
+      .write(
+        service,
+        characteristic,
+        &data,
+        with_response.unwrap_or(true),
+      )
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn subscribe<R: Runtime>(
+    devices: State<'_, Devices>,
+    id: String,
+    service: String,
+    characteristic: String,
+    on_value: Channel<R>,
+  ) -> crate::Result<()> {
+    let (service, characteristic) = parse_uuids(&service, &characteristic)?;
+    let connection = devices.connection(&id)?;
+
+    // the channel waits for the frontend, which must not block the Bluetooth callbacks
+    let (tx, rx) = mpsc::channel::<Vec<u8>>();
+    std::thread::spawn(move || {
+      for value in rx {
+        if on_value.send(&value).is_err() {
+          break;
+        }
+      }
+    });
+    let tx = Mutex::new(tx);
+    connection
+      .subscribe(service, characteristic, move |value| {
+        let _ = tx.lock().unwrap().send(value.to_vec());
+      })
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn disconnect(devices: State<'_, Devices>, id: String) -> crate::Result<()> {
+    let connection = devices.connections.lock().unwrap().remove(&id);
+    match connection {
+      Some(connection) => connection.disconnect().map_err(Into::into),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Initializes the bluetooth plugin, connecting the frontend to the devices the user allowed.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("bluetooth")
+    .invoke_handler(crate::generate_handler![
+      commands::scan,
+      commands::connect,
+      commands::read,
+      commands::write,
+      commands::subscribe,
+      commands::disconnect
+    ])
+    .setup(|app, _api| {
+      app.manage(BluetoothScope::default());
+      app.manage(commands::Devices::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{parse_uuid, BluetoothScope, Uuid};
+
+  #[test]
+  fn parses_uuids() {
+    assert_eq!(
+      parse_uuid("180d").unwrap(),
+      Uuid::parse_str("0000180d-0000-1000-8000-00805f9b34fb").unwrap()
+    );
+    assert_eq!(
+      parse_uuid("0000FFE0").unwrap(),
+      Uuid::parse_str("0000ffe0-0000-1000-8000-00805f9b34fb").unwrap()
+    );
+    assert_eq!(
+      parse_uuid("6e400001-b5a3-f393-e0a9-e50e24dcca9e").unwrap(),
+      Uuid::parse_str("6e400001-b5a3-f393-e0a9-e50e24dcca9e").unwrap()
+    );
+    assert!(parse_uuid("18g0").is_err());
+    assert!(parse_uuid("+18d").is_err());
+    assert!(parse_uuid("180d1").is_err());
+  }
+
+  #[test]
+  fn remembers_the_answers() {
+    let scope = BluetoothScope::default();
+    assert_eq!(scope.is_allowed("a"), None);
+    scope.allow("a");
+    scope.forbid("b");
+    assert_eq!(scope.is_allowed("a"), Some(true));
+    assert_eq!(scope.is_allowed("b"), Some(false));
+  }
+}
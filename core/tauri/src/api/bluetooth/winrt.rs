@@ -0,0 +1,315 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The WinRT Bluetooth LE backend.
+
+use super::{NotificationHandler, Peripheral};
+
+use uuid::Uuid;
+use windows::{
+  core::GUID,
+  Devices::Bluetooth::{
+    Advertisement::{
+      BluetoothLEAdvertisementReceivedEventArgs, BluetoothLEAdvertisementWatcher,
+      BluetoothLEScanningMode,
+    },
+    BluetoothCacheMode, BluetoothLEDevice,
+    GenericAttributeProfile::{
+      GattCharacteristic, GattCharacteristicProperties,
+      GattClientCharacteristicConfigurationDescriptorValue, GattCommunicationStatus,
+      GattValueChangedEventArgs, GattWriteOption,
+    },
+  },
+  Foundation::{EventRegistrationToken, TypedEventHandler},
+  Storage::Streams::{DataReader, DataWriter, IBuffer},
+};
+
+use std::{
+  collections::HashMap,
+  io,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+/// Formats a Bluetooth address as `AA:BB:CC:DD:EE:FF`.
+fn format_address(address: u64) -> String {
+  address.to_be_bytes()[2..]
+    .iter()
+    .map(|b| format!("{b:02X}"))
+    .collect::<Vec<_>>()
+    .join(":")
+}
+
+fn parse_address(id: &str) -> io::Result<u64> {
+  let hex = id.replace(':', "");
+  if hex.len() == 12 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+    u64::from_str_radix(&hex, 16).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+  } else {
+    Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("`{id}` is not a Bluetooth address"),
+    ))
+  }
+}
+
+fn error(e: windows::core::Error) -> io::Error {
+  io::Error::new(io::ErrorKind::Other, e.message().to_string())
+}
+
+fn check(status: GattCommunicationStatus, action: &str) -> io::Result<()> {
+  if status == GattCommunicationStatus::Success {
+    Ok(())
+  } else if status == GattCommunicationStatus::AccessDenied {
+    Err(io::Error::new(
+      io::ErrorKind::PermissionDenied,
+      format!("failed to {action}: access denied"),
+    ))
+  } else {
+    Err(io::Error::new(
+      io::ErrorKind::Other,
+      format!("failed to {action}: the device is unreachable"),
+    ))
+  }
+}
+
+fn guid(uuid: Uuid) -> GUID {
+  GUID::from_u128(uuid.as_u128())
+}
+
+fn bytes(buffer: &IBuffer) -> windows::core::Result<Vec<u8>> {
+  let mut data = vec![0; buffer.Length()? as usize];
+  DataReader::FromBuffer(buffer)?.ReadBytes(&mut data)?;
+  Ok(data)
+}
+
+pub(super) fn scan(timeout: Duration) -> io::Result<Vec<Peripheral>> {
+  let watcher = BluetoothLEAdvertisementWatcher::new().map_err(error)?;
+  watcher
+    .SetScanningMode(BluetoothLEScanningMode::Active)
+    .map_err(error)?;
+
+  let found = Arc::new(Mutex::new(HashMap::<u64, Peripheral>::new()));
+  let found_ = found.clone();
+  let token = watcher
+    .Received(&TypedEventHandler::new(
+      move |_, args: &Option<BluetoothLEAdvertisementReceivedEventArgs>| {
+        if let Some(args) = args {
+          let address = args.BluetoothAddress()?;
+          let advertisement = args.Advertisement()?;
+          let name = advertisement.LocalName()?.to_string();
+          let rssi = args.RawSignalStrengthInDBm()?;
+          let mut services = Vec::new();
+          for service in advertisement.ServiceUuids()? {
+            services.push(Uuid::from_u128(service.to_u128()));
+          }
+
+          // the name and the services may be in the scan response, a separate advertisement
+          let mut found = found_.lock().unwrap();
+          let peripheral = found.entry(address).or_insert_with(|| Peripheral {
+            id: format_address(address),
+            name: None,
+            rssi: None,
+            services: Vec::new(),
+          });
+          if !name.is_empty() {
+            peripheral.name.replace(name);
+          }
+          peripheral.rssi.replace(rssi);
+          for service in services {
+            if !peripheral.services.contains(&service) {
+              peripheral.services.push(service);
+            }
+          }
+        }
+        Ok(())
+      },
+    ))
+    .map_err(error)?;
+
+  watcher.Start().map_err(error)?;
+  std::thread::sleep(timeout);
+  watcher.Stop().map_err(error)?;
+  watcher.RemoveReceived(token).map_err(error)?;
+
+  let found = std::mem::take(&mut *found.lock().unwrap());
+  Ok(found.into_values().collect())
+}
+
+pub(super) struct Connection {
+  device: BluetoothLEDevice,
+  characteristics: Mutex<HashMap<(Uuid, Uuid), GattCharacteristic>>,
+  subscriptions: Mutex<Vec<(GattCharacteristic, EventRegistrationToken)>>,
+}
+
+impl Connection {
+  pub(super) fn connect(id: &str) -> io::Result<Self> {
+    let address = parse_address(id)?;
+    let device = BluetoothLEDevice::FromBluetoothAddressAsync(address)
+      .and_then(|operation| operation.get())
+      .map_err(|_| {
+        io::Error::new(
+          io::ErrorKind::NotFound,
+          format!("the Bluetooth device `{id}` was not found"),
+        )
+      })?;
+    // the device is connected when its services are first requested
+    let services = device
+      .GetGattServicesAsync()
+      .and_then(|operation| operation.get())
+      .map_err(error)?;
+    check(services.Status().map_err(error)?, "connect")?;
+    Ok(Self {
+      device,
+      characteristics: Default::default(),
+      subscriptions: Default::default(),
+    })
+  }
+
+  fn characteristic(&self, service: Uuid, characteristic: Uuid) -> io::Result<GattCharacteristic> {
+    if let Some(c) = self
+      .characteristics
+      .lock()
+      .unwrap()
+      .get(&(service, characteristic))
+    {
+      return Ok(c.clone());
+    }
+
+    let not_found = || {
+      io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("the characteristic {characteristic} of the service {service} was not found"),
+      )
+    };
+    let services = self
+      .device
+      .GetGattServicesForUuidAsync(guid(service))
+      .and_then(|operation| operation.get())
+      .map_err(error)?;
+    check(services.Status().map_err(error)?, "read the services")?;
+    let service_ = services
+      .Services()
+      .and_then(|services| services.GetAt(0))
+      .map_err(|_| not_found())?;
+    let characteristics = service_
+      .GetCharacteristicsForUuidAsync(guid(characteristic))
+      .and_then(|operation| operation.get())
+      .map_err(error)?;
+    check(
+      characteristics.Status().map_err(error)?,
+      "read the characteristics",
+    )?;
+    let c = characteristics
+      .Characteristics()
+      .and_then(|characteristics| characteristics.GetAt(0))
+      .map_err(|_| not_found())?;
+
+    self
+      .characteristics
+      .lock()
+      .unwrap()
+      .insert((service, characteristic), c.clone());
+    Ok(c)
+  }
+
+  pub(super) fn read(&self, service: Uuid, characteristic: Uuid) -> io::Result<Vec<u8>> {
+    let result = self
+      .characteristic(service, characteristic)?
+      .ReadValueWithCacheModeAsync(BluetoothCacheMode::Uncached)
+      .and_then(|operation| operation.get())
+      .map_err(error)?;
+    check(result.Status().map_err(error)?, "read the characteristic")?;
+    result
+      .Value()
+      .and_then(|value| bytes(&value))
+      .map_err(error)
+  }
+
+  pub(super) fn write(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    data: &[u8],
+    with_response: bool,
+  ) -> io::Result<()> {
+    let writer = DataWriter::new().map_err(error)?;
+    writer.WriteBytes(data).map_err(error)?;
+    let buffer = writer.DetachBuffer().map_err(error)?;
+    let option = if with_response {
+      GattWriteOption::WriteWithResponse
+    } else {
+      GattWriteOption::WriteWithoutResponse
+    };
+    let status = self
+      .characteristic(service, characteristic)?
+      .WriteValueWithOptionAsync(&buffer, option)
+      .and_then(|operation| operation.get())
+      .map_err(error)?;
+    check(status, "write the characteristic")
+  }
+
+  pub(super) fn subscribe(
+    &self,
+    service: Uuid,
+    characteristic: Uuid,
+    handler: NotificationHandler,
+  ) -> io::Result<()> {
+    let c = self.characteristic(service, characteristic)?;
+    let token = c
+      .ValueChanged(&TypedEventHandler::new(
+        move |_, args: &Option<GattValueChangedEventArgs>| {
+          if let Some(args) = args {
+            handler(&bytes(&args.CharacteristicValue()?)?);
+          }
+          Ok(())
+        },
+      ))
+      .map_err(error)?;
+
+    let properties = c.CharacteristicProperties().map_err(error)?;
+    let value = if properties & GattCharacteristicProperties::Notify
+      == GattCharacteristicProperties::Notify
+    {
+      GattClientCharacteristicConfigurationDescriptorValue::Notify
+    } else {
+      GattClientCharacteristicConfigurationDescriptorValue::Indicate
+    };
+    let status = c
+      .WriteClientCharacteristicConfigurationDescriptorAsync(value)
+      .and_then(|operation| operation.get())
+      .map_err(error);
+    if let Err(e) = status.and_then(|status| check(status, "subscribe to the characteristic")) {
+      let _ = c.RemoveValueChanged(token);
+      return Err(e);
+    }
+
+    self.subscriptions.lock().unwrap().push((c, token));
+    Ok(())
+  }
+
+  pub(super) fn disconnect(&self) -> io::Result<()> {
+    for (c, token) in self.subscriptions.lock().unwrap().drain(..) {
+      let _ = c.RemoveValueChanged(token);
+    }
+    // the characteristics hold the services, which keep the device connected
+    self.characteristics.lock().unwrap().clear();
+    self.device.Close().map_err(error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{format_address, parse_address};
+
+  #[test]
+  fn formats_addresses() {
+    assert_eq!(format_address(0x1234_5678_9abc), "12:34:56:78:9A:BC");
+    assert_eq!(
+      parse_address("12:34:56:78:9a:bc").unwrap(),
+      0x1234_5678_9abc
+    );
+    assert!(parse_address("12:34:56:78:9A").is_err());
+    assert!(parse_address("12:34:56:78:9A:+C").is_err());
+  }
+}
@@ -0,0 +1,224 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Hardware-backed keys and encrypted storage on mobile, e.g. to keep the refresh tokens of the app.
+//!
+//! The signing keys are ECDSA P-256 keys generated in the Android Keystore or the iOS Keychain,
+//! in the StrongBox or the Secure Enclave when [`KeyOptions::hardware_backed`] is set and the device has one.
+//! The private keys never leave the secure hardware: [`public_key`] returns the DER-encoded `SubjectPublicKeyInfo`
+//! and [`sign`] returns the DER-encoded ECDSA signature of the SHA-256 digest of the data, on both platforms.
+//!
+//! The secrets are stored in the iOS Keychain, and encrypted with an AES-GCM key of the Android Keystore on Android.
+//! They are only readable by the app on this device: the secrets restored from the backup of another device are discarded.
+//!
+//! The functions block until the platform returns, they must not be called on the main thread.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux / macOS / Windows**: Unsupported, the functions return [`Error::Unsupported`].
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Serialize;
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+/// Errors of the crypto functions.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The platform does not have a keystore.
+  #[error("hardware-backed keys are not supported on this platform")]
+  Unsupported,
+  /// No key was generated with the alias.
+  #[error("no key found with alias `{0}`")]
+  KeyNotFound(String),
+  /// The keystore rejected the operation, e.g. when the user did not authenticate.
+  #[error("keystore error: {0}")]
+  Keystore(String),
+  /// The keystore returned invalid data.
+  #[error("invalid keystore response: {0}")]
+  InvalidResponse(#[from] base64::DecodeError),
+}
+
+/// Options of a generated key.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct KeyOptions {
+  /// Generates the key in the StrongBox on Android and the Secure Enclave on iOS, falling back to the keystore
+  /// when the device does not have one.
+  pub hardware_backed: bool,
+  /// Requires the user to authenticate with their biometrics or device credentials before each signature.
+  pub require_user_authentication: bool,
+}
+
+impl KeyOptions {
+  /// Generates the key in the secure hardware of the device when it has one.
+  #[must_use]
+  pub fn hardware_backed(mut self, hardware_backed: bool) -> Self {
+    self.hardware_backed = hardware_backed;
+    self
+  }
+
+  /// Requires the user to authenticate before each signature.
+  #[must_use]
+  pub fn require_user_authentication(mut self, require_user_authentication: bool) -> Self {
+    self.require_user_authentication = require_user_authentication;
+    self
+  }
+}
+
+/// The native crypto plugin.
+#[cfg(mobile)]
+struct CryptoPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyPayload<'a> {
+  alias: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  data: Option<String>,
+  hardware_backed: bool,
+  require_user_authentication: bool,
+}
+
+impl<'a> KeyPayload<'a> {
+  fn new(alias: &'a str) -> Self {
+    Self {
+      alias,
+      data: None,
+      hardware_backed: false,
+      require_user_authentication: false,
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct SecretPayload<'a> {
+  key: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  value: Option<String>,
+}
+
+/// The base64-encoded data returned by the native plugin.
+#[cfg(mobile)]
+#[derive(serde::Deserialize)]
+struct Response {
+  data: Option<String>,
+}
+
+/// Runs a command of the native plugin, returning the decoded data of the response.
+fn run<R: Runtime, M: Manager<R>, P: Serialize>(
+  manager: &M,
+  command: &str,
+  payload: P,
+) -> crate::api::Result<Option<Vec<u8>>> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<CryptoPlugin<R>>() {
+    use crate::plugin::mobile::PluginInvokeError;
+
+    let response = plugin
+      .0
+      .run_mobile_plugin::<Response>(command, payload)
+      .map_err(|e| match e {
+        PluginInvokeError::InvokeRejected(response) => {
+          Error::Keystore(response.message.unwrap_or_else(|| response.to_string()))
+        }
+        e => Error::Keystore(e.to_string()),
+      })?;
+    return response
+      .data
+      .map(|data| STANDARD.decode(data))
+      .transpose()
+      .map_err(|e| Error::InvalidResponse(e).into());
+  }
+
+  let _ = (manager, command, payload);
+  Err(Error::Unsupported.into())
+}
+
+/// Generates a signing key stored under `alias`, replacing the existing key, and returns its public key.
+pub fn generate_key<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  alias: &str,
+  options: KeyOptions,
+) -> crate::api::Result<Vec<u8>> {
+  let payload = KeyPayload {
+    hardware_backed: options.hardware_backed,
+    require_user_authentication: options.require_user_authentication,
+    ..KeyPayload::new(alias)
+  };
+  run(manager, "generateKey", payload)?.ok_or_else(|| Error::KeyNotFound(alias.into()).into())
+}
+
+/// The DER-encoded `SubjectPublicKeyInfo` of the key stored under `alias`, or `None` if there is no such key.
+pub fn public_key<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  alias: &str,
+) -> crate::api::Result<Option<Vec<u8>>> {
+  run(manager, "publicKey", KeyPayload::new(alias))
+}
+
+/// Signs the SHA-256 digest of `data` with the key stored under `alias`, returning the DER-encoded ECDSA signature.
+pub fn sign<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  alias: &str,
+  data: &[u8],
+) -> crate::api::Result<Vec<u8>> {
+  let payload = KeyPayload {
+    data: Some(STANDARD.encode(data)),
+    ..KeyPayload::new(alias)
+  };
+  run(manager, "sign", payload)?.ok_or_else(|| Error::KeyNotFound(alias.into()).into())
+}
+
+/// Deletes the key stored under `alias`, if any.
+pub fn delete_key<R: Runtime, M: Manager<R>>(manager: &M, alias: &str) -> crate::api::Result<()> {
+  run(manager, "deleteKey", KeyPayload::new(alias)).map(|_| ())
+}
+
+/// Stores the encrypted `value` under `key`, replacing the existing value.
+pub fn set_secret<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  key: &str,
+  value: &[u8],
+) -> crate::api::Result<()> {
+  let payload = SecretPayload {
+    key,
+    value: Some(STANDARD.encode(value)),
+  };
+  run(manager, "setSecret", payload).map(|_| ())
+}
+
+/// The value stored under `key`, or `None` if there is no such value.
+pub fn get_secret<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  key: &str,
+) -> crate::api::Result<Option<Vec<u8>>> {
+  run(manager, "getSecret", SecretPayload { key, value: None })
+}
+
+/// Deletes the value stored under `key`, if any.
+pub fn delete_secret<R: Runtime, M: Manager<R>>(manager: &M, key: &str) -> crate::api::Result<()> {
+  run(manager, "deleteSecret", SecretPayload { key, value: None }).map(|_| ())
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("crypto")
+    .setup(|app, _api| {
+      #[cfg(target_os = "android")]
+      app.manage(CryptoPlugin(
+        _api.register_android_plugin("app.tauri.crypto", "CryptoPlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      app.manage(CryptoPlugin(
+        _api.register_ios_plugin(crate::ios::init_crypto_plugin)?,
+      ));
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
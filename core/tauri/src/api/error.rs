@@ -15,4 +15,27 @@ pub enum Error {
   /// IO error.
   #[error(transparent)]
   Io(#[from] std::io::Error),
+  /// Invalid `tauri > security > tls` configuration.
+  #[error("invalid TLS configuration: {0}")]
+  Tls(String),
+  /// OAuth authorization error.
+  #[cfg(feature = "oauth")]
+  #[error(transparent)]
+  OAuth(#[from] crate::api::oauth::Error),
+  /// Keystore error.
+  #[cfg(feature = "crypto")]
+  #[error(transparent)]
+  Crypto(#[from] crate::api::crypto::Error),
+  /// Credential store error.
+  #[cfg(feature = "keyring")]
+  #[error(transparent)]
+  Keyring(#[from] crate::api::keyring::Error),
+  /// Authentication error.
+  #[cfg(feature = "auth")]
+  #[error(transparent)]
+  Auth(#[from] crate::api::auth::Error),
+  /// Share sheet error.
+  #[cfg(feature = "share")]
+  #[error(transparent)]
+  Share(#[from] crate::api::share::Error),
 }
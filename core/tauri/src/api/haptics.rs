@@ -0,0 +1,138 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Haptic feedback, matching the feedback of the system controls.
+//!
+//! The feedback is played asynchronously, the functions return once it is requested.
+//!
+//! ## Platform-specific
+//!
+//! - **Android**: Uses the haptic feedback constants of the views, which the user can disable in the system settings.
+//!   The notification feedback requires Android 11, and falls back to the impact feedback on older versions.
+//! - **Linux / macOS / Windows**: No-op.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+/// The intensity of an impact feedback, e.g. when a view snaps into place.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ImpactStyle {
+  /// A light impact, e.g. for small elements.
+  Light,
+  /// A medium impact.
+  #[default]
+  Medium,
+  /// A heavy impact, e.g. for large elements.
+  Heavy,
+}
+
+/// The outcome of a task reported by a notification feedback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum NotificationFeedback {
+  /// The task succeeded.
+  Success,
+  /// The task produced a warning.
+  Warning,
+  /// The task failed.
+  Error,
+}
+
+/// The native haptics plugin.
+#[cfg(mobile)]
+struct HapticsPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Feedback {
+  Impact { style: ImpactStyle },
+  Selection,
+  Notification { feedback: NotificationFeedback },
+}
+
+fn perform<R: Runtime, M: Manager<R>>(manager: &M, feedback: Feedback) -> crate::Result<()> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<HapticsPlugin<R>>() {
+    return plugin
+      .0
+      .run_mobile_plugin::<()>("perform", feedback)
+      .map_err(Into::into);
+  }
+
+  let _ = (manager, feedback);
+  Ok(())
+}
+
+/// Plays an impact feedback, e.g. when a dragged view collides with a boundary.
+pub fn impact<R: Runtime, M: Manager<R>>(manager: &M, style: ImpactStyle) -> crate::Result<()> {
+  perform(manager, Feedback::Impact { style })
+}
+
+/// Plays a selection feedback, e.g. when the value of a picker changes.
+pub fn selection<R: Runtime, M: Manager<R>>(manager: &M) -> crate::Result<()> {
+  perform(manager, Feedback::Selection)
+}
+
+/// Plays a notification feedback, reporting the outcome of a task.
+pub fn notification<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  feedback: NotificationFeedback,
+) -> crate::Result<()> {
+  perform(manager, Feedback::Notification { feedback })
+}
+
+mod commands {
+  use super::{ImpactStyle, NotificationFeedback};
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn impact<R: Runtime>(
+    app: AppHandle<R>,
+    style: Option<ImpactStyle>,
+  ) -> crate::Result<()> {
+    super::impact(&app, style.unwrap_or_default())
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn selection<R: Runtime>(app: AppHandle<R>) -> crate::Result<()> {
+    super::selection(&app)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn notification<R: Runtime>(
+    app: AppHandle<R>,
+    feedback: NotificationFeedback,
+  ) -> crate::Result<()> {
+    super::notification(&app, feedback)
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("haptics")
+    .invoke_handler(crate::generate_handler![
+      commands::impact,
+      commands::selection,
+      commands::notification
+    ])
+    .setup(|app, _api| {
+      #[cfg(target_os = "android")]
+      app.manage(HapticsPlugin(
+        _api.register_android_plugin("app.tauri.haptics", "HapticsPlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      app.manage(HapticsPlugin(
+        _api.register_ios_plugin(crate::ios::init_haptics_plugin)?,
+      ));
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
@@ -0,0 +1,440 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Human interface devices, e.g. gamepads, read from Rust instead of the Gamepad API of the webview,
+//! which is missing or behaves differently on each platform webview.
+//!
+//! The frontend lists the devices with `devices` from `@tauri-apps/api/hid` and receives the input reports of a device with `listen`.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux**: Uses the `hidraw` devices. The user needs read and write access to `/dev/hidraw*`, usually given by a udev rule.
+//!   Controllers driven by `xpad`, e.g. the Xbox ones over USB, are not HID devices.
+//! - **macOS / Android / iOS**: Unsupported, the functions fail with [`std::io::ErrorKind::Unsupported`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+use serde::Serialize;
+
+use std::{
+  fs::File,
+  io::{Read, Write},
+};
+
+/// The size of the buffer an input report is read into, the largest report size of the HID drivers.
+const REPORT_BUFFER_SIZE: usize = 4096;
+
+/// A human interface device connected to the computer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DeviceInfo {
+  /// The platform path the device is opened with.
+  pub path: String,
+  /// The USB vendor identifier.
+  pub vendor_id: u16,
+  /// The USB product identifier.
+  pub product_id: u16,
+  /// The product name reported by the device.
+  pub product_name: Option<String>,
+  /// The serial number reported by the device.
+  pub serial_number: Option<String>,
+  /// The usage page of the top level collection, e.g. `0x01` for the generic desktop controls.
+  pub usage_page: u16,
+  /// The usage of the top level collection in its page, e.g. `0x05` for a gamepad.
+  pub usage: u16,
+}
+
+impl DeviceInfo {
+  /// Whether the device is a joystick, a gamepad or a multi-axis controller.
+  pub fn is_gamepad(&self) -> bool {
+    self.usage_page == 0x01 && matches!(self.usage, 0x04 | 0x05 | 0x08)
+  }
+}
+
+/// An open human interface device.
+#[derive(Debug)]
+pub struct Device {
+  file: File,
+}
+
+impl Device {
+  /// Opens the device at the path of its [`DeviceInfo`].
+  pub fn open(path: &str) -> crate::api::Result<Self> {
+    #[cfg(any(target_os = "linux", windows))]
+    return std::fs::OpenOptions::new()
+      .read(true)
+      .write(true)
+      .open(path)
+      .map(|file| Self { file })
+      .map_err(Into::into);
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    return Err(unsupported());
+  }
+
+  /// Blocks until the device sends an input report and reads it into the buffer, returning its length.
+  ///
+  /// The report starts with its report identifier, or `0` on Windows if the device does not number its reports.
+  pub fn read(&mut self, buf: &mut [u8]) -> crate::api::Result<usize> {
+    self.file.read(buf).map_err(Into::into)
+  }
+
+  /// Sends an output report to the device. The first byte is the report identifier, `0` if the device does not number its reports.
+  pub fn write(&mut self, report: &[u8]) -> crate::api::Result<()> {
+    self.file.write_all(report).map_err(Into::into)
+  }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn unsupported() -> crate::api::Error {
+  std::io::Error::new(
+    std::io::ErrorKind::Unsupported,
+    "HID devices are not supported on this platform",
+  )
+  .into()
+}
+
+/// Lists the human interface devices connected to the computer.
+pub fn devices() -> crate::api::Result<Vec<DeviceInfo>> {
+  #[cfg(target_os = "linux")]
+  return linux::devices();
+
+  #[cfg(windows)]
+  return win32::devices();
+
+  #[cfg(not(any(target_os = "linux", windows)))]
+  return Err(unsupported());
+}
+
+/// Parses the usage page and the usage of the first top level collection of a report descriptor.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn top_level_usage(descriptor: &[u8]) -> (u16, u16) {
+  let (mut usage_page, mut usage) = (0, 0);
+  let mut i = 0;
+  while i < descriptor.len() {
+    let prefix = descriptor[i];
+    if prefix == 0xFE {
+      // long items are not used by the usages, their data size is the next byte
+      i += 3 + descriptor.get(i + 1).copied().unwrap_or_default() as usize;
+      continue;
+    }
+    let size = match prefix & 0x03 {
+      3 => 4,
+      size => size as usize,
+    };
+    let data = descriptor
+      .get(i + 1..i + 1 + size)
+      .unwrap_or_default()
+      .iter()
+      .rev()
+      .fold(0u32, |value, byte| value << 8 | *byte as u32);
+    match prefix & 0xFC {
+      // Usage Page
+      0x04 => usage_page = data as u16,
+      // Usage, the first one is the usage of the collection
+      0x08 if usage == 0 => usage = data as u16,
+      // Collection
+      0xA0 => break,
+      _ => {}
+    }
+    i += 1 + size;
+  }
+  (usage_page, usage)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use super::{top_level_usage, DeviceInfo};
+  use std::fs;
+
+  /// The identifiers and the strings of the `uevent` file of a HID device.
+  pub(super) fn parse_uevent(uevent: &str) -> Option<(u16, u16, Option<String>, Option<String>)> {
+    let mut ids = None;
+    let mut name = None;
+    let mut serial_number = None;
+    for line in uevent.lines() {
+      if let Some((key, value)) = line.split_once('=') {
+        match key {
+          // bus:vendor:product, in hexadecimal
+          "HID_ID" => {
+            let mut parts = value.split(':').skip(1);
+            let vendor_id = u32::from_str_radix(parts.next()?, 16).ok()?;
+            let product_id = u32::from_str_radix(parts.next()?, 16).ok()?;
+            ids.replace((vendor_id as u16, product_id as u16));
+          }
+          "HID_NAME" if !value.is_empty() => name = Some(value.to_string()),
+          "HID_UNIQ" if !value.is_empty() => serial_number = Some(value.to_string()),
+          _ => {}
+        }
+      }
+    }
+    ids.map(|(vendor_id, product_id)| (vendor_id, product_id, name, serial_number))
+  }
+
+  pub(super) fn devices() -> crate::api::Result<Vec<DeviceInfo>> {
+    let mut devices = Vec::new();
+    let entries = match fs::read_dir("/sys/class/hidraw") {
+      Ok(entries) => entries,
+      // the `hidraw` module is not loaded when no device was connected
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+      Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+      let entry = entry?;
+      let device = entry.path().join("device");
+      let uevent = match fs::read_to_string(device.join("uevent")) {
+        Ok(uevent) => uevent,
+        Err(_) => continue,
+      };
+      if let Some((vendor_id, product_id, product_name, serial_number)) = parse_uevent(&uevent) {
+        let descriptor = fs::read(device.join("report_descriptor")).unwrap_or_default();
+        let (usage_page, usage) = top_level_usage(&descriptor);
+        devices.push(DeviceInfo {
+          path: format!("/dev/{}", entry.file_name().to_string_lossy()),
+          vendor_id,
+          product_id,
+          product_name,
+          serial_number,
+          usage_page,
+          usage,
+        });
+      }
+    }
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(devices)
+  }
+}
+
+#[cfg(windows)]
+mod win32 {
+  use super::DeviceInfo;
+  use std::{fs::OpenOptions, os::windows::fs::OpenOptionsExt, os::windows::io::AsRawHandle};
+  use windows::Win32::{
+    Devices::{
+      DeviceAndDriverInstallation::{
+        SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInterfaces, SetupDiGetClassDevsW,
+        SetupDiGetDeviceInterfaceDetailW, DIGCF_DEVICEINTERFACE, DIGCF_PRESENT,
+        SP_DEVICE_INTERFACE_DATA, SP_DEVICE_INTERFACE_DETAIL_DATA_W,
+      },
+      HumanInterfaceDevice::{
+        HidD_FreePreparsedData, HidD_GetAttributes, HidD_GetHidGuid, HidD_GetPreparsedData,
+        HidD_GetProductString, HidD_GetSerialNumberString, HidP_GetCaps, HIDD_ATTRIBUTES,
+        HIDP_CAPS,
+      },
+    },
+    Foundation::{BOOLEAN, HANDLE, HWND},
+  };
+
+  /// Reads a string of the device, at most 126 characters long for USB devices.
+  fn device_string(get: impl FnOnce(*mut std::ffi::c_void, u32) -> BOOLEAN) -> Option<String> {
+    let mut buffer = [0u16; 128];
+    if !get(
+      buffer.as_mut_ptr() as _,
+      (buffer.len() * std::mem::size_of::<u16>()) as u32,
+    )
+    .as_bool()
+    {
+      return None;
+    }
+    let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+    Some(String::from_utf16_lossy(&buffer[..len])).filter(|s| !s.is_empty())
+  }
+
+  /// The device path of the interface, read from the variable length detail structure.
+  unsafe fn interface_path(
+    set: windows::Win32::Devices::DeviceAndDriverInstallation::HDEVINFO,
+    interface: &SP_DEVICE_INTERFACE_DATA,
+  ) -> Option<String> {
+    let mut size = 0;
+    SetupDiGetDeviceInterfaceDetailW(set, interface, None, 0, Some(&mut size), None);
+    if size == 0 {
+      return None;
+    }
+    // u32 elements keep the structure aligned
+    let mut buffer = vec![0u32; (size as usize + 3) / 4];
+    let detail = buffer.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
+    (*detail).cbSize = std::mem::size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+    if !SetupDiGetDeviceInterfaceDetailW(set, interface, Some(detail), size, None, None).as_bool() {
+      return None;
+    }
+    let path = std::ptr::addr_of!((*detail).DevicePath) as *const u16;
+    let len = (0..).take_while(|i| *path.add(*i) != 0).count();
+    Some(String::from_utf16_lossy(std::slice::from_raw_parts(
+      path, len,
+    )))
+  }
+
+  unsafe fn device_info(path: String) -> Option<DeviceInfo> {
+    // the devices are queried without access rights, the keyboards and mice deny the read access
+    let file = OpenOptions::new().access_mode(0).open(&path).ok()?;
+    let handle = HANDLE(file.as_raw_handle() as isize);
+
+    let mut attributes = HIDD_ATTRIBUTES {
+      Size: std::mem::size_of::<HIDD_ATTRIBUTES>() as u32,
+      ..Default::default()
+    };
+    if !HidD_GetAttributes(handle, &mut attributes).as_bool() {
+      return None;
+    }
+
+    let mut caps = HIDP_CAPS::default();
+    let mut preparsed = 0;
+    if HidD_GetPreparsedData(handle, &mut preparsed).as_bool() {
+      let _ = HidP_GetCaps(preparsed, &mut caps);
+      HidD_FreePreparsedData(preparsed);
+    }
+
+    Some(DeviceInfo {
+      vendor_id: attributes.VendorID,
+      product_id: attributes.ProductID,
+      product_name: device_string(|buffer, len| HidD_GetProductString(handle, buffer, len)),
+      serial_number: device_string(|buffer, len| HidD_GetSerialNumberString(handle, buffer, len)),
+      usage_page: caps.UsagePage,
+      usage: caps.Usage,
+      path,
+    })
+  }
+
+  pub(super) fn devices() -> crate::api::Result<Vec<DeviceInfo>> {
+    unsafe {
+      let guid = HidD_GetHidGuid();
+      let set = SetupDiGetClassDevsW(
+        Some(&guid),
+        None,
+        HWND::default(),
+        DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+      )
+      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+      let mut devices = Vec::new();
+      let mut interface = SP_DEVICE_INTERFACE_DATA {
+        cbSize: std::mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+        ..Default::default()
+      };
+      let mut index = 0;
+      while SetupDiEnumDeviceInterfaces(set, None, &guid, index, &mut interface).as_bool() {
+        if let Some(device) = interface_path(set, &interface).and_then(|p| device_info(p)) {
+          devices.push(device);
+        }
+        index += 1;
+      }
+      SetupDiDestroyDeviceInfoList(set);
+      Ok(devices)
+    }
+  }
+}
+
+mod commands {
+  use super::{Device, DeviceInfo, REPORT_BUFFER_SIZE};
+  use crate::{api::ipc::Channel, command, Runtime, State};
+  use std::{
+    collections::HashMap,
+    sync::{
+      atomic::{AtomicBool, AtomicU32, Ordering},
+      Arc, Mutex,
+    },
+  };
+
+  /// The stop flags of the devices listened to by the frontend.
+  #[derive(Default)]
+  pub(super) struct Listeners {
+    next_id: AtomicU32,
+    stopped: Mutex<HashMap<u32, Arc<AtomicBool>>>,
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn devices() -> crate::Result<Vec<DeviceInfo>> {
+    super::devices().map_err(Into::into)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn listen<R: Runtime>(
+    listeners: State<'_, Listeners>,
+    path: String,
+    on_report: Channel<R>,
+  ) -> crate::Result<u32> {
+    let mut device = Device::open(&path)?;
+    let id = listeners.next_id.fetch_add(1, Ordering::Relaxed);
+    let stopped = Arc::new(AtomicBool::new(false));
+    listeners
+      .stopped
+      .lock()
+      .unwrap()
+      .insert(id, stopped.clone());
+
+    std::thread::spawn(move || {
+      let mut buf = vec![0; REPORT_BUFFER_SIZE];
+      // the thread ends on the first report after the listener is stopped or the channel is closed,
+      // or when the device is disconnected
+      while let Ok(len) = device.read(&mut buf) {
+        if stopped.load(Ordering::Relaxed) || on_report.send(&&buf[..len]).is_err() {
+          break;
+        }
+      }
+    });
+
+    Ok(id)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn unlisten(listeners: State<'_, Listeners>, id: u32) {
+    if let Some(stopped) = listeners.stopped.lock().unwrap().remove(&id) {
+      stopped.store(true, Ordering::Relaxed);
+    }
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn write(path: String, report: Vec<u8>) -> crate::Result<()> {
+    Device::open(&path)?.write(&report).map_err(Into::into)
+  }
+}
+
+/// Initializes the HID plugin, listing the devices and forwarding their input reports to the frontend.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("hid")
+    .invoke_handler(crate::generate_handler![
+      commands::devices,
+      commands::listen,
+      commands::unlisten,
+      commands::write
+    ])
+    .setup(|app, _api| {
+      app.manage(commands::Listeners::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::top_level_usage;
+
+  #[test]
+  fn parses_top_level_usages() {
+    // Usage Page (Generic Desktop), Usage (Game Pad), Collection (Application), Usage (X)
+    let gamepad = [0x05, 0x01, 0x09, 0x05, 0xA1, 0x01, 0x09, 0x30];
+    assert_eq!(top_level_usage(&gamepad), (0x01, 0x05));
+
+    // Usage Page (Vendor Defined 0xFF00) with two bytes of data, Usage (0x01)
+    let vendor = [0x06, 0x00, 0xFF, 0x09, 0x01, 0xA1, 0x01];
+    assert_eq!(top_level_usage(&vendor), (0xFF00, 0x01));
+
+    assert_eq!(top_level_usage(&[]), (0, 0));
+  }
+
+  #[cfg(target_os = "linux")]
+  #[test]
+  fn parses_uevents() {
+    let uevent = "DRIVER=sony\nHID_ID=0003:0000054C:000009CC\nHID_NAME=Wireless Controller\nHID_PHYS=usb-0000:00:14.0-1/input3\nHID_UNIQ=\n";
+    assert_eq!(
+      super::linux::parse_uevent(uevent),
+      Some((0x054C, 0x09CC, Some("Wireless Controller".into()), None))
+    );
+    assert_eq!(super::linux::parse_uevent("DRIVER=sony\n"), None);
+  }
+}
@@ -14,16 +14,22 @@ use tauri_macros::default_runtime;
 
 use crate::{
   command::{CommandArg, CommandItem},
-  InvokeError, Runtime, Window,
+  ipc::{ChannelState, Channels, CHANNEL_ACK_INTERVAL},
+  InvokeError, Manager, Runtime, Window,
 };
+use std::sync::Arc;
 
 const CHANNEL_PREFIX: &str = "__CHANNEL__:";
 
-/// An IPC channel.
+/// An IPC channel, streaming messages to the `Channel` a window passed to a command.
+///
+/// The frontend acknowledges the messages it processed, and the messages sent while too many are pending are queued until then.
+/// The channel is closed when its window is destroyed, or when a message reaches a page that no longer has the `Channel`, e.g. after a reload.
 #[default_runtime(crate::Wry, wry)]
 pub struct Channel<R: Runtime> {
   id: CallbackFn,
   window: Window<R>,
+  state: Arc<ChannelState>,
 }
 
 impl<R: Runtime> Clone for Channel<R> {
@@ -31,6 +37,7 @@ impl<R: Runtime> Clone for Channel<R> {
     Self {
       id: self.id,
       window: self.window.clone(),
+      state: self.state.clone(),
     }
   }
 }
@@ -46,9 +53,14 @@ impl Serialize for Channel {
 
 impl<R: Runtime> Channel<R> {
   /// Sends the given data through the channel.
+  ///
+  /// Never blocks: the message is queued while the frontend has not processed the previous messages yet.
+  /// Fails with [`crate::Error::ChannelFull`] when the queue is full,
+  /// and with [`crate::Error::ChannelClosed`] once the channel is closed.
   pub fn send<S: Serialize>(&self, data: &S) -> crate::Result<()> {
-    let js = format_callback(self.id, data)?;
-    self.window.eval(&js)
+    self.state.send(&self.window, |index| {
+      format_channel_message(self.id, index, data).map_err(Into::into)
+    })
   }
 }
 
@@ -64,10 +76,12 @@ impl<'de, R: Runtime> CommandArg<'de, R> for Channel<R> {
       .split_once(CHANNEL_PREFIX)
       .and_then(|(_prefix, id)| id.parse().ok())
     {
-      return Ok(Channel {
-        id: CallbackFn(callback_id),
-        window,
-      });
+      let id = CallbackFn(callback_id);
+      let state = match window.try_state::<Channels>() {
+        Some(channels) => channels.register(window.label(), id),
+        None => Default::default(),
+      };
+      return Ok(Channel { id, window, state });
     }
     Err(InvokeError::from_anyhow(anyhow::anyhow!(
       "invalid channel value `{value}`, expected a string in the `{CHANNEL_PREFIX}ID` format"
@@ -248,6 +262,38 @@ pub fn format_callback<T: Serialize>(
   })
 }
 
+/// Formats the script delivering the message of a [`Channel`],
+/// which acknowledges it every [`CHANNEL_ACK_INTERVAL`] messages or closes the channel if its page was unloaded.
+fn format_channel_message<T: Serialize>(
+  channel: CallbackFn,
+  index: usize,
+  message: &T,
+) -> crate::api::Result<String> {
+  let ack = if index % CHANNEL_ACK_INTERVAL == 0 {
+    format!(
+      "window.__TAURI_INVOKE__('plugin:ipc|ack', {{ id: {id}, index: {index} }})",
+      id = channel.0
+    )
+  } else {
+    String::new()
+  };
+  serialize_js_with(message, Default::default(), |message| {
+    format!(
+      r#"
+    if (window["_{id}"]) {{
+      try {{
+        window["_{id}"]({message})
+      }} finally {{
+        {ack}
+      }}
+    }} else {{
+      window.__TAURI_INVOKE__('plugin:ipc|close', {{ id: {id} }})
+    }}"#,
+      id = channel.0
+    )
+  })
+}
+
 /// Formats a Result type to its Promise response.
 /// Useful for Promises handling.
 /// If the Result `is_ok()`, the callback will be the `success_callback` function name and the argument will be the Ok value.
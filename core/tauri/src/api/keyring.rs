@@ -0,0 +1,759 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Passwords of the app, e.g. its access tokens, stored in the credential store of the operating system.
+//!
+//! The entries are identified by a service and an account. The services are namespaced by the `tauri > bundle > identifier`
+//! of the app: the entries of the `api` service of the `com.tauri.app` app are stored with the `com.tauri.app.api` service,
+//! so the commands of the frontend only access the entries of the app.
+//!
+//! The functions block until the credential store returns, and it can prompt the user to unlock it,
+//! so they must not be called on the main thread.
+//!
+//! ## Platform-specific
+//!
+//! - **macOS / iOS**: Generic password items of the Keychain.
+//! - **Windows**: Generic credentials of the Credential Manager, encrypted with DPAPI for the user.
+//!   The target name of the credentials is `<service>/<account>` and the passwords are limited to 2560 bytes.
+//! - **Linux**: Items of the default collection of the Secret Service, e.g. GNOME Keyring or KWallet, over D-Bus.
+//! - **Android**: Encrypted with an AES-GCM key of the Android Keystore, in the shared preferences of the app.
+
+use serde::Serialize;
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+/// Errors of the keyring functions.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The credential store is not available, e.g. when no Secret Service is running.
+  #[error("the credential store is not available: {0}")]
+  Unavailable(String),
+  /// The user did not unlock the credential store.
+  #[error("the user did not unlock the credential store")]
+  Locked,
+  /// The stored password is not valid UTF-8.
+  #[error("the stored password is not valid UTF-8")]
+  InvalidPassword,
+  /// The credential store rejected the operation.
+  #[error("credential store error: {0}")]
+  CredentialStore(String),
+}
+
+/// The native keyring plugin.
+#[cfg(target_os = "android")]
+struct KeyringPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+#[derive(Serialize)]
+#[cfg_attr(not(target_os = "android"), allow(dead_code))]
+struct EntryPayload<'a> {
+  service: &'a str,
+  account: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  password: Option<&'a str>,
+}
+
+/// The name of `service` in the credential store, namespaced by the identifier of the app.
+pub fn service_name<R: Runtime, M: Manager<R>>(manager: &M, service: &str) -> String {
+  format!("{}.{service}", manager.config().tauri.bundle.identifier)
+}
+
+/// Stores the password of the account, replacing the existing password.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     tauri::api::keyring::set_password(app, "api", "alice", "refresh-token")?;
+///     Ok(())
+///   });
+/// ```
+pub fn set_password<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  service: &str,
+  account: &str,
+  password: &str,
+) -> crate::api::Result<()> {
+  let service = service_name(manager, service);
+  platform::set_password(manager, &service, account, password).map_err(Into::into)
+}
+
+/// The password of the account, or `None` if there is no such entry.
+pub fn get_password<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  service: &str,
+  account: &str,
+) -> crate::api::Result<Option<String>> {
+  let service = service_name(manager, service);
+  platform::get_password(manager, &service, account).map_err(Into::into)
+}
+
+/// Deletes the password of the account, if any.
+pub fn delete_password<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  service: &str,
+  account: &str,
+) -> crate::api::Result<()> {
+  let service = service_name(manager, service);
+  platform::delete_password(manager, &service, account).map_err(Into::into)
+}
+
+mod commands {
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn get_password<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+  ) -> crate::Result<Option<String>> {
+    super::get_password(&app, &service, &account).map_err(Into::into)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn set_password<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+    password: String,
+  ) -> crate::Result<()> {
+    super::set_password(&app, &service, &account, &password).map_err(Into::into)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn delete_password<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+  ) -> crate::Result<()> {
+    super::delete_password(&app, &service, &account).map_err(Into::into)
+  }
+}
+
+/// The keyring plugin, exposing the commands of `@tauri-apps/api/keyring` when the `tauri > security > keyring` allowlist enables them.
+pub(crate) fn init<R: Runtime>(commands: bool) -> TauriPlugin<R> {
+  let mut builder = Builder::new("keyring");
+  if commands {
+    builder = builder.invoke_handler(crate::generate_handler![
+      commands::get_password,
+      commands::set_password,
+      commands::delete_password
+    ]);
+  }
+  builder
+    .setup(|app, _api| {
+      #[cfg(target_os = "android")]
+      app.manage(KeyringPlugin(
+        _api.register_android_plugin("app.tauri.keyring", "KeyringPlugin")?,
+      ));
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use cocoa::base::{id, nil, YES};
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use std::{ffi::CStr, os::raw::c_void};
+
+  #[link(name = "Security", kind = "framework")]
+  extern "C" {
+    static kSecClass: id;
+    static kSecClassGenericPassword: id;
+    static kSecAttrService: id;
+    static kSecAttrAccount: id;
+    static kSecValueData: id;
+    static kSecReturnData: id;
+    static kSecMatchLimit: id;
+    static kSecMatchLimitOne: id;
+
+    fn SecItemAdd(attributes: id, result: *mut id) -> i32;
+    fn SecItemCopyMatching(query: id, result: *mut id) -> i32;
+    fn SecItemUpdate(query: id, attributes_to_update: id) -> i32;
+    fn SecItemDelete(query: id) -> i32;
+    fn SecCopyErrorMessageString(status: i32, reserved: *mut c_void) -> id;
+  }
+
+  const ERR_SEC_SUCCESS: i32 = 0;
+  const ERR_SEC_USER_CANCELED: i32 = -128;
+  const ERR_SEC_AUTH_FAILED: i32 = -25293;
+  const ERR_SEC_DUPLICATE_ITEM: i32 = -25299;
+  const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+  const ERR_SEC_INTERACTION_NOT_ALLOWED: i32 = -25308;
+
+  /// `NSUTF8StringEncoding`
+  const UTF8_ENCODING: usize = 4;
+
+  pub fn set_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+    password: &str,
+  ) -> Result<(), Error> {
+    with_pool(|| unsafe {
+      let query = query(service, account);
+      let data: id = msg_send![
+        class!(NSData),
+        dataWithBytes: password.as_ptr() as *const c_void
+        length: password.len()
+      ];
+      let attributes: id = msg_send![class!(NSMutableDictionary), dictionary];
+      set(attributes, kSecValueData, data);
+
+      let status = SecItemUpdate(query, attributes);
+      if status != ERR_SEC_ITEM_NOT_FOUND {
+        return check(status);
+      }
+      set(query, kSecValueData, data);
+      match SecItemAdd(query, std::ptr::null_mut()) {
+        // created by another thread since the update
+        ERR_SEC_DUPLICATE_ITEM => check(SecItemUpdate(self::query(service, account), attributes)),
+        status => check(status),
+      }
+    })
+  }
+
+  pub fn get_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<Option<String>, Error> {
+    with_pool(|| unsafe {
+      let query = query(service, account);
+      let yes: id = msg_send![class!(NSNumber), numberWithBool: YES];
+      set(query, kSecReturnData, yes);
+      set(query, kSecMatchLimit, kSecMatchLimitOne);
+
+      let mut data = nil;
+      match SecItemCopyMatching(query, &mut data) {
+        ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+        ERR_SEC_SUCCESS => {
+          let bytes: *const u8 = msg_send![data, bytes];
+          let length: usize = msg_send![data, length];
+          let password = if length == 0 {
+            Vec::new()
+          } else {
+            std::slice::from_raw_parts(bytes, length).to_vec()
+          };
+          let _: () = msg_send![data, release];
+          String::from_utf8(password)
+            .map(Some)
+            .map_err(|_| Error::InvalidPassword)
+        }
+        status => check(status).map(|_| None),
+      }
+    })
+  }
+
+  pub fn delete_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<(), Error> {
+    with_pool(|| unsafe {
+      match SecItemDelete(query(service, account)) {
+        ERR_SEC_ITEM_NOT_FOUND => Ok(()),
+        status => check(status),
+      }
+    })
+  }
+
+  fn with_pool<T>(f: impl FnOnce() -> T) -> T {
+    unsafe {
+      let pool: id = msg_send![class!(NSAutoreleasePool), new];
+      let result = f();
+      let _: () = msg_send![pool, drain];
+      result
+    }
+  }
+
+  /// The query of the generic password item of the entry.
+  unsafe fn query(service: &str, account: &str) -> id {
+    let query: id = msg_send![class!(NSMutableDictionary), dictionary];
+    set(query, kSecClass, kSecClassGenericPassword);
+    set(query, kSecAttrService, ns_string(service));
+    set(query, kSecAttrAccount, ns_string(account));
+    query
+  }
+
+  unsafe fn set(dictionary: id, key: id, value: id) {
+    let _: () = msg_send![dictionary, setObject: value forKey: key];
+  }
+
+  unsafe fn ns_string(value: &str) -> id {
+    let string: id = msg_send![class!(NSString), alloc];
+    let string: id = msg_send![
+      string,
+      initWithBytes: value.as_ptr() as *const c_void
+      length: value.len()
+      encoding: UTF8_ENCODING
+    ];
+    msg_send![string, autorelease]
+  }
+
+  unsafe fn check(status: i32) -> Result<(), Error> {
+    match status {
+      ERR_SEC_SUCCESS => Ok(()),
+      ERR_SEC_USER_CANCELED | ERR_SEC_AUTH_FAILED | ERR_SEC_INTERACTION_NOT_ALLOWED => {
+        Err(Error::Locked)
+      }
+      status => {
+        let message = SecCopyErrorMessageString(status, std::ptr::null_mut());
+        let description = if message == nil {
+          format!("OSStatus {status}")
+        } else {
+          let utf8: *const std::os::raw::c_char = msg_send![message, UTF8String];
+          let description = CStr::from_ptr(utf8).to_string_lossy().into_owned();
+          let _: () = msg_send![message, release];
+          description
+        };
+        Err(Error::CredentialStore(description))
+      }
+    }
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use windows::{
+    core::{PCWSTR, PWSTR},
+    Win32::{
+      Foundation::ERROR_NOT_FOUND,
+      Security::Credentials::{
+        CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+        CRED_TYPE_GENERIC,
+      },
+    },
+  };
+
+  use std::{io, iter::once, os::windows::ffi::OsStrExt};
+
+  /// `CRED_MAX_CREDENTIAL_BLOB_SIZE`
+  const MAX_PASSWORD_SIZE: usize = 5 * 512;
+
+  pub fn set_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+    password: &str,
+  ) -> Result<(), Error> {
+    if password.len() > MAX_PASSWORD_SIZE {
+      return Err(Error::CredentialStore(format!(
+        "the password exceeds {MAX_PASSWORD_SIZE} bytes"
+      )));
+    }
+    let mut target = target_name(service, account);
+    let mut user_name = wide(account);
+    let mut blob = password.as_bytes().to_vec();
+    let credential = CREDENTIALW {
+      Type: CRED_TYPE_GENERIC,
+      TargetName: PWSTR(target.as_mut_ptr()),
+      CredentialBlobSize: blob.len() as u32,
+      CredentialBlob: blob.as_mut_ptr(),
+      Persist: CRED_PERSIST_LOCAL_MACHINE,
+      UserName: PWSTR(user_name.as_mut_ptr()),
+      ..Default::default()
+    };
+    if unsafe { CredWriteW(&credential, 0) }.as_bool() {
+      Ok(())
+    } else {
+      Err(last_error())
+    }
+  }
+
+  pub fn get_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<Option<String>, Error> {
+    let target = target_name(service, account);
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+    unsafe {
+      if !CredReadW(
+        PCWSTR(target.as_ptr()),
+        CRED_TYPE_GENERIC,
+        0,
+        &mut credential,
+      )
+      .as_bool()
+      {
+        return match last_error() {
+          Error::CredentialStore(_) if not_found() => Ok(None),
+          e => Err(e),
+        };
+      }
+      let password = match (
+        (*credential).CredentialBlob,
+        (*credential).CredentialBlobSize,
+      ) {
+        (_, 0) => Vec::new(),
+        (blob, size) => std::slice::from_raw_parts(blob, size as usize).to_vec(),
+      };
+      CredFree(credential as *const _);
+      String::from_utf8(password)
+        .map(Some)
+        .map_err(|_| Error::InvalidPassword)
+    }
+  }
+
+  pub fn delete_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<(), Error> {
+    let target = target_name(service, account);
+    if unsafe { CredDeleteW(PCWSTR(target.as_ptr()), CRED_TYPE_GENERIC, 0) }.as_bool()
+      || not_found()
+    {
+      Ok(())
+    } else {
+      Err(last_error())
+    }
+  }
+
+  fn target_name(service: &str, account: &str) -> Vec<u16> {
+    wide(&format!("{service}/{account}"))
+  }
+
+  fn wide(value: &str) -> Vec<u16> {
+    std::ffi::OsStr::new(value)
+      .encode_wide()
+      .chain(once(0))
+      .collect()
+  }
+
+  fn not_found() -> bool {
+    io::Error::last_os_error().raw_os_error() == Some(ERROR_NOT_FOUND.0 as i32)
+  }
+
+  fn last_error() -> Error {
+    Error::CredentialStore(io::Error::last_os_error().to_string())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::Error;
+  use crate::{Manager, Runtime};
+
+  use gtk::{
+    gio::{self, BusType, Cancellable, DBusCallFlags, DBusConnection, DBusSignalFlags},
+    glib::{self, variant::ObjectPath, ToVariant, Variant},
+  };
+
+  use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+  const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+  const DEFAULT_COLLECTION: &str = "/org/freedesktop/secrets/aliases/default";
+  const SERVICE_INTERFACE: &str = "org.freedesktop.Secret.Service";
+  const COLLECTION_INTERFACE: &str = "org.freedesktop.Secret.Collection";
+  const ITEM_INTERFACE: &str = "org.freedesktop.Secret.Item";
+  const PROMPT_INTERFACE: &str = "org.freedesktop.Secret.Prompt";
+  /// The path returned instead of a prompt when the operation does not need one.
+  const NO_PROMPT: &str = "/";
+
+  /// A session of the Secret Service.
+  struct SecretService {
+    connection: DBusConnection,
+    session: ObjectPath,
+  }
+
+  impl SecretService {
+    fn connect() -> Result<Self, Error> {
+      let connection = gio::bus_get_sync(BusType::Session, Cancellable::NONE)
+        .map_err(|e| Error::Unavailable(e.to_string()))?;
+      // the secrets are not encrypted by the session, the session bus is only accessible to the user
+      let reply = call(
+        &connection,
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "OpenSession",
+        ("plain", "".to_variant()).to_variant(),
+      )
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+      let session = reply.child_value(1).get().ok_or_else(invalid_reply)?;
+      Ok(Self {
+        connection,
+        session,
+      })
+    }
+
+    fn call(
+      &self,
+      path: &str,
+      interface: &str,
+      method: &str,
+      parameters: Variant,
+    ) -> Result<Variant, Error> {
+      call(&self.connection, path, interface, method, parameters)
+        .map_err(|e| Error::CredentialStore(e.to_string()))
+    }
+
+    /// The items of the entry, unlocking them when needed.
+    fn items(&self, service: &str, account: &str) -> Result<Vec<ObjectPath>, Error> {
+      let reply = self.call(
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "SearchItems",
+        (attributes(service, account),).to_variant(),
+      )?;
+      let (mut unlocked, locked): (Vec<ObjectPath>, Vec<ObjectPath>) =
+        reply.get().ok_or_else(invalid_reply)?;
+      if !locked.is_empty() {
+        unlocked.extend(self.unlock(locked)?);
+      }
+      Ok(unlocked)
+    }
+
+    /// Unlocks the objects, prompting the user when needed, and returns the unlocked objects.
+    fn unlock(&self, objects: Vec<ObjectPath>) -> Result<Vec<ObjectPath>, Error> {
+      let reply = self.call(
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "Unlock",
+        (objects,).to_variant(),
+      )?;
+      let (unlocked, prompt): (Vec<ObjectPath>, ObjectPath) =
+        reply.get().ok_or_else(invalid_reply)?;
+      if prompt.as_str() == NO_PROMPT {
+        Ok(unlocked)
+      } else {
+        self.prompt(&prompt)?.get().ok_or_else(invalid_reply)
+      }
+    }
+
+    /// Shows the prompt and waits until the user completes it, returning its result.
+    fn prompt(&self, prompt: &str) -> Result<Variant, Error> {
+      let context = glib::MainContext::new();
+      context
+        .with_thread_default(|| {
+          let completed = Rc::new(RefCell::new(None));
+          let completed_ = completed.clone();
+          let subscription = self.connection.signal_subscribe(
+            None,
+            Some(PROMPT_INTERFACE),
+            Some("Completed"),
+            Some(prompt),
+            None,
+            DBusSignalFlags::NONE,
+            move |_, _, _, _, _, parameters| {
+              completed_.replace(parameters.get::<(bool, Variant)>());
+            },
+          );
+          let shown = self.call(prompt, PROMPT_INTERFACE, "Prompt", ("",).to_variant());
+          while shown.is_ok() && completed.borrow().is_none() {
+            context.iteration(true);
+          }
+          self.connection.signal_unsubscribe(subscription);
+          shown?;
+          let (dismissed, result) = completed.take().ok_or_else(invalid_reply)?;
+          if dismissed {
+            Err(Error::Locked)
+          } else {
+            Ok(result)
+          }
+        })
+        .map_err(|e| Error::CredentialStore(e.to_string()))?
+    }
+
+    fn complete(&self, prompt: ObjectPath) -> Result<(), Error> {
+      if prompt.as_str() != NO_PROMPT {
+        self.prompt(&prompt)?;
+      }
+      Ok(())
+    }
+  }
+
+  pub fn set_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+    password: &str,
+  ) -> Result<(), Error> {
+    let secrets = SecretService::connect()?;
+    let collection = ObjectPath::try_from(DEFAULT_COLLECTION).map_err(|_| invalid_reply())?;
+    secrets.unlock(vec![collection])?;
+
+    let properties = HashMap::from([
+      (
+        "org.freedesktop.Secret.Item.Label",
+        format!("{service}/{account}").to_variant(),
+      ),
+      (
+        "org.freedesktop.Secret.Item.Attributes",
+        attributes(service, account).to_variant(),
+      ),
+    ]);
+    // the (oayays) secret: its session, the parameters of the session, the value and its content type
+    let secret = (
+      secrets.session.clone(),
+      Vec::<u8>::new(),
+      password.as_bytes().to_vec(),
+      "text/plain; charset=utf8",
+    );
+    let reply = secrets.call(
+      DEFAULT_COLLECTION,
+      COLLECTION_INTERFACE,
+      "CreateItem",
+      (properties, secret, true).to_variant(),
+    )?;
+    let (_, prompt): (ObjectPath, ObjectPath) = reply.get().ok_or_else(invalid_reply)?;
+    secrets.complete(prompt)
+  }
+
+  pub fn get_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<Option<String>, Error> {
+    let secrets = SecretService::connect()?;
+    let item = match secrets.items(service, account)?.into_iter().next() {
+      Some(item) => item,
+      None => return Ok(None),
+    };
+    let reply = secrets.call(
+      &item,
+      ITEM_INTERFACE,
+      "GetSecret",
+      (secrets.session.clone(),).to_variant(),
+    )?;
+    let (_, _, password, _): (ObjectPath, Vec<u8>, Vec<u8>, String) =
+      reply.child_value(0).get().ok_or_else(invalid_reply)?;
+    String::from_utf8(password)
+      .map(Some)
+      .map_err(|_| Error::InvalidPassword)
+  }
+
+  pub fn delete_password<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<(), Error> {
+    let secrets = SecretService::connect()?;
+    for item in secrets.items(service, account)? {
+      let reply = secrets.call(&item, ITEM_INTERFACE, "Delete", ().to_variant())?;
+      let (prompt,): (ObjectPath,) = reply.get().ok_or_else(invalid_reply)?;
+      secrets.complete(prompt)?;
+    }
+    Ok(())
+  }
+
+  fn attributes<'a>(service: &'a str, account: &'a str) -> HashMap<&'a str, &'a str> {
+    HashMap::from([("service", service), ("account", account)])
+  }
+
+  fn call(
+    connection: &DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    parameters: Variant,
+  ) -> Result<Variant, glib::Error> {
+    connection.call_sync(
+      Some("org.freedesktop.secrets"),
+      path,
+      interface,
+      method,
+      Some(&parameters),
+      None,
+      DBusCallFlags::NONE,
+      -1,
+      Cancellable::NONE,
+    )
+  }
+
+  fn invalid_reply() -> Error {
+    Error::CredentialStore("invalid Secret Service reply".into())
+  }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+  use super::{EntryPayload, Error, KeyringPlugin};
+  use crate::{plugin::mobile::PluginInvokeError, Manager, Runtime};
+
+  #[derive(serde::Deserialize)]
+  struct Response {
+    password: Option<String>,
+  }
+
+  fn run<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    command: &str,
+    payload: EntryPayload<'_>,
+  ) -> Result<Option<String>, Error> {
+    let plugin = manager
+      .try_state::<KeyringPlugin<R>>()
+      .ok_or_else(|| Error::Unavailable("the keyring plugin is not registered".into()))?;
+    plugin
+      .0
+      .run_mobile_plugin::<Response>(command, payload)
+      .map(|response| response.password)
+      .map_err(|e| match e {
+        PluginInvokeError::InvokeRejected(response) => {
+          Error::CredentialStore(response.message.unwrap_or_else(|| response.to_string()))
+        }
+        e => Error::CredentialStore(e.to_string()),
+      })
+  }
+
+  pub fn set_password<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    service: &str,
+    account: &str,
+    password: &str,
+  ) -> Result<(), Error> {
+    let payload = EntryPayload {
+      service,
+      account,
+      password: Some(password),
+    };
+    run(manager, "setPassword", payload).map(|_| ())
+  }
+
+  pub fn get_password<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<Option<String>, Error> {
+    let payload = EntryPayload {
+      service,
+      account,
+      password: None,
+    };
+    run(manager, "getPassword", payload)
+  }
+
+  pub fn delete_password<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    service: &str,
+    account: &str,
+  ) -> Result<(), Error> {
+    let payload = EntryPayload {
+      service,
+      account,
+      password: None,
+    };
+    run(manager, "deletePassword", payload).map(|_| ())
+  }
+}
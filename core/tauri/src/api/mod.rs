@@ -4,11 +4,51 @@
 
 //! The Tauri API interface.
 
+#[cfg(feature = "auth")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "auth")))]
+pub mod auth;
+#[cfg(feature = "bluetooth")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bluetooth")))]
+pub mod bluetooth;
+#[cfg(feature = "crypto")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "crypto")))]
+pub mod crypto;
 pub mod dir;
 pub mod file;
+#[cfg(feature = "haptics")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "haptics")))]
+pub mod haptics;
+#[cfg(feature = "hid")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hid")))]
+pub mod hid;
 pub mod ipc;
+#[cfg(feature = "keyring")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "keyring")))]
+pub mod keyring;
+#[cfg(feature = "network")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "network")))]
+pub mod network;
+#[cfg(feature = "oauth")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "oauth")))]
+pub mod oauth;
+#[cfg(feature = "sensors")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "sensors")))]
+pub mod sensors;
+#[cfg(feature = "serial")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serial")))]
+pub mod serial;
+#[cfg(feature = "share")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "share")))]
+pub mod share;
+#[cfg(feature = "usb")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "usb")))]
+pub mod usb;
 pub mod version;
 
+#[cfg(feature = "share")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "share")))]
+pub use share::share;
+
 mod error;
 
 /// The error type of Tauri API module.
@@ -0,0 +1,949 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Resolution of the proxy configured in the operating system settings.
+//!
+//! The `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables have precedence over the system settings,
+//! as in most command line tools.
+//!
+//! The HTTP clients created with [`client_builder`] trust the root certificates and enforce the public key pins
+//! of the [`tauri > security > tls`](crate::utils::config::TlsConfig) configuration.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: Uses the WinHTTP auto-proxy, evaluating the proxy auto-config (PAC) files and the WPAD auto-discovery
+//!   enabled in the Internet Options, and the manual proxy and its bypass list otherwise.
+//! - **macOS / iOS**: Uses the CFNetwork proxy settings of the current network service, evaluating the PAC files.
+//! - **Linux**: Uses the GIO proxy resolver, which reads the desktop proxy settings and evaluates the PAC files with `glib-networking`.
+//! - **Android**: Only the environment variables are read.
+
+use crate::{utils::config::TlsConfig, Manager, Runtime};
+
+use serde::Serialize;
+use url::Url;
+
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+/// Resolves the proxy the operating system uses for the URL, e.g. `http://proxy.corp:8080` or `socks5://127.0.0.1:1080`.
+///
+/// Returns `None` when the URL is reached directly. Evaluating a proxy auto-config file downloads it
+/// and can detect it on the network, so this should not be called on the main thread.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::api::network::system_proxy;
+///
+/// let url = "https://tauri.app".parse().unwrap();
+/// match system_proxy(&url).unwrap() {
+///   Some(proxy) => println!("{url} is reached through {proxy}"),
+///   None => println!("{url} is reached directly"),
+/// }
+/// ```
+pub fn system_proxy(url: &Url) -> crate::api::Result<Option<Url>> {
+  if let Some(proxy) = env_proxy(url, |name| std::env::var(name).ok()) {
+    return Ok(proxy);
+  }
+  platform::system_proxy(url)
+}
+
+/// A [`reqwest::Proxy`] using the [`system_proxy`] of each origin, for the HTTP clients of the app and its plugins.
+///
+/// The proxy of an origin is resolved once, on its first request, and a failed resolution reaches the origin directly.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let client = reqwest::Client::builder()
+///   .proxy(tauri::api::network::reqwest_proxy())
+///   .build()
+///   .unwrap();
+/// ```
+pub fn reqwest_proxy() -> reqwest::Proxy {
+  let resolved = Mutex::new(HashMap::<String, Option<Url>>::new());
+  reqwest::Proxy::custom(move |url| {
+    resolved
+      .lock()
+      .unwrap()
+      .entry(url.origin().ascii_serialization())
+      .or_insert_with(|| system_proxy(url).ok().flatten())
+      .clone()
+  })
+}
+
+/// Creates an HTTP client builder applying the [`tauri > security > tls`](crate::utils::config::TlsConfig) configuration,
+/// for the HTTP clients of the app and its plugins, e.g. the http and updater plugins.
+///
+/// A request to a pinned host whose certificate chain contains none of its pinned keys fails with a [`CertificatePinMismatch`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[tauri::command]
+/// async fn fetch_license(app: tauri::AppHandle) -> Result<String, String> {
+///   let client = tauri::api::network::client_builder(&app)
+///     .map_err(|e| e.to_string())?
+///     .build()
+///     .map_err(|e| e.to_string())?;
+///   let response = client.get("https://api.example.com/license").send().await;
+///   match response {
+///     Ok(response) => response.text().await.map_err(|e| e.to_string()),
+///     Err(e) => Err(e.to_string()),
+///   }
+/// }
+/// ```
+pub fn client_builder<R: Runtime, M: Manager<R>>(
+  manager: &M,
+) -> crate::api::Result<reqwest::ClientBuilder> {
+  let config = manager.config();
+  let config = &config.tauri.security.tls;
+  let builder = reqwest::Client::builder();
+  if config == &TlsConfig::default() {
+    return Ok(builder);
+  }
+
+  let ca_bundle = match &config.ca_bundle {
+    Some(path) if path.is_absolute() => Some(std::fs::read(path)?),
+    Some(path) => {
+      let resource_dir = manager
+        .path()
+        .resource_dir()
+        .map_err(|e| crate::api::Error::Tls(e.to_string()))?;
+      Some(std::fs::read(resource_dir.join(path))?)
+    }
+    None => None,
+  };
+  tls::apply(builder, config, ca_bundle.as_deref().unwrap_or_default())
+}
+
+/// The error of a request to a host whose certificate chain contains none of the keys pinned by the
+/// [`tauri > security > tls > pins`](crate::utils::config::TlsConfig#structfield.pins) configuration.
+///
+/// It serializes to `{ "kind": "certificatePinMismatch", "host": "api.example.com" }`,
+/// so commands can return it to the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename = "certificatePinMismatch")]
+pub struct CertificatePinMismatch {
+  /// The host of the request.
+  pub host: String,
+}
+
+impl fmt::Display for CertificatePinMismatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "the certificate chain of {} does not contain a pinned public key",
+      self.host
+    )
+  }
+}
+
+impl std::error::Error for CertificatePinMismatch {}
+
+impl CertificatePinMismatch {
+  /// Finds the pin mismatch that caused the error, e.g. a [`reqwest::Error`].
+  pub fn find<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a Self> {
+    fn downcast<'a>(
+      error: &'a (dyn std::error::Error + 'static),
+    ) -> Option<&'a CertificatePinMismatch> {
+      #[cfg(feature = "rustls-tls")]
+      if let Some(rustls::Error::InvalidCertificate(rustls::CertificateError::Other(other))) =
+        error.downcast_ref::<rustls::Error>()
+      {
+        return other.as_ref().downcast_ref();
+      }
+      error.downcast_ref()
+    }
+
+    let mut error = Some(error);
+    while let Some(e) = error {
+      // `io::Error::source` skips the wrapped error
+      let wrapped = e
+        .downcast_ref::<std::io::Error>()
+        .and_then(|e| e.get_ref())
+        .map(|e| e as &(dyn std::error::Error + 'static));
+      if let Some(mismatch) = downcast(e).or_else(|| wrapped.and_then(downcast)) {
+        return Some(mismatch);
+      }
+      error = e.source();
+    }
+    None
+  }
+}
+
+/// The proxy of the environment variables: `Some(None)` when `NO_PROXY` excludes the URL,
+/// and `None` when no variable applies to the URL scheme.
+fn env_proxy(url: &Url, var: impl Fn(&str) -> Option<String>) -> Option<Option<Url>> {
+  let var = |name: &str| {
+    var(name)
+      .or_else(|| var(&name.to_lowercase()))
+      .filter(|value| !value.is_empty())
+  };
+
+  let proxy = match url.scheme() {
+    "https" => var("HTTPS_PROXY"),
+    "http" => var("HTTP_PROXY"),
+    _ => None,
+  }
+  .or_else(|| var("ALL_PROXY"))?;
+
+  let host = url.host_str().unwrap_or_default();
+  if var("NO_PROXY").map_or(false, |no_proxy| no_proxy_matches(&no_proxy, host)) {
+    return Some(None);
+  }
+
+  Some(parse_proxy(&proxy, "http"))
+}
+
+/// Whether the host matches an entry of the comma separated `NO_PROXY` list,
+/// e.g. `localhost,.corp.example.com,10.0.0.1` or `*`.
+fn no_proxy_matches(no_proxy: &str, host: &str) -> bool {
+  let host = host.trim_start_matches('[').trim_end_matches(']');
+  no_proxy
+    .split(',')
+    .map(|entry| {
+      entry
+        .trim()
+        .trim_start_matches("*.")
+        .trim_start_matches('.')
+    })
+    .filter(|entry| !entry.is_empty())
+    .any(|entry| {
+      entry == "*"
+        || host.eq_ignore_ascii_case(entry)
+        || host
+          .to_ascii_lowercase()
+          .ends_with(&format!(".{}", entry.to_ascii_lowercase()))
+    })
+}
+
+/// Parses a proxy address, using the default scheme when it has none, e.g. `proxy.corp:8080`.
+fn parse_proxy(proxy: &str, default_scheme: &str) -> Option<Url> {
+  let proxy = proxy.trim();
+  if proxy.contains("://") {
+    Url::parse(proxy).ok()
+  } else {
+    Url::parse(&format!("{default_scheme}://{proxy}")).ok()
+  }
+}
+
+/// The proxy of the URL scheme in a WinINet proxy list,
+/// either `host:port` for all schemes or `http=host:port;https=host:port;socks=host:port`.
+#[cfg(any(windows, test))]
+fn parse_proxy_list(list: &str, scheme: &str) -> Option<Url> {
+  let entries = list
+    .split(|c: char| c == ';' || c.is_whitespace())
+    .filter(|entry| !entry.is_empty())
+    .collect::<Vec<_>>();
+
+  let mut socks = None;
+  for entry in &entries {
+    match entry.split_once('=') {
+      Some((entry_scheme, proxy)) if entry_scheme.eq_ignore_ascii_case(scheme) => {
+        return parse_proxy(proxy, "http");
+      }
+      Some((entry_scheme, proxy)) if entry_scheme.eq_ignore_ascii_case("socks") => {
+        socks.get_or_insert(proxy);
+      }
+      Some(_) => {}
+      None => return parse_proxy(entry, "http"),
+    }
+  }
+  socks.and_then(|proxy| parse_proxy(proxy, "socks4"))
+}
+
+/// Whether the host matches an entry of a WinINet bypass list, e.g. `*.corp.example.com;<local>`,
+/// where `<local>` matches the hosts without a dot.
+#[cfg(any(windows, test))]
+fn bypass_matches(bypass: &str, host: &str) -> bool {
+  fn wildcard(pattern: &str, value: &str) -> bool {
+    match pattern.split_once('*') {
+      None => pattern == value,
+      Some((prefix, rest)) => {
+        value.starts_with(prefix)
+          && (0..=value.len() - prefix.len()).any(|i| {
+            value.is_char_boundary(prefix.len() + i) && wildcard(rest, &value[prefix.len() + i..])
+          })
+      }
+    }
+  }
+
+  let host = host.to_ascii_lowercase();
+  bypass
+    .split(|c: char| c == ';' || c.is_whitespace())
+    .filter(|entry| !entry.is_empty())
+    .any(|entry| {
+      if entry.eq_ignore_ascii_case("<local>") {
+        !host.contains('.')
+      } else {
+        wildcard(&entry.to_ascii_lowercase(), &host)
+      }
+    })
+}
+
+#[cfg(any(
+  feature = "native-tls",
+  feature = "native-tls-vendored",
+  feature = "rustls-tls"
+))]
+mod tls {
+  use crate::api::Error;
+  use base64::Engine;
+
+  /// The DER certificates of a PEM bundle.
+  pub fn pem_certificates(pem: &[u8]) -> crate::api::Result<Vec<Vec<u8>>> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    let pem = std::str::from_utf8(pem).map_err(|e| Error::Tls(e.to_string()))?;
+    let mut certificates = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = rest.find(BEGIN) {
+      let body = &rest[start + BEGIN.len()..];
+      let end = body
+        .find(END)
+        .ok_or_else(|| Error::Tls("unterminated PEM certificate".into()))?;
+      let encoded = body[..end]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+      certificates.push(
+        base64::engine::general_purpose::STANDARD
+          .decode(encoded)
+          .map_err(|e| Error::Tls(e.to_string()))?,
+      );
+      rest = &body[end + END.len()..];
+    }
+    Ok(certificates)
+  }
+
+  #[cfg(feature = "rustls-tls")]
+  pub fn apply(
+    builder: reqwest::ClientBuilder,
+    config: &crate::utils::config::TlsConfig,
+    ca_bundle: &[u8],
+  ) -> crate::api::Result<reqwest::ClientBuilder> {
+    use rustls::{
+      client::WebPkiVerifier, Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore,
+    };
+    use std::{collections::HashMap, sync::Arc};
+
+    let mut roots = RootCertStore::empty();
+    if config.builtin_roots {
+      roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+          anchor.subject,
+          anchor.spki,
+          anchor.name_constraints,
+        )
+      }));
+    }
+    for certificate in pem_certificates(ca_bundle)? {
+      roots
+        .add(&Certificate(certificate))
+        .map_err(|e| Error::Tls(e.to_string()))?;
+    }
+
+    let mut pins = HashMap::<String, Vec<[u8; 32]>>::new();
+    for pin in &config.pins {
+      let hashes = pins.entry(pin.host.to_ascii_lowercase()).or_default();
+      for hash in &pin.sha256 {
+        let hash = hash.strip_prefix("sha256/").unwrap_or(hash);
+        let hash = base64::engine::general_purpose::STANDARD
+          .decode(hash)
+          .ok()
+          .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+          .ok_or_else(|| {
+            Error::Tls(format!(
+              "the pin `{hash}` of {} is not a base64 encoded SHA-256 hash",
+              pin.host
+            ))
+          })?;
+        hashes.push(hash);
+      }
+    }
+
+    let verifier = super::pinning::PinnedVerifier {
+      inner: WebPkiVerifier::new(roots, None),
+      pins,
+    };
+    let tls = ClientConfig::builder()
+      .with_safe_defaults()
+      .with_custom_certificate_verifier(Arc::new(verifier))
+      .with_no_client_auth();
+    Ok(builder.use_preconfigured_tls(tls))
+  }
+
+  #[cfg(not(feature = "rustls-tls"))]
+  pub fn apply(
+    mut builder: reqwest::ClientBuilder,
+    config: &crate::utils::config::TlsConfig,
+    ca_bundle: &[u8],
+  ) -> crate::api::Result<reqwest::ClientBuilder> {
+    if !config.pins.is_empty() {
+      return Err(Error::Tls(
+        "certificate pinning requires the `rustls-tls` Cargo feature".into(),
+      ));
+    }
+    builder = builder.tls_built_in_root_certs(config.builtin_roots);
+    for certificate in pem_certificates(ca_bundle)? {
+      builder = builder.add_root_certificate(
+        reqwest::Certificate::from_der(&certificate).map_err(|e| Error::Tls(e.to_string()))?,
+      );
+    }
+    Ok(builder)
+  }
+}
+
+#[cfg(not(any(
+  feature = "native-tls",
+  feature = "native-tls-vendored",
+  feature = "rustls-tls"
+)))]
+mod tls {
+  pub fn apply(
+    _builder: reqwest::ClientBuilder,
+    _config: &crate::utils::config::TlsConfig,
+    _ca_bundle: &[u8],
+  ) -> crate::api::Result<reqwest::ClientBuilder> {
+    Err(crate::api::Error::Tls(
+      "the root certificates and the pins require the `native-tls` or `rustls-tls` Cargo feature"
+        .into(),
+    ))
+  }
+}
+
+#[cfg(any(feature = "rustls-tls", test))]
+mod pinning {
+  /// Reads the DER element at the start of the input, returning its tag, its content and the rest of the input.
+  fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&length, mut rest) = rest.split_first()?;
+    let length = if length < 0x80 {
+      length as usize
+    } else {
+      let bytes = (length & 0x7f) as usize;
+      if bytes == 0 || bytes > 4 || rest.len() < bytes {
+        return None;
+      }
+      let length = rest[..bytes]
+        .iter()
+        .fold(0usize, |length, byte| length << 8 | *byte as usize);
+      rest = &rest[bytes..];
+      length
+    };
+    (rest.len() >= length).then(|| (tag, &rest[..length], &rest[length..]))
+  }
+
+  /// The DER encoded `SubjectPublicKeyInfo` of a certificate, hashed by the pins.
+  pub fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    let (_, certificate, _) = der_element(certificate)?;
+    let (_, tbs_certificate, _) = der_element(certificate)?;
+    let mut rest = tbs_certificate;
+    // the explicitly tagged version is optional
+    if rest.first() == Some(&0xa0) {
+      rest = der_element(rest)?.2;
+    }
+    // the serial number, the signature algorithm, the issuer, the validity and the subject
+    for _ in 0..5 {
+      rest = der_element(rest)?.2;
+    }
+    let (_, _, after) = der_element(rest)?;
+    Some(&rest[..rest.len() - after.len()])
+  }
+
+  /// Whether the host matches the host of a pin, where `*.example.com` matches the subdomains of `example.com`.
+  pub fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+      Some(domain) => host.strip_suffix(domain).map_or(false, |subdomain| {
+        subdomain.len() > 1 && subdomain.ends_with('.')
+      }),
+      None => pattern == host,
+    }
+  }
+
+  #[cfg(feature = "rustls-tls")]
+  pub struct PinnedVerifier {
+    pub inner: rustls::client::WebPkiVerifier,
+    pub pins: std::collections::HashMap<String, Vec<[u8; 32]>>,
+  }
+
+  #[cfg(feature = "rustls-tls")]
+  impl rustls::client::ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+      &self,
+      end_entity: &rustls::Certificate,
+      intermediates: &[rustls::Certificate],
+      server_name: &rustls::ServerName,
+      scts: &mut dyn Iterator<Item = &[u8]>,
+      ocsp_response: &[u8],
+      now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+      use sha2::{Digest, Sha256};
+
+      let verified = self.inner.verify_server_cert(
+        end_entity,
+        intermediates,
+        server_name,
+        scts,
+        ocsp_response,
+        now,
+      )?;
+
+      let host = match server_name {
+        rustls::ServerName::DnsName(name) => name.as_ref().to_ascii_lowercase(),
+        rustls::ServerName::IpAddress(ip) => ip.to_string(),
+        _ => return Ok(verified),
+      };
+      let pins = self
+        .pins
+        .iter()
+        .filter(|(pattern, _)| host_matches(pattern, &host))
+        .flat_map(|(_, hashes)| hashes)
+        .collect::<Vec<_>>();
+      if pins.is_empty() {
+        return Ok(verified);
+      }
+
+      let pinned = std::iter::once(end_entity)
+        .chain(intermediates)
+        .filter_map(|certificate| subject_public_key_info(&certificate.0))
+        .any(|spki| {
+          let hash: [u8; 32] = Sha256::digest(spki).into();
+          pins.contains(&&hash)
+        });
+      if pinned {
+        Ok(verified)
+      } else {
+        Err(rustls::Error::InvalidCertificate(
+          rustls::CertificateError::Other(std::sync::Arc::new(super::CertificatePinMismatch {
+            host,
+          })),
+        ))
+      }
+    }
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{bypass_matches, parse_proxy_list};
+  use url::Url;
+  use windows::{
+    core::{HSTRING, PCWSTR, PWSTR},
+    Win32::{
+      Foundation::BOOL,
+      Networking::WinHttp::{
+        WinHttpCloseHandle, WinHttpGetIEProxyConfigForCurrentUser, WinHttpGetProxyForUrl,
+        WinHttpOpen, WINHTTP_ACCESS_TYPE_NAMED_PROXY, WINHTTP_ACCESS_TYPE_NO_PROXY,
+        WINHTTP_AUTOPROXY_AUTO_DETECT, WINHTTP_AUTOPROXY_CONFIG_URL, WINHTTP_AUTOPROXY_OPTIONS,
+        WINHTTP_AUTO_DETECT_TYPE_DHCP, WINHTTP_AUTO_DETECT_TYPE_DNS_A,
+        WINHTTP_CURRENT_USER_IE_PROXY_CONFIG, WINHTTP_PROXY_INFO,
+      },
+      System::Memory::GlobalFree,
+    },
+  };
+
+  /// Takes the ownership of a string allocated by WinHTTP.
+  unsafe fn take(string: PWSTR) -> Option<String> {
+    if string.is_null() {
+      return None;
+    }
+    let value = string.to_string().ok();
+    let _ = GlobalFree(string.0 as isize);
+    value.filter(|value| !value.is_empty())
+  }
+
+  pub fn system_proxy(url: &Url) -> crate::api::Result<Option<Url>> {
+    let mut config = WINHTTP_CURRENT_USER_IE_PROXY_CONFIG::default();
+    if !unsafe { WinHttpGetIEProxyConfigForCurrentUser(&mut config) }.as_bool() {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    let auto_config_url = unsafe { take(config.lpszAutoConfigUrl) };
+    let proxy = unsafe { take(config.lpszProxy) };
+    let bypass = unsafe { take(config.lpszProxyBypass) };
+
+    if config.fAutoDetect.as_bool() || auto_config_url.is_some() {
+      // the manual proxy is used when the auto-proxy fails, as in the browsers
+      if let Some(proxy) = auto_proxy(url, config.fAutoDetect, auto_config_url.as_deref()) {
+        return Ok(proxy);
+      }
+    }
+
+    let host = url.host_str().unwrap_or_default();
+    if bypass.map_or(false, |bypass| bypass_matches(&bypass, host)) {
+      return Ok(None);
+    }
+    Ok(proxy.and_then(|proxy| parse_proxy_list(&proxy, url.scheme())))
+  }
+
+  /// Evaluates the PAC file, returning `None` when it cannot be detected or evaluated.
+  fn auto_proxy(url: &Url, detect: BOOL, auto_config_url: Option<&str>) -> Option<Option<Url>> {
+    unsafe {
+      let session = WinHttpOpen(
+        PCWSTR::null(),
+        WINHTTP_ACCESS_TYPE_NO_PROXY,
+        PCWSTR::null(),
+        PCWSTR::null(),
+        0,
+      );
+      if session.is_null() {
+        return None;
+      }
+
+      let auto_config_url = auto_config_url.map(HSTRING::from);
+      let mut options = WINHTTP_AUTOPROXY_OPTIONS::default();
+      if detect.as_bool() {
+        options.dwFlags |= WINHTTP_AUTOPROXY_AUTO_DETECT;
+        options.dwAutoDetectFlags = WINHTTP_AUTO_DETECT_TYPE_DHCP | WINHTTP_AUTO_DETECT_TYPE_DNS_A;
+      }
+      if let Some(auto_config_url) = &auto_config_url {
+        options.dwFlags |= WINHTTP_AUTOPROXY_CONFIG_URL;
+        options.lpszAutoConfigUrl = PCWSTR(auto_config_url.as_ptr());
+      }
+      options.fAutoLogonIfChallenged = true.into();
+
+      let mut info = WINHTTP_PROXY_INFO::default();
+      let found = WinHttpGetProxyForUrl(
+        session,
+        &HSTRING::from(url.as_str()),
+        &mut options,
+        &mut info,
+      )
+      .as_bool();
+      WinHttpCloseHandle(session);
+
+      let proxy = take(info.lpszProxy);
+      take(info.lpszProxyBypass);
+      if !found {
+        return None;
+      }
+      if info.dwAccessType == WINHTTP_ACCESS_TYPE_NAMED_PROXY {
+        Some(proxy.and_then(|proxy| parse_proxy_list(&proxy, url.scheme())))
+      } else {
+        Some(None)
+      }
+    }
+  }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+  use cocoa::base::{id, nil, BOOL, YES};
+  use objc::{class, msg_send, sel, sel_impl};
+  use url::Url;
+
+  use std::ffi::{CStr, CString};
+
+  #[link(name = "CFNetwork", kind = "framework")]
+  extern "C" {
+    static kCFProxyTypeKey: id;
+    static kCFProxyHostNameKey: id;
+    static kCFProxyPortNumberKey: id;
+    static kCFProxyAutoConfigurationURLKey: id;
+    static kCFProxyAutoConfigurationJavaScriptKey: id;
+    static kCFProxyTypeNone: id;
+    static kCFProxyTypeHTTP: id;
+    static kCFProxyTypeHTTPS: id;
+    static kCFProxyTypeSOCKS: id;
+    static kCFProxyTypeAutoConfigurationURL: id;
+    static kCFProxyTypeAutoConfigurationJavaScript: id;
+
+    fn CFNetworkCopySystemProxySettings() -> id;
+    fn CFNetworkCopyProxiesForURL(url: id, proxy_settings: id) -> id;
+    fn CFNetworkCopyProxiesForAutoConfigurationScript(script: id, url: id, error: *mut id) -> id;
+  }
+
+  /// `NSUTF8StringEncoding`
+  const UTF8_ENCODING: usize = 4;
+
+  pub fn system_proxy(url: &Url) -> crate::api::Result<Option<Url>> {
+    unsafe {
+      let pool: id = msg_send![class!(NSAutoreleasePool), new];
+
+      let settings = CFNetworkCopySystemProxySettings();
+      let target = ns_url(url.as_str());
+      let proxy = if settings == nil || target == nil {
+        None
+      } else {
+        let proxies = CFNetworkCopyProxiesForURL(target, settings);
+        let proxy = first_proxy(proxies, target, true);
+        release(proxies);
+        proxy
+      };
+      release(settings);
+
+      let _: () = msg_send![pool, drain];
+      Ok(proxy)
+    }
+  }
+
+  /// The first proxy of the list, evaluating the PAC scripts it references unless it is itself the result of a script.
+  unsafe fn first_proxy(proxies: id, target: id, evaluate_scripts: bool) -> Option<Url> {
+    if proxies == nil {
+      return None;
+    }
+    let count: usize = msg_send![proxies, count];
+    for i in 0..count {
+      let proxy: id = msg_send![proxies, objectAtIndex: i];
+      let kind: id = msg_send![proxy, objectForKey: kCFProxyTypeKey];
+
+      if equals(kind, kCFProxyTypeNone) {
+        return None;
+      }
+
+      let scheme = if equals(kind, kCFProxyTypeHTTP) || equals(kind, kCFProxyTypeHTTPS) {
+        "http"
+      } else if equals(kind, kCFProxyTypeSOCKS) {
+        "socks5"
+      } else if evaluate_scripts && equals(kind, kCFProxyTypeAutoConfigurationURL) {
+        let script_url: id = msg_send![proxy, objectForKey: kCFProxyAutoConfigurationURLKey];
+        let script: id = msg_send![
+          class!(NSString),
+          stringWithContentsOfURL: script_url
+          encoding: UTF8_ENCODING
+          error: nil
+        ];
+        return evaluate(script, target);
+      } else if evaluate_scripts && equals(kind, kCFProxyTypeAutoConfigurationJavaScript) {
+        let script: id = msg_send![proxy, objectForKey: kCFProxyAutoConfigurationJavaScriptKey];
+        return evaluate(script, target);
+      } else {
+        continue;
+      };
+
+      let host = string(msg_send![proxy, objectForKey: kCFProxyHostNameKey])?;
+      let port: id = msg_send![proxy, objectForKey: kCFProxyPortNumberKey];
+      let port: i32 = if port == nil {
+        0
+      } else {
+        msg_send![port, intValue]
+      };
+      let proxy = if port > 0 {
+        format!("{scheme}://{host}:{port}")
+      } else {
+        format!("{scheme}://{host}")
+      };
+      return Url::parse(&proxy).ok();
+    }
+    None
+  }
+
+  unsafe fn evaluate(script: id, target: id) -> Option<Url> {
+    if script == nil {
+      return None;
+    }
+    let mut error = nil;
+    let proxies = CFNetworkCopyProxiesForAutoConfigurationScript(script, target, &mut error);
+    let proxy = first_proxy(proxies, target, false);
+    release(proxies);
+    release(error);
+    proxy
+  }
+
+  unsafe fn ns_url(url: &str) -> id {
+    let url = match CString::new(url) {
+      Ok(url) => url,
+      Err(_) => return nil,
+    };
+    let url: id = msg_send![class!(NSString), stringWithUTF8String: url.as_ptr()];
+    msg_send![class!(NSURL), URLWithString: url]
+  }
+
+  unsafe fn string(value: id) -> Option<String> {
+    if value == nil {
+      return None;
+    }
+    let utf8: *const std::os::raw::c_char = msg_send![value, UTF8String];
+    (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned())
+  }
+
+  unsafe fn equals(value: id, other: id) -> bool {
+    if value == nil {
+      return false;
+    }
+    let equal: BOOL = msg_send![value, isEqualToString: other];
+    equal == YES
+  }
+
+  unsafe fn release(value: id) {
+    if value != nil {
+      let _: () = msg_send![value, release];
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gtk::gio::{prelude::ProxyResolverExt, Cancellable, ProxyResolver};
+  use url::Url;
+
+  pub fn system_proxy(url: &Url) -> crate::api::Result<Option<Url>> {
+    let proxies = ProxyResolver::default()
+      .lookup(url.as_str(), Cancellable::NONE)
+      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+    Ok(
+      proxies
+        .iter()
+        .find_map(|proxy| match proxy.as_str() {
+          "direct://" => Some(None),
+          proxy => Url::parse(proxy).ok().map(Some),
+        })
+        .flatten(),
+    )
+  }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+  use url::Url;
+
+  pub fn system_proxy(_url: &Url) -> crate::api::Result<Option<Url>> {
+    Ok(None)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn url(url: &str) -> Url {
+    Url::parse(url).unwrap()
+  }
+
+  #[test]
+  fn reads_the_environment() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+      move |name: &str| {
+        vars
+          .iter()
+          .find(|(var, _)| *var == name)
+          .map(|(_, value)| value.to_string())
+      }
+    };
+
+    let vars = env(&[
+      ("https_proxy", "proxy.corp:8443"),
+      ("HTTP_PROXY", "http://proxy.corp:8080"),
+      ("NO_PROXY", "localhost, .internal.corp"),
+    ]);
+    assert_eq!(
+      env_proxy(&url("https://tauri.app"), vars),
+      Some(Some(url("http://proxy.corp:8443")))
+    );
+    assert_eq!(
+      env_proxy(&url("http://tauri.app"), vars),
+      Some(Some(url("http://proxy.corp:8080")))
+    );
+    assert_eq!(
+      env_proxy(&url("https://api.internal.corp"), vars),
+      Some(None)
+    );
+    assert_eq!(env_proxy(&url("http://localhost:1430"), vars), Some(None));
+    assert_eq!(env_proxy(&url("wss://tauri.app"), vars), None);
+
+    let vars = env(&[("ALL_PROXY", "socks5://127.0.0.1:1080")]);
+    assert_eq!(
+      env_proxy(&url("wss://tauri.app"), vars),
+      Some(Some(url("socks5://127.0.0.1:1080")))
+    );
+    assert_eq!(env_proxy(&url("https://tauri.app"), env(&[])), None);
+  }
+
+  #[test]
+  fn matches_no_proxy() {
+    assert!(no_proxy_matches("*", "tauri.app"));
+    assert!(no_proxy_matches("tauri.app", "docs.tauri.app"));
+    assert!(no_proxy_matches("*.tauri.app", "TAURI.app"));
+    assert!(no_proxy_matches("::1", "[::1]"));
+    assert!(!no_proxy_matches("tauri.app", "nottauri.app"));
+    assert!(!no_proxy_matches("", "tauri.app"));
+  }
+
+  #[test]
+  fn parses_proxy_lists() {
+    assert_eq!(
+      parse_proxy_list("proxy.corp:8080", "https"),
+      Some(url("http://proxy.corp:8080"))
+    );
+    let list = "http=proxy.corp:8080;https=secure.corp:8443;socks=socks.corp:1080";
+    assert_eq!(
+      parse_proxy_list(list, "https"),
+      Some(url("http://secure.corp:8443"))
+    );
+    assert_eq!(
+      parse_proxy_list("http=proxy.corp:8080;socks=socks.corp:1080", "wss"),
+      Some(url("socks4://socks.corp:1080"))
+    );
+    assert_eq!(parse_proxy_list("http=proxy.corp:8080", "https"), None);
+  }
+
+  #[test]
+  fn extracts_the_subject_public_key_info() {
+    use super::pinning::subject_public_key_info;
+
+    let spki = [0x30, 0x03, 0x03, 0x01, 0x00];
+    let mut tbs_certificate = vec![
+      0xa0, 0x03, 0x02, 0x01, 0x02, // version
+      0x02, 0x01, 0x01, // serial number
+      0x30, 0x00, // signature algorithm
+      0x30, 0x00, // issuer
+      0x30, 0x00, // validity
+      0x30, 0x00, // subject
+    ];
+    tbs_certificate.extend(spki);
+    tbs_certificate.extend([0xa3, 0x00]);
+    let mut certificate = vec![
+      0x30,
+      tbs_certificate.len() as u8 + 2,
+      0x30,
+      tbs_certificate.len() as u8,
+    ];
+    certificate.extend(&tbs_certificate);
+
+    assert_eq!(subject_public_key_info(&certificate), Some(&spki[..]));
+    assert_eq!(subject_public_key_info(&certificate[..10]), None);
+  }
+
+  #[test]
+  fn matches_pinned_hosts() {
+    use super::pinning::host_matches;
+
+    assert!(host_matches("api.example.com", "api.example.com"));
+    assert!(host_matches("*.example.com", "api.example.com"));
+    assert!(!host_matches("*.example.com", "example.com"));
+    assert!(!host_matches("*.example.com", "apiexample.com"));
+    assert!(!host_matches("api.example.com", "www.example.com"));
+  }
+
+  #[test]
+  fn serializes_pin_mismatches() {
+    let mismatch = CertificatePinMismatch {
+      host: "api.example.com".into(),
+    };
+    assert_eq!(
+      serde_json::to_value(&mismatch).unwrap(),
+      serde_json::json!({ "kind": "certificatePinMismatch", "host": "api.example.com" })
+    );
+
+    let error = std::io::Error::new(std::io::ErrorKind::Other, mismatch.clone());
+    assert_eq!(CertificatePinMismatch::find(&error), Some(&mismatch));
+  }
+
+  #[test]
+  fn matches_bypass_lists() {
+    let bypass = "*.corp.example.com;10.*;<local>";
+    assert!(bypass_matches(bypass, "git.corp.example.com"));
+    assert!(bypass_matches(bypass, "10.0.0.1"));
+    assert!(bypass_matches(bypass, "intranet"));
+    assert!(!bypass_matches(bypass, "tauri.app"));
+    assert!(!bypass_matches(bypass, "corp.example.com"));
+  }
+}
@@ -0,0 +1,559 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! OAuth 2.0 and OpenID Connect authorization in the system browser, as recommended by [RFC 8252](https://www.rfc-editor.org/rfc/rfc8252).
+//!
+//! [`authorize`] opens the authorization page in the default browser, where the user can use their saved credentials and passkeys,
+//! and receives the redirect of the authorization server with a loopback listener or a custom URL scheme.
+//! The code is protected with PKCE and a random `state`, then exchanged for tokens when a token endpoint is configured.
+//!
+//! ## Platform-specific
+//!
+//! - **Android / iOS**: Unsupported, the system browser cannot be opened. Use an `ASWebAuthenticationSession` or Custom Tabs plugin instead.
+
+use base64::Engine;
+use once_cell::sync::Lazy;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpListener,
+  sync::oneshot,
+};
+use url::Url;
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+/// The authorization flows waiting for the redirect to their custom URL scheme, keyed by `state`.
+static PENDING_REDIRECTS: Lazy<Mutex<HashMap<String, oneshot::Sender<Url>>>> =
+  Lazy::new(Default::default);
+
+/// The page shown by the browser once the loopback listener received the redirect.
+const LOOPBACK_RESPONSE: &str = "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Signed in</title></head><body><p>You can close this tab and return to the app.</p></body></html>";
+
+/// The path of the loopback redirect URI.
+const LOOPBACK_PATH: &str = "/callback";
+
+/// Errors of an authorization.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The authorization server returned an error, e.g. `access_denied` when the user cancelled.
+  #[error("authorization failed: {error}{}", .description.as_ref().map(|d| format!(" ({d})")).unwrap_or_default())]
+  Authorization {
+    /// The error code, see <https://www.rfc-editor.org/rfc/rfc6749#section-4.1.2.1>.
+    error: String,
+    /// The human readable description of the error.
+    description: Option<String>,
+  },
+  /// The redirect does not have the `state` of the request, so it was not initiated by the app.
+  #[error("the authorization redirect has an unexpected state")]
+  StateMismatch,
+  /// The redirect does not have a code.
+  #[error("the authorization redirect has no code")]
+  MissingCode,
+  /// The user did not complete the authorization in time.
+  #[error("the authorization timed out")]
+  Timeout,
+  /// The system browser cannot be opened.
+  #[error("failed to open the browser: {0}")]
+  Browser(String),
+  /// The token request failed.
+  #[error("token request failed: {0}")]
+  Token(#[from] reqwest::Error),
+}
+
+/// Where the authorization server redirects the browser once the user authorized the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Redirect {
+  /// A listener on the loopback interface, with the `http://127.0.0.1:{port}/callback` redirect URI.
+  ///
+  /// A random port is used when it is not set, which the authorization server must allow.
+  Loopback {
+    /// The port of the listener.
+    port: Option<u16>,
+  },
+  /// A custom URL scheme of the app, e.g. `com.example.app:/oauth`.
+  ///
+  /// The app must register the scheme and pass the URLs it is opened with to [`handle_redirect`].
+  Url(Url),
+}
+
+impl Default for Redirect {
+  fn default() -> Self {
+    Self::Loopback { port: None }
+  }
+}
+
+/// The configuration of an authorization.
+#[derive(Debug, Clone)]
+pub struct AuthorizationConfig {
+  authorization_endpoint: Url,
+  token_endpoint: Option<Url>,
+  client_id: String,
+  scopes: Vec<String>,
+  redirect: Redirect,
+  parameters: Vec<(String, String)>,
+  timeout: Duration,
+  client: Option<reqwest::Client>,
+}
+
+impl AuthorizationConfig {
+  /// Authorizes the client on the authorization endpoint, e.g. `https://accounts.example.com/authorize`.
+  pub fn new(authorization_endpoint: Url, client_id: impl Into<String>) -> Self {
+    Self {
+      authorization_endpoint,
+      token_endpoint: None,
+      client_id: client_id.into(),
+      scopes: Vec::new(),
+      redirect: Redirect::default(),
+      parameters: Vec::new(),
+      timeout: Duration::from_secs(300),
+      client: None,
+    }
+  }
+
+  /// Exchanges the code for tokens on the token endpoint, e.g. `https://accounts.example.com/token`.
+  ///
+  /// The code is returned to the app when it is not set, e.g. to exchange it on the app backend.
+  #[must_use]
+  pub fn token_endpoint(mut self, token_endpoint: Url) -> Self {
+    self.token_endpoint.replace(token_endpoint);
+    self
+  }
+
+  /// Requests a scope, e.g. `openid`.
+  #[must_use]
+  pub fn scope(mut self, scope: impl Into<String>) -> Self {
+    self.scopes.push(scope.into());
+    self
+  }
+
+  /// Sets how the redirect of the authorization server is received. Defaults to [`Redirect::Loopback`].
+  #[must_use]
+  pub fn redirect(mut self, redirect: Redirect) -> Self {
+    self.redirect = redirect;
+    self
+  }
+
+  /// Adds a parameter to the authorization request, e.g. `prompt=consent`.
+  #[must_use]
+  pub fn parameter(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+    self.parameters.push((name.into(), value.into()));
+    self
+  }
+
+  /// How long the user has to authorize the app. Defaults to 5 minutes.
+  #[must_use]
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// The HTTP client of the token request, e.g. created with `tauri::api::network::client_builder` with the `network` feature.
+  #[must_use]
+  pub fn client(mut self, client: reqwest::Client) -> Self {
+    self.client.replace(client);
+    self
+  }
+}
+
+/// The result of an authorization.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Authorization {
+  /// The authorization code.
+  pub code: String,
+  /// The PKCE verifier of the code, needed to exchange the code elsewhere.
+  pub code_verifier: String,
+  /// The redirect URI of the authorization request, needed to exchange the code elsewhere.
+  pub redirect_uri: String,
+  /// The tokens the code was exchanged for, when a token endpoint is configured.
+  pub tokens: Option<TokenResponse>,
+}
+
+/// The tokens returned by the token endpoint, see <https://www.rfc-editor.org/rfc/rfc6749#section-5.1>.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenResponse {
+  /// The access token.
+  #[serde(alias = "access_token")]
+  pub access_token: String,
+  /// The type of the access token, usually `Bearer`.
+  #[serde(alias = "token_type")]
+  pub token_type: String,
+  /// The lifetime of the access token in seconds.
+  #[serde(alias = "expires_in")]
+  pub expires_in: Option<u64>,
+  /// The refresh token.
+  #[serde(alias = "refresh_token")]
+  pub refresh_token: Option<String>,
+  /// The OpenID Connect ID token.
+  #[serde(alias = "id_token")]
+  pub id_token: Option<String>,
+  /// The granted scopes, when they differ from the requested ones.
+  pub scope: Option<String>,
+}
+
+/// Authorizes the app in the system browser, returning the authorization code and the tokens it was exchanged for.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::api::oauth::{authorize, AuthorizationConfig};
+///
+/// #[tauri::command]
+/// async fn sign_in() -> Result<String, String> {
+///   let config = AuthorizationConfig::new(
+///     "https://accounts.example.com/authorize".parse().unwrap(),
+///     "my-client-id",
+///   )
+///   .token_endpoint("https://accounts.example.com/token".parse().unwrap())
+///   .scope("openid")
+///   .scope("email");
+///   let authorization = authorize(config).await.map_err(|e| e.to_string())?;
+///   Ok(authorization.tokens.unwrap().access_token)
+/// }
+/// ```
+pub async fn authorize(config: AuthorizationConfig) -> crate::api::Result<Authorization> {
+  let code_verifier = random_string(64);
+  let state = random_string(32);
+
+  let (redirect_uri, redirect) = match &config.redirect {
+    Redirect::Loopback { port } => {
+      let listener = TcpListener::bind(("127.0.0.1", port.unwrap_or(0))).await?;
+      let port = listener.local_addr()?.port();
+      (
+        format!("http://127.0.0.1:{port}{LOOPBACK_PATH}"),
+        RedirectReceiver::Loopback(listener),
+      )
+    }
+    Redirect::Url(url) => {
+      let (tx, rx) = oneshot::channel();
+      PENDING_REDIRECTS.lock().unwrap().insert(state.clone(), tx);
+      (url.to_string(), RedirectReceiver::Url(rx))
+    }
+  };
+
+  let result = authorize_with(&config, code_verifier, &state, redirect_uri, redirect).await;
+  PENDING_REDIRECTS.lock().unwrap().remove(&state);
+  result
+}
+
+/// Passes a URL the app was opened with to the authorization waiting for it, for [`Redirect::Url`].
+///
+/// Returns `false` when no authorization has the `state` of the URL.
+pub fn handle_redirect(url: &Url) -> bool {
+  let state = match url.query_pairs().find(|(name, _)| name == "state") {
+    Some((_, state)) => state.into_owned(),
+    None => return false,
+  };
+  PENDING_REDIRECTS
+    .lock()
+    .unwrap()
+    .remove(&state)
+    .map_or(false, |tx| tx.send(url.clone()).is_ok())
+}
+
+enum RedirectReceiver {
+  Loopback(TcpListener),
+  Url(oneshot::Receiver<Url>),
+}
+
+async fn authorize_with(
+  config: &AuthorizationConfig,
+  code_verifier: String,
+  state: &str,
+  redirect_uri: String,
+  redirect: RedirectReceiver,
+) -> crate::api::Result<Authorization> {
+  let mut url = config.authorization_endpoint.clone();
+  {
+    let mut query = url.query_pairs_mut();
+    query
+      .append_pair("response_type", "code")
+      .append_pair("client_id", &config.client_id)
+      .append_pair("redirect_uri", &redirect_uri)
+      .append_pair("state", state)
+      .append_pair("code_challenge", &code_challenge(&code_verifier))
+      .append_pair("code_challenge_method", "S256");
+    if !config.scopes.is_empty() {
+      query.append_pair("scope", &config.scopes.join(" "));
+    }
+    for (name, value) in &config.parameters {
+      query.append_pair(name, value);
+    }
+  }
+  open_browser(&url).map_err(Error::Browser)?;
+
+  let receive = async {
+    match redirect {
+      RedirectReceiver::Loopback(listener) => receive_loopback(listener).await,
+      RedirectReceiver::Url(rx) => rx.await.map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::Interrupted, "redirect dropped").into()
+      }),
+    }
+  };
+  let redirect = tokio::time::timeout(config.timeout, receive)
+    .await
+    .map_err(|_| Error::Timeout)??;
+  let code = code_from_redirect(&redirect, state)?;
+
+  let tokens = match &config.token_endpoint {
+    Some(token_endpoint) => {
+      let client = config.client.clone().unwrap_or_default();
+      let response = client
+        .post(token_endpoint.clone())
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&[
+          ("grant_type", "authorization_code"),
+          ("code", &code),
+          ("redirect_uri", &redirect_uri),
+          ("client_id", &config.client_id),
+          ("code_verifier", &code_verifier),
+        ])
+        .send()
+        .await
+        .map_err(Error::Token)?;
+      if !response.status().is_success() {
+        return Err(token_error(response).await.into());
+      }
+      Some(response.json().await.map_err(Error::Token)?)
+    }
+    None => None,
+  };
+
+  Ok(Authorization {
+    code,
+    code_verifier,
+    redirect_uri,
+    tokens,
+  })
+}
+
+/// The error of a failed token request, see <https://www.rfc-editor.org/rfc/rfc6749#section-5.2>.
+async fn token_error(response: reqwest::Response) -> Error {
+  #[derive(Deserialize)]
+  struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+  }
+
+  let status = response.status();
+  match response.json::<ErrorResponse>().await {
+    Ok(response) => Error::Authorization {
+      error: response.error,
+      description: response.error_description,
+    },
+    Err(_) => Error::Authorization {
+      error: status.to_string(),
+      description: None,
+    },
+  }
+}
+
+/// Accepts the connections of the browser until it requests the redirect URI.
+async fn receive_loopback(listener: TcpListener) -> crate::api::Result<Url> {
+  loop {
+    let (mut stream, _) = listener.accept().await?;
+
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 16 * 1024 {
+      let read = stream.read(&mut buf).await?;
+      if read == 0 {
+        break;
+      }
+      request.extend_from_slice(&buf[..read]);
+    }
+
+    let target = parse_request_target(&request);
+    let redirect = target
+      .as_deref()
+      .filter(|target| target.split('?').next() == Some(LOOPBACK_PATH))
+      .and_then(|target| Url::parse(&format!("http://127.0.0.1{target}")).ok());
+
+    let response = match &redirect {
+      Some(_) => format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{LOOPBACK_RESPONSE}",
+        LOOPBACK_RESPONSE.len()
+      ),
+      None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".into(),
+    };
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+
+    if let Some(redirect) = redirect {
+      return Ok(redirect);
+    }
+  }
+}
+
+/// The target of an HTTP `GET` request, e.g. `/callback?code=abc&state=xyz`.
+fn parse_request_target(request: &[u8]) -> Option<String> {
+  let line = request.split(|b| *b == b'\n').next()?;
+  let line = std::str::from_utf8(line).ok()?.trim_end();
+  let mut parts = line.split(' ');
+  match (parts.next(), parts.next()) {
+    (Some("GET"), Some(target)) if target.starts_with('/') => Some(target.into()),
+    _ => None,
+  }
+}
+
+/// Checks the state of the redirect and returns its code.
+fn code_from_redirect(redirect: &Url, state: &str) -> Result<String, Error> {
+  let mut parameters = redirect
+    .query_pairs()
+    .into_owned()
+    .collect::<HashMap<String, String>>();
+  if parameters.get("state").map(String::as_str) != Some(state) {
+    return Err(Error::StateMismatch);
+  }
+  if let Some(error) = parameters.remove("error") {
+    return Err(Error::Authorization {
+      error,
+      description: parameters.remove("error_description"),
+    });
+  }
+  parameters.remove("code").ok_or(Error::MissingCode)
+}
+
+fn random_string(len: usize) -> String {
+  rand::thread_rng()
+    .sample_iter(&Alphanumeric)
+    .take(len)
+    .map(char::from)
+    .collect()
+}
+
+/// The S256 PKCE challenge of the verifier, see <https://www.rfc-editor.org/rfc/rfc7636#section-4.2>.
+fn code_challenge(code_verifier: &str) -> String {
+  base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+fn open_browser(url: &Url) -> Result<(), String> {
+  #[cfg(windows)]
+  return unsafe {
+    use windows::{
+      core::{HSTRING, PCWSTR},
+      Win32::{Foundation::HWND, UI::Shell::ShellExecuteW, UI::WindowsAndMessaging::SW_SHOWNORMAL},
+    };
+
+    let instance = ShellExecuteW(
+      HWND::default(),
+      &HSTRING::from("open"),
+      &HSTRING::from(url.as_str()),
+      PCWSTR::null(),
+      PCWSTR::null(),
+      SW_SHOWNORMAL,
+    );
+    // values greater than 32 indicate success
+    if instance.0 > 32 {
+      Ok(())
+    } else {
+      Err(std::io::Error::last_os_error().to_string())
+    }
+  };
+
+  #[cfg(target_os = "macos")]
+  return unsafe {
+    use cocoa::base::{id, nil, BOOL, YES};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let url = std::ffi::CString::new(url.as_str()).map_err(|e| e.to_string())?;
+    let url: id = msg_send![class!(NSString), stringWithUTF8String: url.as_ptr()];
+    let url: id = msg_send![class!(NSURL), URLWithString: url];
+    if url == nil {
+      return Err("invalid URL".into());
+    }
+    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+    let opened: BOOL = msg_send![workspace, openURL: url];
+    if opened == YES {
+      Ok(())
+    } else {
+      Err("NSWorkspace could not open the URL".into())
+    }
+  };
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  return gtk::gio::AppInfo::launch_default_for_uri(url.as_str(), gtk::gio::AppLaunchContext::NONE)
+    .map_err(|e| e.to_string());
+
+  #[cfg(mobile)]
+  {
+    let _ = url;
+    Err("unsupported on mobile".into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn computes_the_code_challenge() {
+    // https://www.rfc-editor.org/rfc/rfc7636#appendix-B
+    assert_eq!(
+      code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+      "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+    );
+  }
+
+  #[test]
+  fn checks_the_redirect() {
+    let url = |query: &str| Url::parse(&format!("http://127.0.0.1:8000/callback?{query}")).unwrap();
+    assert_eq!(
+      code_from_redirect(&url("code=abc&state=xyz"), "xyz").unwrap(),
+      "abc"
+    );
+    assert!(matches!(
+      code_from_redirect(&url("code=abc&state=other"), "xyz"),
+      Err(Error::StateMismatch)
+    ));
+    assert!(matches!(
+      code_from_redirect(&url("error=access_denied&state=xyz"), "xyz"),
+      Err(Error::Authorization { error, description: None }) if error == "access_denied"
+    ));
+    assert!(matches!(
+      code_from_redirect(&url("state=xyz"), "xyz"),
+      Err(Error::MissingCode)
+    ));
+  }
+
+  #[test]
+  fn parses_the_request_target() {
+    assert_eq!(
+      parse_request_target(b"GET /callback?code=abc HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n")
+        .as_deref(),
+      Some("/callback?code=abc")
+    );
+    assert_eq!(parse_request_target(b"POST /callback HTTP/1.1\r\n"), None);
+    assert_eq!(parse_request_target(b""), None);
+  }
+
+  #[test]
+  fn hands_redirects_to_the_pending_flow() {
+    let (tx, mut rx) = oneshot::channel();
+    PENDING_REDIRECTS
+      .lock()
+      .unwrap()
+      .insert("pending-state".into(), tx);
+
+    let url = Url::parse("com.example.app:/oauth?code=abc&state=pending-state").unwrap();
+    assert!(!handle_redirect(
+      &Url::parse("com.example.app:/oauth?code=abc&state=unknown").unwrap()
+    ));
+    assert!(handle_redirect(&url));
+    assert_eq!(rx.try_recv().unwrap(), url);
+  }
+}
@@ -0,0 +1,278 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The motion sensors of the device.
+//!
+//! [`watch`] receives the readings of a sensor at the requested interval until the returned [`SensorWatcher`] is dropped.
+//! The readings use the coordinate system of the device in its natural orientation:
+//! `x` points to the right, `y` to the top and `z` out of the screen.
+//!
+//! ## Platform-specific
+//!
+//! - **Android**: The interval is a hint, the system may deliver the readings more often.
+//! - **Linux / macOS / Windows**: No-op, the sensors are never available.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+#[cfg(mobile)]
+use once_cell::sync::Lazy;
+
+use std::time::Duration;
+#[cfg(mobile)]
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicU32, Ordering},
+    Mutex,
+  },
+};
+
+#[cfg(mobile)]
+type Handler = Box<dyn Fn(SensorReading) + Send + Sync>;
+
+/// The handlers of the running watchers, keyed by the identifier shared with the native plugin.
+#[cfg(mobile)]
+static HANDLERS: Lazy<Mutex<HashMap<u32, std::sync::Arc<Handler>>>> = Lazy::new(Default::default);
+#[cfg(mobile)]
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+/// A motion sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Sensor {
+  /// The acceleration of the device including gravity, in m/s².
+  Accelerometer,
+  /// The rotation rate of the device around each axis, in rad/s.
+  Gyroscope,
+}
+
+/// A reading of a [`Sensor`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SensorReading {
+  /// The value on the x axis.
+  pub x: f64,
+  /// The value on the y axis.
+  pub y: f64,
+  /// The value on the z axis.
+  pub z: f64,
+  /// When the value was measured, in milliseconds since the Unix epoch.
+  pub timestamp: u64,
+}
+
+/// Receives the readings of a sensor, stopping it when dropped.
+pub struct SensorWatcher {
+  #[cfg(mobile)]
+  id: u32,
+  stop: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl SensorWatcher {
+  /// Stops receiving the readings.
+  pub fn stop(mut self) {
+    self.stop_sensor();
+  }
+
+  fn stop_sensor(&mut self) {
+    #[cfg(mobile)]
+    HANDLERS.lock().unwrap().remove(&self.id);
+    if let Some(stop) = self.stop.take() {
+      stop();
+    }
+  }
+}
+
+impl Drop for SensorWatcher {
+  fn drop(&mut self) {
+    self.stop_sensor();
+  }
+}
+
+impl std::fmt::Debug for SensorWatcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SensorWatcher").finish_non_exhaustive()
+  }
+}
+
+/// The native sensors plugin.
+#[cfg(mobile)]
+struct SensorsPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+#[cfg(mobile)]
+#[derive(Deserialize)]
+struct AvailableResponse {
+  available: bool,
+}
+
+/// Whether the device has the sensor.
+pub fn is_available<R: Runtime, M: Manager<R>>(manager: &M, sensor: Sensor) -> bool {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<SensorsPlugin<R>>() {
+    return plugin
+      .0
+      .run_mobile_plugin::<AvailableResponse>(
+        "isAvailable",
+        serde_json::json!({ "sensor": sensor }),
+      )
+      .map(|response| response.available)
+      .unwrap_or(false);
+  }
+
+  let _ = (manager, sensor);
+  false
+}
+
+/// Calls `handler` with the readings of the sensor, measured every `interval`.
+///
+/// The handler is called on a background thread. It is never called when the sensor is not available.
+pub fn watch<R: Runtime, M: Manager<R>, F: Fn(SensorReading) + Send + Sync + 'static>(
+  manager: &M,
+  sensor: Sensor,
+  interval: Duration,
+  handler: F,
+) -> crate::Result<SensorWatcher> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<SensorsPlugin<R>>() {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    HANDLERS
+      .lock()
+      .unwrap()
+      .insert(id, std::sync::Arc::new(Box::new(handler)));
+    let mut watcher = SensorWatcher { id, stop: None };
+    plugin.0.run_mobile_plugin::<()>(
+      "start",
+      serde_json::json!({
+        "id": id,
+        "sensor": sensor,
+        "intervalMicros": interval.as_micros() as u64,
+      }),
+    )?;
+    let handle = plugin.0.clone();
+    watcher.stop = Some(Box::new(move || {
+      let _ = handle.run_mobile_plugin::<()>("stop", serde_json::json!({ "id": id }));
+    }));
+    return Ok(watcher);
+  }
+
+  let _ = (manager, sensor, interval, handler);
+  Ok(SensorWatcher {
+    #[cfg(mobile)]
+    id: 0,
+    stop: None,
+  })
+}
+
+/// Calls the handler of a watcher with a reading of the native plugin.
+#[cfg(mobile)]
+fn dispatch(id: u32, reading: SensorReading) {
+  // the handler is called without the lock, so it can stop the watchers
+  let handler = HANDLERS.lock().unwrap().get(&id).cloned();
+  if let Some(handler) = handler {
+    handler(reading);
+  }
+}
+
+/// Glue between Rust and the Kotlin code that forwards the sensor readings.
+#[cfg(target_os = "android")]
+pub fn handle_android_sensor_event(id: i32, x: f64, y: f64, z: f64, timestamp: i64) {
+  dispatch(
+    id as u32,
+    SensorReading {
+      x,
+      y,
+      z,
+      timestamp: timestamp as u64,
+    },
+  );
+}
+
+mod commands {
+  use super::{Sensor, SensorReading, SensorWatcher};
+  use crate::{api::ipc::Channel, command, AppHandle, Runtime, State};
+
+  use std::{
+    collections::HashMap,
+    sync::{
+      atomic::{AtomicU32, Ordering},
+      Mutex,
+    },
+    time::Duration,
+  };
+
+  /// The sensors watched by the frontend.
+  #[derive(Default)]
+  pub(super) struct Watchers {
+    next_id: AtomicU32,
+    watchers: Mutex<HashMap<u32, SensorWatcher>>,
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn is_available<R: Runtime>(app: AppHandle<R>, sensor: Sensor) -> bool {
+    super::is_available(&app, sensor)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn watch<R: Runtime>(
+    app: AppHandle<R>,
+    watchers: State<'_, Watchers>,
+    sensor: Sensor,
+    interval: u64,
+    on_reading: Channel<R>,
+  ) -> crate::Result<u32> {
+    let watcher = super::watch(
+      &app,
+      sensor,
+      Duration::from_millis(interval),
+      move |reading: SensorReading| {
+        let _ = on_reading.send(&reading);
+      },
+    )?;
+    let id = watchers.next_id.fetch_add(1, Ordering::Relaxed);
+    watchers.watchers.lock().unwrap().insert(id, watcher);
+    Ok(id)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn unwatch(watchers: State<'_, Watchers>, id: u32) -> crate::Result<()> {
+    // the watcher is dropped after releasing the lock, since stopping it waits for the native plugin
+    let watcher = watchers.watchers.lock().unwrap().remove(&id);
+    drop(watcher);
+    Ok(())
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("sensors")
+    .invoke_handler(crate::generate_handler![
+      commands::is_available,
+      commands::watch,
+      commands::unwatch
+    ])
+    .setup(|app, _api| {
+      app.manage(commands::Watchers::default());
+
+      #[cfg(target_os = "android")]
+      app.manage(SensorsPlugin(
+        _api.register_android_plugin("app.tauri.sensors", "SensorsPlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      {
+        extern "C" fn handler(id: u32, x: f64, y: f64, z: f64, timestamp: u64) {
+          dispatch(id, SensorReading { x, y, z, timestamp });
+        }
+        app.manage(SensorsPlugin(
+          _api.register_ios_plugin(crate::ios::init_sensors_plugin)?,
+        ));
+        unsafe { crate::ios::set_sensor_event_handler(crate::ios::SensorEventHandler(handler)) };
+      }
+      Ok(())
+    })
+    .build()
+}
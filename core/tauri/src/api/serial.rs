@@ -0,0 +1,814 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Serial ports, e.g. the USB to UART adapters and the microcontroller boards.
+//!
+//! The frontend lists and opens the ports matching the [`tauri > security > serial > scope`](crate::utils::config::SerialConfig#structfield.scope)
+//! configuration with `@tauri-apps/api/serial`, and receives the data and the disconnection of an open port with a channel.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux**: The user needs read and write access to the ports, usually given by the `dialout` group.
+//! - **macOS**: The `/dev/cu.*` callout devices are listed.
+//! - **Android / iOS**: Unsupported, the functions fail with [`std::io::ErrorKind::Unsupported`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+  fs::File,
+  io::{self, Read, Write},
+  time::Duration,
+};
+
+/// A serial port of the computer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SerialPortInfo {
+  /// The path the port is opened with, e.g. `/dev/ttyUSB0` or `COM3`.
+  pub path: String,
+  /// The USB vendor identifier of a USB port.
+  pub vendor_id: Option<u16>,
+  /// The USB product identifier of a USB port.
+  pub product_id: Option<u16>,
+  /// The product name of a USB port.
+  pub product_name: Option<String>,
+  /// The serial number of a USB port.
+  pub serial_number: Option<String>,
+}
+
+/// The parity bit of the characters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Parity {
+  /// No parity bit.
+  #[default]
+  None,
+  /// The parity bit makes the number of set bits odd.
+  Odd,
+  /// The parity bit makes the number of set bits even.
+  Even,
+}
+
+/// The number of stop bits of the characters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StopBits {
+  /// One stop bit.
+  #[default]
+  One,
+  /// Two stop bits.
+  Two,
+}
+
+/// The flow control of the port.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FlowControl {
+  /// No flow control.
+  #[default]
+  None,
+  /// The RTS and CTS lines pause the transmission.
+  Hardware,
+}
+
+/// The settings a serial port is opened with, `9600` bauds and 8N1 by default.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SerialOptions {
+  baud_rate: u32,
+  data_bits: u8,
+  parity: Parity,
+  stop_bits: StopBits,
+  flow_control: FlowControl,
+  #[serde(with = "timeout_millis")]
+  timeout: Duration,
+}
+
+impl Default for SerialOptions {
+  fn default() -> Self {
+    Self {
+      baud_rate: 9600,
+      data_bits: 8,
+      parity: Parity::None,
+      stop_bits: StopBits::One,
+      flow_control: FlowControl::None,
+      timeout: Duration::from_millis(100),
+    }
+  }
+}
+
+mod timeout_millis {
+  use serde::{Deserialize, Deserializer};
+  use std::time::Duration;
+
+  pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_millis)
+  }
+}
+
+impl SerialOptions {
+  /// The options with the given baud rate.
+  pub fn new(baud_rate: u32) -> Self {
+    Self {
+      baud_rate,
+      ..Default::default()
+    }
+  }
+
+  /// The number of data bits of the characters, from 5 to 8.
+  #[must_use]
+  pub fn data_bits(mut self, data_bits: u8) -> Self {
+    self.data_bits = data_bits;
+    self
+  }
+
+  /// The parity bit of the characters.
+  #[must_use]
+  pub fn parity(mut self, parity: Parity) -> Self {
+    self.parity = parity;
+    self
+  }
+
+  /// The number of stop bits of the characters.
+  #[must_use]
+  pub fn stop_bits(mut self, stop_bits: StopBits) -> Self {
+    self.stop_bits = stop_bits;
+    self
+  }
+
+  /// The flow control of the port.
+  #[must_use]
+  pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+    self.flow_control = flow_control;
+    self
+  }
+
+  /// How long a read waits for data before failing with [`std::io::ErrorKind::TimedOut`]. Defaults to 100 milliseconds.
+  #[must_use]
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+}
+
+/// An open serial port, read and written with [`std::io::Read`] and [`std::io::Write`].
+///
+/// A read fails with [`std::io::ErrorKind::TimedOut`] when no data is received in the timeout of the [`SerialOptions`],
+/// and with another error once the port is disconnected.
+#[derive(Debug)]
+pub struct SerialPort {
+  file: File,
+  timeout: Duration,
+}
+
+impl SerialPort {
+  /// Opens the port with exclusive access.
+  #[cfg_attr(mobile, allow(unused_variables))]
+  pub fn open(path: &str, options: &SerialOptions) -> crate::api::Result<Self> {
+    if !(5..=8).contains(&options.data_bits) {
+      return Err(
+        io::Error::new(
+          io::ErrorKind::InvalidInput,
+          format!("invalid number of data bits {}", options.data_bits),
+        )
+        .into(),
+      );
+    }
+
+    #[cfg(all(unix, desktop))]
+    return unix::open(path, options)
+      .map(|file| Self {
+        file,
+        timeout: options.timeout,
+      })
+      .map_err(Into::into);
+
+    #[cfg(windows)]
+    return win32::open(path, options)
+      .map(|file| Self {
+        file,
+        timeout: options.timeout,
+      })
+      .map_err(Into::into);
+
+    #[cfg(mobile)]
+    return Err(unsupported());
+  }
+
+  /// Creates a handle to the port, e.g. to write from another thread while reading.
+  pub fn try_clone(&self) -> crate::api::Result<Self> {
+    Ok(Self {
+      file: self.file.try_clone()?,
+      timeout: self.timeout,
+    })
+  }
+}
+
+impl Read for SerialPort {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    #[cfg(all(unix, desktop))]
+    if !unix::wait_readable(&self.file, self.timeout)? {
+      return Err(io::ErrorKind::TimedOut.into());
+    }
+
+    match self.file.read(buf)? {
+      // the unix ports only return nothing once disconnected, the Windows ones on a timeout
+      0 if !buf.is_empty() => Err(if cfg!(windows) {
+        io::ErrorKind::TimedOut.into()
+      } else {
+        disconnected()
+      }),
+      len => Ok(len),
+    }
+  }
+}
+
+impl Write for SerialPort {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.file.write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.file.flush()
+  }
+}
+
+#[cfg_attr(windows, allow(dead_code))]
+fn disconnected() -> io::Error {
+  io::Error::new(
+    io::ErrorKind::BrokenPipe,
+    "the serial port was disconnected",
+  )
+}
+
+#[cfg(mobile)]
+fn unsupported() -> crate::api::Error {
+  io::Error::new(
+    io::ErrorKind::Unsupported,
+    "serial ports are not supported on this platform",
+  )
+  .into()
+}
+
+/// Lists the serial ports of the computer.
+pub fn available_ports() -> crate::api::Result<Vec<SerialPortInfo>> {
+  #[cfg(target_os = "linux")]
+  return linux::available_ports().map_err(Into::into);
+
+  #[cfg(all(unix, desktop, not(target_os = "linux")))]
+  return unix::available_ports().map_err(Into::into);
+
+  #[cfg(windows)]
+  return win32::available_ports().map_err(Into::into);
+
+  #[cfg(mobile)]
+  return Err(unsupported());
+}
+
+/// Whether the port matches a pattern of the scope.
+fn is_allowed(scope: &[String], path: &str) -> bool {
+  let options = glob::MatchOptions {
+    // the Windows port names are case insensitive
+    case_sensitive: !cfg!(windows),
+    ..Default::default()
+  };
+  scope.iter().any(|pattern| {
+    glob::Pattern::new(pattern)
+      .map(|pattern| pattern.matches_with(path, options))
+      .unwrap_or(false)
+  })
+}
+
+#[cfg(all(unix, desktop))]
+mod unix {
+  use super::{FlowControl, Parity, SerialOptions, StopBits};
+  use std::{
+    fs::{File, OpenOptions},
+    io,
+    os::unix::{fs::OpenOptionsExt, io::AsRawFd},
+    time::Duration,
+  };
+
+  fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result == -1 {
+      Err(io::Error::last_os_error())
+    } else {
+      Ok(result)
+    }
+  }
+
+  #[cfg(target_os = "linux")]
+  fn speed(baud_rate: u32) -> io::Result<libc::speed_t> {
+    Ok(match baud_rate {
+      300 => libc::B300,
+      600 => libc::B600,
+      1200 => libc::B1200,
+      2400 => libc::B2400,
+      4800 => libc::B4800,
+      9600 => libc::B9600,
+      19200 => libc::B19200,
+      38400 => libc::B38400,
+      57600 => libc::B57600,
+      115200 => libc::B115200,
+      230400 => libc::B230400,
+      460800 => libc::B460800,
+      500000 => libc::B500000,
+      576000 => libc::B576000,
+      921600 => libc::B921600,
+      1000000 => libc::B1000000,
+      1500000 => libc::B1500000,
+      2000000 => libc::B2000000,
+      3000000 => libc::B3000000,
+      4000000 => libc::B4000000,
+      _ => {
+        return Err(io::Error::new(
+          io::ErrorKind::InvalidInput,
+          format!("unsupported baud rate {baud_rate}"),
+        ))
+      }
+    })
+  }
+
+  // the BSD termios take the baud rate itself
+  #[cfg(not(target_os = "linux"))]
+  fn speed(baud_rate: u32) -> io::Result<libc::speed_t> {
+    Ok(baud_rate as libc::speed_t)
+  }
+
+  pub(super) fn open(path: &str, options: &SerialOptions) -> io::Result<File> {
+    // the port is opened without waiting for the carrier detect line
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .custom_flags(libc::O_NOCTTY | libc::O_NONBLOCK)
+      .open(path)?;
+    let fd = file.as_raw_fd();
+
+    unsafe {
+      // the other processes can no longer open the port
+      check(libc::ioctl(fd, libc::TIOCEXCL as _))?;
+
+      let mut termios: libc::termios = std::mem::zeroed();
+      check(libc::tcgetattr(fd, &mut termios))?;
+      libc::cfmakeraw(&mut termios);
+      termios.c_cflag |= libc::CREAD | libc::CLOCAL;
+      termios.c_cflag &=
+        !(libc::CSIZE | libc::PARENB | libc::PARODD | libc::CSTOPB | libc::CRTSCTS);
+      termios.c_cflag |= match options.data_bits {
+        5 => libc::CS5,
+        6 => libc::CS6,
+        7 => libc::CS7,
+        _ => libc::CS8,
+      };
+      match options.parity {
+        Parity::None => {}
+        Parity::Odd => termios.c_cflag |= libc::PARENB | libc::PARODD,
+        Parity::Even => termios.c_cflag |= libc::PARENB,
+      }
+      if options.stop_bits == StopBits::Two {
+        termios.c_cflag |= libc::CSTOPB;
+      }
+      if options.flow_control == FlowControl::Hardware {
+        termios.c_cflag |= libc::CRTSCTS;
+      }
+      // the reads wait for the data with `poll`, then return what was received
+      termios.c_cc[libc::VMIN] = 1;
+      termios.c_cc[libc::VTIME] = 0;
+      check(libc::cfsetspeed(&mut termios, speed(options.baud_rate)?))?;
+      check(libc::tcsetattr(fd, libc::TCSANOW, &termios))?;
+
+      let flags = check(libc::fcntl(fd, libc::F_GETFL))?;
+      check(libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK))?;
+    }
+
+    Ok(file)
+  }
+
+  /// Waits until the port has data, returning `false` on a timeout.
+  pub(super) fn wait_readable(file: &File, timeout: Duration) -> io::Result<bool> {
+    let mut fd = libc::pollfd {
+      fd: file.as_raw_fd(),
+      events: libc::POLLIN,
+      revents: 0,
+    };
+    let timeout = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+    match unsafe { libc::poll(&mut fd, 1, timeout) } {
+      -1 => {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::Interrupted {
+          Ok(false)
+        } else {
+          Err(error)
+        }
+      }
+      0 => Ok(false),
+      _ if fd.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0 => {
+        Err(super::disconnected())
+      }
+      _ => Ok(true),
+    }
+  }
+
+  /// Lists the callout devices of `/dev`.
+  #[cfg(not(target_os = "linux"))]
+  pub(super) fn available_ports() -> io::Result<Vec<super::SerialPortInfo>> {
+    let mut ports = Vec::new();
+    for entry in std::fs::read_dir("/dev")? {
+      let name = entry?.file_name().to_string_lossy().into_owned();
+      // macOS names them `cu.*`, the BSDs `cuaU*` for the USB ports
+      if name.starts_with("cu.") || name.starts_with("cuaU") {
+        ports.push(super::SerialPortInfo {
+          path: format!("/dev/{name}"),
+          vendor_id: None,
+          product_id: None,
+          product_name: None,
+          serial_number: None,
+        });
+      }
+    }
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use super::SerialPortInfo;
+  use std::{fs, io, path::Path};
+
+  fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+      .ok()
+      .map(|s| s.trim().to_string())
+      .filter(|s| !s.is_empty())
+  }
+
+  fn read_hex(path: &Path) -> Option<u16> {
+    read_trimmed(path).and_then(|s| u16::from_str_radix(&s, 16).ok())
+  }
+
+  pub(super) fn available_ports() -> io::Result<Vec<SerialPortInfo>> {
+    let mut ports = Vec::new();
+    for entry in fs::read_dir("/sys/class/tty")? {
+      let entry = entry?;
+      let tty = entry.path();
+      // the virtual terminals have no device
+      let device = match fs::canonicalize(tty.join("device")) {
+        Ok(device) => device,
+        Err(_) => continue,
+      };
+      // the UART placeholders of the kernel have an unknown port type
+      if read_trimmed(&tty.join("type")).as_deref() == Some("0") {
+        continue;
+      }
+
+      // the USB device is an ancestor of the interface of the port
+      let usb = device
+        .ancestors()
+        .take(4)
+        .find(|dir| dir.join("idVendor").exists());
+      ports.push(SerialPortInfo {
+        path: format!("/dev/{}", entry.file_name().to_string_lossy()),
+        vendor_id: usb.and_then(|dir| read_hex(&dir.join("idVendor"))),
+        product_id: usb.and_then(|dir| read_hex(&dir.join("idProduct"))),
+        product_name: usb.and_then(|dir| read_trimmed(&dir.join("product"))),
+        serial_number: usb.and_then(|dir| read_trimmed(&dir.join("serial"))),
+      });
+    }
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+}
+
+#[cfg(windows)]
+mod win32 {
+  use super::{FlowControl, Parity, SerialOptions, SerialPortInfo, StopBits};
+  use std::{
+    fs::{File, OpenOptions},
+    io,
+    os::windows::{fs::OpenOptionsExt, io::AsRawHandle},
+  };
+  use windows::{
+    core::{PCWSTR, PWSTR},
+    Win32::{
+      Devices::Communication::{
+        GetCommState, SetCommState, SetCommTimeouts, COMMTIMEOUTS, DCB, EVENPARITY, NOPARITY,
+        ODDPARITY, ONESTOPBIT, TWOSTOPBITS,
+      },
+      Foundation::{ERROR_SUCCESS, HANDLE},
+      System::Registry::{
+        RegCloseKey, RegEnumValueW, RegOpenKeyExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ,
+      },
+    },
+  };
+
+  // the bits of `DCB::_bitfield`
+  const F_BINARY: u32 = 1;
+  const F_PARITY: u32 = 1 << 1;
+  const F_OUTX_CTS_FLOW: u32 = 1 << 2;
+  const F_RTS_CONTROL_MASK: u32 = 0b11 << 12;
+  const RTS_CONTROL_ENABLE: u32 = 1 << 12;
+  const RTS_CONTROL_HANDSHAKE: u32 = 2 << 12;
+
+  pub(super) fn open(path: &str, options: &SerialOptions) -> io::Result<File> {
+    // the device namespace is required for the ports after `COM9`
+    let device = if path.starts_with(r"\\") {
+      path.to_string()
+    } else {
+      format!(r"\\.\{path}")
+    };
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .share_mode(0)
+      .open(device)?;
+    let handle = HANDLE(file.as_raw_handle() as isize);
+
+    unsafe {
+      let mut dcb = DCB {
+        DCBlength: std::mem::size_of::<DCB>() as u32,
+        ..Default::default()
+      };
+      if !GetCommState(handle, &mut dcb).as_bool() {
+        return Err(io::Error::last_os_error());
+      }
+      dcb.BaudRate = options.baud_rate;
+      dcb.ByteSize = options.data_bits;
+      dcb.Parity = match options.parity {
+        Parity::None => NOPARITY,
+        Parity::Odd => ODDPARITY,
+        Parity::Even => EVENPARITY,
+      };
+      dcb.StopBits = match options.stop_bits {
+        StopBits::One => ONESTOPBIT,
+        StopBits::Two => TWOSTOPBITS,
+      };
+      dcb._bitfield &= !(F_PARITY | F_OUTX_CTS_FLOW | F_RTS_CONTROL_MASK);
+      dcb._bitfield |= F_BINARY;
+      if options.parity != Parity::None {
+        dcb._bitfield |= F_PARITY;
+      }
+      dcb._bitfield |= match options.flow_control {
+        FlowControl::None => RTS_CONTROL_ENABLE,
+        FlowControl::Hardware => F_OUTX_CTS_FLOW | RTS_CONTROL_HANDSHAKE,
+      };
+      if !SetCommState(handle, &dcb).as_bool() {
+        return Err(io::Error::last_os_error());
+      }
+
+      // a read returns as soon as data is received, or nothing after the timeout
+      let timeouts = COMMTIMEOUTS {
+        ReadIntervalTimeout: u32::MAX,
+        ReadTotalTimeoutMultiplier: u32::MAX,
+        ReadTotalTimeoutConstant: options.timeout.as_millis().clamp(1, u32::MAX as u128 - 1) as u32,
+        WriteTotalTimeoutMultiplier: 0,
+        WriteTotalTimeoutConstant: 0,
+      };
+      if !SetCommTimeouts(handle, &timeouts).as_bool() {
+        return Err(io::Error::last_os_error());
+      }
+    }
+
+    Ok(file)
+  }
+
+  /// Lists the ports of the `SERIALCOMM` device map of the registry.
+  pub(super) fn available_ports() -> io::Result<Vec<SerialPortInfo>> {
+    let mut ports = Vec::new();
+    unsafe {
+      let subkey = "HARDWARE\\DEVICEMAP\\SERIALCOMM\0"
+        .encode_utf16()
+        .collect::<Vec<u16>>();
+      let mut key = HKEY::default();
+      let result = RegOpenKeyExW(
+        HKEY_LOCAL_MACHINE,
+        PCWSTR(subkey.as_ptr()),
+        0,
+        KEY_READ,
+        &mut key,
+      );
+      // the key only exists when a port is present
+      if result != ERROR_SUCCESS {
+        return Ok(ports);
+      }
+
+      let mut index = 0;
+      loop {
+        let mut name = [0u16; 256];
+        let mut name_len = name.len() as u32;
+        let mut data = [0u16; 256];
+        let mut data_len = (data.len() * std::mem::size_of::<u16>()) as u32;
+        let result = RegEnumValueW(
+          key,
+          index,
+          PWSTR(name.as_mut_ptr()),
+          &mut name_len,
+          None,
+          None,
+          Some(data.as_mut_ptr() as *mut u8),
+          Some(&mut data_len),
+        );
+        if result != ERROR_SUCCESS {
+          break;
+        }
+        let len = data[..data_len as usize / 2]
+          .iter()
+          .position(|c| *c == 0)
+          .unwrap_or(data_len as usize / 2);
+        ports.push(SerialPortInfo {
+          path: String::from_utf16_lossy(&data[..len]),
+          vendor_id: None,
+          product_id: None,
+          product_name: None,
+          serial_number: None,
+        });
+        index += 1;
+      }
+      RegCloseKey(key);
+    }
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+}
+
+mod commands {
+  use super::{available_ports, is_allowed, SerialOptions, SerialPort, SerialPortInfo};
+  use crate::{api::ipc::Channel, command, AppHandle, Runtime, State};
+  use serde::Serialize;
+  use std::{
+    collections::HashMap,
+    io::{ErrorKind, Read, Write},
+    sync::{
+      atomic::{AtomicBool, AtomicU32, Ordering},
+      Arc, Mutex,
+    },
+  };
+
+  /// The size of the buffer the data of a port is read into.
+  const READ_BUFFER_SIZE: usize = 4096;
+
+  /// An event sent to the frontend for an open port.
+  #[derive(Serialize)]
+  #[serde(tag = "type", rename_all = "camelCase")]
+  enum SerialEvent<'a> {
+    Data { data: &'a [u8] },
+    Disconnected,
+  }
+
+  struct OpenPort {
+    writer: Mutex<SerialPort>,
+    closed: AtomicBool,
+  }
+
+  /// The ports opened by the frontend, removed when their reader thread ends.
+  #[derive(Default)]
+  pub(super) struct Ports {
+    next_id: AtomicU32,
+    ports: Arc<Mutex<HashMap<u32, Arc<OpenPort>>>>,
+  }
+
+  fn check_scope<R: Runtime>(app: &AppHandle<R>, path: &str) -> crate::Result<()> {
+    if is_allowed(&app.config().tauri.security.serial.scope, path) {
+      Ok(())
+    } else {
+      Err(crate::Error::FailedToExecuteApi(
+        std::io::Error::new(
+          ErrorKind::PermissionDenied,
+          format!("the serial port `{path}` is not allowed by the scope"),
+        )
+        .into(),
+      ))
+    }
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn available<R: Runtime>(app: AppHandle<R>) -> crate::Result<Vec<SerialPortInfo>> {
+    let scope = &app.config().tauri.security.serial.scope;
+    Ok(
+      available_ports()?
+        .into_iter()
+        .filter(|port| is_allowed(scope, &port.path))
+        .collect(),
+    )
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn open<R: Runtime>(
+    app: AppHandle<R>,
+    ports: State<'_, Ports>,
+    path: String,
+    options: Option<SerialOptions>,
+    on_event: Channel<R>,
+  ) -> crate::Result<u32> {
+    check_scope(&app, &path)?;
+    let mut reader = SerialPort::open(&path, &options.unwrap_or_default())?;
+    let port = Arc::new(OpenPort {
+      writer: Mutex::new(reader.try_clone()?),
+      closed: AtomicBool::new(false),
+    });
+    let id = ports.next_id.fetch_add(1, Ordering::Relaxed);
+    ports.ports.lock().unwrap().insert(id, port.clone());
+
+    let open_ports = ports.ports.clone();
+    std::thread::spawn(move || {
+      let mut buf = vec![0; READ_BUFFER_SIZE];
+      // the thread checks whether the port is closed after each read timeout
+      while !port.closed.load(Ordering::Relaxed) {
+        match reader.read(&mut buf) {
+          Ok(len) => {
+            if on_event
+              .send(&SerialEvent::Data { data: &buf[..len] })
+              .is_err()
+            {
+              break;
+            }
+          }
+          Err(e) if e.kind() == ErrorKind::TimedOut || e.kind() == ErrorKind::Interrupted => {}
+          Err(_) => {
+            let _ = on_event.send(&SerialEvent::Disconnected);
+            break;
+          }
+        }
+      }
+      open_ports.lock().unwrap().remove(&id);
+    });
+
+    Ok(id)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn write(ports: State<'_, Ports>, id: u32, data: Vec<u8>) -> crate::Result<()> {
+    let port = ports.ports.lock().unwrap().get(&id).cloned();
+    match port {
+      Some(port) => port
+        .writer
+        .lock()
+        .unwrap()
+        .write_all(&data)
+        .map_err(|e| crate::Error::FailedToExecuteApi(e.into())),
+      None => Err(crate::Error::FailedToExecuteApi(
+        std::io::Error::new(ErrorKind::NotConnected, "the serial port is closed").into(),
+      )),
+    }
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn close(ports: State<'_, Ports>, id: u32) {
+    if let Some(port) = ports.ports.lock().unwrap().remove(&id) {
+      port.closed.store(true, Ordering::Relaxed);
+    }
+  }
+}
+
+/// Initializes the serial plugin, opening the ports of the scope for the frontend.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("serial")
+    .invoke_handler(crate::generate_handler![
+      commands::available,
+      commands::open,
+      commands::write,
+      commands::close
+    ])
+    .setup(|app, _api| {
+      app.manage(commands::Ports::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_allowed, Parity, SerialOptions};
+  use std::time::Duration;
+
+  #[test]
+  fn checks_the_scope() {
+    let scope = vec!["/dev/ttyUSB*".to_string(), "COM3".to_string()];
+    assert!(is_allowed(&scope, "/dev/ttyUSB0"));
+    assert!(!is_allowed(&scope, "/dev/ttyS0"));
+    assert!(is_allowed(&scope, "COM3"));
+    assert!(!is_allowed(&scope, "COM30"));
+    assert!(!is_allowed(&[], "/dev/ttyUSB0"));
+  }
+
+  #[test]
+  fn deserializes_options() {
+    let options: SerialOptions =
+      serde_json::from_str(r#"{"baudRate":115200,"parity":"even","timeout":500}"#).unwrap();
+    assert_eq!(
+      options,
+      SerialOptions::new(115200)
+        .parity(Parity::Even)
+        .timeout(Duration::from_millis(500))
+    );
+  }
+}
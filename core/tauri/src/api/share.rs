@@ -0,0 +1,417 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Text, links and files shared with the other applications through the share sheet of the operating system.
+//!
+//! The frontend can only share the files of the [`tauri > security > share > scope`](crate::utils::config::ShareConfig#structfield.scope).
+//!
+//! ## Platform-specific
+//!
+//! - **macOS**: The `NSSharingServicePicker`, shown at the top of the window.
+//! - **Windows**: The share UI of the `DataTransferManager`. The texts are joined with new lines and only the first link is shared.
+//! - **Linux**: Unsupported.
+//! - **Android**: The `ACTION_SEND` chooser. The files are shared through the `${applicationId}.fileprovider` `FileProvider` of the app.
+//! - **iOS**: The `UIActivityViewController`.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime, Window,
+};
+
+/// Errors of the share function.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The platform has no share sheet.
+  #[error("sharing is not supported on this platform")]
+  Unsupported,
+  /// There is nothing to share.
+  #[error("no item to share")]
+  Empty,
+  /// The file is not allowed by the `tauri > security > share > scope` configuration.
+  #[error("the file `{0}` is not allowed by the share scope")]
+  NotAllowed(PathBuf),
+  /// The share sheet could not be shown.
+  #[error("failed to show the share sheet: {0}")]
+  Failed(String),
+}
+
+/// An item shared with [`share`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ShareItem {
+  /// A text.
+  Text(String),
+  /// A link.
+  Url(Url),
+  /// A file, shared with its content.
+  File(PathBuf),
+}
+
+/// The native share plugin.
+#[cfg(mobile)]
+struct SharePlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// The files of the `tauri > security > share > scope` configuration.
+struct ShareScope(crate::scope::FsScope);
+
+/// Shows the share sheet of the window, returning once it is shown.
+///
+/// It must not be called on the main thread.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{api::share::ShareItem, Manager};
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let window = app.get_window("main").unwrap();
+///     std::thread::spawn(move || {
+///       tauri::api::share(
+///         &window,
+///         vec![ShareItem::Url("https://tauri.app".parse().unwrap())],
+///       )
+///       .unwrap();
+///     });
+///     Ok(())
+///   });
+/// ```
+pub fn share<R: Runtime>(window: &Window<R>, items: Vec<ShareItem>) -> crate::api::Result<()> {
+  if items.is_empty() {
+    return Err(Error::Empty.into());
+  }
+  platform::share(window, items).map_err(Into::into)
+}
+
+mod commands {
+  use super::{Error, ShareItem, ShareScope};
+  use crate::{command, Manager, Runtime, Window};
+
+  #[command(root = "crate")]
+  pub(super) async fn share<R: Runtime>(
+    window: Window<R>,
+    items: Vec<ShareItem>,
+  ) -> crate::Result<()> {
+    let scope = window.state::<ShareScope>();
+    for item in &items {
+      if let ShareItem::File(path) = item {
+        if !scope.0.is_allowed(path) {
+          return Err(crate::api::Error::from(Error::NotAllowed(path.clone())).into());
+        }
+      }
+    }
+    super::share(&window, items).map_err(Into::into)
+  }
+}
+
+/// The share plugin, exposing the commands of `@tauri-apps/api/share` when the `tauri > security > share` allowlist enables them.
+pub(crate) fn init<R: Runtime>(commands: bool) -> TauriPlugin<R> {
+  let mut builder = Builder::new("share");
+  if commands {
+    builder = builder.invoke_handler(crate::generate_handler![commands::share]);
+  }
+  builder
+    .setup(move |app, _api| {
+      if commands {
+        let scope = &app.config().tauri.security.share.scope;
+        app.manage(ShareScope(crate::scope::FsScope::for_fs_api(app, scope)?));
+      }
+      #[cfg(target_os = "android")]
+      app.manage(SharePlugin(
+        _api.register_android_plugin("app.tauri.share", "SharePlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      app.manage(SharePlugin(
+        _api.register_ios_plugin(crate::ios::init_share_plugin)?,
+      ));
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::{Error, ShareItem};
+  use crate::{Runtime, Window};
+
+  use cocoa::{
+    base::{id, nil},
+    foundation::{NSPoint, NSRect, NSSize, NSString},
+  };
+  use objc::{class, msg_send, sel, sel_impl};
+
+  use std::sync::mpsc::channel;
+
+  /// `NSRectEdgeMinY`, below the rect since the content view is not flipped.
+  const RECT_EDGE_MIN_Y: usize = 1;
+
+  pub fn share<R: Runtime>(window: &Window<R>, items: Vec<ShareItem>) -> Result<(), Error> {
+    let ns_window = window
+      .ns_window()
+      .map_err(|e| Error::Failed(e.to_string()))? as usize;
+    let (tx, rx) = channel();
+    window
+      .run_on_main_thread(move || {
+        let _ = tx.send(unsafe { show(ns_window as id, &items) });
+      })
+      .map_err(|e| Error::Failed(e.to_string()))?;
+    rx.recv()
+      .unwrap_or_else(|_| Err(Error::Failed("the main thread is not running".into())))
+  }
+
+  unsafe fn show(ns_window: id, items: &[ShareItem]) -> Result<(), Error> {
+    let objects: id = msg_send![class!(NSMutableArray), array];
+    for item in items {
+      let object: id = match item {
+        ShareItem::Text(text) => NSString::alloc(nil).init_str(text).autorelease(),
+        ShareItem::Url(url) => {
+          let url = NSString::alloc(nil).init_str(url.as_str()).autorelease();
+          msg_send![class!(NSURL), URLWithString: url]
+        }
+        ShareItem::File(path) => {
+          let path = NSString::alloc(nil)
+            .init_str(&path.to_string_lossy())
+            .autorelease();
+          msg_send![class!(NSURL), fileURLWithPath: path]
+        }
+      };
+      if object != nil {
+        let _: () = msg_send![objects, addObject: object];
+      }
+    }
+
+    let view: id = msg_send![ns_window, contentView];
+    if view == nil {
+      return Err(Error::Failed("the window has no content view".into()));
+    }
+    let bounds: NSRect = msg_send![view, bounds];
+    let anchor = NSRect::new(
+      NSPoint::new(bounds.size.width / 2., bounds.size.height),
+      NSSize::new(0., 0.),
+    );
+    // the picker is not retained by AppKit while it is shown, so it is never released
+    let picker: id = msg_send![class!(NSSharingServicePicker), alloc];
+    let picker: id = msg_send![picker, initWithItems: objects];
+    let _: () = msg_send![
+      picker,
+      showRelativeToRect: anchor
+      ofView: view
+      preferredEdge: RECT_EDGE_MIN_Y
+    ];
+    Ok(())
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{Error, ShareItem};
+  use crate::{Manager, Runtime, Window};
+
+  use windows::{
+    core::{implement, Interface, HSTRING},
+    ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager},
+    Foundation::{
+      Collections::{IIterable, IIterable_Impl, IIterator, IIterator_Impl},
+      EventRegistrationToken, TypedEventHandler, Uri,
+    },
+    Storage::{IStorageItem, StorageFile},
+    Win32::{
+      Foundation::{E_BOUNDS, HWND},
+      UI::Shell::IDataTransferManagerInterop,
+    },
+  };
+
+  use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc::channel,
+    Arc, Mutex,
+  };
+
+  /// The storage items shared by the `DataPackage`.
+  #[implement(IIterable<IStorageItem>)]
+  struct StorageItems(Vec<IStorageItem>);
+
+  impl IIterable_Impl<IStorageItem> for StorageItems {
+    fn First(&self) -> windows::core::Result<IIterator<IStorageItem>> {
+      Ok(
+        StorageItemsIterator {
+          items: self.0.clone(),
+          index: AtomicUsize::new(0),
+        }
+        .into(),
+      )
+    }
+  }
+
+  #[implement(IIterator<IStorageItem>)]
+  struct StorageItemsIterator {
+    items: Vec<IStorageItem>,
+    index: AtomicUsize,
+  }
+
+  impl IIterator_Impl<IStorageItem> for StorageItemsIterator {
+    fn Current(&self) -> windows::core::Result<IStorageItem> {
+      self
+        .items
+        .get(self.index.load(Ordering::Relaxed))
+        .cloned()
+        .ok_or_else(|| E_BOUNDS.into())
+    }
+
+    fn HasCurrent(&self) -> windows::core::Result<bool> {
+      Ok(self.index.load(Ordering::Relaxed) < self.items.len())
+    }
+
+    fn MoveNext(&self) -> windows::core::Result<bool> {
+      let len = self.items.len();
+      let index = self.index.load(Ordering::Relaxed);
+      if index < len {
+        self.index.store(index + 1, Ordering::Relaxed);
+      }
+      Ok(index + 1 < len)
+    }
+
+    fn GetMany(&self, items: &mut [Option<IStorageItem>]) -> windows::core::Result<u32> {
+      let index = self.index.load(Ordering::Relaxed).min(self.items.len());
+      let remaining = &self.items[index..];
+      let count = remaining.len().min(items.len());
+      for (slot, item) in items.iter_mut().zip(&remaining[..count]) {
+        *slot = Some(item.clone());
+      }
+      self.index.store(index + count, Ordering::Relaxed);
+      Ok(count as u32)
+    }
+  }
+
+  pub fn share<R: Runtime>(window: &Window<R>, items: Vec<ShareItem>) -> Result<(), Error> {
+    let hwnd = window.hwnd().map_err(|e| Error::Failed(e.to_string()))?;
+    // the files are opened on the calling thread, waiting for them would block the event loop
+    let mut files = Vec::new();
+    for item in &items {
+      if let ShareItem::File(path) = item {
+        let file = StorageFile::GetFileFromPathAsync(&HSTRING::from(path.as_os_str()))
+          .and_then(|operation| operation.get())
+          .map_err(error)?;
+        files.push(file);
+      }
+    }
+    let title = window.app_handle().package_info().name.clone();
+
+    let (tx, rx) = channel();
+    window
+      .run_on_main_thread(move || {
+        let _ = tx.send(show(hwnd, title, items, files).map_err(error));
+      })
+      .map_err(|e| Error::Failed(e.to_string()))?;
+    rx.recv()
+      .unwrap_or_else(|_| Err(Error::Failed("the main thread is not running".into())))
+  }
+
+  fn show(
+    hwnd: HWND,
+    title: String,
+    items: Vec<ShareItem>,
+    files: Vec<StorageFile>,
+  ) -> windows::core::Result<()> {
+    let interop = windows::core::factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+    let token = Arc::new(Mutex::new(None::<EventRegistrationToken>));
+    let registration = token.clone();
+    let requested_manager = manager.clone();
+    let handler = TypedEventHandler::new(move |_, args: &Option<DataRequestedEventArgs>| {
+      // the handler is registered for a single request
+      if let Some(token) = registration.lock().unwrap().take() {
+        requested_manager.RemoveDataRequested(token)?;
+      }
+      if let Some(args) = args {
+        let data = args.Request()?.Data()?;
+        data
+          .Properties()?
+          .SetTitle(&HSTRING::from(title.as_str()))?;
+        let mut texts = Vec::new();
+        let mut link = None;
+        for item in &items {
+          match item {
+            ShareItem::Text(text) => texts.push(text.as_str()),
+            ShareItem::Url(url) => {
+              link.get_or_insert(url);
+            }
+            ShareItem::File(_) => (),
+          }
+        }
+        if !texts.is_empty() {
+          data.SetText(&HSTRING::from(texts.join("\n")))?;
+        }
+        if let Some(link) = link {
+          data.SetWebLink(&Uri::CreateUri(&HSTRING::from(link.as_str()))?)?;
+        }
+        if !files.is_empty() {
+          let storage_items = files
+            .iter()
+            .map(|file| file.cast())
+            .collect::<windows::core::Result<Vec<IStorageItem>>>()?;
+          let storage_items: IIterable<IStorageItem> = StorageItems(storage_items).into();
+          data.SetStorageItemsReadOnly(&storage_items)?;
+        }
+      }
+      Ok(())
+    });
+    *token.lock().unwrap() = Some(manager.DataRequested(&handler)?);
+    unsafe { interop.ShowShareUIForWindow(hwnd) }
+  }
+
+  fn error(e: windows::core::Error) -> Error {
+    Error::Failed(e.message().to_string())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{Error, ShareItem};
+  use crate::{Runtime, Window};
+
+  pub fn share<R: Runtime>(_window: &Window<R>, _items: Vec<ShareItem>) -> Result<(), Error> {
+    Err(Error::Unsupported)
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{Error, ShareItem, SharePlugin};
+  use crate::{plugin::mobile::PluginInvokeError, Manager, Runtime, Window};
+
+  #[derive(serde::Serialize)]
+  struct SharePayload {
+    items: Vec<ShareItem>,
+  }
+
+  pub fn share<R: Runtime>(window: &Window<R>, items: Vec<ShareItem>) -> Result<(), Error> {
+    let plugin = window
+      .try_state::<SharePlugin<R>>()
+      .ok_or_else(|| Error::Failed("the share plugin is not registered".into()))?;
+    plugin
+      .0
+      .run_mobile_plugin::<serde::de::IgnoredAny>("share", SharePayload { items })
+      .map(|_| ())
+      .map_err(|e| match e {
+        PluginInvokeError::InvokeRejected(response) => {
+          Error::Failed(response.message.unwrap_or_else(|| response.to_string()))
+        }
+        e => Error::Failed(e.to_string()),
+      })
+  }
+}
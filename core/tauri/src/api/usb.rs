@@ -0,0 +1,1091 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! USB devices, e.g. the boards programmed by a firmware flasher, with bulk, interrupt and control transfers.
+//!
+//! The frontend lists and opens the devices matching the [`tauri > security > usb > scope`](crate::utils::config::UsbConfig#structfield.scope)
+//! configuration with `@tauri-apps/api/usb`.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux**: Uses `usbfs`. The user needs read and write access to `/dev/bus/usb/*/*`, usually given by a udev rule.
+//!   Claiming an interface detaches its kernel driver, which is attached again when the interface is released.
+//! - **Windows**: Uses WinUSB. The device must use the WinUSB driver, installed with a Microsoft OS descriptor of the device or with a tool like Zadig.
+//!   The interfaces of a composite device, which are bound to separate drivers, cannot be opened.
+//! - **macOS / Android / iOS**: Unsupported, the functions fail with [`std::io::ErrorKind::Unsupported`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  utils::config::UsbDeviceFilter,
+  Manager, Runtime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{io, time::Duration};
+
+#[cfg(target_os = "linux")]
+use linux as platform;
+#[cfg(not(any(target_os = "linux", windows)))]
+use unsupported as platform;
+#[cfg(windows)]
+use win32 as platform;
+
+/// A USB device connected to the computer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UsbDeviceInfo {
+  /// The platform path the device is opened with.
+  pub path: String,
+  /// The vendor identifier.
+  pub vendor_id: u16,
+  /// The product identifier.
+  pub product_id: u16,
+  /// The product name reported by the device.
+  pub product_name: Option<String>,
+  /// The serial number reported by the device.
+  pub serial_number: Option<String>,
+}
+
+/// The setup of a control transfer, without its direction and its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlSetup {
+  /// The type and the recipient of the request, the bits 0 to 6 of `bmRequestType`, e.g. `0x21` for a class request to an interface.
+  /// The direction bit is set by the transfer.
+  pub request_type: u8,
+  /// The request, e.g. `0x01` for the `DFU_DNLOAD` request of the Device Firmware Upgrade class.
+  pub request: u8,
+  /// The value of the request.
+  pub value: u16,
+  /// The index of the request, usually an interface or an endpoint number.
+  pub index: u16,
+}
+
+/// The direction bit of `bmRequestType` and of the endpoint addresses, set for the device to host transfers.
+const DIRECTION_IN: u8 = 0x80;
+
+/// An open USB device.
+///
+/// The interface of an endpoint must be claimed before transferring data with the endpoint.
+pub struct UsbDevice {
+  inner: platform::Device,
+}
+
+impl std::fmt::Debug for UsbDevice {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("UsbDevice").finish()
+  }
+}
+
+impl UsbDevice {
+  /// Opens the device at the path of its [`UsbDeviceInfo`].
+  pub fn open(path: &str) -> crate::api::Result<Self> {
+    platform::Device::open(path)
+      .map(|inner| Self { inner })
+      .map_err(Into::into)
+  }
+
+  /// Claims an interface of the device for this process.
+  pub fn claim_interface(&self, interface: u8) -> crate::api::Result<()> {
+    self.inner.claim_interface(interface).map_err(Into::into)
+  }
+
+  /// Releases a claimed interface.
+  pub fn release_interface(&self, interface: u8) -> crate::api::Result<()> {
+    self.inner.release_interface(interface).map_err(Into::into)
+  }
+
+  /// Reads from a bulk or interrupt IN endpoint, e.g. `0x81`, into the buffer and returns the number of bytes read.
+  ///
+  /// A zero timeout waits forever.
+  pub fn read(&self, endpoint: u8, buf: &mut [u8], timeout: Duration) -> crate::api::Result<usize> {
+    check_endpoint(endpoint, DIRECTION_IN)?;
+    self
+      .inner
+      .read(endpoint, buf, timeout_millis(timeout))
+      .map_err(Into::into)
+  }
+
+  /// Writes the data to a bulk or interrupt OUT endpoint, e.g. `0x01`, and returns the number of bytes written.
+  ///
+  /// A zero timeout waits forever.
+  pub fn write(&self, endpoint: u8, data: &[u8], timeout: Duration) -> crate::api::Result<usize> {
+    check_endpoint(endpoint, 0)?;
+    self
+      .inner
+      .write(endpoint, data, timeout_millis(timeout))
+      .map_err(Into::into)
+  }
+
+  /// Sends a control request reading the response of the device into the buffer, and returns the length of the response.
+  ///
+  /// A zero timeout waits forever.
+  pub fn control_in(
+    &self,
+    setup: ControlSetup,
+    buf: &mut [u8],
+    timeout: Duration,
+  ) -> crate::api::Result<usize> {
+    check_control_length(buf.len())?;
+    self
+      .inner
+      .control(
+        setup.request_type | DIRECTION_IN,
+        &setup,
+        buf,
+        timeout_millis(timeout),
+      )
+      .map_err(Into::into)
+  }
+
+  /// Sends a control request with the data, and returns the number of bytes written.
+  ///
+  /// A zero timeout waits forever.
+  pub fn control_out(
+    &self,
+    setup: ControlSetup,
+    data: &[u8],
+    timeout: Duration,
+  ) -> crate::api::Result<usize> {
+    check_control_length(data.len())?;
+    self
+      .inner
+      .control(
+        setup.request_type & !DIRECTION_IN,
+        &setup,
+        &mut data.to_vec(),
+        timeout_millis(timeout),
+      )
+      .map_err(Into::into)
+  }
+}
+
+/// Lists the USB devices connected to the computer.
+pub fn devices() -> crate::api::Result<Vec<UsbDeviceInfo>> {
+  platform::devices().map_err(Into::into)
+}
+
+fn check_endpoint(endpoint: u8, direction: u8) -> crate::api::Result<()> {
+  if endpoint & DIRECTION_IN == direction {
+    return Ok(());
+  }
+  let kind = if direction == DIRECTION_IN {
+    "IN"
+  } else {
+    "OUT"
+  };
+  Err(
+    io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("0x{endpoint:02x} is not an {kind} endpoint"),
+    )
+    .into(),
+  )
+}
+
+fn check_control_length(len: usize) -> crate::api::Result<()> {
+  if len <= u16::MAX as usize {
+    Ok(())
+  } else {
+    Err(
+      io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the data of a control transfer is at most 65535 bytes long",
+      )
+      .into(),
+    )
+  }
+}
+
+/// The timeout of a transfer in milliseconds, `0` waiting forever.
+fn timeout_millis(timeout: Duration) -> u32 {
+  if timeout.is_zero() {
+    0
+  } else {
+    // a timeout shorter than a millisecond must not wait forever
+    timeout.as_millis().clamp(1, u32::MAX as u128) as u32
+  }
+}
+
+/// Whether the device matches a filter of the scope.
+fn is_allowed(scope: &[UsbDeviceFilter], device: &UsbDeviceInfo) -> bool {
+  scope
+    .iter()
+    .any(|filter| filter.matches(device.vendor_id, device.product_id))
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+mod unsupported {
+  use super::{ControlSetup, UsbDeviceInfo};
+  use std::io;
+
+  fn unsupported() -> io::Error {
+    io::Error::new(
+      io::ErrorKind::Unsupported,
+      "USB devices are not supported on this platform",
+    )
+  }
+
+  pub(super) fn devices() -> io::Result<Vec<UsbDeviceInfo>> {
+    Err(unsupported())
+  }
+
+  pub(super) enum Device {}
+
+  impl Device {
+    pub(super) fn open(_path: &str) -> io::Result<Self> {
+      Err(unsupported())
+    }
+
+    pub(super) fn claim_interface(&self, _interface: u8) -> io::Result<()> {
+      match *self {}
+    }
+
+    pub(super) fn release_interface(&self, _interface: u8) -> io::Result<()> {
+      match *self {}
+    }
+
+    pub(super) fn read(&self, _endpoint: u8, _buf: &mut [u8], _timeout: u32) -> io::Result<usize> {
+      match *self {}
+    }
+
+    pub(super) fn write(&self, _endpoint: u8, _data: &[u8], _timeout: u32) -> io::Result<usize> {
+      match *self {}
+    }
+
+    pub(super) fn control(
+      &self,
+      _request_type: u8,
+      _setup: &ControlSetup,
+      _data: &mut [u8],
+      _timeout: u32,
+    ) -> io::Result<usize> {
+      match *self {}
+    }
+  }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use super::{ControlSetup, UsbDeviceInfo};
+  use std::{
+    ffi::c_void,
+    fs::{self, File, OpenOptions},
+    io,
+    os::unix::io::AsRawFd,
+    path::Path,
+  };
+
+  /// `struct usbdevfs_ctrltransfer`
+  #[repr(C)]
+  struct CtrlTransfer {
+    request_type: u8,
+    request: u8,
+    value: u16,
+    index: u16,
+    length: u16,
+    timeout: u32,
+    data: *mut c_void,
+  }
+
+  /// `struct usbdevfs_bulktransfer`
+  #[repr(C)]
+  struct BulkTransfer {
+    endpoint: libc::c_uint,
+    length: libc::c_uint,
+    timeout: libc::c_uint,
+    data: *mut c_void,
+  }
+
+  /// `struct usbdevfs_ioctl`
+  #[repr(C)]
+  struct Ioctl {
+    interface: libc::c_int,
+    code: libc::c_int,
+    data: *mut c_void,
+  }
+
+  /// `struct usbdevfs_disconnect_claim`
+  #[repr(C)]
+  struct DisconnectClaim {
+    interface: libc::c_uint,
+    flags: libc::c_uint,
+    driver: [libc::c_char; 256],
+  }
+
+  const IOC_NONE: u32 = 0;
+  const IOC_WRITE: u32 = 1;
+  const IOC_READ: u32 = 2;
+
+  /// The `_IOC` request number of a `usbdevfs` ioctl, with the generic encoding of x86, ARM and RISC-V.
+  const fn ioc(direction: u32, number: u32, size: usize) -> u32 {
+    direction << 30 | (size as u32) << 16 | (b'U' as u32) << 8 | number
+  }
+
+  pub(super) const USBDEVFS_CONTROL: u32 =
+    ioc(IOC_READ | IOC_WRITE, 0, std::mem::size_of::<CtrlTransfer>());
+  pub(super) const USBDEVFS_BULK: u32 =
+    ioc(IOC_READ | IOC_WRITE, 2, std::mem::size_of::<BulkTransfer>());
+  pub(super) const USBDEVFS_RELEASEINTERFACE: u32 =
+    ioc(IOC_READ, 16, std::mem::size_of::<libc::c_uint>());
+  pub(super) const USBDEVFS_IOCTL: u32 =
+    ioc(IOC_READ | IOC_WRITE, 18, std::mem::size_of::<Ioctl>());
+  pub(super) const USBDEVFS_CONNECT: u32 = ioc(IOC_NONE, 23, 0);
+  pub(super) const USBDEVFS_DISCONNECT_CLAIM: u32 =
+    ioc(IOC_READ, 27, std::mem::size_of::<DisconnectClaim>());
+
+  fn attribute(dir: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(dir.join(name))
+      .ok()
+      .map(|value| value.trim().to_string())
+      .filter(|value| !value.is_empty())
+  }
+
+  fn device_info(dir: &Path) -> Option<UsbDeviceInfo> {
+    // the interfaces are listed with the devices, without identifiers
+    let vendor_id = u16::from_str_radix(&attribute(dir, "idVendor")?, 16).ok()?;
+    let product_id = u16::from_str_radix(&attribute(dir, "idProduct")?, 16).ok()?;
+    let bus: u16 = attribute(dir, "busnum")?.parse().ok()?;
+    let address: u16 = attribute(dir, "devnum")?.parse().ok()?;
+    Some(UsbDeviceInfo {
+      path: format!("/dev/bus/usb/{bus:03}/{address:03}"),
+      vendor_id,
+      product_id,
+      product_name: attribute(dir, "product"),
+      serial_number: attribute(dir, "serial"),
+    })
+  }
+
+  pub(super) fn devices() -> io::Result<Vec<UsbDeviceInfo>> {
+    let entries = match fs::read_dir("/sys/bus/usb/devices") {
+      Ok(entries) => entries,
+      // there is no USB controller
+      Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+      Err(e) => return Err(e),
+    };
+    let mut devices = Vec::new();
+    for entry in entries {
+      if let Some(device) = device_info(&entry?.path()) {
+        devices.push(device);
+      }
+    }
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(devices)
+  }
+
+  pub(super) struct Device {
+    file: File,
+  }
+
+  impl Device {
+    pub(super) fn open(path: &str) -> io::Result<Self> {
+      OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map(|file| Self { file })
+    }
+
+    fn ioctl<T>(&self, request: u32, argument: *mut T) -> io::Result<usize> {
+      let result = unsafe { libc::ioctl(self.file.as_raw_fd(), request as _, argument) };
+      if result < 0 {
+        Err(io::Error::last_os_error())
+      } else {
+        Ok(result as usize)
+      }
+    }
+
+    pub(super) fn claim_interface(&self, interface: u8) -> io::Result<()> {
+      // without a driver name and flags, the driver bound to the interface is detached
+      let mut claim = DisconnectClaim {
+        interface: interface.into(),
+        flags: 0,
+        driver: [0; 256],
+      };
+      self
+        .ioctl(USBDEVFS_DISCONNECT_CLAIM, &mut claim)
+        .map(|_| ())
+    }
+
+    pub(super) fn release_interface(&self, interface: u8) -> io::Result<()> {
+      let mut number = libc::c_uint::from(interface);
+      self.ioctl(USBDEVFS_RELEASEINTERFACE, &mut number)?;
+      // attaches the kernel driver again, which fails when the interface has no driver
+      let mut connect = Ioctl {
+        interface: interface.into(),
+        code: USBDEVFS_CONNECT as libc::c_int,
+        data: std::ptr::null_mut(),
+      };
+      let _ = self.ioctl(USBDEVFS_IOCTL, &mut connect);
+      Ok(())
+    }
+
+    pub(super) fn read(&self, endpoint: u8, buf: &mut [u8], timeout: u32) -> io::Result<usize> {
+      let mut transfer = BulkTransfer {
+        endpoint: endpoint.into(),
+        length: buf.len() as libc::c_uint,
+        timeout,
+        data: buf.as_mut_ptr() as *mut c_void,
+      };
+      // the bulk transfers of `usbfs` use the interrupt transfers for the interrupt endpoints
+      self.ioctl(USBDEVFS_BULK, &mut transfer)
+    }
+
+    pub(super) fn write(&self, endpoint: u8, data: &[u8], timeout: u32) -> io::Result<usize> {
+      let mut transfer = BulkTransfer {
+        endpoint: endpoint.into(),
+        length: data.len() as libc::c_uint,
+        timeout,
+        // the data of an OUT transfer is only read
+        data: data.as_ptr() as *mut c_void,
+      };
+      self.ioctl(USBDEVFS_BULK, &mut transfer)
+    }
+
+    pub(super) fn control(
+      &self,
+      request_type: u8,
+      setup: &ControlSetup,
+      data: &mut [u8],
+      timeout: u32,
+    ) -> io::Result<usize> {
+      let mut transfer = CtrlTransfer {
+        request_type,
+        request: setup.request,
+        value: setup.value,
+        index: setup.index,
+        length: data.len() as u16,
+        timeout,
+        data: data.as_mut_ptr() as *mut c_void,
+      };
+      self.ioctl(USBDEVFS_CONTROL, &mut transfer)
+    }
+  }
+}
+
+#[cfg(windows)]
+mod win32 {
+  use super::{ControlSetup, UsbDeviceInfo};
+  use std::{
+    ffi::c_void,
+    fs::{File, OpenOptions},
+    io,
+    os::windows::{fs::OpenOptionsExt, io::AsRawHandle},
+    sync::Mutex,
+  };
+  use windows::Win32::{
+    Devices::{
+      DeviceAndDriverInstallation::{
+        SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInterfaces, SetupDiGetClassDevsW,
+        SetupDiGetDeviceInterfaceDetailW, SetupDiGetDevicePropertyW, DIGCF_DEVICEINTERFACE,
+        DIGCF_PRESENT, HDEVINFO, SP_DEVICE_INTERFACE_DATA, SP_DEVICE_INTERFACE_DETAIL_DATA_W,
+        SP_DEVINFO_DATA,
+      },
+      Properties::{DEVPKEY_Device_BusReportedDeviceDesc, DEVPROP_TYPE_STRING},
+      Usb::{
+        WinUsb_ControlTransfer, WinUsb_Free, WinUsb_GetAssociatedInterface, WinUsb_Initialize,
+        WinUsb_QueryInterfaceSettings, WinUsb_QueryPipe, WinUsb_ReadPipe, WinUsb_SetPipePolicy,
+        WinUsb_WritePipe, GUID_DEVINTERFACE_USB_DEVICE, PIPE_TRANSFER_TIMEOUT,
+        USB_INTERFACE_DESCRIPTOR, WINUSB_PIPE_INFORMATION, WINUSB_SETUP_PACKET,
+      },
+    },
+    Foundation::{BOOL, HANDLE, HWND},
+    Storage::FileSystem::FILE_FLAG_OVERLAPPED,
+  };
+
+  /// The identifiers and the serial number in the path of a device,
+  /// e.g. `\\?\usb#vid_2341&pid_0043#75833353934351d0a1e1#{a5dcbf10-6530-11d2-901f-00c04fb951ed}`.
+  pub(super) fn parse_path(path: &str) -> Option<(u16, u16, Option<String>)> {
+    let mut parts = path.split('#').skip(1);
+    let (mut vendor_id, mut product_id) = (None, None);
+    for id in parts.next()?.to_ascii_lowercase().split('&') {
+      if let Some(id) = id.strip_prefix("vid_") {
+        vendor_id = u16::from_str_radix(id, 16).ok();
+      } else if let Some(id) = id.strip_prefix("pid_") {
+        product_id = u16::from_str_radix(id, 16).ok();
+      }
+    }
+    // the instance identifier is generated, with `&` separators, when the device has no serial number
+    let serial_number = parts
+      .next()
+      .filter(|id| !id.is_empty() && !id.contains('&'))
+      .map(Into::into);
+    Some((vendor_id?, product_id?, serial_number))
+  }
+
+  /// The device path of the interface, read from the variable length detail structure, and its device.
+  unsafe fn interface_path(
+    set: HDEVINFO,
+    interface: &SP_DEVICE_INTERFACE_DATA,
+  ) -> Option<(String, SP_DEVINFO_DATA)> {
+    let mut size = 0;
+    SetupDiGetDeviceInterfaceDetailW(set, interface, None, 0, Some(&mut size), None);
+    if size == 0 {
+      return None;
+    }
+    // u32 elements keep the structure aligned
+    let mut buffer = vec![0u32; (size as usize + 3) / 4];
+    let detail = buffer.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
+    (*detail).cbSize = std::mem::size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+    let mut device = SP_DEVINFO_DATA {
+      cbSize: std::mem::size_of::<SP_DEVINFO_DATA>() as u32,
+      ..Default::default()
+    };
+    if !SetupDiGetDeviceInterfaceDetailW(
+      set,
+      interface,
+      Some(detail),
+      size,
+      None,
+      Some(&mut device),
+    )
+    .as_bool()
+    {
+      return None;
+    }
+    let path = std::ptr::addr_of!((*detail).DevicePath) as *const u16;
+    let len = (0..).take_while(|i| *path.add(*i) != 0).count();
+    Some((
+      String::from_utf16_lossy(std::slice::from_raw_parts(path, len)),
+      device,
+    ))
+  }
+
+  /// The product string the device reported when it was connected.
+  unsafe fn product_name(set: HDEVINFO, device: &SP_DEVINFO_DATA) -> Option<String> {
+    let mut buffer = [0u8; 512];
+    let mut kind = 0;
+    if !SetupDiGetDevicePropertyW(
+      set,
+      device,
+      &DEVPKEY_Device_BusReportedDeviceDesc,
+      &mut kind,
+      Some(&mut buffer),
+      None,
+      0,
+    )
+    .as_bool()
+      || kind != DEVPROP_TYPE_STRING
+    {
+      return None;
+    }
+    let name = buffer
+      .chunks_exact(2)
+      .map(|c| u16::from_le_bytes([c[0], c[1]]))
+      .take_while(|c| *c != 0)
+      .collect::<Vec<_>>();
+    Some(String::from_utf16_lossy(&name)).filter(|name| !name.is_empty())
+  }
+
+  pub(super) fn devices() -> io::Result<Vec<UsbDeviceInfo>> {
+    unsafe {
+      let set = SetupDiGetClassDevsW(
+        Some(&GUID_DEVINTERFACE_USB_DEVICE),
+        None,
+        HWND::default(),
+        DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+      )
+      .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+      let mut devices = Vec::new();
+      let mut interface = SP_DEVICE_INTERFACE_DATA {
+        cbSize: std::mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+        ..Default::default()
+      };
+      let mut index = 0;
+      while SetupDiEnumDeviceInterfaces(
+        set,
+        None,
+        &GUID_DEVINTERFACE_USB_DEVICE,
+        index,
+        &mut interface,
+      )
+      .as_bool()
+      {
+        if let Some((path, device)) = interface_path(set, &interface) {
+          if let Some((vendor_id, product_id, serial_number)) = parse_path(&path) {
+            devices.push(UsbDeviceInfo {
+              product_name: product_name(set, &device),
+              path,
+              vendor_id,
+              product_id,
+              serial_number,
+            });
+          }
+        }
+        index += 1;
+      }
+      SetupDiDestroyDeviceInfoList(set);
+      Ok(devices)
+    }
+  }
+
+  fn check(result: BOOL) -> io::Result<()> {
+    if result.as_bool() {
+      Ok(())
+    } else {
+      Err(io::Error::last_os_error())
+    }
+  }
+
+  /// A WinUSB interface handle, which can be used from any thread.
+  #[derive(Clone, Copy, PartialEq, Eq)]
+  struct Handle(*mut c_void);
+
+  unsafe impl Send for Handle {}
+  unsafe impl Sync for Handle {}
+
+  struct Interface {
+    number: u8,
+    handle: Handle,
+    endpoints: Vec<u8>,
+  }
+
+  /// The number and the endpoint addresses of an interface.
+  unsafe fn query_interface(handle: Handle) -> io::Result<(u8, Vec<u8>)> {
+    let mut descriptor = USB_INTERFACE_DESCRIPTOR::default();
+    check(WinUsb_QueryInterfaceSettings(handle.0, 0, &mut descriptor))?;
+    let mut endpoints = Vec::new();
+    for index in 0..descriptor.bNumEndpoints {
+      let mut pipe = WINUSB_PIPE_INFORMATION::default();
+      check(WinUsb_QueryPipe(handle.0, 0, index, &mut pipe))?;
+      endpoints.push(pipe.PipeId);
+    }
+    Ok((descriptor.bInterfaceNumber, endpoints))
+  }
+
+  pub(super) struct Device {
+    /// The handle of the first interface, used by the control transfers and freed with the device.
+    first: Handle,
+    first_number: u8,
+    claimed: Mutex<Vec<Interface>>,
+    _file: File,
+  }
+
+  impl Device {
+    pub(super) fn open(path: &str) -> io::Result<Self> {
+      let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(FILE_FLAG_OVERLAPPED.0)
+        .open(path)?;
+      let mut handle = std::ptr::null_mut();
+      unsafe {
+        if !WinUsb_Initialize(HANDLE(file.as_raw_handle() as isize), &mut handle).as_bool() {
+          return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+              "the USB device does not use the WinUSB driver: {}",
+              io::Error::last_os_error()
+            ),
+          ));
+        }
+        let mut device = Self {
+          first: Handle(handle),
+          first_number: 0,
+          claimed: Default::default(),
+          _file: file,
+        };
+        device.first_number = query_interface(device.first)?.0;
+        Ok(device)
+      }
+    }
+
+    pub(super) fn claim_interface(&self, interface: u8) -> io::Result<()> {
+      let mut claimed = self.claimed.lock().unwrap();
+      if claimed.iter().any(|i| i.number == interface) {
+        return Ok(());
+      }
+      let handle = if interface == self.first_number {
+        self.first
+      } else {
+        // the associated interfaces are indexed from the interface following the first one
+        let index = interface
+          .checked_sub(self.first_number)
+          .and_then(|index| index.checked_sub(1))
+          .ok_or_else(|| {
+            io::Error::new(
+              io::ErrorKind::NotFound,
+              format!("the interface {interface} was not found"),
+            )
+          })?;
+        let mut handle = std::ptr::null_mut();
+        check(unsafe { WinUsb_GetAssociatedInterface(self.first.0, index, &mut handle) })?;
+        Handle(handle)
+      };
+      match unsafe { query_interface(handle) } {
+        Ok((_, endpoints)) => {
+          claimed.push(Interface {
+            number: interface,
+            handle,
+            endpoints,
+          });
+          Ok(())
+        }
+        Err(e) => {
+          if handle != self.first {
+            unsafe { WinUsb_Free(handle.0) };
+          }
+          Err(e)
+        }
+      }
+    }
+
+    pub(super) fn release_interface(&self, interface: u8) -> io::Result<()> {
+      let mut claimed = self.claimed.lock().unwrap();
+      if let Some(index) = claimed.iter().position(|i| i.number == interface) {
+        let interface = claimed.remove(index);
+        if interface.handle != self.first {
+          unsafe { WinUsb_Free(interface.handle.0) };
+        }
+      }
+      Ok(())
+    }
+
+    /// The handle of the claimed interface of the endpoint, with the transfer timeout of the endpoint set.
+    fn pipe(&self, endpoint: u8, timeout: u32) -> io::Result<Handle> {
+      let handle = self
+        .claimed
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|i| i.endpoints.contains(&endpoint))
+        .map(|i| i.handle)
+        .ok_or_else(|| {
+          io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("the endpoint 0x{endpoint:02x} is not in a claimed interface"),
+          )
+        })?;
+      set_timeout(handle, endpoint, timeout)?;
+      Ok(handle)
+    }
+
+    pub(super) fn read(&self, endpoint: u8, buf: &mut [u8], timeout: u32) -> io::Result<usize> {
+      let handle = self.pipe(endpoint, timeout)?;
+      let mut len = 0;
+      check(unsafe { WinUsb_ReadPipe(handle.0, endpoint, Some(buf), Some(&mut len), None) })?;
+      Ok(len as usize)
+    }
+
+    pub(super) fn write(&self, endpoint: u8, data: &[u8], timeout: u32) -> io::Result<usize> {
+      let handle = self.pipe(endpoint, timeout)?;
+      let mut len = 0;
+      check(unsafe { WinUsb_WritePipe(handle.0, endpoint, data, Some(&mut len), None) })?;
+      Ok(len as usize)
+    }
+
+    pub(super) fn control(
+      &self,
+      request_type: u8,
+      setup: &ControlSetup,
+      data: &mut [u8],
+      timeout: u32,
+    ) -> io::Result<usize> {
+      // the default control pipe
+      set_timeout(self.first, 0, timeout)?;
+      let packet = WINUSB_SETUP_PACKET {
+        RequestType: request_type,
+        Request: setup.request,
+        Value: setup.value,
+        Index: setup.index,
+        Length: data.len() as u16,
+      };
+      let mut len = 0;
+      check(unsafe {
+        WinUsb_ControlTransfer(self.first.0, packet, Some(data), Some(&mut len), None)
+      })?;
+      Ok(len as usize)
+    }
+  }
+
+  fn set_timeout(handle: Handle, pipe: u8, timeout: u32) -> io::Result<()> {
+    check(unsafe {
+      WinUsb_SetPipePolicy(
+        handle.0,
+        pipe,
+        PIPE_TRANSFER_TIMEOUT,
+        std::mem::size_of::<u32>() as u32,
+        &timeout as *const u32 as *const c_void,
+      )
+    })
+  }
+
+  impl Drop for Device {
+    fn drop(&mut self) {
+      for interface in self.claimed.get_mut().unwrap().drain(..) {
+        if interface.handle != self.first {
+          unsafe { WinUsb_Free(interface.handle.0) };
+        }
+      }
+      unsafe { WinUsb_Free(self.first.0) };
+    }
+  }
+}
+
+mod commands {
+  use super::{devices as usb_devices, is_allowed, ControlSetup, UsbDevice, UsbDeviceInfo};
+  use crate::{command, AppHandle, Runtime, State};
+  use std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    sync::{
+      atomic::{AtomicU32, Ordering},
+      Arc, Mutex,
+    },
+    time::Duration,
+  };
+
+  /// How long a transfer waits when the frontend does not set its timeout.
+  const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+  /// The devices opened by the frontend.
+  #[derive(Default)]
+  pub(super) struct Devices {
+    next_id: AtomicU32,
+    devices: Mutex<HashMap<u32, Arc<UsbDevice>>>,
+  }
+
+  impl Devices {
+    fn get(&self, id: u32) -> crate::Result<Arc<UsbDevice>> {
+      self
+        .devices
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| {
+          crate::Error::FailedToExecuteApi(
+            io::Error::new(ErrorKind::NotConnected, "the USB device is closed").into(),
+          )
+        })
+    }
+  }
+
+  fn timeout(timeout: Option<u64>) -> Duration {
+    timeout.map_or(DEFAULT_TIMEOUT, Duration::from_millis)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn devices<R: Runtime>(app: AppHandle<R>) -> crate::Result<Vec<UsbDeviceInfo>> {
+    let scope = &app.config().tauri.security.usb.scope;
+    Ok(
+      usb_devices()?
+        .into_iter()
+        .filter(|device| is_allowed(scope, device))
+        .collect(),
+    )
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn open<R: Runtime>(
+    app: AppHandle<R>,
+    devices: State<'_, Devices>,
+    path: String,
+  ) -> crate::Result<u32> {
+    // the path must be the one of a connected device, the identifiers cannot be read from any path
+    let scope = &app.config().tauri.security.usb.scope;
+    if !usb_devices()?
+      .iter()
+      .any(|device| device.path == path && is_allowed(scope, device))
+    {
+      return Err(crate::Error::FailedToExecuteApi(
+        io::Error::new(
+          ErrorKind::PermissionDenied,
+          format!("the USB device `{path}` is not allowed by the scope"),
+        )
+        .into(),
+      ));
+    }
+
+    let device = UsbDevice::open(&path)?;
+    let id = devices.next_id.fetch_add(1, Ordering::Relaxed);
+    devices.devices.lock().unwrap().insert(id, Arc::new(device));
+    Ok(id)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn claim_interface(
+    devices: State<'_, Devices>,
+    id: u32,
+    interface: u8,
+  ) -> crate::Result<()> {
+    devices
+      .get(id)?
+      .claim_interface(interface)
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn release_interface(
+    devices: State<'_, Devices>,
+    id: u32,
+    interface: u8,
+  ) -> crate::Result<()> {
+    devices
+      .get(id)?
+      .release_interface(interface)
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn transfer_in(
+    devices: State<'_, Devices>,
+    id: u32,
+    endpoint: u8,
+    length: u32,
+    timeout: Option<u64>,
+  ) -> crate::Result<Vec<u8>> {
+    let mut buf = vec![0; length as usize];
+    let len = devices
+      .get(id)?
+      .read(endpoint, &mut buf, self::timeout(timeout))?;
+    buf.truncate(len);
+    Ok(buf)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn transfer_out(
+    devices: State<'_, Devices>,
+    id: u32,
+    endpoint: u8,
+    data: Vec<u8>,
+    timeout: Option<u64>,
+  ) -> crate::Result<usize> {
+    devices
+      .get(id)?
+      .write(endpoint, &data, self::timeout(timeout))
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn control_transfer_in(
+    devices: State<'_, Devices>,
+    id: u32,
+    setup: ControlSetup,
+    length: u16,
+    timeout: Option<u64>,
+  ) -> crate::Result<Vec<u8>> {
+    let mut buf = vec![0; length as usize];
+    let len = devices
+      .get(id)?
+      .control_in(setup, &mut buf, self::timeout(timeout))?;
+    buf.truncate(len);
+    Ok(buf)
+  }
+
+  #[command(root = "crate", blocking)]
+  pub(super) fn control_transfer_out(
+    devices: State<'_, Devices>,
+    id: u32,
+    setup: ControlSetup,
+    data: Vec<u8>,
+    timeout: Option<u64>,
+  ) -> crate::Result<usize> {
+    devices
+      .get(id)?
+      .control_out(setup, &data, self::timeout(timeout))
+      .map_err(Into::into)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn close(devices: State<'_, Devices>, id: u32) {
+    // the device is closed once the running transfers end
+    devices.devices.lock().unwrap().remove(&id);
+  }
+}
+
+/// Initializes the USB plugin, opening the devices of the scope for the frontend.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("usb")
+    .invoke_handler(crate::generate_handler![
+      commands::devices,
+      commands::open,
+      commands::claim_interface,
+      commands::release_interface,
+      commands::transfer_in,
+      commands::transfer_out,
+      commands::control_transfer_in,
+      commands::control_transfer_out,
+      commands::close
+    ])
+    .setup(|app, _api| {
+      app.manage(commands::Devices::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_allowed, timeout_millis, UsbDeviceFilter, UsbDeviceInfo};
+  use std::time::Duration;
+
+  #[test]
+  fn checks_the_scope() {
+    let device = |vendor_id, product_id| UsbDeviceInfo {
+      path: String::new(),
+      vendor_id,
+      product_id,
+      product_name: None,
+      serial_number: None,
+    };
+    let scope = vec![
+      UsbDeviceFilter {
+        vendor_id: 0x2341,
+        product_id: None,
+      },
+      UsbDeviceFilter {
+        vendor_id: 0x0483,
+        product_id: Some(0xdf11),
+      },
+    ];
+    assert!(is_allowed(&scope, &device(0x2341, 0x0043)));
+    assert!(is_allowed(&scope, &device(0x0483, 0xdf11)));
+    assert!(!is_allowed(&scope, &device(0x0483, 0x5740)));
+    assert!(!is_allowed(&[], &device(0x2341, 0x0043)));
+  }
+
+  #[test]
+  fn converts_timeouts() {
+    assert_eq!(timeout_millis(Duration::ZERO), 0);
+    assert_eq!(timeout_millis(Duration::from_micros(10)), 1);
+    assert_eq!(timeout_millis(Duration::from_secs(5)), 5000);
+    assert_eq!(timeout_millis(Duration::MAX), u32::MAX);
+  }
+
+  #[cfg(target_os = "linux")]
+  #[test]
+  fn encodes_the_ioctls() {
+    use super::linux::*;
+    #[cfg(target_pointer_width = "64")]
+    {
+      assert_eq!(USBDEVFS_CONTROL, 0xc018_5500);
+      assert_eq!(USBDEVFS_BULK, 0xc018_5502);
+      assert_eq!(USBDEVFS_IOCTL, 0xc010_5512);
+    }
+    assert_eq!(USBDEVFS_RELEASEINTERFACE, 0x8004_5510);
+    assert_eq!(USBDEVFS_CONNECT, 0x5517);
+    assert_eq!(USBDEVFS_DISCONNECT_CLAIM, 0x8108_551b);
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn parses_device_paths() {
+    assert_eq!(
+      super::win32::parse_path(
+        r"\\?\usb#vid_2341&pid_0043#75833353934351d0a1e1#{a5dcbf10-6530-11d2-901f-00c04fb951ed}"
+      ),
+      Some((0x2341, 0x0043, Some("75833353934351d0a1e1".into())))
+    );
+    assert_eq!(
+      super::win32::parse_path(
+        r"\\?\usb#vid_0483&pid_df11#5&2b5e6d1&0&3#{a5dcbf10-6530-11d2-901f-00c04fb951ed}"
+      ),
+      Some((0x0483, 0xdf11, None))
+    );
+  }
+}
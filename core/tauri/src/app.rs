@@ -2,16 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+pub(crate) mod lifecycle;
 #[cfg(all(desktop, feature = "system-tray"))]
 pub(crate) mod tray;
 
+pub use lifecycle::LifecycleEvent;
+
 use crate::{
   api::ipc::CallbackFn,
-  command::{CommandArg, CommandItem},
+  command::{CommandArg, CommandCache, CommandItem},
+  event::{assert_event_name_is_valid, EventSchemas},
   hooks::{
-    window_invoke_responder, InvokeHandler, InvokeResponder, OnPageLoad, PageLoadPayload, SetupHook,
+    window_invoke_responder, InvokeHandler, InvokeResponder, OnHotRestart, OnPageLoad,
+    PageLoadPayload, SetupHook,
   },
   manager::{Asset, CustomProtocol, WindowManager},
+  permissions::{ConsentPrompt, PermissionRequest, PluginPermissions},
   plugin::{Plugin, PluginStore},
   runtime::{
     http::{Request as HttpRequest, Response as HttpResponse},
@@ -20,17 +26,20 @@ use crate::{
     ExitRequestedEventAction, RunEvent as RuntimeRunEvent,
   },
   scope::IpcScope,
+  scripts::AppScripts,
   sealed::{ManagerBase, RuntimeOrDispatch},
+  startup::{self, StartupPhase, StartupTimeline},
   utils::config::Config,
   utils::{assets::Assets, Env},
   Context, DeviceEventFilter, EventLoopMessage, Icon, Invoke, InvokeError, InvokeResponse, Manager,
-  Runtime, Scopes, StateManager, Theme, Window,
+  Runtime, Scopes, Script, ScriptId, StartupMetrics, StateManager, Theme, Window,
 };
 
 #[cfg(feature = "protocol-asset")]
 use crate::scope::FsScope;
 
 use raw_window_handle::HasRawDisplayHandle;
+use serde_json::Value as JsonValue;
 use tauri_macros::default_runtime;
 use tauri_runtime::window::{
   dpi::{PhysicalPosition, PhysicalSize},
@@ -42,6 +51,7 @@ use std::{
   collections::HashMap,
   fmt,
   sync::{mpsc::Sender, Arc, Weak},
+  time::Instant,
 };
 
 use crate::runtime::menu::{Menu, MenuId, MenuIdRef};
@@ -155,7 +165,9 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 pub enum RunEvent {
   /// Event loop is exiting.
   Exit,
-  /// The app is about to exit
+  /// The app is about to exit because its last window was closed.
+  ///
+  /// The exit is already prevented with the `tauri > keepRunningWithoutWindows` configuration.
   #[non_exhaustive]
   ExitRequested {
     /// Event API
@@ -177,11 +189,102 @@ pub enum RunEvent {
   ///
   /// This event is useful as a place to put your code that should be run after all state-changing events have been handled and you want to do stuff (updating state, performing calculations, etc) that happens as the “main body” of your event loop.
   MainEventsCleared,
+  /// Emitted when the app is activated again while it is running, e.g. by clicking its dock icon.
+  ///
+  /// An app keeping running without windows can recreate its main window when `has_visible_windows` is `false`:
+  ///
+  /// ```,no_run
+  /// let app = tauri::Builder::default()
+  ///   // on an actual app, remove the string argument
+  ///   .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+  ///   .expect("error while building tauri application");
+  /// app.run(|app_handle, event| match event {
+  ///   #[cfg(target_os = "macos")]
+  ///   tauri::RunEvent::Reopen { has_visible_windows, .. } if !has_visible_windows => {
+  ///     tauri::WindowBuilder::new(app_handle, "main", tauri::WindowUrl::default())
+  ///       .build()
+  ///       .unwrap();
+  ///   }
+  ///   _ => {}
+  /// });
+  /// ```
+  #[cfg(target_os = "macos")]
+  #[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
+  #[non_exhaustive]
+  Reopen {
+    /// Whether the app has visible windows.
+    has_visible_windows: bool,
+  },
+  /// The memory pressure of the system changed, e.g. the app should release its caches
+  /// and call [`Window::trim_memory`] when the system is low on memory.
+  ///
+  /// The event is also emitted to the webviews as `tauri://memory-pressure`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows:** The available physical memory is polled, the pressure is a warning under 10% and critical under 5%.
+  /// - **iOS / Android:** Unsupported.
+  #[non_exhaustive]
+  MemoryPressure {
+    /// How low the system is on memory.
+    level: crate::process::MemoryPressureLevel,
+  },
+  /// The app lifecycle changed on mobile, e.g. the app should persist its state when it moves to the background.
+  ///
+  /// The event is also emitted to the webviews as `tauri://lifecycle`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / Windows / macOS:** Unsupported.
+  Lifecycle(LifecycleEvent),
+  /// A push notification or a new push token was received on mobile, see the [`crate::push`] module.
+  ///
+  /// The event is also emitted to the webviews as `tauri://push`.
+  Push(crate::push::PushEvent),
+  /// A purchase completed outside of [`crate::purchases::purchase`] was received on mobile,
+  /// see the [`crate::purchases`] module.
+  ///
+  /// The event is also emitted to the webviews as `tauri://purchase`.
+  Purchase(crate::purchases::Purchase),
+  /// A shortcut of the app was activated, see the [`crate::shortcuts`] module.
+  ///
+  /// The event is also emitted to the webviews as `tauri://shortcut`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS:** Unsupported.
+  ShortcutActivated {
+    /// The identifier of the shortcut.
+    id: String,
+  },
+  /// The frontend assets archive enabled by `tauri > externalAssets` is missing or was tampered with,
+  /// so the app runs without frontend assets. Emitted once after [`RunEvent::Ready`].
+  ///
+  /// The app can e.g. show an error dialog and exit, or download a new archive.
+  #[cfg(feature = "external-assets")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "external-assets")))]
+  #[non_exhaustive]
+  AssetVerificationFailed {
+    /// The path of the archive.
+    path: std::path::PathBuf,
+    /// Why the archive failed to load.
+    reason: String,
+  },
 }
 
 impl From<EventLoopMessage> for RunEvent {
   fn from(event: EventLoopMessage) -> Self {
-    match event {}
+    match event {
+      EventLoopMessage::MemoryPressure(level) => Self::MemoryPressure { level },
+      EventLoopMessage::Lifecycle(event) => Self::Lifecycle(event),
+      EventLoopMessage::Push(event) => Self::Push(event),
+      EventLoopMessage::Purchase(purchase) => Self::Purchase(purchase),
+      EventLoopMessage::Shortcut(id) => Self::ShortcutActivated { id },
+      #[cfg(feature = "external-assets")]
+      EventLoopMessage::AssetVerificationFailed(path, reason) => {
+        Self::AssetVerificationFailed { path, reason }
+      }
+    }
   }
 }
 
@@ -328,6 +431,13 @@ impl<R: Runtime> AppHandle<R> {
   ///   });
   /// ```
   pub fn plugin<P: Plugin<R> + 'static>(&self, mut plugin: P) -> crate::Result<()> {
+    self
+      .manager()
+      .inner
+      .plugins
+      .lock()
+      .unwrap()
+      .check_dependencies(&plugin)?;
     plugin
       .initialize(
         self,
@@ -385,6 +495,28 @@ impl<R: Runtime> AppHandle<R> {
       .unregister(plugin)
   }
 
+  /// Changes the configuration of a plugin at runtime, running its [`on_config_changed`](Plugin::on_config_changed) hook.
+  ///
+  /// The value replaces the plugin entry of the `plugins` object of the configuration.
+  ///
+  /// # Examples
+  /// ```
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.handle().reconfigure_plugin("log", serde_json::json!({ "level": "debug" }))?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn reconfigure_plugin(&self, plugin: &str, config: JsonValue) -> crate::Result<()> {
+    self
+      .manager()
+      .inner
+      .plugins
+      .lock()
+      .unwrap()
+      .reconfigure(self, plugin, config)
+  }
+
   /// Exits the app. This is the same as [`std::process::exit`], but it performs cleanup on this application.
   pub fn exit(&self, exit_code: i32) {
     self.cleanup_before_exit();
@@ -563,6 +695,343 @@ macro_rules! shared_app_impl {
         self.manager.package_info()
       }
 
+      /// Gets the app's display name for the given BCP-47 language tag (e.g. `fr-FR`),
+      /// as configured on `tauri > bundle > localizations`.
+      ///
+      /// Use the `os` plugin to get the current locale.
+      /// Falls back to the package name when the locale has no localized name.
+      pub fn localized_name(&self, locale: &str) -> String {
+        self
+          .manager
+          .config()
+          .tauri
+          .bundle
+          .localization(locale)
+          .and_then(|localization| localization.product_name.clone())
+          .unwrap_or_else(|| self.manager.package_info().name.clone())
+      }
+
+      /// Gets the state returned by the [`Builder::on_hot_restart`] hook before `tauri dev` restarted the app.
+      ///
+      /// Returns `None` if the app was not restarted by `tauri dev` or if the state is not a valid `T`.
+      pub fn hot_restart_state<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self
+          .try_state::<crate::hot_restart::HotRestart>()?
+          .app_state()
+      }
+
+      /// Gets the startup timeline of the app.
+      ///
+      /// # Examples
+      /// ```
+      /// use tauri::Manager;
+      ///
+      /// tauri::Builder::default()
+      ///   .on_page_load(|window, _payload| {
+      ///     println!("{}", window.app_handle().startup_metrics());
+      ///   });
+      /// ```
+      pub fn startup_metrics(&self) -> StartupMetrics {
+        self.state::<StartupTimeline>().metrics()
+      }
+
+      /// Measures the memory and CPU usage of the app process and of the processes running its webviews.
+      ///
+      /// # Examples
+      /// ```
+      /// #[tauri::command]
+      /// async fn memory_usage(app: tauri::AppHandle) -> u64 {
+      ///   app.memory_info().await.total()
+      /// }
+      /// ```
+      pub async fn memory_info(&self) -> crate::process::MemoryInfo {
+        #[allow(unused_mut)]
+        let mut info = crate::process::MemoryInfo {
+          app: crate::process::metrics::measure(
+            std::process::id(),
+            crate::process::ProcessKind::App,
+          ),
+          ..Default::default()
+        };
+        #[cfg(feature = "wry")]
+        for (label, window) in self.windows() {
+          if let Ok(processes) = window.webview_processes().await {
+            info.webviews.insert(label, processes);
+          }
+        }
+        info
+      }
+
+      /// Gets the window preloaded when the app started, configured with `preload` on `tauri > windows`.
+      ///
+      /// The window is hidden and its page is already loaded, so showing it is instant.
+      /// Returns `None` if the window is not preloaded or if it was destroyed.
+      ///
+      /// # Examples
+      /// ```
+      /// #[tauri::command]
+      /// fn open_settings(app: tauri::AppHandle) -> Result<(), String> {
+      ///   let window = app.get_preloaded_window("settings").ok_or("settings window not preloaded")?;
+      ///   window.show().map_err(|e| e.to_string())?;
+      ///   window.set_focus().map_err(|e| e.to_string())
+      /// }
+      /// ```
+      #[cfg(desktop)]
+      pub fn get_preloaded_window(&self, label: &str) -> Option<Window<R>> {
+        if self
+          .state::<crate::window::preload::PreloadedWindows>()
+          .contains(label)
+        {
+          self.get_window(label)
+        } else {
+          None
+        }
+      }
+
+      /// Closes the splashscreen configured on `tauri > splashscreen` and shows the windows it hid,
+      /// once the splashscreen has been shown for its minimum display time.
+      ///
+      /// Does nothing if the app has no splashscreen or if it is already closed.
+      ///
+      /// # Examples
+      /// ```
+      /// use tauri::Manager;
+      ///
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     let handle = app.handle();
+      ///     tauri::async_runtime::spawn(async move {
+      ///       // load the data of the app...
+      ///       handle.close_splashscreen();
+      ///     });
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn close_splashscreen(&self) {
+        crate::splashscreen::close(self);
+      }
+
+      /// Asks the user for the permission to show notifications and registers the app for push notifications,
+      /// returning the token the app server sends the notifications to, see the [`crate::push`] module.
+      ///
+      /// This blocks until the platform returns the token, so it must not be called on the main thread.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Linux / Windows / macOS:** Unsupported, returns [`crate::Error::PushNotificationsUnavailable`].
+      pub fn register_for_push_notifications(&self) -> crate::Result<String> {
+        crate::push::register(self)
+      }
+
+      /// The push notification tapped by the user to launch the app, if any.
+      pub fn launch_notification(&self) -> Option<crate::push::PushNotification> {
+        crate::push::launch_notification()
+      }
+
+      /// Adds a script injected in the windows created afterwards, see [`Builder::initialization_script`].
+      ///
+      /// The windows that already exist are not affected.
+      ///
+      /// # Examples
+      /// ```
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     let id = app.add_initialization_script(tauri::Script::new("console.log('hello')").once());
+      ///     app.remove_initialization_script(id);
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn add_initialization_script(&self, script: impl Into<Script>) -> ScriptId {
+        self.state::<AppScripts>().add(script.into())
+      }
+
+      /// Removes a script added with [`Builder::initialization_script`] or [`Self::add_initialization_script`]
+      /// from the windows created afterwards. Returns `false` if the script was already removed.
+      pub fn remove_initialization_script(&self, id: ScriptId) -> bool {
+        self.state::<AppScripts>().remove(id)
+      }
+
+      /// Removes the cached responses of a command using the `cache` attribute of [`crate::command`],
+      /// e.g. after the data it reads changed.
+      ///
+      /// The responses are removed for every window.
+      /// Plugin commands use the `plugin:<plugin-name>|<command>` format, e.g. `plugin:store|get`.
+      ///
+      /// # Examples
+      /// ```
+      /// #[tauri::command(cache = "30s")]
+      /// fn settings() -> Vec<String> {
+      ///   Vec::new()
+      /// }
+      ///
+      /// #[tauri::command]
+      /// fn save_settings(app: tauri::AppHandle, settings: Vec<String>) {
+      ///   // save the settings
+      ///   app.invalidate_command_cache("settings");
+      /// }
+      /// ```
+      pub fn invalidate_command_cache(&self, command: &str) {
+        self.state::<Arc<CommandCache>>().invalidate(command);
+      }
+
+      /// Removes the cached responses of all commands.
+      pub fn clear_command_cache(&self) {
+        self.state::<Arc<CommandCache>>().clear();
+      }
+
+      /// Whether a screen reader is running, so the app can adapt, e.g. by announcing its updates with [`Window::announce`].
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Linux:** Reads the screen reader setting of GNOME, `false` on the other desktops.
+      /// - **Android / iOS:** Unsupported, returns `false`.
+      pub fn is_screen_reader_active(&self) -> bool {
+        crate::accessibility::screen_reader_active()
+      }
+
+      /// The state of the Local Network permission, see the [`crate::local_network`] module.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **macOS / iOS:** Probing the permission accesses the local network, which shows the prompt when the user was not asked yet.
+      /// - **Windows / Linux / Android:** There is no such permission, returns [`LocalNetworkPermission::Granted`](crate::local_network::LocalNetworkPermission::Granted).
+      pub fn local_network_permission(&self) -> crate::local_network::LocalNetworkPermission {
+        crate::local_network::state()
+      }
+
+      /// Shows the Local Network permission prompt if the user was not asked yet,
+      /// and waits up to the timeout for the user to grant the access.
+      ///
+      /// This blocks the current thread, so it should be called from an async command or a separate thread.
+      ///
+      /// # Examples
+      ///
+      /// ```
+      /// use std::time::Duration;
+      /// use tauri::local_network::LocalNetworkPermission;
+      ///
+      /// #[tauri::command]
+      /// async fn enable_discovery(app: tauri::AppHandle) -> bool {
+      ///   app.request_local_network_permission(Duration::from_secs(30)) == LocalNetworkPermission::Granted
+      /// }
+      /// ```
+      pub fn request_local_network_permission(
+        &self,
+        timeout: std::time::Duration,
+      ) -> crate::local_network::LocalNetworkPermission {
+        crate::local_network::request(timeout)
+      }
+
+      /// Replaces the jump list of the app on the Windows taskbar. A click on an item runs the app with the item arguments.
+      ///
+      /// See the [`crate::jump_list`] module for how the click reaches the running app.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **macOS / Linux / Android / iOS:** Unsupported, does nothing.
+      ///
+      /// # Examples
+      ///
+      /// ```
+      /// use tauri::jump_list::{JumpListCategory, JumpListItem};
+      ///
+      /// tauri::Builder::default().setup(|app| {
+      ///   app.set_jump_list(vec![JumpListCategory::tasks(vec![
+      ///     JumpListItem::new("New window").arguments(["--new-window"]),
+      ///   ])])?;
+      ///   Ok(())
+      /// });
+      /// ```
+      pub fn set_jump_list(&self, categories: Vec<crate::jump_list::JumpListCategory>) -> crate::Result<()> {
+        crate::jump_list::set(&categories)
+      }
+
+      /// Replaces the shortcuts of the app: the app shortcuts on Android, the Home Screen quick actions on iOS
+      /// and the tasks of the jump list on Windows.
+      ///
+      /// Activating a shortcut emits [`RunEvent::ShortcutActivated`], see the [`crate::shortcuts`] module.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Windows:** Replaces the jump list set with [`Self::set_jump_list`].
+      /// - **Android:** The launcher shows at most 4 shortcuts, including the static ones.
+      /// - **Linux / macOS:** Unsupported, does nothing.
+      ///
+      /// # Examples
+      ///
+      /// ```
+      /// use tauri::shortcuts::Shortcut;
+      ///
+      /// tauri::Builder::default().setup(|app| {
+      ///   app.set_shortcuts(vec![Shortcut::new("compose", "New note").icon("ic_compose")])?;
+      ///   Ok(())
+      /// });
+      /// ```
+      pub fn set_shortcuts(&self, shortcuts: Vec<crate::shortcuts::Shortcut>) -> crate::Result<()> {
+        crate::shortcuts::set(self, shortcuts)
+      }
+
+      /// The identifier of the shortcut activated to launch the app, if any.
+      pub fn launch_shortcut(&self) -> Option<String> {
+        crate::shortcuts::launch_shortcut()
+      }
+
+      /// Schedules a periodic background task, replacing its previous schedule, and calls `handler` on each run.
+      ///
+      /// The schedule persists across the launches of the app, which must register the handler again on each launch,
+      /// usually in [`Builder::setup`]. See the [`crate::background_tasks`] module.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Linux / Windows / macOS:** Unsupported, returns [`crate::Error::BackgroundTasksUnavailable`].
+      ///
+      /// # Examples
+      ///
+      /// ```
+      /// use std::time::Duration;
+      /// use tauri::background_tasks::BackgroundTask;
+      ///
+      /// tauri::Builder::default().setup(|app| {
+      ///   let task = BackgroundTask::new("com.tauri.app.sync", Duration::from_secs(60 * 60)).requires_network(true);
+      ///   let _ = app.schedule_background_task(task, |completion| {
+      ///     // sync the data, then report the outcome to the system
+      ///     completion.complete(true);
+      ///   });
+      ///   Ok(())
+      /// });
+      /// ```
+      pub fn schedule_background_task<F: Fn(crate::background_tasks::TaskCompletion) + Send + Sync + 'static>(
+        &self,
+        task: crate::background_tasks::BackgroundTask,
+        handler: F,
+      ) -> crate::Result<()> {
+        crate::background_tasks::schedule(self, task, handler)
+      }
+
+      /// Cancels the periodic background task with the given identifier.
+      pub fn cancel_background_task(&self, id: &str) -> crate::Result<()> {
+        crate::background_tasks::cancel(self, id)
+      }
+
+      /// Adds the file to the recent documents of the app, listed by the dock menu on macOS,
+      /// the jump list on Windows and the file choosers on Linux. Clicking one opens it with the app.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Android / iOS:** Unsupported, does nothing.
+      pub fn add_recent_document<P: AsRef<std::path::Path>>(&self, path: P) -> crate::Result<()> {
+        crate::recent_documents::add(&self.app_handle(), path.as_ref())
+      }
+
+      /// Clears the recent documents of the app.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Android / iOS:** Unsupported, does nothing.
+      pub fn clear_recent_documents(&self) -> crate::Result<()> {
+        crate::recent_documents::clear(&self.app_handle())
+      }
+
       /// The application's asset resolver.
       pub fn asset_resolver(&self) -> AssetResolver<R> {
         AssetResolver {
@@ -576,24 +1045,41 @@ macro_rules! shared_app_impl {
       }
 
       /// Shows the application, but does not automatically focus it.
-      #[cfg(target_os = "macos")]
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Windows / Linux / Android / iOS:** Shows all the windows of the app, which adds them back to the taskbar.
       pub fn show(&self) -> crate::Result<()> {
+        #[cfg(target_os = "macos")]
         match self.runtime() {
           RuntimeOrDispatch::Runtime(r) => r.show(),
           RuntimeOrDispatch::RuntimeHandle(h) => h.show()?,
           _ => unreachable!(),
         }
+        #[cfg(not(target_os = "macos"))]
+        for window in self.manager.windows().values() {
+          window.show()?;
+        }
         Ok(())
       }
 
-      /// Hides the application.
-      #[cfg(target_os = "macos")]
+      /// Hides the application, which removes it from the taskbar while it keeps running,
+      /// e.g. in the system tray with the `tauri > keepRunningWithoutWindows` configuration.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Windows / Linux / Android / iOS:** Hides all the windows of the app.
       pub fn hide(&self) -> crate::Result<()> {
+        #[cfg(target_os = "macos")]
         match self.runtime() {
           RuntimeOrDispatch::Runtime(r) => r.hide(),
           RuntimeOrDispatch::RuntimeHandle(h) => h.hide()?,
           _ => unreachable!(),
         }
+        #[cfg(not(target_os = "macos"))]
+        for window in self.manager.windows().values() {
+          window.hide()?;
+        }
         Ok(())
       }
     }
@@ -607,6 +1093,68 @@ impl<R: Runtime> App<R> {
   fn register_core_plugins(&self) -> crate::Result<()> {
     self.handle.plugin(crate::path::init())?;
     self.handle.plugin(crate::event::init())?;
+    self.handle.plugin(crate::ipc::init())?;
+    self.handle.plugin(crate::config::init())?;
+    self.handle.plugin(crate::window::keyboard::init())?;
+    self.handle.plugin(crate::splashscreen::init())?;
+    self.handle.plugin(crate::push::init())?;
+    self.handle.plugin(crate::purchases::init())?;
+    self.handle.plugin(crate::shortcuts::init())?;
+    self.handle.plugin(crate::background_tasks::init())?;
+    self.handle.plugin(crate::widgets::init())?;
+    #[cfg(desktop)]
+    self.handle.plugin(crate::window::title_bar::init())?;
+    self.handle.plugin(crate::window::system_bars::init())?;
+    // the device plugins are only registered when their allowlist enables them
+    #[cfg(any(
+      feature = "haptics",
+      feature = "sensors",
+      feature = "hid",
+      feature = "serial",
+      feature = "bluetooth",
+      feature = "usb",
+      feature = "keyring",
+      feature = "auth",
+      feature = "share"
+    ))]
+    let security = self.config().tauri.security.clone();
+    #[cfg(feature = "haptics")]
+    if security.haptics {
+      self.handle.plugin(crate::api::haptics::init())?;
+    }
+    #[cfg(feature = "sensors")]
+    if security.sensors {
+      self.handle.plugin(crate::api::sensors::init())?;
+    }
+    #[cfg(feature = "hid")]
+    if security.hid {
+      self.handle.plugin(crate::api::hid::init())?;
+    }
+    #[cfg(feature = "serial")]
+    if security.serial.enable {
+      self.handle.plugin(crate::api::serial::init())?;
+    }
+    #[cfg(feature = "bluetooth")]
+    if security.bluetooth {
+      self.handle.plugin(crate::api::bluetooth::init())?;
+    }
+    #[cfg(feature = "usb")]
+    if security.usb.enable {
+      self.handle.plugin(crate::api::usb::init())?;
+    }
+    #[cfg(feature = "crypto")]
+    self.handle.plugin(crate::api::crypto::init())?;
+    // the Rust API is available with the feature, the allowlist only enables its commands
+    #[cfg(feature = "keyring")]
+    self
+      .handle
+      .plugin(crate::api::keyring::init(security.keyring))?;
+    #[cfg(feature = "auth")]
+    self.handle.plugin(crate::api::auth::init(security.auth))?;
+    #[cfg(feature = "share")]
+    self
+      .handle
+      .plugin(crate::api::share::init(security.share.enable))?;
     Ok(())
   }
 
@@ -775,9 +1323,21 @@ pub struct Builder<R: Runtime> {
   /// Page load hook.
   on_page_load: Box<OnPageLoad<R>>,
 
+  /// The hook saving the state to restore after `tauri dev` restarts the app.
+  on_hot_restart: Option<Box<OnHotRestart<R>>>,
+
   /// windows to create when starting up.
   pending_windows: Vec<PendingWindow<EventLoopMessage, R>>,
 
+  /// The scripts injected in all windows.
+  initialization_scripts: Vec<Script>,
+
+  /// The payload schemas of the events, validated in debug builds.
+  event_schemas: EventSchemas,
+
+  /// When the builder was created, the start of the [`StartupMetrics`] timeline.
+  startup: Instant,
+
   /// All passed plugins
   plugins: PluginStore<R>,
 
@@ -810,6 +1370,13 @@ pub struct Builder<R: Runtime> {
 
   /// The device event filter.
   device_event_filter: DeviceEventFilter,
+
+  /// The consent prompt for optional plugin permissions.
+  permission_prompt: Option<Box<ConsentPrompt>>,
+
+  /// The directory of the dynamic plugins and the public key of their signatures.
+  #[cfg(feature = "dynamic-plugins")]
+  dynamic_plugins: Option<(std::path::PathBuf, String)>,
 }
 
 impl<R: Runtime> Builder<R> {
@@ -824,7 +1391,11 @@ impl<R: Runtime> Builder<R> {
       invoke_initialization_script:
         format!("Object.defineProperty(window, '__TAURI_POST_MESSAGE__', {{ value: (message) => window.ipc.postMessage({}(message)) }})", crate::manager::STRINGIFY_IPC_MESSAGE_FN),
       on_page_load: Box::new(|_, _| ()),
+      on_hot_restart: None,
       pending_windows: Default::default(),
+      initialization_scripts: Vec::new(),
+      event_schemas: Default::default(),
+      startup: Instant::now(),
       plugins: PluginStore::default(),
       uri_scheme_protocols: Default::default(),
       state: StateManager::new(),
@@ -837,6 +1408,9 @@ impl<R: Runtime> Builder<R> {
       #[cfg(all(desktop, feature = "system-tray"))]
       system_tray_event_listeners: Vec::new(),
       device_event_filter: Default::default(),
+      permission_prompt: None,
+      #[cfg(feature = "dynamic-plugins")]
+      dynamic_plugins: None,
     }
   }
 
@@ -923,6 +1497,140 @@ impl<R: Runtime> Builder<R> {
     self
   }
 
+  /// Adds a script injected in the windows, including the windows defined in the configuration.
+  ///
+  /// The app scripts run after the Tauri scripts and before the window scripts, in insertion order.
+  /// Use [`Script::windows`] to only inject it in some windows.
+  ///
+  /// # Examples
+  /// ```
+  /// tauri::Builder::default()
+  ///   .initialization_script("window.__MY_CUSTOM_PROPERTY__ = { foo: 'bar' }")
+  ///   .initialization_script(tauri::Script::new("console.log('main window')").windows(["main"]));
+  /// ```
+  #[must_use]
+  pub fn initialization_script(mut self, script: impl Into<Script>) -> Self {
+    self.initialization_scripts.push(script.into());
+    self
+  }
+
+  /// Registers the payload type of an event.
+  ///
+  /// In debug builds the payloads of the event emitted from Rust and from the frontend must deserialize into `T`:
+  /// emitting another payload fails with an error naming the event and the field, and the frontend `emit` call is rejected.
+  /// Payloads are not validated in release builds.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the event name is invalid.
+  ///
+  /// # Examples
+  /// ```
+  /// #[derive(serde::Deserialize)]
+  /// struct DownloadProgress {
+  ///   id: String,
+  ///   percent: u8,
+  /// }
+  ///
+  /// tauri::Builder::default()
+  ///   .event_schema::<DownloadProgress>("download-progress");
+  /// ```
+  #[must_use]
+  pub fn event_schema<T: serde::de::DeserializeOwned>(mut self, event: impl Into<String>) -> Self {
+    let event = event.into();
+    assert_event_name_is_valid(&event);
+    self.event_schemas.register::<T>(event);
+    self
+  }
+
+  /// Defines the hook called before `tauri dev` restarts the app because its Rust code changed.
+  ///
+  /// The returned value is saved to disk and can be read after the restart with [`AppHandle::hot_restart_state`].
+  /// The hook runs on a separate thread and is never called outside of `tauri dev`.
+  ///
+  /// The frontend can save the state of its windows as well, see the `@tauri-apps/api/hotRestart` module.
+  ///
+  /// # Examples
+  /// ```
+  /// use std::sync::Mutex;
+  /// use tauri::Manager;
+  ///
+  /// struct Counter(Mutex<u32>);
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let count = app.hot_restart_state::<u32>().unwrap_or_default();
+  ///     app.manage(Counter(Mutex::new(count)));
+  ///     Ok(())
+  ///   })
+  ///   .on_hot_restart(|app| *app.state::<Counter>().0.lock().unwrap());
+  /// ```
+  #[must_use]
+  pub fn on_hot_restart<T, F>(mut self, on_hot_restart: F) -> Self
+  where
+    T: serde::Serialize,
+    F: Fn(&AppHandle<R>) -> T + Send + Sync + 'static,
+  {
+    self.on_hot_restart = Some(Box::new(move |app: &AppHandle<R>| {
+      serde_json::to_value(on_hot_restart(app)).unwrap_or_default()
+    }));
+    self
+  }
+
+  /// Defines the consent prompt for optional plugin permissions.
+  ///
+  /// The handler is called on the main thread when a window invokes a command of an optional permission
+  /// that was not granted yet, and returns whether the user granted the permission.
+  /// Without a handler, the commands of optional permissions are rejected until the permission is granted with [`PluginPermissions::grant`].
+  ///
+  /// # Examples
+  /// ```
+  /// tauri::Builder::default()
+  ///   .on_permission_request(|request| {
+  ///     println!(
+  ///       "window {} requested the {} permission of the {} plugin",
+  ///       request.window, request.permission.identifier, request.plugin
+  ///     );
+  ///     false
+  ///   });
+  /// ```
+  ///
+  /// [`PluginPermissions::grant`]: crate::permissions::PluginPermissions::grant
+  #[must_use]
+  pub fn on_permission_request<F>(mut self, prompt: F) -> Self
+  where
+    F: Fn(&PermissionRequest) -> bool + Send + Sync + 'static,
+  {
+    self.permission_prompt.replace(Box::new(prompt));
+    self
+  }
+
+  /// Loads the plugins of the shared libraries in the given directory when the app is built.
+  ///
+  /// Each library must be signed with the Ed25519 key of the base64 encoded `public_key`,
+  /// otherwise [`Builder::build`] fails. A dynamic plugin can't replace a plugin registered with [`Builder::plugin`].
+  /// See [`crate::plugin::dynamic`] for the library format.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default()
+  ///   .dynamic_plugins("/opt/my-app/plugins", "BASE64_ENCODED_PUBLIC_KEY");
+  /// ```
+  #[cfg(feature = "dynamic-plugins")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "dynamic-plugins")))]
+  #[must_use]
+  pub fn dynamic_plugins<P: Into<std::path::PathBuf>>(
+    mut self,
+    directory: P,
+    public_key: &str,
+  ) -> Self {
+    self
+      .dynamic_plugins
+      .replace((directory.into(), public_key.into()));
+    self
+  }
+
   /// Adds a Tauri application plugin.
   ///
   /// A plugin is created using the [`crate::plugin::Builder`] struct.Check its documentation for more information.
@@ -1268,11 +1976,66 @@ impl<R: Runtime> Builder<R> {
   /// Builds the application.
   #[allow(clippy::type_complexity)]
   pub fn build<A: Assets>(mut self, context: Context<A>) -> crate::Result<App<R>> {
+    let timeline = StartupTimeline::new(self.startup);
+    timeline.record(StartupPhase::Config);
+    self.state.set(timeline);
+
+    #[cfg(feature = "external-assets")]
+    let asset_verification_failure = context.asset_verification_failure.clone();
+
+    let app_scripts = AppScripts::default();
+    for script in self.initialization_scripts.drain(..) {
+      app_scripts.add(script);
+    }
+    self.state.set(app_scripts);
+    self.state.set(std::mem::take(&mut self.event_schemas));
+    self.state.set(Arc::new(CommandCache::default()));
+    self
+      .state
+      .set(crate::shared_buffer::SharedBuffers::default());
+    self.state.set(crate::blocking_pool::BlockingPool::new(
+      &context.config.tauri.blocking_pool,
+    ));
+
     #[cfg(target_os = "macos")]
     if self.menu.is_none() && self.enable_macos_default_menu {
       self.menu = Some(Menu::os_default(&context.package_info().name));
     }
 
+    #[cfg(feature = "dynamic-plugins")]
+    if let Some((directory, public_key)) = self.dynamic_plugins.take() {
+      for plugin in crate::plugin::dynamic::load_dir(&directory, &public_key)? {
+        let name = crate::plugin::Plugin::<R>::name(&plugin);
+        if self.plugins.contains(name) {
+          return Err(crate::Error::DynamicPlugin(
+            directory,
+            format!("the plugin `{name}` is already registered"),
+          ));
+        }
+        self.plugins.register(plugin);
+      }
+    }
+
+    #[cfg(desktop)]
+    {
+      let preloaded_windows = crate::window::preload::PreloadedWindows::default();
+      for config in context.config.tauri.windows.iter().filter(|c| c.preload) {
+        preloaded_windows.insert(config.label.clone());
+      }
+      self.state.set(preloaded_windows);
+    }
+
+    if let Some(splashscreen) = context.config.tauri.splashscreen.clone() {
+      self
+        .state
+        .set(crate::splashscreen::Splashscreen::new(splashscreen));
+    }
+
+    self.state.set(PluginPermissions::new(
+      context.plugin_permissions.clone(),
+      self.permission_prompt.take(),
+    ));
+
     let manager = WindowManager::with_handlers(
       context,
       self.plugins,
@@ -1286,9 +2049,26 @@ impl<R: Runtime> Builder<R> {
     );
 
     // set up all the windows defined in the config
-    for config in manager.config().tauri.windows.clone() {
+    let mut window_configs = manager.config().tauri.windows.clone();
+    // the preloaded windows load their page hidden until they are shown
+    for config in window_configs.iter_mut().filter(|c| c.preload) {
+      config.visible = false;
+    }
+    // the splashscreen is created first and the other windows are hidden until it closes
+    if let Some(splashscreen) = manager
+      .state()
+      .try_get::<crate::splashscreen::Splashscreen>()
+    {
+      let splashscreen = splashscreen.window_config(&mut window_configs);
+      window_configs.insert(0, splashscreen);
+    }
+    for config in window_configs {
       let label = config.label.clone();
-      let webview_attributes = WebviewAttributes::from(&config);
+      let mut webview_attributes = WebviewAttributes::from(&config);
+      if label == crate::splashscreen::SPLASHSCREEN_LABEL {
+        webview_attributes =
+          webview_attributes.initialization_script(crate::splashscreen::SPLASHSCREEN_SCRIPT);
+      }
       self.pending_windows.push(PendingWindow::with_config(
         config,
         webview_attributes,
@@ -1296,6 +2076,18 @@ impl<R: Runtime> Builder<R> {
       )?);
     }
 
+    // GTK uses the program name as the app_id of the Wayland windows and the WM_CLASS of the X11 windows
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    if let Some(app_id) = &manager.config().tauri.linux.app_id {
+      glib::set_prgname(Some(app_id.as_str()));
+    }
+
     #[cfg(any(windows, target_os = "linux"))]
     let mut runtime = if self.runtime_any_thread {
       R::new_any_thread()?
@@ -1306,6 +2098,7 @@ impl<R: Runtime> Builder<R> {
     let mut runtime = R::new()?;
 
     runtime.set_device_event_filter(self.device_event_filter);
+    startup::record(&manager, StartupPhase::RuntimeInit);
 
     let runtime_handle = runtime.handle();
 
@@ -1322,6 +2115,28 @@ impl<R: Runtime> Builder<R> {
     };
 
     app.register_core_plugins()?;
+    app
+      .handle
+      .plugin(crate::hot_restart::init(self.on_hot_restart))?;
+
+    #[cfg(desktop)]
+    crate::process::memory::watch(&app.handle);
+    #[cfg(mobile)]
+    lifecycle::listen(&app.handle);
+
+    #[cfg(feature = "external-assets")]
+    if let Some((path, reason)) = asset_verification_failure {
+      use crate::runtime::{EventLoopProxy, RuntimeHandle};
+      let _ = app
+        .handle
+        .runtime_handle
+        .create_proxy()
+        .send_event(EventLoopMessage::AssetVerificationFailed(path, reason));
+    }
+
+    if let Ok(local_data_dir) = app.path().app_local_data_dir() {
+      crate::async_runtime::set_schedule_store(local_data_dir.join("schedules.json"));
+    }
 
     let env = Env::default();
     app.manage(env);
@@ -1332,6 +2147,11 @@ impl<R: Runtime> Builder<R> {
       asset_protocol: FsScope::for_fs_api(&app, &app.config().tauri.security.asset_protocol.scope)?,
     });
 
+    #[cfg(feature = "protocol-asset")]
+    if app.config().tauri.security.asset_protocol.persist_scope {
+      crate::scope::persist_asset_protocol_scope(&app)?;
+    }
+
     #[cfg(windows)]
     {
       if let crate::utils::config::WebviewInstallMode::FixedRuntime { path } = &app
@@ -1417,6 +2237,10 @@ fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
         .manager
         .prepare_window(app.handle.clone(), pending, &window_labels)?;
       let window_effects = pending.webview_attributes.window_effects.clone();
+      #[cfg(desktop)]
+      let title_bar_overlay = pending.webview_attributes.title_bar_overlay.clone();
+      #[cfg(desktop)]
+      let kiosk = pending.webview_attributes.kiosk;
       let detached = if let RuntimeOrDispatch::RuntimeHandle(runtime) = app.handle().runtime() {
         runtime.create_window(pending)?
       } else {
@@ -1428,8 +2252,24 @@ fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
       if let Some(effects) = window_effects {
         crate::vibrancy::set_window_effects(&window, Some(effects))?;
       }
+      #[cfg(desktop)]
+      if let Some(overlay) = title_bar_overlay {
+        crate::window::title_bar::attach(&window, overlay)?;
+      }
+      #[cfg(desktop)]
+      if kiosk {
+        crate::window::kiosk::attach(&window)?;
+      }
+      #[cfg(desktop)]
+      if app
+        .state::<crate::window::preload::PreloadedWindows>()
+        .contains(window.label())
+      {
+        crate::window::preload::attach(&window);
+      }
     }
   }
+  startup::record(&app.manager, StartupPhase::WindowCreation);
 
   if let Some(setup) = app.setup.take() {
     (setup)(app).map_err(|e| crate::Error::Setup(e.into()))?;
@@ -1454,9 +2294,15 @@ fn on_event_loop_event<R: Runtime, F: FnMut(&AppHandle<R>, RunEvent) + 'static>(
 
   let event = match event {
     RuntimeRunEvent::Exit => RunEvent::Exit,
-    RuntimeRunEvent::ExitRequested { tx } => RunEvent::ExitRequested {
-      api: ExitRequestApi(tx),
-    },
+    RuntimeRunEvent::ExitRequested { tx } => {
+      if manager.config().tauri.keep_running_without_windows {
+        // the last window was closed, the app only exits with `AppHandle::exit`
+        let _ = tx.send(ExitRequestedEventAction::Prevent);
+      }
+      RunEvent::ExitRequested {
+        api: ExitRequestApi(tx),
+      }
+    }
     RuntimeRunEvent::WindowEvent { label, event } => RunEvent::WindowEvent {
       label,
       event: event.into(),
@@ -1485,8 +2331,35 @@ fn on_event_loop_event<R: Runtime, F: FnMut(&AppHandle<R>, RunEvent) + 'static>(
       RunEvent::Ready
     }
     RuntimeRunEvent::Resumed => RunEvent::Resumed,
+    #[cfg(target_os = "macos")]
+    RuntimeRunEvent::Reopen {
+      has_visible_windows,
+    } => RunEvent::Reopen {
+      has_visible_windows,
+    },
     RuntimeRunEvent::MainEventsCleared => RunEvent::MainEventsCleared,
-    RuntimeRunEvent::UserEvent(t) => t.into(),
+    RuntimeRunEvent::UserEvent(t) => {
+      match &t {
+        EventLoopMessage::MemoryPressure(level) => {
+          let _ = app_handle.emit_all(crate::process::memory::MEMORY_PRESSURE_EVENT, level);
+        }
+        EventLoopMessage::Lifecycle(event) => {
+          let _ = app_handle.emit_all(lifecycle::LIFECYCLE_EVENT, event);
+        }
+        EventLoopMessage::Push(event) => {
+          let _ = app_handle.emit_all(crate::push::PUSH_EVENT, event);
+        }
+        EventLoopMessage::Purchase(purchase) => {
+          let _ = app_handle.emit_all(crate::purchases::PURCHASE_EVENT, purchase);
+        }
+        EventLoopMessage::Shortcut(id) => {
+          let _ = app_handle.emit_all(crate::shortcuts::SHORTCUT_EVENT, id);
+        }
+        #[cfg(feature = "external-assets")]
+        EventLoopMessage::AssetVerificationFailed(..) => {}
+      }
+      t.into()
+    }
     _ => unimplemented!(),
   };
 
@@ -0,0 +1,139 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The lifecycle of the app on mobile, emitted as [`crate::RunEvent::Lifecycle`].
+
+use crate::process::MemoryPressureLevel;
+
+use serde::Serialize;
+
+/// The event emitted to the webviews when the app lifecycle changes, with the [`LifecycleEvent`] as payload.
+pub(crate) const LIFECYCLE_EVENT: &str = "tauri://lifecycle";
+
+/// A transition of the app lifecycle on mobile.
+///
+/// The app should persist its state when it moves to the background, since the system can terminate it at any time afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum LifecycleEvent {
+  /// The app is about to be visible, e.g. when the user switches back to it.
+  ///
+  /// Android `onStart`, iOS `willEnterForeground`.
+  Foreground,
+  /// The app is in the foreground and receives user input.
+  ///
+  /// Android `onResume`, iOS scene phase `active`.
+  Active,
+  /// The app is still visible but no longer receives user input, e.g. during a phone call or in the app switcher.
+  ///
+  /// Android `onPause`, iOS scene phase `inactive`.
+  Inactive,
+  /// The app is no longer visible. It should persist its state and pause its work.
+  ///
+  /// Android `onStop`, iOS scene phase `background`.
+  Background,
+  /// The system is low on memory, the app should release its caches.
+  ///
+  /// Android `onTrimMemory`, iOS `didReceiveMemoryWarning`, which is always a [`MemoryPressureLevel::Warning`].
+  #[non_exhaustive]
+  LowMemory {
+    /// How low the system is on memory.
+    level: MemoryPressureLevel,
+  },
+}
+
+#[cfg(mobile)]
+pub(crate) use mobile::listen;
+#[cfg(target_os = "android")]
+pub use mobile::{handle_android_lifecycle_event, handle_android_trim_memory};
+
+#[cfg(mobile)]
+mod mobile {
+  use super::LifecycleEvent;
+  use crate::{
+    process::MemoryPressureLevel,
+    runtime::{EventLoopProxy, RuntimeHandle},
+    AppHandle, EventLoopMessage, Runtime,
+  };
+
+  use once_cell::sync::OnceCell;
+
+  use std::sync::Mutex;
+
+  type Sender = Box<dyn Fn(LifecycleEvent) + Send>;
+
+  /// Sends the lifecycle events received from the platform to the event loop.
+  static SENDER: OnceCell<Mutex<Sender>> = OnceCell::new();
+
+  // the event codes shared with the Kotlin and Swift code
+  const FOREGROUND: i32 = 0;
+  const ACTIVE: i32 = 1;
+  const INACTIVE: i32 = 2;
+  const BACKGROUND: i32 = 3;
+  const LOW_MEMORY: i32 = 4;
+
+  /// Android `ComponentCallbacks2` trim memory levels.
+  #[cfg(target_os = "android")]
+  const TRIM_MEMORY_RUNNING_CRITICAL: i32 = 15;
+  #[cfg(target_os = "android")]
+  const TRIM_MEMORY_UI_HIDDEN: i32 = 20;
+  #[cfg(target_os = "android")]
+  const TRIM_MEMORY_MODERATE: i32 = 60;
+
+  /// Forwards the lifecycle events of the platform to the event loop.
+  pub(crate) fn listen<R: Runtime>(app_handle: &AppHandle<R>) {
+    let proxy = app_handle.runtime_handle.create_proxy();
+    let _ = SENDER.set(Mutex::new(Box::new(move |event| {
+      let _ = proxy.send_event(EventLoopMessage::Lifecycle(event));
+    })));
+
+    #[cfg(target_os = "ios")]
+    {
+      extern "C" fn callback(event: std::os::raw::c_int) {
+        dispatch(event);
+      }
+      unsafe { crate::ios::observe_lifecycle_events(crate::ios::LifecycleEventCallback(callback)) };
+    }
+  }
+
+  fn dispatch(code: i32) {
+    let event = match code {
+      FOREGROUND => LifecycleEvent::Foreground,
+      ACTIVE => LifecycleEvent::Active,
+      INACTIVE => LifecycleEvent::Inactive,
+      BACKGROUND => LifecycleEvent::Background,
+      LOW_MEMORY => LifecycleEvent::LowMemory {
+        level: MemoryPressureLevel::Warning,
+      },
+      _ => return,
+    };
+    send(event);
+  }
+
+  fn send(event: LifecycleEvent) {
+    if let Some(sender) = SENDER.get() {
+      (sender.lock().unwrap())(event);
+    }
+  }
+
+  /// Glue between Rust and the Kotlin code that forwards the activity lifecycle.
+  #[cfg(target_os = "android")]
+  pub fn handle_android_lifecycle_event(event: i32) {
+    dispatch(event);
+  }
+
+  /// Glue between Rust and the Kotlin code that forwards the `onTrimMemory` calls.
+  #[cfg(target_os = "android")]
+  pub fn handle_android_trim_memory(level: i32) {
+    let level = match level {
+      // the UI is hidden, which is already notified as `Background`
+      TRIM_MEMORY_UI_HIDDEN => return,
+      TRIM_MEMORY_RUNNING_CRITICAL => MemoryPressureLevel::Critical,
+      level if level >= TRIM_MEMORY_MODERATE => MemoryPressureLevel::Critical,
+      _ => MemoryPressureLevel::Warning,
+    };
+    send(LifecycleEvent::LowMemory { level });
+  }
+}
@@ -20,6 +20,11 @@ pub use tokio::{
   task::JoinHandle as TokioJoinHandle,
 };
 
+mod schedule;
+
+pub(crate) use schedule::set_store as set_schedule_store;
+pub use schedule::{schedule, Schedule, ScheduledTask};
+
 use std::{
   future::Future,
   pin::Pin,
@@ -0,0 +1,414 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Recurring tasks run on the async runtime, see [`schedule`].
+
+use once_cell::sync::OnceCell;
+use rand::Rng;
+
+use std::{
+  collections::HashMap,
+  future::Future,
+  path::PathBuf,
+  sync::{Arc, Mutex},
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::JoinHandle;
+
+/// The monotonic clock used by the timers stops while the system sleeps,
+/// so the wall clock is checked again at least this often to run the tasks due during the sleep.
+const WAKE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How far a cron expression is searched for its next occurrence, e.g. `0 0 29 2 *` only matches on leap years.
+const CRON_SEARCH_YEARS: u64 = 5;
+
+/// The file storing the last run of the persisted schedules, set when the app is built.
+static STORE: OnceCell<PathBuf> = OnceCell::new();
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// When a task scheduled with [`schedule`] runs.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tauri::async_runtime::Schedule;
+///
+/// // every 15 minutes, spread over a minute so the clients of a server do not sync at once
+/// let sync = Schedule::every(Duration::from_secs(15 * 60))
+///   .jitter(Duration::from_secs(60))
+///   .persist("sync");
+/// // every day at 03:30 UTC
+/// let cleanup = Schedule::cron("30 3 * * *").unwrap().persist("cleanup");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schedule {
+  kind: ScheduleKind,
+  jitter: Duration,
+  id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum ScheduleKind {
+  Interval(Duration),
+  Cron(Cron),
+}
+
+impl Schedule {
+  /// Runs the task every `interval`, starting one interval after it is scheduled.
+  pub fn every(interval: Duration) -> Self {
+    Self {
+      kind: ScheduleKind::Interval(interval.max(Duration::from_millis(1))),
+      jitter: Duration::ZERO,
+      id: None,
+    }
+  }
+
+  /// Runs the task on the minutes matching the cron expression, evaluated in UTC.
+  ///
+  /// The expression has five fields: the minute, the hour, the day of the month, the month and the day of the week,
+  /// where Sunday is `0` or `7`. A field is `*` or a list of values and ranges, with an optional step,
+  /// e.g. `*/15`, `1-5` or `0,30`. The `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shortcuts are also supported.
+  pub fn cron(expression: &str) -> crate::Result<Self> {
+    let cron = Cron::parse(expression)
+      .map_err(|reason| crate::Error::InvalidCronExpression(expression.into(), reason))?;
+    Ok(Self {
+      kind: ScheduleKind::Cron(cron),
+      jitter: Duration::ZERO,
+      id: None,
+    })
+  }
+
+  /// Delays each run by a random duration up to `max`, spreading the load of the tasks scheduled at the same time.
+  #[must_use]
+  pub fn jitter(mut self, max: Duration) -> Self {
+    self.jitter = max;
+    self
+  }
+
+  /// Persists the last run of the task under the given unique identifier, so the schedule survives the app restarts:
+  /// a run missed while the app was not running happens as soon as the task is scheduled again.
+  ///
+  /// The last runs are stored in the app local data directory.
+  #[must_use]
+  pub fn persist(mut self, id: impl Into<String>) -> Self {
+    self.id.replace(id.into());
+    self
+  }
+
+  /// The next run after the previous one.
+  fn next(&self, previous: SystemTime) -> Option<SystemTime> {
+    let next = match &self.kind {
+      ScheduleKind::Interval(interval) => previous + *interval,
+      ScheduleKind::Cron(cron) => cron.next_after(previous)?,
+    };
+    if self.jitter.is_zero() {
+      Some(next)
+    } else {
+      let jitter = rand::thread_rng().gen_range(Duration::ZERO..=self.jitter);
+      Some(next + jitter)
+    }
+  }
+}
+
+impl From<Duration> for Schedule {
+  fn from(interval: Duration) -> Self {
+    Self::every(interval)
+  }
+}
+
+/// A task scheduled with [`schedule`].
+#[derive(Debug)]
+pub struct ScheduledTask(JoinHandle<()>);
+
+impl ScheduledTask {
+  /// Cancels the task. A run in progress is aborted at its next `.await`.
+  pub fn cancel(&self) {
+    self.0.abort();
+  }
+}
+
+/// Runs the task on the async runtime according to the schedule, until it is cancelled.
+///
+/// The task runs whether or not the app has windows, e.g. in an app living in the system tray.
+/// The schedule follows the wall clock, so a run due while the system was asleep happens when it wakes up,
+/// and on mobile when the app is resumed.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tauri::async_runtime::{schedule, Schedule};
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     schedule(Schedule::every(Duration::from_secs(3600)).persist("sync"), || async {
+///       // sync the local database...
+///     });
+///     Ok(())
+///   });
+/// ```
+pub fn schedule<S, F, Fut>(schedule: S, task: F) -> ScheduledTask
+where
+  S: Into<Schedule>,
+  F: Fn() -> Fut + Send + Sync + 'static,
+  Fut: Future<Output = ()> + Send + 'static,
+{
+  let schedule = schedule.into();
+  let task = Arc::new(task);
+  ScheduledTask(super::spawn(async move {
+    let mut previous = schedule
+      .id
+      .as_deref()
+      .and_then(last_run)
+      .unwrap_or_else(SystemTime::now);
+    while let Some(next) = schedule.next(previous) {
+      sleep_until(next).await;
+      task().await;
+      previous = SystemTime::now();
+      if let Some(id) = &schedule.id {
+        record_run(id, previous);
+      }
+    }
+  }))
+}
+
+/// Sleeps until the wall clock reaches the deadline.
+async fn sleep_until(deadline: SystemTime) {
+  while let Ok(remaining) = deadline.duration_since(SystemTime::now()) {
+    if remaining.is_zero() {
+      break;
+    }
+    tokio::time::sleep(remaining.min(WAKE_CHECK_INTERVAL)).await;
+  }
+}
+
+/// Sets the file storing the last run of the persisted schedules.
+pub(crate) fn set_store(path: PathBuf) {
+  let _ = STORE.set(path);
+}
+
+fn read_store(path: &PathBuf) -> HashMap<String, u64> {
+  std::fs::read(path)
+    .ok()
+    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+    .unwrap_or_default()
+}
+
+fn last_run(id: &str) -> Option<SystemTime> {
+  let path = STORE.get()?;
+  let _lock = STORE_LOCK.lock().unwrap();
+  let seconds = *read_store(path).get(id)?;
+  Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn record_run(id: &str, time: SystemTime) {
+  let Some(path) = STORE.get() else {
+    return;
+  };
+  let _lock = STORE_LOCK.lock().unwrap();
+  let mut runs = read_store(path);
+  let seconds = time
+    .duration_since(UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_secs())
+    .unwrap_or_default();
+  runs.insert(id.into(), seconds);
+  if let Some(parent) = path.parent() {
+    let _ = std::fs::create_dir_all(parent);
+  }
+  if let Ok(json) = serde_json::to_vec(&runs) {
+    let _ = std::fs::write(path, json);
+  }
+}
+
+/// A parsed cron expression, each field as a bit set of the matching values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cron {
+  minutes: u64,
+  hours: u32,
+  days: u32,
+  months: u16,
+  weekdays: u8,
+  /// Whether the day of the month is `*`.
+  any_day: bool,
+  /// Whether the day of the week is `*`.
+  any_weekday: bool,
+}
+
+impl Cron {
+  fn parse(expression: &str) -> Result<Self, String> {
+    let expression = match expression.trim() {
+      "@hourly" => "0 * * * *",
+      "@daily" | "@midnight" => "0 0 * * *",
+      "@weekly" => "0 0 * * 0",
+      "@monthly" => "0 0 1 * *",
+      "@yearly" | "@annually" => "0 0 1 1 *",
+      expression => expression,
+    };
+    let fields = expression.split_whitespace().collect::<Vec<_>>();
+    let [minutes, hours, days, months, weekdays] = fields[..] else {
+      return Err(format!("expected 5 fields, found {}", fields.len()));
+    };
+
+    // Sunday is both 0 and 7
+    let weekday_values = parse_field(weekdays, 0, 7)?;
+    Ok(Self {
+      minutes: parse_field(minutes, 0, 59)?,
+      hours: parse_field(hours, 0, 23)? as u32,
+      days: parse_field(days, 1, 31)? as u32,
+      months: parse_field(months, 1, 12)? as u16,
+      weekdays: ((weekday_values | weekday_values >> 7) & 0x7f) as u8,
+      any_day: days == "*",
+      any_weekday: weekdays == "*",
+    })
+  }
+
+  /// Whether the expression matches the day. When both the day of the month and the day of the week are restricted,
+  /// a day matching either of them matches.
+  fn matches_day(&self, month: u32, day: u32, weekday: u32) -> bool {
+    if self.months & (1 << month) == 0 {
+      return false;
+    }
+    let day_matches = self.days & (1 << day) != 0;
+    let weekday_matches = self.weekdays & (1 << weekday) != 0;
+    match (self.any_day, self.any_weekday) {
+      (false, false) => day_matches || weekday_matches,
+      _ => day_matches && weekday_matches,
+    }
+  }
+
+  /// The first matching minute strictly after the given time.
+  fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+    let seconds = after.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let mut minute = seconds / 60 + 1;
+    let limit = minute + CRON_SEARCH_YEARS * 366 * 24 * 60;
+    while minute < limit {
+      let days = minute / (24 * 60);
+      let (month, day) = civil_from_days(days);
+      // 1970-01-01 was a Thursday
+      let weekday = ((days + 4) % 7) as u32;
+      if !self.matches_day(month, day, weekday) {
+        minute = (days + 1) * 24 * 60;
+        continue;
+      }
+      let hour = (minute / 60) % 24;
+      if self.hours & (1 << hour) == 0 {
+        minute = (minute / 60 + 1) * 60;
+        continue;
+      }
+      if self.minutes & (1 << (minute % 60)) == 0 {
+        minute += 1;
+        continue;
+      }
+      return Some(UNIX_EPOCH + Duration::from_secs(minute * 60));
+    }
+    None
+  }
+}
+
+/// Parses a cron field into the bit set of its values.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+  let mut values = 0u64;
+  for part in field.split(',') {
+    let (range, step) = match part.split_once('/') {
+      Some((range, step)) => (
+        range,
+        step
+          .parse::<u32>()
+          .ok()
+          .filter(|step| *step > 0)
+          .ok_or_else(|| format!("invalid step `{step}`"))?,
+      ),
+      None => (part, 1),
+    };
+    let (start, end) = if range == "*" {
+      (min, max)
+    } else if let Some((start, end)) = range.split_once('-') {
+      (parse_value(start, min, max)?, parse_value(end, min, max)?)
+    } else {
+      let value = parse_value(range, min, max)?;
+      // `5/10` starts at 5 and runs to the end of the range
+      (value, if step > 1 { max } else { value })
+    };
+    if start > end {
+      return Err(format!("invalid range `{range}`"));
+    }
+    for value in (start..=end).step_by(step as usize) {
+      values |= 1 << value;
+    }
+  }
+  Ok(values)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+  value
+    .parse::<u32>()
+    .ok()
+    .filter(|value| (min..=max).contains(value))
+    .ok_or_else(|| format!("`{value}` is not between {min} and {max}"))
+}
+
+/// Converts the days since the Unix epoch to the month and the day of the month, in the proleptic Gregorian calendar.
+fn civil_from_days(days: u64) -> (u32, u32) {
+  let days = days as i64 + 719_468;
+  let era = days.div_euclid(146_097);
+  let day_of_era = days - era * 146_097;
+  let year_of_era =
+    (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_index = (5 * day_of_year + 2) / 153;
+  let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+  let month = if month_index < 10 {
+    month_index + 3
+  } else {
+    month_index - 9
+  } as u32;
+  (month, day)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Cron, Schedule};
+  use std::time::{Duration, UNIX_EPOCH};
+
+  #[test]
+  fn parses_cron_expressions() {
+    let cron = Cron::parse("*/15 9-17 * * 1-5").unwrap();
+    assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+    assert_eq!(cron.hours, 0b11_1111_1110_0000_0000);
+    assert_eq!(cron.weekdays, 0b011_1110);
+    assert!(cron.any_day && !cron.any_weekday);
+
+    assert_eq!(Cron::parse("0 0 * * 7").unwrap().weekdays, 1);
+    assert_eq!(Cron::parse("@daily"), Cron::parse("0 0 * * *"));
+    assert!(Cron::parse("60 * * * *").is_err());
+    assert!(Cron::parse("* * * *").is_err());
+    assert!(Schedule::cron("*/0 * * * *").is_err());
+  }
+
+  #[test]
+  fn computes_next_cron_run() {
+    // 2023-03-15T10:20:30Z, a Wednesday
+    let now = UNIX_EPOCH + Duration::from_secs(1_678_875_630);
+    let next = |expression: &str| {
+      Cron::parse(expression)
+        .unwrap()
+        .next_after(now)
+        .unwrap()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+    };
+
+    // 2023-03-15T10:30:00Z
+    assert_eq!(next("*/15 * * * *"), 1_678_876_200);
+    // 2023-03-16T03:30:00Z
+    assert_eq!(next("30 3 * * *"), 1_678_937_400);
+    // 2023-03-19T00:00:00Z, the next Sunday
+    assert_eq!(next("0 0 * * 0"), 1_679_184_000);
+    // 2024-02-29T00:00:00Z
+    assert_eq!(next("0 0 29 2 *"), 1_709_164_800);
+  }
+}
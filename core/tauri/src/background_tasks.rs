@@ -0,0 +1,281 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Periodic background work on Android and iOS, scheduled with [`crate::AppHandle::schedule_background_task`].
+//!
+//! The system runs the tasks while the app is in the background, with WorkManager on Android and
+//! BGTaskScheduler on iOS, at most as often as requested and when their constraints are met.
+//! Each run calls the handler of the task with a [`TaskCompletion`], which the handler completes once its work is done.
+//!
+//! The identifiers of the tasks must be declared in
+//! [`tauri > bundle > backgroundTasks > identifiers`](crate::utils::config::BackgroundTasksConfig#structfield.identifiers).
+//!
+//! ## Platform-specific
+//!
+//! - **Android**: The interval is at least 15 minutes. The task only runs while the app process runs the Tauri app:
+//!   when the system starts the process to run the task after the app was closed, the run is skipped.
+//!   A run is stopped after 10 minutes.
+//! - **iOS**: The system launches the app in the background to run the task, and decides when it runs based on
+//!   the usage of the app. A run lasts about 30 seconds, or a few minutes with the network or charging constraints.
+//! - **Linux / macOS / Windows**: Unsupported, returns [`crate::Error::BackgroundTasksUnavailable`].
+//!   Use [`crate::async_runtime::schedule`] instead.
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+type Handler = Arc<dyn Fn(TaskCompletion) + Send + Sync>;
+type Complete = Box<dyn Fn(u32, bool) + Send + Sync>;
+
+/// The handlers of the scheduled tasks, keyed by their identifier.
+static HANDLERS: Lazy<Mutex<HashMap<String, Handler>>> = Lazy::new(Default::default);
+/// The runs started by the system before the app registered the handler of their task.
+static PENDING: Mutex<Vec<(String, u32)>> = Mutex::new(Vec::new());
+/// Reports the completion of a run to the native plugin.
+static COMPLETE: OnceCell<Complete> = OnceCell::new();
+
+/// A periodic background task.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tauri::background_tasks::BackgroundTask;
+///
+/// let sync = BackgroundTask::new("com.tauri.app.sync", Duration::from_secs(60 * 60)).requires_network(true);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundTask {
+  id: String,
+  #[serde(rename = "intervalSecs", serialize_with = "serialize_secs")]
+  interval: Duration,
+  requires_network: bool,
+  requires_charging: bool,
+}
+
+fn serialize_secs<S: serde::Serializer>(
+  duration: &Duration,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  serializer.serialize_u64(duration.as_secs())
+}
+
+impl BackgroundTask {
+  /// Creates a task with the given identifier, declared in `tauri > bundle > backgroundTasks > identifiers`,
+  /// running at most every `interval`.
+  pub fn new(id: impl Into<String>, interval: Duration) -> Self {
+    Self {
+      id: id.into(),
+      interval,
+      requires_network: false,
+      requires_charging: false,
+    }
+  }
+
+  /// Only runs the task when the device is connected to the network.
+  #[must_use]
+  pub fn requires_network(mut self, requires_network: bool) -> Self {
+    self.requires_network = requires_network;
+    self
+  }
+
+  /// Only runs the task when the device is charging.
+  #[must_use]
+  pub fn requires_charging(mut self, requires_charging: bool) -> Self {
+    self.requires_charging = requires_charging;
+    self
+  }
+}
+
+/// Reports the completion of a run of a background task to the system.
+///
+/// The system keeps the app running until the run is completed or its time is over.
+/// Dropping the handle completes the run as failed.
+pub struct TaskCompletion {
+  run_id: u32,
+  completed: bool,
+}
+
+impl TaskCompletion {
+  /// Completes the run. A failed run is retried sooner on Android.
+  pub fn complete(mut self, success: bool) {
+    self.finish(success);
+  }
+
+  fn finish(&mut self, success: bool) {
+    if !self.completed {
+      self.completed = true;
+      if let Some(complete) = COMPLETE.get() {
+        complete(self.run_id, success);
+      }
+    }
+  }
+}
+
+impl Drop for TaskCompletion {
+  fn drop(&mut self) {
+    self.finish(false);
+  }
+}
+
+impl std::fmt::Debug for TaskCompletion {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TaskCompletion")
+      .field("run_id", &self.run_id)
+      .finish()
+  }
+}
+
+/// Calls the handler of a task on the blocking thread pool.
+fn run(handler: Handler, run_id: u32) {
+  crate::async_runtime::spawn_blocking(move || {
+    handler(TaskCompletion {
+      run_id,
+      completed: false,
+    })
+  });
+}
+
+/// Handles a run of a task started by the system.
+#[cfg_attr(not(mobile), allow(dead_code))]
+fn dispatch(id: String, run_id: u32) {
+  // the lock is held until the run is queued, so a handler registered meanwhile receives it
+  let mut pending = PENDING.lock().unwrap();
+  let handler = HANDLERS.lock().unwrap().get(&id).cloned();
+  match handler {
+    Some(handler) => run(handler, run_id),
+    None => pending.push((id, run_id)),
+  }
+}
+
+/// Glue between Rust and the Kotlin code that forwards the runs of the tasks.
+#[cfg(target_os = "android")]
+pub fn handle_android_background_task(
+  env: jni::JNIEnv<'_>,
+  id: jni::objects::JString<'_>,
+  run_id: i32,
+) {
+  if let Ok(id) = env.get_string(id) {
+    dispatch(id.into(), run_id as u32);
+  }
+}
+
+/// The native background tasks plugin, managed when background tasks are declared.
+#[cfg(mobile)]
+struct BackgroundTasksPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Schedules the task, replacing its previous schedule, and calls `handler` on each run.
+pub(crate) fn schedule<R: Runtime, M: Manager<R>, F: Fn(TaskCompletion) + Send + Sync + 'static>(
+  manager: &M,
+  task: BackgroundTask,
+  handler: F,
+) -> crate::Result<()> {
+  if !manager
+    .config()
+    .tauri
+    .bundle
+    .background_tasks
+    .identifiers
+    .contains(&task.id)
+  {
+    return Err(crate::Error::BackgroundTaskNotDeclared(task.id));
+  }
+
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<BackgroundTasksPlugin<R>>() {
+    let handler: Handler = Arc::new(handler);
+    let mut pending = PENDING.lock().unwrap();
+    HANDLERS
+      .lock()
+      .unwrap()
+      .insert(task.id.clone(), handler.clone());
+    pending.retain(|(id, run_id)| {
+      if id == &task.id {
+        run(handler.clone(), *run_id);
+        false
+      } else {
+        true
+      }
+    });
+    drop(pending);
+
+    return plugin
+      .0
+      .run_mobile_plugin::<()>("schedule", &task)
+      .map_err(Into::into);
+  }
+
+  let _ = handler;
+  Err(crate::Error::BackgroundTasksUnavailable)
+}
+
+/// Cancels the task scheduled with the identifier.
+pub(crate) fn cancel<R: Runtime, M: Manager<R>>(manager: &M, id: &str) -> crate::Result<()> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<BackgroundTasksPlugin<R>>() {
+    HANDLERS.lock().unwrap().remove(id);
+    return plugin
+      .0
+      .run_mobile_plugin::<()>("cancel", serde_json::json!({ "id": id }))
+      .map_err(Into::into);
+  }
+
+  let _ = (manager, id);
+  Err(crate::Error::BackgroundTasksUnavailable)
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("background-tasks")
+    .setup(|app, _api| {
+      #[cfg(mobile)]
+      if !app
+        .config()
+        .tauri
+        .bundle
+        .background_tasks
+        .identifiers
+        .is_empty()
+      {
+        #[cfg(target_os = "android")]
+        let handle =
+          _api.register_android_plugin("app.tauri.backgroundtasks", "BackgroundTasksPlugin")?;
+        #[cfg(target_os = "ios")]
+        let handle = {
+          extern "C" fn handler(id: *const std::os::raw::c_char, run_id: u32) {
+            let id = unsafe { std::ffi::CStr::from_ptr(id) };
+            dispatch(id.to_string_lossy().into_owned(), run_id);
+          }
+          let handle = _api.register_ios_plugin(crate::ios::init_background_tasks_plugin)?;
+          unsafe {
+            crate::ios::set_background_task_handler(crate::ios::BackgroundTaskHandler(handler))
+          };
+          handle
+        };
+
+        let complete = handle.clone();
+        let _ = COMPLETE.set(Box::new(move |run_id, success| {
+          let _ = complete.run_mobile_plugin::<()>(
+            "complete",
+            serde_json::json!({ "runId": run_id, "success": success }),
+          );
+        }));
+        app.manage(BackgroundTasksPlugin(handle));
+      }
+
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
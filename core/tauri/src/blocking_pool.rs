@@ -0,0 +1,133 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The thread pool running the commands marked with `#[tauri::command(blocking)]`.
+//!
+//! The other synchronous commands run on the main thread, which processes the IPC messages,
+//! so a slow command freezes the UI until it returns.
+
+use crate::utils::config::BlockingPoolConfig;
+use std::{
+  sync::{
+    mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+    Arc, Mutex,
+  },
+  thread,
+};
+
+const DEFAULT_QUEUE_LIMIT: usize = 1024;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A fixed-size pool of threads with a bounded queue, spawned on the first command.
+pub(crate) struct BlockingPool {
+  size: usize,
+  queue_limit: usize,
+  sender: Mutex<Option<SyncSender<Job>>>,
+}
+
+impl BlockingPool {
+  pub(crate) fn new(config: &BlockingPoolConfig) -> Self {
+    let size = config
+      .size
+      .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+      .unwrap_or(4)
+      .max(1);
+    Self {
+      size,
+      queue_limit: config.queue_limit.unwrap_or(DEFAULT_QUEUE_LIMIT),
+      sender: Mutex::new(None),
+    }
+  }
+
+  /// Runs the job on a thread of the pool, or fails if the queue is full.
+  pub(crate) fn spawn<F: FnOnce() + Send + 'static>(&self, job: F) -> Result<(), String> {
+    let mut sender = self.sender.lock().unwrap();
+    let sender = sender.get_or_insert_with(|| self.start());
+    match sender.try_send(Box::new(job)) {
+      Ok(()) => Ok(()),
+      Err(TrySendError::Full(_)) => Err(format!(
+        "the blocking command queue is full ({} commands are waiting)",
+        self.queue_limit
+      )),
+      Err(TrySendError::Disconnected(_)) => Err("the blocking command pool stopped".into()),
+    }
+  }
+
+  fn start(&self) -> SyncSender<Job> {
+    let (sender, receiver) = sync_channel::<Job>(self.queue_limit);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for i in 0..self.size {
+      let receiver = receiver.clone();
+      let _ = thread::Builder::new()
+        .name(format!("tauri-blocking-{i}"))
+        .spawn(move || work(&receiver));
+    }
+    sender
+  }
+}
+
+fn work(receiver: &Mutex<Receiver<Job>>) {
+  loop {
+    // the lock is released before running the job so the other threads can receive the next ones
+    let job = receiver.lock().unwrap().recv();
+    match job {
+      Ok(job) => job(),
+      Err(_) => break,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::BlockingPool;
+  use crate::utils::config::BlockingPoolConfig;
+  use std::sync::{mpsc::channel, Arc, Barrier};
+
+  #[test]
+  fn runs_jobs_in_parallel() {
+    let pool = BlockingPool::new(&BlockingPoolConfig {
+      size: Some(2),
+      queue_limit: None,
+    });
+    let barrier = Arc::new(Barrier::new(2));
+    let (tx, rx) = channel();
+    for i in 0..2 {
+      let barrier = barrier.clone();
+      let tx = tx.clone();
+      pool
+        .spawn(move || {
+          // both jobs must run at the same time to pass the barrier
+          barrier.wait();
+          tx.send(i).unwrap();
+        })
+        .unwrap();
+    }
+    let mut done = vec![rx.recv().unwrap(), rx.recv().unwrap()];
+    done.sort_unstable();
+    assert_eq!(done, [0, 1]);
+  }
+
+  #[test]
+  fn rejects_jobs_when_the_queue_is_full() {
+    let pool = BlockingPool::new(&BlockingPoolConfig {
+      size: Some(1),
+      queue_limit: Some(1),
+    });
+    let (started_tx, started_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    pool
+      .spawn(move || {
+        started_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+      })
+      .unwrap();
+    started_rx.recv().unwrap();
+
+    // the thread is busy, one job can wait in the queue
+    pool.spawn(|| {}).unwrap();
+    assert!(pool.spawn(|| {}).is_err());
+    release_tx.send(()).unwrap();
+  }
+}
@@ -12,6 +12,12 @@ use crate::InvokeMessage;
 use crate::Runtime;
 use serde::de::Visitor;
 use serde::{Deserialize, Deserializer};
+use serde_json::Value as JsonValue;
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
 
 /// Represents a custom command.
 pub struct CommandItem<'a, R: Runtime> {
@@ -38,6 +44,7 @@ pub struct CommandItem<'a, R: Runtime> {
 /// Tauri implements [`CommandArg`] automatically for a number of types.
 /// * [`crate::Window`]
 /// * [`crate::State`]
+/// * [`crate::ipc::InvokeRequest`]
 /// * `T where T: serde::Deserialize`
 ///   * Any type that implements `Deserialize` can automatically be used as a [`CommandArg`].
 pub trait CommandArg<'de, R: Runtime>: Sized {
@@ -150,16 +157,149 @@ impl<'de, R: Runtime> Deserializer<'de> for CommandItem<'de, R> {
   pass!(deserialize_ignored_any, visitor: V);
 }
 
+/// The invoke message keys that are not command arguments, ignored by the cache keys.
+const NON_ARGUMENT_KEYS: &[&str] = &["__TAURI_CANCELLATION_ID__"];
+
+/// The key of a cached response: the command, using the `plugin:<name>|<command>` format for plugin commands,
+/// the label of the window that invoked it and its arguments.
+type CacheKey = (String, String, String);
+
+/// The responses of the commands using the `cache` attribute.
+#[derive(Default)]
+pub(crate) struct CommandCache(Mutex<HashMap<CacheKey, (Instant, JsonValue)>>);
+
+impl CommandCache {
+  fn get(&self, key: &CacheKey) -> Option<JsonValue> {
+    let entries = self.0.lock().unwrap();
+    entries
+      .get(key)
+      .filter(|(expires, _)| *expires > Instant::now())
+      .map(|(_, response)| response.clone())
+  }
+
+  fn insert(&self, key: CacheKey, ttl: Duration, response: JsonValue) {
+    let mut entries = self.0.lock().unwrap();
+    let now = Instant::now();
+    entries.retain(|_, (expires, _)| *expires > now);
+    entries.insert(key, (now + ttl, response));
+  }
+
+  /// Removes the cached responses of the command, for every window.
+  pub(crate) fn invalidate(&self, command: &str) {
+    self.0.lock().unwrap().retain(|(c, _, _), _| c != command);
+  }
+
+  /// Removes all cached responses.
+  pub(crate) fn clear(&self) {
+    self.0.lock().unwrap().clear();
+  }
+}
+
 /// [Autoref-based stable specialization](https://github.com/dtolnay/case-studies/blob/master/autoref-specialization/README.md)
 ///
 /// Nothing in this module is considered stable.
 #[doc(hidden)]
 pub mod private {
-  use crate::{InvokeError, InvokeResolver, Runtime};
+  use super::{CacheKey, CommandCache, NON_ARGUMENT_KEYS};
+  use crate::{InvokeError, InvokeMessage, InvokeResolver, Manager, Runtime};
   use futures_util::{FutureExt, TryFutureExt};
   use serde::Serialize;
   use serde_json::Value;
-  use std::future::Future;
+  use std::{future::Future, sync::Arc, time::Duration};
+
+  /// The result of a cache lookup for a command using the `cache` attribute.
+  pub enum CacheLookup {
+    /// The cached response.
+    Hit(Value),
+    /// The response must be computed, and is stored with [`CacheSlot::wrap`].
+    Miss(CacheSlot),
+  }
+
+  /// Where the response of a command is cached.
+  pub struct CacheSlot {
+    cache: Option<Arc<CommandCache>>,
+    key: CacheKey,
+    ttl: Duration,
+  }
+
+  impl CacheSlot {
+    /// Caches the successful response of the command.
+    pub fn wrap<F>(self, response: F) -> impl Future<Output = Result<Value, InvokeError>>
+    where
+      F: Future<Output = Result<Value, InvokeError>>,
+    {
+      response.map(move |response| {
+        if let (Some(cache), Ok(value)) = (&self.cache, &response) {
+          cache.insert(self.key, self.ttl, value.clone());
+        }
+        response
+      })
+    }
+  }
+
+  /// Runs a command marked with `#[command(blocking)]` on the blocking pool.
+  pub fn spawn_blocking<R: Runtime, F: FnOnce() + Send + 'static>(
+    message: &InvokeMessage<R>,
+    command: F,
+  ) -> Result<(), InvokeError> {
+    match message
+      .window_ref()
+      .try_state::<crate::blocking_pool::BlockingPool>()
+    {
+      Some(pool) => pool.spawn(command).map_err(Into::into),
+      None => {
+        std::thread::spawn(command);
+        Ok(())
+      }
+    }
+  }
+
+  /// Looks up the cached response of the command for the window and the arguments of the message.
+  pub fn command_cache<R: Runtime>(message: &InvokeMessage<R>, ttl_millis: u64) -> CacheLookup {
+    let mut arguments = message.payload().clone();
+    if let Value::Object(arguments) = &mut arguments {
+      for key in NON_ARGUMENT_KEYS {
+        arguments.remove(*key);
+      }
+    }
+    let command = match &message.plugin {
+      Some(plugin) => format!("plugin:{plugin}|{}", message.command()),
+      None => message.command().to_string(),
+    };
+    let key = (
+      command,
+      message.window_ref().label().to_string(),
+      arguments.to_string(),
+    );
+    let cache = message
+      .window_ref()
+      .try_state::<Arc<CommandCache>>()
+      .map(|cache| cache.inner().clone());
+    if let Some(response) = cache.as_ref().and_then(|cache| cache.get(&key)) {
+      return CacheLookup::Hit(response);
+    }
+    CacheLookup::Miss(CacheSlot {
+      cache,
+      key,
+      ttl: Duration::from_millis(ttl_millis),
+    })
+  }
+
+  /// Rejects the invoke if the window isn't one of the `allowed_origins` of the command.
+  #[inline(always)]
+  pub fn ensure_origin_allowed<R: Runtime>(
+    message: &InvokeMessage<R>,
+    allowed: &[&str],
+  ) -> Result<(), InvokeError> {
+    if message.is_origin_allowed(allowed) {
+      Ok(())
+    } else {
+      Err(
+        crate::window::ipc_origin_error_message(message.window_ref().label(), message.command())
+          .into(),
+      )
+    }
+  }
 
   // ===== impl Serialize =====
 
@@ -294,3 +434,94 @@ pub mod private {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{CacheKey, CommandCache};
+  use serde_json::json;
+  use std::time::Duration;
+
+  fn key(command: &str, window: &str, arguments: &str) -> CacheKey {
+    (command.into(), window.into(), arguments.into())
+  }
+
+  #[test]
+  fn cache_responses() {
+    let cache = CommandCache::default();
+    assert_eq!(cache.get(&key("settings", "main", "{}")), None);
+
+    cache.insert(
+      key("settings", "main", "{}"),
+      Duration::from_secs(60),
+      json!(1),
+    );
+    cache.insert(
+      key("settings", "main", r#"{"id":2}"#),
+      Duration::from_secs(60),
+      json!(2),
+    );
+    assert_eq!(cache.get(&key("settings", "main", "{}")), Some(json!(1)));
+    assert_eq!(
+      cache.get(&key("settings", "main", r#"{"id":2}"#)),
+      Some(json!(2))
+    );
+    assert_eq!(cache.get(&key("other", "main", "{}")), None);
+    // the responses are not shared with the other windows or with a plugin command of the same name
+    assert_eq!(cache.get(&key("settings", "other", "{}")), None);
+    assert_eq!(cache.get(&key("plugin:store|settings", "main", "{}")), None);
+  }
+
+  #[test]
+  fn expired_responses() {
+    let cache = CommandCache::default();
+    cache.insert(key("settings", "main", "{}"), Duration::ZERO, json!(1));
+    assert_eq!(cache.get(&key("settings", "main", "{}")), None);
+
+    // inserting prunes the expired responses
+    cache.insert(
+      key("other", "main", "{}"),
+      Duration::from_secs(60),
+      json!(2),
+    );
+    assert_eq!(cache.0.lock().unwrap().len(), 1);
+  }
+
+  #[test]
+  fn invalidate_and_clear() {
+    let cache = CommandCache::default();
+    cache.insert(
+      key("settings", "main", "{}"),
+      Duration::from_secs(60),
+      json!(1),
+    );
+    cache.insert(
+      key("settings", "other", "{}"),
+      Duration::from_secs(60),
+      json!(1),
+    );
+    cache.insert(
+      key("plugin:store|settings", "main", "{}"),
+      Duration::from_secs(60),
+      json!(2),
+    );
+
+    cache.invalidate("settings");
+    assert_eq!(cache.get(&key("settings", "main", "{}")), None);
+    assert_eq!(cache.get(&key("settings", "other", "{}")), None);
+    assert_eq!(
+      cache.get(&key("plugin:store|settings", "main", "{}")),
+      Some(json!(2))
+    );
+
+    cache.invalidate("plugin:store|settings");
+    assert_eq!(cache.get(&key("plugin:store|settings", "main", "{}")), None);
+
+    cache.insert(
+      key("other", "main", "{}"),
+      Duration::from_secs(60),
+      json!(3),
+    );
+    cache.clear();
+    assert_eq!(cache.get(&key("other", "main", "{}")), None);
+  }
+}
@@ -0,0 +1,24 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The `config` core plugin, exposing the configuration values allowed by
+//! [`SecurityConfig::config_allowlist`](crate::utils::config::SecurityConfig::config_allowlist) to the frontend.
+
+use crate::{
+  command,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Result, Runtime,
+};
+use serde_json::Value as JsonValue;
+
+#[command(root = "crate")]
+fn get_config<R: Runtime>(app: AppHandle<R>) -> Result<JsonValue> {
+  app.config().allowlisted().map_err(Into::into)
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("config")
+    .invoke_handler(crate::generate_handler![get_config])
+    .build()
+}
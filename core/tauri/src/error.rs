@@ -61,6 +61,18 @@ pub enum Error {
   /// Error initializing plugin.
   #[error("failed to initialize plugin `{0}`: {1}")]
   PluginInitialization(String, String),
+  /// A plugin depends on a plugin that is not registered.
+  #[error("plugin `{0}` requires the `{1}` plugin, which is not registered")]
+  PluginDependencyMissing(String, String),
+  /// The plugin with the given name is not registered.
+  #[error("plugin `{0}` not found")]
+  PluginNotFound(String),
+  /// Error reconfiguring plugin.
+  #[error("failed to reconfigure plugin `{0}`: {1}")]
+  PluginReconfiguration(String, String),
+  /// The plugin dependencies form a cycle.
+  #[error("plugin dependency cycle: {0}")]
+  PluginDependencyCycle(String),
   /// A part of the URL is malformed or invalid. This may occur when parsing and combining
   /// user-provided URLs and paths.
   #[error("invalid url: {0}")]
@@ -72,19 +84,87 @@ pub enum Error {
   #[cfg(feature = "isolation")]
   #[error("isolation pattern error: {0}")]
   IsolationPattern(#[from] tauri_utils::pattern::isolation::Error),
+  /// An IPC message was received before the window loaded the isolation frame.
+  #[cfg(feature = "isolation")]
+  #[error("the isolation frame of window `{0}` is not loaded")]
+  IsolationFrameNotLoaded(String),
   /// An invalid window URL was provided. Includes details about the error.
   #[error("invalid window url: {0}")]
   InvalidWindowUrl(&'static str),
   /// Invalid glob pattern.
   #[error("invalid glob pattern: {0}")]
   GlobPattern(#[from] glob::PatternError),
+  /// The signature of a persisted scope store doesn't match.
+  #[error("invalid signature on the scope store {0}")]
+  InvalidScopeStore(std::path::PathBuf),
+  /// An event payload doesn't match the schema registered with [`crate::Builder::event_schema`].
+  #[error("invalid payload for event `{0}`: {1}")]
+  InvalidEventPayload(String, String),
+  /// The IPC channel was closed because its window was destroyed or its page was unloaded.
+  #[error("the IPC channel is closed")]
+  ChannelClosed,
+  /// The IPC channel queued too many messages the frontend has not processed yet.
+  #[error("the IPC channel is full")]
+  ChannelFull,
+  /// A dynamic plugin library could not be loaded.
+  #[cfg(feature = "dynamic-plugins")]
+  #[error("failed to load the dynamic plugin {0}: {1}")]
+  DynamicPlugin(std::path::PathBuf, String),
+  /// The frontend assets archive could not be loaded.
+  #[cfg(feature = "external-assets")]
+  #[error("failed to load the frontend assets archive {0}: {1}")]
+  ExternalAssets(std::path::PathBuf, String),
   /// Error decoding PNG image.
   #[cfg(feature = "icon-png")]
   #[error("failed to decode PNG: {0}")]
   PngDecode(#[from] png::DecodingError),
+  /// The jump list or the recent documents could not be updated.
+  #[error("failed to update the jump list: {0}")]
+  JumpList(String),
   /// The Window's raw handle is invalid for the platform.
   #[error("Unexpected `raw_window_handle` for the current platform")]
   InvalidWindowHandle,
+  /// The window was not created with a parent window, or its parent was closed.
+  #[cfg(desktop)]
+  #[error("window does not have a parent window")]
+  WindowWithoutParent,
+  /// The Wayland compositor does not support the layer shell protocol.
+  #[cfg(all(
+    feature = "wayland-layer-shell",
+    any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    )
+  ))]
+  #[error("the compositor does not support the layer shell protocol")]
+  LayerShellUnsupported,
+  /// Invalid cron expression.
+  #[error("invalid cron expression `{0}`: {1}")]
+  InvalidCronExpression(String, String),
+  /// Push notifications are not enabled with `tauri > bundle > push > enable`, or are not supported on this platform.
+  #[error("push notifications are not available")]
+  PushNotificationsUnavailable,
+  /// In-app purchases are not enabled with `tauri > bundle > inAppPurchases > enable`, or are not supported on this platform.
+  #[error("in-app purchases are not available")]
+  InAppPurchasesUnavailable,
+  /// Background tasks are not supported on this platform.
+  #[error("background tasks are not available")]
+  BackgroundTasksUnavailable,
+  /// The background task is not declared in `tauri > bundle > backgroundTasks > identifiers`.
+  #[error(
+    "background task `{0}` is not declared in `tauri > bundle > backgroundTasks > identifiers`"
+  )]
+  BackgroundTaskNotDeclared(String),
+  /// Widgets are not supported on this platform.
+  #[error("widgets are not available")]
+  WidgetsUnavailable,
+  /// Error invoking a mobile plugin.
+  #[cfg(mobile)]
+  #[error(transparent)]
+  PluginInvoke(#[from] crate::plugin::mobile::PluginInvokeError),
   /// JNI error.
   #[cfg(target_os = "android")]
   #[error("jni error: {0}")]
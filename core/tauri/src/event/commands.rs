@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+#[cfg(debug_assertions)]
+use crate::sealed::ManagerBase;
 use crate::{api::ipc::CallbackFn, command, Manager, Result, Runtime, Window};
 use serde::{Deserialize, Deserializer};
 use serde_json::Value as JsonValue;
@@ -67,19 +69,23 @@ pub fn emit<R: Runtime>(
   window_label: Option<WindowLabel>,
   payload: Option<JsonValue>,
 ) -> Result<()> {
+  let payload_json = payload.as_ref().and_then(|p| {
+    serde_json::to_string(&p)
+      .map_err(|e| {
+        #[cfg(debug_assertions)]
+        eprintln!("{e}");
+        e
+      })
+      .ok()
+  });
+  // reject the payloads that don't match the registered schema before dispatching them
+  #[cfg(debug_assertions)]
+  window
+    .manager()
+    .validate_event_payload(&event.0, payload_json.as_deref())?;
+
   // dispatch the event to Rust listeners
-  window.trigger(
-    &event.0,
-    payload.as_ref().and_then(|p| {
-      serde_json::to_string(&p)
-        .map_err(|e| {
-          #[cfg(debug_assertions)]
-          eprintln!("{e}");
-          e
-        })
-        .ok()
-    }),
-  );
+  window.trigger(&event.0, payload_json);
 
   // emit event to JS
   if let Some(target) = window_label {
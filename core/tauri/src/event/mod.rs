@@ -7,7 +7,9 @@ use uuid::Uuid;
 
 mod commands;
 mod listener;
+mod schema;
 pub(crate) use listener::Listeners;
+pub(crate) use schema::EventSchemas;
 
 use crate::{
   plugin::{Builder, TauriPlugin},
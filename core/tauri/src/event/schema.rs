@@ -0,0 +1,100 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The payload schemas of the events, registered with [`crate::Builder::event_schema`].
+//!
+//! In debug builds the payloads emitted from Rust and from the frontend are deserialized into the registered type,
+//! so a payload whose shape drifted from the other side is rejected with an error naming the event and the field.
+
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+type Validator = Box<dyn Fn(&str) -> serde_json::Result<()> + Send + Sync>;
+
+#[derive(Default)]
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+pub(crate) struct EventSchemas(HashMap<String, Validator>);
+
+impl EventSchemas {
+  pub(crate) fn register<T: DeserializeOwned>(&mut self, event: String) {
+    self.0.insert(
+      event,
+      Box::new(|payload| serde_json::from_str::<T>(payload).map(|_| ())),
+    );
+  }
+
+  /// Validates the JSON payload of the event.
+  #[cfg(debug_assertions)]
+  pub(crate) fn validate(&self, event: &str, payload: Option<&str>) -> crate::Result<()> {
+    let validator = match self.0.get(event) {
+      Some(validator) => validator,
+      None => return Ok(()),
+    };
+
+    // the payload is pretty printed so the line of the error points to the field
+    let pretty = payload
+      .and_then(|p| serde_json::from_str::<serde_json::Value>(p).ok())
+      .map(|value| serde_json::to_string_pretty(&value).unwrap())
+      .unwrap_or_else(|| "null".into());
+    validator(&pretty).map_err(|e| {
+      let field = pretty
+        .lines()
+        .nth(e.line().saturating_sub(1))
+        .map(|line| line.trim().trim_end_matches(','))
+        .filter(|line| line.starts_with('"'));
+      let message = e.to_string();
+      // the position is replaced by the field, serde_json appends ` at line {line} column {column}`
+      let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+      crate::Error::InvalidEventPayload(
+        event.into(),
+        match field {
+          Some(field) => format!("{message} at `{field}`"),
+          None => message.into(),
+        },
+      )
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::EventSchemas;
+
+  #[derive(serde::Deserialize)]
+  #[allow(dead_code)]
+  struct Progress {
+    id: String,
+    percent: u8,
+  }
+
+  #[test]
+  fn validates_payloads() {
+    let mut schemas = EventSchemas::default();
+    schemas.register::<Progress>("progress".into());
+
+    assert!(schemas
+      .validate("progress", Some(r#"{"id":"a","percent":10}"#))
+      .is_ok());
+    assert!(schemas.validate("other", Some("1")).is_ok());
+
+    let error = schemas
+      .validate("progress", Some(r#"{"id":"a","percent":"10"}"#))
+      .unwrap_err()
+      .to_string();
+    assert!(error.starts_with("invalid payload for event `progress`: invalid type"));
+    assert!(error.ends_with(r#"at `"percent": "10"`"#));
+
+    let error = schemas
+      .validate("progress", Some(r#"{"percent":10}"#))
+      .unwrap_err()
+      .to_string();
+    assert_eq!(
+      error,
+      "invalid payload for event `progress`: missing field `id`"
+    );
+    assert!(schemas.validate("progress", None).is_err());
+  }
+}
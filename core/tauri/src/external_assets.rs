@@ -0,0 +1,100 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Loads the frontend assets archive written by the codegen when `tauri > externalAssets > enable` is set.
+//!
+//! The archive is read from the resources directory and must either match the SHA-256 digest of the archive built with the binary,
+//! or have a `<archive>.sig` file containing its base64 encoded Ed25519 signature, verified against `tauri > externalAssets > pubkey`.
+//! A tampered archive is never served: the app starts without frontend assets and receives [`crate::RunEvent::AssetVerificationFailed`].
+
+use crate::{
+  utils::{
+    assets::{ExternalAssets, EXTERNAL_ASSETS_FILE_NAME},
+    platform::resource_dir,
+  },
+  PackageInfo,
+};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::{
+  fs::read,
+  path::{Path, PathBuf},
+};
+
+/// Reads and verifies the assets archive, falling back to an empty archive if it can't be loaded.
+///
+/// Returns the path and the reason of the failure, reported as [`crate::RunEvent::AssetVerificationFailed`].
+pub fn load_verified(
+  package_info: &PackageInfo,
+  digest: &str,
+  public_key: Option<&str>,
+) -> (ExternalAssets, Option<(PathBuf, String)>) {
+  match load(package_info, digest, public_key) {
+    Ok(assets) => (assets, None),
+    Err(error) => {
+      log::error!("{error}");
+      let failure = match error {
+        crate::Error::ExternalAssets(path, reason) => (path, reason),
+        error => (EXTERNAL_ASSETS_FILE_NAME.into(), error.to_string()),
+      };
+      (ExternalAssets::default(), Some(failure))
+    }
+  }
+}
+
+/// Reads and verifies the assets archive.
+pub fn load(
+  package_info: &PackageInfo,
+  digest: &str,
+  public_key: Option<&str>,
+) -> crate::Result<ExternalAssets> {
+  let path = resource_dir(package_info, &Default::default())
+    .map_err(|e| {
+      crate::Error::ExternalAssets(
+        EXTERNAL_ASSETS_FILE_NAME.into(),
+        format!("failed to resolve the resources directory: {e}"),
+      )
+    })?
+    .join(EXTERNAL_ASSETS_FILE_NAME);
+  let error = |reason: String| crate::Error::ExternalAssets(path.clone(), reason);
+
+  let archive = read(&path).map_err(|e| error(e.to_string()))?;
+  let archive_digest = Sha256::digest(&archive)
+    .iter()
+    .map(|b| format!("{b:02x}"))
+    .collect::<String>();
+  if archive_digest != digest {
+    match public_key {
+      Some(public_key) => verify_signature(&path, &archive, public_key).map_err(error)?,
+      None => return Err(error("the archive does not match the app binary".into())),
+    }
+  }
+
+  ExternalAssets::from_bytes(archive).ok_or_else(|| error("invalid archive".into()))
+}
+
+/// Verifies the `<archive>.sig` signature of a replaced archive.
+fn verify_signature(path: &Path, archive: &[u8], public_key: &str) -> Result<(), String> {
+  let public_key = base64::engine::general_purpose::STANDARD
+    .decode(public_key)
+    .map_err(|e| format!("invalid public key: {e}"))?;
+
+  let mut signature_path = path.as_os_str().to_owned();
+  signature_path.push(".sig");
+  let signature_path = PathBuf::from(signature_path);
+
+  let signature = read(&signature_path).map_err(|e| {
+    format!(
+      "the archive does not match the app binary and its signature {} could not be read: {e}",
+      signature_path.display()
+    )
+  })?;
+  let signature = base64::engine::general_purpose::STANDARD
+    .decode(String::from_utf8_lossy(&signature).trim())
+    .map_err(|e| format!("invalid signature: {e}"))?;
+
+  ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+    .verify(archive, &signature)
+    .map_err(|_| "signature verification failed".into())
+}
@@ -4,13 +4,20 @@
 
 use crate::{
   api::ipc::{format_callback, format_callback_result, CallbackFn},
-  app::App,
+  app::{App, AppHandle},
   Runtime, StateManager, Window,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use serialize_to_javascript::{default_template, Template};
-use std::{future::Future, sync::Arc};
+use std::{
+  collections::{HashMap, VecDeque},
+  future::Future,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+use tauri_utils::config::RateLimit;
+use url::Url;
 
 use tauri_macros::default_runtime;
 
@@ -28,6 +35,9 @@ pub type InvokeResponder<R> =
 /// A closure that is run once every time a window is created and loaded.
 pub type OnPageLoad<R> = dyn Fn(Window<R>, PageLoadPayload) + Send + Sync + 'static;
 
+/// A closure that is run before `tauri dev` restarts the app, returning the state to restore after the restart.
+pub type OnHotRestart<R> = dyn Fn(&AppHandle<R>) -> JsonValue + Send + Sync + 'static;
+
 // todo: why is this derive broken but the output works manually?
 #[derive(Template)]
 #[default_template("../scripts/ipc.js")]
@@ -70,6 +80,70 @@ pub struct InvokePayload {
   pub inner: JsonValue,
 }
 
+/// The error an invoke is rejected with when it exceeds the IPC rate limits.
+///
+/// See [`IpcLimitsConfig`](crate::utils::config::IpcLimitsConfig).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InvokeRejection {
+  /// The window exceeded the rate limit of the command.
+  RateLimited {
+    /// The command that was invoked.
+    command: String,
+    /// The number of milliseconds until the command can be invoked again.
+    #[serde(rename = "retryAfter")]
+    retry_after: u64,
+  },
+}
+
+/// Tracks the invokes of each window and command to enforce the rate limits.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+  invokes: Mutex<HashMap<(String, String), VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+  /// Records an invoke, returning the time until the command can be invoked again if the limit was reached.
+  pub(crate) fn check(
+    &self,
+    window: &str,
+    command: &str,
+    limit: &RateLimit,
+    now: Instant,
+  ) -> Result<(), Duration> {
+    let interval = Duration::from_millis(limit.interval);
+    let mut invokes = self.invokes.lock().unwrap();
+    let timestamps = invokes.entry((window.into(), command.into())).or_default();
+
+    while timestamps
+      .front()
+      .map_or(false, |t| now.duration_since(*t) >= interval)
+    {
+      timestamps.pop_front();
+    }
+
+    if timestamps.len() >= limit.requests as usize {
+      return Err(
+        timestamps
+          .front()
+          .map_or(interval, |t| interval - now.duration_since(*t)),
+      );
+    }
+
+    timestamps.push_back(now);
+    Ok(())
+  }
+
+  /// Forgets the invokes of a window.
+  pub(crate) fn remove_window(&self, window: &str) {
+    self
+      .invokes
+      .lock()
+      .unwrap()
+      .retain(|(label, _), _| label != window);
+  }
+}
+
 /// The message and resolver given to a custom command.
 #[default_runtime(crate::Wry, wry)]
 #[derive(Debug)]
@@ -295,6 +369,8 @@ pub struct InvokeMessage<R: Runtime> {
   pub(crate) state: Arc<StateManager>,
   /// The IPC command.
   pub(crate) command: String,
+  /// The plugin handling the command, if it was invoked as `plugin:<name>|<command>`.
+  pub(crate) plugin: Option<String>,
   /// The JSON argument passed on the invoke message.
   pub(crate) payload: JsonValue,
 }
@@ -305,6 +381,7 @@ impl<R: Runtime> Clone for InvokeMessage<R> {
       window: self.window.clone(),
       state: self.state.clone(),
       command: self.command.clone(),
+      plugin: self.plugin.clone(),
       payload: self.payload.clone(),
     }
   }
@@ -322,6 +399,7 @@ impl<R: Runtime> InvokeMessage<R> {
       window,
       state,
       command,
+      plugin: None,
       payload,
     }
   }
@@ -361,4 +439,76 @@ impl<R: Runtime> InvokeMessage<R> {
   pub fn state_ref(&self) -> &StateManager {
     &self.state
   }
+
+  /// Whether the window that received the invoke matches one of the given window labels or origins.
+  ///
+  /// Origins are URLs such as `https://tauri.app`, compared with the scheme, host and port of the window URL.
+  pub fn is_origin_allowed<S: AsRef<str>>(&self, allowed: &[S]) -> bool {
+    is_origin_allowed(allowed, self.window.label(), &self.window.url())
+  }
+}
+
+/// Whether the window label or URL matches one of the given window labels or origins.
+pub(crate) fn is_origin_allowed<S: AsRef<str>>(allowed: &[S], label: &str, url: &Url) -> bool {
+  allowed.iter().any(|entry| {
+    let entry = entry.as_ref();
+    if entry.contains("://") {
+      Url::parse(entry).map_or(false, |origin| {
+        origin.scheme() == url.scheme()
+          && origin.host_str() == url.host_str()
+          && origin.port_or_known_default() == url.port_or_known_default()
+      })
+    } else {
+      entry == label
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_origin_allowed, RateLimiter};
+  use std::time::{Duration, Instant};
+  use tauri_utils::config::RateLimit;
+
+  #[test]
+  fn rate_limit() {
+    let limiter = RateLimiter::default();
+    let limit = RateLimit {
+      requests: 2,
+      interval: 1000,
+    };
+    let now = Instant::now();
+
+    assert!(limiter.check("main", "save", &limit, now).is_ok());
+    assert!(limiter
+      .check("main", "save", &limit, now + Duration::from_millis(400))
+      .is_ok());
+    assert_eq!(
+      limiter.check("main", "save", &limit, now + Duration::from_millis(600)),
+      Err(Duration::from_millis(400))
+    );
+    // windows and commands are tracked separately
+    assert!(limiter.check("other", "save", &limit, now).is_ok());
+    assert!(limiter.check("main", "load", &limit, now).is_ok());
+    // the oldest invoke is outside the interval
+    assert!(limiter
+      .check("main", "save", &limit, now + Duration::from_millis(1000))
+      .is_ok());
+  }
+
+  #[test]
+  fn origin_allow_list() {
+    let allowed = ["main", "https://tauri.app"];
+    let remote = "https://tauri.app/docs".parse().unwrap();
+    let local = "tauri://localhost".parse().unwrap();
+
+    assert!(is_origin_allowed(&allowed, "main", &local));
+    assert!(is_origin_allowed(&allowed, "docs", &remote));
+    assert!(!is_origin_allowed(&allowed, "docs", &local));
+    assert!(!is_origin_allowed(
+      &allowed,
+      "docs",
+      &"https://tauri.app:8080".parse().unwrap()
+    ));
+  }
 }
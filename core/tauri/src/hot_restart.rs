@@ -0,0 +1,185 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The `hot-restart` core plugin, saving the app state when `tauri dev` restarts the app because its Rust code changed.
+//!
+//! `tauri dev` starts the app with the `TAURI_DEV_HOT_RESTART_ADDRESS` environment variable set to the address of a TCP server,
+//! and sends a `save` line before killing the app.
+//! The app emits the `tauri://before-hot-restart` event, waits for the windows listening to it to save their state,
+//! writes the state to the `TAURI_DEV_HOT_RESTART_STATE` file and replies with a `saved` line.
+//! The next run reads and deletes that file.
+
+use crate::{
+  command,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Manager, OnHotRestart, Runtime, State, Window,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+  collections::HashMap,
+  io::{BufRead, BufReader, Write},
+  net::TcpStream,
+  path::{Path, PathBuf},
+  sync::{Condvar, Mutex},
+  time::{Duration, Instant},
+};
+use url::Url;
+
+const ADDRESS_ENV: &str = "TAURI_DEV_HOT_RESTART_ADDRESS";
+const STATE_ENV: &str = "TAURI_DEV_HOT_RESTART_STATE";
+const BEFORE_HOT_RESTART_EVENT: &str = "tauri://before-hot-restart";
+/// How long the windows have to save their state.
+const FRONTEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+  /// The value returned by the [`crate::Builder::on_hot_restart`] hook.
+  app: Option<JsonValue>,
+  windows: HashMap<String, WindowState>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WindowState {
+  url: Url,
+  /// The value saved by the `onBeforeHotRestart` handler of the window.
+  state: Option<JsonValue>,
+}
+
+pub(crate) struct HotRestart {
+  restored: Option<SavedState>,
+  frontend_states: Mutex<HashMap<String, JsonValue>>,
+  frontend_saved: Condvar,
+}
+
+impl HotRestart {
+  pub(crate) fn app_state<T: DeserializeOwned>(&self) -> Option<T> {
+    let state = self.restored.as_ref()?.app.clone()?;
+    serde_json::from_value(state).ok()
+  }
+}
+
+#[command(root = "crate")]
+fn save<R: Runtime>(window: Window<R>, hot_restart: State<'_, HotRestart>, state: JsonValue) {
+  hot_restart
+    .frontend_states
+    .lock()
+    .unwrap()
+    .insert(window.label().into(), state);
+  hot_restart.frontend_saved.notify_all();
+}
+
+pub(crate) fn init<R: Runtime>(hook: Option<Box<OnHotRestart<R>>>) -> TauriPlugin<R> {
+  let state_path = std::env::var_os(STATE_ENV)
+    .filter(|_| cfg!(dev))
+    .map(PathBuf::from);
+  let restored = state_path.as_ref().and_then(|path| {
+    let state = std::fs::read(path).ok()?;
+    let _ = std::fs::remove_file(path);
+    serde_json::from_slice::<SavedState>(&state).ok()
+  });
+
+  let mut builder = Builder::new("hot-restart").invoke_handler(crate::generate_handler![save]);
+  if let Some(restored) = &restored {
+    builder = builder.js_init_script(format!(
+      "Object.defineProperty(window, '__TAURI_HOT_RESTART__', {{ value: {} }})",
+      serde_json::to_string(&restored.windows).unwrap()
+    ));
+  }
+
+  builder
+    .setup(move |app, _api| {
+      app.manage(HotRestart {
+        restored,
+        frontend_states: Default::default(),
+        frontend_saved: Condvar::new(),
+      });
+
+      let address = std::env::var(ADDRESS_ENV).ok().filter(|_| cfg!(dev));
+      if let (Some(address), Some(state_path)) = (address, state_path) {
+        let app = app.clone();
+        std::thread::spawn(move || {
+          if let Err(e) = listen(&app, &address, &state_path, hook.as_deref()) {
+            log::error!("failed to communicate with the Tauri CLI for hot restarts: {e}");
+          }
+        });
+      }
+
+      Ok(())
+    })
+    .build()
+}
+
+/// Saves the state each time `tauri dev` asks for it.
+fn listen<R: Runtime>(
+  app: &AppHandle<R>,
+  address: &str,
+  state_path: &Path,
+  hook: Option<&OnHotRestart<R>>,
+) -> std::io::Result<()> {
+  let stream = TcpStream::connect(address)?;
+  let mut writer = stream.try_clone()?;
+  for line in BufReader::new(stream).lines() {
+    if line? != "save" {
+      continue;
+    }
+    let state = save_state(app, hook);
+    std::fs::write(state_path, serde_json::to_vec(&state)?)?;
+    writer.write_all(b"saved\n")?;
+  }
+  Ok(())
+}
+
+fn save_state<R: Runtime>(app: &AppHandle<R>, hook: Option<&OnHotRestart<R>>) -> SavedState {
+  let hot_restart = app.state::<HotRestart>();
+  hot_restart.frontend_states.lock().unwrap().clear();
+
+  let windows = app.windows();
+  let listening = windows
+    .values()
+    .filter(|window| {
+      window.has_js_listener(None, BEFORE_HOT_RESTART_EVENT)
+        || window.has_js_listener(Some(window.label().into()), BEFORE_HOT_RESTART_EVENT)
+    })
+    .map(|window| window.label().to_string())
+    .collect::<Vec<_>>();
+  let _ = app.emit_all(BEFORE_HOT_RESTART_EVENT, ());
+
+  let deadline = Instant::now() + FRONTEND_TIMEOUT;
+  let mut frontend_states = hot_restart.frontend_states.lock().unwrap();
+  while !listening
+    .iter()
+    .all(|label| frontend_states.contains_key(label))
+  {
+    let now = Instant::now();
+    if now >= deadline {
+      break;
+    }
+    frontend_states = hot_restart
+      .frontend_saved
+      .wait_timeout(frontend_states, deadline - now)
+      .unwrap()
+      .0;
+  }
+
+  let windows = windows
+    .into_iter()
+    .map(|(label, window)| {
+      let state = frontend_states.remove(&label);
+      (
+        label,
+        WindowState {
+          url: window.url(),
+          state,
+        },
+      )
+    })
+    .collect();
+  drop(frontend_states);
+
+  SavedState {
+    app: hook.map(|hook| hook(app)),
+    windows,
+  }
+}
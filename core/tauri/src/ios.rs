@@ -23,6 +23,72 @@ impl<'a> SwiftArg<'a> for PluginMessageCallback {
   }
 }
 
+type LifecycleEventCallbackFn = extern "C" fn(c_int);
+pub struct LifecycleEventCallback(pub LifecycleEventCallbackFn);
+
+impl<'a> SwiftArg<'a> for LifecycleEventCallback {
+  type ArgType = LifecycleEventCallbackFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type PushEventHandlerFn = extern "C" fn(*const c_char);
+pub struct PushEventHandler(pub PushEventHandlerFn);
+
+impl<'a> SwiftArg<'a> for PushEventHandler {
+  type ArgType = PushEventHandlerFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type PurchaseEventHandlerFn = extern "C" fn(*const c_char);
+pub struct PurchaseEventHandler(pub PurchaseEventHandlerFn);
+
+impl<'a> SwiftArg<'a> for PurchaseEventHandler {
+  type ArgType = PurchaseEventHandlerFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type ShortcutEventHandlerFn = extern "C" fn(*const c_char, bool);
+pub struct ShortcutEventHandler(pub ShortcutEventHandlerFn);
+
+impl<'a> SwiftArg<'a> for ShortcutEventHandler {
+  type ArgType = ShortcutEventHandlerFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type BackgroundTaskHandlerFn = extern "C" fn(*const c_char, u32);
+pub struct BackgroundTaskHandler(pub BackgroundTaskHandlerFn);
+
+impl<'a> SwiftArg<'a> for BackgroundTaskHandler {
+  type ArgType = BackgroundTaskHandlerFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
+type SensorEventHandlerFn = extern "C" fn(u32, f64, f64, f64, u64);
+pub struct SensorEventHandler(pub SensorEventHandlerFn);
+
+impl<'a> SwiftArg<'a> for SensorEventHandler {
+  type ArgType = SensorEventHandlerFn;
+
+  unsafe fn as_arg(&'a self) -> Self::ArgType {
+    self.0
+  }
+}
+
 swift!(pub fn post_ipc_message(
   webview: *const c_void,
   name: &SRString,
@@ -45,6 +111,27 @@ swift!(pub fn register_plugin(
   webview: *const c_void
 ));
 swift!(pub fn on_webview_created(webview: *const c_void, controller: *const c_void));
+swift!(pub fn observe_lifecycle_events(callback: LifecycleEventCallback));
+swift!(pub fn init_push_plugin() -> *const c_void);
+swift!(pub fn set_push_event_handler(handler: PushEventHandler));
+#[cfg(feature = "crypto")]
+swift!(pub fn init_crypto_plugin() -> *const c_void);
+swift!(pub fn init_system_bars_plugin() -> *const c_void);
+#[cfg(feature = "haptics")]
+swift!(pub fn init_haptics_plugin() -> *const c_void);
+#[cfg(feature = "share")]
+swift!(pub fn init_share_plugin() -> *const c_void);
+#[cfg(feature = "sensors")]
+swift!(pub fn init_sensors_plugin() -> *const c_void);
+#[cfg(feature = "sensors")]
+swift!(pub fn set_sensor_event_handler(handler: SensorEventHandler));
+swift!(pub fn init_purchases_plugin() -> *const c_void);
+swift!(pub fn set_purchase_event_handler(handler: PurchaseEventHandler));
+swift!(pub fn init_shortcuts_plugin() -> *const c_void);
+swift!(pub fn set_shortcut_event_handler(handler: ShortcutEventHandler));
+swift!(pub fn init_background_tasks_plugin() -> *const c_void);
+swift!(pub fn set_background_task_handler(handler: BackgroundTaskHandler));
+swift!(pub fn init_widgets_plugin() -> *const c_void);
 
 pub fn json_to_dictionary(json: &JsonValue) -> id {
   if let serde_json::Value::Object(map) = json {
@@ -0,0 +1,603 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The IPC between the frontend and the Rust code.
+//!
+//! A [`Channel`] streams messages to the window that passed it to a command, e.g. the progress of a download:
+//!
+//! ```
+//! use tauri::ipc::Channel;
+//!
+//! #[tauri::command]
+//! fn download(url: String, on_progress: Channel) -> Result<(), String> {
+//!   for percent in 0..=100 {
+//!     on_progress.send(&percent).map_err(|e| e.to_string())?;
+//!   }
+//!   Ok(())
+//! }
+//! ```
+//!
+//! ```javascript
+//! import { invoke, Channel } from '@tauri-apps/api/tauri'
+//!
+//! const onProgress = new Channel()
+//! onProgress.onmessage = (percent) => console.log(percent)
+//! await invoke('download', { url: 'https://tauri.app', onProgress })
+//! ```
+//!
+//! A [`CancellationToken`] argument is cancelled when the `signal` passed to `invoke` is aborted or when the window is destroyed:
+//!
+//! ```
+//! use tauri::ipc::CancellationToken;
+//!
+//! #[tauri::command]
+//! async fn index(token: CancellationToken) -> Result<(), String> {
+//!   for _ in 0..100 {
+//!     if token.is_cancelled() {
+//!       return Err("cancelled".into());
+//!     }
+//!     // index the next batch
+//!   }
+//!   Ok(())
+//! }
+//! ```
+//!
+//! ```javascript
+//! import { invoke } from '@tauri-apps/api/tauri'
+//!
+//! const controller = new AbortController()
+//! invoke('index', {}, { signal: controller.signal })
+//! controller.abort()
+//! ```
+//!
+//! An [`InvokeRequest`] argument describes the window that invoked the command, to authorize the caller:
+//!
+//! ```
+//! use tauri::ipc::InvokeRequest;
+//!
+//! #[tauri::command]
+//! fn delete_account(request: InvokeRequest) -> Result<(), String> {
+//!   if !request.is_allowed(&["settings"]) {
+//!     return Err(format!("`{}` cannot delete the account", request.window_label()));
+//!   }
+//!   Ok(())
+//! }
+//! ```
+//!
+//! A [`SharedBuffer`] is read by the frontend as an `ArrayBuffer` from the `buffer` protocol, instead of a JSON array:
+//!
+//! ```
+//! use tauri::ipc::SharedBuffer;
+//!
+//! #[tauri::command]
+//! fn thumbnail(app: tauri::AppHandle, state: tauri::State<'_, std::sync::Mutex<Option<SharedBuffer>>>) -> SharedBuffer {
+//!   let buffer = SharedBuffer::new(&app, 256 * 256 * 4);
+//!   // render the thumbnail in `buffer.write()`
+//!   // the buffer is served while a handle to it exists
+//!   state.lock().unwrap().replace(buffer.clone());
+//!   buffer
+//! }
+//! ```
+//!
+//! ```javascript
+//! import { invoke, readSharedBuffer } from '@tauri-apps/api/tauri'
+//!
+//! const pixels = new Uint8ClampedArray(await readSharedBuffer(await invoke('thumbnail')))
+//! ```
+
+pub use crate::api::ipc::{CallbackFn, Channel};
+pub use crate::shared_buffer::SharedBuffer;
+
+use crate::{
+  command,
+  command::{CommandArg, CommandItem},
+  plugin::{Builder, TauriPlugin},
+  InvokeError, Manager, Runtime, State, Window,
+};
+use std::{
+  collections::{HashMap, VecDeque},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, Weak,
+  },
+};
+use tokio::sync::Notify;
+use url::Url;
+
+/// The metadata of an invoke, describing the caller of a command.
+///
+/// The IPC messages are posted by the webview rather than sent as HTTP requests, so the caller is identified by its window:
+/// the label and the URL are read when the message is received, from the webview that posted it.
+#[derive(Debug, Clone)]
+pub struct InvokeRequest {
+  command: String,
+  window_label: String,
+  url: Url,
+}
+
+impl InvokeRequest {
+  /// The invoked command, without the `plugin:<name>|` prefix for plugin commands.
+  pub fn command(&self) -> &str {
+    &self.command
+  }
+
+  /// The label of the window that invoked the command.
+  pub fn window_label(&self) -> &str {
+    &self.window_label
+  }
+
+  /// The URL of the window that invoked the command.
+  pub fn url(&self) -> &Url {
+    &self.url
+  }
+
+  /// The origin of the window URL, e.g. `tauri://localhost` or `https://tauri.app`.
+  ///
+  /// Opaque origins, such as the origin of the `data:` URLs, are serialized as `null`.
+  pub fn origin(&self) -> String {
+    self.url.origin().ascii_serialization()
+  }
+
+  /// Whether the window URL is one of the allowed origins (`<scheme>://<host>[:<port>]`) or window labels,
+  /// like the `allowed_origins` attribute of [`crate::command`].
+  pub fn is_allowed<S: AsRef<str>>(&self, allowed: &[S]) -> bool {
+    crate::hooks::is_origin_allowed(allowed, &self.window_label, &self.url)
+  }
+}
+
+impl<'de, R: Runtime> CommandArg<'de, R> for InvokeRequest {
+  /// Grabs the metadata of the [`CommandItem`]. This will never fail.
+  fn from_command(command: CommandItem<'de, R>) -> Result<Self, InvokeError> {
+    let window = command.message.window_ref();
+    Ok(Self {
+      command: command.message.command().into(),
+      window_label: window.label().into(),
+      url: window.url(),
+    })
+  }
+}
+
+/// How many messages of a channel can be delivered before the frontend acknowledges them.
+const CHANNEL_CAPACITY: usize = 64;
+/// How many messages of a channel can be queued while the frontend has not acknowledged the delivered ones.
+const CHANNEL_QUEUE_CAPACITY: usize = 1024;
+/// The frontend acknowledges the messages of a channel every `CHANNEL_ACK_INTERVAL` messages.
+pub(crate) const CHANNEL_ACK_INTERVAL: usize = 16;
+
+#[derive(Default)]
+struct Progress {
+  sent: usize,
+  acked: usize,
+  closed: bool,
+  queue: VecDeque<String>,
+}
+
+impl Progress {
+  fn delivered(&self) -> usize {
+    self.sent - self.queue.len()
+  }
+}
+
+/// The delivery state of a channel, shared by its clones.
+#[derive(Default)]
+pub(crate) struct ChannelState(Mutex<Progress>);
+
+impl ChannelState {
+  /// Formats the script of the next message with its 1-based index and evaluates it on the window,
+  /// or queues it while the frontend has not acknowledged enough of the delivered messages.
+  ///
+  /// Never blocks, so that commands running on the main thread can send messages:
+  /// fails if the channel is closed or if its queue is full.
+  pub(crate) fn send<R: Runtime, F: FnOnce(usize) -> crate::Result<String>>(
+    &self,
+    window: &Window<R>,
+    script: F,
+  ) -> crate::Result<()> {
+    let mut progress = self.0.lock().unwrap();
+    if progress.closed {
+      return Err(crate::Error::ChannelClosed);
+    }
+    if progress.queue.len() >= CHANNEL_QUEUE_CAPACITY {
+      return Err(crate::Error::ChannelFull);
+    }
+    let index = progress.sent + 1;
+    let script = script(index)?;
+    progress.sent = index;
+    if progress.queue.is_empty() && progress.delivered() - progress.acked <= CHANNEL_CAPACITY {
+      window.eval(&script)
+    } else {
+      progress.queue.push_back(script);
+      Ok(())
+    }
+  }
+
+  /// Whether the channel has queued messages waiting for the frontend.
+  fn is_pending(&self) -> bool {
+    !self.0.lock().unwrap().queue.is_empty()
+  }
+
+  /// Marks the messages up to `index` as processed and delivers the queued messages the frontend can receive.
+  fn ack<R: Runtime>(&self, window: &Window<R>, index: usize) {
+    let mut progress = self.0.lock().unwrap();
+    progress.acked = progress.acked.max(index.min(progress.delivered()));
+    while progress.delivered() - progress.acked < CHANNEL_CAPACITY {
+      let Some(script) = progress.queue.pop_front() else {
+        break;
+      };
+      if window.eval(&script).is_err() {
+        progress.closed = true;
+        progress.queue.clear();
+      }
+    }
+  }
+
+  fn close(&self) {
+    let mut progress = self.0.lock().unwrap();
+    progress.closed = true;
+    progress.queue.clear();
+  }
+}
+
+/// The channels passed to commands, by window label and callback id.
+///
+/// The entries are pruned when a channel is registered, once the senders dropped the channel and its queue was delivered.
+#[derive(Default)]
+pub(crate) struct Channels(Mutex<HashMap<(String, usize), Arc<ChannelState>>>);
+
+impl Channels {
+  /// The state of the channel, shared with the channels already passed to a command with the same id.
+  pub(crate) fn register(&self, label: &str, id: CallbackFn) -> Arc<ChannelState> {
+    let mut channels = self.0.lock().unwrap();
+    channels.retain(|_, state| Arc::strong_count(state) > 1 || state.is_pending());
+    channels
+      .entry((label.to_string(), id.0))
+      .or_default()
+      .clone()
+  }
+
+  fn get(&self, label: &str, id: usize) -> Option<Arc<ChannelState>> {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .get(&(label.to_string(), id))
+      .cloned()
+  }
+
+  /// Closes the channels of the window, dropping their queued messages.
+  pub(crate) fn close_window(&self, label: &str) {
+    self.0.lock().unwrap().retain(|(window, _), state| {
+      if window == label {
+        state.close();
+        false
+      } else {
+        true
+      }
+    });
+  }
+}
+
+/// The invoke message key of the id identifying the `signal` passed to `invoke`.
+const CANCELLATION_ID_KEY: &str = "__TAURI_CANCELLATION_ID__";
+
+#[derive(Default)]
+struct Cancellation {
+  cancelled: AtomicBool,
+  notify: Notify,
+}
+
+impl Cancellation {
+  fn cancel(&self) {
+    self.cancelled.store(true, Ordering::SeqCst);
+    self.notify.notify_waiters();
+  }
+}
+
+/// A command argument cancelled when the frontend aborts the `signal` passed to `invoke`,
+/// or when the window that invoked the command is destroyed.
+///
+/// The command must check the token, cancelling it does not stop the command.
+#[derive(Clone)]
+pub struct CancellationToken(Arc<Cancellation>);
+
+impl CancellationToken {
+  /// Whether the invoke was cancelled.
+  pub fn is_cancelled(&self) -> bool {
+    self.0.cancelled.load(Ordering::SeqCst)
+  }
+
+  /// Waits until the invoke is cancelled.
+  pub async fn cancelled(&self) {
+    loop {
+      let notified = self.0.notify.notified();
+      if self.is_cancelled() {
+        return;
+      }
+      notified.await;
+    }
+  }
+}
+
+impl std::fmt::Debug for CancellationToken {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("CancellationToken")
+      .field("cancelled", &self.is_cancelled())
+      .finish()
+  }
+}
+
+impl<'de, R: Runtime> CommandArg<'de, R> for CancellationToken {
+  /// Grabs the cancellation id from the [`CommandItem`], the token is never cancelled by the frontend when `invoke` has no `signal`.
+  fn from_command(command: CommandItem<'de, R>) -> Result<Self, InvokeError> {
+    let window = command.message.window_ref();
+    let id = command
+      .message
+      .payload()
+      .get(CANCELLATION_ID_KEY)
+      .and_then(|id| id.as_u64());
+    let cancellation = match window.try_state::<Cancellations>() {
+      Some(cancellations) => cancellations.register(window.label(), id),
+      None => Default::default(),
+    };
+    Ok(Self(cancellation))
+  }
+}
+
+/// A cancellation registered by the window label and the cancellation id of its invoke.
+type CancellationEntry = (String, Option<u64>, Weak<Cancellation>);
+
+/// The cancellation tokens of the in-flight commands, by window label and cancellation id.
+#[derive(Default)]
+pub(crate) struct Cancellations(Mutex<Vec<CancellationEntry>>);
+
+impl Cancellations {
+  fn register(&self, label: &str, id: Option<u64>) -> Arc<Cancellation> {
+    let mut cancellations = self.0.lock().unwrap();
+    cancellations.retain(|(_, _, cancellation)| cancellation.strong_count() > 0);
+    if id.is_some() {
+      if let Some(cancellation) = cancellations
+        .iter()
+        .find(|(window, i, _)| window == label && *i == id)
+        .and_then(|(_, _, cancellation)| cancellation.upgrade())
+      {
+        return cancellation;
+      }
+    }
+    let cancellation = Arc::new(Cancellation::default());
+    cancellations.push((label.into(), id, Arc::downgrade(&cancellation)));
+    cancellation
+  }
+
+  fn cancel(&self, label: &str, id: u64) {
+    for (_, _, cancellation) in self
+      .0
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(window, i, _)| window == label && *i == Some(id))
+    {
+      if let Some(cancellation) = cancellation.upgrade() {
+        cancellation.cancel();
+      }
+    }
+  }
+
+  /// Cancels the tokens of the window.
+  pub(crate) fn cancel_window(&self, label: &str) {
+    self.0.lock().unwrap().retain(|(window, _, cancellation)| {
+      if window == label {
+        if let Some(cancellation) = cancellation.upgrade() {
+          cancellation.cancel();
+        }
+        false
+      } else {
+        true
+      }
+    });
+  }
+}
+
+#[command(root = "crate")]
+fn cancel<R: Runtime>(window: Window<R>, cancellations: State<'_, Cancellations>, id: u64) {
+  cancellations.cancel(window.label(), id);
+}
+
+#[command(root = "crate")]
+fn ack<R: Runtime>(window: Window<R>, channels: State<'_, Channels>, id: usize, index: usize) {
+  if let Some(state) = channels.get(window.label(), id) {
+    state.ack(&window, index);
+  }
+}
+
+#[command(root = "crate")]
+fn close<R: Runtime>(window: Window<R>, channels: State<'_, Channels>, id: usize) {
+  if let Some(state) = channels.get(window.label(), id) {
+    state.close();
+  }
+}
+
+/// Initializes the IPC plugin, receiving the acknowledgements of the frontend and the cancelled invokes.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("ipc")
+    .invoke_handler(crate::generate_handler![ack, close, cancel])
+    .setup(|app, _api| {
+      app.manage(Channels::default());
+      app.manage(Cancellations::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    CallbackFn, CancellationToken, Cancellations, Channel, Channels, CHANNEL_CAPACITY,
+    CHANNEL_QUEUE_CAPACITY,
+  };
+  use crate::{
+    test::{assert_ipc_response, mock_builder, mock_context, noop_assets, MockRuntime, NoopAsset},
+    App, Context, InvokePayload, Manager, Window, WindowBuilder,
+  };
+  use std::sync::Arc;
+
+  #[test]
+  fn cancels_tokens() {
+    let cancellations = Cancellations::default();
+    let token = CancellationToken(cancellations.register("main", Some(1)));
+    let other = CancellationToken(cancellations.register("main", Some(2)));
+    let without_signal = CancellationToken(cancellations.register("main", None));
+
+    cancellations.cancel("other", 1);
+    assert!(!token.is_cancelled());
+    cancellations.cancel("main", 1);
+    assert!(token.is_cancelled());
+    assert!(!other.is_cancelled());
+    crate::async_runtime::block_on(token.cancelled());
+
+    cancellations.cancel_window("main");
+    assert!(other.is_cancelled());
+    assert!(without_signal.is_cancelled());
+  }
+
+  fn app(context: Context<NoopAsset>) -> (App<MockRuntime>, Window<MockRuntime>) {
+    let app = mock_builder()
+      .invoke_handler(crate::generate_handler![stream])
+      .build(context)
+      .unwrap();
+    let window = WindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    (app, window)
+  }
+
+  fn invoke(cmd: &str, inner: serde_json::Value) -> InvokePayload {
+    InvokePayload {
+      cmd: cmd.into(),
+      callback: CallbackFn(0),
+      error: CallbackFn(1),
+      inner,
+    }
+  }
+
+  #[crate::command(root = "crate")]
+  fn stream(channel: Channel<MockRuntime>, count: usize) -> Result<(), String> {
+    for i in 0..count {
+      channel.send(&i).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn queues_messages_until_acked() {
+    let (_app, window) = app(mock_context(noop_assets()));
+    let channels = window.state::<Channels>();
+    let state = channels.register("main", CallbackFn(1));
+    assert!(Arc::ptr_eq(
+      &state,
+      &channels.register("main", CallbackFn(1))
+    ));
+
+    for _ in 0..CHANNEL_CAPACITY + 2 {
+      state.send(&window, |index| Ok(index.to_string())).unwrap();
+    }
+    assert_eq!(state.0.lock().unwrap().delivered(), CHANNEL_CAPACITY);
+    assert!(state.is_pending());
+
+    channels.get("main", 1).unwrap().ack(&window, 16);
+    assert_eq!(state.0.lock().unwrap().delivered(), CHANNEL_CAPACITY + 2);
+    assert!(!state.is_pending());
+  }
+
+  #[test]
+  fn fails_when_the_queue_is_full() {
+    let (_app, window) = app(mock_context(noop_assets()));
+    let state = window.state::<Channels>().register("main", CallbackFn(1));
+    for _ in 0..CHANNEL_CAPACITY + CHANNEL_QUEUE_CAPACITY {
+      state.send(&window, |index| Ok(index.to_string())).unwrap();
+    }
+    assert!(matches!(
+      state.send(&window, |index| Ok(index.to_string())),
+      Err(crate::Error::ChannelFull)
+    ));
+  }
+
+  #[test]
+  fn sync_commands_do_not_block_on_sends() {
+    let (_app, window) = app(mock_context(noop_assets()));
+    assert_ipc_response(
+      &window,
+      invoke(
+        "stream",
+        serde_json::json!({ "channel": "__CHANNEL__:1", "count": CHANNEL_CAPACITY * 2 }),
+      ),
+      Ok(()),
+    );
+
+    // the queued messages outlive the channel dropped by the command
+    let channels = window.state::<Channels>();
+    let state = channels.get("main", 1).unwrap();
+    assert_eq!(state.0.lock().unwrap().delivered(), CHANNEL_CAPACITY);
+
+    for index in [16, 32, 48, 64] {
+      assert_ipc_response(
+        &window,
+        invoke(
+          "plugin:ipc|ack",
+          serde_json::json!({ "id": 1, "index": index }),
+        ),
+        Ok(()),
+      );
+    }
+    assert_eq!(state.0.lock().unwrap().delivered(), CHANNEL_CAPACITY * 2);
+  }
+
+  #[test]
+  fn capabilities_allow_acks() {
+    let mut context = mock_context(noop_assets());
+    context.config_mut().tauri.security.capabilities = vec![serde_json::from_value(
+      serde_json::json!({ "identifier": "main", "windows": ["main"], "commands": ["stream"] }),
+    )
+    .unwrap()];
+    let (_app, window) = app(context);
+    assert_ipc_response(
+      &window,
+      invoke(
+        "stream",
+        serde_json::json!({ "channel": "__CHANNEL__:1", "count": CHANNEL_CAPACITY + 1 }),
+      ),
+      Ok(()),
+    );
+
+    assert_ipc_response(
+      &window,
+      invoke(
+        "plugin:ipc|ack",
+        serde_json::json!({ "id": 1, "index": 16 }),
+      ),
+      Ok(()),
+    );
+    let state = window.state::<Channels>().get("main", 1).unwrap();
+    assert_eq!(state.0.lock().unwrap().delivered(), CHANNEL_CAPACITY + 1);
+  }
+
+  #[test]
+  fn closing_the_window_drops_queued_messages() {
+    let (_app, window) = app(mock_context(noop_assets()));
+    let channels = window.state::<Channels>();
+    let state = channels.register("main", CallbackFn(1));
+    let other = channels.register("other", CallbackFn(1));
+    for _ in 0..CHANNEL_CAPACITY + 1 {
+      state.send(&window, |index| Ok(index.to_string())).unwrap();
+    }
+
+    channels.close_window("main");
+    assert!(!state.is_pending());
+    assert!(matches!(
+      state.send(&window, |index| Ok(index.to_string())),
+      Err(crate::Error::ChannelClosed)
+    ));
+    assert!(channels.get("main", 1).is_none());
+    assert!(other.send(&window, |index| Ok(index.to_string())).is_ok());
+  }
+}
@@ -0,0 +1,215 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The jump list of the app on the Windows taskbar, set with [`crate::AppHandle::set_jump_list`].
+//!
+//! Clicking an item runs the app executable with the item arguments. The app reads them with [`std::env::args`],
+//! and forwards them to its running instance with a single instance plugin to handle the click there.
+
+use std::path::PathBuf;
+
+/// An item of the jump list, running the app with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpListItem {
+  title: String,
+  arguments: Vec<String>,
+  description: Option<String>,
+  icon: Option<PathBuf>,
+}
+
+impl JumpListItem {
+  /// Creates an item with the given title.
+  pub fn new(title: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      arguments: Vec::new(),
+      description: None,
+      icon: None,
+    }
+  }
+
+  /// The arguments passed to the app when the item is clicked.
+  #[must_use]
+  pub fn arguments<I: IntoIterator<Item = S>, S: Into<String>>(mut self, arguments: I) -> Self {
+    self.arguments = arguments.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// The tooltip of the item.
+  #[must_use]
+  pub fn description(mut self, description: impl Into<String>) -> Self {
+    self.description.replace(description.into());
+    self
+  }
+
+  /// The `.ico` file or the executable with the icon of the item. Defaults to the app icon.
+  #[must_use]
+  pub fn icon(mut self, icon: impl Into<PathBuf>) -> Self {
+    self.icon.replace(icon.into());
+    self
+  }
+}
+
+/// A group of items in the jump list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpListCategory {
+  title: Option<String>,
+  items: Vec<JumpListItem>,
+}
+
+impl JumpListCategory {
+  /// Creates a category with the given title.
+  pub fn new(title: impl Into<String>, items: Vec<JumpListItem>) -> Self {
+    Self {
+      title: Some(title.into()),
+      items,
+    }
+  }
+
+  /// Creates the `Tasks` category, listed below the other ones.
+  pub fn tasks(items: Vec<JumpListItem>) -> Self {
+    Self { title: None, items }
+  }
+}
+
+/// Quotes an argument following the rules of `CommandLineToArgvW`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn quote_argument(argument: &str) -> String {
+  if !argument.is_empty() && !argument.contains([' ', '\t', '"']) {
+    return argument.into();
+  }
+
+  let mut quoted = String::from('"');
+  let mut backslashes = 0;
+  for c in argument.chars() {
+    match c {
+      '\\' => backslashes += 1,
+      '"' => {
+        // the backslashes before a quote and the quote itself are escaped
+        quoted.extend(std::iter::repeat('\\').take(backslashes * 2 + 1));
+        quoted.push('"');
+        backslashes = 0;
+      }
+      c => {
+        quoted.extend(std::iter::repeat('\\').take(backslashes));
+        quoted.push(c);
+        backslashes = 0;
+      }
+    }
+  }
+  // the backslashes before the closing quote are escaped
+  quoted.extend(std::iter::repeat('\\').take(backslashes * 2));
+  quoted.push('"');
+  quoted
+}
+
+/// Replaces the jump list of the app.
+#[cfg(windows)]
+pub(crate) fn set(categories: &[JumpListCategory]) -> crate::Result<()> {
+  use std::{mem::ManuallyDrop, path::Path};
+  use windows::{
+    core::{Interface, HSTRING, PWSTR},
+    Win32::{
+      Storage::EnhancedStorage::PKEY_Title,
+      System::Com::{
+        CoCreateInstance, CoInitializeEx,
+        StructuredStorage::{PROPVARIANT, PROPVARIANT_0, PROPVARIANT_0_0, PROPVARIANT_0_0_0},
+        CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, VT_LPWSTR,
+      },
+      UI::Shell::{
+        Common::{IObjectArray, IObjectCollection},
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+        PropertiesSystem::IPropertyStore,
+        ShellLink,
+      },
+    },
+  };
+
+  unsafe fn set_list(exe: &Path, categories: &[JumpListCategory]) -> windows::core::Result<()> {
+    // COM is already initialized on the main thread, this only initializes the other threads
+    let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+    let list: ICustomDestinationList =
+      CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+    let mut min_slots = 0;
+    let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+
+    for category in categories {
+      let collection: IObjectCollection =
+        CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+      for item in &category.items {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.SetPath(&HSTRING::from(exe.as_os_str()))?;
+        let arguments = item
+          .arguments
+          .iter()
+          .map(|a| quote_argument(a))
+          .collect::<Vec<_>>()
+          .join(" ");
+        link.SetArguments(&HSTRING::from(arguments))?;
+        if let Some(description) = &item.description {
+          link.SetDescription(&HSTRING::from(description))?;
+        }
+        if let Some(icon) = &item.icon {
+          link.SetIconLocation(&HSTRING::from(icon.as_os_str()), 0)?;
+        }
+
+        // the title is a property of the link, the string is copied by `SetValue`
+        let mut title = item
+          .title
+          .encode_utf16()
+          .chain(std::iter::once(0))
+          .collect::<Vec<u16>>();
+        let value = PROPVARIANT {
+          Anonymous: PROPVARIANT_0 {
+            Anonymous: ManuallyDrop::new(PROPVARIANT_0_0 {
+              vt: VT_LPWSTR,
+              wReserved1: 0,
+              wReserved2: 0,
+              wReserved3: 0,
+              Anonymous: PROPVARIANT_0_0_0 {
+                pwszVal: PWSTR(title.as_mut_ptr()),
+              },
+            }),
+          },
+        };
+        let store: IPropertyStore = link.cast()?;
+        store.SetValue(&PKEY_Title, &value)?;
+        store.Commit()?;
+
+        collection.AddObject(&link)?;
+      }
+
+      let items: IObjectArray = collection.cast()?;
+      match &category.title {
+        Some(title) => list.AppendCategory(&HSTRING::from(title), &items)?,
+        None => list.AddUserTasks(&items)?,
+      }
+    }
+
+    list.CommitList()
+  }
+
+  let exe = std::env::current_exe()?;
+  unsafe { set_list(&exe, categories) }.map_err(|e| crate::Error::JumpList(e.to_string()))
+}
+
+/// Replaces the jump list of the app.
+#[cfg(not(windows))]
+pub(crate) fn set(_categories: &[JumpListCategory]) -> crate::Result<()> {
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::quote_argument;
+
+  #[test]
+  fn quotes_arguments() {
+    assert_eq!(quote_argument("--new-window"), "--new-window");
+    assert_eq!(quote_argument(""), "\"\"");
+    assert_eq!(quote_argument("C:\\My Files\\"), "\"C:\\My Files\\\\\"");
+    assert_eq!(quote_argument("say \"hi\""), "\"say \\\"hi\\\"\"");
+  }
+}
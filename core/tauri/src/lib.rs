@@ -27,10 +27,16 @@
 //! - **macos-private-api**: Enables features only available in **macOS**'s private APIs, currently the `transparent` window functionality and the `fullScreenEnabled` preference setting to `true`. Enabled by default if the `tauri > macosPrivateApi` config flag is set to `true` on the `tauri.conf.json` file.
 //! - **window-data-url**: Enables usage of data URLs on the webview.
 //! - **compression** *(enabled by default): Enables asset compression. You should only disable this if you want faster compile times in release builds - it produces larger binaries.
-//! - **config-json5**: Adds support to JSON5 format for `tauri.conf.json`.
-//! - **config-toml**: Adds support to TOML format for the configuration `Tauri.toml`.
+//! - **config-json5**: Adds support to JSON5 format for `tauri.conf.json`. Enabled by the Tauri CLI if the configuration is read from a `.json5` file.
+//! - **config-toml**: Adds support to TOML format for the configuration `Tauri.toml`. Enabled by the Tauri CLI if the configuration is read from a `.toml` file.
 //! - **icon-ico**: Adds support to set `.ico` window icons. Enables [`Icon::File`] and [`Icon::Raw`] variants.
 //! - **icon-png**: Adds support to set `.png` window icons. Enables [`Icon::File`] and [`Icon::Raw`] variants.
+//! - **dynamic-plugins**: Enables loading signed plugins from shared libraries at runtime with [`Builder::dynamic_plugins`]. See [`plugin::dynamic`].
+//! - **external-assets**: Loads the frontend assets from an archive shipped with the app instead of embedding them in the binary. Enabled by default if the `tauri > externalAssets > enable` config flag is set to `true` on the `tauri.conf.json` file.
+//! - **network**: Enables the [`api::network`] module, resolving the system proxy and applying the `tauri > security > tls` configuration to the HTTP clients of the app and its plugins.
+//! - **oauth**: Enables the [`api::oauth`] module, authorizing the app with OAuth 2.0 and OpenID Connect in the system browser.
+//! - **crypto**: Enables the [`api::crypto`] module, generating hardware-backed keys and storing encrypted secrets with the Android Keystore and the iOS Keychain.
+//! - **wayland-layer-shell**: Enables [`WindowBuilder::layer_shell`] on Linux, showing windows as layer shell surfaces of the Wayland compositors for panels and launchers. Requires the `gtk-layer-shell` system library.
 //!
 //! ## Cargo allowlist features
 //!
@@ -40,6 +46,45 @@
 //! ### Protocol allowlist
 //!
 //! - **protocol-asset**: Enables the `asset` custom protocol.
+//!
+//! ### HID allowlist
+//!
+//! - **hid**: Enables the [`api::hid`] module and the commands of `@tauri-apps/api/hid`, reading the gamepads and the other human interface devices.
+//!
+//! ### Serial allowlist
+//!
+//! - **serial**: Enables the [`api::serial`] module and the commands of `@tauri-apps/api/serial`, opening the serial ports of the `tauri > security > serial > scope` configuration.
+//!
+//! ### Bluetooth allowlist
+//!
+//! - **bluetooth**: Enables the [`api::bluetooth`] module and the commands of `@tauri-apps/api/bluetooth`, connecting to the Bluetooth LE devices the user allows.
+//!
+//! ### USB allowlist
+//!
+//! - **usb**: Enables the [`api::usb`] module and the commands of `@tauri-apps/api/usb`, opening the USB devices of the `tauri > security > usb > scope` configuration.
+//!
+//! ### Haptics allowlist
+//!
+//! - **haptics**: Enables the [`api::haptics`] module and the commands of `@tauri-apps/api/haptics`, playing haptic feedback on Android and iOS.
+//!
+//! ### Sensors allowlist
+//!
+//! - **sensors**: Enables the [`api::sensors`] module and the commands of `@tauri-apps/api/sensors`, watching the motion sensors on Android and iOS.
+//!
+//! ### Keyring allowlist
+//!
+//! - **keyring**: Enables the [`api::keyring`] module, storing the passwords of the app in the credential store of the operating system.
+//!   The commands of `@tauri-apps/api/keyring` are only enabled if the `tauri > security > keyring` config flag is set to `true`.
+//!
+//! ### Auth allowlist
+//!
+//! - **auth**: Enables the [`api::auth`] module, authenticating the user with Touch ID, Windows Hello, polkit or the biometrics of the device.
+//!   The commands of `@tauri-apps/api/auth` are only enabled if the `tauri > security > auth` config flag is set to `true`.
+//!
+//! ### Share allowlist
+//!
+//! - **share**: Enables the [`api::share`] module, sharing texts, links and files through the share sheet of the operating system.
+//!   The commands of `@tauri-apps/api/share` are only enabled if the `tauri > security > share > enable` config flag is set to `true`.
 
 #![warn(missing_docs, rust_2018_idioms)]
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
@@ -67,17 +112,29 @@ pub use swift_rs;
 pub use tauri_macros::mobile_entry_point;
 pub use tauri_macros::{command, generate_handler};
 
+mod accessibility;
 pub mod api;
 pub(crate) mod app;
 #[cfg(feature = "protocol-asset")]
 pub(crate) mod asset_protocol;
 pub mod async_runtime;
+pub mod background_tasks;
+mod blocking_pool;
 pub mod command;
+mod config;
 mod error;
 mod event;
+#[cfg(feature = "external-assets")]
+#[doc(hidden)]
+pub mod external_assets;
 mod hooks;
+mod hot_restart;
+pub mod ipc;
+pub mod jump_list;
+pub mod local_network;
 mod manager;
 mod pattern;
+pub mod permissions;
 pub mod plugin;
 mod vibrancy;
 pub mod window;
@@ -89,9 +146,18 @@ mod jni_helpers;
 /// Path APIs.
 pub mod path;
 pub mod process;
+pub mod purchases;
+pub mod push;
+mod recent_documents;
 /// The allowlist scopes.
 pub mod scope;
+mod scripts;
+mod shared_buffer;
+pub mod shortcuts;
+mod splashscreen;
+mod startup;
 mod state;
+pub mod widgets;
 
 pub use tauri_utils as utils;
 
@@ -125,14 +191,135 @@ macro_rules! android_binding {
     ) {
       ::tauri::handle_android_plugin_response(env, id, success, error);
     }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      plugin,
+      PluginManager,
+      handleLifecycleEvent,
+      [i32],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handleLifecycleEvent(_: JNIEnv, _: JClass, event: i32) {
+      ::tauri::handle_android_lifecycle_event(event);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      plugin,
+      PluginManager,
+      handleTrimMemory,
+      [i32],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handleTrimMemory(_: JNIEnv, _: JClass, level: i32) {
+      ::tauri::handle_android_trim_memory(level);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      push,
+      PushPlugin,
+      handlePushEvent,
+      [JString],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handlePushEvent(env: JNIEnv, _: JClass, payload: JString) {
+      ::tauri::handle_android_push_event(env, payload);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      sensors,
+      SensorsPlugin,
+      handleSensorEvent,
+      [i32, f64, f64, f64, i64],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handleSensorEvent(
+      _: JNIEnv,
+      _: JClass,
+      id: i32,
+      x: f64,
+      y: f64,
+      z: f64,
+      timestamp: i64,
+    ) {
+      ::tauri::handle_android_sensor_event(id, x, y, z, timestamp);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      purchases,
+      PurchasesPlugin,
+      handlePurchaseEvent,
+      [JString],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handlePurchaseEvent(env: JNIEnv, _: JClass, payload: JString) {
+      ::tauri::handle_android_purchase_event(env, payload);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      shortcuts,
+      ShortcutsPlugin,
+      handleShortcutEvent,
+      [JString, u8],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handleShortcutEvent(env: JNIEnv, _: JClass, id: JString, cold_start: u8) {
+      ::tauri::handle_android_shortcut_event(env, id, cold_start != 0);
+    }
+
+    ::tauri::wry::application::android_fn!(
+      app_tauri,
+      backgroundtasks,
+      BackgroundTasksPlugin,
+      handleBackgroundTask,
+      [JString, i32],
+    );
+
+    #[allow(non_snake_case)]
+    pub unsafe fn handleBackgroundTask(env: JNIEnv, _: JClass, id: JString, run_id: i32) {
+      ::tauri::handle_android_background_task(env, id, run_id);
+    }
   };
 }
 
+#[cfg(all(feature = "wry", target_os = "android", feature = "sensors"))]
+#[doc(hidden)]
+pub use api::sensors::handle_android_sensor_event;
+/// The sensor events are only sent by the native plugin registered with the `sensors` feature.
+#[cfg(all(feature = "wry", target_os = "android", not(feature = "sensors")))]
+#[doc(hidden)]
+pub fn handle_android_sensor_event(_id: i32, _x: f64, _y: f64, _z: f64, _timestamp: i64) {}
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use app::lifecycle::{handle_android_lifecycle_event, handle_android_trim_memory};
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use background_tasks::handle_android_background_task;
 #[cfg(all(feature = "wry", target_os = "android"))]
 #[doc(hidden)]
 pub use plugin::mobile::handle_android_plugin_response;
 #[cfg(all(feature = "wry", target_os = "android"))]
 #[doc(hidden)]
+pub use purchases::handle_android_purchase_event;
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use push::handle_android_push_event;
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
+pub use shortcuts::handle_android_shortcut_event;
+#[cfg(all(feature = "wry", target_os = "android"))]
+#[doc(hidden)]
 pub use tauri_runtime_wry::wry;
 
 /// `Result<T, ::tauri::Error>`
@@ -171,12 +358,13 @@ pub use {
 };
 pub use {
   self::app::{
-    App, AppHandle, AssetResolver, Builder, CloseRequestApi, GlobalWindowEvent, RunEvent,
-    WindowEvent,
+    App, AppHandle, AssetResolver, Builder, CloseRequestApi, GlobalWindowEvent, LifecycleEvent,
+    RunEvent, WindowEvent,
   },
   self::hooks::{
-    Invoke, InvokeError, InvokeHandler, InvokeMessage, InvokePayload, InvokeResolver,
-    InvokeResponder, InvokeResponse, OnPageLoad, PageLoadPayload, SetupHook,
+    Invoke, InvokeError, InvokeHandler, InvokeMessage, InvokePayload, InvokeRejection,
+    InvokeResolver, InvokeResponder, InvokeResponse, OnHotRestart, OnPageLoad, PageLoadPayload,
+    SetupHook,
   },
   self::manager::Asset,
   self::runtime::{
@@ -239,7 +427,22 @@ pub fn log_stdout() {
 
 /// The user event type.
 #[derive(Debug, Clone)]
-pub enum EventLoopMessage {}
+#[non_exhaustive]
+pub enum EventLoopMessage {
+  /// The memory pressure of the system changed.
+  MemoryPressure(process::MemoryPressureLevel),
+  /// The app lifecycle changed on mobile.
+  Lifecycle(LifecycleEvent),
+  /// A push event was received on mobile.
+  Push(push::PushEvent),
+  /// A purchase was updated on mobile.
+  Purchase(purchases::Purchase),
+  /// A shortcut of the app was activated.
+  Shortcut(String),
+  /// The frontend assets archive failed to load.
+  #[cfg(feature = "external-assets")]
+  AssetVerificationFailed(std::path::PathBuf, String),
+}
 
 /// The webview runtime interface. A wrapper around [`runtime::Runtime`] with the proper user event type associated.
 pub trait Runtime: runtime::Runtime<EventLoopMessage> {}
@@ -281,6 +484,8 @@ macro_rules! tauri_build_context {
 }
 
 pub use pattern::Pattern;
+pub use scripts::{Script, ScriptId};
+pub use startup::StartupMetrics;
 
 /// A icon definition.
 #[derive(Debug, Clone)]
@@ -383,6 +588,9 @@ pub struct Context<A: Assets> {
   pub(crate) package_info: PackageInfo,
   pub(crate) _info_plist: (),
   pub(crate) pattern: Pattern,
+  pub(crate) plugin_permissions: tauri_utils::plugin::PluginPermissions,
+  #[cfg(feature = "external-assets")]
+  pub(crate) asset_verification_failure: Option<(std::path::PathBuf, String)>,
 }
 
 impl<A: Assets> fmt::Debug for Context<A> {
@@ -392,7 +600,8 @@ impl<A: Assets> fmt::Debug for Context<A> {
       .field("default_window_icon", &self.default_window_icon)
       .field("app_icon", &self.app_icon)
       .field("package_info", &self.package_info)
-      .field("pattern", &self.pattern);
+      .field("pattern", &self.pattern)
+      .field("plugin_permissions", &self.plugin_permissions);
 
     #[cfg(desktop)]
     d.field("system_tray_icon", &self.system_tray_icon);
@@ -492,9 +701,32 @@ impl<A: Assets> Context<A> {
       package_info,
       _info_plist: info_plist,
       pattern,
+      plugin_permissions: Default::default(),
+      #[cfg(feature = "external-assets")]
+      asset_verification_failure: None,
     }
   }
 
+  /// The permission manifests of the app plugins, keyed by plugin name.
+  #[inline(always)]
+  pub fn plugin_permissions(&self) -> &tauri_utils::plugin::PluginPermissions {
+    &self.plugin_permissions
+  }
+
+  /// Sets the permission manifests of the app plugins.
+  #[inline(always)]
+  pub fn set_plugin_permissions(&mut self, permissions: tauri_utils::plugin::PluginPermissions) {
+    self.plugin_permissions = permissions;
+  }
+
+  /// Sets the failure to load the frontend assets archive, reported when the app starts.
+  #[cfg(feature = "external-assets")]
+  #[doc(hidden)]
+  #[inline(always)]
+  pub fn set_asset_verification_failure(&mut self, failure: Option<(std::path::PathBuf, String)>) {
+    self.asset_verification_failure = failure;
+  }
+
   /// Sets the app tray icon.
   #[cfg(desktop)]
   #[inline(always)]
@@ -816,6 +1048,14 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
   fn path(&self) -> &crate::path::PathResolver<R> {
     self.state::<crate::path::PathResolver<R>>().inner()
   }
+
+  /// Gets the permissions of the app plugins.
+  fn plugin_permissions(&self) -> permissions::PluginPermissions {
+    self
+      .state::<permissions::PluginPermissions>()
+      .inner()
+      .clone()
+  }
 }
 
 /// Prevent implementation details from leaking out of the [`Manager`] trait.
@@ -0,0 +1,105 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The Local Network permission of macOS and iOS.
+//!
+//! macOS 15 and iOS 14 prompt the user the first time the app accesses a device of the local network,
+//! e.g. when it browses Bonjour services or connects to a printer, showing the reason of the
+//! [`tauri > bundle > localNetwork > usageDescription`](crate::utils::config::LocalNetworkConfig#structfield.usage_description)
+//! configuration. [`crate::AppHandle::request_local_network_permission`] shows the prompt at a predictable time,
+//! e.g. on an onboarding screen, instead of on the first connection.
+//!
+//! The platforms do not expose the permission state, so it is probed by sending an empty datagram
+//! to the mDNS multicast group, which fails with `EHOSTUNREACH` when the access is denied.
+
+use serde::Serialize;
+
+use std::time::{Duration, Instant};
+
+/// The state of the Local Network permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum LocalNetworkPermission {
+  /// The app can access the local network.
+  Granted,
+  /// The user denied the access, or did not answer the prompt yet.
+  Denied,
+  /// The state cannot be probed, e.g. the computer is not connected to a network.
+  Unavailable,
+}
+
+/// How often [`request`] probes the permission while the prompt is shown.
+const PROBE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Probes the permission, which shows the prompt when the user was not asked yet.
+pub(crate) fn state() -> LocalNetworkPermission {
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  return probe();
+
+  #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+  LocalNetworkPermission::Granted
+}
+
+/// Probes the permission until it is granted or the timeout elapses.
+pub(crate) fn request(timeout: Duration) -> LocalNetworkPermission {
+  let deadline = Instant::now() + timeout;
+  loop {
+    let state = state();
+    if state == LocalNetworkPermission::Granted || Instant::now() >= deadline {
+      return state;
+    }
+    std::thread::sleep(PROBE_INTERVAL.min(deadline.saturating_duration_since(Instant::now())));
+  }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn probe() -> LocalNetworkPermission {
+  use std::net::{Ipv4Addr, UdpSocket};
+
+  const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+  const MDNS_PORT: u16 = 5353;
+
+  let result = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+    .and_then(|socket| socket.send_to(&[], (MDNS_GROUP, MDNS_PORT)));
+  match result {
+    Ok(_) => LocalNetworkPermission::Granted,
+    Err(e) => classify(e.raw_os_error()),
+  }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn classify(errno: Option<i32>) -> LocalNetworkPermission {
+  if errno == Some(libc::EHOSTUNREACH) {
+    LocalNetworkPermission::Denied
+  } else {
+    LocalNetworkPermission::Unavailable
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+  #[test]
+  fn granted_without_a_permission() {
+    assert_eq!(state(), LocalNetworkPermission::Granted);
+    assert_eq!(request(Duration::ZERO), LocalNetworkPermission::Granted);
+  }
+
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  #[test]
+  fn classifies_the_errors() {
+    assert_eq!(
+      classify(Some(libc::EHOSTUNREACH)),
+      LocalNetworkPermission::Denied
+    );
+    assert_eq!(
+      classify(Some(libc::ENETUNREACH)),
+      LocalNetworkPermission::Unavailable
+    );
+    assert_eq!(classify(None), LocalNetworkPermission::Unavailable);
+  }
+}
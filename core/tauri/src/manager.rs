@@ -30,10 +30,14 @@ use crate::hooks::IpcJavascript;
 #[cfg(feature = "isolation")]
 use crate::hooks::IsolationJavascript;
 use crate::pattern::PatternJavascript;
+use crate::shared_buffer::{shared_buffer_protocol_handler, SharedBuffers, SHARED_BUFFER_PROTOCOL};
 use crate::{
   app::{AppHandle, GlobalWindowEvent, GlobalWindowEventListener},
-  event::{assert_event_name_is_valid, Event, EventHandler, Listeners},
-  hooks::{InvokeHandler, InvokePayload, InvokeResponder, OnPageLoad, PageLoadPayload},
+  event::{assert_event_name_is_valid, Event, EventHandler, EventSchemas, Listeners},
+  hooks::{
+    InvokeHandler, InvokePayload, InvokeRejection, InvokeResponder, OnPageLoad, PageLoadPayload,
+    RateLimiter,
+  },
   plugin::PluginStore,
   runtime::{
     http::{
@@ -234,6 +238,11 @@ pub struct InnerWindowManager<R: Runtime> {
   invoke_initialization_script: String,
   /// Application pattern.
   pub(crate) pattern: Pattern,
+  /// The isolation pattern keys of each window, generated every time the window loads the isolation frame.
+  #[cfg(feature = "isolation")]
+  isolation_keys: Arc<Mutex<HashMap<String, tauri_utils::pattern::isolation::Keys>>>,
+  /// The invokes tracked for the IPC rate limits.
+  rate_limiter: RateLimiter,
 }
 
 impl<R: Runtime> fmt::Debug for InnerWindowManager<R> {
@@ -334,6 +343,9 @@ impl<R: Runtime> WindowManager<R> {
         window_event_listeners: Arc::new(window_event_listeners),
         invoke_responder,
         invoke_initialization_script,
+        #[cfg(feature = "isolation")]
+        isolation_keys: Default::default(),
+        rate_limiter: Default::default(),
       }),
     }
   }
@@ -388,7 +400,14 @@ impl<R: Runtime> WindowManager<R> {
     Cow::Owned(Url::parse("tauri://localhost").unwrap())
   }
 
-  fn csp(&self) -> Option<Csp> {
+  /// The CSP of the window with the given label, falling back to the global policy.
+  fn csp(&self, window_label: Option<&str>) -> Option<Csp> {
+    if let Some(csp) =
+      window_label.and_then(|label| self.inner.config.tauri.security.window_csp.get(label))
+    {
+      return Some(csp.clone());
+    }
+
     if cfg!(feature = "custom-protocol") {
       self.inner.config.tauri.security.csp.clone()
     } else {
@@ -433,6 +452,8 @@ impl<R: Runtime> WindowManager<R> {
     .render_default(&Default::default())?;
 
     let mut webview_attributes = pending.webview_attributes;
+    // the Tauri scripts run first, then the app scripts and the window scripts
+    let window_scripts = std::mem::take(&mut webview_attributes.initialization_scripts);
 
     let mut window_labels = window_labels.to_vec();
     let l = label.to_string();
@@ -467,6 +488,27 @@ impl<R: Runtime> WindowManager<R> {
       );
     }
 
+    #[cfg(desktop)]
+    if webview_attributes.title_bar_overlay.is_some() {
+      webview_attributes = webview_attributes
+        .initialization_script(crate::window::title_bar::TITLE_BAR_OVERLAY_SCRIPT);
+    }
+
+    #[cfg(desktop)]
+    if webview_attributes.kiosk {
+      webview_attributes =
+        webview_attributes.initialization_script(crate::window::kiosk::KIOSK_SCRIPT);
+    }
+
+    if let Some(app_scripts) = self.state().try_get::<crate::scripts::AppScripts>() {
+      webview_attributes
+        .initialization_scripts
+        .extend(app_scripts.for_window(label));
+    }
+    webview_attributes
+      .initialization_scripts
+      .extend(window_scripts);
+
     pending.webview_attributes = webview_attributes;
 
     let mut registered_scheme_protocols = Vec::new();
@@ -501,11 +543,19 @@ impl<R: Runtime> WindowManager<R> {
       let web_resource_request_handler = pending.web_resource_request_handler.take();
       pending.register_uri_scheme_protocol(
         "tauri",
-        self.prepare_uri_scheme_protocol(&window_origin, web_resource_request_handler),
+        self.prepare_uri_scheme_protocol(&window_origin, label, web_resource_request_handler),
       );
       registered_scheme_protocols.push("tauri".into());
     }
 
+    if !registered_scheme_protocols.contains(&SHARED_BUFFER_PROTOCOL.into()) {
+      let buffers = self.state().get::<SharedBuffers>().inner().clone();
+      let window_origin = window_origin.clone();
+      pending.register_uri_scheme_protocol(SHARED_BUFFER_PROTOCOL, move |request| {
+        shared_buffer_protocol_handler(&buffers, request, &window_origin)
+      });
+    }
+
     #[cfg(feature = "protocol-asset")]
     if !registered_scheme_protocols.contains(&"asset".into()) {
       let asset_scope = self.state().get::<crate::Scopes>().asset_protocol.clone();
@@ -523,21 +573,38 @@ impl<R: Runtime> WindowManager<R> {
       assets,
       schema,
       key: _,
-      crypto_keys,
     } = &self.inner.pattern
     {
       let assets = assets.clone();
       let schema_ = schema.clone();
       let url_base = format!("{schema_}://localhost");
-      let aes_gcm_key = *crypto_keys.aes_gcm().raw();
+      let isolation_keys = self.inner.isolation_keys.clone();
+      let window_label = label.to_string();
 
       pending.register_uri_scheme_protocol(schema, move |request| {
         match request_to_path(request, &url_base).as_str() {
           "index.html" => match assets.get(&"index.html".into()) {
             Some(asset) => {
+              // every isolation frame load starts a new session with its own key
+              let keys = match tauri_utils::pattern::isolation::Keys::new() {
+                Ok(keys) => keys,
+                Err(_) => {
+                  return HttpResponseBuilder::new()
+                    .status(500)
+                    .mimetype("text/plain")
+                    .body(Vec::new())
+                }
+              };
+              let aes_gcm_key = *keys.aes_gcm().raw();
+              isolation_keys
+                .lock()
+                .unwrap()
+                .insert(window_label.clone(), keys);
+
               let asset = String::from_utf8_lossy(asset.as_ref());
               let template = tauri_utils::pattern::isolation::IsolationJavascriptRuntime {
                 runtime_aes_gcm_key: &aes_gcm_key,
+                window_label: &window_label,
                 stringify_ipc_message_fn: STRINGIFY_IPC_MESSAGE_FN,
               };
               match template.render(asset.as_ref(), &Default::default()) {
@@ -571,14 +638,36 @@ impl<R: Runtime> WindowManager<R> {
     let manager = self.clone();
     Box::new(move |window, #[allow(unused_mut)] mut request| {
       if let Some(window) = manager.get_window(&window.label) {
+        // the oversized messages are dropped before being decrypted or deserialized
+        if let Some(limit) = manager.config().tauri.security.ipc_limits.max_payload_size {
+          if request.len() > limit {
+            let error = format!(
+              "IPC message of {} bytes dropped, the maximum payload size is {limit} bytes",
+              request.len()
+            );
+            log::warn!("{error}");
+            let _ = window.eval(&format!(r#"console.error({})"#, JsonValue::String(error)));
+            return;
+          }
+        }
+
         #[cfg(feature = "isolation")]
-        if let Pattern::Isolation { crypto_keys, .. } = manager.pattern() {
-          match RawIsolationPayload::try_from(request.as_str())
-            .and_then(|raw| crypto_keys.decrypt(raw))
+        if let Pattern::Isolation { .. } = manager.pattern() {
+          let decrypted = match manager
+            .inner
+            .isolation_keys
+            .lock()
+            .unwrap()
+            .get(window.label())
           {
+            Some(keys) => RawIsolationPayload::try_from(request.as_str())
+              .and_then(|raw| keys.decrypt(raw))
+              .map_err(Into::into),
+            None => Err(crate::Error::IsolationFrameNotLoaded(window.label().into())),
+          };
+          match decrypted {
             Ok(json) => request = json,
-            Err(e) => {
-              let error: crate::Error = e.into();
+            Err(error) => {
               let _ = window.eval(&format!(
                 r#"console.error({})"#,
                 JsonValue::String(error.to_string())
@@ -604,21 +693,56 @@ impl<R: Runtime> WindowManager<R> {
     })
   }
 
-  pub fn get_asset(&self, mut path: String) -> Result<Asset, Box<dyn std::error::Error>> {
-    let assets = &self.inner.assets;
+  fn asset_path(mut path: String) -> String {
     if path.ends_with('/') {
       path.pop();
     }
     path = percent_encoding::percent_decode(path.as_bytes())
       .decode_utf8_lossy()
       .to_string();
-    let path = if path.is_empty() {
+    if path.is_empty() {
       // if the url is `tauri://localhost`, we should load `index.html`
       "index.html".to_string()
     } else {
       // skip leading `/`
       path.chars().skip(1).collect::<String>()
+    }
+  }
+
+  /// Gets the brotli compressed bytes and the mime type of the text asset on the given path.
+  ///
+  /// Returns `None` for HTML files, binary files and when the assets are not stored compressed.
+  pub fn get_compressed_asset(&self, path: String) -> Option<(Vec<u8>, String)> {
+    let path = Self::asset_path(path);
+    let mime_type = match MimeType::parse_from_uri_with_fallback(&path, MimeType::OctetStream) {
+      mime @ (MimeType::Css
+      | MimeType::Csv
+      | MimeType::Js
+      | MimeType::Json
+      | MimeType::Jsonld
+      | MimeType::Svg
+      | MimeType::Txt) => mime.to_string(),
+      _ => return None,
     };
+    self
+      .inner
+      .assets
+      .get_compressed(&path.as_str().into())
+      .map(|bytes| (bytes.into_owned(), mime_type))
+  }
+
+  pub fn get_asset(&self, path: String) -> Result<Asset, Box<dyn std::error::Error>> {
+    self.get_window_asset(path, None)
+  }
+
+  /// Gets the asset on the given path, injecting the CSP of the window with the given label on HTML files.
+  pub fn get_window_asset(
+    &self,
+    path: String,
+    window_label: Option<&str>,
+  ) -> Result<Asset, Box<dyn std::error::Error>> {
+    let assets = &self.inner.assets;
+    let path = Self::asset_path(path);
 
     let mut asset_path = AssetKey::from(path.as_str());
 
@@ -659,7 +783,7 @@ impl<R: Runtime> WindowManager<R> {
       Ok(asset) => {
         let final_data = if is_html {
           let mut asset = String::from_utf8_lossy(&asset).into_owned();
-          if let Some(csp) = self.csp() {
+          if let Some(csp) = self.csp(window_label) {
             csp_header.replace(set_csp(
               &mut asset,
               self.inner.assets.clone(),
@@ -691,6 +815,7 @@ impl<R: Runtime> WindowManager<R> {
   fn prepare_uri_scheme_protocol(
     &self,
     window_origin: &str,
+    window_label: &str,
     web_resource_request_handler: Option<
       Box<dyn Fn(&HttpRequest, &mut HttpResponse) + Send + Sync>,
     >,
@@ -707,6 +832,10 @@ impl<R: Runtime> WindowManager<R> {
     #[cfg(not(all(dev, mobile)))]
     let manager = self.clone();
     let window_origin = window_origin.to_string();
+    #[cfg(not(all(dev, mobile)))]
+    let window_label = window_label.to_string();
+    #[cfg(all(dev, mobile))]
+    let _ = window_label;
 
     #[cfg(all(dev, mobile))]
     #[derive(Clone)]
@@ -795,12 +924,29 @@ impl<R: Runtime> WindowManager<R> {
 
       #[cfg(not(all(dev, mobile)))]
       let mut response = {
-        let asset = manager.get_asset(path)?;
-        builder = builder.mimetype(&asset.mime_type);
-        if let Some(csp) = &asset.csp_header {
-          builder = builder.header("Content-Security-Policy", csp);
+        let accepts_brotli = manager.config().build.serve_compressed_assets
+          && request
+            .headers()
+            .get("Accept-Encoding")
+            .and_then(|encoding| encoding.to_str().ok())
+            .map_or(false, |encoding| encoding.contains("br"));
+        match accepts_brotli
+          .then(|| manager.get_compressed_asset(path.clone()))
+          .flatten()
+        {
+          Some((bytes, mime_type)) => builder
+            .mimetype(&mime_type)
+            .header("Content-Encoding", "br")
+            .body(bytes)?,
+          None => {
+            let asset = manager.get_window_asset(path, Some(&window_label))?;
+            builder = builder.mimetype(&asset.mime_type);
+            if let Some(csp) = &asset.csp_header {
+              builder = builder.header("Content-Security-Policy", csp);
+            }
+            builder.body(asset.bytes)?
+          }
         }
-        builder.body(asset.bytes)?
       };
       if let Some(handler) = &web_resource_request_handler {
         handler(request, &mut response);
@@ -1025,7 +1171,7 @@ impl<R: Runtime> WindowManager<R> {
     }
 
     #[cfg(feature = "window-data-url")]
-    if let Some(csp) = self.csp() {
+    if let Some(csp) = self.csp(Some(&pending.label)) {
       if url.scheme() == "data" {
         if let Ok(data_url) = data_url::DataUrl::process(url.as_str()) {
           let (body, _) = data_url.decode_to_vec().unwrap();
@@ -1100,6 +1246,27 @@ impl<R: Runtime> WindowManager<R> {
         BaseDirectory::LocalData,
       );
       if let Ok(user_data_dir) = local_app_data {
+        // a separate data directory gives the webview its own web context or WebView2 environment
+        let user_data_dir = if self.inner.config.tauri.process_isolation
+          || pending.webview_attributes.process_isolation
+        {
+          use sha2::Digest;
+          let name = pending.label.replace(
+            |c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "_",
+          );
+          // the hash of the label keeps the directories of labels sanitized to the same name apart
+          let hash = sha2::Sha256::digest(pending.label.as_bytes());
+          let hash = hash[..8]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+          user_data_dir
+            .join("webviews")
+            .join(format!("{name}-{hash}"))
+        } else {
+          user_data_dir
+        };
         pending.webview_attributes.data_directory = Some(user_data_dir);
       }
     }
@@ -1114,6 +1281,9 @@ impl<R: Runtime> WindowManager<R> {
     #[cfg(feature = "isolation")]
     let pattern = self.pattern().clone();
     let navigation_handler = pending.navigation_handler.take();
+    // a kiosk window cannot navigate away from the origin of its url
+    let kiosk_origin = pending.webview_attributes.kiosk.then(|| url.origin());
+    let manager = self.clone();
     pending.navigation_handler = Some(Box::new(move |url| {
       // always allow navigation events for the isolation iframe and do not emit them for consumers
       #[cfg(feature = "isolation")]
@@ -1124,6 +1294,12 @@ impl<R: Runtime> WindowManager<R> {
           return true;
         }
       }
+      if let Some(origin) = &kiosk_origin {
+        if &url.origin() != origin {
+          return false;
+        }
+      }
+      crate::startup::record(&manager, crate::startup::StartupPhase::FirstNavigation);
       if let Some(handler) = &navigation_handler {
         handler(url)
       } else {
@@ -1200,6 +1376,24 @@ impl<R: Runtime> WindowManager<R> {
 
   pub(crate) fn on_window_close(&self, label: &str) {
     self.windows_lock().remove(label);
+    #[cfg(feature = "isolation")]
+    self.inner.isolation_keys.lock().unwrap().remove(label);
+    self.inner.rate_limiter.remove_window(label);
+  }
+
+  /// Records an invoke of the command, rejecting it if the window exceeded the command rate limit.
+  pub(crate) fn check_rate_limit(&self, label: &str, command: &str) -> Result<(), InvokeRejection> {
+    match self.config().tauri.security.ipc_limits.rate_limit(command) {
+      Some(limit) => self
+        .inner
+        .rate_limiter
+        .check(label, command, limit, std::time::Instant::now())
+        .map_err(|retry_after| InvokeRejection::RateLimited {
+          command: command.into(),
+          retry_after: retry_after.as_millis() as u64,
+        }),
+      None => Ok(()),
+    }
   }
 
   pub fn emit_filter<S, F>(
@@ -1214,6 +1408,8 @@ impl<R: Runtime> WindowManager<R> {
     F: Fn(&Window<R>) -> bool,
   {
     assert_event_name_is_valid(event);
+    #[cfg(debug_assertions)]
+    self.validate_event_payload(event, Some(&serde_json::to_string(&payload)?))?;
     self
       .windows_lock()
       .values()
@@ -1245,8 +1441,25 @@ impl<R: Runtime> WindowManager<R> {
     self.inner.listeners.unlisten(handler_id)
   }
 
+  /// Validates the payload against the schema registered with [`crate::Builder::event_schema`].
+  #[cfg(debug_assertions)]
+  pub(crate) fn validate_event_payload(
+    &self,
+    event: &str,
+    payload: Option<&str>,
+  ) -> crate::Result<()> {
+    match self.state().try_get::<EventSchemas>() {
+      Some(schemas) => schemas.validate(event, payload),
+      None => Ok(()),
+    }
+  }
+
   pub fn trigger(&self, event: &str, window: Option<String>, data: Option<String>) {
     assert_event_name_is_valid(event);
+    #[cfg(debug_assertions)]
+    if let Err(e) = self.validate_event_payload(event, data.as_deref()) {
+      log::warn!("{e}");
+    }
     self.inner.listeners.trigger(event, window, data)
   }
 
@@ -1339,6 +1552,18 @@ fn on_window_event<R: Runtime>(
     WindowEvent::Destroyed => {
       window.emit(WINDOW_DESTROYED_EVENT, ())?;
       let label = window.label();
+      if let Some(channels) = manager.state().try_get::<crate::ipc::Channels>() {
+        channels.close_window(label);
+      }
+      if let Some(cancellations) = manager.state().try_get::<crate::ipc::Cancellations>() {
+        cancellations.cancel_window(label);
+      }
+      if let Some(listeners) = manager
+        .state()
+        .try_get::<crate::window::keyboard::KeyboardEventListeners>()
+      {
+        listeners.remove_window(label);
+      }
       let windows_map = manager.inner.windows.lock().unwrap();
       let windows = windows_map.values();
       for window in windows {
@@ -1428,7 +1653,12 @@ fn request_to_path(request: &tauri_runtime::http::Request, base_url: &str) -> St
 
 #[cfg(test)]
 mod tests {
-  use super::replace_with_callback;
+  use super::{replace_csp_nonce, replace_with_callback};
+  use std::collections::HashMap;
+  use tauri_utils::{
+    config::{Csp, CspDirectiveSources},
+    html::SCRIPT_NONCE_TOKEN,
+  };
 
   #[test]
   fn string_replace_with_callback() {
@@ -1446,4 +1676,56 @@ mod tests {
       assert_eq!(replace_with_callback(src, pattern, replacement), result);
     }
   }
+
+  #[test]
+  fn csp_nonce_and_hashes() {
+    let csp = Csp::Policy("default-src 'self'".into()).with_directive("script-src", ["https:"]);
+    let mut csp: HashMap<String, CspDirectiveSources> = csp.into();
+    let mut asset = format!(
+      r#"<script nonce="{SCRIPT_NONCE_TOKEN}"></script><script nonce="{SCRIPT_NONCE_TOKEN}"></script>"#
+    );
+
+    replace_csp_nonce(
+      &mut asset,
+      SCRIPT_NONCE_TOKEN,
+      &mut csp,
+      "script-src",
+      vec!["'sha256-hash'".into()],
+    );
+
+    assert!(!asset.contains(SCRIPT_NONCE_TOKEN));
+    let sources = Vec::<String>::from(csp["script-src"].clone());
+    assert_eq!(sources[..2], ["https:", "'self'"]);
+    assert_eq!(sources.len(), 5);
+    for source in &sources[2..4] {
+      let nonce = source
+        .strip_prefix("'nonce-")
+        .and_then(|s| s.strip_suffix('\''))
+        .unwrap();
+      assert!(asset.contains(&format!(r#"nonce="{nonce}""#)));
+    }
+    assert_eq!(sources[4], "'sha256-hash'");
+    assert_eq!(
+      Vec::<String>::from(csp["default-src"].clone()),
+      vec!["'self'"]
+    );
+  }
+
+  #[test]
+  fn csp_without_nonce_or_hashes() {
+    let mut csp: HashMap<String, CspDirectiveSources> =
+      Csp::Policy("default-src 'self'".into()).into();
+    let mut asset = "<script></script>".to_string();
+
+    replace_csp_nonce(
+      &mut asset,
+      SCRIPT_NONCE_TOKEN,
+      &mut csp,
+      "script-src",
+      Vec::new(),
+    );
+
+    assert_eq!(asset, "<script></script>");
+    assert!(!csp.contains_key("script-src"));
+  }
 }
@@ -22,6 +22,9 @@ pub enum Error {
   /// Cannot resolve current directory.
   #[error("failed to read current dir: {0}")]
   CurrentDir(std::io::Error),
+  /// Failed to extract a compressed resource.
+  #[error("failed to extract resource: {0}")]
+  ResourceExtraction(std::io::Error),
   /// Unknown path.
   #[cfg(not(target_os = "android"))]
   #[error("unknown path")]
@@ -232,6 +232,60 @@ impl<R: Runtime> PathResolver<R> {
     resolve_path::<R>(self, base_directory, Some(path.as_ref().to_path_buf()))
   }
 
+  /// Resolves a resource bundled with `tauri > bundle > compressResources` enabled.
+  ///
+  /// The resource is decompressed to the `resources` folder of the [`Self::app_cache_dir`] on first access,
+  /// and extracted again when the bundled file is newer than the cached one.
+  /// Uncompressed resources, such as the ones copied to the target directory in development, are returned as is.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let path = app.path().resolve_compressed_resource("assets/dictionary.db")?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(all(desktop, feature = "compression"))]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "compression")))]
+  pub fn resolve_compressed_resource<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+    use crate::utils::resources::{decompress_resource, COMPRESSED_RESOURCE_EXTENSION};
+
+    let path = path.as_ref();
+    let resource = self.resolve(path, BaseDirectory::Resource)?;
+    if resource.exists() {
+      return Ok(resource);
+    }
+
+    let mut file_name = resource
+      .file_name()
+      .ok_or(Error::NoBasename)?
+      .to_os_string();
+    file_name.push(".");
+    file_name.push(COMPRESSED_RESOURCE_EXTENSION);
+    let compressed = resource.with_file_name(file_name);
+
+    let extracted = self.app_cache_dir()?.join("resources").join(path);
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let up_to_date = match (modified(&extracted), modified(&compressed)) {
+      (Some(extracted), Some(compressed)) => extracted >= compressed,
+      _ => false,
+    };
+
+    if !up_to_date {
+      let parent = extracted.parent().ok_or(Error::NoParent)?;
+      std::fs::create_dir_all(parent).map_err(Error::ResourceExtraction)?;
+      // extract to a temporary file first so an interrupted extraction is not used later
+      let tmp = extracted.with_extension("partial");
+      decompress_resource(&compressed, &tmp).map_err(Error::ResourceExtraction)?;
+      std::fs::rename(&tmp, &extracted).map_err(Error::ResourceExtraction)?;
+    }
+
+    Ok(extracted)
+  }
+
   /// Parse the given path, resolving a [`BaseDirectory`] variable if the path starts with one.
   ///
   /// # Examples
@@ -32,9 +32,6 @@ pub enum Pattern<A: Assets = EmbeddedAssets> {
     ///
     /// This should be regenerated at runtime.
     key: String,
-
-    /// Cryptographically secure keys
-    crypto_keys: Box<tauri_utils::pattern::isolation::Keys>,
   },
 }
 
@@ -47,12 +44,10 @@ impl<A: Assets> Clone for Pattern<A> {
         assets,
         schema,
         key,
-        crypto_keys,
       } => Self::Isolation {
         assets: assets.clone(),
         schema: schema.clone(),
         key: key.clone(),
-        crypto_keys: crypto_keys.clone(),
       },
     }
   }
@@ -85,6 +80,7 @@ impl From<&Pattern> for PatternObject {
 }
 
 /// Where the JavaScript is injected to
+#[cfg(feature = "isolation")]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum IsolationSide {
@@ -95,6 +91,7 @@ pub(crate) enum IsolationSide {
   Secure,
 }
 
+#[cfg(feature = "isolation")]
 impl Default for IsolationSide {
   fn default() -> Self {
     Self::Original
@@ -0,0 +1,178 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The permissions declared by the app plugins.
+//!
+//! Plugins declare their permissions in a manifest collected at build time, see [`tauri_utils::plugin`].
+//! The commands of optional permissions are rejected until the permission is granted,
+//! either with [`PluginPermissions::grant`] or by the consent prompt registered with [`crate::Builder::on_permission_request`].
+
+use std::{
+  collections::HashSet,
+  fmt,
+  sync::{Arc, Mutex},
+};
+
+use tauri_utils::plugin::PluginPermissions as Manifests;
+pub use tauri_utils::plugin::{Permission, PermissionManifest};
+
+/// A request for the user consent to an optional plugin permission.
+#[derive(Debug, Clone)]
+pub struct PermissionRequest {
+  /// The plugin that declares the permission.
+  pub plugin: String,
+  /// The label of the window that invoked a command of the permission.
+  pub window: String,
+  /// The requested permission.
+  pub permission: Permission,
+}
+
+pub(crate) type ConsentPrompt = dyn Fn(&PermissionRequest) -> bool + Send + Sync;
+
+struct Inner {
+  manifests: Manifests,
+  granted: Mutex<HashSet<(String, String)>>,
+  prompt: Option<Box<ConsentPrompt>>,
+}
+
+/// The permission manifests of the app plugins and the optional permissions granted at runtime.
+#[derive(Clone)]
+pub struct PluginPermissions {
+  inner: Arc<Inner>,
+}
+
+impl fmt::Debug for PluginPermissions {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("PluginPermissions")
+      .field("manifests", &self.inner.manifests)
+      .field("granted", &self.inner.granted)
+      .finish()
+  }
+}
+
+impl PluginPermissions {
+  pub(crate) fn new(manifests: Manifests, prompt: Option<Box<ConsentPrompt>>) -> Self {
+    Self {
+      inner: Arc::new(Inner {
+        manifests,
+        granted: Default::default(),
+        prompt,
+      }),
+    }
+  }
+
+  /// The permission manifests of the app plugins, keyed by plugin name.
+  pub fn manifests(&self) -> &Manifests {
+    &self.inner.manifests
+  }
+
+  /// Whether the optional permission of the plugin was granted.
+  pub fn is_granted(&self, plugin: &str, permission: &str) -> bool {
+    self
+      .inner
+      .granted
+      .lock()
+      .unwrap()
+      .contains(&(plugin.into(), permission.into()))
+  }
+
+  /// Grants an optional permission of the plugin, e.g. to restore a consent the app persisted.
+  pub fn grant(&self, plugin: &str, permission: &str) {
+    self
+      .inner
+      .granted
+      .lock()
+      .unwrap()
+      .insert((plugin.into(), permission.into()));
+  }
+
+  /// Revokes an optional permission of the plugin.
+  pub fn revoke(&self, plugin: &str, permission: &str) {
+    self
+      .inner
+      .granted
+      .lock()
+      .unwrap()
+      .remove(&(plugin.into(), permission.into()));
+  }
+
+  /// Whether the window can invoke the plugin command,
+  /// prompting the user for consent if the command requires an optional permission that was not granted yet.
+  ///
+  /// Returns the denied permission identifier.
+  pub(crate) fn check_command(
+    &self,
+    window: &str,
+    plugin: &str,
+    command: &str,
+  ) -> Result<(), String> {
+    let permission = match self
+      .inner
+      .manifests
+      .get(plugin)
+      .and_then(|manifest| manifest.optional_permission(command))
+    {
+      Some(permission) => permission,
+      None => return Ok(()),
+    };
+
+    if self.is_granted(plugin, &permission.identifier) {
+      return Ok(());
+    }
+
+    let request = PermissionRequest {
+      plugin: plugin.into(),
+      window: window.into(),
+      permission: permission.clone(),
+    };
+    if self
+      .inner
+      .prompt
+      .as_ref()
+      .map_or(false, |prompt| prompt(&request))
+    {
+      self.grant(plugin, &permission.identifier);
+      Ok(())
+    } else {
+      Err(permission.identifier.clone())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn permissions(consent: bool) -> PluginPermissions {
+    let mut manifests = Manifests::new();
+    manifests.insert(
+      "fs".into(),
+      PermissionManifest {
+        permissions: vec![Permission {
+          identifier: "write".into(),
+          description: None,
+          commands: vec!["write_file".into()],
+          optional: true,
+        }],
+      },
+    );
+    PluginPermissions::new(manifests, Some(Box::new(move |_| consent)))
+  }
+
+  #[test]
+  fn optional_permission_consent() {
+    let denied = permissions(false);
+    assert!(denied.check_command("main", "fs", "read_file").is_ok());
+    assert_eq!(
+      denied.check_command("main", "fs", "write_file"),
+      Err("write".into())
+    );
+    denied.grant("fs", "write");
+    assert!(denied.check_command("main", "fs", "write_file").is_ok());
+
+    let allowed = permissions(true);
+    assert!(allowed.check_command("main", "fs", "write_file").is_ok());
+    assert!(allowed.is_granted("fs", "write"));
+  }
+}
@@ -12,12 +12,16 @@ use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
 use tauri_macros::default_runtime;
 
-use std::{collections::HashMap, fmt, result::Result as StdResult, sync::Arc};
+use std::{fmt, result::Result as StdResult, sync::Arc};
 
 /// Mobile APIs.
 #[cfg(mobile)]
 pub mod mobile;
 
+#[cfg(feature = "dynamic-plugins")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "dynamic-plugins")))]
+pub mod dynamic;
+
 /// The result type of Tauri plugin module.
 pub type Result<T> = StdResult<T, Box<dyn std::error::Error>>;
 
@@ -26,6 +30,13 @@ pub trait Plugin<R: Runtime>: Send {
   /// The plugin name. Used as key on the plugin config object.
   fn name(&self) -> &'static str;
 
+  /// The names of the plugins this plugin depends on.
+  ///
+  /// The dependencies are initialized before this plugin.
+  fn dependencies(&self) -> &[&'static str] {
+    &[]
+  }
+
   /// Initializes the plugin.
   #[allow(unused_variables)]
   fn initialize(&mut self, app: &AppHandle<R>, config: JsonValue) -> Result<()> {
@@ -56,6 +67,12 @@ pub trait Plugin<R: Runtime>: Send {
   #[allow(unused_variables)]
   fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {}
 
+  /// Callback invoked when the plugin configuration changes at runtime, see [`AppHandle::reconfigure_plugin`].
+  #[allow(unused_variables)]
+  fn on_config_changed(&mut self, app: &AppHandle<R>, config: JsonValue) -> Result<()> {
+    Ok(())
+  }
+
   /// Extend commands to [`crate::Builder::invoke_handler`].
   #[allow(unused_variables)]
   fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
@@ -66,6 +83,7 @@ pub trait Plugin<R: Runtime>: Send {
 type SetupHook<R, C> = dyn FnOnce(&AppHandle<R>, PluginApi<R, C>) -> Result<()> + Send;
 type OnWebviewReady<R> = dyn FnMut(Window<R>) + Send;
 type OnEvent<R> = dyn FnMut(&AppHandle<R>, &RunEvent) + Send;
+type OnConfigChanged<R, C> = dyn FnMut(&AppHandle<R>, C) -> Result<()> + Send;
 type OnPageLoad<R> = dyn FnMut(Window<R>, PageLoadPayload) + Send;
 type OnDrop<R> = dyn FnOnce(AppHandle<R>) + Send;
 
@@ -189,12 +207,14 @@ impl<R: Runtime, C: DeserializeOwned> PluginApi<R, C> {
 /// ```
 pub struct Builder<R: Runtime, C: DeserializeOwned = ()> {
   name: &'static str,
+  dependencies: Vec<&'static str>,
   invoke_handler: Box<InvokeHandler<R>>,
   setup: Option<Box<SetupHook<R, C>>>,
   js_init_script: Option<String>,
   on_page_load: Box<OnPageLoad<R>>,
   on_webview_ready: Box<OnWebviewReady<R>>,
   on_event: Box<OnEvent<R>>,
+  on_config_changed: Option<Box<OnConfigChanged<R, C>>>,
   on_drop: Option<Box<OnDrop<R>>>,
 }
 
@@ -203,12 +223,14 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
   pub fn new(name: &'static str) -> Self {
     Self {
       name,
+      dependencies: Vec::new(),
       setup: None,
       js_init_script: None,
       invoke_handler: Box::new(|_| false),
       on_page_load: Box::new(|_, _| ()),
       on_webview_ready: Box::new(|_| ()),
       on_event: Box::new(|_, _| ()),
+      on_config_changed: None,
       on_drop: None,
     }
   }
@@ -393,6 +415,45 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
     self
   }
 
+  /// Callback invoked when the plugin configuration changes at runtime, see [`AppHandle::reconfigure_plugin`].
+  ///
+  /// The new configuration is deserialized the same way as the configuration given to [`Self::setup`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use tauri::{plugin::{Builder, TauriPlugin}, Manager, Runtime};
+  /// use std::sync::Mutex;
+  ///
+  /// #[derive(Default, serde::Deserialize)]
+  /// struct Config {
+  ///   proxy: Option<String>,
+  /// }
+  ///
+  /// struct Proxy(Mutex<Option<String>>);
+  ///
+  /// fn init<R: Runtime>() -> TauriPlugin<R, Config> {
+  ///   Builder::new("example")
+  ///     .setup(|app, api| {
+  ///       app.manage(Proxy(Mutex::new(api.config().proxy.clone())));
+  ///       Ok(())
+  ///     })
+  ///     .on_config_changed(|app, config| {
+  ///       *app.state::<Proxy>().0.lock().unwrap() = config.proxy;
+  ///       Ok(())
+  ///     })
+  ///     .build()
+  /// }
+  /// ```
+  #[must_use]
+  pub fn on_config_changed<F>(mut self, on_config_changed: F) -> Self
+  where
+    F: FnMut(&AppHandle<R>, C) -> Result<()> + Send + 'static,
+  {
+    self.on_config_changed.replace(Box::new(on_config_changed));
+    self
+  }
+
   /// Callback invoked when the plugin is dropped.
   ///
   /// # Examples
@@ -418,10 +479,33 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
     self
   }
 
+  /// Declares a dependency on the plugin with the given name.
+  ///
+  /// The dependency is initialized before this plugin,
+  /// and the app fails to start if it is not registered or if the dependencies form a cycle.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use tauri::{plugin::{Builder, TauriPlugin}, Runtime};
+  ///
+  /// fn init<R: Runtime>() -> TauriPlugin<R> {
+  ///   Builder::new("example")
+  ///     .requires("fs")
+  ///     .build()
+  /// }
+  /// ```
+  #[must_use]
+  pub fn requires(mut self, plugin: &'static str) -> Self {
+    self.dependencies.push(plugin);
+    self
+  }
+
   /// Builds the [TauriPlugin].
   pub fn build(self) -> TauriPlugin<R, C> {
     TauriPlugin {
       name: self.name,
+      dependencies: self.dependencies,
       app: None,
       invoke_handler: self.invoke_handler,
       setup: self.setup,
@@ -429,6 +513,7 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
       on_page_load: self.on_page_load,
       on_webview_ready: self.on_webview_ready,
       on_event: self.on_event,
+      on_config_changed: self.on_config_changed,
       on_drop: self.on_drop,
     }
   }
@@ -437,6 +522,7 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
 /// Plugin struct that is returned by the [`Builder`]. Should only be constructed through the builder.
 pub struct TauriPlugin<R: Runtime, C: DeserializeOwned = ()> {
   name: &'static str,
+  dependencies: Vec<&'static str>,
   app: Option<AppHandle<R>>,
   invoke_handler: Box<InvokeHandler<R>>,
   setup: Option<Box<SetupHook<R, C>>>,
@@ -444,6 +530,7 @@ pub struct TauriPlugin<R: Runtime, C: DeserializeOwned = ()> {
   on_page_load: Box<OnPageLoad<R>>,
   on_webview_ready: Box<OnWebviewReady<R>>,
   on_event: Box<OnEvent<R>>,
+  on_config_changed: Option<Box<OnConfigChanged<R, C>>>,
   on_drop: Option<Box<OnDrop<R>>>,
 }
 
@@ -460,6 +547,10 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
     self.name
   }
 
+  fn dependencies(&self) -> &[&'static str] {
+    &self.dependencies
+  }
+
   fn initialize(&mut self, app: &AppHandle<R>, config: JsonValue) -> Result<()> {
     self.app.replace(app.clone());
     if let Some(s) = self.setup.take() {
@@ -492,6 +583,13 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
     (self.on_event)(app, event)
   }
 
+  fn on_config_changed(&mut self, app: &AppHandle<R>, config: JsonValue) -> Result<()> {
+    if let Some(on_config_changed) = &mut self.on_config_changed {
+      on_config_changed(app, serde_json::from_value(config)?)?;
+    }
+    Ok(())
+  }
+
   fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
     (self.invoke_handler)(invoke)
   }
@@ -500,22 +598,24 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
 /// Plugin collection type.
 #[default_runtime(crate::Wry, wry)]
 pub(crate) struct PluginStore<R: Runtime> {
-  store: HashMap<&'static str, Box<dyn Plugin<R>>>,
+  /// The plugins in registration order, sorted by dependencies on initialization.
+  store: Vec<Box<dyn Plugin<R>>>,
 }
 
 impl<R: Runtime> fmt::Debug for PluginStore<R> {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     f.debug_struct("PluginStore")
-      .field("plugins", &self.store.keys())
+      .field(
+        "plugins",
+        &self.store.iter().map(|p| p.name()).collect::<Vec<_>>(),
+      )
       .finish()
   }
 }
 
 impl<R: Runtime> Default for PluginStore<R> {
   fn default() -> Self {
-    Self {
-      store: HashMap::new(),
-    }
+    Self { store: Vec::new() }
   }
 }
 
@@ -524,21 +624,105 @@ impl<R: Runtime> PluginStore<R> {
   ///
   /// Returns `true` if a plugin with the same name is already in the store.
   pub fn register<P: Plugin<R> + 'static>(&mut self, plugin: P) -> bool {
-    self.store.insert(plugin.name(), Box::new(plugin)).is_some()
+    match self.store.iter_mut().find(|p| p.name() == plugin.name()) {
+      Some(existing) => {
+        *existing = Box::new(plugin);
+        true
+      }
+      None => {
+        self.store.push(Box::new(plugin));
+        false
+      }
+    }
   }
 
   /// Removes the plugin with the given name from the store.
   pub fn unregister(&mut self, plugin: &'static str) -> bool {
-    self.store.remove(plugin).is_some()
+    let len = self.store.len();
+    self.store.retain(|p| p.name() != plugin);
+    len != self.store.len()
+  }
+
+  pub(crate) fn contains(&self, plugin: &str) -> bool {
+    self.store.iter().any(|p| p.name() == plugin)
+  }
+
+  /// Ensures the dependencies of a plugin registered at runtime are already in the store.
+  pub(crate) fn check_dependencies(&self, plugin: &dyn Plugin<R>) -> crate::Result<()> {
+    match plugin
+      .dependencies()
+      .iter()
+      .find(|dependency| !self.contains(dependency))
+    {
+      Some(dependency) => Err(crate::Error::PluginDependencyMissing(
+        plugin.name().into(),
+        dependency.to_string(),
+      )),
+      None => Ok(()),
+    }
   }
 
-  /// Initializes all plugins in the store.
+  /// Sorts the plugins so each plugin comes after its dependencies, keeping the registration order otherwise.
+  fn sort(&mut self) -> crate::Result<()> {
+    fn visit<R: Runtime>(
+      index: usize,
+      store: &[Box<dyn Plugin<R>>],
+      visiting: &mut Vec<usize>,
+      sorted: &mut Vec<usize>,
+    ) -> crate::Result<()> {
+      if sorted.contains(&index) {
+        return Ok(());
+      }
+      if let Some(position) = visiting.iter().position(|i| *i == index) {
+        let cycle = visiting[position..]
+          .iter()
+          .chain(std::iter::once(&index))
+          .map(|i| store[*i].name())
+          .collect::<Vec<_>>();
+        return Err(crate::Error::PluginDependencyCycle(cycle.join(" -> ")));
+      }
+
+      visiting.push(index);
+      for dependency in store[index].dependencies() {
+        match store.iter().position(|p| p.name() == *dependency) {
+          Some(dependency) => visit(dependency, store, visiting, sorted)?,
+          None => {
+            return Err(crate::Error::PluginDependencyMissing(
+              store[index].name().into(),
+              dependency.to_string(),
+            ))
+          }
+        }
+      }
+      visiting.pop();
+      sorted.push(index);
+      Ok(())
+    }
+
+    let mut sorted = Vec::with_capacity(self.store.len());
+    for index in 0..self.store.len() {
+      visit(index, &self.store, &mut Vec::new(), &mut sorted)?;
+    }
+
+    let mut plugins = std::mem::take(&mut self.store)
+      .into_iter()
+      .map(Some)
+      .collect::<Vec<_>>();
+    self.store = sorted
+      .into_iter()
+      .filter_map(|index| plugins[index].take())
+      .collect();
+    Ok(())
+  }
+
+  /// Initializes all plugins in the store, after their dependencies.
   pub(crate) fn initialize(
     &mut self,
     app: &AppHandle<R>,
     config: &PluginConfig,
   ) -> crate::Result<()> {
-    self.store.values_mut().try_for_each(|plugin| {
+    self.sort()?;
+    self.store.iter_mut().try_for_each(|plugin| {
       plugin
         .initialize(
           app,
@@ -552,7 +736,7 @@ impl<R: Runtime> PluginStore<R> {
   pub(crate) fn initialization_script(&self) -> String {
     self
       .store
-      .values()
+      .iter()
       .filter_map(|p| p.initialization_script())
       .fold(String::new(), |acc, script| {
         format!("{acc}\n(function () {{ {script} }})();")
@@ -563,7 +747,7 @@ impl<R: Runtime> PluginStore<R> {
   pub(crate) fn created(&mut self, window: Window<R>) {
     self
       .store
-      .values_mut()
+      .iter_mut()
       .for_each(|plugin| plugin.created(window.clone()))
   }
 
@@ -571,7 +755,7 @@ impl<R: Runtime> PluginStore<R> {
   pub(crate) fn on_page_load(&mut self, window: Window<R>, payload: PageLoadPayload) {
     self
       .store
-      .values_mut()
+      .iter_mut()
       .for_each(|plugin| plugin.on_page_load(window.clone(), payload.clone()))
   }
 
@@ -579,15 +763,30 @@ impl<R: Runtime> PluginStore<R> {
   pub(crate) fn on_event(&mut self, app: &AppHandle<R>, event: &RunEvent) {
     self
       .store
-      .values_mut()
+      .iter_mut()
       .for_each(|plugin| plugin.on_event(app, event))
   }
 
+  /// Runs the on_config_changed hook of the plugin with the given name.
+  pub(crate) fn reconfigure(
+    &mut self,
+    app: &AppHandle<R>,
+    plugin: &str,
+    config: JsonValue,
+  ) -> crate::Result<()> {
+    match self.store.iter_mut().find(|p| p.name() == plugin) {
+      Some(plugin) => plugin
+        .on_config_changed(app, config)
+        .map_err(|e| crate::Error::PluginReconfiguration(plugin.name().into(), e.to_string())),
+      None => Err(crate::Error::PluginNotFound(plugin.into())),
+    }
+  }
+
   /// Runs the plugin `extend_api` hook if it exists. Returns whether the invoke message was handled or not.
   ///
   /// The message is not handled when the plugin exists **and** the command does not.
   pub(crate) fn extend_api(&mut self, plugin: &str, invoke: Invoke<R>) -> bool {
-    if let Some(plugin) = self.store.get_mut(plugin) {
+    if let Some(plugin) = self.store.iter_mut().find(|p| p.name() == plugin) {
       plugin.extend_api(invoke)
     } else {
       invoke.resolver.reject(format!("plugin {plugin} not found"));
@@ -595,3 +794,47 @@ impl<R: Runtime> PluginStore<R> {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{Builder, PluginStore};
+  use crate::test::MockRuntime;
+
+  fn store(plugins: &[(&'static str, &[&'static str])]) -> PluginStore<MockRuntime> {
+    let mut store = PluginStore::default();
+    for (name, dependencies) in plugins {
+      let mut builder = Builder::<MockRuntime>::new(name);
+      for dependency in dependencies.iter() {
+        builder = builder.requires(dependency);
+      }
+      store.register(builder.build());
+    }
+    store
+  }
+
+  fn names(store: &PluginStore<MockRuntime>) -> Vec<&'static str> {
+    store.store.iter().map(|p| p.name()).collect()
+  }
+
+  #[test]
+  fn sorts_by_dependencies() {
+    let mut store = store(&[("a", &["c"]), ("b", &[]), ("c", &["b"]), ("d", &[])]);
+    store.sort().unwrap();
+    assert_eq!(names(&store), vec!["b", "c", "a", "d"]);
+  }
+
+  #[test]
+  fn dependency_errors() {
+    let mut missing = store(&[("a", &["fs"])]);
+    assert!(matches!(
+      missing.sort(),
+      Err(crate::Error::PluginDependencyMissing(plugin, dependency)) if plugin == "a" && dependency == "fs"
+    ));
+
+    let mut cycle = store(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+    assert!(matches!(
+      cycle.sort(),
+      Err(crate::Error::PluginDependencyCycle(cycle)) if cycle == "a -> b -> c -> a"
+    ));
+  }
+}
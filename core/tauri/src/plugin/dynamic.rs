@@ -0,0 +1,376 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Plugins loaded at runtime from shared libraries.
+//!
+//! Enabled by the `dynamic-plugins` Cargo feature and [`crate::Builder::dynamic_plugins`].
+//! Every shared library (`.so`, `.dylib` or `.dll` depending on the platform) in the plugins directory
+//! must be signed: a `<library>.sig` file next to it contains the base64 encoded Ed25519 signature of the library,
+//! verified against the public key given to the builder. A library with a missing or invalid signature makes [`crate::Builder::build`] fail.
+//! The library is read once, and the verified content is copied to a private temporary directory where it is loaded from,
+//! so the file can't be swapped between the verification and the load.
+//!
+//! A library exposes its plugin through a stable C ABI: it exports a [`DESCRIPTOR_SYMBOL`] function returning a [`PluginDescriptor`].
+//! Loaded libraries are never unloaded.
+//!
+//! # Examples
+//!
+//! A dynamic plugin written in Rust, built as a `cdylib`:
+//!
+//! ```rust,no_run
+//! use std::ffi::{c_char, CStr, CString};
+//! use tauri::plugin::dynamic::{PluginDescriptor, ABI_VERSION, INVOKE_RESOLVED, INVOKE_UNHANDLED};
+//!
+//! unsafe extern "C" fn invoke(
+//!   command: *const c_char,
+//!   _payload: *const c_char,
+//!   response: *mut *mut c_char,
+//! ) -> i32 {
+//!   match CStr::from_ptr(command).to_str() {
+//!     Ok("ping") => {
+//!       *response = CString::new(r#""pong""#).unwrap().into_raw();
+//!       INVOKE_RESOLVED
+//!     }
+//!     _ => INVOKE_UNHANDLED,
+//!   }
+//! }
+//!
+//! unsafe extern "C" fn free_string(value: *mut c_char) {
+//!   drop(CString::from_raw(value));
+//! }
+//!
+//! static DESCRIPTOR: PluginDescriptor = PluginDescriptor {
+//!   abi_version: ABI_VERSION,
+//!   name: b"ping\0".as_ptr() as *const c_char,
+//!   initialize: None,
+//!   invoke,
+//!   free_string,
+//! };
+//!
+//! #[no_mangle]
+//! pub extern "C" fn tauri_plugin_descriptor() -> *const PluginDescriptor {
+//!   &DESCRIPTOR
+//! }
+//! ```
+
+use std::{
+  ffi::{c_char, c_void, CStr, CString},
+  fs::read,
+  path::{Path, PathBuf},
+  ptr,
+};
+
+use base64::Engine;
+use serde_json::Value as JsonValue;
+
+use super::{Plugin, Result};
+use crate::{AppHandle, Invoke, Runtime};
+
+/// The version of the C ABI described by [`PluginDescriptor`].
+pub const ABI_VERSION: u32 = 1;
+
+/// The symbol of the `extern "C" fn() -> *const PluginDescriptor` function exported by a dynamic plugin library.
+pub const DESCRIPTOR_SYMBOL: &str = "tauri_plugin_descriptor";
+
+/// Returned by [`PluginDescriptor::invoke`] when the command succeeded, the response is the JSON value to resolve.
+pub const INVOKE_RESOLVED: i32 = 0;
+/// Returned by [`PluginDescriptor::invoke`] when the command failed, the response is the JSON value to reject.
+pub const INVOKE_REJECTED: i32 = 1;
+/// Returned by [`PluginDescriptor::invoke`] when the plugin doesn't have the command, the response is ignored.
+pub const INVOKE_UNHANDLED: i32 = 2;
+
+/// The entry point of a dynamic plugin.
+///
+/// All strings are NUL-terminated UTF-8 and the functions might be called from any thread.
+#[repr(C)]
+pub struct PluginDescriptor {
+  /// Must be [`ABI_VERSION`].
+  pub abi_version: u32,
+  /// The plugin name, used as the `plugin:<name>|<command>` IPC prefix and as key on the plugin config object.
+  pub name: *const c_char,
+  /// Initializes the plugin with its JSON configuration. Returns `0` on success.
+  pub initialize: Option<unsafe extern "C" fn(config: *const c_char) -> i32>,
+  /// Runs a command with its JSON arguments, writing a JSON response allocated by the plugin.
+  /// Returns [`INVOKE_RESOLVED`], [`INVOKE_REJECTED`] or [`INVOKE_UNHANDLED`].
+  pub invoke: unsafe extern "C" fn(
+    command: *const c_char,
+    payload: *const c_char,
+    response: *mut *mut c_char,
+  ) -> i32,
+  /// Releases a response written by `invoke`.
+  pub free_string: unsafe extern "C" fn(value: *mut c_char),
+}
+
+// SAFETY: the descriptor is immutable and its functions are required to be thread safe.
+unsafe impl Sync for PluginDescriptor {}
+unsafe impl Send for PluginDescriptor {}
+
+/// A plugin loaded from a shared library.
+pub(crate) struct DynamicPlugin {
+  name: &'static str,
+  descriptor: &'static PluginDescriptor,
+}
+
+impl DynamicPlugin {
+  fn invoke(&self, command: &str, payload: &JsonValue) -> Option<InvokeResult> {
+    let command = CString::new(command).ok()?;
+    let payload = CString::new(payload.to_string()).ok()?;
+    let mut response = ptr::null_mut();
+    // SAFETY: the pointers are valid for the duration of the call and the response is released by the plugin
+    let (status, response) = unsafe {
+      let status = (self.descriptor.invoke)(command.as_ptr(), payload.as_ptr(), &mut response);
+      let value = if response.is_null() {
+        JsonValue::Null
+      } else {
+        let value = serde_json::from_slice(CStr::from_ptr(response).to_bytes())
+          .unwrap_or_else(|e| JsonValue::String(format!("invalid plugin response: {e}")));
+        (self.descriptor.free_string)(response);
+        value
+      };
+      (status, value)
+    };
+    match status {
+      INVOKE_RESOLVED => Some(Ok(response)),
+      INVOKE_UNHANDLED => None,
+      _ => Some(Err(response)),
+    }
+  }
+}
+
+type InvokeResult = std::result::Result<JsonValue, JsonValue>;
+
+impl<R: Runtime> Plugin<R> for DynamicPlugin {
+  fn name(&self) -> &'static str {
+    self.name
+  }
+
+  fn initialize(&mut self, _app: &AppHandle<R>, config: JsonValue) -> Result<()> {
+    if let Some(initialize) = self.descriptor.initialize {
+      let config = CString::new(config.to_string())?;
+      // SAFETY: the config pointer is valid for the duration of the call
+      let status = unsafe { initialize(config.as_ptr()) };
+      if status != 0 {
+        return Err(
+          format!(
+            "plugin `{}` failed to initialize with status {status}",
+            self.name
+          )
+          .into(),
+        );
+      }
+    }
+    Ok(())
+  }
+
+  fn extend_api(&mut self, invoke: Invoke<R>) -> bool {
+    match self.invoke(invoke.message.command(), invoke.message.payload()) {
+      Some(Ok(value)) => invoke.resolver.resolve(value),
+      Some(Err(error)) => invoke.resolver.reject(error),
+      None => return false,
+    }
+    true
+  }
+}
+
+/// Loads the signed plugin libraries of the directory, sorted by file name.
+pub(crate) fn load_dir(directory: &Path, public_key: &str) -> crate::Result<Vec<DynamicPlugin>> {
+  let error = |path: &Path, reason: String| crate::Error::DynamicPlugin(path.to_path_buf(), reason);
+
+  let public_key = base64::engine::general_purpose::STANDARD
+    .decode(public_key)
+    .map_err(|e| error(directory, format!("invalid public key: {e}")))?;
+
+  let mut libraries = std::fs::read_dir(directory)
+    .map_err(|e| error(directory, e.to_string()))?
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| {
+      path.is_file()
+        && path.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+    })
+    .collect::<Vec<_>>();
+  libraries.sort();
+
+  // the verified libraries are loaded from a directory only writable by the current user
+  let private_dir = tempfile::Builder::new()
+    .prefix("tauri-plugins")
+    .tempdir()
+    .map_err(|e| {
+      error(
+        directory,
+        format!("failed to create the private directory: {e}"),
+      )
+    })?;
+
+  let mut plugins = Vec::new();
+  for path in libraries {
+    let library = read(&path).map_err(|e| error(&path, e.to_string()))?;
+    verify_signature(&path, &library, &public_key).map_err(|reason| error(&path, reason))?;
+    let private_path = private_dir.path().join(path.file_name().unwrap());
+    std::fs::write(&private_path, &library).map_err(|e| error(&path, e.to_string()))?;
+    plugins.push(load(&private_path).map_err(|reason| error(&path, reason))?);
+  }
+  Ok(plugins)
+}
+
+/// Verifies the content of the library against its `<library>.sig` signature.
+fn verify_signature(
+  path: &Path,
+  library: &[u8],
+  public_key: &[u8],
+) -> std::result::Result<(), String> {
+  let mut signature_path = path.as_os_str().to_owned();
+  signature_path.push(".sig");
+  let signature_path = PathBuf::from(signature_path);
+
+  let signature = read(&signature_path)
+    .map_err(|e| format!("failed to read signature {}: {e}", signature_path.display()))?;
+  let signature = base64::engine::general_purpose::STANDARD
+    .decode(String::from_utf8_lossy(&signature).trim())
+    .map_err(|e| format!("invalid signature: {e}"))?;
+
+  ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+    .verify(library, &signature)
+    .map_err(|_| "signature verification failed".into())
+}
+
+fn load(path: &Path) -> std::result::Result<DynamicPlugin, String> {
+  let symbol = library::open(path)?.symbol(DESCRIPTOR_SYMBOL)?;
+  // SAFETY: the library contract is that the symbol is a `extern "C" fn() -> *const PluginDescriptor`
+  // returning a pointer valid while the library is loaded, which is forever
+  let descriptor = unsafe {
+    let entry: extern "C" fn() -> *const PluginDescriptor = std::mem::transmute(symbol);
+    entry()
+      .as_ref()
+      .ok_or_else(|| "the plugin descriptor is null".to_string())?
+  };
+
+  if descriptor.abi_version != ABI_VERSION {
+    return Err(format!(
+      "unsupported ABI version {}, expected {ABI_VERSION}",
+      descriptor.abi_version
+    ));
+  }
+  if descriptor.name.is_null() {
+    return Err("the plugin name is null".into());
+  }
+  // SAFETY: checked for null above, the string is NUL-terminated by contract
+  let name = unsafe { CStr::from_ptr(descriptor.name) }
+    .to_str()
+    .map_err(|e| format!("invalid plugin name: {e}"))?;
+
+  Ok(DynamicPlugin {
+    name: Box::leak(name.to_string().into_boxed_str()),
+    descriptor,
+  })
+}
+
+#[cfg(unix)]
+mod library {
+  use super::*;
+  use std::os::unix::ffi::OsStrExt;
+
+  pub struct Library(*mut c_void);
+
+  fn last_error() -> String {
+    // SAFETY: dlerror returns null or a NUL-terminated string
+    unsafe {
+      let error = libc::dlerror();
+      if error.is_null() {
+        "unknown error".into()
+      } else {
+        CStr::from_ptr(error).to_string_lossy().into_owned()
+      }
+    }
+  }
+
+  pub fn open(path: &Path) -> std::result::Result<Library, String> {
+    let path = CString::new(path.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+    // SAFETY: the path is a valid NUL-terminated string
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if handle.is_null() {
+      Err(last_error())
+    } else {
+      Ok(Library(handle))
+    }
+  }
+
+  impl Library {
+    pub fn symbol(&self, name: &str) -> std::result::Result<*mut c_void, String> {
+      let name = CString::new(name).map_err(|e| e.to_string())?;
+      // SAFETY: the handle was returned by dlopen and is never closed
+      let symbol = unsafe { libc::dlsym(self.0, name.as_ptr()) };
+      if symbol.is_null() {
+        Err(last_error())
+      } else {
+        Ok(symbol)
+      }
+    }
+  }
+}
+
+#[cfg(windows)]
+mod library {
+  use super::*;
+  use std::os::windows::ffi::OsStrExt;
+  use windows::{
+    core::{PCSTR, PCWSTR},
+    Win32::{
+      Foundation::HINSTANCE,
+      System::LibraryLoader::{GetProcAddress, LoadLibraryW},
+    },
+  };
+
+  pub struct Library(HINSTANCE);
+
+  pub fn open(path: &Path) -> std::result::Result<Library, String> {
+    let path = path
+      .as_os_str()
+      .encode_wide()
+      .chain(std::iter::once(0))
+      .collect::<Vec<u16>>();
+    // SAFETY: the path is a valid NUL-terminated wide string
+    unsafe { LoadLibraryW(PCWSTR::from_raw(path.as_ptr())) }
+      .map(Library)
+      .map_err(|e| e.to_string())
+  }
+
+  impl Library {
+    pub fn symbol(&self, name: &str) -> std::result::Result<*mut c_void, String> {
+      let name = CString::new(name).map_err(|e| e.to_string())?;
+      // SAFETY: the module was returned by LoadLibraryW and is never freed
+      unsafe { GetProcAddress(self.0, PCSTR::from_raw(name.as_ptr() as *const u8)) }
+        .map(|symbol| symbol as *mut c_void)
+        .ok_or_else(|| windows::core::Error::from_win32().to_string())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::verify_signature;
+  use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+  };
+
+  #[test]
+  fn verifies_library_signatures() {
+    let dir = tempfile::tempdir().unwrap();
+    let library = dir.path().join("plugin.so");
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public_key = key_pair.public_key().as_ref();
+
+    let signature = base64::Engine::encode(
+      &base64::engine::general_purpose::STANDARD,
+      key_pair.sign(b"library"),
+    );
+    assert!(verify_signature(&library, b"library", public_key).is_err());
+
+    std::fs::write(dir.path().join("plugin.so.sig"), &signature).unwrap();
+    assert!(verify_signature(&library, b"library", public_key).is_ok());
+    assert!(verify_signature(&library, b"tampered", public_key).is_err());
+  }
+}
@@ -8,6 +8,12 @@ use crate::Env;
 
 use std::path::PathBuf;
 
+pub(crate) mod memory;
+pub(crate) mod metrics;
+
+pub use memory::{MemoryInfo, MemoryPressureLevel};
+pub use metrics::{ProcessKind, ProcessMetrics};
+
 /// Finds the current running binary's path.
 ///
 /// With exception to any following platform-specific behavior, the path is cached as soon as
@@ -0,0 +1,232 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The memory usage of the app, returned by [`crate::AppHandle::memory_info`],
+//! and the memory pressure of the system, emitted as [`crate::RunEvent::MemoryPressure`].
+
+use super::ProcessMetrics;
+
+use serde::Serialize;
+
+use std::collections::HashMap;
+
+/// The event emitted to the webviews when the memory pressure of the system changes, with the [`MemoryPressureLevel`] as payload.
+pub(crate) const MEMORY_PRESSURE_EVENT: &str = "tauri://memory-pressure";
+
+/// The memory usage of the app.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MemoryInfo {
+  /// The process of the app itself.
+  pub app: Option<ProcessMetrics>,
+  /// The processes running the webview of each window, by window label.
+  ///
+  /// The windows sharing their webview processes list the same processes, see [`crate::Window::webview_processes`].
+  pub webviews: HashMap<String, Vec<ProcessMetrics>>,
+}
+
+impl MemoryInfo {
+  /// The total resident memory of the app and of its webview processes in bytes, counting the shared processes once.
+  pub fn total(&self) -> u64 {
+    let mut pids = Vec::new();
+    self
+      .app
+      .iter()
+      .chain(self.webviews.values().flatten())
+      .filter(|process| {
+        if pids.contains(&process.pid) {
+          false
+        } else {
+          pids.push(process.pid);
+          true
+        }
+      })
+      .map(|process| process.memory)
+      .sum()
+  }
+}
+
+/// How low the system is on memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum MemoryPressureLevel {
+  /// The system has recovered from a memory pressure.
+  Normal,
+  /// The system is low on memory, the app should release its caches.
+  Warning,
+  /// The system is about to terminate the processes using the most memory,
+  /// the app should release all the memory it can.
+  Critical,
+}
+
+/// Watches the memory pressure of the system, sending its changes to the event loop.
+#[cfg(desktop)]
+pub(crate) fn watch<R: crate::Runtime>(app_handle: &crate::AppHandle<R>) {
+  use crate::runtime::{EventLoopProxy, RuntimeHandle};
+
+  let proxy = app_handle.runtime_handle.create_proxy();
+  platform::watch(move |level| {
+    let _ = proxy.send_event(crate::EventLoopMessage::MemoryPressure(level));
+  });
+}
+
+/// Polls the memory available on the system, since Linux and Windows only expose it as a level.
+#[cfg(all(
+  desktop,
+  any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    windows
+  )
+))]
+mod platform {
+  use super::MemoryPressureLevel;
+
+  use std::time::Duration;
+
+  const POLL_INTERVAL: Duration = Duration::from_secs(5);
+  /// The share of the physical memory available under which the pressure is a warning.
+  const WARNING_AVAILABLE_RATIO: f64 = 0.1;
+  /// The share of the physical memory available under which the pressure is critical.
+  const CRITICAL_AVAILABLE_RATIO: f64 = 0.05;
+
+  pub(super) fn watch<F: Fn(MemoryPressureLevel) + Send + 'static>(f: F) {
+    std::thread::spawn(move || {
+      let mut last = MemoryPressureLevel::Normal;
+      loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let Some(ratio) = available_ratio() else {
+          // the memory cannot be queried on this system
+          return;
+        };
+        let level = if ratio < CRITICAL_AVAILABLE_RATIO {
+          MemoryPressureLevel::Critical
+        } else if ratio < WARNING_AVAILABLE_RATIO {
+          MemoryPressureLevel::Warning
+        } else {
+          MemoryPressureLevel::Normal
+        };
+        if level != last {
+          last = level;
+          f(level);
+        }
+      }
+    });
+  }
+
+  /// The share of the physical memory that is available.
+  #[cfg(not(windows))]
+  fn available_ratio() -> Option<f64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let field = |name: &str| {
+      meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix(name))
+        .and_then(|value| {
+          value
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse::<f64>()
+            .ok()
+        })
+    };
+    let total = field("MemTotal:")?;
+    let available = field("MemAvailable:")?;
+    (total > 0.).then(|| available / total)
+  }
+
+  /// The share of the physical memory that is available.
+  #[cfg(windows)]
+  fn available_ratio() -> Option<f64> {
+    use windows::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+    let mut status = MEMORYSTATUSEX {
+      dwLength: std::mem::size_of::<MEMORYSTATUSEX>() as u32,
+      ..Default::default()
+    };
+    if !unsafe { GlobalMemoryStatusEx(&mut status) }.as_bool() || status.ullTotalPhys == 0 {
+      return None;
+    }
+    Some(status.ullAvailPhys as f64 / status.ullTotalPhys as f64)
+  }
+}
+
+/// Listens to the memory pressure dispatch source, the same source AppKit and WebKit react to.
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::MemoryPressureLevel;
+
+  use std::ffi::c_void;
+
+  const DISPATCH_MEMORYPRESSURE_NORMAL: usize = 0x01;
+  const DISPATCH_MEMORYPRESSURE_WARN: usize = 0x02;
+  const DISPATCH_MEMORYPRESSURE_CRITICAL: usize = 0x04;
+  const DISPATCH_QUEUE_PRIORITY_DEFAULT: isize = 0;
+
+  extern "C" {
+    static _dispatch_source_type_memorypressure: c_void;
+    fn dispatch_get_global_queue(identifier: isize, flags: usize) -> *mut c_void;
+    fn dispatch_source_create(
+      type_: *const c_void,
+      handle: usize,
+      mask: usize,
+      queue: *mut c_void,
+    ) -> *mut c_void;
+    fn dispatch_set_context(object: *mut c_void, context: *mut c_void);
+    fn dispatch_source_set_event_handler_f(
+      source: *mut c_void,
+      handler: extern "C" fn(*mut c_void),
+    );
+    fn dispatch_source_get_data(source: *mut c_void) -> usize;
+    fn dispatch_resume(object: *mut c_void);
+  }
+
+  struct Context {
+    source: *mut c_void,
+    f: Box<dyn Fn(MemoryPressureLevel) + Send + Sync>,
+  }
+
+  extern "C" fn handler(context: *mut c_void) {
+    let context = unsafe { &*(context as *const Context) };
+    let data = unsafe { dispatch_source_get_data(context.source) };
+    let level = if data & DISPATCH_MEMORYPRESSURE_CRITICAL != 0 {
+      MemoryPressureLevel::Critical
+    } else if data & DISPATCH_MEMORYPRESSURE_WARN != 0 {
+      MemoryPressureLevel::Warning
+    } else {
+      MemoryPressureLevel::Normal
+    };
+    (context.f)(level);
+  }
+
+  pub(super) fn watch<F: Fn(MemoryPressureLevel) + Send + Sync + 'static>(f: F) {
+    unsafe {
+      let source = dispatch_source_create(
+        &_dispatch_source_type_memorypressure,
+        0,
+        DISPATCH_MEMORYPRESSURE_NORMAL
+          | DISPATCH_MEMORYPRESSURE_WARN
+          | DISPATCH_MEMORYPRESSURE_CRITICAL,
+        dispatch_get_global_queue(DISPATCH_QUEUE_PRIORITY_DEFAULT, 0),
+      );
+      if source.is_null() {
+        return;
+      }
+      // the source lives as long as the app, so its context is never released
+      let context = Box::into_raw(Box::new(Context {
+        source,
+        f: Box::new(f),
+      }));
+      dispatch_set_context(source, context as *mut c_void);
+      dispatch_source_set_event_handler_f(source, handler);
+      dispatch_resume(source);
+    }
+  }
+}
@@ -0,0 +1,342 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The memory and CPU usage of the processes of the app and of its webviews.
+
+use serde::Serialize;
+
+use std::time::Duration;
+
+/// The role of a process of the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ProcessKind {
+  /// The process of the app itself, running the Rust code.
+  App,
+  /// The browser process of a webview, managing its other processes.
+  Browser,
+  /// A process rendering the web content of a webview and running its JavaScript.
+  Renderer,
+  /// The GPU process of a webview.
+  Gpu,
+  /// The network process of a webview.
+  Network,
+  /// Another helper process of a webview.
+  Utility,
+}
+
+/// The resources used by a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProcessMetrics {
+  /// The identifier of the process.
+  pub pid: u32,
+  /// The role of the process.
+  pub kind: ProcessKind,
+  /// The resident memory of the process in bytes.
+  pub memory: u64,
+  /// The CPU time used by the process since it started, in user and kernel mode.
+  ///
+  /// The CPU usage is the difference between two measures divided by the time elapsed between them.
+  pub cpu_time: Duration,
+}
+
+/// Measures the resources used by the process, or returns `None` if it exited or cannot be queried.
+pub(crate) fn measure(pid: u32, kind: ProcessKind) -> Option<ProcessMetrics> {
+  let (memory, cpu_time) = platform::measure(pid)?;
+  Some(ProcessMetrics {
+    pid,
+    kind,
+    memory,
+    cpu_time,
+  })
+}
+
+/// Lists the processes running the webview. Must run on the main thread.
+#[cfg(feature = "wry")]
+pub(crate) fn webview_processes(
+  webview: &crate::window::PlatformWebview,
+) -> Vec<(u32, ProcessKind)> {
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    // WebKitGTK does not expose the processes of a webview
+    let _ = webview;
+    webkit_processes()
+  }
+
+  #[cfg(windows)]
+  {
+    webview2_processes(webview).unwrap_or_default()
+  }
+
+  #[cfg(all(target_os = "macos", feature = "macos-private-api"))]
+  {
+    use objc::{msg_send, sel, sel_impl};
+    let pid: libc::pid_t = unsafe { msg_send![webview.inner(), _webProcessIdentifier] };
+    if pid > 0 {
+      vec![(pid as u32, ProcessKind::Renderer)]
+    } else {
+      Vec::new()
+    }
+  }
+
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    windows,
+    all(target_os = "macos", feature = "macos-private-api")
+  )))]
+  {
+    let _ = webview;
+    Vec::new()
+  }
+}
+
+/// Lists the processes of the WebView2 environment of the webview,
+/// which are shared by the webviews using the same data directory.
+#[cfg(all(windows, feature = "wry"))]
+fn webview2_processes(
+  webview: &crate::window::PlatformWebview,
+) -> windows::core::Result<Vec<(u32, ProcessKind)>> {
+  use webview2_com::Microsoft::Web::WebView2::Win32::{
+    ICoreWebView2Environment8, ICoreWebView2_2, COREWEBVIEW2_PROCESS_KIND,
+    COREWEBVIEW2_PROCESS_KIND_BROWSER, COREWEBVIEW2_PROCESS_KIND_GPU,
+    COREWEBVIEW2_PROCESS_KIND_RENDERER,
+  };
+  use windows::core::Interface;
+
+  unsafe {
+    let core = webview.controller().CoreWebView2()?;
+    let environment = core
+      .cast::<ICoreWebView2_2>()?
+      .Environment()?
+      .cast::<ICoreWebView2Environment8>();
+    let Ok(environment) = environment else {
+      // the runtime is too old to list the processes, only the browser process is known
+      let mut pid = 0;
+      core.BrowserProcessId(&mut pid)?;
+      return Ok(vec![(pid, ProcessKind::Browser)]);
+    };
+
+    let infos = environment.GetProcessInfos()?;
+    let mut count = 0;
+    infos.Count(&mut count)?;
+    let mut processes = Vec::new();
+    for i in 0..count {
+      let info = infos.GetValueAtIndex(i)?;
+      let mut pid = 0;
+      info.ProcessId(&mut pid)?;
+      let mut kind = COREWEBVIEW2_PROCESS_KIND::default();
+      info.Kind(&mut kind)?;
+      let kind = match kind {
+        COREWEBVIEW2_PROCESS_KIND_BROWSER => ProcessKind::Browser,
+        COREWEBVIEW2_PROCESS_KIND_RENDERER => ProcessKind::Renderer,
+        COREWEBVIEW2_PROCESS_KIND_GPU => ProcessKind::Gpu,
+        _ => ProcessKind::Utility,
+      };
+      processes.push((pid as u32, kind));
+    }
+    Ok(processes)
+  }
+}
+
+/// Lists the WebKit processes spawned by the app, which are shared by the webviews using the same web context.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn webkit_processes() -> Vec<(u32, ProcessKind)> {
+  let app = std::process::id();
+  let Ok(entries) = std::fs::read_dir("/proc") else {
+    return Vec::new();
+  };
+
+  entries
+    .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+    .filter_map(|pid| {
+      let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+      let (name, fields) = platform::split_stat(&stat)?;
+      // the parent process id is the second field after the name
+      let parent = fields.get(1)?.parse::<u32>().ok()?;
+      if parent != app {
+        return None;
+      }
+      let kind = match name {
+        name if name.starts_with("WebKitWebProces") => ProcessKind::Renderer,
+        name if name.starts_with("WebKitNetworkPr") => ProcessKind::Network,
+        name if name.starts_with("WebKitGPUProces") => ProcessKind::Gpu,
+        name if name.starts_with("WebKit") => ProcessKind::Utility,
+        _ => return None,
+      };
+      Some((pid, kind))
+    })
+    .collect()
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use std::time::Duration;
+
+  /// Splits a `/proc/<pid>/stat` line into the name of the process and the fields following it.
+  /// The name is in parentheses and can contain spaces.
+  pub(super) fn split_stat(stat: &str) -> Option<(&str, Vec<&str>)> {
+    let start = stat.find('(')?;
+    let end = stat.rfind(')')?;
+    let name = &stat[start + 1..end];
+    Some((name, stat[end + 1..].split_whitespace().collect()))
+  }
+
+  pub(super) fn measure(pid: u32) -> Option<(u64, Duration)> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let memory = status
+      .lines()
+      .find_map(|line| line.strip_prefix("VmRSS:"))
+      .and_then(|value| {
+        value
+          .trim()
+          .trim_end_matches("kB")
+          .trim()
+          .parse::<u64>()
+          .ok()
+      })
+      .map(|kb| kb * 1024)
+      .unwrap_or_default();
+
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let (_, fields) = split_stat(&stat)?;
+    // `utime` and `stime` are the 14th and 15th fields, in clock ticks
+    let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+    let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+    let cpu_time = Duration::from_nanos(ticks * 1_000_000_000 / ticks_per_second);
+
+    Some((memory, cpu_time))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use std::time::Duration;
+
+  pub(super) fn measure(pid: u32) -> Option<(u64, Duration)> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let read = unsafe {
+      libc::proc_pidinfo(
+        pid as libc::c_int,
+        libc::PROC_PIDTASKINFO,
+        0,
+        &mut info as *mut _ as *mut libc::c_void,
+        size,
+      )
+    };
+    if read != size {
+      return None;
+    }
+
+    // the CPU times are in Mach absolute time units, which are not nanoseconds on Apple silicon
+    let mut timebase = libc::mach_timebase_info { numer: 0, denom: 0 };
+    #[allow(deprecated)]
+    unsafe {
+      libc::mach_timebase_info(&mut timebase)
+    };
+    let ticks = info.pti_total_user + info.pti_total_system;
+    let nanos = if timebase.denom == 0 {
+      ticks
+    } else {
+      ticks * timebase.numer as u64 / timebase.denom as u64
+    };
+
+    Some((info.pti_resident_size, Duration::from_nanos(nanos)))
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::time::Duration;
+
+  use windows::Win32::{
+    Foundation::{CloseHandle, FILETIME},
+    System::{
+      ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+      Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+    },
+  };
+
+  pub(super) fn measure(pid: u32) -> Option<(u64, Duration)> {
+    unsafe {
+      let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+      let mut counters = PROCESS_MEMORY_COUNTERS::default();
+      let memory = if K32GetProcessMemoryInfo(
+        process,
+        &mut counters,
+        std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+      )
+      .as_bool()
+      {
+        counters.WorkingSetSize as u64
+      } else {
+        0
+      };
+
+      let (mut creation, mut exit, mut kernel, mut user) = (
+        FILETIME::default(),
+        FILETIME::default(),
+        FILETIME::default(),
+        FILETIME::default(),
+      );
+      let cpu_time =
+        if GetProcessTimes(process, &mut creation, &mut exit, &mut kernel, &mut user).as_bool() {
+          // the times are in 100-nanosecond units
+          Duration::from_nanos((filetime(&kernel) + filetime(&user)) * 100)
+        } else {
+          Duration::ZERO
+        };
+
+      CloseHandle(process);
+      Some((memory, cpu_time))
+    }
+  }
+
+  fn filetime(time: &FILETIME) -> u64 {
+    (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64
+  }
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  target_os = "macos",
+  windows
+)))]
+mod platform {
+  use std::time::Duration;
+
+  pub(super) fn measure(_pid: u32) -> Option<(u64, Duration)> {
+    None
+  }
+}
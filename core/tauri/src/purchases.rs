@@ -0,0 +1,319 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! In-app purchases on Android and iOS, enabled with the
+//! [`tauri > bundle > inAppPurchases > enable`](crate::utils::config::InAppPurchasesConfig#structfield.enable) configuration.
+//!
+//! The products are configured in the Google Play Console and App Store Connect, and sold through
+//! Google Play Billing on Android and StoreKit on iOS.
+//!
+//! A purchase must be [finished](finish) once the app delivered the content, after verifying the
+//! [`Purchase::receipt`] on the app server, otherwise the store refunds it after a few days.
+//! The purchases completed outside of [`purchase`], e.g. approved by a parent or renewed subscriptions,
+//! are emitted as [`crate::RunEvent::Purchase`].
+//!
+//! ## Platform-specific
+//!
+//! - **iOS**: Requires iOS 15.
+//! - **Linux / macOS / Windows**: Unsupported, returns [`crate::Error::InAppPurchasesUnavailable`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+#[cfg(mobile)]
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+#[cfg(mobile)]
+use std::sync::Mutex;
+
+/// The event emitted to the webviews when a purchase is updated, with the [`Purchase`] as payload.
+pub(crate) const PURCHASE_EVENT: &str = "tauri://purchase";
+
+#[cfg(mobile)]
+type Sender = Box<dyn Fn(Purchase) + Send>;
+
+/// Sends the purchases updated outside of the purchase flow to the event loop.
+#[cfg(mobile)]
+static SENDER: OnceCell<Mutex<Sender>> = OnceCell::new();
+/// The purchases updated before the event loop could receive them.
+#[cfg(mobile)]
+static PENDING: Mutex<Vec<Purchase>> = Mutex::new(Vec::new());
+
+/// The kind of a product.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ProductKind {
+  /// A product purchased once, either consumable or not.
+  #[default]
+  OneTime,
+  /// An auto-renewable subscription.
+  Subscription,
+}
+
+/// A product sold in the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Product {
+  /// The product identifier configured in the store.
+  pub id: String,
+  /// The kind of the product.
+  pub kind: ProductKind,
+  /// The localized title.
+  pub title: String,
+  /// The localized description.
+  pub description: String,
+  /// The price formatted in the currency of the user, e.g. `$0.99`.
+  ///
+  /// For a subscription, the price of its first base plan on Android.
+  pub price: String,
+  /// The price in micro-units of the currency, e.g. `990000` for `$0.99`.
+  pub price_micros: i64,
+  /// The ISO 4217 code of the currency, e.g. `USD`.
+  pub currency_code: String,
+}
+
+/// The state of a [`Purchase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum PurchaseState {
+  /// The product was paid, the content must be delivered and the purchase finished.
+  Purchased,
+  /// The payment is not completed yet, e.g. waiting for a cash payment or the approval of a parent.
+  ///
+  /// The purchase is emitted as [`crate::RunEvent::Purchase`] once completed.
+  Pending,
+}
+
+/// A purchase of a [`Product`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Purchase {
+  /// The identifier of the purchased product.
+  pub product_id: String,
+  /// The Google Play order identifier or the App Store transaction identifier,
+  /// `None` while the purchase is pending on Android.
+  pub order_id: Option<String>,
+  /// The purchase token on Android, or the transaction identifier on iOS,
+  /// used to [finish](finish) the purchase.
+  pub token: String,
+  /// The proof of purchase to verify on the app server: the purchase token on Android,
+  /// or the JWS representation of the transaction on iOS.
+  pub receipt: String,
+  /// The state of the purchase.
+  pub state: PurchaseState,
+  /// Whether the purchase was finished.
+  #[serde(default)]
+  pub acknowledged: bool,
+  /// When the product was purchased, in milliseconds since the Unix epoch.
+  pub purchase_time: u64,
+}
+
+/// The native purchases plugin, managed when the in-app purchases are enabled.
+#[cfg(mobile)]
+struct PurchasesPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Runs a command of the native plugin.
+fn run<R: Runtime, M: Manager<R>, T: serde::de::DeserializeOwned, P: Serialize>(
+  manager: &M,
+  command: &str,
+  payload: P,
+) -> crate::Result<T> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<PurchasesPlugin<R>>() {
+    return plugin
+      .0
+      .run_mobile_plugin(command, payload)
+      .map_err(Into::into);
+  }
+
+  let _ = (manager, command, payload);
+  Err(crate::Error::InAppPurchasesUnavailable)
+}
+
+#[derive(Deserialize)]
+struct ProductsResponse {
+  products: Vec<Product>,
+}
+
+#[derive(Deserialize)]
+struct PurchaseResponse {
+  purchase: Option<Purchase>,
+}
+
+#[derive(Deserialize)]
+struct PurchasesResponse {
+  purchases: Vec<Purchase>,
+}
+
+/// Queries the products with the given identifiers, skipping the unknown ones.
+///
+/// The products must be queried before they can be purchased.
+pub fn products<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  ids: Vec<String>,
+  kind: ProductKind,
+) -> crate::Result<Vec<Product>> {
+  run::<R, _, ProductsResponse, _>(
+    manager,
+    "getProducts",
+    serde_json::json!({ "ids": ids, "kind": kind }),
+  )
+  .map(|response| response.products)
+}
+
+/// Shows the store sheet to purchase the product, returning `None` if the user cancelled it.
+pub fn purchase<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  product_id: &str,
+  kind: ProductKind,
+) -> crate::Result<Option<Purchase>> {
+  run::<R, _, PurchaseResponse, _>(
+    manager,
+    "purchase",
+    serde_json::json!({ "productId": product_id, "kind": kind }),
+  )
+  .map(|response| response.purchase)
+}
+
+/// Finishes a purchase once its content was delivered.
+///
+/// A consumable product, e.g. in-game currency, is consumed so it can be purchased again.
+/// The `consume` flag is ignored on iOS, where the consumable products are declared in App Store Connect.
+pub fn finish<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  purchase: &Purchase,
+  consume: bool,
+) -> crate::Result<()> {
+  run(
+    manager,
+    "finish",
+    serde_json::json!({ "token": purchase.token, "consume": consume }),
+  )
+}
+
+/// Restores the active purchases of the user, e.g. after reinstalling the app or on a new device.
+///
+/// Returns the non-consumable products and active subscriptions owned by the user,
+/// and the purchases not finished yet.
+pub fn restore<R: Runtime, M: Manager<R>>(manager: &M) -> crate::Result<Vec<Purchase>> {
+  run::<R, _, PurchasesResponse, _>(manager, "restore", ()).map(|response| response.purchases)
+}
+
+/// Forwards the purchases updated outside of the purchase flow to the event loop,
+/// starting with the purchases received so far.
+#[cfg(mobile)]
+fn listen<R: Runtime>(app_handle: &crate::AppHandle<R>) {
+  use crate::runtime::{EventLoopProxy, RuntimeHandle};
+
+  let proxy = app_handle.runtime_handle.create_proxy();
+  let mut pending = PENDING.lock().unwrap();
+  for purchase in pending.drain(..) {
+    let _ = proxy.send_event(crate::EventLoopMessage::Purchase(purchase));
+  }
+  let _ = SENDER.set(Mutex::new(Box::new(move |purchase| {
+    let _ = proxy.send_event(crate::EventLoopMessage::Purchase(purchase));
+  })));
+}
+
+/// Handles a purchase update serialized by the Kotlin or Swift code.
+#[cfg(mobile)]
+fn dispatch(json: &str) {
+  let Ok(purchase) = serde_json::from_str::<Purchase>(json) else {
+    return;
+  };
+
+  // the pending purchases are flushed while holding the lock, so the updates stay in order
+  let mut pending = PENDING.lock().unwrap();
+  match SENDER.get() {
+    Some(sender) => (sender.lock().unwrap())(purchase),
+    None => pending.push(purchase),
+  }
+}
+
+/// Glue between Rust and the Kotlin code that forwards the purchase updates.
+#[cfg(target_os = "android")]
+pub fn handle_android_purchase_event(env: jni::JNIEnv<'_>, payload: jni::objects::JString<'_>) {
+  if let Ok(payload) = env.get_string(payload) {
+    dispatch(&String::from(payload));
+  }
+}
+
+mod commands {
+  use super::{Product, ProductKind, Purchase};
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn get_products<R: Runtime>(
+    app: AppHandle<R>,
+    ids: Vec<String>,
+    kind: Option<ProductKind>,
+  ) -> crate::Result<Vec<Product>> {
+    super::products(&app, ids, kind.unwrap_or_default())
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn purchase<R: Runtime>(
+    app: AppHandle<R>,
+    product_id: String,
+    kind: Option<ProductKind>,
+  ) -> crate::Result<Option<Purchase>> {
+    super::purchase(&app, &product_id, kind.unwrap_or_default())
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn finish<R: Runtime>(
+    app: AppHandle<R>,
+    purchase: Purchase,
+    consume: bool,
+  ) -> crate::Result<()> {
+    super::finish(&app, &purchase, consume)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn restore<R: Runtime>(app: AppHandle<R>) -> crate::Result<Vec<Purchase>> {
+    super::restore(&app)
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("purchases")
+    .invoke_handler(crate::generate_handler![
+      commands::get_products,
+      commands::purchase,
+      commands::finish,
+      commands::restore
+    ])
+    .setup(|app, _api| {
+      #[cfg(mobile)]
+      if app.config().tauri.bundle.in_app_purchases.enable {
+        #[cfg(target_os = "android")]
+        let handle = _api.register_android_plugin("app.tauri.purchases", "PurchasesPlugin")?;
+        #[cfg(target_os = "ios")]
+        let handle = {
+          extern "C" fn handler(payload: *const std::os::raw::c_char) {
+            let payload = unsafe { std::ffi::CStr::from_ptr(payload) };
+            dispatch(&payload.to_string_lossy());
+          }
+          let handle = _api.register_ios_plugin(crate::ios::init_purchases_plugin)?;
+          unsafe {
+            crate::ios::set_purchase_event_handler(crate::ios::PurchaseEventHandler(handler))
+          };
+          handle
+        };
+        app.manage(PurchasesPlugin(handle));
+        listen(app);
+      }
+
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
@@ -0,0 +1,199 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Push notifications on Android and iOS, enabled with the
+//! [`tauri > bundle > push > enable`](crate::utils::config::PushConfig#structfield.enable) configuration.
+//!
+//! [`crate::AppHandle::register_for_push_notifications`] asks the user for the permission to show notifications
+//! and returns the token the app server sends the notifications to, through Firebase Cloud Messaging on Android
+//! and the Apple Push Notification service on iOS.
+//! The notifications and the token updates are emitted as [`crate::RunEvent::Push`].
+//!
+//! The notification tapped by the user to launch the app is also returned by [`crate::AppHandle::launch_notification`],
+//! since it is received before the app can listen to the events.
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Runtime,
+};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+#[cfg(mobile)]
+use std::sync::Mutex;
+
+/// The event emitted to the webviews when a push event is received, with the [`PushEvent`] as payload.
+pub(crate) const PUSH_EVENT: &str = "tauri://push";
+
+#[cfg(mobile)]
+type Sender = Box<dyn Fn(PushEvent) + Send>;
+
+/// Sends the push events received from the platform to the event loop.
+#[cfg(mobile)]
+static SENDER: OnceCell<Mutex<Sender>> = OnceCell::new();
+/// The events received before the event loop could receive them, e.g. the notification launching the app.
+#[cfg(mobile)]
+static PENDING: Mutex<Vec<PushEvent>> = Mutex::new(Vec::new());
+static LAUNCH_NOTIFICATION: OnceCell<PushNotification> = OnceCell::new();
+
+/// A push event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum PushEvent {
+  /// The push token changed, the app server must send the notifications to the new token.
+  #[non_exhaustive]
+  Token {
+    /// The Firebase Cloud Messaging registration token on Android,
+    /// or the hex-encoded Apple Push Notification service device token on iOS.
+    token: String,
+  },
+  /// A push notification was received.
+  Notification(PushNotification),
+}
+
+/// A push notification.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PushNotification {
+  /// The title of the notification, or `None` for a data-only notification.
+  pub title: Option<String>,
+  /// The body of the notification.
+  pub body: Option<String>,
+  /// The custom data of the notification.
+  #[serde(default)]
+  pub data: HashMap<String, serde_json::Value>,
+  /// Whether the user tapped the notification, otherwise it was received while the app is running.
+  #[serde(default)]
+  pub opened: bool,
+  /// Whether the notification launched the app.
+  #[serde(default)]
+  pub cold_start: bool,
+}
+
+/// Forwards the push events of the platform to the event loop, starting with the events received so far.
+#[cfg(mobile)]
+pub(crate) fn listen<R: Runtime>(app_handle: &crate::AppHandle<R>) {
+  use crate::runtime::{EventLoopProxy, RuntimeHandle};
+
+  let proxy = app_handle.runtime_handle.create_proxy();
+  let mut pending = PENDING.lock().unwrap();
+  for event in pending.drain(..) {
+    let _ = proxy.send_event(crate::EventLoopMessage::Push(event));
+  }
+  let _ = SENDER.set(Mutex::new(Box::new(move |event| {
+    let _ = proxy.send_event(crate::EventLoopMessage::Push(event));
+  })));
+}
+
+/// Handles a push event serialized by the Kotlin or Swift code.
+#[cfg(mobile)]
+pub(crate) fn dispatch(json: &str) {
+  let Ok(event) = serde_json::from_str::<PushEvent>(json) else {
+    return;
+  };
+  if let PushEvent::Notification(notification) = &event {
+    if notification.cold_start {
+      let _ = LAUNCH_NOTIFICATION.set(notification.clone());
+    }
+  }
+
+  // the pending events are flushed while holding the lock, so the events stay in order
+  let mut pending = PENDING.lock().unwrap();
+  match SENDER.get() {
+    Some(sender) => (sender.lock().unwrap())(event),
+    None => pending.push(event),
+  }
+}
+
+/// Glue between Rust and the Kotlin code that forwards the push events.
+#[cfg(target_os = "android")]
+pub fn handle_android_push_event(env: jni::JNIEnv<'_>, payload: jni::objects::JString<'_>) {
+  if let Ok(payload) = env.get_string(payload) {
+    dispatch(&String::from(payload));
+  }
+}
+
+/// The notification tapped by the user to launch the app.
+pub(crate) fn launch_notification() -> Option<PushNotification> {
+  LAUNCH_NOTIFICATION.get().cloned()
+}
+
+#[cfg(mobile)]
+#[derive(Deserialize)]
+struct RegisterResponse {
+  token: String,
+}
+
+/// The native push plugin, managed when the push notifications are enabled.
+#[cfg(mobile)]
+pub(crate) struct PushPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Asks the permission to show notifications and returns the push token.
+pub(crate) fn register<R: Runtime, M: crate::Manager<R>>(manager: &M) -> crate::Result<String> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<PushPlugin<R>>() {
+    return plugin
+      .0
+      .run_mobile_plugin::<RegisterResponse>("register", ())
+      .map(|response| response.token)
+      .map_err(Into::into);
+  }
+
+  let _ = manager;
+  Err(crate::Error::PushNotificationsUnavailable)
+}
+
+mod commands {
+  use super::PushNotification;
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn register<R: Runtime>(app: AppHandle<R>) -> crate::Result<String> {
+    super::register(&app)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn launch_notification() -> Option<PushNotification> {
+    super::launch_notification()
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("push")
+    .invoke_handler(crate::generate_handler![
+      commands::register,
+      commands::launch_notification
+    ])
+    .setup(|app, _api| {
+      #[cfg(mobile)]
+      {
+        use crate::Manager;
+
+        if app.config().tauri.bundle.push.enable {
+          #[cfg(target_os = "android")]
+          let handle = _api.register_android_plugin("app.tauri.push", "PushPlugin")?;
+          #[cfg(target_os = "ios")]
+          let handle = {
+            extern "C" fn handler(payload: *const std::os::raw::c_char) {
+              let payload = unsafe { std::ffi::CStr::from_ptr(payload) };
+              dispatch(&payload.to_string_lossy());
+            }
+            let handle = _api.register_ios_plugin(crate::ios::init_push_plugin)?;
+            unsafe { crate::ios::set_push_event_handler(crate::ios::PushEventHandler(handler)) };
+            handle
+          };
+          app.manage(PushPlugin(handle));
+          listen(app);
+        }
+      }
+
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
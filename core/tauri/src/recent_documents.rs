@@ -0,0 +1,124 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The recent documents of the app, listed by the dock menu on macOS, the jump list on Windows
+//! and the file choosers on Linux. Clicking one opens it with the app.
+
+#![allow(unused_variables)]
+
+use crate::{AppHandle, Runtime};
+use std::path::Path;
+
+/// Adds the file to the recent documents.
+pub(crate) fn add<R: Runtime>(app: &AppHandle<R>, path: &Path) -> crate::Result<()> {
+  let path = std::fs::canonicalize(path)?;
+
+  #[cfg(target_os = "macos")]
+  {
+    let path = path.to_string_lossy().into_owned();
+    app.run_on_main_thread(move || unsafe {
+      use cocoa::{
+        base::{id, nil},
+        foundation::NSString,
+      };
+      use objc::{class, msg_send, sel, sel_impl};
+
+      let path = NSString::alloc(nil).init_str(&path);
+      let url: id = msg_send![class!(NSURL), fileURLWithPath: path];
+      let controller: id = msg_send![class!(NSDocumentController), sharedDocumentController];
+      let _: () = msg_send![controller, noteNewRecentDocumentURL: url];
+      let _: () = msg_send![path, release];
+    })?;
+  }
+
+  #[cfg(windows)]
+  {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::UI::Shell::{SHAddToRecentDocs, SHARD_PATHW};
+
+    let path = path
+      .as_os_str()
+      .encode_wide()
+      .chain(std::iter::once(0))
+      .collect::<Vec<u16>>();
+    unsafe { SHAddToRecentDocs(SHARD_PATHW.0 as u32, Some(path.as_ptr() as _)) };
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    let uri = url::Url::from_file_path(&path)
+      .map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "the path is not absolute")
+      })?
+      .to_string();
+    app.run_on_main_thread(move || {
+      use gtk::prelude::RecentManagerExt;
+      if let Some(manager) = gtk::RecentManager::default() {
+        manager.add_item(&uri);
+      }
+    })?;
+  }
+
+  Ok(())
+}
+
+/// Clears the recent documents of the app.
+pub(crate) fn clear<R: Runtime>(app: &AppHandle<R>) -> crate::Result<()> {
+  #[cfg(target_os = "macos")]
+  app.run_on_main_thread(|| unsafe {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let controller: id = msg_send![class!(NSDocumentController), sharedDocumentController];
+    let _: () = msg_send![controller, clearRecentDocuments: nil];
+  })?;
+
+  #[cfg(windows)]
+  unsafe {
+    use windows::Win32::{
+      System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+      },
+      UI::Shell::{ApplicationDestinations, IApplicationDestinations},
+    };
+
+    // COM is already initialized on the main thread, this only initializes the other threads
+    let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+    let destinations: IApplicationDestinations =
+      CoCreateInstance(&ApplicationDestinations, None, CLSCTX_INPROC_SERVER)
+        .map_err(|e| crate::Error::JumpList(e.to_string()))?;
+    destinations
+      .RemoveAllDestinations()
+      .map_err(|e| crate::Error::JumpList(e.to_string()))?;
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  app.run_on_main_thread(|| {
+    use gtk::prelude::RecentManagerExt;
+    // the recent documents of the other apps are kept
+    if let (Some(manager), Some(name)) = (gtk::RecentManager::default(), glib::application_name()) {
+      for info in manager.items() {
+        if info.has_application(&name) {
+          if let Some(uri) = info.uri() {
+            let _ = manager.remove_item(&uri);
+          }
+        }
+      }
+    }
+  })?;
+
+  Ok(())
+}
@@ -10,7 +10,11 @@ use std::{
 };
 
 pub use glob::Pattern;
-use tauri_utils::config::FsScope;
+use serde::{Deserialize, Serialize};
+use tauri_utils::{
+  config::{FsScope, ScopePatternOptions},
+  scope::{ScopePattern, ScopePolicy, Verdict},
+};
 use uuid::Uuid;
 
 /// Scope change event.
@@ -20,6 +24,51 @@ pub enum Event {
   PathAllowed(PathBuf),
   /// A path has been forbidden.
   PathForbidden(PathBuf),
+  /// A path has been checked and it is not allowed by the scope.
+  PathDenied(PathBuf),
+}
+
+/// A path allowed or forbidden at runtime, recorded so the scope can be persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Change {
+  AllowDirectory { path: PathBuf, recursive: bool },
+  AllowFile { path: PathBuf },
+  ForbidDirectory { path: PathBuf, recursive: bool },
+  ForbidFile { path: PathBuf },
+}
+
+/// The scope changes written to disk by [`Scope::persist`].
+#[cfg(feature = "protocol-asset")]
+#[derive(Serialize, Deserialize)]
+struct Store {
+  changes: Vec<Change>,
+  /// The hex-encoded HMAC-SHA256 of the serialized changes.
+  signature: String,
+}
+
+#[cfg(feature = "protocol-asset")]
+fn sign(changes: &[Change], key: &[u8]) -> crate::Result<String> {
+  let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key);
+  let tag = ring::hmac::sign(&key, &serde_json::to_vec(changes)?);
+  Ok(tag.as_ref().iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(feature = "protocol-asset")]
+fn verify(changes: &[Change], signature: &str, key: &[u8]) -> crate::Result<bool> {
+  let message = serde_json::to_vec(changes)?;
+  let signature = (0..signature.len())
+    .step_by(2)
+    .map(|i| {
+      signature
+        .get(i..i + 2)
+        .and_then(|b| u8::from_str_radix(b, 16).ok())
+    })
+    .collect::<Option<Vec<u8>>>();
+  let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key);
+  Ok(signature.map_or(false, |signature| {
+    ring::hmac::verify(&key, &message, &signature).is_ok()
+  }))
 }
 
 type EventListener = Box<dyn Fn(&Event) + Send>;
@@ -27,10 +76,11 @@ type EventListener = Box<dyn Fn(&Event) + Send>;
 /// Scope for filesystem access.
 #[derive(Clone)]
 pub struct Scope {
-  allowed_patterns: Arc<Mutex<HashSet<Pattern>>>,
-  forbidden_patterns: Arc<Mutex<HashSet<Pattern>>>,
+  allowed_patterns: Arc<Mutex<HashSet<ScopePattern>>>,
+  forbidden_patterns: Arc<Mutex<HashSet<ScopePattern>>>,
   event_listeners: Arc<Mutex<HashMap<Uuid, EventListener>>>,
-  match_options: glob::MatchOptions,
+  changes: Arc<Mutex<Vec<Change>>>,
+  policy: ScopePolicy,
 }
 
 impl fmt::Debug for Scope {
@@ -43,7 +93,7 @@ impl fmt::Debug for Scope {
           .lock()
           .unwrap()
           .iter()
-          .map(|p| p.as_str())
+          .map(|p| p.pattern().as_str())
           .collect::<Vec<&str>>(),
       )
       .field(
@@ -53,15 +103,15 @@ impl fmt::Debug for Scope {
           .lock()
           .unwrap()
           .iter()
-          .map(|p| p.as_str())
+          .map(|p| p.pattern().as_str())
           .collect::<Vec<&str>>(),
       )
       .finish()
   }
 }
 
-fn push_pattern<P: AsRef<Path>, F: Fn(&str) -> Result<Pattern, glob::PatternError>>(
-  list: &mut HashSet<Pattern>,
+fn push_pattern<P: AsRef<Path>, F: Fn(&str) -> Result<ScopePattern, glob::PatternError>>(
+  list: &mut HashSet<ScopePattern>,
   pattern: P,
   f: F,
 ) -> crate::Result<()> {
@@ -87,16 +137,22 @@ impl Scope {
   ) -> crate::Result<Self> {
     let mut allowed_patterns = HashSet::new();
     for path in scope.allowed_paths() {
-      if let Ok(path) = manager.path().parse(path) {
-        push_pattern(&mut allowed_patterns, path, Pattern::new)?;
+      if let Ok(parsed) = manager.path().parse(path) {
+        let options = scope.pattern_options(path);
+        push_pattern(&mut allowed_patterns, parsed, |p| {
+          config_pattern(p, options)
+        })?;
       }
     }
 
     let mut forbidden_patterns = HashSet::new();
     if let Some(forbidden_paths) = scope.forbidden_paths() {
       for path in forbidden_paths {
-        if let Ok(path) = manager.path().parse(path) {
-          push_pattern(&mut forbidden_patterns, path, Pattern::new)?;
+        if let Ok(parsed) = manager.path().parse(path) {
+          let options = scope.pattern_options(path);
+          push_pattern(&mut forbidden_patterns, parsed, |p| {
+            config_pattern(p, options)
+          })?;
         }
       }
     }
@@ -117,24 +173,41 @@ impl Scope {
       allowed_patterns: Arc::new(Mutex::new(allowed_patterns)),
       forbidden_patterns: Arc::new(Mutex::new(forbidden_patterns)),
       event_listeners: Default::default(),
-      match_options: glob::MatchOptions {
-        // this is needed so `/dir/*` doesn't match files within subdirectories such as `/dir/subdir/file.txt`
-        // see: https://github.com/tauri-apps/tauri/security/advisories/GHSA-6mv3-wm7j-h4w5
-        require_literal_separator: true,
-        require_literal_leading_dot,
-        ..Default::default()
+      changes: Default::default(),
+      policy: ScopePolicy {
+        match_options: glob::MatchOptions {
+          // this is needed so `/dir/*` doesn't match files within subdirectories such as `/dir/subdir/file.txt`
+          // see: https://github.com/tauri-apps/tauri/security/advisories/GHSA-6mv3-wm7j-h4w5
+          require_literal_separator: true,
+          require_literal_leading_dot,
+          ..Default::default()
+        },
+        precedence: scope.precedence(),
+        symlinks: scope.symlinks(),
       },
     })
   }
 
   /// The list of allowed patterns.
   pub fn allowed_patterns(&self) -> HashSet<Pattern> {
-    self.allowed_patterns.lock().unwrap().clone()
+    self
+      .allowed_patterns
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|p| p.pattern().clone())
+      .collect()
   }
 
   /// The list of forbidden patterns.
   pub fn forbidden_patterns(&self) -> HashSet<Pattern> {
-    self.forbidden_patterns.lock().unwrap().clone()
+    self
+      .forbidden_patterns
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|p| p.pattern().clone())
+      .collect()
   }
 
   /// Listen to an event on this scope.
@@ -168,6 +241,10 @@ impl Scope {
         escaped_pattern_with(p, if recursive { "**" } else { "*" })
       })?;
     }
+    self.record(Change::AllowDirectory {
+      path: path.to_path_buf(),
+      recursive,
+    });
     self.trigger(Event::PathAllowed(path.to_path_buf()));
     Ok(())
   }
@@ -182,6 +259,9 @@ impl Scope {
       path,
       escaped_pattern,
     )?;
+    self.record(Change::AllowFile {
+      path: path.to_path_buf(),
+    });
     self.trigger(Event::PathAllowed(path.to_path_buf()));
     Ok(())
   }
@@ -201,6 +281,10 @@ impl Scope {
         escaped_pattern_with(p, if recursive { "**" } else { "*" })
       })?;
     }
+    self.record(Change::ForbidDirectory {
+      path: path.to_path_buf(),
+      recursive,
+    });
     self.trigger(Event::PathForbidden(path.to_path_buf()));
     Ok(())
   }
@@ -215,76 +299,135 @@ impl Scope {
       path,
       escaped_pattern,
     )?;
+    self.record(Change::ForbidFile {
+      path: path.to_path_buf(),
+    });
     self.trigger(Event::PathForbidden(path.to_path_buf()));
     Ok(())
   }
 
   /// Determines if the given path is allowed on this scope.
+  ///
+  /// Triggers [`Event::PathDenied`] if the path is not allowed,
+  /// so the app can ask the user to grant access to it.
   pub fn is_allowed<P: AsRef<Path>>(&self, path: P) -> bool {
+    let allowed = self.check(path.as_ref()).is_allowed();
+    if !allowed {
+      self.trigger(Event::PathDenied(path.as_ref().to_path_buf()));
+    }
+    allowed
+  }
+
+  /// Checks the given path on this scope, returning the pattern that allowed or forbidden it.
+  pub fn check<P: AsRef<Path>>(&self, path: P) -> Verdict {
+    tauri_utils::scope::check(
+      path.as_ref(),
+      self.allowed_patterns.lock().unwrap().iter(),
+      self.forbidden_patterns.lock().unwrap().iter(),
+      &self.policy,
+    )
+  }
+}
+
+impl Scope {
+  fn record(&self, change: Change) {
+    let mut changes = self.changes.lock().unwrap();
+    if !changes.contains(&change) {
+      changes.push(change);
+    }
+  }
+
+  /// Writes the paths allowed and forbidden at runtime to the given file,
+  /// signed with the given key so the store can't be modified without it.
+  #[cfg(feature = "protocol-asset")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "protocol-asset")))]
+  pub fn persist<P: AsRef<Path>>(&self, path: P, key: &[u8]) -> crate::Result<()> {
     let path = path.as_ref();
-    let path = if !path.exists() {
-      crate::Result::Ok(path.to_path_buf())
-    } else {
-      std::fs::canonicalize(path).map_err(Into::into)
+    let changes = self.changes.lock().unwrap().clone();
+    let store = Store {
+      signature: sign(&changes, key)?,
+      changes,
     };
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec(&store)?)?;
+    Ok(())
+  }
 
-    if let Ok(path) = path {
-      let path: PathBuf = path.components().collect();
-      let forbidden = self
-        .forbidden_patterns
-        .lock()
-        .unwrap()
-        .iter()
-        .any(|p| p.matches_path_with(&path, self.match_options));
-
-      if forbidden {
-        false
-      } else {
-        let allowed = self
-          .allowed_patterns
-          .lock()
-          .unwrap()
-          .iter()
-          .any(|p| p.matches_path_with(&path, self.match_options));
-        allowed
+  /// Applies the paths stored by [`Self::persist`] to this scope.
+  ///
+  /// Returns [`crate::Error::InvalidScopeStore`] if the store signature doesn't match the given key.
+  #[cfg(feature = "protocol-asset")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "protocol-asset")))]
+  pub fn restore<P: AsRef<Path>>(&self, path: P, key: &[u8]) -> crate::Result<()> {
+    let path = path.as_ref();
+    let store: Store = serde_json::from_slice(&std::fs::read(path)?)?;
+    if !verify(&store.changes, &store.signature, key)? {
+      return Err(crate::Error::InvalidScopeStore(path.to_path_buf()));
+    }
+    for change in store.changes {
+      match change {
+        Change::AllowDirectory { path, recursive } => self.allow_directory(path, recursive)?,
+        Change::AllowFile { path } => self.allow_file(path)?,
+        Change::ForbidDirectory { path, recursive } => self.forbid_directory(path, recursive)?,
+        Change::ForbidFile { path } => self.forbid_file(path)?,
       }
-    } else {
-      false
     }
+    Ok(())
   }
 }
 
-fn escaped_pattern(p: &str) -> Result<Pattern, glob::PatternError> {
-  Pattern::new(&glob::Pattern::escape(p))
+fn config_pattern(
+  p: &str,
+  options: Option<&ScopePatternOptions>,
+) -> Result<ScopePattern, glob::PatternError> {
+  let options = options.cloned().unwrap_or_default();
+  Ok(
+    ScopePattern::from(Pattern::new(p)?)
+      .case_sensitive(options.case_sensitive)
+      .max_depth(options.max_depth),
+  )
 }
 
-fn escaped_pattern_with(p: &str, append: &str) -> Result<Pattern, glob::PatternError> {
+fn escaped_pattern(p: &str) -> Result<ScopePattern, glob::PatternError> {
+  Pattern::new(&glob::Pattern::escape(p)).map(Into::into)
+}
+
+fn escaped_pattern_with(p: &str, append: &str) -> Result<ScopePattern, glob::PatternError> {
   Pattern::new(&format!(
     "{}{}{append}",
     glob::Pattern::escape(p),
     MAIN_SEPARATOR
   ))
+  .map(Into::into)
 }
 
 #[cfg(test)]
 mod tests {
-  use super::Scope;
+  use super::{Event, Scope, ScopePolicy};
+  use std::sync::{Arc, Mutex};
 
   fn new_scope() -> Scope {
     Scope {
       allowed_patterns: Default::default(),
       forbidden_patterns: Default::default(),
       event_listeners: Default::default(),
-      match_options: glob::MatchOptions {
-        // this is needed so `/dir/*` doesn't match files within subdirectories such as `/dir/subdir/file.txt`
-        // see: https://github.com/tauri-apps/tauri/security/advisories/GHSA-6mv3-wm7j-h4w5
-        require_literal_separator: true,
-        // dotfiles are not supposed to be exposed by default on unix
-        #[cfg(unix)]
-        require_literal_leading_dot: false,
-        #[cfg(windows)]
-        require_literal_leading_dot: true,
-        ..Default::default()
+      changes: Default::default(),
+      policy: ScopePolicy {
+        match_options: glob::MatchOptions {
+          // this is needed so `/dir/*` doesn't match files within subdirectories such as `/dir/subdir/file.txt`
+          // see: https://github.com/tauri-apps/tauri/security/advisories/GHSA-6mv3-wm7j-h4w5
+          require_literal_separator: true,
+          // dotfiles are not supposed to be exposed by default on unix
+          #[cfg(unix)]
+          require_literal_leading_dot: false,
+          #[cfg(windows)]
+          require_literal_leading_dot: true,
+          ..Default::default()
+        },
+        precedence: Default::default(),
+        symlinks: Default::default(),
       },
     }
   }
@@ -385,4 +528,52 @@ mod tests {
       assert!(scope.is_allowed("C:\\home\\tauri\\anyfile"));
     }
   }
+
+  #[cfg(feature = "protocol-asset")]
+  #[test]
+  fn persisted_changes_are_restored() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("scope.json");
+
+    let scope = new_scope();
+    scope.allow_directory(dir.path(), true).unwrap();
+    scope.forbid_file(dir.path().join("secret")).unwrap();
+    scope.persist(&store, b"key").unwrap();
+
+    let restored = new_scope();
+    restored.restore(&store, b"key").unwrap();
+    assert!(restored.is_allowed(dir.path().join("file")));
+    assert!(!restored.is_allowed(dir.path().join("secret")));
+
+    assert!(new_scope().restore(&store, b"other key").is_err());
+
+    // the changes can't be edited without the key
+    let tampered = std::fs::read_to_string(&store)
+      .unwrap()
+      .replace("forbidFile", "allowFile");
+    std::fs::write(&store, tampered).unwrap();
+    assert!(new_scope().restore(&store, b"key").is_err());
+  }
+
+  #[test]
+  fn denied_path_triggers_event() {
+    let scope = new_scope();
+    let denied = Arc::new(Mutex::new(Vec::new()));
+    let denied_ = denied.clone();
+    scope.listen(move |event| {
+      if let Event::PathDenied(path) = event {
+        denied_.lock().unwrap().push(path.clone());
+      }
+    });
+
+    #[cfg(unix)]
+    let path = "/home/tauri/file";
+    #[cfg(windows)]
+    let path = "C:\\home\\tauri\\file";
+    assert!(!scope.is_allowed(path));
+    assert_eq!(
+      *denied.lock().unwrap(),
+      vec![std::path::PathBuf::from(path)]
+    );
+  }
 }
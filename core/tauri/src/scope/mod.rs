@@ -9,6 +9,13 @@ pub mod ipc;
 pub use self::ipc::Scope as IpcScope;
 pub use fs::{Event as FsScopeEvent, Pattern as GlobPattern, Scope as FsScope};
 use std::path::Path;
+pub use tauri_utils::scope::Verdict as FsScopeVerdict;
+
+#[cfg(feature = "protocol-asset")]
+const ASSET_PROTOCOL_SCOPE_STORE: &str = "asset-protocol-scope.json";
+/// The key signing the scope stores, generated at build time or read from the `TAURI_SCOPE_STORE_KEY` environment variable.
+#[cfg(feature = "protocol-asset")]
+const SCOPE_STORE_KEY: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/scope-store-key"));
 
 /// Managed state for all the core scopes in a tauri application.
 pub struct Scopes {
@@ -42,3 +49,35 @@ impl Scopes {
     Ok(())
   }
 }
+
+/// Restores the asset protocol scope changes of the previous launches and persists the new ones.
+///
+/// A store that doesn't match the signing key is discarded.
+#[cfg(feature = "protocol-asset")]
+pub(crate) fn persist_asset_protocol_scope<R: crate::Runtime, M: crate::Manager<R>>(
+  manager: &M,
+) -> crate::Result<()> {
+  let store_path = match manager.path().app_local_data_dir() {
+    Ok(local_data_dir) => local_data_dir.join(ASSET_PROTOCOL_SCOPE_STORE),
+    // the scope changes can't be persisted without a local data directory
+    Err(_) => return Ok(()),
+  };
+  let scope = manager.asset_protocol_scope();
+  if store_path.exists() {
+    if let Err(crate::Error::InvalidScopeStore(_)) = scope.restore(&store_path, SCOPE_STORE_KEY) {
+      let _ = std::fs::remove_file(&store_path);
+    }
+  }
+
+  let scope_ = scope.clone();
+  scope.listen(move |event| {
+    if matches!(
+      event,
+      FsScopeEvent::PathAllowed(_) | FsScopeEvent::PathForbidden(_)
+    ) {
+      let _ = scope_.persist(&store_path, SCOPE_STORE_KEY);
+    }
+  });
+
+  Ok(())
+}
@@ -0,0 +1,206 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The initialization scripts injected in the webviews.
+//!
+//! The scripts of a window run in this order, before any script of the page:
+//! the Tauri scripts (IPC, plugins and the isolation pattern), the app scripts added with
+//! [`crate::Builder::initialization_script`] or [`crate::AppHandle::add_initialization_script`] in insertion order,
+//! then the window scripts added with [`crate::WindowBuilder::initialization_script`] in insertion order.
+
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+  sync::{
+    atomic::{AtomicU32, Ordering},
+    Mutex,
+  },
+};
+
+/// A JavaScript script that runs when a page is loaded, after the global object has been created
+/// but before the HTML document has been parsed and before any other script included by the HTML document is run.
+///
+/// By default the script runs on every page navigation of the top-level document and of the child frames,
+/// use [`Self::main_frame_only`] and [`Self::once`] to restrict it.
+///
+/// # Examples
+///
+/// ```
+/// use tauri::Script;
+///
+/// tauri::Builder::default()
+///   .initialization_script(Script::new("window.__MY_APP__ = { ready: false }").main_frame_only())
+///   .initialization_script(Script::new("console.log('first load')").once().windows(["main"]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Script {
+  source: String,
+  main_frame_only: bool,
+  once: bool,
+  windows: Option<Vec<String>>,
+}
+
+impl Script {
+  /// Creates a script running the given JavaScript source.
+  pub fn new(source: impl Into<String>) -> Self {
+    Self {
+      source: source.into(),
+      main_frame_only: false,
+      once: false,
+      windows: None,
+    }
+  }
+
+  /// Only runs the script in the top-level document, not in the child frames.
+  #[must_use]
+  pub fn main_frame_only(mut self) -> Self {
+    self.main_frame_only = true;
+    self
+  }
+
+  /// Only runs the script on the first page load of each origin in the window,
+  /// not on the following navigations and reloads.
+  ///
+  /// The loaded state is kept in the `sessionStorage` of the origin.
+  #[must_use]
+  pub fn once(mut self) -> Self {
+    self.once = true;
+    self
+  }
+
+  /// Only injects the script in the windows with the given labels.
+  ///
+  /// Only applies to the app scripts, a window script is always injected in its window.
+  #[must_use]
+  pub fn windows<I: IntoIterator<Item = S>, S: Into<String>>(mut self, labels: I) -> Self {
+    self.windows = Some(labels.into_iter().map(Into::into).collect());
+    self
+  }
+
+  /// The JavaScript source of the script.
+  pub fn source(&self) -> &str {
+    &self.source
+  }
+
+  pub(crate) fn applies_to(&self, label: &str) -> bool {
+    self
+      .windows
+      .as_ref()
+      .map_or(true, |windows| windows.iter().any(|w| w == label))
+  }
+
+  /// The script injected in the webview.
+  ///
+  /// The source is wrapped in a guard function when it is restricted to the main frame or the first load,
+  /// so its top-level declarations are not globals in that case.
+  pub(crate) fn render(&self) -> String {
+    if !self.main_frame_only && !self.once {
+      return self.source.clone();
+    }
+
+    let mut guards = String::new();
+    if self.main_frame_only {
+      guards.push_str("if (window !== window.top) return;\n");
+    }
+    if self.once {
+      let mut hasher = DefaultHasher::new();
+      self.source.hash(&mut hasher);
+      let key = format!("__TAURI_SCRIPT_LOADED_{:x}__", hasher.finish());
+      guards.push_str(&format!(
+        "try {{ if (window.sessionStorage.getItem('{key}')) return; window.sessionStorage.setItem('{key}', '1') }} catch (_) {{}}\n"
+      ));
+    }
+    format!("(function () {{\n{guards}{}\n}})();", self.source)
+  }
+}
+
+impl From<&str> for Script {
+  fn from(source: &str) -> Self {
+    Self::new(source)
+  }
+}
+
+impl From<&String> for Script {
+  fn from(source: &String) -> Self {
+    Self::new(source.as_str())
+  }
+}
+
+impl From<String> for Script {
+  fn from(source: String) -> Self {
+    Self::new(source)
+  }
+}
+
+/// The identifier of an app script, returned by [`crate::AppHandle::add_initialization_script`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScriptId(u32);
+
+/// The app scripts, injected in the windows created after they are added.
+#[derive(Default)]
+pub(crate) struct AppScripts {
+  next_id: AtomicU32,
+  scripts: Mutex<Vec<(ScriptId, Script)>>,
+}
+
+impl AppScripts {
+  pub(crate) fn add(&self, script: Script) -> ScriptId {
+    let id = ScriptId(self.next_id.fetch_add(1, Ordering::Relaxed));
+    self.scripts.lock().unwrap().push((id, script));
+    id
+  }
+
+  pub(crate) fn remove(&self, id: ScriptId) -> bool {
+    let mut scripts = self.scripts.lock().unwrap();
+    let len = scripts.len();
+    scripts.retain(|(script_id, _)| *script_id != id);
+    scripts.len() != len
+  }
+
+  /// The rendered scripts of the window, in insertion order.
+  pub(crate) fn for_window(&self, label: &str) -> Vec<String> {
+    self
+      .scripts
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(_, script)| script.applies_to(label))
+      .map(|(_, script)| script.render())
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{AppScripts, Script};
+
+  #[test]
+  fn app_scripts_keep_insertion_order() {
+    let scripts = AppScripts::default();
+    let first = scripts.add(Script::new("first()"));
+    scripts.add(Script::new("main()").windows(["main"]));
+    scripts.add(Script::new("last()"));
+
+    assert_eq!(scripts.for_window("main"), ["first()", "main()", "last()"]);
+    assert_eq!(scripts.for_window("other"), ["first()", "last()"]);
+
+    assert!(scripts.remove(first));
+    assert!(!scripts.remove(first));
+    assert_eq!(scripts.for_window("other"), ["last()"]);
+  }
+
+  #[test]
+  fn render_guards() {
+    assert_eq!(Script::new("run()").render(), "run()");
+
+    let main_frame = Script::new("run()").main_frame_only().render();
+    assert!(main_frame.contains("window !== window.top"));
+    assert!(!main_frame.contains("sessionStorage"));
+
+    let once = Script::new("run()").once().render();
+    assert!(once.contains("sessionStorage"));
+    assert_eq!(once, Script::new("run()").once().render());
+    assert_ne!(once, Script::new("other()").once().render());
+  }
+}
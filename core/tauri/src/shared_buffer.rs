@@ -0,0 +1,207 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Large buffers read by the frontend as an `ArrayBuffer` from the `buffer` custom protocol,
+//! instead of being serialized to JSON in an IPC response.
+
+use crate::{Manager, Runtime};
+use serde::{Serialize, Serializer};
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak},
+};
+use tauri_runtime::http::{
+  header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE},
+  status::StatusCode,
+  HttpRange, Request, Response, ResponseBuilder,
+};
+
+/// The custom protocol serving the shared buffers.
+pub(crate) const SHARED_BUFFER_PROTOCOL: &str = "buffer";
+
+type Data = Arc<RwLock<Vec<u8>>>;
+type WeakData = Weak<RwLock<Vec<u8>>>;
+
+/// A buffer shared with the frontend, which reads it with `fetch` from its [`Self::url`]
+/// or with `readSharedBuffer` from `@tauri-apps/api/tauri`.
+///
+/// The buffer is served while a handle to it exists and is serialized as its URL, so a command can return it.
+/// Each request copies the buffer once into the response body, without the JSON encoding of the IPC;
+/// a part of it can be requested with the `Range` header.
+///
+/// See the [`crate::ipc`] module for an example.
+#[derive(Clone)]
+pub struct SharedBuffer {
+  id: String,
+  data: Data,
+}
+
+impl SharedBuffer {
+  /// Allocates a zeroed buffer of `len` bytes.
+  pub fn new<R: Runtime, M: Manager<R>>(manager: &M, len: usize) -> Self {
+    Self::from_vec(manager, vec![0; len])
+  }
+
+  /// Shares the given bytes.
+  pub fn from_vec<R: Runtime, M: Manager<R>>(manager: &M, data: Vec<u8>) -> Self {
+    let buffer = Self {
+      id: uuid::Uuid::new_v4().simple().to_string(),
+      data: Arc::new(RwLock::new(data)),
+    };
+    if let Some(buffers) = manager.try_state::<SharedBuffers>() {
+      buffers.insert(&buffer);
+    }
+    buffer
+  }
+
+  /// The identifier of the buffer.
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+
+  /// The URL the frontend reads the buffer from.
+  pub fn url(&self) -> String {
+    if cfg!(windows) {
+      format!("https://{SHARED_BUFFER_PROTOCOL}.localhost/{}", self.id)
+    } else {
+      format!("{SHARED_BUFFER_PROTOCOL}://localhost/{}", self.id)
+    }
+  }
+
+  /// Locks the buffer for reading.
+  pub fn read(&self) -> RwLockReadGuard<'_, Vec<u8>> {
+    self.data.read().unwrap()
+  }
+
+  /// Locks the buffer for writing. The frontend requests wait until the lock is released.
+  pub fn write(&self) -> RwLockWriteGuard<'_, Vec<u8>> {
+    self.data.write().unwrap()
+  }
+}
+
+impl std::fmt::Debug for SharedBuffer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SharedBuffer")
+      .field("id", &self.id)
+      .field("len", &self.read().len())
+      .finish()
+  }
+}
+
+impl Serialize for SharedBuffer {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.url())
+  }
+}
+
+/// The buffers served by the protocol, pruned when a buffer is shared.
+#[derive(Default, Clone)]
+pub(crate) struct SharedBuffers(Arc<Mutex<HashMap<String, WeakData>>>);
+
+impl SharedBuffers {
+  fn insert(&self, buffer: &SharedBuffer) {
+    let mut buffers = self.0.lock().unwrap();
+    buffers.retain(|_, data| data.strong_count() > 0);
+    buffers.insert(buffer.id.clone(), Arc::downgrade(&buffer.data));
+  }
+
+  fn get(&self, id: &str) -> Option<Data> {
+    self.0.lock().unwrap().get(id).and_then(Weak::upgrade)
+  }
+}
+
+/// Serves the buffer, or the first range of the `Range` header.
+pub(crate) fn shared_buffer_protocol_handler(
+  buffers: &SharedBuffers,
+  request: &Request,
+  window_origin: &str,
+) -> Result<Response, Box<dyn std::error::Error>> {
+  let uri = url::Url::parse(request.uri())?;
+  let id = uri.path().trim_start_matches('/');
+  let data = match buffers.get(id) {
+    Some(data) => data,
+    None => return ResponseBuilder::new().status(404).body(Vec::new()),
+  };
+  let data = data.read().unwrap();
+  let len = data.len() as u64;
+
+  let response = ResponseBuilder::new()
+    .header("Access-Control-Allow-Origin", window_origin)
+    .header(CONTENT_TYPE, "application/octet-stream")
+    .header(ACCEPT_RANGES, "bytes");
+
+  let range = request
+    .headers()
+    .get("range")
+    .and_then(|r| r.to_str().ok())
+    .map(|r| HttpRange::parse(r, len));
+  match range {
+    Some(Ok(ranges)) if !ranges.is_empty() => {
+      let start = ranges[0].start;
+      let end = start + ranges[0].length;
+      response
+        .status(StatusCode::PARTIAL_CONTENT)
+        .header(CONTENT_RANGE, format!("bytes {start}-{}/{len}", end - 1))
+        .header(CONTENT_LENGTH, end - start)
+        .body(data[start as usize..end as usize].to_vec())
+    }
+    Some(_) => ResponseBuilder::new()
+      .status(StatusCode::RANGE_NOT_SATISFIABLE)
+      .header(CONTENT_RANGE, format!("bytes */{len}"))
+      .body(Vec::new()),
+    None => response.header(CONTENT_LENGTH, len).body(data.to_vec()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{shared_buffer_protocol_handler, SharedBuffer, SharedBuffers};
+  use std::sync::{Arc, RwLock};
+  use tauri_runtime::http::{
+    header::{HeaderMap, HeaderValue},
+    method::Method,
+    Request, RequestParts,
+  };
+
+  fn request(buffer: &SharedBuffer, range: Option<&str>) -> Request {
+    let mut headers = HeaderMap::new();
+    if let Some(range) = range {
+      headers.insert("range", HeaderValue::from_str(range).unwrap());
+    }
+    Request::new_internal(
+      RequestParts {
+        method: Method::GET,
+        uri: format!("buffer://localhost/{}", buffer.id()),
+        headers,
+      },
+      Vec::new(),
+    )
+  }
+
+  #[test]
+  fn serves_buffers_while_a_handle_exists() {
+    let buffers = SharedBuffers::default();
+    let buffer = SharedBuffer {
+      id: "frame".into(),
+      data: Arc::new(RwLock::new((0..10).collect())),
+    };
+    buffers.insert(&buffer);
+    buffer.write()[0] = 42;
+
+    let response = shared_buffer_protocol_handler(&buffers, &request(&buffer, None), "*").unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body()[..3], [42, 1, 2]);
+
+    let response =
+      shared_buffer_protocol_handler(&buffers, &request(&buffer, Some("bytes=2-4")), "*").unwrap();
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body()[..], [2, 3, 4]);
+    assert_eq!(response.headers()["content-range"], "bytes 2-4/10");
+
+    let request = request(&buffer, None);
+    drop(buffer);
+    let response = shared_buffer_protocol_handler(&buffers, &request, "*").unwrap();
+    assert_eq!(response.status(), 404);
+  }
+}
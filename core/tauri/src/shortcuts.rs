@@ -0,0 +1,261 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The shortcuts of the app, set with [`crate::AppHandle::set_shortcuts`]: the app shortcuts shown when long pressing
+//! the app icon on Android, the Home Screen quick actions on iOS and the tasks of the jump list on Windows.
+//!
+//! Activating a shortcut emits [`crate::RunEvent::ShortcutActivated`] with its identifier.
+//! The shortcut activated to launch the app is also returned by [`crate::AppHandle::launch_shortcut`],
+//! since it is received before the app can listen to the events.
+//!
+//! The static shortcuts, available before the app first runs, are configured in
+//! [`tauri > bundle > shortcuts`](crate::utils::config::BundleConfig#structfield.shortcuts) on Android and iOS.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: A task runs the app executable with the [`SHORTCUT_ARGUMENT`] argument followed by the shortcut identifier.
+//!   The event is only emitted by the launched instance, an app using a single instance plugin reads the identifier
+//!   of the arguments it forwards with [`shortcut_from_args`].
+//! - **Linux / macOS**: Unsupported.
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+use std::sync::Mutex;
+
+/// The event emitted to the webviews when a shortcut is activated, with the shortcut identifier as payload.
+pub(crate) const SHORTCUT_EVENT: &str = "tauri://shortcut";
+
+/// The argument preceding the identifier of the shortcut running the app on Windows.
+pub const SHORTCUT_ARGUMENT: &str = "--tauri-shortcut";
+
+type Sender = Box<dyn Fn(String) + Send>;
+
+/// Sends the activated shortcuts to the event loop.
+static SENDER: OnceCell<Mutex<Sender>> = OnceCell::new();
+/// The shortcuts activated before the event loop could receive them, e.g. the shortcut launching the app.
+static PENDING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LAUNCH_SHORTCUT: OnceCell<String> = OnceCell::new();
+
+/// A shortcut of the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shortcut {
+  id: String,
+  title: String,
+  description: Option<String>,
+  icon: Option<String>,
+}
+
+impl Shortcut {
+  /// Creates a shortcut with the given identifier, received by the app when it is activated, and title.
+  pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+    Self {
+      id: id.into(),
+      title: title.into(),
+      description: None,
+      icon: None,
+    }
+  }
+
+  /// The long label on Android, shown when there is enough space, the subtitle on iOS or the tooltip on Windows.
+  #[must_use]
+  pub fn description(mut self, description: impl Into<String>) -> Self {
+    self.description.replace(description.into());
+    self
+  }
+
+  /// The name of a drawable resource on Android, the name of an SF Symbol on iOS,
+  /// or the path of an `.ico` file on Windows. Defaults to the app icon on Android and Windows.
+  #[must_use]
+  pub fn icon(mut self, icon: impl Into<String>) -> Self {
+    self.icon.replace(icon.into());
+    self
+  }
+}
+
+/// Returns the identifier of the shortcut following [`SHORTCUT_ARGUMENT`] in the arguments of the app.
+///
+/// # Examples
+///
+/// ```
+/// let args = ["app.exe", "--tauri-shortcut", "new-note"];
+/// assert_eq!(tauri::shortcuts::shortcut_from_args(args), Some("new-note".into()));
+/// ```
+pub fn shortcut_from_args<I: IntoIterator<Item = S>, S: AsRef<str>>(args: I) -> Option<String> {
+  let mut args = args.into_iter();
+  args
+    .by_ref()
+    .find(|arg| arg.as_ref() == SHORTCUT_ARGUMENT)?;
+  args.next().map(|id| id.as_ref().to_string())
+}
+
+/// Forwards the activated shortcuts to the event loop, starting with the shortcuts activated so far.
+fn listen<R: Runtime>(app_handle: &crate::AppHandle<R>) {
+  use crate::runtime::{EventLoopProxy, RuntimeHandle};
+
+  let proxy = app_handle.runtime_handle.create_proxy();
+  let mut pending = PENDING.lock().unwrap();
+  for id in pending.drain(..) {
+    let _ = proxy.send_event(crate::EventLoopMessage::Shortcut(id));
+  }
+  let _ = SENDER.set(Mutex::new(Box::new(move |id| {
+    let _ = proxy.send_event(crate::EventLoopMessage::Shortcut(id));
+  })));
+}
+
+/// Handles an activated shortcut.
+#[cfg_attr(not(any(windows, mobile)), allow(dead_code))]
+fn dispatch(id: String, cold_start: bool) {
+  if cold_start {
+    let _ = LAUNCH_SHORTCUT.set(id.clone());
+  }
+
+  // the pending shortcuts are flushed while holding the lock, so the shortcuts stay in order
+  let mut pending = PENDING.lock().unwrap();
+  match SENDER.get() {
+    Some(sender) => (sender.lock().unwrap())(id),
+    None => pending.push(id),
+  }
+}
+
+/// Glue between Rust and the Kotlin code that forwards the activated shortcuts.
+#[cfg(target_os = "android")]
+pub fn handle_android_shortcut_event(
+  env: jni::JNIEnv<'_>,
+  id: jni::objects::JString<'_>,
+  cold_start: bool,
+) {
+  if let Ok(id) = env.get_string(id) {
+    dispatch(id.into(), cold_start);
+  }
+}
+
+/// The shortcut activated to launch the app.
+pub(crate) fn launch_shortcut() -> Option<String> {
+  LAUNCH_SHORTCUT.get().cloned()
+}
+
+/// The native shortcuts plugin.
+#[cfg(mobile)]
+struct ShortcutsPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Replaces the shortcuts of the app.
+pub(crate) fn set<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  shortcuts: Vec<Shortcut>,
+) -> crate::Result<()> {
+  #[cfg(windows)]
+  {
+    use crate::jump_list::{JumpListCategory, JumpListItem};
+
+    let _ = manager;
+    let items = shortcuts
+      .into_iter()
+      .map(|shortcut| {
+        let mut item =
+          JumpListItem::new(shortcut.title).arguments([SHORTCUT_ARGUMENT.to_string(), shortcut.id]);
+        if let Some(description) = shortcut.description {
+          item = item.description(description);
+        }
+        if let Some(icon) = shortcut.icon {
+          item = item.icon(icon);
+        }
+        item
+      })
+      .collect();
+    crate::jump_list::set(&[JumpListCategory::tasks(items)])
+  }
+
+  #[cfg(mobile)]
+  {
+    if let Some(plugin) = manager.try_state::<ShortcutsPlugin<R>>() {
+      plugin.0.run_mobile_plugin::<()>(
+        "setShortcuts",
+        serde_json::json!({ "shortcuts": shortcuts }),
+      )?;
+    }
+    Ok(())
+  }
+
+  #[cfg(not(any(windows, mobile)))]
+  {
+    let _ = (manager, shortcuts);
+    Ok(())
+  }
+}
+
+mod commands {
+  use super::Shortcut;
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn set_shortcuts<R: Runtime>(
+    app: AppHandle<R>,
+    shortcuts: Vec<Shortcut>,
+  ) -> crate::Result<()> {
+    super::set(&app, shortcuts)
+  }
+
+  #[command(root = "crate")]
+  pub(super) fn launch_shortcut() -> Option<String> {
+    super::launch_shortcut()
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("shortcuts")
+    .invoke_handler(crate::generate_handler![
+      commands::set_shortcuts,
+      commands::launch_shortcut
+    ])
+    .setup(|app, _api| {
+      #[cfg(windows)]
+      if let Some(id) = shortcut_from_args(std::env::args()) {
+        dispatch(id, true);
+      }
+
+      #[cfg(target_os = "android")]
+      app.manage(ShortcutsPlugin(
+        _api.register_android_plugin("app.tauri.shortcuts", "ShortcutsPlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      {
+        extern "C" fn handler(id: *const std::os::raw::c_char, cold_start: bool) {
+          let id = unsafe { std::ffi::CStr::from_ptr(id) };
+          dispatch(id.to_string_lossy().into_owned(), cold_start);
+        }
+        app.manage(ShortcutsPlugin(
+          _api.register_ios_plugin(crate::ios::init_shortcuts_plugin)?,
+        ));
+        unsafe {
+          crate::ios::set_shortcut_event_handler(crate::ios::ShortcutEventHandler(handler))
+        };
+      }
+
+      listen(app);
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::shortcut_from_args;
+
+  #[test]
+  fn reads_shortcut_from_args() {
+    assert_eq!(
+      shortcut_from_args(["app", "--tauri-shortcut", "compose"]),
+      Some("compose".into())
+    );
+    assert_eq!(shortcut_from_args(["app", "--tauri-shortcut"]), None);
+    assert_eq!(shortcut_from_args(["app", "compose"]), None);
+  }
+}
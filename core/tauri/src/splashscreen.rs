@@ -0,0 +1,129 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The splashscreen configured on `tauri > splashscreen`, closed by [`crate::AppHandle::close_splashscreen`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  utils::config::{SplashscreenConfig, WindowConfig, WindowUrl},
+  Manager, Runtime,
+};
+
+use std::{
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+  },
+  time::{Duration, Instant},
+};
+
+/// The label of the splashscreen window.
+pub(crate) const SPLASHSCREEN_LABEL: &str = "splashscreen";
+
+/// The script scaling the splashscreen image to fit the window and fading the splashscreen out.
+pub(crate) const SPLASHSCREEN_SCRIPT: &str = include_str!("../scripts/splashscreen.js");
+
+pub(crate) struct Splashscreen {
+  config: SplashscreenConfig,
+  shown_at: Instant,
+  /// The labels of the windows hidden while the splashscreen is shown.
+  hidden_windows: Mutex<Vec<String>>,
+  closed: AtomicBool,
+}
+
+impl Splashscreen {
+  pub(crate) fn new(config: SplashscreenConfig) -> Self {
+    Self {
+      config,
+      shown_at: Instant::now(),
+      hidden_windows: Default::default(),
+      closed: AtomicBool::new(false),
+    }
+  }
+
+  /// Hides the visible windows of the configuration, and returns the configuration of the splashscreen window.
+  pub(crate) fn window_config(&self, windows: &mut [WindowConfig]) -> WindowConfig {
+    let mut hidden_windows = self.hidden_windows.lock().unwrap();
+    for window in windows.iter_mut().filter(|window| window.visible) {
+      window.visible = false;
+      hidden_windows.push(window.label.clone());
+    }
+
+    WindowConfig {
+      label: SPLASHSCREEN_LABEL.into(),
+      url: match &self.config.image {
+        Some(image) => WindowUrl::App(image.clone()),
+        None => self.config.url.clone(),
+      },
+      width: self.config.width,
+      height: self.config.height,
+      center: true,
+      resizable: false,
+      maximizable: false,
+      minimizable: false,
+      decorations: false,
+      transparent: self.config.transparent,
+      ..Default::default()
+    }
+  }
+}
+
+/// Shows the hidden windows and closes the splashscreen, once it has been shown for the minimum display time.
+pub(crate) fn close<R: Runtime, M: Manager<R>>(manager: &M) {
+  let Some(splashscreen) = manager.try_state::<Splashscreen>() else {
+    return;
+  };
+  if splashscreen.closed.swap(true, Ordering::SeqCst) {
+    return;
+  }
+
+  let remaining = Duration::from_millis(splashscreen.config.min_display_time)
+    .saturating_sub(splashscreen.shown_at.elapsed());
+  let fade_out_duration = splashscreen.config.fade_out_duration;
+  let hidden_windows = std::mem::take(&mut *splashscreen.hidden_windows.lock().unwrap());
+  let app_handle = manager.app_handle();
+
+  // `Window::close` cannot be called on the main thread
+  std::thread::spawn(move || {
+    std::thread::sleep(remaining);
+
+    for label in &hidden_windows {
+      if let Some(window) = app_handle.get_window(label) {
+        let _ = window.show();
+      }
+    }
+    if let Some(window) = hidden_windows
+      .first()
+      .and_then(|label| app_handle.get_window(label))
+    {
+      let _ = window.set_focus();
+    }
+
+    if let Some(splashscreen) = app_handle.get_window(SPLASHSCREEN_LABEL) {
+      if fade_out_duration > 0 {
+        let _ = splashscreen.eval(&format!(
+          "window.__TAURI_SPLASHSCREEN__ && window.__TAURI_SPLASHSCREEN__.fadeOut({fade_out_duration})"
+        ));
+        std::thread::sleep(Duration::from_millis(fade_out_duration));
+      }
+      let _ = splashscreen.close();
+    }
+  });
+}
+
+mod commands {
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) fn close<R: Runtime>(app: AppHandle<R>) {
+    super::close(&app);
+  }
+}
+
+/// Initializes the splashscreen plugin, letting the webview close the splashscreen.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("splashscreen")
+    .invoke_handler(crate::generate_handler![commands::close])
+    .build()
+}
@@ -0,0 +1,130 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The startup timeline of the app, returned by [`crate::AppHandle::startup_metrics`].
+
+use crate::{manager::WindowManager, Runtime};
+use std::{
+  fmt,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// When each startup phase completed, as the time elapsed since the [`crate::Builder`] was created.
+///
+/// A phase is `None` until it completes. In development the timeline is printed when the DOM of the first window is ready.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StartupMetrics {
+  /// The context, including the configuration, is loaded and [`crate::Builder::build`] is called.
+  pub config: Option<Duration>,
+  /// The event loop and the webview runtime are initialized.
+  pub runtime_init: Option<Duration>,
+  /// The windows defined in the configuration are created.
+  pub window_creation: Option<Duration>,
+  /// A window starts loading its URL.
+  pub first_navigation: Option<Duration>,
+  /// The DOM of a window is loaded.
+  pub dom_ready: Option<Duration>,
+}
+
+impl fmt::Display for StartupMetrics {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let phases = [
+      ("config", self.config),
+      ("runtime init", self.runtime_init),
+      ("window creation", self.window_creation),
+      ("first navigation", self.first_navigation),
+      ("DOM ready", self.dom_ready),
+    ];
+    for (i, (name, elapsed)) in phases.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      match elapsed {
+        Some(elapsed) => write!(f, "{name} {:.1}ms", elapsed.as_secs_f64() * 1000.)?,
+        None => write!(f, "{name} -")?,
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum StartupPhase {
+  Config,
+  RuntimeInit,
+  WindowCreation,
+  FirstNavigation,
+  DomReady,
+}
+
+pub(crate) struct StartupTimeline {
+  start: Instant,
+  metrics: Mutex<StartupMetrics>,
+}
+
+impl StartupTimeline {
+  pub(crate) fn new(start: Instant) -> Self {
+    Self {
+      start,
+      metrics: Default::default(),
+    }
+  }
+
+  /// Records the phase, unless it already completed.
+  pub(crate) fn record(&self, phase: StartupPhase) {
+    let elapsed = self.start.elapsed();
+    let mut metrics = self.metrics.lock().unwrap();
+    let slot = match phase {
+      StartupPhase::Config => &mut metrics.config,
+      StartupPhase::RuntimeInit => &mut metrics.runtime_init,
+      StartupPhase::WindowCreation => &mut metrics.window_creation,
+      StartupPhase::FirstNavigation => &mut metrics.first_navigation,
+      StartupPhase::DomReady => &mut metrics.dom_ready,
+    };
+    if slot.is_some() {
+      return;
+    }
+    *slot = Some(elapsed);
+
+    #[cfg(dev)]
+    if let StartupPhase::DomReady = phase {
+      log::info!("Startup timeline: {}", *metrics);
+    }
+  }
+
+  pub(crate) fn metrics(&self) -> StartupMetrics {
+    *self.metrics.lock().unwrap()
+  }
+}
+
+/// Records the phase on the timeline of the app.
+pub(crate) fn record<R: Runtime>(manager: &WindowManager<R>, phase: StartupPhase) {
+  if let Some(timeline) = manager.state().try_get::<StartupTimeline>() {
+    timeline.record(phase);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{StartupPhase, StartupTimeline};
+  use std::time::Instant;
+
+  #[test]
+  fn records_phases_once() {
+    let timeline = StartupTimeline::new(Instant::now());
+    timeline.record(StartupPhase::Config);
+    let config = timeline.metrics().config;
+    assert!(config.is_some());
+    assert!(timeline.metrics().dom_ready.is_none());
+
+    timeline.record(StartupPhase::Config);
+    timeline.record(StartupPhase::DomReady);
+    let metrics = timeline.metrics();
+    assert_eq!(metrics.config, config);
+    assert!(metrics.dom_ready >= config);
+    assert!(metrics.to_string().starts_with("config "));
+  }
+}
@@ -7,7 +7,7 @@
 
 use tauri_runtime::{
   menu::{Menu, MenuUpdate},
-  monitor::Monitor,
+  monitor::{FullscreenMode, Monitor},
   webview::{WindowBuilder, WindowBuilderBase},
   window::{
     dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -303,6 +303,10 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
+  fn ignore_cursor_events(self, ignore: bool) -> Self {
+    self
+  }
+
   fn shadow(self, enable: bool) -> Self {
     self
   }
@@ -627,6 +631,10 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
     Ok(())
   }
 
+  fn set_fullscreen_mode(&self, mode: Option<FullscreenMode>) -> Result<()> {
+    Ok(())
+  }
+
   fn set_focus(&self) -> Result<()> {
     Ok(())
   }
@@ -129,9 +129,16 @@ pub fn mock_context<A: Assets>(assets: A) -> crate::Context<A> {
         security: Default::default(),
         system_tray: None,
         macos_private_api: false,
+        external_assets: Default::default(),
+        blocking_pool: Default::default(),
+        keep_running_without_windows: false,
+        linux: Default::default(),
+        splashscreen: Default::default(),
+        process_isolation: false,
       },
       build: Default::default(),
       plugins: Default::default(),
+      custom: None,
     },
     assets: Arc::new(assets),
     default_window_icon: None,
@@ -147,6 +154,9 @@ pub fn mock_context<A: Assets>(assets: A) -> crate::Context<A> {
     },
     _info_plist: (),
     pattern: Pattern::Brownfield(std::marker::PhantomData),
+    plugin_permissions: Default::default(),
+    #[cfg(feature = "external-assets")]
+    asset_verification_failure: None,
   }
 }
 
@@ -71,12 +71,16 @@ pub fn apply_effects(window: id, effects: WindowEffectsConfig) {
       appearance = NSVisualEffectMaterial::AppearanceBased;
     }
 
+    // replace the view of the previous effects instead of stacking them
+    clear_effects(window);
+
     let ns_view: id = window.contentView();
     let bounds = NSView::bounds(ns_view);
 
     let blurred_view = NSVisualEffectView::initWithFrame_(NSVisualEffectView::alloc(nil), bounds);
     blurred_view.autorelease();
 
+    let _: () = msg_send![blurred_view, setIdentifier: effects_view_identifier()];
     blurred_view.setMaterial_(appearance);
     blurred_view.setCornerRadius_(radius.unwrap_or(0.0));
     blurred_view.setBlendingMode_(NSVisualEffectBlendingMode::BehindWindow);
@@ -94,6 +98,37 @@ pub fn apply_effects(window: id, effects: WindowEffectsConfig) {
   }
 }
 
+/// Removes the view added by [`apply_effects`].
+pub fn clear_effects(window: id) {
+  unsafe {
+    if !msg_send![class!(NSThread), isMainThread] {
+      return;
+    }
+
+    let ns_view: id = window.contentView();
+    let subviews: id = msg_send![ns_view, subviews];
+    let count: usize = msg_send![subviews, count];
+    let identifier = effects_view_identifier();
+    // iterate in reverse since the removed views are removed from the array
+    for i in (0..count).rev() {
+      let view: id = msg_send![subviews, objectAtIndex: i];
+      let view_identifier: id = msg_send![view, identifier];
+      let same: BOOL = msg_send![view_identifier, isEqualToString: identifier];
+      if view_identifier != nil && same == cocoa::base::YES {
+        view.removeFromSuperview();
+      }
+    }
+  }
+}
+
+/// The identifier of the [`NSVisualEffectView`] added by [`apply_effects`].
+unsafe fn effects_view_identifier() -> id {
+  use cocoa::foundation::NSString;
+  NSString::alloc(nil)
+    .init_str("tauri-window-effects")
+    .autorelease()
+}
+
 #[allow(non_upper_case_globals)]
 const NSAppKitVersionNumber10_14: f64 = 1671.0;
 
@@ -283,7 +318,14 @@ impl From<crate::window::Effect> for NSVisualEffectMaterial {
       Effect::ContentBackground => NSVisualEffectMaterial::ContentBackground,
       Effect::UnderWindowBackground => NSVisualEffectMaterial::UnderWindowBackground,
       Effect::UnderPageBackground => NSVisualEffectMaterial::UnderPageBackground,
-      Effect::Mica | Effect::Blur | Effect::Acrylic => unreachable!(),
+      Effect::Mica
+      | Effect::MicaDark
+      | Effect::MicaLight
+      | Effect::Tabbed
+      | Effect::TabbedDark
+      | Effect::TabbedLight
+      | Effect::Blur
+      | Effect::Acrylic => unreachable!(),
     }
   }
 }
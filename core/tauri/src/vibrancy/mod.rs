@@ -34,6 +34,11 @@ pub fn set_window_effects<R: Runtime>(
       let hwnd = window.hwnd()?;
       windows::clear_effects(hwnd);
     }
+    #[cfg(target_os = "macos")]
+    {
+      let ns_window = window.ns_window()?;
+      macos::clear_effects(ns_window as _);
+    }
   }
   Ok(())
 }
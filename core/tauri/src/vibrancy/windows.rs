@@ -23,19 +23,36 @@ use windows::Win32::{
 
 pub fn apply_effects(window: HWND, effects: WindowEffectsConfig) {
   let WindowEffectsConfig { effects, color, .. } = effects;
-  let effect = if let Some(effect) = effects
-    .iter()
-    .find(|e| matches!(e, Effect::Mica | Effect::Acrylic | Effect::Blur))
-  {
+  let effect = if let Some(effect) = effects.iter().find(|e| {
+    matches!(
+      e,
+      Effect::Mica
+        | Effect::MicaDark
+        | Effect::MicaLight
+        | Effect::Tabbed
+        | Effect::TabbedDark
+        | Effect::TabbedLight
+        | Effect::Acrylic
+        | Effect::Blur
+    )
+  }) {
     effect
   } else {
     return;
   };
 
+  // the previous effect would be kept below the new one if it is not the same kind
+  clear_effects(window);
+
   match effect {
     Effect::Blur => apply_blur(window, color),
     Effect::Acrylic => apply_acrylic(window, color),
-    Effect::Mica => apply_mica(window),
+    Effect::Mica => apply_mica(window, None),
+    Effect::MicaDark => apply_mica(window, Some(true)),
+    Effect::MicaLight => apply_mica(window, Some(false)),
+    Effect::Tabbed => apply_tabbed(window, None),
+    Effect::TabbedDark => apply_tabbed(window, Some(true)),
+    Effect::TabbedLight => apply_tabbed(window, Some(false)),
     _ => unreachable!(),
   }
 }
@@ -46,6 +63,21 @@ pub fn clear_effects(window: HWND) {
   clear_mica(window);
 }
 
+/// Forces the dark or light mode of the window frame, which the Mica and Tabbed materials follow.
+fn set_dark_mode(hwnd: HWND, dark: Option<bool>) {
+  if let Some(dark) = dark {
+    let dark = BOOL::from(dark);
+    let _ = unsafe {
+      DwmSetWindowAttribute(
+        hwnd,
+        DWMWA_USE_IMMERSIVE_DARK_MODE,
+        &dark as *const _ as _,
+        4,
+      )
+    };
+  }
+}
+
 pub fn apply_blur(hwnd: HWND, color: Option<Color>) {
   if is_windows_7() {
     let bb = DWM_BLURBEHIND {
@@ -114,7 +146,8 @@ pub fn clear_acrylic(hwnd: HWND) {
   }
 }
 
-pub fn apply_mica(hwnd: HWND) {
+pub fn apply_mica(hwnd: HWND, dark: Option<bool>) {
+  set_dark_mode(hwnd, dark);
   if is_backdroptype_supported() {
     unsafe {
       let _ = DwmSetWindowAttribute(
@@ -131,6 +164,23 @@ pub fn apply_mica(hwnd: HWND) {
   }
 }
 
+/// Applies the Tabbed material, which falls back to Mica before Windows 11 build 22523.
+pub fn apply_tabbed(hwnd: HWND, dark: Option<bool>) {
+  if is_backdroptype_supported() {
+    set_dark_mode(hwnd, dark);
+    unsafe {
+      let _ = DwmSetWindowAttribute(
+        hwnd,
+        DWMWA_SYSTEMBACKDROP_TYPE,
+        &DWM_SYSTEMBACKDROP_TYPE::DWMSBT_TABBEDWINDOW as *const _ as _,
+        4,
+      );
+    }
+  } else {
+    apply_mica(hwnd, dark);
+  }
+}
+
 pub fn clear_mica(hwnd: HWND) {
   if is_backdroptype_supported() {
     unsafe {
@@ -148,6 +198,7 @@ pub fn clear_mica(hwnd: HWND) {
   }
 }
 
+const DWMWA_USE_IMMERSIVE_DARK_MODE: DWMWINDOWATTRIBUTE = DWMWINDOWATTRIBUTE(20i32);
 const DWMWA_MICA_EFFECT: DWMWINDOWATTRIBUTE = DWMWINDOWATTRIBUTE(1029i32);
 const DWMWA_SYSTEMBACKDROP_TYPE: DWMWINDOWATTRIBUTE = DWMWINDOWATTRIBUTE(38i32);
 
@@ -222,7 +273,6 @@ unsafe fn SetWindowCompositionAttribute(
   }
 }
 
-#[allow(unused)]
 #[repr(C)]
 enum DWM_SYSTEMBACKDROP_TYPE {
   DWMSBT_DISABLE = 1,         // None
@@ -0,0 +1,129 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The data displayed by the home screen widgets of the app on Android and iOS.
+//!
+//! The widgets are generated with `tauri android add-module widget` and `tauri ios add-extension widget`.
+//! They run outside of the app, so the app writes their data as JSON to a container shared with them,
+//! then [reloads](reload) them to display it.
+//!
+//! ## Platform-specific
+//!
+//! - **Android**: The data is stored in the `tauri_widgets` shared preferences of the app.
+//! - **iOS**: The data is stored in the user defaults of the `group.<identifier>` App Group,
+//!   where `<identifier>` is [`tauri > bundle > identifier`](crate::utils::config::BundleConfig#structfield.identifier).
+//!   Requires iOS 14.
+//! - **Linux / macOS / Windows**: Unsupported, returns [`crate::Error::WidgetsUnavailable`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime,
+};
+
+use serde::Serialize;
+
+/// The native widgets plugin.
+#[cfg(mobile)]
+struct WidgetsPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Runs a command of the native plugin, sending the App Group of the widgets along with the payload.
+fn run<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  command: &str,
+  mut payload: serde_json::Value,
+) -> crate::Result<()> {
+  #[cfg(mobile)]
+  if let Some(plugin) = manager.try_state::<WidgetsPlugin<R>>() {
+    payload["appGroup"] = format!("group.{}", manager.config().tauri.bundle.identifier).into();
+    return plugin
+      .0
+      .run_mobile_plugin::<()>(command, payload)
+      .map_err(Into::into);
+  }
+
+  let _ = (manager, command, &mut payload);
+  Err(crate::Error::WidgetsUnavailable)
+}
+
+/// Writes the data stored with the key, serialized as JSON.
+///
+/// The widgets display the new data once [reloaded](reload).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let handle = app.handle();
+///     tauri::widgets::set_data(&handle, "widget", &serde_json::json!({ "title": "3 tasks left" }))?;
+///     tauri::widgets::reload(&handle)?;
+///     Ok(())
+///   });
+/// ```
+pub fn set_data<R: Runtime, M: Manager<R>, T: Serialize + ?Sized>(
+  manager: &M,
+  key: &str,
+  data: &T,
+) -> crate::Result<()> {
+  run(
+    manager,
+    "setData",
+    serde_json::json!({ "key": key, "value": serde_json::to_string(data)? }),
+  )
+}
+
+/// Removes the data stored with the key.
+pub fn remove_data<R: Runtime, M: Manager<R>>(manager: &M, key: &str) -> crate::Result<()> {
+  run(manager, "removeData", serde_json::json!({ "key": key }))
+}
+
+/// Reloads the widgets of the app, which read their data again.
+pub fn reload<R: Runtime, M: Manager<R>>(manager: &M) -> crate::Result<()> {
+  run(manager, "reload", serde_json::json!({}))
+}
+
+mod commands {
+  use crate::{command, AppHandle, Runtime};
+
+  #[command(root = "crate")]
+  pub(super) async fn set_data<R: Runtime>(
+    app: AppHandle<R>,
+    key: String,
+    data: serde_json::Value,
+  ) -> crate::Result<()> {
+    super::set_data(&app, &key, &data)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn remove_data<R: Runtime>(app: AppHandle<R>, key: String) -> crate::Result<()> {
+    super::remove_data(&app, &key)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn reload<R: Runtime>(app: AppHandle<R>) -> crate::Result<()> {
+    super::reload(&app)
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("widgets")
+    .invoke_handler(crate::generate_handler![
+      commands::set_data,
+      commands::remove_data,
+      commands::reload
+    ])
+    .setup(|app, _api| {
+      #[cfg(target_os = "android")]
+      app.manage(WidgetsPlugin(
+        _api.register_android_plugin("app.tauri.widgets", "WidgetsPlugin")?,
+      ));
+      #[cfg(target_os = "ios")]
+      app.manage(WidgetsPlugin(
+        _api.register_ios_plugin(crate::ios::init_widgets_plugin)?,
+      ));
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
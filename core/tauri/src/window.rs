@@ -4,10 +4,67 @@
 
 //! The Tauri window types and functions.
 
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod hints;
+pub(crate) mod keyboard;
+#[cfg(desktop)]
+pub(crate) mod kiosk;
+#[cfg(all(
+  feature = "wayland-layer-shell",
+  any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  )
+))]
+mod layer_shell;
 pub(crate) mod menu;
-
+#[cfg(desktop)]
+mod modal;
+#[cfg(desktop)]
+pub(crate) mod preload;
+pub(crate) mod system_bars;
+#[cfg(desktop)]
+pub(crate) mod title_bar;
+
+pub use crate::accessibility::AnnouncementPriority;
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub use hints::WindowTypeHint;
+pub use keyboard::{KeyEvent, KeyLocation, KeyboardEvent};
+#[cfg(all(
+  feature = "wayland-layer-shell",
+  any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  )
+))]
+pub use layer_shell::{ExclusiveZone, Layer, LayerEdge, LayerShell};
 pub use menu::{MenuEvent, MenuHandle};
-pub use tauri_utils::{config::Color, WindowEffect as Effect, WindowEffectState as EffectState};
+pub use system_bars::SafeAreaInsets;
+pub use tauri_runtime::monitor::VideoMode;
+pub use tauri_utils::{
+  config::{Color, SystemBarStyle},
+  WindowEffect as Effect, WindowEffectState as EffectState,
+};
+#[cfg(desktop)]
+pub use title_bar::TitleBarOverlayRect;
 use url::Url;
 
 #[cfg(target_os = "macos")]
@@ -75,6 +132,8 @@ pub struct Monitor {
   pub(crate) size: PhysicalSize<u32>,
   pub(crate) position: PhysicalPosition<i32>,
   pub(crate) scale_factor: f64,
+  #[serde(skip)]
+  pub(crate) video_modes: Vec<VideoMode>,
 }
 
 impl From<RuntimeMonitor> for Monitor {
@@ -84,6 +143,19 @@ impl From<RuntimeMonitor> for Monitor {
       size: monitor.size,
       position: monitor.position,
       scale_factor: monitor.scale_factor,
+      video_modes: monitor.video_modes,
+    }
+  }
+}
+
+impl From<Monitor> for RuntimeMonitor {
+  fn from(monitor: Monitor) -> Self {
+    Self {
+      name: monitor.name,
+      size: monitor.size,
+      position: monitor.position,
+      scale_factor: monitor.scale_factor,
+      video_modes: monitor.video_modes,
     }
   }
 }
@@ -109,6 +181,45 @@ impl Monitor {
   pub fn scale_factor(&self) -> f64 {
     self.scale_factor
   }
+
+  /// Returns the video modes supported by the monitor in exclusive fullscreen.
+  pub fn video_modes(&self) -> &[VideoMode] {
+    &self.video_modes
+  }
+}
+
+/// How a window is shown in fullscreen, see [`Window::set_fullscreen_mode`].
+#[derive(Debug, Clone)]
+pub enum FullscreenMode {
+  /// A borderless window covering the given monitor, or the current monitor of the window if `None`.
+  Borderless(Option<Monitor>),
+  /// The window takes exclusive control of the monitor, switching it to the given video mode,
+  /// which must be one of the [`Monitor::video_modes`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** Unsupported, falls back to a borderless fullscreen on the current monitor of the window.
+  Exclusive {
+    /// The monitor of the window.
+    monitor: Monitor,
+    /// The video mode of the monitor.
+    video_mode: VideoMode,
+  },
+}
+
+impl From<FullscreenMode> for crate::runtime::monitor::FullscreenMode {
+  fn from(mode: FullscreenMode) -> Self {
+    match mode {
+      FullscreenMode::Borderless(monitor) => Self::Borderless(monitor.map(Into::into)),
+      FullscreenMode::Exclusive {
+        monitor,
+        video_mode,
+      } => Self::Exclusive {
+        monitor: monitor.into(),
+        video_mode,
+      },
+    }
+  }
 }
 
 /// A builder for a webview window managed by Tauri.
@@ -122,6 +233,39 @@ pub struct WindowBuilder<'a, R: Runtime> {
   pub(crate) webview_attributes: WebviewAttributes,
   web_resource_request_handler: Option<Box<WebResourceRequestHandler>>,
   navigation_handler: Option<Box<NavigationHandler>>,
+  #[cfg(desktop)]
+  parent: Option<Window<R>>,
+  #[cfg(desktop)]
+  modal: bool,
+  #[cfg(desktop)]
+  child: bool,
+  #[cfg(all(
+    feature = "wayland-layer-shell",
+    any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    )
+  ))]
+  layer_shell: Option<LayerShell>,
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  type_hint: Option<WindowTypeHint>,
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  skip_pager: bool,
 }
 
 impl<'a, R: Runtime> fmt::Debug for WindowBuilder<'a, R> {
@@ -196,6 +340,39 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
       webview_attributes: WebviewAttributes::new(url),
       web_resource_request_handler: None,
       navigation_handler: None,
+      #[cfg(desktop)]
+      parent: None,
+      #[cfg(desktop)]
+      modal: false,
+      #[cfg(desktop)]
+      child: false,
+      #[cfg(all(
+        feature = "wayland-layer-shell",
+        any(
+          target_os = "linux",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "netbsd",
+          target_os = "openbsd"
+        )
+      ))]
+      layer_shell: None,
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      type_hint: None,
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      skip_pager: false,
     }
   }
 
@@ -234,6 +411,39 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
       ),
       web_resource_request_handler: None,
       navigation_handler: None,
+      #[cfg(desktop)]
+      parent: None,
+      #[cfg(desktop)]
+      modal: false,
+      #[cfg(desktop)]
+      child: false,
+      #[cfg(all(
+        feature = "wayland-layer-shell",
+        any(
+          target_os = "linux",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "netbsd",
+          target_os = "openbsd"
+        )
+      ))]
+      layer_shell: None,
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      type_hint: None,
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      skip_pager: false,
     };
 
     builder
@@ -313,6 +523,15 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 
   /// Creates a new webview window.
   pub fn build(mut self) -> crate::Result<Window<R>> {
+    #[cfg(windows)]
+    if let Some(hwnd) = self.parent.as_ref().and_then(|parent| parent.hwnd().ok()) {
+      self.window_builder = if self.child {
+        self.window_builder.parent_window(hwnd)
+      } else {
+        self.window_builder.owner_window(hwnd)
+      };
+    }
+
     let mut pending = PendingWindow::new(
       self.window_builder.clone(),
       self.webview_attributes.clone(),
@@ -325,7 +544,12 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     let pending = self
       .manager
       .prepare_window(self.app_handle.clone(), pending, &labels)?;
+    #[cfg(desktop)]
     let window_effects = pending.webview_attributes.window_effects.clone();
+    #[cfg(desktop)]
+    let title_bar_overlay = pending.webview_attributes.title_bar_overlay.clone();
+    #[cfg(desktop)]
+    let kiosk = pending.webview_attributes.kiosk;
     let window = match &mut self.runtime {
       RuntimeOrDispatch::Runtime(runtime) => runtime.create_window(pending),
       RuntimeOrDispatch::RuntimeHandle(handle) => handle.create_window(pending),
@@ -333,8 +557,50 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     }
     .map(|window| self.manager.attach_window(self.app_handle.clone(), window))?;
 
+    // the effects are applied on the main thread since the macOS views cannot be created on other threads
+    #[cfg(desktop)]
     if let Some(effects) = window_effects {
-      crate::vibrancy::set_window_effects(&window, Some(effects))?;
+      window.set_effects(effects)?;
+    }
+    #[cfg(desktop)]
+    if let Some(overlay) = title_bar_overlay {
+      title_bar::attach(&window, overlay)?;
+    }
+    #[cfg(desktop)]
+    if kiosk {
+      kiosk::attach(&window)?;
+    }
+    #[cfg(desktop)]
+    if let Some(parent) = self.parent.take() {
+      modal::attach(&parent, &window, self.modal, self.child)?;
+    }
+    #[cfg(all(
+      feature = "wayland-layer-shell",
+      any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      )
+    ))]
+    if let Some(config) = self.layer_shell.take() {
+      layer_shell::init(&window, config)?;
+    }
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    {
+      if let Some(hint) = self.type_hint {
+        window.set_type_hint(hint)?;
+      }
+      if self.skip_pager {
+        window.set_skip_pager(true)?;
+      }
     }
     self.manager.eval_script_all(format!(
       "window.__TAURI_METADATA__.__windows = {window_labels_array}.map(function (label) {{ return {{ label: label }} }})",
@@ -458,6 +724,45 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     self
   }
 
+  /// Shows the window in kiosk mode, e.g. for a point of sale or a digital signage.
+  ///
+  /// A kiosk window is an undecorated, always on top and fullscreen window that cannot be closed by the user,
+  /// with the devtools, the context menu and the navigation away from the origin of its URL disabled.
+  /// While a kiosk window is open the system shortcuts switching or closing the apps are blocked,
+  /// and the app is relaunched if it panics. The window can still be closed with [`Window::close`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** `Ctrl+Alt+Del` cannot be blocked.
+  /// - **Linux:** The system shortcuts are not blocked, configure the compositor of the kiosk instead.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     tauri::WindowBuilder::new(app, "signage", tauri::WindowUrl::App("index.html".into()))
+  ///       .kiosk(true)
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn kiosk(mut self, kiosk: bool) -> Self {
+    if kiosk {
+      self.window_builder = self
+        .window_builder
+        .fullscreen(true)
+        .always_on_top(true)
+        .decorations(false)
+        .resizable(false)
+        .minimizable(false)
+        .closable(false);
+    }
+    self.webview_attributes = self.webview_attributes.kiosk(kiosk);
+    self
+  }
+
   /// Sets the window to be initially focused.
   #[must_use]
   #[deprecated(
@@ -552,6 +857,32 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     self
   }
 
+  /// Whether the window ignores the cursor events, letting the clicks pass through to the windows below it.
+  ///
+  /// Combined with a [transparent](Self::transparent), undecorated and always on top window, this creates
+  /// an overlay, e.g. a streaming widget or a screen annotation layer. Use [`Window::set_ignore_cursor_events`]
+  /// to let the overlay receive the clicks again, e.g. while a toolbar is hovered.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     tauri::WindowBuilder::new(app, "overlay", tauri::WindowUrl::App("overlay.html".into()))
+  ///       .transparent(true)
+  ///       .decorations(false)
+  ///       .always_on_top(true)
+  ///       .ignore_cursor_events(true)
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn ignore_cursor_events(mut self, ignore: bool) -> Self {
+    self.window_builder = self.window_builder.ignore_cursor_events(ignore);
+    self
+  }
+
   /// Sets whether or not the window has shadow.
   ///
   /// ## Platform-specific
@@ -587,6 +918,115 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     self
   }
 
+  /// Sets the parent of the window to be created, which stays above its parent
+  /// and is closed with it.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** The window is owned by its parent, see [`Self::owner_window`], or embedded in it when it is a [child](Self::child).
+  /// - **macOS:** The window is a child window of its parent, moving with it, or a sheet when it is [modal](Self::modal).
+  /// - **Linux:** The window is transient for its parent.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #[tauri::command]
+  /// async fn open_settings(window: tauri::Window) -> Result<(), String> {
+  ///   tauri::WindowBuilder::new(&window, "settings", tauri::WindowUrl::App("settings.html".into()))
+  ///     .parent(&window)
+  ///     .modal(true)
+  ///     .build()
+  ///     .map_err(|e| e.to_string())?;
+  ///   Ok(())
+  /// }
+  /// ```
+  #[must_use]
+  pub fn parent(mut self, parent: &Window<R>) -> Self {
+    self.parent.replace(parent.clone());
+    self
+  }
+
+  /// Whether the window blocks the interaction with its [parent](Self::parent) until it is closed,
+  /// e.g. for a settings or confirmation window. Has no effect without a parent.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** The parent is disabled until the window is destroyed.
+  /// - **macOS:** The window is shown as a sheet attached to the title bar of its parent.
+  /// - **Linux:** The window is a GTK modal window, blocking the other windows of the app.
+  #[must_use]
+  pub fn modal(mut self, modal: bool) -> Self {
+    self.modal = modal;
+    self
+  }
+
+  /// Whether the window is a child of its [parent](Self::parent), moving with it, e.g. for tool palettes
+  /// and popovers anchored to an element of the parent with [`Window::position_relative_to_parent`].
+  /// A child window is never [modal](Self::modal). Has no effect without a parent.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** The window has the `WS_CHILD` style and is clipped to the client area of its parent, see [`Self::parent_window`].
+  /// - **macOS:** The window is a child window of its parent, which is the behavior without this flag.
+  /// - **Linux:** On X11 the window is reparented, so it is clipped to its parent. Otherwise it follows the moves of its parent.
+  #[must_use]
+  pub fn child(mut self, child: bool) -> Self {
+    self.child = child;
+    self
+  }
+
+  /// Shows the window as a layer shell surface of the Wayland compositor, anchored to the edges of the screen,
+  /// e.g. for a panel, a dock or a launcher. See the [`LayerShell`] configuration.
+  ///
+  /// [`Self::build`] returns [`crate::Error::LayerShellUnsupported`] when the compositor does not
+  /// implement the `wlr-layer-shell` protocol, e.g. on GNOME or X11.
+  #[cfg(all(
+    feature = "wayland-layer-shell",
+    any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    )
+  ))]
+  #[must_use]
+  pub fn layer_shell(mut self, config: LayerShell) -> Self {
+    self.layer_shell.replace(config);
+    self
+  }
+
+  /// Sets the type of the window, telling the window manager how to place and decorate it,
+  /// e.g. the tiling window managers float the dialogs and the utility windows.
+  ///
+  /// See [`Window::set_type_hint`].
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  #[must_use]
+  pub fn type_hint(mut self, hint: WindowTypeHint) -> Self {
+    self.type_hint.replace(hint);
+    self
+  }
+
+  /// Whether to hide the window from the pagers, e.g. the workspace switchers.
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  #[must_use]
+  pub fn skip_pager(mut self, skip: bool) -> Self {
+    self.skip_pager = skip;
+    self
+  }
+
   /// Set an owner to the window to be created.
   ///
   /// From MSDN:
@@ -610,6 +1050,36 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     self
   }
 
+  /// Sets the layout of the window controls drawn over the webview with the [`TitleBarStyle::Overlay`] title bar.
+  ///
+  /// The webview can lay out its own title bar around the controls with the `--tauri-title-bar-overlay-x`, `-y`,
+  /// `-width` and `-height` CSS variables of the document, which are updated when the controls move,
+  /// or with [`Window::title_bar_overlay_rect`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::{utils::config::{TitleBarInset, TitleBarOverlayConfig}, TitleBarStyle};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     tauri::WindowBuilder::new(app, "main", tauri::WindowUrl::App("index.html".into()))
+  ///       .title_bar_style(TitleBarStyle::Overlay)
+  ///       .title_bar_overlay(TitleBarOverlayConfig {
+  ///         height: Some(44.),
+  ///         inset: Some(TitleBarInset { x: 16., y: 14. }),
+  ///       })
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(target_os = "macos")]
+  #[must_use]
+  pub fn title_bar_overlay(mut self, overlay: crate::utils::config::TitleBarOverlayConfig) -> Self {
+    self.webview_attributes = self.webview_attributes.title_bar_overlay(overlay);
+    self
+  }
+
   /// Hide the window title.
   #[cfg(target_os = "macos")]
   #[must_use]
@@ -657,8 +1127,11 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
   /// Adds the provided JavaScript to a list of scripts that should be run after the global object has been created,
   /// but before the HTML document has been parsed and before any other script included by the HTML document is run.
   ///
-  /// Since it runs on all top-level document and child frame page navigations,
-  /// it's recommended to check the `window.location` to guard your script from running on unexpected origins.
+  /// The window scripts run after the Tauri scripts and the app scripts added with [`crate::Builder::initialization_script`],
+  /// in insertion order.
+  ///
+  /// Unless restricted with [`crate::Script::main_frame_only`] or [`crate::Script::once`], it runs on all top-level document and child frame page navigations,
+  /// so it's recommended to check the `window.location` to guard your script from running on unexpected origins.
   ///
   /// # Examples
   ///
@@ -684,11 +1157,23 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
   /// }
   /// ```
   #[must_use]
-  pub fn initialization_script(mut self, script: &str) -> Self {
+  pub fn initialization_script(mut self, script: impl Into<crate::Script>) -> Self {
     self
       .webview_attributes
       .initialization_scripts
-      .push(script.to_string());
+      .push(script.into().render());
+    self
+  }
+
+  /// Adds the scripts with [`Self::initialization_script`], in order.
+  #[must_use]
+  pub fn initialization_scripts<I: IntoIterator<Item = crate::Script>>(
+    mut self,
+    scripts: I,
+  ) -> Self {
+    for script in scripts {
+      self = self.initialization_script(script);
+    }
     self
   }
 
@@ -752,6 +1237,39 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
     self.webview_attributes.incognito = incognito;
     self
   }
+
+  /// Runs the webview in its own processes, so it does not share its memory nor its crashes with the other windows,
+  /// e.g. for the documents of a multi-document app. Also enabled for all the windows by the `tauri > processIsolation` configuration.
+  ///
+  /// The webview gets its own data directory under the default one, named after the window label,
+  /// so its storage, e.g. the cookies and the local storage, is not shared with the other windows either.
+  /// A data directory set with [`Self::data_directory`] is used as is.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** The webview has its own WebView2 browser process and its child processes.
+  /// - **Linux:** The webview has its own WebKit web context, with its own network and web processes.
+  /// - **macOS / iOS:** WebKit already runs the content of each webview in its own process, the storage is not separated.
+  /// - **Android:** Unsupported.
+  #[must_use]
+  pub fn process_isolation(mut self, isolation: bool) -> Self {
+    self.webview_attributes.process_isolation = isolation;
+    self
+  }
+
+  /// Forwards the keyboard and IME composition events of the webview to the listeners registered
+  /// with [`Window::on_keyboard_event`].
+  ///
+  /// The events are the ones of the focused webview, after the dead keys and the input method are applied,
+  /// so an editor or a game can handle the composed text along with its shortcuts.
+  #[must_use]
+  pub fn keyboard_events(self, enable: bool) -> Self {
+    if enable {
+      self.initialization_script(keyboard::KEYBOARD_SCRIPT)
+    } else {
+      self
+    }
+  }
 }
 
 /// Key for a JS event listener.
@@ -777,6 +1295,9 @@ pub struct Window<R: Runtime> {
   manager: WindowManager<R>,
   pub(crate) app_handle: AppHandle<R>,
   js_event_listeners: Arc<Mutex<HashMap<JsEventListenerKey, HashSet<usize>>>>,
+  /// The parent of the window, set by [`WindowBuilder::parent`].
+  #[cfg(desktop)]
+  pub(crate) parent: Arc<Mutex<Option<modal::ParentWindow>>>,
 
   #[cfg(test)]
   pub(crate) current_url: url::Url,
@@ -795,6 +1316,8 @@ impl<R: Runtime> Clone for Window<R> {
       manager: self.manager.clone(),
       app_handle: self.app_handle.clone(),
       js_event_listeners: self.js_event_listeners.clone(),
+      #[cfg(desktop)]
+      parent: self.parent.clone(),
       #[cfg(test)]
       current_url: self.current_url.clone(),
     }
@@ -949,6 +1472,8 @@ impl<R: Runtime> Window<R> {
       manager,
       app_handle,
       js_event_listeners: Default::default(),
+      #[cfg(desktop)]
+      parent: Default::default(),
       #[cfg(test)]
       current_url: "http://tauri.app".parse().unwrap(),
     }
@@ -988,6 +1513,21 @@ impl<R: Runtime> Window<R> {
     &self.window.label
   }
 
+  /// The scopes of the plugin granted to this window, one for each capability matching its label.
+  ///
+  /// See [`Capability::scopes`](crate::utils::config::Capability#structfield.scopes).
+  pub fn capability_scopes(&self, plugin: &str) -> Vec<serde_json::Value> {
+    self
+      .manager
+      .config()
+      .tauri
+      .security
+      .window_scopes(&self.window.label, plugin)
+      .into_iter()
+      .cloned()
+      .collect()
+  }
+
   /// Registers a window event listener.
   pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) {
     self
@@ -996,6 +1536,33 @@ impl<R: Runtime> Window<R> {
       .on_window_event(move |event| f(&event.clone().into()));
   }
 
+  /// Registers a listener of the keyboard and IME composition events of the webview.
+  ///
+  /// The events are only sent by windows created with [`WindowBuilder::keyboard_events`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::window::KeyboardEvent;
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let window = tauri::WindowBuilder::new(app, "editor", tauri::WindowUrl::App("index.html".into()))
+  ///     .keyboard_events(true)
+  ///     .build()?;
+  ///   window.on_keyboard_event(|event| match event {
+  ///     KeyboardEvent::KeyDown(key) if !key.is_composing => println!("key {}", key.key),
+  ///     KeyboardEvent::CompositionEnd { data } => println!("text {data}"),
+  ///     _ => {}
+  ///   });
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn on_keyboard_event<F: Fn(&KeyboardEvent) + Send + Sync + 'static>(&self, f: F) {
+    if let Some(listeners) = self.try_state::<keyboard::KeyboardEventListeners>() {
+      listeners.listen(self.label(), f);
+    }
+  }
+
   /// Registers a menu event listener.
   pub fn on_menu_event<F: Fn(MenuEvent) + Send + 'static>(&self, f: F) -> uuid::Uuid {
     let menu_ids = self.window.menu_ids.clone();
@@ -1061,8 +1628,8 @@ impl<R: Runtime> Window<R> {
   ///   });
   /// }
   /// ```
-  #[cfg(all(feature = "wry"))]
-  #[cfg_attr(doc_cfg, doc(all(feature = "wry")))]
+  #[cfg(feature = "wry")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "wry")))]
   pub fn with_webview<F: FnOnce(PlatformWebview) + Send + 'static>(
     &self,
     f: F,
@@ -1073,6 +1640,98 @@ impl<R: Runtime> Window<R> {
       .with_webview(|w| f(PlatformWebview(*w.downcast().unwrap())))
       .map_err(Into::into)
   }
+
+  /// Measures the memory and CPU usage of the processes running the webview of this window.
+  ///
+  /// The processes are shared by the webviews using the same data directory, unless the window
+  /// is created with [`WindowBuilder::process_isolation`] or the `tauri > processIsolation` configuration.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** WebKitGTK does not expose the processes of a webview, so the WebKit processes of all the windows are returned.
+  /// - **macOS:** Requires the `macos-private-api` feature flag, returns an empty list otherwise.
+  /// - **iOS / Android:** Unsupported, returns an empty list.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #[tauri::command]
+  /// async fn webview_memory(window: tauri::Window) -> Result<u64, String> {
+  ///   let processes = window.webview_processes().await.map_err(|e| e.to_string())?;
+  ///   Ok(processes.iter().map(|process| process.memory).sum())
+  /// }
+  /// ```
+  #[cfg(feature = "wry")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "wry")))]
+  pub async fn webview_processes(&self) -> crate::Result<Vec<crate::process::ProcessMetrics>> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    self.with_webview(move |webview| {
+      let _ = tx.send(crate::process::metrics::webview_processes(&webview));
+    })?;
+    let processes = rx.await.unwrap_or_default();
+    Ok(
+      processes
+        .into_iter()
+        .filter_map(|(pid, kind)| crate::process::metrics::measure(pid, kind))
+        .collect(),
+    )
+  }
+
+  /// Releases the memory cached by the webview of this window, e.g. when the system is low on memory,
+  /// see [`crate::RunEvent::MemoryPressure`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Clears the HTTP cache of the webview and runs the JavaScript garbage collector.
+  /// - **Linux:** Clears the HTTP cache of the web context of the webview.
+  /// - **macOS / iOS:** Unsupported, WebKit releases its caches by itself on memory pressure.
+  /// - **Android:** Unsupported.
+  #[cfg(feature = "wry")]
+  #[cfg_attr(doc_cfg, doc(cfg(feature = "wry")))]
+  pub fn trim_memory(&self) -> crate::Result<()> {
+    self.with_webview(|webview| {
+      #[cfg(windows)]
+      unsafe {
+        use webview2_com::CallDevToolsProtocolMethodCompletedHandler;
+        use windows::w;
+
+        if let Ok(core) = webview.controller().CoreWebView2() {
+          for method in [
+            w!("Network.clearBrowserCache"),
+            w!("HeapProfiler.collectGarbage"),
+          ] {
+            let handler =
+              CallDevToolsProtocolMethodCompletedHandler::create(Box::new(|_, _| Ok(())));
+            let _ = core.CallDevToolsProtocolMethod(method, w!("{}"), &handler);
+          }
+        }
+      }
+
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      {
+        use webkit2gtk::{WebContextExt, WebViewExt};
+        if let Some(context) = webview.inner().context() {
+          context.clear_cache();
+        }
+      }
+
+      #[cfg(not(any(
+        windows,
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      )))]
+      let _ = webview;
+    })
+  }
 }
 
 /// Window getters.
@@ -1263,6 +1922,17 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.gtk_window().map_err(Into::into)
   }
 
+  /// Returns the bounding rectangle of the window controls drawn over the webview in logical pixels,
+  /// or `None` if the window does not use the overlay title bar.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Always returns `None`.
+  #[cfg(desktop)]
+  pub fn title_bar_overlay_rect(&self) -> crate::Result<Option<TitleBarOverlayRect>> {
+    title_bar::overlay_rect(self)
+  }
+
   /// Returns the current window theme.
   ///
   /// ## Platform-specific
@@ -1273,6 +1943,67 @@ impl<R: Runtime> Window<R> {
   }
 }
 
+/// Mobile system bars.
+impl<R: Runtime> Window<R> {
+  /// Sets the background color of the status bar.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS:** Draws the color behind the transparent status bar.
+  /// - **Linux / macOS / Windows:** Unsupported.
+  pub fn set_status_bar_color(&self, color: Color) -> crate::Result<()> {
+    system_bars::set_status_bar(self, system_bars::SystemBar::color(color))
+  }
+
+  /// Sets the style of the status bar icons.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS / Windows:** Unsupported.
+  pub fn set_status_bar_style(&self, style: SystemBarStyle) -> crate::Result<()> {
+    system_bars::set_status_bar(self, system_bars::SystemBar::style(style))
+  }
+
+  /// Sets the background color of the navigation bar.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Linux / macOS / Windows:** Unsupported.
+  pub fn set_navigation_bar_color(&self, color: Color) -> crate::Result<()> {
+    system_bars::set_navigation_bar(self, system_bars::SystemBar::color(color))
+  }
+
+  /// Sets the style of the navigation bar icons.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android:** Requires Android 8.1.
+  /// - **iOS / Linux / macOS / Windows:** Unsupported.
+  pub fn set_navigation_bar_style(&self, style: SystemBarStyle) -> crate::Result<()> {
+    system_bars::set_navigation_bar(self, system_bars::SystemBar::style(style))
+  }
+
+  /// Lays out the webview behind the system bars, using the whole screen.
+  /// The content must then be padded with the [`Self::safe_area_insets`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS:** The webview always uses the whole screen, this disables the automatic adjustment of its scroll view insets.
+  /// - **Linux / macOS / Windows:** Unsupported.
+  pub fn set_edge_to_edge(&self, enable: bool) -> crate::Result<()> {
+    system_bars::set_edge_to_edge(self, enable)
+  }
+
+  /// Returns the insets of the webview area not covered by the system bars, the display cutout and the rounded corners.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux / macOS / Windows:** Always returns zero insets.
+  pub fn safe_area_insets(&self) -> crate::Result<SafeAreaInsets> {
+    system_bars::safe_area_insets(self)
+  }
+}
+
 /// Desktop window setters and actions.
 #[cfg(desktop)]
 impl<R: Runtime> Window<R> {
@@ -1467,7 +2198,9 @@ impl<R: Runtime> Window<R> {
   ///
   /// ## Platform-specific:
   ///
-  /// - **Windows**: If using decorations or shadows, you may want to try this workaround https://github.com/tauri-apps/tao/issues/72#issuecomment-975607891
+  /// - **Windows**: Mica and Tabbed require Windows 11, Acrylic and Blur Windows 10 or Windows 7.
+  ///   If using decorations or shadows, you may want to try this workaround https://github.com/tauri-apps/tao/issues/72#issuecomment-975607891
+  /// - **macOS**: `state` controls whether the material follows the active state of the window, and `radius` rounds its corners.
   /// - **Linux**: Unsupported
   pub fn set_effects<E: Into<Option<WindowEffectsConfig>>>(&self, effects: E) -> crate::Result<()> {
     let effects = effects.into();
@@ -1531,6 +2264,29 @@ impl<R: Runtime> Window<R> {
       .map_err(Into::into)
   }
 
+  /// Moves this window to the given position relative to the top-left corner of the client area of its
+  /// [parent](WindowBuilder::parent), e.g. to anchor a popover to an element of the parent webview
+  /// with the logical position of its `getBoundingClientRect()`.
+  ///
+  /// Returns [`crate::Error::WindowWithoutParent`] if the window was not created with a parent or its parent was closed.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #[tauri::command]
+  /// fn anchor_popover(window: tauri::Window, x: f64, y: f64) -> Result<(), String> {
+  ///   window
+  ///     .position_relative_to_parent(tauri::LogicalPosition::new(x, y))
+  ///     .map_err(|e| e.to_string())
+  /// }
+  /// ```
+  pub fn position_relative_to_parent<Pos: Into<Position>>(
+    &self,
+    position: Pos,
+  ) -> crate::Result<()> {
+    modal::position_relative_to_parent(self, position.into())
+  }
+
   /// Determines if this window should be fullscreen.
   pub fn set_fullscreen(&self, fullscreen: bool) -> crate::Result<()> {
     self
@@ -1540,6 +2296,36 @@ impl<R: Runtime> Window<R> {
       .map_err(Into::into)
   }
 
+  /// Shows the window in fullscreen with the given mode, or leaves the fullscreen with `None`.
+  ///
+  /// The monitors are listed by [`Window::available_monitors`], and an unknown monitor or an unsupported video mode
+  /// leaves the window unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{window::FullscreenMode, Manager};
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_window("main").unwrap();
+  ///     // the highest resolution of the primary monitor
+  ///     if let Some(monitor) = window.primary_monitor()? {
+  ///       if let Some(video_mode) = monitor.video_modes().iter().max_by_key(|mode| mode.size.width * mode.size.height) {
+  ///         let video_mode = *video_mode;
+  ///         window.set_fullscreen_mode(Some(FullscreenMode::Exclusive { monitor, video_mode }))?;
+  ///       }
+  ///     }
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn set_fullscreen_mode(&self, mode: Option<FullscreenMode>) -> crate::Result<()> {
+    self
+      .window
+      .dispatcher
+      .set_fullscreen_mode(mode.map(Into::into))
+      .map_err(Into::into)
+  }
+
   /// Bring the window to front and focus.
   pub fn set_focus(&self) -> crate::Result<()> {
     self.window.dispatcher.set_focus().map_err(Into::into)
@@ -1567,6 +2353,46 @@ impl<R: Runtime> Window<R> {
       .map_err(Into::into)
   }
 
+  /// Sets the type of the window, telling the window manager how to place and decorate it,
+  /// which is the `_NET_WM_WINDOW_TYPE` property on X11.
+  ///
+  /// The window managers only read the type when the window is shown, so a visible window is hidden and shown again.
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  pub fn set_type_hint(&self, hint: WindowTypeHint) -> crate::Result<()> {
+    hints::set_type_hint(self, hint)
+  }
+
+  /// Whether to hide the window from the pagers, e.g. the workspace switchers.
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  pub fn set_skip_pager(&self, skip: bool) -> crate::Result<()> {
+    hints::set_skip_pager(self, skip)
+  }
+
+  /// Sets the urgency hint of the window, which stays set until it is unset, unlike [`Self::request_user_attention`]
+  /// which the window manager may unset when the window is focused.
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  pub fn set_urgency_hint(&self, urgent: bool) -> crate::Result<()> {
+    hints::set_urgency_hint(self, urgent)
+  }
+
   /// Grabs the cursor, preventing it from leaving the window.
   ///
   /// There's no guarantee that the cursor will be hidden. You should
@@ -1619,7 +2445,9 @@ impl<R: Runtime> Window<R> {
       .map_err(Into::into)
   }
 
-  /// Ignores the window cursor events.
+  /// Ignores the window cursor events, letting the clicks pass through to the windows below it.
+  ///
+  /// See [`WindowBuilder::ignore_cursor_events`].
   pub fn set_ignore_cursor_events(&self, ignore: bool) -> crate::Result<()> {
     self
       .window
@@ -1685,6 +2513,7 @@ impl<R: Runtime> Window<R> {
     };
     match payload.cmd.as_str() {
       "__initialized" => {
+        crate::startup::record(&manager, crate::startup::StartupPhase::DomReady);
         let payload: PageLoadPayload = serde_json::from_value(payload.inner)?;
         manager.run_on_page_load(self, payload);
       }
@@ -1704,6 +2533,40 @@ impl<R: Runtime> Window<R> {
           return Ok(());
         }
 
+        if !is_core_plugin_command(&payload.cmd)
+          && !manager
+            .config()
+            .tauri
+            .security
+            .is_command_allowed(&self.window.label, &payload.cmd)
+        {
+          invoke.resolver.reject(ipc_capability_error_message(
+            &self.window.label,
+            &payload.cmd,
+          ));
+          return Ok(());
+        }
+
+        if let Some(origins) = manager
+          .config()
+          .tauri
+          .security
+          .command_origins
+          .get(&payload.cmd)
+        {
+          if !crate::hooks::is_origin_allowed(origins, &self.window.label, &current_url) {
+            invoke
+              .resolver
+              .reject(ipc_origin_error_message(&self.window.label, &payload.cmd));
+            return Ok(());
+          }
+        }
+
+        if let Err(rejection) = manager.check_rate_limit(&self.window.label, &payload.cmd) {
+          invoke.resolver.reject(rejection);
+          return Ok(());
+        }
+
         if payload.cmd.starts_with("plugin:") {
           if !is_local {
             let command = invoke.message.command.replace("plugin:", "");
@@ -1724,6 +2587,18 @@ impl<R: Runtime> Window<R> {
             .next()
             .map(|c| c.to_string())
             .unwrap_or_else(String::new);
+          invoke.message.plugin = Some(plugin.to_string());
+
+          if let Err(permission) = self.plugin_permissions().check_command(
+            &self.window.label,
+            plugin,
+            &invoke.message.command,
+          ) {
+            invoke
+              .resolver
+              .reject(ipc_permission_error_message(plugin, &permission));
+            return Ok(());
+          }
 
           let command = invoke.message.command.clone();
           let resolver = invoke.resolver.clone();
@@ -1845,6 +2720,25 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.eval_script(js).map_err(Into::into)
   }
 
+  /// Makes the screen reader read the text, without showing it.
+  ///
+  /// The text is written to an ARIA live region of the page, a hidden element created on the first announcement.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::{window::AnnouncementPriority, Manager};
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let window = app.get_window("main").unwrap();
+  ///   window.announce("Download completed", AnnouncementPriority::Polite)?;
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn announce(&self, text: &str, priority: AnnouncementPriority) -> crate::Result<()> {
+    self.eval(&crate::accessibility::announce_script(text, priority)?)
+  }
+
   /// Register a JS event listener and return its identifier.
   pub(crate) fn listen_js(
     &self,
@@ -2234,6 +3128,34 @@ pub(crate) fn ipc_scope_window_error_message(label: &str) -> String {
   format!("Scope not defined for window `{}`. See https://tauri.app/v1/api/config/#securityconfig.dangerousremotedomainipcaccess and https://docs.rs/tauri/1/tauri/scope/struct.IpcScope.html#method.configure_remote_access", label)
 }
 
+/// Plugins registered by Tauri itself, which are always allowed by the window capabilities.
+///
+/// Includes the plugins invoked by the scripts Tauri injects in the windows,
+/// e.g. the channel acknowledgements or the keyboard events.
+const CORE_PLUGINS: &[&str] = &["event", "path", "config", "ipc", "keyboard", "title-bar"];
+
+fn is_core_plugin_command(command: &str) -> bool {
+  command
+    .strip_prefix("plugin:")
+    .and_then(|c| c.split('|').next())
+    .map_or(false, |plugin| {
+      // the app state is only saved for the restarts of `tauri dev`
+      CORE_PLUGINS.contains(&plugin) || (cfg!(dev) && plugin == "hot-restart")
+    })
+}
+
+pub(crate) fn ipc_capability_error_message(label: &str, command: &str) -> String {
+  format!("Command `{command}` not allowed by the capabilities of window `{label}`. See https://tauri.app/v1/api/config/#securityconfig.capabilities")
+}
+
+pub(crate) fn ipc_origin_error_message(label: &str, command: &str) -> String {
+  format!("Command `{command}` not allowed from window `{label}` or its origin. See https://tauri.app/v1/api/config/#securityconfig.commandorigins")
+}
+
+pub(crate) fn ipc_permission_error_message(plugin: &str, permission: &str) -> String {
+  format!("The optional `{permission}` permission of the `{plugin}` plugin was not granted. See https://docs.rs/tauri/2/tauri/permissions/index.html")
+}
+
 pub(crate) fn ipc_scope_domain_error_message(url: &str) -> String {
   format!("Scope not defined for URL `{url}`. See https://tauri.app/v1/api/config/#securityconfig.dangerousremotedomainipcaccess and https://docs.rs/tauri/1/tauri/scope/struct.IpcScope.html#method.configure_remote_access")
 }
@@ -2245,4 +3167,20 @@ mod tests {
     crate::test_utils::assert_send::<super::Window>();
     crate::test_utils::assert_sync::<super::Window>();
   }
+
+  #[test]
+  fn core_plugin_commands() {
+    use super::is_core_plugin_command;
+
+    assert!(is_core_plugin_command("plugin:event|listen"));
+    assert!(is_core_plugin_command("plugin:path|resolve_directory"));
+    assert!(is_core_plugin_command("plugin:config|get_config"));
+    assert!(is_core_plugin_command("plugin:ipc|ack"));
+    assert!(is_core_plugin_command("plugin:keyboard|emit"));
+    assert!(is_core_plugin_command("plugin:title-bar|overlay_rect"));
+    assert_eq!(is_core_plugin_command("plugin:hot-restart|save"), cfg!(dev));
+    assert!(!is_core_plugin_command("plugin:fs|read_file"));
+    assert!(!is_core_plugin_command("event"));
+    assert!(!is_core_plugin_command("plugin:eventually|listen"));
+  }
 }
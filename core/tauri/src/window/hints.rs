@@ -0,0 +1,95 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The hints telling the X11 window managers and the Linux desktop environments how to handle a window.
+
+use crate::{Runtime, Window};
+
+use gtk::{gdk, prelude::GtkWindowExt, prelude::WidgetExt};
+
+/// The type of a window, telling the window manager how to place and decorate it.
+///
+/// On X11 it sets the `_NET_WM_WINDOW_TYPE` property of the window, e.g. the tiling window managers
+/// float the dialogs and the splashscreens, and the panels reserve space for the docks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum WindowTypeHint {
+  /// A normal toplevel window.
+  #[default]
+  Normal,
+  /// A dialog window.
+  Dialog,
+  /// A torn-off menu.
+  Menu,
+  /// A torn-off toolbar.
+  Toolbar,
+  /// A splashscreen shown while the app starts.
+  Splashscreen,
+  /// A utility window, e.g. a tool palette, which is kept above its app.
+  Utility,
+  /// A dock or panel, which is kept on top of the other windows.
+  Dock,
+  /// The desktop background.
+  Desktop,
+  /// A notification.
+  Notification,
+}
+
+impl From<WindowTypeHint> for gdk::WindowTypeHint {
+  fn from(hint: WindowTypeHint) -> Self {
+    match hint {
+      WindowTypeHint::Normal => Self::Normal,
+      WindowTypeHint::Dialog => Self::Dialog,
+      WindowTypeHint::Menu => Self::Menu,
+      WindowTypeHint::Toolbar => Self::Toolbar,
+      WindowTypeHint::Splashscreen => Self::Splashscreen,
+      WindowTypeHint::Utility => Self::Utility,
+      WindowTypeHint::Dock => Self::Dock,
+      WindowTypeHint::Desktop => Self::Desktop,
+      WindowTypeHint::Notification => Self::Notification,
+    }
+  }
+}
+
+/// Sets the type of the window.
+///
+/// The window managers only read the type when the window is mapped,
+/// so a visible window is hidden and shown again.
+pub(crate) fn set_type_hint<R: Runtime>(
+  window: &Window<R>,
+  hint: WindowTypeHint,
+) -> crate::Result<()> {
+  with_gtk_window(window, move |window| {
+    let visible = window.is_visible();
+    if visible {
+      window.hide();
+    }
+    window.set_type_hint(hint.into());
+    if visible {
+      window.show();
+    }
+  })
+}
+
+/// Sets whether the window is hidden from the pagers, e.g. the workspace switchers.
+pub(crate) fn set_skip_pager<R: Runtime>(window: &Window<R>, skip: bool) -> crate::Result<()> {
+  with_gtk_window(window, move |window| window.set_skip_pager_hint(skip))
+}
+
+/// Sets whether the window needs the attention of the user, e.g. its taskbar entry blinks until it is unset.
+pub(crate) fn set_urgency_hint<R: Runtime>(window: &Window<R>, urgent: bool) -> crate::Result<()> {
+  with_gtk_window(window, move |window| window.set_urgency_hint(urgent))
+}
+
+fn with_gtk_window<R: Runtime, F: FnOnce(&gtk::ApplicationWindow) + Send + 'static>(
+  window: &Window<R>,
+  f: F,
+) -> crate::Result<()> {
+  let handle = window.clone();
+  window.run_on_main_thread(move || {
+    if let Ok(window) = handle.gtk_window() {
+      f(&window);
+    }
+  })
+}
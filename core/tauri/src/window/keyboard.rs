@@ -0,0 +1,206 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The keyboard and IME composition events of the webview, forwarded to the windows created with
+//! [`crate::WindowBuilder::keyboard_events`].
+
+use crate::{
+  command,
+  plugin::{Builder, TauriPlugin},
+  Manager, Runtime, State, Window,
+};
+
+use serde::Deserialize;
+
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+};
+
+/// The script forwarding the events of the webview.
+pub(crate) const KEYBOARD_SCRIPT: &str = include_str!("../../scripts/keyboard.js");
+
+/// The location of a key on the keyboard, telling apart the left and right modifiers and the numpad keys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "u8")]
+#[non_exhaustive]
+pub enum KeyLocation {
+  /// A key with a single location, e.g. `A`.
+  #[default]
+  Standard,
+  /// The left key of a pair, e.g. the left `Shift`.
+  Left,
+  /// The right key of a pair, e.g. the right `Shift`.
+  Right,
+  /// A key of the numpad.
+  Numpad,
+}
+
+impl From<u8> for KeyLocation {
+  fn from(location: u8) -> Self {
+    match location {
+      1 => Self::Left,
+      2 => Self::Right,
+      3 => Self::Numpad,
+      _ => Self::Standard,
+    }
+  }
+}
+
+/// A key pressed or released in the webview.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct KeyEvent {
+  /// The value of the key with the keyboard layout and the modifiers applied, e.g. `a`, `A` or `Enter`.
+  /// A dead key is `Dead`, the character it composes is the key of the next event.
+  pub key: String,
+  /// The physical key, independent of the keyboard layout, e.g. `KeyA`.
+  pub code: String,
+  /// The location of the key.
+  pub location: KeyLocation,
+  /// Whether the key is held down and the event is repeated.
+  pub repeat: bool,
+  /// Whether the key is part of an IME composition. Shortcuts should ignore these events.
+  pub is_composing: bool,
+  /// Whether `Shift` is held down.
+  pub shift_key: bool,
+  /// Whether `Control` is held down.
+  pub ctrl_key: bool,
+  /// Whether `Alt` (`Option` on macOS) is held down.
+  pub alt_key: bool,
+  /// Whether `Meta` (`Command` on macOS, `Windows` on Windows) is held down.
+  pub meta_key: bool,
+}
+
+/// A keyboard or IME composition event of the webview.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum KeyboardEvent {
+  /// A key was pressed.
+  KeyDown(KeyEvent),
+  /// A key was released.
+  KeyUp(KeyEvent),
+  /// An IME composition started, e.g. when typing CJK text.
+  CompositionStart {
+    /// The text replaced by the composition, usually empty.
+    data: String,
+  },
+  /// The text of the IME composition changed.
+  CompositionUpdate {
+    /// The text being composed.
+    data: String,
+  },
+  /// The IME composition was committed or cancelled.
+  CompositionEnd {
+    /// The committed text, empty when the composition was cancelled.
+    data: String,
+  },
+}
+
+type KeyboardEventListener = Arc<dyn Fn(&KeyboardEvent) + Send + Sync>;
+
+/// The keyboard event listeners of each window, removed when the window is destroyed.
+#[derive(Default, Clone)]
+pub(crate) struct KeyboardEventListeners(Arc<Mutex<HashMap<String, Vec<KeyboardEventListener>>>>);
+
+impl KeyboardEventListeners {
+  pub(crate) fn listen<F: Fn(&KeyboardEvent) + Send + Sync + 'static>(&self, label: &str, f: F) {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .entry(label.into())
+      .or_default()
+      .push(Arc::new(f));
+  }
+
+  fn emit(&self, label: &str, event: &KeyboardEvent) {
+    // the listeners are called without the lock so they can register other listeners
+    let listeners = self.0.lock().unwrap().get(label).cloned();
+    for listener in listeners.unwrap_or_default() {
+      listener(event);
+    }
+  }
+
+  pub(crate) fn remove_window(&self, label: &str) {
+    self.0.lock().unwrap().remove(label);
+  }
+}
+
+#[command(root = "crate")]
+fn emit<R: Runtime>(
+  window: Window<R>,
+  listeners: State<'_, KeyboardEventListeners>,
+  event: KeyboardEvent,
+) {
+  listeners.emit(window.label(), &event);
+}
+
+/// Initializes the keyboard plugin, receiving the events of the webview.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("keyboard")
+    .invoke_handler(crate::generate_handler![emit])
+    .setup(|app, _api| {
+      app.manage(KeyboardEventListeners::default());
+      Ok(())
+    })
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{KeyLocation, KeyboardEvent, KeyboardEventListeners};
+  use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  };
+
+  #[test]
+  fn deserializes_events() {
+    let event: KeyboardEvent = serde_json::from_str(
+      r#"{"type":"keyDown","key":"Dead","code":"Quote","location":0,"repeat":false,"isComposing":false,"shiftKey":false,"ctrlKey":false,"altKey":true,"metaKey":false}"#,
+    )
+    .unwrap();
+    match event {
+      KeyboardEvent::KeyDown(key) => {
+        assert_eq!(key.key, "Dead");
+        assert_eq!(key.location, KeyLocation::Standard);
+        assert!(key.alt_key);
+      }
+      e => panic!("unexpected event {e:?}"),
+    }
+
+    let event: KeyboardEvent =
+      serde_json::from_str(r#"{"type":"compositionEnd","data":"漢字"}"#).unwrap();
+    assert_eq!(
+      event,
+      KeyboardEvent::CompositionEnd {
+        data: "漢字".into()
+      }
+    );
+  }
+
+  #[test]
+  fn emits_to_the_window_listeners() {
+    let listeners = KeyboardEventListeners::default();
+    let count = Arc::new(AtomicUsize::new(0));
+    let count_ = count.clone();
+    listeners.listen("main", move |_| {
+      count_.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let event = KeyboardEvent::CompositionStart {
+      data: String::new(),
+    };
+    listeners.emit("other", &event);
+    listeners.emit("main", &event);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    listeners.remove_window("main");
+    listeners.emit("main", &event);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+  }
+}
@@ -0,0 +1,154 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The kiosk windows, e.g. for a point of sale or a digital signage, see [`crate::WindowBuilder::kiosk`].
+//!
+//! The window itself is configured by the runtime: fullscreen, always on top, undecorated and without devtools.
+//! This module locks down the rest: the user close requests, the context menu and the system shortcuts,
+//! and relaunches the app when it panics.
+
+use crate::{Env, Manager, Runtime, Window};
+
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Once,
+  },
+  time::{Duration, Instant},
+};
+
+/// The script disabling the context menu and the webview shortcuts, e.g. the reload and the devtools shortcuts.
+pub(crate) const KIOSK_SCRIPT: &str = include_str!("../../scripts/kiosk.js");
+
+/// The app is not relaunched when it panics sooner than this after the first kiosk window is created,
+/// so a panic on startup does not turn into a relaunch loop.
+const WATCHDOG_MIN_UPTIME: Duration = Duration::from_secs(10);
+
+/// The number of kiosk windows, the system shortcuts are blocked while there is at least one.
+static KIOSK_WINDOWS: AtomicUsize = AtomicUsize::new(0);
+
+/// Locks down the created kiosk window.
+pub(crate) fn attach<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+  install_watchdog(window.env());
+
+  if KIOSK_WINDOWS.fetch_add(1, Ordering::SeqCst) == 0 {
+    window
+      .app_handle
+      .run_on_main_thread(|| block_system_shortcuts(true))?;
+  }
+
+  let app_handle = window.app_handle.clone();
+  window.on_window_event(move |event| match event {
+    // only the user close requests are prevented, `Window::close` still closes the window
+    crate::WindowEvent::CloseRequested { api } => api.prevent_close(),
+    crate::WindowEvent::Destroyed if KIOSK_WINDOWS.fetch_sub(1, Ordering::SeqCst) == 1 => {
+      let _ = app_handle.run_on_main_thread(|| block_system_shortcuts(false));
+    }
+    _ => {}
+  });
+
+  Ok(())
+}
+
+/// Relaunches the app when it panics, after the default panic hook reports the panic.
+fn install_watchdog(env: Env) {
+  static WATCHDOG: Once = Once::new();
+  WATCHDOG.call_once(move || {
+    let started = Instant::now();
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+      hook(info);
+      if started.elapsed() >= WATCHDOG_MIN_UPTIME {
+        crate::process::restart(&env);
+      }
+    }));
+  });
+}
+
+/// Blocks or unblocks the system shortcuts switching, hiding or closing the apps. Must run on the main thread.
+///
+/// - **Windows:** A low-level keyboard hook swallows the Windows keys, `Alt+Tab`, `Alt+Esc`, `Alt+F4` and `Ctrl+Esc`.
+///   `Ctrl+Alt+Del` cannot be blocked.
+/// - **macOS:** The presentation options of the app hide the Dock and the menu bar, and disable the app switcher,
+///   the force quit, the log out and the hide shortcuts.
+/// - **Linux:** Unsupported, the shortcuts are handled by the compositor.
+fn block_system_shortcuts(block: bool) {
+  #[cfg(windows)]
+  windows_hook::set(block);
+
+  #[cfg(target_os = "macos")]
+  unsafe {
+    use cocoa::{appkit::NSApp, base::id};
+    use objc::{msg_send, sel, sel_impl};
+
+    /// `NSApplicationPresentationHideDock | NSApplicationPresentationHideMenuBar
+    /// | NSApplicationPresentationDisableProcessSwitching | NSApplicationPresentationDisableForceQuit
+    /// | NSApplicationPresentationDisableSessionTermination | NSApplicationPresentationDisableHideApplication`
+    const KIOSK_PRESENTATION_OPTIONS: u64 = 1 << 1 | 1 << 3 | 1 << 5 | 1 << 6 | 1 << 7 | 1 << 8;
+
+    let app: id = NSApp();
+    let options: u64 = if block { KIOSK_PRESENTATION_OPTIONS } else { 0 };
+    let _: () = msg_send![app, setPresentationOptions: options];
+  }
+
+  #[cfg(not(any(windows, target_os = "macos")))]
+  let _ = block;
+}
+
+#[cfg(windows)]
+mod windows_hook {
+  use std::sync::atomic::{AtomicIsize, Ordering};
+
+  use windows::Win32::{
+    Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM},
+    UI::{
+      Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_ESCAPE, VK_F4, VK_LWIN, VK_RWIN, VK_TAB,
+      },
+      WindowsAndMessaging::{
+        CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT,
+        LLKHF_ALTDOWN, WH_KEYBOARD_LL,
+      },
+    },
+  };
+
+  /// The installed keyboard hook, or zero.
+  static HOOK: AtomicIsize = AtomicIsize::new(0);
+
+  /// Installs or removes the keyboard hook. The hook runs on the thread that installed it,
+  /// so it is installed on the main thread, which pumps the messages of the event loop.
+  pub(super) fn set(block: bool) {
+    if block {
+      if HOOK.load(Ordering::SeqCst) == 0 {
+        if let Ok(hook) =
+          unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_proc), HINSTANCE(0), 0) }
+        {
+          HOOK.store(hook.0, Ordering::SeqCst);
+        }
+      }
+    } else {
+      let hook = HOOK.swap(0, Ordering::SeqCst);
+      if hook != 0 {
+        unsafe { UnhookWindowsHookEx(HHOOK(hook)) };
+      }
+    }
+  }
+
+  unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+      let key = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+      let vk = VIRTUAL_KEY(key.vkCode as u16);
+      let alt = key.flags.0 & LLKHF_ALTDOWN.0 != 0;
+      let ctrl = GetAsyncKeyState(VK_CONTROL.0 as i32) < 0;
+      let blocked = vk == VK_LWIN
+        || vk == VK_RWIN
+        || (alt && (vk == VK_TAB || vk == VK_ESCAPE || vk == VK_F4))
+        || (ctrl && vk == VK_ESCAPE);
+      if blocked {
+        return LRESULT(1);
+      }
+    }
+    CallNextHookEx(HHOOK(HOOK.load(Ordering::SeqCst)), code, wparam, lparam)
+  }
+}
@@ -0,0 +1,211 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The layer shell surfaces of the Wayland compositors, e.g. for panels, docks, launchers and wallpapers.
+//!
+//! A layer surface is placed by the compositor on a layer above or below the other windows,
+//! anchored to the edges of the screen, and can reserve an exclusive zone that the maximized
+//! windows do not cover. It is only supported by the compositors implementing the
+//! `wlr-layer-shell` protocol, e.g. Sway, Hyprland and KDE Plasma.
+
+use crate::{Runtime, Window};
+
+/// The layer of a [`LayerShell`] surface, from the bottom to the top of the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Layer {
+  /// Below the desktop icons, e.g. for a wallpaper.
+  Background,
+  /// Below the windows, e.g. for desktop widgets.
+  Bottom,
+  /// Above the windows, e.g. for a panel or a dock.
+  #[default]
+  Top,
+  /// Above the fullscreen windows, e.g. for a launcher or a notification.
+  Overlay,
+}
+
+/// An edge of the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayerEdge {
+  /// The left edge.
+  Left,
+  /// The right edge.
+  Right,
+  /// The top edge.
+  Top,
+  /// The bottom edge.
+  Bottom,
+}
+
+/// The area of the screen reserved by a [`LayerShell`] surface, which the other windows do not cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ExclusiveZone {
+  /// The surface does not reserve an area, but it is moved by the exclusive zones of the other surfaces.
+  #[default]
+  None,
+  /// The surface reserves the given number of pixels from the edge it is anchored to.
+  Size(u32),
+  /// The surface reserves its size from the edge it is anchored to.
+  Auto,
+  /// The surface ignores the exclusive zones of the other surfaces, e.g. for a wallpaper.
+  Ignore,
+}
+
+/// The configuration of a window shown as a layer shell surface, see [`crate::WindowBuilder::layer_shell`].
+///
+/// # Examples
+///
+/// ```
+/// use tauri::window::{ExclusiveZone, Layer, LayerEdge, LayerShell};
+///
+/// // a panel at the top of the screen
+/// let panel = LayerShell::new(Layer::Top)
+///   .anchor(LayerEdge::Top)
+///   .anchor(LayerEdge::Left)
+///   .anchor(LayerEdge::Right)
+///   .exclusive_zone(ExclusiveZone::Auto)
+///   .namespace("panel");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayerShell {
+  layer: Layer,
+  anchors: Vec<LayerEdge>,
+  margins: Vec<(LayerEdge, i32)>,
+  exclusive_zone: ExclusiveZone,
+  keyboard_interactivity: bool,
+  namespace: Option<String>,
+}
+
+impl LayerShell {
+  /// Creates the configuration of a surface on the given layer, centered on the screen.
+  pub fn new(layer: Layer) -> Self {
+    Self {
+      layer,
+      ..Default::default()
+    }
+  }
+
+  /// Anchors the surface to an edge of the screen. A surface anchored to two opposite edges is stretched between them.
+  #[must_use]
+  pub fn anchor(mut self, edge: LayerEdge) -> Self {
+    if !self.anchors.contains(&edge) {
+      self.anchors.push(edge);
+    }
+    self
+  }
+
+  /// Sets the distance in pixels between the surface and an edge of the screen it is anchored to.
+  #[must_use]
+  pub fn margin(mut self, edge: LayerEdge, margin: i32) -> Self {
+    self.margins.retain(|(e, _)| *e != edge);
+    self.margins.push((edge, margin));
+    self
+  }
+
+  /// Sets the area of the screen reserved by the surface.
+  #[must_use]
+  pub fn exclusive_zone(mut self, zone: ExclusiveZone) -> Self {
+    self.exclusive_zone = zone;
+    self
+  }
+
+  /// Whether the surface can receive the keyboard focus, e.g. for a launcher with a search field.
+  #[must_use]
+  pub fn keyboard_interactivity(mut self, interactive: bool) -> Self {
+    self.keyboard_interactivity = interactive;
+    self
+  }
+
+  /// Sets the namespace of the surface, which the compositor can use to apply rules to it.
+  #[must_use]
+  pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+    self.namespace.replace(namespace.into());
+    self
+  }
+}
+
+/// Turns the created window into a layer shell surface.
+///
+/// The surface role of a Wayland window cannot change once it is mapped,
+/// so the window is hidden and unrealized before it is initialized as a layer surface.
+pub(crate) fn init<R: Runtime>(window: &Window<R>, config: LayerShell) -> crate::Result<()> {
+  let (tx, rx) = std::sync::mpsc::channel();
+  let handle = window.clone();
+  window.run_on_main_thread(move || {
+    let _ = tx.send(
+      handle
+        .gtk_window()
+        .and_then(|window| apply(&window, &config)),
+    );
+  })?;
+  rx.recv().unwrap_or(Ok(()))
+}
+
+fn apply(window: &gtk::ApplicationWindow, config: &LayerShell) -> crate::Result<()> {
+  use gtk::prelude::WidgetExt;
+  use gtk_layer_shell as shell;
+
+  if !shell::is_supported() {
+    return Err(crate::Error::LayerShellUnsupported);
+  }
+
+  let visible = window.is_visible();
+  window.hide();
+  if window.is_realized() {
+    window.unrealize();
+  }
+
+  shell::init_for_window(window);
+  shell::set_layer(
+    window,
+    match config.layer {
+      Layer::Background => shell::Layer::Background,
+      Layer::Bottom => shell::Layer::Bottom,
+      Layer::Top => shell::Layer::Top,
+      Layer::Overlay => shell::Layer::Overlay,
+    },
+  );
+  for edge in [
+    LayerEdge::Left,
+    LayerEdge::Right,
+    LayerEdge::Top,
+    LayerEdge::Bottom,
+  ] {
+    shell::set_anchor(window, edge.into(), config.anchors.contains(&edge));
+  }
+  for (edge, margin) in &config.margins {
+    shell::set_margin(window, (*edge).into(), *margin);
+  }
+  match config.exclusive_zone {
+    ExclusiveZone::None => shell::set_exclusive_zone(window, 0),
+    ExclusiveZone::Size(size) => {
+      shell::set_exclusive_zone(window, size.try_into().unwrap_or(i32::MAX))
+    }
+    ExclusiveZone::Auto => shell::auto_exclusive_zone_enable(window),
+    ExclusiveZone::Ignore => shell::set_exclusive_zone(window, -1),
+  }
+  shell::set_keyboard_interactivity(window, config.keyboard_interactivity);
+  if let Some(namespace) = &config.namespace {
+    shell::set_namespace(window, namespace);
+  }
+
+  if visible {
+    window.show();
+  }
+  Ok(())
+}
+
+impl From<LayerEdge> for gtk_layer_shell::Edge {
+  fn from(edge: LayerEdge) -> Self {
+    match edge {
+      LayerEdge::Left => Self::Left,
+      LayerEdge::Right => Self::Right,
+      LayerEdge::Top => Self::Top,
+      LayerEdge::Bottom => Self::Bottom,
+    }
+  }
+}
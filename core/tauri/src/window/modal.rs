@@ -0,0 +1,200 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The windows created with a parent by [`crate::WindowBuilder::parent`]: the modal windows blocking their parent,
+//! and the [child](crate::WindowBuilder::child) windows embedded in their parent.
+
+use crate::{
+  runtime::window::dpi::{PhysicalPosition, Position},
+  Manager, Runtime, Window,
+};
+
+/// The parent of a window.
+#[derive(Debug, Clone)]
+pub(crate) struct ParentWindow {
+  /// The label of the parent window.
+  pub(crate) label: String,
+  /// Whether the window is embedded in the client area of its parent, so its position is relative to the parent.
+  pub(crate) embedded: bool,
+}
+
+/// Attaches the created window to its parent, blocking the parent until the window is destroyed when it is modal.
+pub(crate) fn attach<R: Runtime>(
+  parent: &Window<R>,
+  window: &Window<R>,
+  modal: bool,
+  child: bool,
+) -> crate::Result<()> {
+  // a child window cannot be a sheet nor disable the window it is embedded in
+  let modal = modal && !child;
+  // the window is embedded with `WS_CHILD` on Windows and reparented on X11
+  let embedded = cfg!(windows) && child;
+  window.parent.lock().unwrap().replace(ParentWindow {
+    label: parent.label().into(),
+    embedded,
+  });
+
+  // the owner or parent window is set on creation, see `WindowBuilder::build`
+  #[cfg(windows)]
+  if modal {
+    use windows::Win32::UI::{
+      Input::KeyboardAndMouse::EnableWindow, WindowsAndMessaging::SetForegroundWindow,
+    };
+
+    let parent = parent.hwnd()?;
+    unsafe { EnableWindow(parent, false) };
+    window.on_window_event(move |event| {
+      if let crate::WindowEvent::Destroyed = event {
+        unsafe {
+          EnableWindow(parent, true);
+          SetForegroundWindow(parent);
+        }
+      }
+    });
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    use cocoa::base::id;
+    use objc::{msg_send, sel, sel_impl};
+
+    /// `NSWindowAbove`
+    const ORDERED_ABOVE: isize = 1;
+
+    let parent = parent.ns_window()? as usize;
+    let child = window.ns_window()? as usize;
+    window.run_on_main_thread(move || unsafe {
+      let (parent, child) = (parent as id, child as id);
+      if modal {
+        // a sheet slides from the title bar of the parent and blocks it until it ends
+        let _: () = msg_send![parent, beginSheet: child completionHandler: cocoa::base::nil];
+      } else {
+        let _: () = msg_send![parent, addChildWindow: child ordered: ORDERED_ABOVE];
+      }
+    })?;
+
+    if modal {
+      let main_thread = window.clone();
+      window.on_window_event(move |event| {
+        if let crate::WindowEvent::Destroyed = event {
+          let _ = main_thread.run_on_main_thread(move || unsafe {
+            let parent = parent as id;
+            let sheet: id = msg_send![parent, attachedSheet];
+            if sheet != cocoa::base::nil {
+              let _: () = msg_send![parent, endSheet: sheet];
+            }
+          });
+        }
+      });
+    }
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (parent_window, child_window) = (parent.clone(), window.clone());
+    window.run_on_main_thread(move || {
+      use gtk::{
+        glib::ObjectExt,
+        prelude::{GtkWindowExt, WidgetExt},
+      };
+
+      let mut embedded = false;
+      if let (Ok(parent), Ok(window)) = (parent_window.gtk_window(), child_window.gtk_window()) {
+        window.set_transient_for(Some(&parent));
+        window.set_destroy_with_parent(true);
+        window.set_modal(modal);
+
+        // X11 windows can be reparented, which clips them to the parent and moves them with it
+        let x11 = WidgetExt::display(&window).type_().name() == "GdkX11Display";
+        if child && x11 {
+          let (x, y) = window.position();
+          if let (Some(parent), Some(window)) = (parent.window(), window.window()) {
+            let (_, parent_x, parent_y) = parent.origin();
+            window.reparent(&parent, x - parent_x, y - parent_y);
+            embedded = true;
+          }
+        }
+      }
+      let _ = tx.send(embedded);
+    })?;
+
+    if rx.recv().unwrap_or(false) {
+      if let Some(parent) = window.parent.lock().unwrap().as_mut() {
+        parent.embedded = true;
+      }
+    } else if child {
+      follow_parent(parent, window)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Moves the window with its parent, for the child windows that are not embedded in their parent.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn follow_parent<R: Runtime>(parent: &Window<R>, window: &Window<R>) -> crate::Result<()> {
+  let last_position = std::sync::Mutex::new(parent.outer_position()?);
+  let label = window.label().to_string();
+  let manager = window.app_handle.clone();
+  parent.on_window_event(move |event| {
+    if let crate::WindowEvent::Moved(position) = event {
+      let mut last_position = last_position.lock().unwrap();
+      if let Some(window) = manager.get_window(&label) {
+        if let Ok(current) = window.outer_position() {
+          let _ = window.set_position(PhysicalPosition::new(
+            current.x + position.x - last_position.x,
+            current.y + position.y - last_position.y,
+          ));
+        }
+      }
+      *last_position = *position;
+    }
+  });
+  Ok(())
+}
+
+/// Moves the window to the given position relative to the top-left corner of the client area of its parent.
+pub(crate) fn position_relative_to_parent<R: Runtime>(
+  window: &Window<R>,
+  position: Position,
+) -> crate::Result<()> {
+  let parent = window
+    .parent
+    .lock()
+    .unwrap()
+    .clone()
+    .ok_or(crate::Error::WindowWithoutParent)?;
+
+  // the position of an embedded window is already relative to its parent
+  if parent.embedded {
+    return window.set_position(position);
+  }
+
+  let parent = window
+    .app_handle
+    .get_window(&parent.label)
+    .ok_or(crate::Error::WindowWithoutParent)?;
+  let origin = parent.inner_position()?;
+  let offset = match position {
+    Position::Physical(position) => position,
+    Position::Logical(position) => position.to_physical(parent.scale_factor()?),
+  };
+  window.set_position(PhysicalPosition::new(
+    origin.x + offset.x,
+    origin.y + offset.y,
+  ))
+}
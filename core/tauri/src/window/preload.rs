@@ -0,0 +1,35 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The windows preloaded when the app starts, configured with `preload` on `tauri > windows`
+//! and returned by [`crate::AppHandle::get_preloaded_window`].
+
+use crate::{Runtime, Window};
+
+use std::{collections::HashSet, sync::Mutex};
+
+/// The labels of the preloaded windows.
+#[derive(Default)]
+pub(crate) struct PreloadedWindows(Mutex<HashSet<String>>);
+
+impl PreloadedWindows {
+  pub(crate) fn insert(&self, label: String) {
+    self.0.lock().unwrap().insert(label);
+  }
+
+  pub(crate) fn contains(&self, label: &str) -> bool {
+    self.0.lock().unwrap().contains(label)
+  }
+}
+
+/// Keeps the created preloaded window alive, hiding it instead of closing it when the user closes it.
+pub(crate) fn attach<R: Runtime>(window: &Window<R>) {
+  let handle = window.clone();
+  window.on_window_event(move |event| {
+    if let crate::WindowEvent::CloseRequested { api } = event {
+      api.prevent_close();
+      let _ = handle.hide();
+    }
+  });
+}
@@ -0,0 +1,178 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The status and navigation bars drawn by the system over the window on mobile,
+//! initially configured with [`tauri > bundle > systemBars`](crate::utils::config::SystemBarsConfig).
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  utils::config::{Color, SystemBarStyle},
+  Runtime, Window,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The distances from the edges of the webview to its area not covered by the system bars, the display cutout
+/// and the rounded corners, in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SafeAreaInsets {
+  /// The inset from the top edge.
+  pub top: f64,
+  /// The inset from the right edge.
+  pub right: f64,
+  /// The inset from the bottom edge.
+  pub bottom: f64,
+  /// The inset from the left edge.
+  pub left: f64,
+}
+
+/// An update of a system bar, leaving the unset properties unchanged.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SystemBar {
+  /// The background color, as `#RRGGBBAA`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  color: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  style: Option<SystemBarStyle>,
+}
+
+impl SystemBar {
+  pub(crate) fn color(color: Color) -> Self {
+    let Color(r, g, b, a) = color;
+    Self {
+      color: Some(format!("#{r:02x}{g:02x}{b:02x}{a:02x}")),
+      style: None,
+    }
+  }
+
+  pub(crate) fn style(style: SystemBarStyle) -> Self {
+    Self {
+      color: None,
+      style: Some(style),
+    }
+  }
+}
+
+/// The native system bars plugin.
+#[cfg(mobile)]
+struct SystemBarsPlugin<R: Runtime>(crate::plugin::PluginHandle<R>);
+
+/// Runs a command of the native plugin, or returns `None` on desktop.
+fn run<R: Runtime, T: serde::de::DeserializeOwned, P: Serialize>(
+  window: &Window<R>,
+  command: &str,
+  payload: P,
+) -> crate::Result<Option<T>> {
+  #[cfg(mobile)]
+  {
+    use crate::Manager;
+
+    if let Some(plugin) = window.try_state::<SystemBarsPlugin<R>>() {
+      return plugin
+        .0
+        .run_mobile_plugin(command, payload)
+        .map(Some)
+        .map_err(Into::into);
+    }
+  }
+
+  let _ = (window, command, payload);
+  Ok(None)
+}
+
+pub(crate) fn set_status_bar<R: Runtime>(window: &Window<R>, bar: SystemBar) -> crate::Result<()> {
+  run::<R, (), _>(window, "setStatusBar", bar).map(|_| ())
+}
+
+pub(crate) fn set_navigation_bar<R: Runtime>(
+  window: &Window<R>,
+  bar: SystemBar,
+) -> crate::Result<()> {
+  run::<R, (), _>(window, "setNavigationBar", bar).map(|_| ())
+}
+
+pub(crate) fn set_edge_to_edge<R: Runtime>(window: &Window<R>, enable: bool) -> crate::Result<()> {
+  run::<R, (), _>(
+    window,
+    "setEdgeToEdge",
+    serde_json::json!({ "enable": enable }),
+  )
+  .map(|_| ())
+}
+
+pub(crate) fn safe_area_insets<R: Runtime>(window: &Window<R>) -> crate::Result<SafeAreaInsets> {
+  run(window, "safeAreaInsets", ()).map(Option::unwrap_or_default)
+}
+
+mod commands {
+  use super::{SafeAreaInsets, SystemBar};
+  use crate::{command, Runtime, Window};
+
+  #[command(root = "crate")]
+  pub(super) async fn set_status_bar<R: Runtime>(
+    window: Window<R>,
+    bar: SystemBar,
+  ) -> crate::Result<()> {
+    super::set_status_bar(&window, bar)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn set_navigation_bar<R: Runtime>(
+    window: Window<R>,
+    bar: SystemBar,
+  ) -> crate::Result<()> {
+    super::set_navigation_bar(&window, bar)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn set_edge_to_edge<R: Runtime>(
+    window: Window<R>,
+    enable: bool,
+  ) -> crate::Result<()> {
+    super::set_edge_to_edge(&window, enable)
+  }
+
+  #[command(root = "crate")]
+  pub(super) async fn safe_area_insets<R: Runtime>(
+    window: Window<R>,
+  ) -> crate::Result<SafeAreaInsets> {
+    super::safe_area_insets(&window)
+  }
+}
+
+/// Initializes the system bars plugin, controlling the system bars from the webview.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("system-bars")
+    .invoke_handler(crate::generate_handler![
+      commands::set_status_bar,
+      commands::set_navigation_bar,
+      commands::set_edge_to_edge,
+      commands::safe_area_insets
+    ])
+    .setup(|app, _api| {
+      #[cfg(mobile)]
+      {
+        use crate::Manager;
+
+        #[cfg(target_os = "android")]
+        let handle = _api.register_android_plugin("app.tauri.systembars", "SystemBarsPlugin")?;
+        #[cfg(target_os = "ios")]
+        let handle = {
+          let handle = _api.register_ios_plugin(crate::ios::init_system_bars_plugin)?;
+          // the main activity lays out the webview edge to edge on Android
+          if app.config().tauri.bundle.system_bars.edge_to_edge {
+            handle
+              .run_mobile_plugin::<()>("setEdgeToEdge", serde_json::json!({ "enable": true }))?;
+          }
+          handle
+        };
+        app.manage(SystemBarsPlugin(handle));
+      }
+
+      let _ = app;
+      Ok(())
+    })
+    .build()
+}
@@ -0,0 +1,201 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The window controls drawn over the webview by the windows with the overlay title bar,
+//! see [`crate::WindowBuilder::title_bar_overlay`].
+
+use crate::{
+  plugin::{Builder, TauriPlugin},
+  utils::config::TitleBarOverlayConfig,
+  Runtime, Window,
+};
+
+use serde::Serialize;
+
+/// The script exposing the bounding rectangle of the window controls to the webview.
+pub(crate) const TITLE_BAR_OVERLAY_SCRIPT: &str =
+  include_str!("../../scripts/title_bar_overlay.js");
+
+/// The bounding rectangle of the window controls drawn over the webview, in logical pixels
+/// from the top-left corner of the webview.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TitleBarOverlayRect {
+  /// The horizontal position of the controls.
+  pub x: f64,
+  /// The vertical position of the controls.
+  pub y: f64,
+  /// The width of the controls.
+  pub width: f64,
+  /// The height of the controls.
+  pub height: f64,
+}
+
+/// Lays out the window controls of the created window, and again whenever AppKit resets them,
+/// e.g. when the window is resized or leaves the fullscreen mode.
+pub(crate) fn attach<R: Runtime>(
+  window: &Window<R>,
+  config: TitleBarOverlayConfig,
+) -> crate::Result<()> {
+  layout(window, config.clone())?;
+
+  let handle = window.clone();
+  window.on_window_event(move |event| {
+    if let crate::WindowEvent::Resized(_) | crate::WindowEvent::ScaleFactorChanged { .. } = event {
+      let _ = layout(&handle, config.clone());
+    }
+  });
+  Ok(())
+}
+
+/// Moves the window controls and sends their new bounding rectangle to the webview.
+fn layout<R: Runtime>(window: &Window<R>, config: TitleBarOverlayConfig) -> crate::Result<()> {
+  #[cfg(target_os = "macos")]
+  {
+    let ns_window = window.ns_window()? as usize;
+    let handle = window.clone();
+    window.run_on_main_thread(move || {
+      unsafe { macos::layout(ns_window as _, &config) };
+      if let Ok(rect) = serde_json::to_string(&unsafe { macos::rect(ns_window as _) }) {
+        let _ = handle.eval(&format!(
+          "window.__TAURI_TITLE_BAR_OVERLAY__ && window.__TAURI_TITLE_BAR_OVERLAY__.update({rect})"
+        ));
+      }
+    })?;
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  let _ = (window, config);
+
+  Ok(())
+}
+
+/// Returns the bounding rectangle of the window controls drawn over the webview,
+/// or `None` if the window does not draw its controls over the webview.
+pub(crate) fn overlay_rect<R: Runtime>(
+  window: &Window<R>,
+) -> crate::Result<Option<TitleBarOverlayRect>> {
+  #[cfg(target_os = "macos")]
+  {
+    let ns_window = window.ns_window()? as usize;
+    let (tx, rx) = std::sync::mpsc::channel();
+    window.run_on_main_thread(move || {
+      let _ = tx.send(unsafe { macos::rect(ns_window as _) });
+    })?;
+    return Ok(rx.recv().unwrap_or(None));
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  {
+    let _ = window;
+    Ok(None)
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+  use super::TitleBarOverlayRect;
+  use crate::utils::config::TitleBarOverlayConfig;
+
+  use cocoa::{
+    appkit::{NSView, NSWindow, NSWindowButton, NSWindowStyleMask},
+    base::{id, nil},
+    foundation::NSRect,
+  };
+  use objc::{msg_send, sel, sel_impl};
+
+  unsafe fn buttons(ns_window: id) -> Option<[id; 3]> {
+    let close = ns_window.standardWindowButton_(NSWindowButton::NSWindowCloseButton);
+    let miniaturize = ns_window.standardWindowButton_(NSWindowButton::NSWindowMiniaturizeButton);
+    let zoom = ns_window.standardWindowButton_(NSWindowButton::NSWindowZoomButton);
+    if close == nil || miniaturize == nil || zoom == nil {
+      None
+    } else {
+      Some([close, miniaturize, zoom])
+    }
+  }
+
+  /// Whether the content view fills the window, under the transparent title bar.
+  unsafe fn is_overlay(ns_window: id) -> bool {
+    ns_window
+      .styleMask()
+      .contains(NSWindowStyleMask::NSFullSizeContentViewWindowMask)
+  }
+
+  pub(super) unsafe fn layout(ns_window: id, config: &TitleBarOverlayConfig) {
+    let Some([close, miniaturize, zoom]) = buttons(ns_window) else {
+      return;
+    };
+    if !is_overlay(ns_window) {
+      return;
+    }
+
+    let close_rect = NSView::frame(close);
+    let button_height = close_rect.size.height;
+    let (x, y) = match (config.inset, config.height) {
+      (Some(inset), _) => (inset.x, inset.y),
+      (None, Some(height)) => (close_rect.origin.x, ((height - button_height) / 2.).max(0.)),
+      (None, None) => return,
+    };
+
+    // the buttons are at the bottom of the title bar container, so its height sets their vertical position
+    let title_bar_container: id = msg_send![NSView::superview(close), superview];
+    let title_bar_height = (button_height + y).max(config.height.unwrap_or_default());
+    let mut title_bar_rect = NSView::frame(title_bar_container);
+    title_bar_rect.size.height = title_bar_height;
+    title_bar_rect.origin.y = NSView::frame(ns_window).size.height - title_bar_height;
+    let _: () = msg_send![title_bar_container, setFrame: title_bar_rect];
+
+    let spacing = NSView::frame(miniaturize).origin.x - close_rect.origin.x;
+    for (i, button) in [close, miniaturize, zoom].into_iter().enumerate() {
+      let mut rect: NSRect = NSView::frame(button);
+      rect.origin.x = x + i as f64 * spacing;
+      rect.origin.y = title_bar_height - y - button_height;
+      button.setFrameOrigin(rect.origin);
+    }
+  }
+
+  pub(super) unsafe fn rect(ns_window: id) -> Option<TitleBarOverlayRect> {
+    let [close, _, zoom] = buttons(ns_window)?;
+    let hidden: bool = msg_send![close, isHidden];
+    if hidden || !is_overlay(ns_window) {
+      return None;
+    }
+
+    // the frames of the buttons are relative to the title bar, from its bottom-left corner
+    let title_bar: id = NSView::superview(close);
+    let title_bar_height = NSView::frame(title_bar).size.height;
+    let container_rect = NSView::frame(NSView::superview(title_bar));
+    let window_height = NSView::frame(ns_window).size.height;
+    let container_top = window_height - (container_rect.origin.y + container_rect.size.height);
+
+    let close_rect = NSView::frame(close);
+    let zoom_rect = NSView::frame(zoom);
+    Some(TitleBarOverlayRect {
+      x: close_rect.origin.x,
+      y: container_top + title_bar_height - close_rect.origin.y - close_rect.size.height,
+      width: zoom_rect.origin.x + zoom_rect.size.width - close_rect.origin.x,
+      height: close_rect.size.height,
+    })
+  }
+}
+
+mod commands {
+  use super::TitleBarOverlayRect;
+  use crate::{command, Runtime, Window};
+
+  #[command(root = "crate")]
+  pub(super) fn overlay_rect<R: Runtime>(
+    window: Window<R>,
+  ) -> Result<Option<TitleBarOverlayRect>, String> {
+    super::overlay_rect(&window).map_err(|e| e.to_string())
+  }
+}
+
+/// Initializes the title bar plugin, exposing the bounding rectangle of the window controls to the webview.
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("title-bar")
+    .invoke_handler(crate::generate_handler![commands::overlay_rect])
+    .build()
+}
@@ -0,0 +1,33 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Authenticate the user with the prompt of the operating system, e.g. Touch ID or Windows Hello, to unlock the app.
+ *
+ * This package is also accessible with `window.__TAURI__.auth` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.auth`](https://tauri.app/v1/api/config/#securityconfig.auth) in `tauri.conf.json`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * Prompts the user to authenticate, resolving once the operating system verified the user.
+ * The promise is rejected if the user cancels the prompt or cannot be authenticated.
+ * @example
+ * ```typescript
+ * import { authenticate } from '@tauri-apps/api/auth';
+ * await authenticate('unlock your notes');
+ * ```
+ *
+ * @param reason The reason shown on the prompt. Ignored on Linux, where the prompt shows the message of the polkit action of the app.
+ *
+ * @since 2.0.0
+ */
+async function authenticate(reason: string): Promise<void> {
+  return invoke('plugin:auth|authenticate', { reason })
+}
+
+export { authenticate }
@@ -0,0 +1,145 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Scan for Bluetooth LE devices, connect to them and read, write and subscribe to their GATT characteristics, since Web Bluetooth is not available in the webviews.
+ *
+ * This package is also accessible with `window.__TAURI__.bluetooth` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.bluetooth`](https://tauri.app/v1/api/config/#securityconfig.bluetooth) in `tauri.conf.json`.
+ * The user is asked the first time a window connects to a device.
+ *
+ * The services and the characteristics are UUIDs, either full or the 16-bit ones assigned by the Bluetooth SIG, e.g. `180d` for the heart rate service.
+ * @module
+ */
+
+import { Channel, invoke } from './tauri'
+
+/**
+ * A Bluetooth LE device found by {@link scan}.
+ *
+ * @since 2.0.0
+ */
+interface Peripheral {
+  /** The identifier the device is connected with. */
+  id: string
+  /** The name the device advertises. */
+  name: string | null
+  /** The signal strength of the advertisement, in dBm. */
+  rssi: number | null
+  /** The services the device advertises. Devices usually advertise a few of their services only. */
+  services: string[]
+}
+
+/**
+ * @since 2.0.0
+ */
+interface ScanOptions {
+  /** How long the scan lasts, in milliseconds. Defaults to 5 seconds. */
+  timeout?: number
+  /** Only returns the devices advertising one of the services. */
+  services?: string[]
+}
+
+/**
+ * Scans for the advertising devices, the closest first.
+ * @example
+ * ```typescript
+ * import { scan } from '@tauri-apps/api/bluetooth';
+ * const heartRateMonitors = await scan({ services: ['180d'] });
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function scan(options: ScanOptions = {}): Promise<Peripheral[]> {
+  return invoke('plugin:bluetooth|scan', { ...options })
+}
+
+/**
+ * A connection to a Bluetooth LE device.
+ *
+ * @since 2.0.0
+ */
+class Connection {
+  /** The identifier of the device. */
+  readonly id: string
+
+  /** @ignore */
+  constructor(id: string) {
+    this.id = id
+  }
+
+  /**
+   * Connects to a device found by {@link scan}, asking the user the first time.
+   * @example
+   * ```typescript
+   * import { scan, Connection } from '@tauri-apps/api/bluetooth';
+   * const [monitor] = await scan({ services: ['180d'] });
+   * const connection = await Connection.connect(monitor.id);
+   * await connection.subscribe('180d', '2a37', (value) => {
+   *   console.log(`${value[1]} bpm`);
+   * });
+   * ```
+   */
+  static async connect(id: string): Promise<Connection> {
+    await invoke('plugin:bluetooth|connect', { id })
+    return new Connection(id)
+  }
+
+  /** Reads the value of a characteristic. */
+  async read(service: string, characteristic: string): Promise<Uint8Array> {
+    return Uint8Array.from(
+      await invoke<number[]>('plugin:bluetooth|read', {
+        id: this.id,
+        service,
+        characteristic
+      })
+    )
+  }
+
+  /**
+   * Writes the value of a characteristic.
+   *
+   * @param options.withResponse Whether the device acknowledges the write, `true` by default.
+   */
+  async write(
+    service: string,
+    characteristic: string,
+    data: Uint8Array | number[],
+    options: { withResponse?: boolean } = {}
+  ): Promise<void> {
+    return invoke('plugin:bluetooth|write', {
+      id: this.id,
+      service,
+      characteristic,
+      data: Array.from(data),
+      withResponse: options.withResponse
+    })
+  }
+
+  /** Receives the values the device notifies or indicates for a characteristic. */
+  async subscribe(
+    service: string,
+    characteristic: string,
+    handler: (value: Uint8Array) => void
+  ): Promise<void> {
+    const onValue = new Channel<number[]>()
+    onValue.onmessage = (value) => handler(Uint8Array.from(value))
+    return invoke('plugin:bluetooth|subscribe', {
+      id: this.id,
+      service,
+      characteristic,
+      onValue
+    })
+  }
+
+  /** Unsubscribes from the characteristics and disconnects from the device. */
+  async disconnect(): Promise<void> {
+    return invoke('plugin:bluetooth|disconnect', { id: this.id })
+  }
+}
+
+export type { Peripheral, ScanOptions }
+
+export { scan, Connection }
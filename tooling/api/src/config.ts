@@ -0,0 +1,34 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Read the app configuration at runtime.
+ *
+ * This package is also accessible with `window.__TAURI__.config` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * Gets the configuration values listed on [`tauri.security.configAllowlist`](https://tauri.app/v1/api/config/#securityconfig.configallowlist),
+ * merged into a single object. Keys that are not allowlisted are omitted.
+ * @example
+ * ```typescript
+ * // with `"configAllowlist": ["package.version", "custom.theme"]`
+ * import { getConfig } from '@tauri-apps/api/config';
+ * const config = await getConfig<{
+ *   package: { version: string }
+ *   custom: { theme: string }
+ * }>();
+ * console.log(config.custom.theme);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function getConfig<T = Record<string, unknown>>(): Promise<T> {
+  return invoke('plugin:config|get_config')
+}
+
+export { getConfig }
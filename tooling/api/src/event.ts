@@ -56,7 +56,13 @@ enum TauriEvent {
   WINDOW_FILE_DROP = 'tauri://file-drop',
   WINDOW_FILE_DROP_HOVER = 'tauri://file-drop-hover',
   WINDOW_FILE_DROP_CANCELLED = 'tauri://file-drop-cancelled',
-  MENU = 'tauri://menu'
+  MENU = 'tauri://menu',
+  BEFORE_HOT_RESTART = 'tauri://before-hot-restart',
+  MEMORY_PRESSURE = 'tauri://memory-pressure',
+  LIFECYCLE = 'tauri://lifecycle',
+  PUSH = 'tauri://push',
+  PURCHASE = 'tauri://purchase',
+  SHORTCUT = 'tauri://shortcut'
 }
 
 /**
@@ -0,0 +1,76 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Play haptic feedback on Android and iOS, the functions do nothing on desktop.
+ *
+ * This package is also accessible with `window.__TAURI__.haptics` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.haptics`](https://tauri.app/v1/api/config/#securityconfig.haptics) in `tauri.conf.json`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * The intensity of an impact feedback.
+ *
+ * @since 2.0.0
+ */
+type ImpactStyle = 'light' | 'medium' | 'heavy'
+
+/**
+ * The outcome of a task reported by a notification feedback.
+ *
+ * @since 2.0.0
+ */
+type NotificationFeedback = 'success' | 'warning' | 'error'
+
+/**
+ * Plays an impact feedback, e.g. when a dragged view collides with a boundary.
+ * @example
+ * ```typescript
+ * import { impactFeedback } from '@tauri-apps/api/haptics';
+ * await impactFeedback('light');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function impactFeedback(style: ImpactStyle = 'medium'): Promise<void> {
+  return invoke('plugin:haptics|impact', { style })
+}
+
+/**
+ * Plays a selection feedback, e.g. when the value of a picker changes.
+ * @example
+ * ```typescript
+ * import { selectionFeedback } from '@tauri-apps/api/haptics';
+ * await selectionFeedback();
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function selectionFeedback(): Promise<void> {
+  return invoke('plugin:haptics|selection')
+}
+
+/**
+ * Plays a notification feedback, reporting the outcome of a task.
+ * @example
+ * ```typescript
+ * import { notificationFeedback } from '@tauri-apps/api/haptics';
+ * await notificationFeedback('success');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function notificationFeedback(
+  feedback: NotificationFeedback
+): Promise<void> {
+  return invoke('plugin:haptics|notification', { feedback })
+}
+
+export type { ImpactStyle, NotificationFeedback }
+
+export { impactFeedback, selectionFeedback, notificationFeedback }
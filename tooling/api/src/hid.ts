@@ -0,0 +1,110 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Read the gamepads and the other human interface devices, instead of relying on the Gamepad API of the webview.
+ *
+ * This package is also accessible with `window.__TAURI__.hid` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.hid`](https://tauri.app/v1/api/config/#securityconfig.hid) in `tauri.conf.json`.
+ * @module
+ */
+
+import { Channel, invoke } from './tauri'
+
+/**
+ * A human interface device connected to the computer.
+ *
+ * @since 2.0.0
+ */
+interface DeviceInfo {
+  /** The platform path the device is opened with. */
+  path: string
+  /** The USB vendor identifier. */
+  vendorId: number
+  /** The USB product identifier. */
+  productId: number
+  /** The product name reported by the device. */
+  productName: string | null
+  /** The serial number reported by the device. */
+  serialNumber: string | null
+  /** The usage page of the top level collection, e.g. `0x01` for the generic desktop controls. */
+  usagePage: number
+  /** The usage of the top level collection in its page, e.g. `0x05` for a gamepad. */
+  usage: number
+}
+
+/**
+ * Stops forwarding the input reports of a device.
+ *
+ * @since 2.0.0
+ */
+type UnlistenFn = () => Promise<void>
+
+/**
+ * Lists the human interface devices connected to the computer.
+ * @example
+ * ```typescript
+ * import { devices, isGamepad } from '@tauri-apps/api/hid';
+ * const gamepads = (await devices()).filter(isGamepad);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function devices(): Promise<DeviceInfo[]> {
+  return invoke('plugin:hid|devices')
+}
+
+/**
+ * Whether the device is a joystick, a gamepad or a multi-axis controller.
+ *
+ * @since 2.0.0
+ */
+function isGamepad(device: DeviceInfo): boolean {
+  return (
+    device.usagePage === 0x01 && [0x04, 0x05, 0x08].includes(device.usage)
+  )
+}
+
+/**
+ * Receives the input reports of the device. Each report starts with its report identifier.
+ * @example
+ * ```typescript
+ * import { devices, isGamepad, listen } from '@tauri-apps/api/hid';
+ * const [gamepad] = (await devices()).filter(isGamepad);
+ * const unlisten = await listen(gamepad.path, (report) => {
+ *   console.log(report);
+ * });
+ * ```
+ *
+ * @returns A function stopping the listener.
+ *
+ * @since 2.0.0
+ */
+async function listen(
+  path: string,
+  handler: (report: Uint8Array) => void
+): Promise<UnlistenFn> {
+  const onReport = new Channel<number[]>()
+  onReport.onmessage = (report) => handler(Uint8Array.from(report))
+  const id = await invoke<number>('plugin:hid|listen', { path, onReport })
+  return async () => invoke('plugin:hid|unlisten', { id })
+}
+
+/**
+ * Sends an output report to the device, e.g. to set the rumble of a gamepad.
+ * The first byte is the report identifier, `0` if the device does not number its reports.
+ *
+ * @since 2.0.0
+ */
+async function write(
+  path: string,
+  report: Uint8Array | number[]
+): Promise<void> {
+  return invoke('plugin:hid|write', { path, report: Array.from(report) })
+}
+
+export type { DeviceInfo, UnlistenFn }
+
+export { devices, isGamepad, listen, write }
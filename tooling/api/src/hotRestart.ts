@@ -0,0 +1,89 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Preserve the frontend state when `tauri dev` restarts the app because its Rust code changed.
+ *
+ * Restarts are only triggered by `tauri dev`, these functions have no effect in production builds.
+ *
+ * This package is also accessible with `window.__TAURI__.hotRestart` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+import { listen, TauriEvent, UnlistenFn } from './event'
+
+/** @ignore */
+declare global {
+  interface Window {
+    __TAURI_HOT_RESTART__?: Record<string, HotRestartState>
+  }
+}
+
+/**
+ * The state of a window before the app was restarted.
+ *
+ * @since 2.0.0
+ */
+interface HotRestartState<T = unknown> {
+  /** The URL the window was showing, including the route of the frontend router. */
+  url: string
+  /** The value returned by the {@link onBeforeHotRestart} handler, or `null` without handler. */
+  state: T | null
+}
+
+/**
+ * Saves the state of the current window before the app is restarted.
+ * The value returned by the handler must be JSON serializable, it is passed to {@link onHotRestart} after the restart.
+ *
+ * The app waits up to one second for the windows to save their state.
+ * @example
+ * ```typescript
+ * import { onBeforeHotRestart } from '@tauri-apps/api/hotRestart';
+ * await onBeforeHotRestart(() => ({ scrollY: window.scrollY }));
+ * ```
+ *
+ * @returns A promise resolving to a function to remove the handler.
+ *
+ * @since 2.0.0
+ */
+async function onBeforeHotRestart<T>(
+  handler: () => T | Promise<T>
+): Promise<UnlistenFn> {
+  return listen(TauriEvent.BEFORE_HOT_RESTART, async () => {
+    const state = await handler()
+    await invoke('plugin:hot-restart|save', { state: state ?? null })
+  })
+}
+
+/**
+ * Calls the handler with the state of the current window saved before the app was restarted.
+ * The handler is not called when the app was started normally.
+ * @example
+ * ```typescript
+ * import { onHotRestart } from '@tauri-apps/api/hotRestart';
+ * onHotRestart<{ scrollY: number }>(({ url, state }) => {
+ *   router.replace(new URL(url).pathname);
+ *   if (state) window.scrollTo(0, state.scrollY);
+ * });
+ * ```
+ *
+ * @since 2.0.0
+ */
+function onHotRestart<T>(handler: (state: HotRestartState<T>) => void): void {
+  const state =
+    window.__TAURI_HOT_RESTART__?.[
+      window.__TAURI_METADATA__.__currentWindow.label
+    ]
+  if (state) {
+    // keep the handler asynchronous whether the app was restarted or not
+    void Promise.resolve().then(() => {
+      handler(state as HotRestartState<T>)
+    })
+  }
+}
+
+export type { HotRestartState }
+
+export { onBeforeHotRestart, onHotRestart }
@@ -13,11 +13,52 @@
  * @module
  */
 
+import * as auth from './auth'
+import * as bluetooth from './bluetooth'
+import * as config from './config'
 import * as event from './event'
+import * as haptics from './haptics'
+import * as hid from './hid'
+import * as hotRestart from './hotRestart'
+import * as keyring from './keyring'
 import * as tauri from './tauri'
 import * as path from './path'
+import * as purchases from './purchases'
+import * as push from './push'
+import * as sensors from './sensors'
+import * as serial from './serial'
+import * as share from './share'
+import * as shortcuts from './shortcuts'
+import * as splashscreen from './splashscreen'
+import * as systemBars from './systemBars'
+import * as titleBar from './titleBar'
+import * as usb from './usb'
+import * as widgets from './widgets'
 
 /** @ignore */
 const invoke = tauri.invoke
 
-export { invoke, event, path, tauri }
+export {
+  invoke,
+  auth,
+  bluetooth,
+  config,
+  event,
+  haptics,
+  hid,
+  hotRestart,
+  keyring,
+  path,
+  purchases,
+  push,
+  sensors,
+  serial,
+  share,
+  shortcuts,
+  splashscreen,
+  systemBars,
+  tauri,
+  titleBar,
+  usb,
+  widgets
+}
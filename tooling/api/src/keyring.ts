@@ -0,0 +1,69 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Store the passwords of the app, e.g. its access tokens, in the credential store of the operating system.
+ *
+ * The entries are identified by a service and an account. The services are namespaced by the bundle identifier of the app.
+ *
+ * This package is also accessible with `window.__TAURI__.keyring` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.keyring`](https://tauri.app/v1/api/config/#securityconfig.keyring) in `tauri.conf.json`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * Stores the password of the account, replacing the existing password.
+ * @example
+ * ```typescript
+ * import { setPassword } from '@tauri-apps/api/keyring';
+ * await setPassword('api', 'alice', refreshToken);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setPassword(
+  service: string,
+  account: string,
+  password: string
+): Promise<void> {
+  return invoke('plugin:keyring|set_password', { service, account, password })
+}
+
+/**
+ * Reads the password of the account.
+ * @example
+ * ```typescript
+ * import { getPassword } from '@tauri-apps/api/keyring';
+ * const refreshToken = await getPassword('api', 'alice');
+ * ```
+ *
+ * @returns The password, or `null` if there is no such entry.
+ *
+ * @since 2.0.0
+ */
+async function getPassword(
+  service: string,
+  account: string
+): Promise<string | null> {
+  return invoke('plugin:keyring|get_password', { service, account })
+}
+
+/**
+ * Deletes the password of the account, if any.
+ * @example
+ * ```typescript
+ * import { deletePassword } from '@tauri-apps/api/keyring';
+ * await deletePassword('api', 'alice');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function deletePassword(service: string, account: string): Promise<void> {
+  return invoke('plugin:keyring|delete_password', { service, account })
+}
+
+export { setPassword, getPassword, deletePassword }
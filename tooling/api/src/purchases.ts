@@ -0,0 +1,159 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Sell in-app products and subscriptions on Android and iOS, enabled with [`tauri.bundle.inAppPurchases.enable`](https://tauri.app/v1/api/config/#bundleconfig.inapppurchases).
+ *
+ * The products are sold through Google Play Billing on Android and StoreKit on iOS 15 and later.
+ * A purchase must be finished with {@link finishPurchase} once its content is delivered, otherwise the store refunds it.
+ *
+ * This package is also accessible with `window.__TAURI__.purchases` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+import { listen, TauriEvent, UnlistenFn } from './event'
+
+/**
+ * The kind of a product: purchased once, either consumable or not, or an auto-renewable subscription.
+ *
+ * @since 2.0.0
+ */
+type ProductKind = 'oneTime' | 'subscription'
+
+/**
+ * A product sold in the app.
+ *
+ * @since 2.0.0
+ */
+interface Product {
+  /** The product identifier configured in the store. */
+  id: string
+  kind: ProductKind
+  /** The localized title. */
+  title: string
+  /** The localized description. */
+  description: string
+  /** The price formatted in the currency of the user, e.g. `$0.99`. */
+  price: string
+  /** The price in micro-units of the currency, e.g. `990000` for `$0.99`. */
+  priceMicros: number
+  /** The ISO 4217 code of the currency, e.g. `USD`. */
+  currencyCode: string
+}
+
+/**
+ * A purchase of a {@link Product}.
+ *
+ * @since 2.0.0
+ */
+interface Purchase {
+  /** The identifier of the purchased product. */
+  productId: string
+  /** The Google Play order identifier or the App Store transaction identifier, `null` while the purchase is pending on Android. */
+  orderId: string | null
+  /** The purchase token on Android, or the transaction identifier on iOS. */
+  token: string
+  /** The proof of purchase to verify on the app server: the purchase token on Android, or the JWS representation of the transaction on iOS. */
+  receipt: string
+  /**
+   * `purchased` once paid, or `pending` while waiting for a cash payment or the approval of a parent.
+   * The pending purchases are received by {@link onPurchaseUpdated} once completed.
+   */
+  state: 'purchased' | 'pending'
+  /** Whether the purchase was finished. */
+  acknowledged: boolean
+  /** When the product was purchased, in milliseconds since the Unix epoch. */
+  purchaseTime: number
+}
+
+/**
+ * Queries the products with the given identifiers, skipping the unknown ones.
+ * The products must be queried before they can be purchased.
+ * @example
+ * ```typescript
+ * import { getProducts } from '@tauri-apps/api/purchases';
+ * const [premium] = await getProducts(['premium']);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function getProducts(
+  ids: string[],
+  kind: ProductKind = 'oneTime'
+): Promise<Product[]> {
+  return invoke('plugin:purchases|get_products', { ids, kind })
+}
+
+/**
+ * Shows the store sheet to purchase the product.
+ * @example
+ * ```typescript
+ * import { purchase, finishPurchase } from '@tauri-apps/api/purchases';
+ * const result = await purchase('premium');
+ * if (result?.state === 'purchased') {
+ *   await fetch('https://example.com/verify', { method: 'POST', body: result.receipt });
+ *   await finishPurchase(result);
+ * }
+ * ```
+ *
+ * @returns A promise resolving to the purchase, or `null` if the user cancelled it.
+ *
+ * @since 2.0.0
+ */
+async function purchase(
+  productId: string,
+  kind: ProductKind = 'oneTime'
+): Promise<Purchase | null> {
+  return invoke('plugin:purchases|purchase', { productId, kind })
+}
+
+/**
+ * Finishes a purchase once its content was delivered.
+ *
+ * @param consume Whether the product is consumable, e.g. in-game currency, so it can be purchased again.
+ * Ignored on iOS, where the consumable products are declared in App Store Connect.
+ *
+ * @since 2.0.0
+ */
+async function finishPurchase(
+  purchase: Purchase,
+  consume = false
+): Promise<void> {
+  return invoke('plugin:purchases|finish', { purchase, consume })
+}
+
+/**
+ * Restores the non-consumable products and active subscriptions owned by the user, and the purchases not finished yet.
+ *
+ * @since 2.0.0
+ */
+async function restorePurchases(): Promise<Purchase[]> {
+  return invoke('plugin:purchases|restore')
+}
+
+/**
+ * Listens to the purchases completed outside of {@link purchase}, e.g. approved by a parent or renewed subscriptions.
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function onPurchaseUpdated(
+  handler: (purchase: Purchase) => void
+): Promise<UnlistenFn> {
+  return listen<Purchase>(TauriEvent.PURCHASE, ({ payload }) => {
+    handler(payload)
+  })
+}
+
+export type { ProductKind, Product, Purchase }
+
+export {
+  getProducts,
+  purchase,
+  finishPurchase,
+  restorePurchases,
+  onPurchaseUpdated
+}
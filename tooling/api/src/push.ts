@@ -0,0 +1,115 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Receive push notifications on Android and iOS, enabled with [`tauri.bundle.push.enable`](https://tauri.app/v1/api/config/#bundleconfig.push).
+ *
+ * This package is also accessible with `window.__TAURI__.push` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+import { listen, TauriEvent, UnlistenFn } from './event'
+
+/**
+ * A push notification.
+ *
+ * @since 2.0.0
+ */
+interface PushNotification {
+  /** The title of the notification, or `null` for a data-only notification. */
+  title: string | null
+  /** The body of the notification. */
+  body: string | null
+  /** The custom data of the notification. */
+  data: Record<string, unknown>
+  /** Whether the user tapped the notification, otherwise it was received while the app is running. */
+  opened: boolean
+  /** Whether the notification launched the app. */
+  coldStart: boolean
+}
+
+/** @ignore */
+type PushEvent =
+  | { type: 'token'; token: string }
+  | ({ type: 'notification' } & PushNotification)
+
+/**
+ * Asks the user for the permission to show notifications and registers the app for push notifications.
+ * @example
+ * ```typescript
+ * import { registerForPushNotifications } from '@tauri-apps/api/push';
+ * const token = await registerForPushNotifications();
+ * await fetch('https://example.com/devices', { method: 'POST', body: token });
+ * ```
+ *
+ * @returns A promise resolving to the token the app server sends the notifications to:
+ * the Firebase Cloud Messaging registration token on Android, or the hex-encoded APNs device token on iOS.
+ *
+ * @since 2.0.0
+ */
+async function registerForPushNotifications(): Promise<string> {
+  return invoke('plugin:push|register')
+}
+
+/**
+ * Gets the push notification tapped by the user to launch the app.
+ *
+ * @returns A promise resolving to the notification, or `null` if the app was not launched by a notification.
+ *
+ * @since 2.0.0
+ */
+async function getLaunchNotification(): Promise<PushNotification | null> {
+  return invoke('plugin:push|launch_notification')
+}
+
+/**
+ * Listens to the push notifications received while the app is running or tapped by the user.
+ * @example
+ * ```typescript
+ * import { onPushNotification } from '@tauri-apps/api/push';
+ * const unlisten = await onPushNotification((notification) => {
+ *   if (notification.opened) router.push(notification.data.route as string);
+ * });
+ * ```
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function onPushNotification(
+  handler: (notification: PushNotification) => void
+): Promise<UnlistenFn> {
+  return listen<PushEvent>(TauriEvent.PUSH, ({ payload }) => {
+    if (payload.type === 'notification') {
+      handler(payload)
+    }
+  })
+}
+
+/**
+ * Listens to the push token changes, after which the app server must send the notifications to the new token.
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function onPushTokenChanged(
+  handler: (token: string) => void
+): Promise<UnlistenFn> {
+  return listen<PushEvent>(TauriEvent.PUSH, ({ payload }) => {
+    if (payload.type === 'token') {
+      handler(payload.token)
+    }
+  })
+}
+
+export type { PushNotification }
+
+export {
+  registerForPushNotifications,
+  getLaunchNotification,
+  onPushNotification,
+  onPushTokenChanged
+}
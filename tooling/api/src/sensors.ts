@@ -0,0 +1,84 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Read the motion sensors on Android and iOS, the sensors are never available on desktop.
+ *
+ * This package is also accessible with `window.__TAURI__.sensors` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.sensors`](https://tauri.app/v1/api/config/#securityconfig.sensors) in `tauri.conf.json`.
+ * @module
+ */
+
+import { Channel, invoke } from './tauri'
+
+/**
+ * A motion sensor: the accelerometer measures the acceleration including gravity in m/s²,
+ * the gyroscope measures the rotation rate in rad/s.
+ *
+ * @since 2.0.0
+ */
+type Sensor = 'accelerometer' | 'gyroscope'
+
+/**
+ * A reading of a sensor, in the coordinate system of the device in its natural orientation.
+ *
+ * @since 2.0.0
+ */
+interface SensorReading {
+  x: number
+  y: number
+  z: number
+  /** When the value was measured, in milliseconds since the Unix epoch. */
+  timestamp: number
+}
+
+/**
+ * Whether the device has the sensor.
+ * @example
+ * ```typescript
+ * import { isSensorAvailable } from '@tauri-apps/api/sensors';
+ * const hasGyroscope = await isSensorAvailable('gyroscope');
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function isSensorAvailable(sensor: Sensor): Promise<boolean> {
+  return invoke('plugin:sensors|is_available', { sensor })
+}
+
+/**
+ * Receives the readings of a sensor, measured every `interval` milliseconds.
+ * @example
+ * ```typescript
+ * import { watchSensor } from '@tauri-apps/api/sensors';
+ * const unwatch = await watchSensor('accelerometer', 100, (reading) => {
+ *   console.log(reading.x, reading.y, reading.z);
+ * });
+ * // stop the sensor
+ * await unwatch();
+ * ```
+ *
+ * @returns A function stopping the sensor.
+ *
+ * @since 2.0.0
+ */
+async function watchSensor(
+  sensor: Sensor,
+  interval: number,
+  handler: (reading: SensorReading) => void
+): Promise<() => Promise<void>> {
+  const onReading = new Channel<SensorReading>()
+  onReading.onmessage = handler
+  const id = await invoke<number>('plugin:sensors|watch', {
+    sensor,
+    interval,
+    onReading
+  })
+  return async () => invoke('plugin:sensors|unwatch', { id })
+}
+
+export type { Sensor, SensorReading }
+
+export { isSensorAvailable, watchSensor }
@@ -0,0 +1,148 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Open the serial ports, e.g. the USB to UART adapters and the microcontroller boards.
+ *
+ * This package is also accessible with `window.__TAURI__.serial` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.serial.enable`](https://tauri.app/v1/api/config/#serialconfig.enable) in `tauri.conf.json`,
+ * and only the ports matching [`tauri.security.serial.scope`](https://tauri.app/v1/api/config/#serialconfig.scope) are listed and opened.
+ * @module
+ */
+
+import { Channel, invoke } from './tauri'
+
+/**
+ * A serial port of the computer.
+ *
+ * @since 2.0.0
+ */
+interface SerialPortInfo {
+  /** The path the port is opened with, e.g. `/dev/ttyUSB0` or `COM3`. */
+  path: string
+  /** The USB vendor identifier of a USB port. */
+  vendorId: number | null
+  /** The USB product identifier of a USB port. */
+  productId: number | null
+  /** The product name of a USB port. */
+  productName: string | null
+  /** The serial number of a USB port. */
+  serialNumber: string | null
+}
+
+/**
+ * The settings a serial port is opened with, `9600` bauds and 8N1 by default.
+ *
+ * @since 2.0.0
+ */
+interface SerialOptions {
+  baudRate?: number
+  /** The number of data bits of the characters, from 5 to 8. */
+  dataBits?: 5 | 6 | 7 | 8
+  parity?: 'none' | 'odd' | 'even'
+  stopBits?: 'one' | 'two'
+  flowControl?: 'none' | 'hardware'
+  /** How often the port checks whether it was closed while waiting for data, in milliseconds. */
+  timeout?: number
+}
+
+/**
+ * An event of an open port.
+ *
+ * @since 2.0.0
+ */
+type SerialEvent =
+  | { type: 'data'; data: Uint8Array }
+  | {
+      /** The port was unplugged, it is closed. */
+      type: 'disconnected'
+    }
+
+/**
+ * Lists the serial ports of the scope.
+ * @example
+ * ```typescript
+ * import { availablePorts } from '@tauri-apps/api/serial';
+ * const ports = await availablePorts();
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function availablePorts(): Promise<SerialPortInfo[]> {
+  return invoke('plugin:serial|available')
+}
+
+/**
+ * An open serial port.
+ *
+ * @since 2.0.0
+ */
+class SerialPort {
+  /** The path of the port. */
+  readonly path: string
+  /** @ignore */
+  readonly #id: number
+
+  /** @ignore */
+  constructor(path: string, id: number) {
+    this.path = path
+    this.#id = id
+  }
+
+  /**
+   * Opens the port with exclusive access.
+   * @example
+   * ```typescript
+   * import { SerialPort } from '@tauri-apps/api/serial';
+   * const port = await SerialPort.open('/dev/ttyUSB0', { baudRate: 115200 }, (event) => {
+   *   if (event.type === 'data') {
+   *     console.log(new TextDecoder().decode(event.data));
+   *   }
+   * });
+   * await port.write(new TextEncoder().encode('AT\r\n'));
+   * ```
+   *
+   * @param handler Receives the data of the port and its disconnection.
+   */
+  static async open(
+    path: string,
+    options: SerialOptions,
+    handler: (event: SerialEvent) => void
+  ): Promise<SerialPort> {
+    const onEvent = new Channel<
+      { type: 'data'; data: number[] } | { type: 'disconnected' }
+    >()
+    onEvent.onmessage = (event) => {
+      handler(
+        event.type === 'data'
+          ? { type: 'data', data: Uint8Array.from(event.data) }
+          : event
+      )
+    }
+    const id = await invoke<number>('plugin:serial|open', {
+      path,
+      options,
+      onEvent
+    })
+    return new SerialPort(path, id)
+  }
+
+  /** Writes the data to the port. */
+  async write(data: Uint8Array | number[]): Promise<void> {
+    return invoke('plugin:serial|write', {
+      id: this.#id,
+      data: Array.from(data)
+    })
+  }
+
+  /** Closes the port. */
+  async close(): Promise<void> {
+    return invoke('plugin:serial|close', { id: this.#id })
+  }
+}
+
+export type { SerialPortInfo, SerialOptions, SerialEvent }
+
+export { availablePorts, SerialPort }
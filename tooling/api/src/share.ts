@@ -0,0 +1,40 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Share texts, links and files with the other applications through the share sheet of the operating system.
+ *
+ * This package is also accessible with `window.__TAURI__.share` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.share.enable`](https://tauri.app/v1/api/config/#shareconfig.enable) in `tauri.conf.json`,
+ * and only the files matching [`tauri.security.share.scope`](https://tauri.app/v1/api/config/#shareconfig.scope) are shared.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * An item of the share sheet: a text, a link or the path of a file.
+ *
+ * @since 2.0.0
+ */
+type ShareItem = { text: string } | { url: string } | { file: string }
+
+/**
+ * Shows the share sheet of the current window, resolving once it is shown.
+ * @example
+ * ```typescript
+ * import { share } from '@tauri-apps/api/share';
+ * await share([{ text: 'Built with Tauri' }, { url: 'https://tauri.app' }]);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function share(items: ShareItem[]): Promise<void> {
+  return invoke('plugin:share|share', { items })
+}
+
+export type { ShareItem }
+
+export { share }
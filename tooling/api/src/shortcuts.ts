@@ -0,0 +1,82 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Set the shortcuts of the app: the app shortcuts on Android, the Home Screen quick actions on iOS and the tasks of the jump list on Windows.
+ *
+ * The static shortcuts, available before the app first runs, are configured in [`tauri.bundle.shortcuts`](https://tauri.app/v1/api/config/#bundleconfig.shortcuts).
+ *
+ * This package is also accessible with `window.__TAURI__.shortcuts` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+import { listen, TauriEvent, UnlistenFn } from './event'
+
+/**
+ * A shortcut of the app.
+ *
+ * @since 2.0.0
+ */
+interface Shortcut {
+  /** The identifier of the shortcut, received by the app when it is activated. */
+  id: string
+  /** The title of the shortcut. */
+  title: string
+  /** The long label on Android, shown when there is enough space, the subtitle on iOS or the tooltip on Windows. */
+  description?: string
+  /** The name of a drawable resource on Android, the name of an SF Symbol on iOS, or the path of an `.ico` file on Windows. */
+  icon?: string
+}
+
+/**
+ * Replaces the shortcuts of the app.
+ * @example
+ * ```typescript
+ * import { setShortcuts } from '@tauri-apps/api/shortcuts';
+ * await setShortcuts([{ id: 'compose', title: 'New note', icon: 'square.and.pencil' }]);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setShortcuts(shortcuts: Shortcut[]): Promise<void> {
+  return invoke('plugin:shortcuts|set_shortcuts', { shortcuts })
+}
+
+/**
+ * Gets the identifier of the shortcut activated to launch the app.
+ *
+ * @returns A promise resolving to the identifier, or `null` if the app was not launched by a shortcut.
+ *
+ * @since 2.0.0
+ */
+async function getLaunchShortcut(): Promise<string | null> {
+  return invoke('plugin:shortcuts|launch_shortcut')
+}
+
+/**
+ * Listens to the activated shortcuts.
+ * @example
+ * ```typescript
+ * import { onShortcutActivated } from '@tauri-apps/api/shortcuts';
+ * const unlisten = await onShortcutActivated((id) => {
+ *   if (id === 'compose') router.push('/notes/new');
+ * });
+ * ```
+ *
+ * @returns A promise resolving to a function to stop listening.
+ *
+ * @since 2.0.0
+ */
+async function onShortcutActivated(
+  handler: (id: string) => void
+): Promise<UnlistenFn> {
+  return listen<string>(TauriEvent.SHORTCUT, ({ payload }) => {
+    handler(payload)
+  })
+}
+
+export type { Shortcut }
+
+export { setShortcuts, getLaunchShortcut, onShortcutActivated }
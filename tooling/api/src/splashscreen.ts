@@ -0,0 +1,29 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Close the splashscreen configured on [`tauri.splashscreen`](https://tauri.app/v1/api/config/#tauriconfig.splashscreen).
+ *
+ * This package is also accessible with `window.__TAURI__.splashscreen` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * Closes the splashscreen and shows the windows it hid, once the splashscreen has been shown for its minimum display time.
+ * Does nothing if the app has no splashscreen or if it is already closed.
+ * @example
+ * ```typescript
+ * import { closeSplashscreen } from '@tauri-apps/api/splashscreen';
+ * window.addEventListener('load', () => closeSplashscreen());
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function closeSplashscreen(): Promise<void> {
+  return invoke('plugin:splashscreen|close')
+}
+
+export { closeSplashscreen }
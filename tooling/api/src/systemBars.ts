@@ -0,0 +1,105 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Customize the status and navigation bars on Android and iOS, the functions do nothing on desktop.
+ *
+ * Their initial appearance is configured with [`tauri.bundle.systemBars`](https://tauri.app/v1/api/config/#systembarsconfig) in `tauri.conf.json`.
+ *
+ * This package is also accessible with `window.__TAURI__.systemBars` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * The style of the icons of a system bar: `lightContent` for dark backgrounds, `darkContent` for light backgrounds,
+ * or `default` to follow the system theme.
+ *
+ * @since 2.0.0
+ */
+type SystemBarStyle = 'default' | 'lightContent' | 'darkContent'
+
+/**
+ * The appearance of a system bar, the unset properties are left unchanged.
+ *
+ * @since 2.0.0
+ */
+interface SystemBar {
+  /** The background color, as `#RRGGBB` or `#RRGGBBAA`. */
+  color?: string
+  style?: SystemBarStyle
+}
+
+/**
+ * The distances from the edges of the webview to its area not covered by the system bars, the display cutout
+ * and the rounded corners, in logical pixels.
+ *
+ * @since 2.0.0
+ */
+interface SafeAreaInsets {
+  top: number
+  right: number
+  bottom: number
+  left: number
+}
+
+/**
+ * Sets the appearance of the status bar. On iOS, the color is drawn behind the transparent status bar.
+ * @example
+ * ```typescript
+ * import { setStatusBar } from '@tauri-apps/api/systemBars';
+ * await setStatusBar({ color: '#000000', style: 'lightContent' });
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setStatusBar(bar: SystemBar): Promise<void> {
+  return invoke('plugin:system-bars|set_status_bar', { bar })
+}
+
+/**
+ * Sets the appearance of the navigation bar on Android, the style requires Android 8.1.
+ * @example
+ * ```typescript
+ * import { setNavigationBar } from '@tauri-apps/api/systemBars';
+ * await setNavigationBar({ color: '#ffffff', style: 'darkContent' });
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setNavigationBar(bar: SystemBar): Promise<void> {
+  return invoke('plugin:system-bars|set_navigation_bar', { bar })
+}
+
+/**
+ * Lays out the webview behind the system bars, using the whole screen.
+ * The content must then be padded with the {@link safeAreaInsets}.
+ * @example
+ * ```typescript
+ * import { setEdgeToEdge, safeAreaInsets } from '@tauri-apps/api/systemBars';
+ * await setEdgeToEdge(true);
+ * const insets = await safeAreaInsets();
+ * document.body.style.paddingTop = `${insets.top}px`;
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setEdgeToEdge(enable: boolean): Promise<void> {
+  return invoke('plugin:system-bars|set_edge_to_edge', { enable })
+}
+
+/**
+ * Gets the insets of the webview area not covered by the system bars, the display cutout and the rounded corners.
+ * They change when the device rotates.
+ *
+ * @since 2.0.0
+ */
+async function safeAreaInsets(): Promise<SafeAreaInsets> {
+  return invoke('plugin:system-bars|safe_area_insets')
+}
+
+export type { SystemBarStyle, SystemBar, SafeAreaInsets }
+
+export { setStatusBar, setNavigationBar, setEdgeToEdge, safeAreaInsets }
@@ -127,35 +127,76 @@ async function addPluginListener<T>(
  */
 type InvokeArgs = Record<string, unknown>
 
+/**
+ * Options of an {@link invoke} call.
+ *
+ * @since 2.0.0
+ */
+interface InvokeOptions {
+  /**
+   * Cancels the invoke when aborted: the promise is rejected with the abort reason
+   * and the `CancellationToken` argument of the command is cancelled.
+   */
+  signal?: AbortSignal
+}
+
 /**
  * Sends a message to the backend.
  * @example
  * ```typescript
  * import { invoke } from '@tauri-apps/api/tauri';
  * await invoke('login', { user: 'tauri', password: 'poiwe3h4r5ip3yrhtew9ty' });
+ *
+ * const controller = new AbortController();
+ * invoke('index_files', {}, { signal: controller.signal });
+ * controller.abort();
  * ```
  *
  * @param cmd The command name.
  * @param args The optional arguments to pass to the command.
+ * @param options The invoke options, since 2.0.0.
  * @return A promise resolving or rejecting to the backend response.
  *
  * @since 1.0.0
  */
-async function invoke<T>(cmd: string, args: InvokeArgs = {}): Promise<T> {
+async function invoke<T>(
+  cmd: string,
+  args: InvokeArgs = {},
+  options?: InvokeOptions
+): Promise<T> {
   return new Promise((resolve, reject) => {
+    const signal = options?.signal
+    if (signal?.aborted) {
+      reject(signal.reason)
+      return
+    }
+
+    const cancellationId = signal ? uid() : undefined
+    const onAbort = (): void => {
+      Reflect.deleteProperty(window, `_${callback}`)
+      Reflect.deleteProperty(window, `_${error}`)
+      reject(signal?.reason)
+      void invoke('plugin:ipc|cancel', { id: cancellationId })
+    }
     const callback = transformCallback((e: T) => {
+      signal?.removeEventListener('abort', onAbort)
       resolve(e)
       Reflect.deleteProperty(window, `_${error}`)
     }, true)
     const error = transformCallback((e) => {
+      signal?.removeEventListener('abort', onAbort)
       reject(e)
       Reflect.deleteProperty(window, `_${callback}`)
     }, true)
+    signal?.addEventListener('abort', onAbort, { once: true })
 
     window.__TAURI_IPC__({
       cmd,
       callback,
       error,
+      ...(cancellationId === undefined
+        ? {}
+        : { __TAURI_CANCELLATION_ID__: cancellationId }),
       ...args
     })
   })
@@ -198,7 +239,36 @@ function convertFileSrc(filePath: string, protocol = 'asset'): string {
     : `${protocol}://localhost/${path}`
 }
 
-export type { InvokeArgs }
+/**
+ * Reads a buffer shared by the Rust code, from the URL returned by a command.
+ * @example
+ * ```typescript
+ * import { invoke, readSharedBuffer } from '@tauri-apps/api/tauri';
+ * const url = await invoke<string>('thumbnail');
+ * const pixels = new Uint8ClampedArray(await readSharedBuffer(url));
+ * ```
+ *
+ * @param url The URL of the buffer.
+ * @param range The byte range to read, the end is inclusive.
+ *
+ * @since 2.0.0
+ */
+async function readSharedBuffer(
+  url: string,
+  range?: { start: number; end: number }
+): Promise<ArrayBuffer> {
+  const response = await fetch(url, {
+    headers: range ? { Range: `bytes=${range.start}-${range.end}` } : {}
+  })
+  if (!response.ok) {
+    throw new Error(
+      `the shared buffer ${url} is not available (${response.status})`
+    )
+  }
+  return response.arrayBuffer()
+}
+
+export type { InvokeArgs, InvokeOptions }
 
 export {
   transformCallback,
@@ -206,5 +276,6 @@ export {
   PluginListener,
   addPluginListener,
   invoke,
-  convertFileSrc
+  convertFileSrc,
+  readSharedBuffer
 }
@@ -0,0 +1,73 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Lay out a custom title bar around the window controls drawn over the webview.
+ *
+ * The windows created with the `Overlay` title bar style and the [`titleBarOverlay`](https://tauri.app/v1/api/config/#windowconfig.titlebaroverlay)
+ * configuration draw the native window controls over the webview. Their bounding rectangle is also available
+ * in the `--tauri-title-bar-overlay-x`, `-y`, `-width` and `-height` CSS variables of the document.
+ *
+ * This package is also accessible with `window.__TAURI__.titleBar` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * The bounding rectangle of the window controls, in logical pixels from the top-left corner of the webview.
+ *
+ * @since 2.0.0
+ */
+interface TitleBarOverlayRect {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+
+/**
+ * Gets the bounding rectangle of the window controls drawn over the webview,
+ * or `null` if the window does not use the overlay title bar.
+ * @example
+ * ```typescript
+ * import { overlayRect } from '@tauri-apps/api/titleBar';
+ * const rect = await overlayRect();
+ * if (rect) {
+ *   document.querySelector('header').style.paddingLeft = `${rect.x + rect.width}px`;
+ * }
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function overlayRect(): Promise<TitleBarOverlayRect | null> {
+  return invoke('plugin:title-bar|overlay_rect')
+}
+
+/**
+ * Listens to the changes of the bounding rectangle of the window controls,
+ * e.g. when the window leaves the fullscreen mode.
+ * @example
+ * ```typescript
+ * import { onOverlayRectChanged } from '@tauri-apps/api/titleBar';
+ * const unlisten = onOverlayRectChanged((rect) => console.log(rect));
+ * ```
+ *
+ * @returns A function to remove the handler.
+ *
+ * @since 2.0.0
+ */
+function onOverlayRectChanged(
+  handler: (rect: TitleBarOverlayRect | null) => void
+): () => void {
+  const listener = (event: Event): void => {
+    handler((event as CustomEvent<TitleBarOverlayRect | null>).detail)
+  }
+  window.addEventListener('tauri://title-bar-overlay', listener)
+  return () => window.removeEventListener('tauri://title-bar-overlay', listener)
+}
+
+export type { TitleBarOverlayRect }
+
+export { overlayRect, onOverlayRectChanged }
@@ -0,0 +1,212 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Open the USB devices and transfer data with their endpoints, e.g. to flash the firmware of a board, since WebUSB is not available in the webviews.
+ *
+ * This package is also accessible with `window.__TAURI__.usb` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * The APIs must be enabled with [`tauri.security.usb.enable`](https://tauri.app/v1/api/config/#usbconfig.enable) in `tauri.conf.json`,
+ * and only the devices matching [`tauri.security.usb.scope`](https://tauri.app/v1/api/config/#usbconfig.scope) are listed and opened.
+ *
+ * The transfers time out after 5 seconds by default, a `0` timeout waits forever.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * A USB device connected to the computer.
+ *
+ * @since 2.0.0
+ */
+interface UsbDeviceInfo {
+  /** The path the device is opened with. */
+  path: string
+  vendorId: number
+  productId: number
+  /** The product name reported by the device. */
+  productName: string | null
+  /** The serial number reported by the device. */
+  serialNumber: string | null
+}
+
+/**
+ * The setup of a control transfer, as in WebUSB.
+ *
+ * @since 2.0.0
+ */
+interface ControlTransferParameters {
+  requestType: 'standard' | 'class' | 'vendor'
+  recipient: 'device' | 'interface' | 'endpoint' | 'other'
+  request: number
+  value: number
+  index: number
+}
+
+/**
+ * @since 2.0.0
+ */
+interface TransferOptions {
+  /** How long the transfer waits for the device, in milliseconds. */
+  timeout?: number
+}
+
+/**
+ * Lists the USB devices of the scope.
+ * @example
+ * ```typescript
+ * import { devices } from '@tauri-apps/api/usb';
+ * const boards = (await devices()).filter((d) => d.vendorId === 0x2341);
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function devices(): Promise<UsbDeviceInfo[]> {
+  return invoke('plugin:usb|devices')
+}
+
+/** Encodes the type and the recipient of the setup in `bmRequestType`, the transfer sets the direction. */
+function setupPacket(setup: ControlTransferParameters): {
+  requestType: number
+  request: number
+  value: number
+  index: number
+} {
+  const types = ['standard', 'class', 'vendor']
+  const recipients = ['device', 'interface', 'endpoint', 'other']
+  return {
+    requestType:
+      (types.indexOf(setup.requestType) << 5) |
+      recipients.indexOf(setup.recipient),
+    request: setup.request,
+    value: setup.value,
+    index: setup.index
+  }
+}
+
+/**
+ * An open USB device.
+ *
+ * @since 2.0.0
+ */
+class UsbDevice {
+  /** The path of the device. */
+  readonly path: string
+  /** @ignore */
+  readonly #id: number
+
+  /** @ignore */
+  constructor(path: string, id: number) {
+    this.path = path
+    this.#id = id
+  }
+
+  /**
+   * Opens a device listed by {@link devices}.
+   * @example
+   * ```typescript
+   * import { devices, UsbDevice } from '@tauri-apps/api/usb';
+   * const [board] = await devices();
+   * const device = await UsbDevice.open(board.path);
+   * await device.claimInterface(0);
+   * await device.transferOut(0x01, firmwareChunk);
+   * const status = await device.transferIn(0x81, 64);
+   * await device.close();
+   * ```
+   */
+  static async open(path: string): Promise<UsbDevice> {
+    const id = await invoke<number>('plugin:usb|open', { path })
+    return new UsbDevice(path, id)
+  }
+
+  /** Claims an interface, which must be done before transferring data with its endpoints. */
+  async claimInterface(interfaceNumber: number): Promise<void> {
+    return invoke('plugin:usb|claim_interface', {
+      id: this.#id,
+      interface: interfaceNumber
+    })
+  }
+
+  /** Releases a claimed interface. */
+  async releaseInterface(interfaceNumber: number): Promise<void> {
+    return invoke('plugin:usb|release_interface', {
+      id: this.#id,
+      interface: interfaceNumber
+    })
+  }
+
+  /**
+   * Reads at most `length` bytes from a bulk or interrupt IN endpoint, e.g. `0x81`.
+   */
+  async transferIn(
+    endpoint: number,
+    length: number,
+    options: TransferOptions = {}
+  ): Promise<Uint8Array> {
+    return Uint8Array.from(
+      await invoke<number[]>('plugin:usb|transfer_in', {
+        id: this.#id,
+        endpoint,
+        length,
+        timeout: options.timeout
+      })
+    )
+  }
+
+  /**
+   * Writes the data to a bulk or interrupt OUT endpoint, e.g. `0x01`, and returns the number of bytes written.
+   */
+  async transferOut(
+    endpoint: number,
+    data: Uint8Array | number[],
+    options: TransferOptions = {}
+  ): Promise<number> {
+    return invoke('plugin:usb|transfer_out', {
+      id: this.#id,
+      endpoint,
+      data: Array.from(data),
+      timeout: options.timeout
+    })
+  }
+
+  /** Sends a control request and reads at most `length` bytes of its response. */
+  async controlTransferIn(
+    setup: ControlTransferParameters,
+    length: number,
+    options: TransferOptions = {}
+  ): Promise<Uint8Array> {
+    return Uint8Array.from(
+      await invoke<number[]>('plugin:usb|control_transfer_in', {
+        id: this.#id,
+        setup: setupPacket(setup),
+        length,
+        timeout: options.timeout
+      })
+    )
+  }
+
+  /** Sends a control request with the data and returns the number of bytes written. */
+  async controlTransferOut(
+    setup: ControlTransferParameters,
+    data: Uint8Array | number[] = [],
+    options: TransferOptions = {}
+  ): Promise<number> {
+    return invoke('plugin:usb|control_transfer_out', {
+      id: this.#id,
+      setup: setupPacket(setup),
+      data: Array.from(data),
+      timeout: options.timeout
+    })
+  }
+
+  /** Closes the device. */
+  async close(): Promise<void> {
+    return invoke('plugin:usb|close', { id: this.#id })
+  }
+}
+
+export type { UsbDeviceInfo, ControlTransferParameters, TransferOptions }
+
+export { devices, UsbDevice }
@@ -0,0 +1,50 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Write the data displayed by the home screen widgets generated with `tauri android add-module widget` and `tauri ios add-extension widget`.
+ *
+ * The data is serialized as JSON to a container shared with the widgets: the `tauri_widgets` shared preferences on Android,
+ * and the user defaults of the `group.<identifier>` App Group on iOS 14 and later.
+ *
+ * This package is also accessible with `window.__TAURI__.widgets` when [`build.withGlobalTauri`](https://tauri.app/v1/api/config/#buildconfig.withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './tauri'
+
+/**
+ * Writes the data stored with the key. The widgets display it once {@link reloadWidgets | reloaded}.
+ * @example
+ * ```typescript
+ * import { setWidgetData, reloadWidgets } from '@tauri-apps/api/widgets';
+ * await setWidgetData('widget', { title: '3 tasks left' });
+ * await reloadWidgets();
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function setWidgetData(key: string, data: unknown): Promise<void> {
+  return invoke('plugin:widgets|set_data', { key, data })
+}
+
+/**
+ * Removes the data stored with the key.
+ *
+ * @since 2.0.0
+ */
+async function removeWidgetData(key: string): Promise<void> {
+  return invoke('plugin:widgets|remove_data', { key })
+}
+
+/**
+ * Reloads the widgets of the app, which read their data again.
+ *
+ * @since 2.0.0
+ */
+async function reloadWidgets(): Promise<void> {
+  return invoke('plugin:widgets|reload')
+}
+
+export { setWidgetData, removeWidgetData, reloadWidgets }
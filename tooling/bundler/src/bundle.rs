@@ -5,6 +5,7 @@
 
 mod category;
 mod common;
+mod fingerprint;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
@@ -20,8 +21,8 @@ use tauri_utils::display_path;
 pub use self::{
   category::AppCategory,
   settings::{
-    BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings, PackageType,
-    Settings, SettingsBuilder, UpdaterSettings,
+    BundleBinary, BundleSettings, DebianSettings, FlatpakSettings, MacOsSettings, PackageSettings,
+    PackageType, RpmSettings, Settings, SettingsBuilder, SnapSettings, UpdaterSettings,
   },
 };
 #[cfg(target_os = "macos")]
@@ -42,12 +43,27 @@ pub struct Bundle {
 
 /// Bundles the project.
 /// Returns the list of paths where the bundles can be found.
+///
+/// The bundles whose inputs did not change since they were last created are skipped,
+/// unless [`Settings::force_bundle`] is set.
 pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
   let package_types = settings.package_types()?;
   if package_types.is_empty() {
     return Ok(Vec::new());
   }
 
+  let mut fingerprints = fingerprint::Fingerprints::load(&settings);
+  let up_to_date = package_types
+    .iter()
+    .map(|package_type| {
+      if settings.force_bundle() {
+        return None;
+      }
+      let fingerprint = fingerprint::fingerprint(&settings, *package_type)?;
+      fingerprints.up_to_date(*package_type, &fingerprint)
+    })
+    .collect::<Vec<_>>();
+
   let mut bundles: Vec<Bundle> = Vec::new();
 
   let target_os = settings
@@ -61,12 +77,59 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
     warn!("Cross-platform compilation is experimental and does not support all features. Please use a matching host system for full compatibility.");
   }
 
-  for package_type in &package_types {
+  // the Linux bundles only read the settings and write to their own folder, so they are created in parallel
+  #[cfg(target_os = "linux")]
+  let mut linux_bundles = {
+    let pending = package_types
+      .iter()
+      .zip(&up_to_date)
+      .filter(|(package_type, up_to_date)| {
+        linux::is_package_type(**package_type) && up_to_date.is_none()
+      })
+      .map(|(package_type, _)| *package_type)
+      .collect::<Vec<_>>();
+    let settings = &settings;
+    std::thread::scope(|scope| {
+      pending
+        .into_iter()
+        .map(|package_type| {
+          (
+            package_type,
+            scope.spawn(move || linux::bundle_project(settings, package_type)),
+          )
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|(package_type, handle)| {
+          (
+            package_type,
+            handle.join().expect("failed to join the bundler thread"),
+          )
+        })
+        .collect::<Vec<_>>()
+    })
+  };
+
+  let mut created_bundle = false;
+  for (package_type, up_to_date) in package_types.iter().zip(up_to_date) {
     // bundle was already built! e.g. DMG already built .app
     if bundles.iter().any(|b| b.package_type == *package_type) {
       continue;
     }
 
+    // the dmg and updater bundles are created from the other bundles
+    let up_to_date = up_to_date.filter(|_| {
+      !(created_bundle && matches!(package_type, PackageType::Dmg | PackageType::Updater))
+    });
+    if let Some(bundle_paths) = up_to_date {
+      info!(action = "Skipping"; "{} bundle, its inputs did not change (use `--force-bundle` to recreate it)", package_type.short_name());
+      bundles.push(Bundle {
+        package_type: package_type.to_owned(),
+        bundle_paths,
+      });
+      continue;
+    }
+
     let bundle_paths = match package_type {
       #[cfg(target_os = "macos")]
       PackageType::MacOsBundle => macos::app::bundle_project(&settings)?,
@@ -88,13 +151,20 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
       #[cfg(target_os = "windows")]
       PackageType::WindowsMsi => windows::msi::bundle_project(&settings, false)?,
       PackageType::Nsis => windows::nsis::bundle_project(&settings, false)?,
+      PackageType::Portable => windows::portable::bundle_project(&settings)?,
 
       #[cfg(target_os = "linux")]
-      PackageType::Deb => linux::debian::bundle_project(&settings)?,
-      #[cfg(target_os = "linux")]
-      PackageType::Rpm => linux::rpm::bundle_project(&settings)?,
-      #[cfg(target_os = "linux")]
-      PackageType::AppImage => linux::appimage::bundle_project(&settings)?,
+      PackageType::Deb
+      | PackageType::Rpm
+      | PackageType::AppImage
+      | PackageType::Flatpak
+      | PackageType::Snap => {
+        let i = linux_bundles
+          .iter()
+          .position(|(t, _)| t == package_type)
+          .unwrap();
+        linux_bundles.remove(i).1?
+      }
 
       // updater is dependant of multiple bundle, we send our bundles to prevent rebuilding
       PackageType::Updater => {
@@ -118,6 +188,7 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
       }
     };
 
+    created_bundle = true;
     bundles.push(Bundle {
       package_type: package_type.to_owned(),
       bundle_paths,
@@ -151,6 +222,21 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
     }
   }
 
+  // fingerprinted after bundling since the binaries can be signed in place
+  for bundle in &bundles {
+    match fingerprint::fingerprint(&settings, bundle.package_type) {
+      Some(fingerprint) => fingerprints.insert(
+        bundle.package_type,
+        fingerprint,
+        bundle.bundle_paths.clone(),
+      ),
+      None => fingerprints.remove(bundle.package_type),
+    }
+  }
+  if let Err(e) = fingerprints.save(&settings) {
+    warn!("Failed to save the bundle fingerprints: {e}");
+  }
+
   if !bundles.is_empty() {
     let bundles_wo_updater = bundles
       .iter()
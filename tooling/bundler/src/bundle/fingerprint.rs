@@ -0,0 +1,125 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Fingerprints of the bundle inputs, used to skip the bundles that are up to date.
+//!
+//! A fingerprint hashes the settings and the size and modification time of the bundled files:
+//! the binaries, icons, resources, external binaries and the custom Linux package files.
+
+use super::{PackageType, Settings};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use std::{
+  collections::HashMap,
+  fs::{create_dir_all, metadata, read, write},
+  path::PathBuf,
+  time::UNIX_EPOCH,
+};
+
+/// The file storing the fingerprints, relative to the project output directory.
+const FINGERPRINTS_FILE: &str = "bundle/.fingerprints.json";
+
+#[derive(Serialize, Deserialize)]
+struct BundleFingerprint {
+  fingerprint: String,
+  bundle_paths: Vec<PathBuf>,
+}
+
+/// The fingerprints of the bundles created by the previous runs, keyed by their package type.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Fingerprints(HashMap<String, BundleFingerprint>);
+
+impl Fingerprints {
+  pub fn load(settings: &Settings) -> Self {
+    read(settings.project_out_directory().join(FINGERPRINTS_FILE))
+      .ok()
+      .and_then(|f| serde_json::from_slice(&f).ok())
+      .unwrap_or_default()
+  }
+
+  pub fn save(&self, settings: &Settings) -> crate::Result<()> {
+    let path = settings.project_out_directory().join(FINGERPRINTS_FILE);
+    create_dir_all(path.parent().unwrap())?;
+    write(path, serde_json::to_vec_pretty(self)?)?;
+    Ok(())
+  }
+
+  /// The paths of the bundle if it was created with the same fingerprint and still exists.
+  pub fn up_to_date(&self, package_type: PackageType, fingerprint: &str) -> Option<Vec<PathBuf>> {
+    self
+      .0
+      .get(package_type.short_name())
+      .filter(|b| b.fingerprint == fingerprint && b.bundle_paths.iter().all(|p| p.exists()))
+      .map(|b| b.bundle_paths.clone())
+  }
+
+  pub fn insert(
+    &mut self,
+    package_type: PackageType,
+    fingerprint: String,
+    bundle_paths: Vec<PathBuf>,
+  ) {
+    self.0.insert(
+      package_type.short_name().into(),
+      BundleFingerprint {
+        fingerprint,
+        bundle_paths,
+      },
+    );
+  }
+
+  pub fn remove(&mut self, package_type: PackageType) {
+    self.0.remove(package_type.short_name());
+  }
+}
+
+/// Computes the fingerprint of a bundle, or `None` if a bundled file can't be read.
+pub fn fingerprint(settings: &Settings, package_type: PackageType) -> Option<String> {
+  let mut hasher = Sha256::new();
+  hasher.update(package_type.short_name());
+
+  // the Debug output lists the maps in a random order, so its lines are sorted
+  let settings_debug = settings.fingerprint_settings();
+  let mut lines = settings_debug.lines().collect::<Vec<_>>();
+  lines.sort_unstable();
+  for line in lines {
+    hasher.update(line);
+    hasher.update("\n");
+  }
+
+  let mut files = settings
+    .binaries()
+    .iter()
+    .map(|binary| settings.binary_path(binary))
+    .collect::<Vec<_>>();
+  for file in settings
+    .icon_files()
+    .chain(settings.resource_files())
+    .chain(settings.external_binaries())
+  {
+    files.push(file.ok()?);
+  }
+  files.extend(settings.deb().files.values().cloned());
+  files.extend(settings.deb().desktop_template.clone());
+  files.extend(settings.rpm().files.values().cloned());
+  files.extend(settings.external_assets().cloned());
+  files.sort();
+
+  for file in files {
+    let metadata = metadata(&file).ok()?;
+    let modified = metadata
+      .modified()
+      .ok()?
+      .duration_since(UNIX_EPOCH)
+      .ok()?
+      .as_nanos();
+    hasher.update(file.to_string_lossy().as_bytes());
+    hasher.update(metadata.len().to_le_bytes());
+    hasher.update(modified.to_le_bytes());
+  }
+
+  Some(hex::encode(hasher.finalize()))
+}
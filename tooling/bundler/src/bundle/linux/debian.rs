@@ -35,7 +35,7 @@ use serde::Serialize;
 use walkdir::WalkDir;
 
 use std::{
-  collections::BTreeSet,
+  collections::{BTreeMap, BTreeSet, HashMap},
   ffi::OsStr,
   fs::{self, read_to_string, File},
   io::{self, Write},
@@ -81,7 +81,8 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   let (data_dir, _) = generate_data(settings, &package_dir)
     .with_context(|| "Failed to build data folders and files")?;
-  copy_custom_files(settings, &data_dir).with_context(|| "Failed to copy custom files")?;
+  copy_custom_files(&settings.deb().files, &data_dir)
+    .with_context(|| "Failed to copy custom files")?;
 
   // Generate control files.
   let control_dir = package_dir.join("control");
@@ -167,6 +168,21 @@ fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<
     exec: &'a str,
     icon: &'a str,
     name: &'a str,
+    localized_comments: BTreeMap<String, &'a str>,
+    localized_names: BTreeMap<String, &'a str>,
+  }
+
+  // desktop entries use the `lang_COUNTRY` locale format
+  let mut localized_comments = BTreeMap::new();
+  let mut localized_names = BTreeMap::new();
+  for (locale, localization) in settings.localizations() {
+    let locale = locale.replace('-', "_");
+    if let Some(comment) = &localization.short_description {
+      localized_comments.insert(locale.clone(), comment.as_str());
+    }
+    if let Some(name) = &localization.product_name {
+      localized_names.insert(locale, name.as_str());
+    }
   }
 
   handlebars.render_to_write(
@@ -184,6 +200,8 @@ fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<
       exec: bin_name,
       icon: bin_name,
       name: settings.product_name(),
+      localized_comments,
+      localized_names,
     },
     file,
   )?;
@@ -272,9 +290,9 @@ fn copy_resource_files(settings: &Settings, data_dir: &Path) -> crate::Result<()
   settings.copy_resources(&resource_dir)
 }
 
-/// Copies user-defined files to the deb package.
-fn copy_custom_files(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
-  for (deb_path, path) in settings.deb().files.iter() {
+/// Copies user-defined files to the package `data_dir`.
+pub fn copy_custom_files(files: &HashMap<PathBuf, PathBuf>, data_dir: &Path) -> crate::Result<()> {
+  for (deb_path, path) in files.iter() {
     let deb_path = if deb_path.is_absolute() {
       deb_path.strip_prefix("/").unwrap()
     } else {
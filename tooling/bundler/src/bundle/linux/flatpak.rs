@@ -0,0 +1,183 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// A Flatpak bundle is built from a manifest consumed by `flatpak-builder`:
+//
+// bundle/flatpak/
+//     <identifier>.json          # The flatpak-builder manifest
+//     files/                     # The app files, later copied to /app inside the sandbox
+//         bin/foobar                                    # Binary executable file
+//         lib/foobar/...                                # Other resource files
+//         share/applications/<identifier>.desktop       # Desktop file
+//         share/icons/hicolor/.../<identifier>.png      # Icon files
+//     repo/                      # The OSTree repository the app is exported to
+//     foobar_1.2.3_x86_64.flatpak                       # Single-file bundle
+//
+// The application files are generated with the same structure used by the Debian package,
+// then renamed after the bundle identifier since Flatpak requires the desktop file and icons
+// to match the application ID.
+
+use super::{super::common::CommandExt, debian};
+use crate::Settings;
+use anyhow::Context;
+use log::info;
+use serde_json::json;
+use walkdir::WalkDir;
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+const DEFAULT_RUNTIME: &str = "org.gnome.Platform";
+const DEFAULT_RUNTIME_VERSION: &str = "44";
+const DEFAULT_SDK: &str = "org.gnome.Sdk";
+
+/// Permissions every Tauri app needs to display a window.
+const DEFAULT_FINISH_ARGS: &[&str] = &[
+  "--share=ipc",
+  "--socket=wayland",
+  "--socket=fallback-x11",
+  "--device=dri",
+];
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Flatpak bundle and its manifest were created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = settings.binary_arch();
+  let app_id = settings.bundle_identifier();
+  if app_id.is_empty() {
+    return Err(crate::Error::GenericError(
+      "the Flatpak bundle requires a bundle identifier".into(),
+    ));
+  }
+
+  let output_path = settings.project_out_directory().join("bundle/flatpak");
+  if output_path.exists() {
+    fs::remove_dir_all(&output_path).with_context(|| "Failed to remove old flatpak bundle")?;
+  }
+  fs::create_dir_all(&output_path)?;
+
+  let bundle_name = format!(
+    "{}_{}_{}.flatpak",
+    settings.main_binary_name(),
+    settings.version_string(),
+    arch
+  );
+  let bundle_path = output_path.join(&bundle_name);
+
+  info!(action = "Bundling"; "{} ({})", bundle_name, bundle_path.display());
+
+  // generate the app files using the debian package structure
+  let package_dir = settings.project_out_directory().join("bundle/flatpak_deb");
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  let files_dir = output_path.join("files");
+  fs::rename(data_dir.join("usr"), &files_dir)
+    .with_context(|| "Failed to move the app files to the flatpak folder")?;
+  fs::remove_dir_all(&package_dir)?;
+  rename_desktop_entry(settings, &files_dir, app_id)
+    .with_context(|| "Failed to rename the desktop entry")?;
+
+  let manifest_path = output_path.join(format!("{app_id}.json"));
+  let manifest = generate_manifest(settings, app_id);
+  fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+    .with_context(|| "Failed to write the flatpak manifest")?;
+
+  Command::new("flatpak-builder")
+    .arg("--force-clean")
+    .arg(format!("--arch={arch}"))
+    .arg("--repo=repo")
+    .arg("build")
+    .arg(&manifest_path)
+    .current_dir(&output_path)
+    .output_ok()
+    .context("error running flatpak-builder, make sure flatpak-builder and the runtime and SDK set on `tauri > bundle > flatpak` are installed")?;
+
+  Command::new("flatpak")
+    .arg("build-bundle")
+    .arg(format!("--arch={arch}"))
+    .arg("repo")
+    .arg(&bundle_path)
+    .arg(app_id)
+    .current_dir(&output_path)
+    .output_ok()
+    .context("error running flatpak build-bundle")?;
+
+  fs::remove_dir_all(output_path.join("build"))?;
+
+  Ok(vec![bundle_path, manifest_path])
+}
+
+/// Generates the flatpak-builder manifest.
+fn generate_manifest(settings: &Settings, app_id: &str) -> serde_json::Value {
+  let flatpak = settings.flatpak();
+
+  let mut finish_args: Vec<String> = DEFAULT_FINISH_ARGS.iter().map(|a| a.to_string()).collect();
+  for arg in &flatpak.finish_args {
+    if !finish_args.contains(arg) {
+      finish_args.push(arg.clone());
+    }
+  }
+
+  json!({
+    "app-id": app_id,
+    "runtime": flatpak.runtime.as_deref().unwrap_or(DEFAULT_RUNTIME),
+    "runtime-version": flatpak.runtime_version.as_deref().unwrap_or(DEFAULT_RUNTIME_VERSION),
+    "sdk": flatpak.sdk.as_deref().unwrap_or(DEFAULT_SDK),
+    "command": settings.main_binary_name(),
+    "finish-args": finish_args,
+    "modules": [{
+      "name": settings.main_binary_name(),
+      "buildsystem": "simple",
+      "build-commands": ["cp -a . /app/"],
+      "sources": [{
+        "type": "dir",
+        "path": "files"
+      }]
+    }]
+  })
+}
+
+/// Renames the desktop file and icons after the app identifier, as required by Flatpak.
+fn rename_desktop_entry(settings: &Settings, files_dir: &Path, app_id: &str) -> crate::Result<()> {
+  let bin_name = settings.main_binary_name();
+
+  let applications_dir = files_dir.join("share/applications");
+  let desktop_file = fs::read_to_string(applications_dir.join(format!("{bin_name}.desktop")))?;
+  let desktop_file = desktop_file
+    .lines()
+    .map(|line| {
+      if line.starts_with("Icon=") {
+        format!("Icon={app_id}")
+      } else {
+        line.to_string()
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+  fs::remove_file(applications_dir.join(format!("{bin_name}.desktop")))?;
+  fs::write(
+    applications_dir.join(format!("{app_id}.desktop")),
+    desktop_file + "\n",
+  )?;
+
+  let icon_name = format!("{bin_name}.png");
+  let icons = WalkDir::new(files_dir.join("share/icons"))
+    .into_iter()
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.into_path())
+    .filter(|path| {
+      path
+        .file_name()
+        .map_or(false, |name| name == icon_name.as_str())
+    })
+    .collect::<Vec<_>>();
+  for icon in icons {
+    fs::rename(&icon, icon.with_file_name(format!("{app_id}.png")))?;
+  }
+
+  Ok(())
+}
@@ -5,4 +5,36 @@
 
 pub mod appimage;
 pub mod debian;
+pub mod flatpak;
 pub mod rpm;
+pub mod snap;
+
+use super::{PackageType, Settings};
+use std::path::PathBuf;
+
+/// Whether the package type is a Linux bundle.
+pub fn is_package_type(package_type: PackageType) -> bool {
+  matches!(
+    package_type,
+    PackageType::Deb
+      | PackageType::Rpm
+      | PackageType::AppImage
+      | PackageType::Flatpak
+      | PackageType::Snap
+  )
+}
+
+/// Creates a Linux bundle.
+pub fn bundle_project(
+  settings: &Settings,
+  package_type: PackageType,
+) -> crate::Result<Vec<PathBuf>> {
+  match package_type {
+    PackageType::Deb => debian::bundle_project(settings),
+    PackageType::Rpm => rpm::bundle_project(settings),
+    PackageType::AppImage => appimage::bundle_project(settings),
+    PackageType::Flatpak => flatpak::bundle_project(settings),
+    PackageType::Snap => snap::bundle_project(settings),
+    _ => unreachable!("{} is not a Linux bundle", package_type.short_name()),
+  }
+}
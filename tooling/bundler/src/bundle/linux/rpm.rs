@@ -3,12 +3,695 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+// The structure of a RPM package looks something like this:
+//
+// foobar-1.2.3-1.x86_64.rpm
+//     lead                    # Legacy 96 bytes identification header
+//     signature header        # Digests and (optionally) the GPG signature of the header
+//     header                  # Package metadata, dependencies, scriptlets and file list
+//     payload                 # gzip compressed cpio (newc) archive with the files to install:
+//         ./usr/bin/foobar                            # Binary executable file
+//         ./usr/share/applications/foobar.desktop     # Desktop file (for apps)
+//         ./usr/share/icons/hicolor/...               # Icon files (for apps)
+//         ./usr/lib/foobar/...                        # Other resource files
+//
+// The data files are generated with the same structure used by the Debian package,
+// and the package is written directly without relying on `rpmbuild`.
+
+use super::{super::common::CommandExt, debian};
 use crate::Settings;
+use anyhow::Context;
+use libflate::gzip;
+use log::info;
+use sha2::Digest;
+use walkdir::WalkDir;
+
+use std::{
+  collections::BTreeMap,
+  fs::{self, File},
+  io::Write,
+  os::unix::fs::{MetadataExt, PermissionsExt},
+  path::{Path, PathBuf},
+  process::Command,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+const SIGTAG_SIZE: u32 = 1000;
+const SIGTAG_PAYLOADSIZE: u32 = 1007;
+const SIGTAG_RSA: u32 = 268;
+const SIGTAG_SHA1: u32 = 269;
+const SIGTAG_SHA256: u32 = 273;
+
+const TAG_HEADERSIGNATURES: u32 = 62;
+const TAG_HEADERIMMUTABLE: u32 = 63;
+const TAG_HEADERI18NTABLE: u32 = 100;
+const TAG_NAME: u32 = 1000;
+const TAG_VERSION: u32 = 1001;
+const TAG_RELEASE: u32 = 1002;
+const TAG_SUMMARY: u32 = 1004;
+const TAG_DESCRIPTION: u32 = 1005;
+const TAG_BUILDTIME: u32 = 1006;
+const TAG_BUILDHOST: u32 = 1007;
+const TAG_SIZE: u32 = 1009;
+const TAG_VENDOR: u32 = 1011;
+const TAG_LICENSE: u32 = 1014;
+const TAG_PACKAGER: u32 = 1015;
+const TAG_GROUP: u32 = 1016;
+const TAG_URL: u32 = 1020;
+const TAG_OS: u32 = 1021;
+const TAG_ARCH: u32 = 1022;
+const TAG_POSTIN: u32 = 1024;
+const TAG_POSTUN: u32 = 1026;
+const TAG_FILESIZES: u32 = 1028;
+const TAG_FILEMODES: u32 = 1030;
+const TAG_FILERDEVS: u32 = 1033;
+const TAG_FILEMTIMES: u32 = 1034;
+const TAG_FILEDIGESTS: u32 = 1035;
+const TAG_FILELINKTOS: u32 = 1036;
+const TAG_FILEFLAGS: u32 = 1037;
+const TAG_FILEUSERNAME: u32 = 1039;
+const TAG_FILEGROUPNAME: u32 = 1040;
+const TAG_SOURCERPM: u32 = 1044;
+const TAG_PROVIDENAME: u32 = 1047;
+const TAG_REQUIREFLAGS: u32 = 1048;
+const TAG_REQUIRENAME: u32 = 1049;
+const TAG_REQUIREVERSION: u32 = 1050;
+const TAG_RPMVERSION: u32 = 1064;
+const TAG_POSTINPROG: u32 = 1086;
+const TAG_POSTUNPROG: u32 = 1088;
+const TAG_FILEDEVICES: u32 = 1095;
+const TAG_FILEINODES: u32 = 1096;
+const TAG_FILELANGS: u32 = 1097;
+const TAG_PROVIDEFLAGS: u32 = 1112;
+const TAG_PROVIDEVERSION: u32 = 1113;
+const TAG_DIRINDEXES: u32 = 1116;
+const TAG_BASENAMES: u32 = 1117;
+const TAG_DIRNAMES: u32 = 1118;
+const TAG_PAYLOADFORMAT: u32 = 1124;
+const TAG_PAYLOADCOMPRESSOR: u32 = 1125;
+const TAG_PAYLOADFLAGS: u32 = 1126;
+const TAG_FILEDIGESTALGO: u32 = 5011;
+const TAG_PAYLOADDIGEST: u32 = 5092;
+const TAG_PAYLOADDIGESTALGO: u32 = 5093;
 
-use std::path::PathBuf;
+const SENSE_LESS: u32 = 1 << 1;
+const SENSE_GREATER: u32 = 1 << 2;
+const SENSE_EQUAL: u32 = 1 << 3;
+const SENSE_INTERP: u32 = 1 << 8;
+const SENSE_SCRIPT_POST: u32 = 1 << 10;
+const SENSE_SCRIPT_POSTUN: u32 = 1 << 12;
+const SENSE_RPMLIB: u32 = 1 << 24;
+
+/// The `PGPHASHALGO_SHA256` identifier used for the file and payload digests.
+const HASH_ALGO_SHA256: u32 = 8;
+
+/// Refreshes the desktop entries and icon caches after installing or removing the package.
+const DESKTOP_DATABASE_SCRIPTLET: &str =
+  "update-desktop-database -q /usr/share/applications >/dev/null 2>&1 || :
+touch --no-create /usr/share/icons/hicolor >/dev/null 2>&1 || :
+gtk-update-icon-cache -q /usr/share/icons/hicolor >/dev/null 2>&1 || :
+";
 
 /// Bundles the project.
-/// Not implemented yet.
-pub fn bundle_project(_settings: &Settings) -> crate::Result<Vec<PathBuf>> {
-  unimplemented!();
+/// Returns a vector of PathBuf that shows where the RPM was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i686",
+    "arm" => "armv7hl",
+    other => other,
+  };
+  let name = settings.main_binary_name();
+  // RPM versions can't contain dashes; `~` makes pre-releases sort before the release
+  let version = settings.version_string().replace('-', "~");
+  let release = settings.rpm().release.as_deref().unwrap_or("1");
+  let package_base_name = format!("{name}-{version}-{release}.{arch}");
+  let package_name = format!("{package_base_name}.rpm");
+
+  let base_dir = settings.project_out_directory().join("bundle/rpm");
+  let package_dir = base_dir.join(&package_base_name);
+  if package_dir.exists() {
+    fs::remove_dir_all(&package_dir)
+      .with_context(|| format!("Failed to remove old {package_base_name}"))?;
+  }
+  let package_path = base_dir.join(&package_name);
+
+  info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  debian::copy_custom_files(&settings.rpm().files, &data_dir)
+    .with_context(|| "Failed to copy custom files")?;
+
+  let files = collect_files(settings, &data_dir).with_context(|| "Failed to read data files")?;
+
+  let cpio = create_cpio(&data_dir, &files).with_context(|| "Failed to create cpio payload")?;
+  let mut encoder = gzip::Encoder::new(Vec::new())?;
+  encoder.write_all(&cpio)?;
+  let payload = encoder.finish().into_result()?;
+
+  let header = generate_header(settings, arch, &version, release, &files, &payload)
+    .to_bytes(TAG_HEADERIMMUTABLE);
+
+  let mut signature = Header::default();
+  signature
+    .add(
+      SIGTAG_SHA1,
+      Value::String(hex::encode(sha1::Sha1::digest(&header))),
+    )
+    .add(
+      SIGTAG_SHA256,
+      Value::String(hex::encode(sha2::Sha256::digest(&header))),
+    )
+    .add(
+      SIGTAG_SIZE,
+      Value::Int32(vec![(header.len() + payload.len()) as u32]),
+    )
+    .add(SIGTAG_PAYLOADSIZE, Value::Int32(vec![cpio.len() as u32]));
+  if let Some(key) = &settings.rpm().signing_key {
+    let sig = sign(key, &header, &package_dir).with_context(|| "Failed to sign RPM package")?;
+    signature.add(SIGTAG_RSA, Value::Bin(sig));
+  }
+  let mut signature = signature.to_bytes(TAG_HEADERSIGNATURES);
+  // the signature header is padded to an 8 byte boundary
+  pad(&mut signature, 8);
+
+  let mut file = File::create(&package_path)
+    .with_context(|| format!("Failed to create {}", package_path.display()))?;
+  file.write_all(&lead(&format!("{name}-{version}-{release}")))?;
+  file.write_all(&signature)?;
+  file.write_all(&header)?;
+  file.write_all(&payload)?;
+  file.flush()?;
+
+  fs::remove_dir_all(&package_dir)?;
+
+  Ok(vec![package_path])
+}
+
+/// A file or directory owned by the package.
+struct RpmFile {
+  /// The absolute installation path.
+  path: String,
+  mode: u32,
+  size: u32,
+  mtime: u32,
+  /// The SHA-256 digest of the file contents (empty for directories).
+  digest: String,
+}
+
+/// Lists the package files, sorted by path.
+///
+/// Only the folders created by the app (e.g. the resources folder) are owned by the package,
+/// system folders such as `/usr/bin` belong to the filesystem package.
+fn collect_files(settings: &Settings, data_dir: &Path) -> crate::Result<Vec<RpmFile>> {
+  let resource_dir = format!("/usr/lib/{}", settings.main_binary_name());
+  let mut files = Vec::new();
+  for entry in WalkDir::new(data_dir).sort_by_file_name() {
+    let entry = entry?;
+    let path = format!(
+      "/{}",
+      entry
+        .path()
+        .strip_prefix(data_dir)
+        .unwrap()
+        .to_string_lossy()
+    );
+    let metadata = fs::metadata(entry.path())?;
+    let mtime = metadata.mtime() as u32;
+    let mode = metadata.permissions().mode();
+
+    if metadata.is_dir() {
+      if path == resource_dir || path.starts_with(&format!("{resource_dir}/")) {
+        files.push(RpmFile {
+          path,
+          mode,
+          size: 0,
+          mtime,
+          digest: String::new(),
+        });
+      }
+    } else {
+      let contents = fs::read(entry.path())?;
+      files.push(RpmFile {
+        path,
+        mode,
+        size: metadata.len() as u32,
+        mtime,
+        digest: hex::encode(sha2::Sha256::digest(&contents)),
+      });
+    }
+  }
+  Ok(files)
+}
+
+/// Creates the cpio archive (newc format) with the package files.
+fn create_cpio(data_dir: &Path, files: &[RpmFile]) -> crate::Result<Vec<u8>> {
+  let mut archive = Vec::new();
+  for (i, file) in files.iter().enumerate() {
+    let contents = if file.digest.is_empty() {
+      Vec::new()
+    } else {
+      fs::read(data_dir.join(file.path.trim_start_matches('/')))?
+    };
+    write_cpio_entry(
+      &mut archive,
+      &format!(".{}", file.path),
+      i as u32 + 1,
+      file.mode,
+      file.mtime,
+      &contents,
+    );
+  }
+  write_cpio_entry(&mut archive, "TRAILER!!!", 0, 0, 0, &[]);
+  Ok(archive)
+}
+
+fn write_cpio_entry(
+  archive: &mut Vec<u8>,
+  name: &str,
+  ino: u32,
+  mode: u32,
+  mtime: u32,
+  data: &[u8],
+) {
+  let nlink = if mode & 0o040000 != 0 { 2 } else { 1 };
+  archive.extend(
+    format!(
+      "070701{ino:08x}{mode:08x}{:08x}{:08x}{nlink:08x}{mtime:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}",
+      0,
+      0,
+      data.len(),
+      0,
+      0,
+      0,
+      0,
+      name.len() + 1,
+      0
+    )
+    .as_bytes(),
+  );
+  archive.extend(name.as_bytes());
+  archive.push(0);
+  pad(archive, 4);
+  archive.extend(data);
+  pad(archive, 4);
+}
+
+fn pad(bytes: &mut Vec<u8>, alignment: usize) {
+  bytes.resize(
+    bytes.len() + (alignment - bytes.len() % alignment) % alignment,
+    0,
+  );
+}
+
+/// Generates the main package header.
+fn generate_header(
+  settings: &Settings,
+  arch: &str,
+  version: &str,
+  release: &str,
+  files: &[RpmFile],
+  payload: &[u8],
+) -> Header {
+  let name = settings.main_binary_name();
+  let build_time = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs() as u32)
+    .unwrap_or_default();
+
+  let mut header = Header::default();
+  header
+    .add(TAG_HEADERI18NTABLE, Value::StringArray(vec!["C".into()]))
+    .add(TAG_NAME, Value::String(name.into()))
+    .add(TAG_VERSION, Value::String(version.into()))
+    .add(TAG_RELEASE, Value::String(release.into()))
+    .add(
+      TAG_SUMMARY,
+      Value::I18nString(settings.short_description().into()),
+    )
+    .add(
+      TAG_DESCRIPTION,
+      Value::I18nString(
+        settings
+          .long_description()
+          .unwrap_or_else(|| settings.short_description())
+          .into(),
+      ),
+    )
+    .add(TAG_BUILDTIME, Value::Int32(vec![build_time]))
+    .add(TAG_BUILDHOST, Value::String("localhost".into()))
+    .add(
+      TAG_SIZE,
+      Value::Int32(vec![files.iter().map(|f| f.size).sum()]),
+    )
+    .add(
+      TAG_LICENSE,
+      Value::String(
+        settings
+          .rpm()
+          .license
+          .clone()
+          .unwrap_or_else(|| "Unknown".into()),
+      ),
+    )
+    .add(TAG_GROUP, Value::I18nString("Unspecified".into()))
+    .add(TAG_OS, Value::String("linux".into()))
+    .add(TAG_ARCH, Value::String(arch.into()))
+    .add(TAG_POSTIN, Value::String(DESKTOP_DATABASE_SCRIPTLET.into()))
+    .add(TAG_POSTUN, Value::String(DESKTOP_DATABASE_SCRIPTLET.into()))
+    .add(TAG_POSTINPROG, Value::StringArray(vec!["/bin/sh".into()]))
+    .add(TAG_POSTUNPROG, Value::StringArray(vec!["/bin/sh".into()]))
+    .add(
+      TAG_SOURCERPM,
+      Value::String(format!("{name}-{version}-{release}.src.rpm")),
+    )
+    .add(TAG_RPMVERSION, Value::String("4.16.0".into()))
+    .add(TAG_PAYLOADFORMAT, Value::String("cpio".into()))
+    .add(TAG_PAYLOADCOMPRESSOR, Value::String("gzip".into()))
+    .add(TAG_PAYLOADFLAGS, Value::String("9".into()))
+    .add(
+      TAG_PAYLOADDIGEST,
+      Value::StringArray(vec![hex::encode(sha2::Sha256::digest(payload))]),
+    )
+    .add(TAG_PAYLOADDIGESTALGO, Value::Int32(vec![HASH_ALGO_SHA256]));
+
+  if let Some(publisher) = settings.publisher() {
+    header.add(TAG_VENDOR, Value::String(publisher.into()));
+  }
+  if let Some(authors) = settings.authors_comma_separated() {
+    header.add(TAG_PACKAGER, Value::String(authors));
+  }
+  if !settings.homepage_url().is_empty() {
+    header.add(TAG_URL, Value::String(settings.homepage_url().into()));
+  }
+
+  // provides
+  header
+    .add(TAG_PROVIDENAME, Value::StringArray(vec![name.into()]))
+    .add(TAG_PROVIDEFLAGS, Value::Int32(vec![SENSE_EQUAL]))
+    .add(
+      TAG_PROVIDEVERSION,
+      Value::StringArray(vec![format!("{version}-{release}")]),
+    );
+
+  // requires
+  let mut requires = vec![(
+    "/bin/sh".to_string(),
+    SENSE_INTERP | SENSE_SCRIPT_POST | SENSE_SCRIPT_POSTUN,
+    String::new(),
+  )];
+  for dependency in settings.rpm().depends.iter().flatten() {
+    requires.push(parse_dependency(dependency));
+  }
+  for (feature, version) in [
+    ("rpmlib(CompressedFileNames)", "3.0.4-1"),
+    ("rpmlib(FileDigests)", "4.6.0-1"),
+    ("rpmlib(PayloadFilesHavePrefix)", "4.0-1"),
+  ] {
+    requires.push((
+      feature.into(),
+      SENSE_RPMLIB | SENSE_LESS | SENSE_EQUAL,
+      version.into(),
+    ));
+  }
+  header
+    .add(
+      TAG_REQUIRENAME,
+      Value::StringArray(requires.iter().map(|r| r.0.clone()).collect()),
+    )
+    .add(
+      TAG_REQUIREFLAGS,
+      Value::Int32(requires.iter().map(|r| r.1).collect()),
+    )
+    .add(
+      TAG_REQUIREVERSION,
+      Value::StringArray(requires.iter().map(|r| r.2.clone()).collect()),
+    );
+
+  // file list
+  let mut dir_names: Vec<String> = Vec::new();
+  let mut dir_indexes = Vec::new();
+  let mut base_names = Vec::new();
+  for file in files {
+    let (dir, base) = file.path.rsplit_once('/').unwrap();
+    let dir = format!("{dir}/");
+    let index = match dir_names.iter().position(|d| *d == dir) {
+      Some(index) => index,
+      None => {
+        dir_names.push(dir);
+        dir_names.len() - 1
+      }
+    };
+    dir_indexes.push(index as u32);
+    base_names.push(base.to_string());
+  }
+  let strings = |value: &str| Value::StringArray(vec![value.to_string(); files.len()]);
+  header
+    .add(
+      TAG_FILESIZES,
+      Value::Int32(files.iter().map(|f| f.size).collect()),
+    )
+    .add(
+      TAG_FILEMODES,
+      Value::Int16(files.iter().map(|f| f.mode as u16).collect()),
+    )
+    .add(TAG_FILERDEVS, Value::Int16(vec![0; files.len()]))
+    .add(
+      TAG_FILEMTIMES,
+      Value::Int32(files.iter().map(|f| f.mtime).collect()),
+    )
+    .add(
+      TAG_FILEDIGESTS,
+      Value::StringArray(files.iter().map(|f| f.digest.clone()).collect()),
+    )
+    .add(TAG_FILELINKTOS, strings(""))
+    .add(TAG_FILEFLAGS, Value::Int32(vec![0; files.len()]))
+    .add(TAG_FILEUSERNAME, strings("root"))
+    .add(TAG_FILEGROUPNAME, strings("root"))
+    .add(TAG_FILEDEVICES, Value::Int32(vec![1; files.len()]))
+    .add(
+      TAG_FILEINODES,
+      Value::Int32((1..=files.len() as u32).collect()),
+    )
+    .add(TAG_FILELANGS, strings(""))
+    .add(TAG_DIRINDEXES, Value::Int32(dir_indexes))
+    .add(TAG_BASENAMES, Value::StringArray(base_names))
+    .add(TAG_DIRNAMES, Value::StringArray(dir_names))
+    .add(TAG_FILEDIGESTALGO, Value::Int32(vec![HASH_ALGO_SHA256]));
+
+  header
+}
+
+/// Parses a dependency such as `openssl >= 3.0` into its name, sense flags and version.
+fn parse_dependency(dependency: &str) -> (String, u32, String) {
+  let parts: Vec<&str> = dependency.split_whitespace().collect();
+  if let [name, operator, version] = parts.as_slice() {
+    let flags = match *operator {
+      "<" => Some(SENSE_LESS),
+      "<=" => Some(SENSE_LESS | SENSE_EQUAL),
+      "=" => Some(SENSE_EQUAL),
+      ">=" => Some(SENSE_GREATER | SENSE_EQUAL),
+      ">" => Some(SENSE_GREATER),
+      _ => None,
+    };
+    if let Some(flags) = flags {
+      return (name.to_string(), flags, version.to_string());
+    }
+  }
+  (dependency.trim().to_string(), 0, String::new())
+}
+
+/// Signs the header with GPG, returning the binary detached signature.
+fn sign(key: &str, header: &[u8], package_dir: &Path) -> crate::Result<Vec<u8>> {
+  let header_path = package_dir.join("header");
+  fs::write(&header_path, header)?;
+  let output = Command::new("gpg")
+    .arg("--batch")
+    .arg("--no-armor")
+    .arg("--digest-algo")
+    .arg("sha256")
+    .arg("--local-user")
+    .arg(key)
+    .arg("--output")
+    .arg("-")
+    .arg("--detach-sign")
+    .arg(&header_path)
+    .output_ok()
+    .context(
+      "error running gpg, make sure it is installed and the signing key is in the keyring",
+    )?;
+  fs::remove_file(header_path)?;
+  Ok(output.stdout)
+}
+
+/// Generates the legacy lead section.
+fn lead(name: &str) -> Vec<u8> {
+  let mut lead = vec![0xed, 0xab, 0xee, 0xdb, 3, 0];
+  // binary package
+  lead.extend(0u16.to_be_bytes());
+  // architecture number, unused by rpm
+  lead.extend(0u16.to_be_bytes());
+  let mut name_bytes = name.as_bytes().to_vec();
+  name_bytes.resize(66, 0);
+  name_bytes[65] = 0;
+  lead.extend(name_bytes);
+  // linux
+  lead.extend(1u16.to_be_bytes());
+  // header-style signature
+  lead.extend(5u16.to_be_bytes());
+  lead.extend([0; 16]);
+  lead
+}
+
+/// A header entry value.
+enum Value {
+  Int16(Vec<u16>),
+  Int32(Vec<u32>),
+  String(String),
+  Bin(Vec<u8>),
+  StringArray(Vec<String>),
+  I18nString(String),
+}
+
+impl Value {
+  fn kind(&self) -> u32 {
+    match self {
+      Self::Int16(_) => 3,
+      Self::Int32(_) => 4,
+      Self::String(_) => 6,
+      Self::Bin(_) => 7,
+      Self::StringArray(_) => 8,
+      Self::I18nString(_) => 9,
+    }
+  }
+
+  fn count(&self) -> usize {
+    match self {
+      Self::Int16(v) => v.len(),
+      Self::Int32(v) => v.len(),
+      Self::Bin(v) => v.len(),
+      Self::StringArray(v) => v.len(),
+      Self::String(_) | Self::I18nString(_) => 1,
+    }
+  }
+
+  fn alignment(&self) -> usize {
+    match self {
+      Self::Int16(_) => 2,
+      Self::Int32(_) => 4,
+      _ => 1,
+    }
+  }
+
+  fn write(&self, data: &mut Vec<u8>) {
+    match self {
+      Self::Int16(v) => v.iter().for_each(|i| data.extend(i.to_be_bytes())),
+      Self::Int32(v) => v.iter().for_each(|i| data.extend(i.to_be_bytes())),
+      Self::Bin(v) => data.extend(v),
+      Self::String(s) | Self::I18nString(s) => {
+        data.extend(s.as_bytes());
+        data.push(0);
+      }
+      Self::StringArray(v) => {
+        for s in v {
+          data.extend(s.as_bytes());
+          data.push(0);
+        }
+      }
+    }
+  }
+}
+
+/// A RPM header structure, used for both the signature and the main header.
+#[derive(Default)]
+struct Header {
+  entries: BTreeMap<u32, Value>,
+}
+
+impl Header {
+  fn add(&mut self, tag: u32, value: Value) -> &mut Self {
+    self.entries.insert(tag, value);
+    self
+  }
+
+  /// Serializes the header, with all entries enclosed in the given region tag.
+  fn to_bytes(&self, region_tag: u32) -> Vec<u8> {
+    let entry_count = self.entries.len() + 1;
+    let mut index = Vec::new();
+    let mut data = Vec::new();
+
+    for (tag, value) in &self.entries {
+      pad(&mut data, value.alignment());
+      index.push((*tag, value.kind(), data.len() as i32, value.count() as u32));
+      value.write(&mut data);
+    }
+
+    // the region trailer points back to the start of the index
+    let region_offset = data.len() as i32;
+    data.extend(region_tag.to_be_bytes());
+    data.extend(7u32.to_be_bytes());
+    data.extend((-(entry_count as i32 * 16)).to_be_bytes());
+    data.extend(16u32.to_be_bytes());
+    index.insert(0, (region_tag, 7, region_offset, 16));
+
+    let mut bytes = vec![0x8e, 0xad, 0xe8, 0x01, 0, 0, 0, 0];
+    bytes.extend((entry_count as u32).to_be_bytes());
+    bytes.extend((data.len() as u32).to_be_bytes());
+    for (tag, kind, offset, count) in index {
+      bytes.extend(tag.to_be_bytes());
+      bytes.extend(kind.to_be_bytes());
+      bytes.extend(offset.to_be_bytes());
+      bytes.extend(count.to_be_bytes());
+    }
+    bytes.extend(data);
+    bytes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn header_layout() {
+    let mut header = Header::default();
+    header
+      .add(TAG_NAME, Value::String("app".into()))
+      .add(TAG_FILEMODES, Value::Int16(vec![0o100755]))
+      .add(TAG_SIZE, Value::Int32(vec![42]));
+    let bytes = header.to_bytes(TAG_HEADERIMMUTABLE);
+
+    let read_u32 =
+      |offset: usize| u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    assert_eq!(&bytes[..4], &[0x8e, 0xad, 0xe8, 0x01]);
+    // region entry, name, size, file modes
+    assert_eq!(read_u32(8), 4);
+    // "app\0" + int32 + int16 + region trailer
+    assert_eq!(read_u32(12), 4 + 4 + 2 + 16);
+
+    let index = 16;
+    assert_eq!(read_u32(index), TAG_HEADERIMMUTABLE);
+    assert_eq!(read_u32(index + 16), TAG_NAME);
+    assert_eq!(read_u32(index + 32), TAG_SIZE);
+    assert_eq!(read_u32(index + 40), 4);
+    assert_eq!(read_u32(index + 48), TAG_FILEMODES);
+    // int16 values are 2 bytes aligned after the int32
+    assert_eq!(read_u32(index + 56), 8);
+
+    let data = index + 4 * 16;
+    let trailer = data + read_u32(index + 8) as usize;
+    assert_eq!(
+      i32::from_be_bytes(bytes[trailer + 8..trailer + 12].try_into().unwrap()),
+      -64
+    );
+  }
+
+  #[test]
+  fn dependency() {
+    assert_eq!(
+      parse_dependency("openssl >= 3.0"),
+      ("openssl".into(), SENSE_GREATER | SENSE_EQUAL, "3.0".into())
+    );
+    assert_eq!(parse_dependency("gtk3"), ("gtk3".into(), 0, String::new()));
+  }
 }
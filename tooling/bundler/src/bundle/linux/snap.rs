@@ -0,0 +1,168 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// A Snap package is built by `snapcraft` from the following project structure:
+//
+// bundle/snap/
+//     snap/snapcraft.yaml        # The snapcraft project file
+//     data/                      # The app files, dumped as-is into the snap
+//         usr/bin/foobar                            # Binary executable file
+//         usr/share/applications/foobar.desktop     # Desktop file (for apps)
+//         usr/share/icons/hicolor/...               # Icon files (for apps)
+//         usr/lib/foobar/...                        # Other resource files
+//     foobar_1.2.3_amd64.snap    # The generated snap package
+//
+// The data folder uses the same structure as the Debian package.
+
+use super::{super::common::CommandExt, debian};
+use crate::Settings;
+use anyhow::Context;
+use handlebars::Handlebars;
+use heck::AsKebabCase;
+use log::info;
+use serde::Serialize;
+use tauri_utils::config::SnapConfinement;
+
+use std::{fs, path::PathBuf, process::Command};
+
+const DEFAULT_BASE: &str = "core22";
+const DEFAULT_GRADE: &str = "stable";
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Snap package was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    "x86_64" => "amd64",
+    "arm" => "armhf",
+    "aarch64" => "arm64",
+    other => other,
+  };
+  let snap_name = AsKebabCase(settings.main_binary_name()).to_string();
+  let package_name = format!("{}_{}_{}.snap", snap_name, settings.version_string(), arch);
+
+  let output_path = settings.project_out_directory().join("bundle/snap");
+  if output_path.exists() {
+    fs::remove_dir_all(&output_path).with_context(|| "Failed to remove old snap package")?;
+  }
+  let package_path = output_path.join(&package_name);
+
+  info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
+
+  let (data_dir, icons) = debian::generate_data(settings, &output_path)
+    .with_context(|| "Failed to build data folders and files")?;
+  let icon = icons
+    .iter()
+    .filter(|i| i.width == i.height)
+    .max_by_key(|i| i.width)
+    .map(|i| {
+      i.path
+        .strip_prefix(&output_path)
+        .unwrap()
+        .to_string_lossy()
+        .to_string()
+    })
+    .ok_or_else(|| {
+      crate::Error::GenericError("couldn't find a square icon to use as Snap icon".into())
+    })?;
+
+  // snapcraft copies the snap icon to `meta/gui/icon.png`
+  let desktop_file_path = data_dir
+    .join("usr/share/applications")
+    .join(format!("{}.desktop", settings.main_binary_name()));
+  let desktop_file = fs::read_to_string(&desktop_file_path)?
+    .lines()
+    .map(|line| {
+      if line.starts_with("Icon=") {
+        "Icon=${SNAP}/meta/gui/icon.png".to_string()
+      } else {
+        line.to_string()
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+  fs::write(&desktop_file_path, desktop_file + "\n")?;
+
+  generate_snapcraft_file(settings, &snap_name, &icon, &output_path)
+    .with_context(|| "Failed to create snapcraft.yaml")?;
+
+  Command::new("snapcraft")
+    .arg("pack")
+    .arg("--output")
+    .arg(&package_name)
+    .current_dir(&output_path)
+    .output_ok()
+    .context("error running snapcraft, make sure snapcraft is installed")?;
+
+  fs::remove_dir_all(&data_dir)?;
+
+  Ok(vec![package_path])
+}
+
+/// Generates the `snap/snapcraft.yaml` project file.
+fn generate_snapcraft_file(
+  settings: &Settings,
+  snap_name: &str,
+  icon: &str,
+  output_path: &std::path::Path,
+) -> crate::Result<()> {
+  let snap = settings.snap();
+
+  #[derive(Serialize)]
+  struct SnapcraftTemplateParams<'a> {
+    name: &'a str,
+    bin: &'a str,
+    base: &'a str,
+    version: String,
+    summary: String,
+    description: String,
+    grade: &'a str,
+    confinement: &'a str,
+    icon: String,
+    desktop_extension: bool,
+    plugs: &'a [String],
+  }
+
+  // the summary is limited to 78 characters
+  let summary: String = settings.short_description().chars().take(78).collect();
+
+  let params = SnapcraftTemplateParams {
+    name: snap_name,
+    bin: settings.main_binary_name(),
+    base: snap.base.as_deref().unwrap_or(DEFAULT_BASE),
+    // quote strings so they are always parsed as YAML strings
+    version: serde_json::to_string(settings.version_string())?,
+    summary: serde_json::to_string(&summary)?,
+    description: serde_json::to_string(
+      settings
+        .long_description()
+        .unwrap_or_else(|| settings.short_description()),
+    )?,
+    grade: snap.grade.as_deref().unwrap_or(DEFAULT_GRADE),
+    confinement: match snap.confinement {
+      SnapConfinement::Strict => "strict",
+      SnapConfinement::Classic => "classic",
+      SnapConfinement::Devmode => "devmode",
+    },
+    icon: serde_json::to_string(icon)?,
+    // the GNOME extension provides the desktop interfaces and libraries, but it can't be used by classic snaps
+    desktop_extension: snap.confinement != SnapConfinement::Classic,
+    plugs: &snap.plugs,
+  };
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  handlebars
+    .register_template_string("snapcraft.yaml", include_str!("./templates/snapcraft.yaml"))
+    .expect("Failed to register template for handlebars");
+
+  let snapcraft_path = output_path.join("snap/snapcraft.yaml");
+  fs::create_dir_all(snapcraft_path.parent().unwrap())?;
+  fs::write(
+    snapcraft_path,
+    handlebars.render("snapcraft.yaml", &params)?,
+  )?;
+
+  Ok(())
+}
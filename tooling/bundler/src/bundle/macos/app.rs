@@ -25,7 +25,7 @@
 use super::{
   super::common,
   icon::create_icns_file,
-  sign::{notarize, notarize_auth_args, sign},
+  sign::{notarize, notarize_auth_args, sign, skip_notarization},
 };
 use crate::Settings;
 
@@ -72,6 +72,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   create_info_plist(&bundle_directory, bundle_icon_file, settings)
     .with_context(|| "Failed to create Info.plist")?;
 
+  create_info_plist_strings(&resources_dir, settings)
+    .with_context(|| "Failed to create the localized InfoPlist.strings files")?;
+
   copy_frameworks_to_bundle(&bundle_directory, settings)
     .with_context(|| "Failed to bundle frameworks")?;
 
@@ -87,12 +90,16 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // sign application
     sign(app_bundle_path.clone(), identity, settings, true)?;
     // notarization is required for distribution
-    match notarize_auth_args() {
-      Ok(args) => {
-        notarize(app_bundle_path.clone(), args, settings)?;
-      }
-      Err(e) => {
-        warn!("skipping app notarization, {}", e.to_string());
+    if skip_notarization() {
+      warn!("skipping app notarization, TAURI_SKIP_NOTARIZATION is set");
+    } else {
+      match notarize_auth_args(settings) {
+        Ok(args) => {
+          notarize(app_bundle_path.clone(), args, settings)?;
+        }
+        Err(e) => {
+          warn!("skipping app notarization, {}", e.to_string());
+        }
       }
     }
   }
@@ -168,6 +175,14 @@ fn create_info_plist(
   if let Some(copyright) = settings.copyright_string() {
     plist.insert("NSHumanReadableCopyright".into(), copyright.into());
   }
+  if !settings.localizations().is_empty() {
+    let mut locales = settings.localizations().keys().cloned().collect::<Vec<_>>();
+    locales.sort();
+    plist.insert(
+      "CFBundleLocalizations".into(),
+      plist::Value::Array(locales.into_iter().map(Into::into).collect()),
+    );
+  }
 
   if let Some(exception_domain) = settings.macos().exception_domain.clone() {
     let mut security = plist::Dictionary::new();
@@ -181,6 +196,27 @@ fn create_info_plist(
     plist.insert("NSAppTransportSecurity".into(), security.into());
   }
 
+  let local_network = &settings.macos().local_network;
+  if let Some(usage_description) = &local_network.usage_description {
+    plist.insert(
+      "NSLocalNetworkUsageDescription".into(),
+      usage_description.clone().into(),
+    );
+  }
+  if !local_network.bonjour_services.is_empty() {
+    plist.insert(
+      "NSBonjourServices".into(),
+      plist::Value::Array(
+        local_network
+          .bonjour_services
+          .iter()
+          .cloned()
+          .map(Into::into)
+          .collect(),
+      ),
+    );
+  }
+
   if let Some(user_plist_path) = &settings.macos().info_plist_path {
     let user_plist = plist::Value::from_file(user_plist_path)?;
     if let Some(dict) = user_plist.into_dictionary() {
@@ -195,6 +231,42 @@ fn create_info_plist(
   Ok(())
 }
 
+// Creates the `{locale}.lproj/InfoPlist.strings` files with the localized bundle metadata.
+fn create_info_plist_strings(resources_dir: &Path, settings: &Settings) -> crate::Result<()> {
+  fn escape(value: &str) -> String {
+    value
+      .replace('\\', "\\\\")
+      .replace('"', "\\\"")
+      .replace('\n', "\\n")
+  }
+
+  for (locale, localization) in settings.localizations() {
+    let mut strings = String::new();
+    if let Some(product_name) = &localization.product_name {
+      strings.push_str(&format!(
+        "\"CFBundleName\" = \"{}\";\n",
+        escape(product_name)
+      ));
+      strings.push_str(&format!(
+        "\"CFBundleDisplayName\" = \"{}\";\n",
+        escape(product_name)
+      ));
+    }
+    if let Some(copyright) = &localization.copyright {
+      strings.push_str(&format!(
+        "\"NSHumanReadableCopyright\" = \"{}\";\n",
+        escape(copyright)
+      ));
+    }
+
+    let lproj_dir = resources_dir.join(format!("{locale}.lproj"));
+    fs::create_dir_all(&lproj_dir)?;
+    fs::write(lproj_dir.join("InfoPlist.strings"), strings)?;
+  }
+
+  Ok(())
+}
+
 // Copies the framework under `{src_dir}/{framework}.framework` to `{dest_dir}/{framework}.framework`.
 fn copy_framework_from(dest_dir: &Path, framework: &str, src_dir: &Path) -> crate::Result<bool> {
   let src_name = format!("{}.framework", framework);
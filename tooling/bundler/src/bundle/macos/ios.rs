@@ -48,10 +48,8 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   fs::create_dir_all(&app_bundle_path)
     .with_context(|| format!("Failed to create bundle directory at {:?}", app_bundle_path))?;
 
-  for src in settings.resource_files() {
-    let src = src?;
-    let dest = app_bundle_path.join(tauri_utils::resources::resource_relpath(&src));
-    common::copy_file(&src, &dest)
+  for (src, target) in settings.resource_entries()? {
+    common::copy_file(&src, &app_bundle_path.join(target))
       .with_context(|| format!("Failed to copy resource file {:?}", src))?;
   }
 
@@ -8,8 +8,7 @@ use std::{fs::File, io::prelude::*, path::PathBuf, process::Command};
 
 use crate::{bundle::common::CommandExt, Settings};
 use anyhow::Context;
-use log::info;
-use regex::Regex;
+use log::{info, warn};
 
 const KEYCHAIN_ID: &str = "tauri-build.keychain";
 const KEYCHAIN_PWD: &str = "tauri-build";
@@ -217,8 +216,6 @@ pub fn notarize(
   auth_args: Vec<String>,
   settings: &Settings,
 ) -> crate::Result<()> {
-  let identifier = settings.bundle_identifier();
-
   let bundle_stem = app_bundle_path
     .file_stem()
     .expect("failed to get bundle filename");
@@ -252,55 +249,57 @@ pub fn notarize(
     sign(zip_path.clone(), identity, settings, false)?;
   };
 
-  let mut notarize_args = vec![
-    "altool",
-    "--notarize-app",
-    "-f",
-    zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-    "--primary-bundle-id",
-    identifier,
-  ];
-
-  if let Some(provider_short_name) = &settings.macos().provider_short_name {
-    notarize_args.push("--asc-provider");
-    notarize_args.push(provider_short_name);
-  }
-
   info!(action = "Notarizing"; "{}", app_bundle_path.display());
 
   let output = Command::new("xcrun")
-    .args(notarize_args)
+    .args(["notarytool", "submit", "--wait", "--output-format", "json"])
+    .arg(&zip_path)
     .args(auth_args.clone())
-    .output_ok()
-    .context("failed to upload app to Apple's notarization servers.")?;
-
-  // combine both stdout and stderr to support macOS below 10.15
-  let mut notarize_response = std::str::from_utf8(&output.stdout)?.to_string();
-  notarize_response.push('\n');
-  notarize_response.push_str(std::str::from_utf8(&output.stderr)?);
-  notarize_response.push('\n');
-  if let Some(uuid) = Regex::new(r"\nRequestUUID = (.+?)\n")?
-    .captures_iter(&notarize_response)
-    .next()
-  {
-    info!("notarization started; waiting for Apple response...");
-
-    let uuid = uuid[1].to_string();
-    get_notarization_status(uuid, auth_args)?;
-    staple_app(app_bundle_path.clone())?;
-  } else {
-    return Err(
+    .output()
+    .context("failed to run notarytool, make sure Xcode 13 or newer is installed")?;
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  match serde_json::from_str::<NotarytoolSubmitOutput>(&stdout) {
+    Ok(submit_output) => {
+      if submit_output.status == "Accepted" {
+        info!(action = "Notarized"; "{} ({})", app_bundle_path.display(), submit_output.id);
+        staple_app(app_bundle_path.clone())?;
+        Ok(())
+      } else {
+        let log = Command::new("xcrun")
+          .args(["notarytool", "log", submit_output.id.as_str()])
+          .args(auth_args)
+          .output_ok()
+          .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+          .unwrap_or_default();
+        Err(
+          anyhow::anyhow!(
+            "Apple failed to notarize your app ({} status: {}). {}\n{}",
+            submit_output.id,
+            submit_output.status,
+            submit_output.message,
+            log
+          )
+          .into(),
+        )
+      }
+    }
+    Err(_) => Err(
       anyhow::anyhow!(
-        "failed to parse RequestUUID from upload output. {}",
-        notarize_response
+        "failed to upload app to Apple's notarization servers. {}\n{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
       )
       .into(),
-    );
+    ),
   }
+}
 
-  Ok(())
+#[derive(serde::Deserialize)]
+struct NotarytoolSubmitOutput {
+  id: String,
+  status: String,
+  message: String,
 }
 
 fn staple_app(mut app_bundle_path: PathBuf) -> crate::Result<()> {
@@ -322,54 +321,17 @@ fn staple_app(mut app_bundle_path: PathBuf) -> crate::Result<()> {
   Ok(())
 }
 
-fn get_notarization_status(uuid: String, auth_args: Vec<String>) -> crate::Result<()> {
-  std::thread::sleep(std::time::Duration::from_secs(10));
-  let result = Command::new("xcrun")
-    .args(vec!["altool", "--notarization-info", &uuid])
-    .args(auth_args.clone())
-    .output_ok();
-
-  if let Ok(output) = result {
-    // combine both stdout and stderr to support macOS below 10.15
-    let mut notarize_status = std::str::from_utf8(&output.stdout)?.to_string();
-    notarize_status.push('\n');
-    notarize_status.push_str(std::str::from_utf8(&output.stderr)?);
-    notarize_status.push('\n');
-    if let Some(status) = Regex::new(r"\n *Status: (.+?)\n")?
-      .captures_iter(&notarize_status)
-      .next()
-    {
-      let status = status[1].to_string();
-      if status == "in progress" {
-        get_notarization_status(uuid, auth_args)
-      } else if status == "invalid" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Apple failed to notarize your app. {}",
-            notarize_status
-          ))
-          .into(),
-        )
-      } else if status != "success" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Unknown notarize status {}. {}",
-            status, notarize_status
-          ))
-          .into(),
-        )
-      } else {
-        Ok(())
-      }
-    } else {
-      get_notarization_status(uuid, auth_args)
-    }
-  } else {
-    get_notarization_status(uuid, auth_args)
-  }
+/// Whether notarization was disabled with the `TAURI_SKIP_NOTARIZATION` environment variable.
+pub fn skip_notarization() -> bool {
+  std::env::var("TAURI_SKIP_NOTARIZATION").map_or(false, |v| v == "true" || v == "1")
 }
 
-pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
+/// Reads the notarytool authentication arguments from the environment.
+///
+/// Either `APPLE_ID`, `APPLE_PASSWORD` and `APPLE_TEAM_ID`
+/// or the App Store Connect API key `APPLE_API_KEY` and `APPLE_API_ISSUER` must be set.
+/// The deprecated provider short name is used as the team ID when `APPLE_TEAM_ID` is not set.
+pub fn notarize_auth_args(settings: &Settings) -> crate::Result<Vec<String>> {
   match (
     std::env::var_os("APPLE_ID"),
     std::env::var_os("APPLE_PASSWORD"),
@@ -383,11 +345,25 @@ pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
         .to_str()
         .expect("failed to convert APPLE_PASSWORD to string")
         .to_string();
+      let team_id = match std::env::var("APPLE_TEAM_ID") {
+        Ok(team_id) => team_id,
+        Err(_) => match &settings.macos().provider_short_name {
+          Some(provider_short_name) => {
+            warn!("APPLE_PROVIDER_SHORT_NAME and `tauri > bundle > macOS > providerShortName` are deprecated since notarytool identifies the team by its ID, using `{provider_short_name}` as the APPLE_TEAM_ID");
+            provider_short_name.clone()
+          }
+          None => {
+            return Err(anyhow::anyhow!("the APPLE_TEAM_ID environment variable is required when notarizing with APPLE_ID & APPLE_PASSWORD").into())
+          }
+        },
+      };
       Ok(vec![
-        "-u".to_string(),
+        "--apple-id".to_string(),
         apple_id,
-        "-p".to_string(),
+        "--password".to_string(),
         apple_password,
+        "--team-id".to_string(),
+        team_id,
       ])
     }
     _ => {
@@ -395,10 +371,49 @@ pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
         (Some(api_key), Some(api_issuer)) => {
           let api_key = api_key.to_str().expect("failed to convert APPLE_API_KEY to string").to_string();
           let api_issuer = api_issuer.to_str().expect("failed to convert APPLE_API_ISSUER to string").to_string();
-          Ok(vec!["--apiKey".to_string(), api_key, "--apiIssuer".to_string(), api_issuer])
+          let key_path = find_api_key(&api_key)?;
+          Ok(vec![
+            "--key".to_string(),
+            key_path.to_string_lossy().to_string(),
+            "--key-id".to_string(),
+            api_key,
+            "--issuer".to_string(),
+            api_issuer,
+          ])
         },
-        _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD or APPLE_API_KEY & APPLE_API_ISSUER environment variables found").into())
+        _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD & APPLE_TEAM_ID or APPLE_API_KEY & APPLE_API_ISSUER environment variables found").into())
       }
     }
   }
 }
+
+/// Finds the `AuthKey_<api_key>.p8` private key file.
+///
+/// Uses `APPLE_API_KEY_PATH` if set, otherwise searches the same directories `altool` used to.
+fn find_api_key(api_key: &str) -> crate::Result<PathBuf> {
+  if let Some(path) = std::env::var_os("APPLE_API_KEY_PATH") {
+    return Ok(PathBuf::from(path));
+  }
+
+  let file_name = format!("AuthKey_{api_key}.p8");
+  let mut search_dirs = vec![PathBuf::from("./private_keys")];
+  if let Some(dir) = std::env::var_os("API_PRIVATE_KEYS_DIR") {
+    search_dirs.insert(0, PathBuf::from(dir));
+  }
+  if let Some(home_dir) = dirs_next::home_dir() {
+    search_dirs.push(home_dir.join("private_keys"));
+    search_dirs.push(home_dir.join(".private_keys"));
+    search_dirs.push(home_dir.join(".appstoreconnect/private_keys"));
+  }
+
+  search_dirs
+    .into_iter()
+    .map(|dir| dir.join(&file_name))
+    .find(|path| path.exists())
+    .ok_or_else(|| {
+      anyhow::anyhow!(
+        "could not find {file_name}, set the APPLE_API_KEY_PATH environment variable to the API key file path"
+      )
+      .into()
+    })
+}
@@ -7,12 +7,17 @@ use super::category::AppCategory;
 use crate::bundle::{common, platform::target_triple};
 pub use tauri_utils::config::WebviewInstallMode;
 use tauri_utils::{
-  config::{BundleType, NSISInstallerMode},
+  config::{
+    BundleLocalization, BundleType, LocalNetworkConfig, NSISInstallerMode, SnapConfinement,
+    WindowsSignConfig, WixInstallMode,
+  },
   resources::{external_binaries, ResourcePaths},
 };
 
+use anyhow::Context;
+
 use std::{
-  collections::HashMap,
+  collections::{HashMap, HashSet},
   path::{Path, PathBuf},
 };
 
@@ -28,12 +33,18 @@ pub enum PackageType {
   WindowsMsi,
   /// The NSIS bundle (.exe).
   Nsis,
+  /// The portable Windows executable (.exe).
+  Portable,
   /// The Linux Debian package bundle (.deb).
   Deb,
   /// The Linux RPM bundle (.rpm).
   Rpm,
   /// The Linux AppImage bundle (.AppImage).
   AppImage,
+  /// The Linux Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Linux Snap package (.snap).
+  Snap,
   /// The macOS DMG bundle (.dmg).
   Dmg,
   /// The Updater bundle.
@@ -44,9 +55,13 @@ impl From<BundleType> for PackageType {
   fn from(bundle: BundleType) -> Self {
     match bundle {
       BundleType::Deb => Self::Deb,
+      BundleType::Rpm => Self::Rpm,
       BundleType::AppImage => Self::AppImage,
+      BundleType::Flatpak => Self::Flatpak,
+      BundleType::Snap => Self::Snap,
       BundleType::Msi => Self::WindowsMsi,
       BundleType::Nsis => Self::Nsis,
+      BundleType::Portable => Self::Portable,
       BundleType::App => Self::MacOsBundle,
       BundleType::Dmg => Self::Dmg,
       BundleType::Updater => Self::Updater,
@@ -56,7 +71,7 @@ impl From<BundleType> for PackageType {
 
 impl PackageType {
   /// Maps a short name to a PackageType.
-  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "updater".
+  /// Possible values are "deb", "ios", "msi", "nsis", "portable", "app", "rpm", "appimage", "flatpak", "snap", "dmg", "updater".
   pub fn from_short_name(name: &str) -> Option<PackageType> {
     // Other types we may eventually want to support: apk.
     match name {
@@ -64,9 +79,12 @@ impl PackageType {
       "ios" => Some(PackageType::IosBundle),
       "msi" => Some(PackageType::WindowsMsi),
       "nsis" => Some(PackageType::Nsis),
+      "portable" => Some(PackageType::Portable),
       "app" => Some(PackageType::MacOsBundle),
       "rpm" => Some(PackageType::Rpm),
       "appimage" => Some(PackageType::AppImage),
+      "flatpak" => Some(PackageType::Flatpak),
+      "snap" => Some(PackageType::Snap),
       "dmg" => Some(PackageType::Dmg),
       "updater" => Some(PackageType::Updater),
       _ => None,
@@ -81,15 +99,18 @@ impl PackageType {
       PackageType::IosBundle => "ios",
       PackageType::WindowsMsi => "msi",
       PackageType::Nsis => "nsis",
+      PackageType::Portable => "portable",
       PackageType::MacOsBundle => "app",
       PackageType::Rpm => "rpm",
       PackageType::AppImage => "appimage",
+      PackageType::Flatpak => "flatpak",
+      PackageType::Snap => "snap",
       PackageType::Dmg => "dmg",
       PackageType::Updater => "updater",
     }
   }
 
-  /// Gets the list of the possible package types.
+  /// Gets the list of the package types that can be bundled on the current host.
   pub fn all() -> &'static [PackageType] {
     ALL_PACKAGE_TYPES
   }
@@ -102,8 +123,8 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::IosBundle,
   #[cfg(target_os = "windows")]
   PackageType::WindowsMsi,
-  #[cfg(target_os = "windows")]
   PackageType::Nsis,
+  PackageType::Portable,
   #[cfg(target_os = "macos")]
   PackageType::MacOsBundle,
   #[cfg(target_os = "linux")]
@@ -112,6 +133,10 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::Dmg,
   #[cfg(target_os = "linux")]
   PackageType::AppImage,
+  #[cfg(target_os = "linux")]
+  PackageType::Flatpak,
+  #[cfg(target_os = "linux")]
+  PackageType::Snap,
   PackageType::Updater,
 ];
 
@@ -163,6 +188,50 @@ pub struct DebianSettings {
   pub desktop_template: Option<PathBuf>,
 }
 
+/// The Linux RPM bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct RpmSettings {
+  /// the list of RPM dependencies.
+  pub depends: Option<Vec<String>>,
+  /// the package release number. Defaults to `1`.
+  pub release: Option<String>,
+  /// the package license. Defaults to `Unknown`.
+  pub license: Option<String>,
+  /// List of custom files to add to the RPM package.
+  /// Maps the path on the RPM package to the path of the file to include (relative to the current working directory).
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// The GPG key ID used to sign the package.
+  pub signing_key: Option<String>,
+}
+
+/// The Linux Flatpak bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct FlatpakSettings {
+  /// The runtime the app runs on. Defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The version of the runtime. Defaults to `44`.
+  pub runtime_version: Option<String>,
+  /// The SDK used to build the app. Defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// The sandbox permissions granted to the app (`finish-args`),
+  /// in addition to the default display and GPU access.
+  pub finish_args: Vec<String>,
+}
+
+/// The Linux Snap package settings.
+#[derive(Clone, Debug, Default)]
+pub struct SnapSettings {
+  /// The confinement level of the snap.
+  pub confinement: SnapConfinement,
+  /// The base snap. Defaults to `core22`.
+  pub base: Option<String>,
+  /// The quality grade of the snap. Defaults to `stable`.
+  pub grade: Option<String>,
+  /// The interfaces plugged by the app,
+  /// in addition to the desktop interfaces provided by the GNOME extension.
+  pub plugs: Vec<String>,
+}
+
 /// The macOS bundle settings.
 #[derive(Clone, Debug, Default)]
 pub struct MacOsSettings {
@@ -195,6 +264,8 @@ pub struct MacOsSettings {
   pub entitlements: Option<String>,
   /// Path to the Info.plist file for the bundle.
   pub info_plist_path: Option<PathBuf>,
+  /// The local network usage written to the `Info.plist` file.
+  pub local_network: LocalNetworkConfig,
 }
 
 /// Configuration for a target language for the WiX build.
@@ -252,6 +323,10 @@ pub struct WixSettings {
   pub dialog_image_path: Option<PathBuf>,
   /// Enables FIPS compliant algorithms.
   pub fips_compliant: bool,
+  /// Whether the installation will be for all users or just the current user.
+  pub install_mode: WixInstallMode,
+  /// The name of the start menu folder. Defaults to the product name.
+  pub start_menu_folder: Option<String>,
 }
 
 /// Settings specific to the NSIS implementation.
@@ -289,6 +364,8 @@ pub struct NsisSettings {
   /// Whether to display a language selector dialog before the installer and uninstaller windows are rendered or not.
   /// By default the OS language is selected, with a fallback to the first language in the `languages` array.
   pub display_language_selector: bool,
+  /// The name of the start menu folder. Defaults to the product name.
+  pub start_menu_folder: Option<String>,
 }
 
 /// The Windows bundle settings.
@@ -323,6 +400,8 @@ pub struct WindowsSettings {
   ///
   /// /// The default value of this flag is `true`.
   pub allow_downgrades: bool,
+  /// A code signing backend to use instead of `signtool` and [`Self::certificate_thumbprint`].
+  pub sign: Option<WindowsSignConfig>,
 }
 
 impl Default for WindowsSettings {
@@ -338,6 +417,7 @@ impl Default for WindowsSettings {
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
       allow_downgrades: true,
+      sign: None,
     }
   }
 }
@@ -358,6 +438,10 @@ pub struct BundleSettings {
   ///
   /// supports glob patterns.
   pub resources: Option<Vec<String>>,
+  /// Whether the resources should be compressed with brotli.
+  pub compress_resources: bool,
+  /// The frontend assets archive of `tauri > externalAssets`, bundled at the root of the resources directory.
+  pub external_assets: Option<PathBuf>,
   /// the app's copyright.
   pub copyright: Option<String>,
   /// the app's category.
@@ -386,12 +470,20 @@ pub struct BundleSettings {
   pub external_bin: Option<Vec<String>>,
   /// Debian-specific settings.
   pub deb: DebianSettings,
+  /// RPM-specific settings.
+  pub rpm: RpmSettings,
+  /// Flatpak-specific settings.
+  pub flatpak: FlatpakSettings,
+  /// Snap-specific settings.
+  pub snap: SnapSettings,
   /// MacOS-specific settings.
   pub macos: MacOsSettings,
   /// Updater configuration.
   pub updater: Option<UpdaterSettings>,
   /// Windows-specific settings.
   pub windows: WindowsSettings,
+  /// Localized bundle metadata, keyed by BCP-47 language tag.
+  pub localizations: HashMap<String, BundleLocalization>,
 }
 
 /// A binary to bundle.
@@ -464,6 +556,8 @@ pub struct Settings {
   binaries: Vec<BundleBinary>,
   /// The target triple.
   target: String,
+  /// Whether the bundles are created even when their inputs did not change.
+  force_bundle: bool,
 }
 
 /// A builder for [`Settings`].
@@ -476,6 +570,7 @@ pub struct SettingsBuilder {
   bundle_settings: BundleSettings,
   binaries: Vec<BundleBinary>,
   target: Option<String>,
+  force_bundle: bool,
 }
 
 impl SettingsBuilder {
@@ -535,6 +630,13 @@ impl SettingsBuilder {
     self
   }
 
+  /// Creates the bundles even when their inputs did not change since they were last created.
+  #[must_use]
+  pub fn force_bundle(mut self, force: bool) -> Self {
+    self.force_bundle = force;
+    self
+  }
+
   /// Builds a Settings from the CLI args.
   ///
   /// Package settings will be read from Cargo.toml.
@@ -564,6 +666,7 @@ impl SettingsBuilder {
         ..self.bundle_settings
       },
       target,
+      force_bundle: self.force_bundle,
     })
   }
 }
@@ -579,6 +682,27 @@ impl Settings {
     self.log_level
   }
 
+  /// Sets whether the bundles are created even when their inputs did not change.
+  pub fn set_force_bundle(&mut self, force: bool) {
+    self.force_bundle = force;
+  }
+
+  /// Returns whether the bundles are created even when their inputs did not change.
+  pub fn force_bundle(&self) -> bool {
+    self.force_bundle
+  }
+
+  /// The settings that affect the content of the bundles, formatted for their fingerprint.
+  pub(crate) fn fingerprint_settings(&self) -> String {
+    let settings = Self {
+      log_level: log::Level::Error,
+      package_types: None,
+      force_bundle: false,
+      ..self.clone()
+    };
+    format!("{settings:#?}")
+  }
+
   /// Returns the directory where the bundle should be placed.
   pub fn project_out_directory(&self) -> &Path {
     &self.project_out_directory
@@ -646,10 +770,17 @@ impl Settings {
       .unwrap_or(std::env::consts::OS)
       .replace("darwin", "macos");
 
+    // package types that are only bundled when explicitly requested
+    let opt_in_types = match target_os.as_str() {
+      "linux" => vec![PackageType::Flatpak, PackageType::Snap],
+      "windows" => vec![PackageType::Portable],
+      _ => vec![],
+    };
+
     let mut platform_types = match target_os.as_str() {
       "macos" => vec![PackageType::MacOsBundle, PackageType::Dmg],
       "ios" => vec![PackageType::IosBundle],
-      "linux" => vec![PackageType::Deb, PackageType::AppImage],
+      "linux" => vec![PackageType::Deb, PackageType::Rpm, PackageType::AppImage],
       "windows" => vec![PackageType::WindowsMsi, PackageType::Nsis],
       os => {
         return Err(crate::Error::GenericError(format!(
@@ -663,14 +794,33 @@ impl Settings {
       platform_types.push(PackageType::Updater)
     }
 
+    // when cross compiling, the default package types might not be supported on the current host
+    if self.package_types.is_none() && target_os != std::env::consts::OS {
+      let (supported, unsupported): (Vec<_>, Vec<_>) = platform_types
+        .into_iter()
+        .partition(|t| PackageType::all().contains(t));
+      if !unsupported.is_empty() {
+        log::warn!(
+          "Skipping the {} bundles since they can't be created on {} hosts",
+          unsupported
+            .iter()
+            .map(|t| t.short_name())
+            .collect::<Vec<_>>()
+            .join(", "),
+          std::env::consts::OS
+        );
+      }
+      platform_types = supported;
+    }
+
     if let Some(package_types) = &self.package_types {
       let mut types = vec![];
       for package_type in package_types {
         let package_type = *package_type;
         if platform_types
-          .clone()
-          .into_iter()
-          .any(|t| t == package_type)
+          .iter()
+          .chain(opt_in_types.iter())
+          .any(|t| *t == package_type)
         {
           types.push(package_type);
         }
@@ -738,14 +888,58 @@ impl Settings {
     Ok(())
   }
 
+  /// Returns the frontend assets archive to bundle, if the assets are not embedded in the binary.
+  pub fn external_assets(&self) -> Option<&PathBuf> {
+    self.bundle_settings.external_assets.as_ref()
+  }
+
   /// Copies resources to a path.
   pub fn copy_resources(&self, path: &Path) -> crate::Result<()> {
+    for (src, target) in self.resource_entries()? {
+      common::copy_file(&src, path.join(target))?;
+    }
+    Ok(())
+  }
+
+  /// Returns the resources to bundle as `(source path, path relative to the resources directory)` pairs.
+  ///
+  /// When the resources must be compressed, each one is compressed with brotli
+  /// to the `compressed-resources` folder of the project output directory and gets the `.br` extension.
+  pub fn resource_entries(&self) -> crate::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut entries = Vec::new();
+    let mut added_resources = HashSet::new();
     for src in self.resource_files() {
       let src = src?;
-      let dest = path.join(tauri_utils::resources::resource_relpath(&src));
-      common::copy_file(&src, dest)?;
+      // directories with glob patterns might yield the same file twice
+      if !added_resources.insert(src.clone()) {
+        continue;
+      }
+      let target = tauri_utils::resources::resource_relpath(&src);
+      if self.bundle_settings.compress_resources {
+        let mut file_name = target.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(tauri_utils::resources::COMPRESSED_RESOURCE_EXTENSION);
+        let target = target.with_file_name(file_name);
+        let compressed = self
+          .project_out_directory
+          .join("compressed-resources")
+          .join(&target);
+        if let Some(parent) = compressed.parent() {
+          std::fs::create_dir_all(parent)?;
+        }
+        tauri_utils::resources::compress_resource(&src, &compressed)
+          .with_context(|| format!("Failed to compress resource {}", src.display()))?;
+        entries.push((compressed, target));
+      } else {
+        entries.push((src, target));
+      }
     }
-    Ok(())
+    // the app loads the archive by its file name and it is already compressed
+    if let Some(archive) = &self.bundle_settings.external_assets {
+      let target = PathBuf::from(archive.file_name().unwrap_or_default());
+      entries.push((archive.clone(), target));
+    }
+    Ok(entries)
   }
 
   /// Returns the version string of the bundle.
@@ -800,11 +994,36 @@ impl Settings {
     self.bundle_settings.long_description.as_deref()
   }
 
+  /// Returns the localized bundle metadata, keyed by BCP-47 language tag.
+  pub fn localizations(&self) -> &HashMap<String, BundleLocalization> {
+    &self.bundle_settings.localizations
+  }
+
+  /// Returns the bundle localization for the given BCP-47 language tag.
+  pub fn localization(&self, locale: &str) -> Option<&BundleLocalization> {
+    BundleLocalization::find(&self.bundle_settings.localizations, locale)
+  }
+
   /// Returns the debian settings.
   pub fn deb(&self) -> &DebianSettings {
     &self.bundle_settings.deb
   }
 
+  /// Returns the RPM settings.
+  pub fn rpm(&self) -> &RpmSettings {
+    &self.bundle_settings.rpm
+  }
+
+  /// Returns the Flatpak settings.
+  pub fn flatpak(&self) -> &FlatpakSettings {
+    &self.bundle_settings.flatpak
+  }
+
+  /// Returns the Snap settings.
+  pub fn snap(&self) -> &SnapSettings {
+    &self.bundle_settings.snap
+  }
+
   /// Returns the MacOS settings.
   pub fn macos(&self) -> &MacOsSettings {
     &self.bundle_settings.macos
@@ -6,7 +6,7 @@
 #[cfg(target_os = "windows")]
 pub mod msi;
 pub mod nsis;
-#[cfg(target_os = "windows")]
+pub mod portable;
 pub mod sign;
 
 mod util;
@@ -15,7 +15,7 @@ use crate::bundle::{
 };
 use anyhow::{bail, Context};
 use handlebars::{to_json, Handlebars};
-use log::info;
+use log::{info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -25,8 +25,9 @@ use std::{
   path::{Path, PathBuf},
   process::Command,
 };
+use tauri_utils::config::{WebviewInstallMode, WixInstallMode};
 use tauri_utils::display_path;
-use tauri_utils::{config::WebviewInstallMode, resources::resource_relpath};
+use tauri_utils::platform::WindowsInstallScope;
 use uuid::Uuid;
 
 // URLS for the WIX toolchain.  Can be used for cross-platform compilation.
@@ -518,25 +519,10 @@ pub fn build_wix_app_installer(
 
   if let Some(wix) = &settings.windows().wix {
     if let Some(license) = &wix.license {
-      if license.ends_with(".rtf") {
-        data.insert("license", to_json(license));
-      } else {
-        let license_contents = read_to_string(license)?;
-        let license_rtf = format!(
-          r#"{{\rtf1\ansi\ansicpg1252\deff0\nouicompat\deflang1033{{\fonttbl{{\f0\fnil\fcharset0 Calibri;}}}}
-{{\*\generator Riched20 10.0.18362}}\viewkind4\uc1
-\pard\sa200\sl276\slmult1\f0\fs22\lang9 {}\par
-}}
- "#,
-          license_contents.replace('\n', "\\par ")
-        );
-        let rtf_output_path = settings
-          .project_out_directory()
-          .join("wix")
-          .join("LICENSE.rtf");
-        std::fs::write(&rtf_output_path, license_rtf)?;
-        data.insert("license", to_json(rtf_output_path));
-      }
+      data.insert(
+        "license",
+        to_json(license_rtf_path(settings, license, "LICENSE.rtf")?),
+      );
     }
   }
 
@@ -610,6 +596,8 @@ pub fn build_wix_app_installer(
   handlebars.register_escape_fn(handlebars::no_escape);
   let mut custom_template_path = None;
   let mut enable_elevated_update_task = false;
+  let mut install_mode = WixInstallMode::PerMachine;
+  let mut start_menu_folder = settings.product_name().to_string();
 
   if let Some(wix) = &settings.windows().wix {
     data.insert("component_group_refs", to_json(&wix.component_group_refs));
@@ -620,6 +608,10 @@ pub fn build_wix_app_installer(
     fragment_paths = wix.fragment_paths.clone();
     enable_elevated_update_task = wix.enable_elevated_update_task;
     custom_template_path = wix.template.clone();
+    install_mode = wix.install_mode;
+    if let Some(folder) = &wix.start_menu_folder {
+      start_menu_folder = folder.clone();
+    }
 
     if let Some(banner_path) = &wix.banner_path {
       let filename = banner_path
@@ -646,6 +638,18 @@ pub fn build_wix_app_installer(
     }
   }
 
+  let per_user = install_mode == WixInstallMode::CurrentUser;
+  if per_user && enable_elevated_update_task {
+    warn!("The elevated update task is not supported by per-user MSI installers, skipping it.");
+    enable_elevated_update_task = false;
+  }
+  data.insert("per_user", to_json(per_user));
+  data.insert(
+    "install_scope",
+    to_json(if per_user { "perUser" } else { "perMachine" }),
+  );
+  data.insert("start_menu_folder", to_json(start_menu_folder));
+
   if let Some(path) = custom_template_path {
     handlebars
       .register_template_string("main.wxs", read_to_string(path)?)
@@ -659,16 +663,16 @@ pub fn build_wix_app_installer(
   }
 
   if enable_elevated_update_task {
-    data.insert(
-      "msiexec_args",
-      to_json(
-        settings
-          .updater()
-          .and_then(|updater| updater.msiexec_args)
-          .map(|args| args.join(" "))
-          .unwrap_or_else(|| "/passive".to_string()),
-      ),
-    );
+    // the elevated update task is only available for the per-machine installers
+    let msiexec_args = settings
+      .updater()
+      .and_then(|updater| updater.msiexec_args)
+      .unwrap_or(&["/passive"])
+      .iter()
+      .chain(WindowsInstallScope::PerMachine.msiexec_args())
+      .copied()
+      .collect::<Vec<_>>();
+    data.insert("msiexec_args", to_json(msiexec_args.join(" ")));
 
     // Create the update task XML
     let mut skip_uac_task = Handlebars::new();
@@ -760,10 +764,15 @@ pub fn build_wix_app_installer(
       ),
     };
 
+    let localization = settings.localization(&language);
+    let product_name = localization
+      .and_then(|l| l.product_name.as_deref())
+      .unwrap_or_else(|| settings.product_name());
+
     let locale_strings = include_str!("./default-locale-strings.xml")
       .replace("__language__", &language_metadata.lang_id.to_string())
       .replace("__codepage__", &language_metadata.ascii_code.to_string())
-      .replace("__productName__", settings.product_name());
+      .replace("__productName__", product_name);
 
     let mut unset_locale_strings = String::new();
     let prefix_len = "<String ".len();
@@ -789,7 +798,7 @@ pub fn build_wix_app_installer(
       fileout.write_all(locale_contents.as_bytes())?;
     }
 
-    let arguments = vec![
+    let mut arguments = vec![
       format!(
         "-cultures:{}",
         if language == "en-US" {
@@ -802,6 +811,22 @@ pub fn build_wix_app_installer(
       display_path(&locale_path),
       "*.wixobj".into(),
     ];
+    // the license dialog is only rendered when `tauri > bundle > windows > wix > license` is set
+    if data.contains_key("license") {
+      if let Some(license) = localization.and_then(|l| l.license.as_ref()) {
+        let license_path =
+          license_rtf_path(settings, license, &format!("LICENSE-{}.rtf", language))?;
+        arguments.push(format!("-dWixUILicenseRtf={}", display_path(license_path)));
+      }
+    }
+    if per_user {
+      // per-user components install to the user profile and are tracked by HKCU registry values instead
+      arguments.extend([
+        "-sice:ICE38".into(),
+        "-sice:ICE64".into(),
+        "-sice:ICE91".into(),
+      ]);
+    }
     let msi_output_path = output_path.join("output.msi");
     let msi_path = app_installer_output_path(settings, &language, &app_version, updater)?;
     create_dir_all(msi_path.parent().unwrap())?;
@@ -823,6 +848,30 @@ pub fn build_wix_app_installer(
   Ok(output_paths)
 }
 
+/// Returns the path to the license file in the RTF format, converting it to RTF if needed.
+fn license_rtf_path(
+  settings: &Settings,
+  license: &Path,
+  file_name: &str,
+) -> crate::Result<PathBuf> {
+  if license.extension().map_or(false, |ext| ext == "rtf") {
+    return Ok(license.to_path_buf());
+  }
+
+  let license_contents = read_to_string(license)?;
+  let license_rtf = format!(
+    r#"{{\rtf1\ansi\ansicpg1252\deff0\nouicompat\deflang1033{{\fonttbl{{\f0\fnil\fcharset0 Calibri;}}}}
+{{\*\generator Riched20 10.0.18362}}\viewkind4\uc1
+\pard\sa200\sl276\slmult1\f0\fs22\lang9 {}\par
+}}
+ "#,
+    license_contents.replace('\n', "\\par ")
+  );
+  let rtf_output_path = settings.project_out_directory().join("wix").join(file_name);
+  std::fs::write(&rtf_output_path, license_rtf)?;
+  Ok(rtf_output_path)
+}
+
 /// Generates the data required for the external binaries and extra binaries bundling.
 fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   let mut binaries = Vec::new();
@@ -910,9 +959,7 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
 
   let mut added_resources = Vec::new();
 
-  for src in settings.resource_files() {
-    let src = src?;
-
+  for (src, target_path) in settings.resource_entries()? {
     let resource_path = cwd
       .join(src.clone())
       .into_os_string()
@@ -935,7 +982,6 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
     };
 
     // split the resource path directories
-    let target_path = resource_relpath(&src);
     let components_count = target_path.components().count();
     let directories = target_path
       .components()
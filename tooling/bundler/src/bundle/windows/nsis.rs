@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-#[cfg(target_os = "windows")]
 use crate::bundle::windows::util::try_sign;
 use crate::{
   bundle::{
@@ -20,10 +19,7 @@ use tauri_utils::display_path;
 use anyhow::Context;
 use handlebars::{to_json, Handlebars};
 use log::{info, warn};
-use tauri_utils::{
-  config::{NSISInstallerMode, WebviewInstallMode},
-  resources::resource_relpath,
-};
+use tauri_utils::config::{NSISInstallerMode, WebviewInstallMode};
 
 use std::{
   collections::{BTreeMap, HashMap},
@@ -67,20 +63,27 @@ const NSIS_REQUIRED_FILES: &[&str] = &[
 /// Returns a vector of PathBuf that shows where the NSIS installer was created.
 pub fn bundle_project(settings: &Settings, updater: bool) -> crate::Result<Vec<PathBuf>> {
   let tauri_tools_path = dirs_next::cache_dir().unwrap().join("tauri");
+  let nsis_toolset_path = get_nsis_toolset(&tauri_tools_path)?;
+
+  build_nsis_app_installer(settings, &nsis_toolset_path, &tauri_tools_path, updater)
+}
+
+/// Gets the path to the NSIS toolset, downloading it if needed.
+pub(super) fn get_nsis_toolset(tauri_tools_path: &Path) -> crate::Result<PathBuf> {
   let nsis_toolset_path = tauri_tools_path.join("NSIS");
 
   if !nsis_toolset_path.exists() {
-    get_and_extract_nsis(&nsis_toolset_path, &tauri_tools_path)?;
+    get_and_extract_nsis(&nsis_toolset_path, tauri_tools_path)?;
   } else if NSIS_REQUIRED_FILES
     .iter()
     .any(|p| !nsis_toolset_path.join(p).exists())
   {
     warn!("NSIS directory is missing some files. Recreating it.");
     std::fs::remove_dir_all(&nsis_toolset_path)?;
-    get_and_extract_nsis(&nsis_toolset_path, &tauri_tools_path)?;
+    get_and_extract_nsis(&nsis_toolset_path, tauri_tools_path)?;
   }
 
-  build_nsis_app_installer(settings, &nsis_toolset_path, &tauri_tools_path, updater)
+  Ok(nsis_toolset_path)
 }
 
 // Gets NSIS and verifies the download via Sha1
@@ -121,7 +124,7 @@ fn get_and_extract_nsis(nsis_toolset_path: &Path, _tauri_tools_path: &Path) -> c
   Ok(())
 }
 
-fn add_build_number_if_needed(version_str: &str) -> anyhow::Result<String> {
+pub(super) fn add_build_number_if_needed(version_str: &str) -> anyhow::Result<String> {
   let version = semver::Version::parse(version_str).context("invalid app version")?;
   if !version.build.is_empty() {
     let build = version.build.parse::<u64>();
@@ -160,19 +163,13 @@ fn build_nsis_app_installer(
 
   info!("Target: {}", arch);
 
-  #[cfg(target_os = "windows")]
-  {
-    let main_binary = settings
-      .binaries()
-      .iter()
-      .find(|bin| bin.main())
-      .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
-    let app_exe_source = settings.binary_path(main_binary);
-    try_sign(&app_exe_source, settings)?;
-  }
-
-  #[cfg(not(target_os = "windows"))]
-  info!("Code signing is currently only supported on Windows hosts, skipping...");
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+  let app_exe_source = settings.binary_path(main_binary).with_extension("exe");
+  try_sign(&app_exe_source, settings)?;
 
   let output_path = settings.project_out_directory().join("nsis").join(arch);
   if output_path.exists() {
@@ -248,6 +245,9 @@ fn build_nsis_app_installer(
       "display_language_selector",
       to_json(nsis.display_language_selector && languages.len() > 1),
     );
+    if let Some(start_menu_folder) = &nsis.start_menu_folder {
+      data.insert("start_menu_folder", to_json(start_menu_folder));
+    }
   }
   data.insert(
     "install_mode",
@@ -278,19 +278,11 @@ fn build_nsis_app_installer(
     ),
   );
 
-  let main_binary = settings
-    .binaries()
-    .iter()
-    .find(|bin| bin.main())
-    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
   data.insert(
     "main_binary_name",
     to_json(main_binary.name().replace(".exe", "")),
   );
-  data.insert(
-    "main_binary_path",
-    to_json(settings.binary_path(main_binary).with_extension("exe")),
-  );
+  data.insert("main_binary_path", to_json(&app_exe_source));
 
   let out_file = "nsis-output.exe";
   data.insert("out_file", to_json(out_file));
@@ -301,6 +293,86 @@ fn build_nsis_app_installer(
   let binaries = generate_binaries_data(settings)?;
   data.insert("binaries", to_json(binaries));
 
+  insert_webview2_data(settings, tauri_tools_path, arch, updater, &mut data)?;
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(escape_nsis_string);
+  if let Some(path) = custom_template_path {
+    handlebars
+      .register_template_string("installer.nsi", std::fs::read_to_string(path)?)
+      .map_err(|e| e.to_string())
+      .expect("Failed to setup custom handlebar template");
+  } else {
+    handlebars
+      .register_template_string("installer.nsi", include_str!("./templates/installer.nsi"))
+      .map_err(|e| e.to_string())
+      .expect("Failed to setup handlebar template");
+  }
+  let installer_nsi_path = output_path.join("installer.nsi");
+  write_ut16_le_with_bom(
+    &installer_nsi_path,
+    handlebars.render("installer.nsi", &data)?.as_str(),
+  )?;
+
+  for (lang, data) in languages_data.iter() {
+    if let Some(content) = data {
+      write_ut16_le_with_bom(output_path.join(lang).with_extension("nsh"), content)?;
+    }
+  }
+
+  let package_base_name = format!(
+    "{}_{}_{}-setup",
+    main_binary.name().replace(".exe", ""),
+    settings.version_string(),
+    arch,
+  );
+
+  let nsis_output_path = output_path.join(out_file);
+  let nsis_installer_path = settings.project_out_directory().to_path_buf().join(format!(
+    "bundle/{}/{}.exe",
+    if updater {
+      NSIS_UPDATER_OUTPUT_FOLDER_NAME
+    } else {
+      NSIS_OUTPUT_FOLDER_NAME
+    },
+    package_base_name
+  ));
+  create_dir_all(nsis_installer_path.parent().unwrap())?;
+
+  info!(action = "Running"; "makensis.exe to produce {}", display_path(&nsis_installer_path));
+
+  #[cfg(target_os = "windows")]
+  let mut nsis_cmd = Command::new(_nsis_toolset_path.join("makensis.exe"));
+  #[cfg(not(target_os = "windows"))]
+  let mut nsis_cmd = Command::new("makensis");
+
+  nsis_cmd
+    .arg(match settings.log_level() {
+      log::Level::Error => "-V1",
+      log::Level::Warn => "-V2",
+      log::Level::Info => "-V3",
+      _ => "-V4",
+    })
+    .arg(installer_nsi_path)
+    .current_dir(output_path)
+    .piped()
+    .context("error running makensis.exe")?;
+
+  rename(nsis_output_path, &nsis_installer_path)?;
+
+  try_sign(&nsis_installer_path, settings)?;
+
+  Ok(vec![nsis_installer_path])
+}
+
+/// Inserts the WebView2 runtime installation data used by the NSIS templates.
+pub(super) fn insert_webview2_data(
+  settings: &Settings,
+  tauri_tools_path: &Path,
+  arch: &str,
+  updater: bool,
+  data: &mut BTreeMap<&'static str, serde_json::Value>,
+) -> crate::Result<()> {
   let silent_webview2_install = if let WebviewInstallMode::DownloadBootstrapper { silent }
   | WebviewInstallMode::EmbedBootstrapper { silent }
   | WebviewInstallMode::OfflineInstaller { silent } =
@@ -387,102 +459,35 @@ fn build_nsis_app_installer(
     _ => {}
   }
 
-  let mut handlebars = Handlebars::new();
-  handlebars.register_escape_fn(|s| {
-    let mut output = String::new();
-    for c in s.chars() {
-      match c {
-        '\"' => output.push_str("$\\\""),
-        '$' => output.push_str("$$"),
-        '`' => output.push_str("$\\`"),
-        '\n' => output.push_str("$\\n"),
-        '\t' => output.push_str("$\\t"),
-        '\r' => output.push_str("$\\r"),
-        _ => output.push(c),
-      }
-    }
-    output
-  });
-  if let Some(path) = custom_template_path {
-    handlebars
-      .register_template_string("installer.nsi", std::fs::read_to_string(path)?)
-      .map_err(|e| e.to_string())
-      .expect("Failed to setup custom handlebar template");
-  } else {
-    handlebars
-      .register_template_string("installer.nsi", include_str!("./templates/installer.nsi"))
-      .map_err(|e| e.to_string())
-      .expect("Failed to setup handlebar template");
-  }
-  let installer_nsi_path = output_path.join("installer.nsi");
-  write_ut16_le_with_bom(
-    &installer_nsi_path,
-    handlebars.render("installer.nsi", &data)?.as_str(),
-  )?;
+  Ok(())
+}
 
-  for (lang, data) in languages_data.iter() {
-    if let Some(content) = data {
-      write_ut16_le_with_bom(output_path.join(lang).with_extension("nsh"), content)?;
+/// Escapes a string to be used in a NSIS script.
+pub(super) fn escape_nsis_string(s: &str) -> String {
+  let mut output = String::new();
+  for c in s.chars() {
+    match c {
+      '\"' => output.push_str("$\\\""),
+      '$' => output.push_str("$$"),
+      '`' => output.push_str("$\\`"),
+      '\n' => output.push_str("$\\n"),
+      '\t' => output.push_str("$\\t"),
+      '\r' => output.push_str("$\\r"),
+      _ => output.push(c),
     }
   }
-
-  let package_base_name = format!(
-    "{}_{}_{}-setup",
-    main_binary.name().replace(".exe", ""),
-    settings.version_string(),
-    arch,
-  );
-
-  let nsis_output_path = output_path.join(out_file);
-  let nsis_installer_path = settings.project_out_directory().to_path_buf().join(format!(
-    "bundle/{}/{}.exe",
-    if updater {
-      NSIS_UPDATER_OUTPUT_FOLDER_NAME
-    } else {
-      NSIS_OUTPUT_FOLDER_NAME
-    },
-    package_base_name
-  ));
-  create_dir_all(nsis_installer_path.parent().unwrap())?;
-
-  info!(action = "Running"; "makensis.exe to produce {}", display_path(&nsis_installer_path));
-
-  #[cfg(target_os = "windows")]
-  let mut nsis_cmd = Command::new(_nsis_toolset_path.join("makensis.exe"));
-  #[cfg(not(target_os = "windows"))]
-  let mut nsis_cmd = Command::new("makensis");
-
-  nsis_cmd
-    .arg(match settings.log_level() {
-      log::Level::Error => "-V1",
-      log::Level::Warn => "-V2",
-      log::Level::Info => "-V3",
-      _ => "-V4",
-    })
-    .arg(installer_nsi_path)
-    .current_dir(output_path)
-    .piped()
-    .context("error running makensis.exe")?;
-
-  rename(nsis_output_path, &nsis_installer_path)?;
-
-  // Code signing is currently only supported on Windows hosts
-  #[cfg(target_os = "windows")]
-  try_sign(&nsis_installer_path, settings)?;
-
-  Ok(vec![nsis_installer_path])
+  output
 }
 
 /// BTreeMap<OriginalPath, (ParentOfTargetPath, TargetPath)>
-type ResourcesMap = BTreeMap<PathBuf, (String, PathBuf)>;
-fn generate_resource_data(settings: &Settings) -> crate::Result<ResourcesMap> {
+pub(super) type ResourcesMap = BTreeMap<PathBuf, (String, PathBuf)>;
+pub(super) fn generate_resource_data(settings: &Settings) -> crate::Result<ResourcesMap> {
   let mut resources = ResourcesMap::new();
   let cwd = std::env::current_dir()?;
 
   let mut added_resources = Vec::new();
 
-  for src in settings.resource_files() {
-    let src = src?;
+  for (src, target_path) in settings.resource_entries()? {
     let resource_path = dunce::canonicalize(cwd.join(&src))?;
 
     // In some glob resource paths like `assets/**/*` a file might appear twice
@@ -493,7 +498,6 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourcesMap> {
     }
     added_resources.push(resource_path.clone());
 
-    let target_path = resource_relpath(&src);
     resources.insert(
       resource_path,
       (
@@ -510,8 +514,8 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourcesMap> {
 }
 
 /// BTreeMap<OriginalPath, TargetFileName>
-type BinariesMap = BTreeMap<PathBuf, String>;
-fn generate_binaries_data(settings: &Settings) -> crate::Result<BinariesMap> {
+pub(super) type BinariesMap = BTreeMap<PathBuf, String>;
+pub(super) fn generate_binaries_data(settings: &Settings) -> crate::Result<BinariesMap> {
   let mut binaries = BinariesMap::new();
   let cwd = std::env::current_dir()?;
 
@@ -575,7 +579,7 @@ fn get_lang_data(
   Ok(Some((lang_path, lang_content)))
 }
 
-fn write_ut16_le_with_bom<P: AsRef<Path>>(path: P, content: &str) -> crate::Result<()> {
+pub(super) fn write_ut16_le_with_bom<P: AsRef<Path>>(path: P, content: &str) -> crate::Result<()> {
   use std::fs::File;
   use std::io::{BufWriter, Write};
 
@@ -0,0 +1,145 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The portable executable is a NSIS launcher that embeds the app binary, resources and sidecars.
+// On startup it installs the WebView2 runtime if needed, extracts the app to a temporary
+// directory, runs it forwarding the command line arguments and removes the directory on exit.
+
+use crate::{
+  bundle::{
+    common::CommandExt,
+    windows::nsis::{
+      add_build_number_if_needed, escape_nsis_string, generate_binaries_data,
+      generate_resource_data, get_nsis_toolset, insert_webview2_data, write_ut16_le_with_bom,
+    },
+    windows::util::try_sign,
+  },
+  Settings,
+};
+use tauri_utils::display_path;
+
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use log::info;
+
+use std::{
+  collections::BTreeMap,
+  fs::{create_dir_all, remove_dir_all, rename},
+  path::PathBuf,
+  process::Command,
+};
+
+/// Runs all of the commands to build the portable executable.
+/// Returns a vector of PathBuf that shows where the executable was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let tauri_tools_path = dirs_next::cache_dir().unwrap().join("tauri");
+  let _nsis_toolset_path = get_nsis_toolset(&tauri_tools_path)?;
+
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+  let main_binary_path = settings.binary_path(main_binary).with_extension("exe");
+
+  try_sign(&main_binary_path, settings)?;
+
+  let output_path = settings.project_out_directory().join("portable").join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  create_dir_all(&output_path)?;
+
+  let mut data = BTreeMap::new();
+
+  #[cfg(not(target_os = "windows"))]
+  {
+    let mut dir = dirs_next::cache_dir().unwrap();
+    dir.extend(["tauri", "NSIS", "Plugins", "x86-unicode"]);
+    data.insert("additional_plugins_path", to_json(dir));
+  }
+
+  let version = settings.version_string();
+  data.insert("product_name", to_json(settings.product_name()));
+  data.insert("short_description", to_json(settings.short_description()));
+  data.insert("copyright", to_json(settings.copyright_string()));
+  data.insert("version", to_json(version));
+  data.insert(
+    "version_with_build",
+    to_json(add_build_number_if_needed(version)?),
+  );
+  data.insert(
+    "icon",
+    to_json(dunce::canonicalize(&settings.windows().icon_path)?),
+  );
+  data.insert(
+    "main_binary_name",
+    to_json(main_binary.name().replace(".exe", "")),
+  );
+  data.insert("main_binary_path", to_json(&main_binary_path));
+
+  let out_file = "portable-output.exe";
+  data.insert("out_file", to_json(out_file));
+  data.insert("resources", to_json(generate_resource_data(settings)?));
+  data.insert("binaries", to_json(generate_binaries_data(settings)?));
+
+  insert_webview2_data(settings, &tauri_tools_path, arch, false, &mut data)?;
+
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(escape_nsis_string);
+  handlebars
+    .register_template_string("portable.nsi", include_str!("./templates/portable.nsi"))
+    .map_err(|e| e.to_string())
+    .expect("Failed to setup handlebar template");
+  let portable_nsi_path = output_path.join("portable.nsi");
+  write_ut16_le_with_bom(
+    &portable_nsi_path,
+    handlebars.render("portable.nsi", &data)?.as_str(),
+  )?;
+
+  let portable_path = settings.project_out_directory().join(format!(
+    "bundle/portable/{}_{}_{}-portable.exe",
+    main_binary.name().replace(".exe", ""),
+    version,
+    arch,
+  ));
+  create_dir_all(portable_path.parent().unwrap())?;
+
+  info!(action = "Running"; "makensis.exe to produce {}", display_path(&portable_path));
+
+  #[cfg(target_os = "windows")]
+  let mut nsis_cmd = Command::new(_nsis_toolset_path.join("makensis.exe"));
+  #[cfg(not(target_os = "windows"))]
+  let mut nsis_cmd = Command::new("makensis");
+
+  nsis_cmd
+    .arg(match settings.log_level() {
+      log::Level::Error => "-V1",
+      log::Level::Warn => "-V2",
+      log::Level::Info => "-V3",
+      _ => "-V4",
+    })
+    .arg(portable_nsi_path)
+    .current_dir(&output_path)
+    .piped()
+    .context("error running makensis.exe")?;
+
+  rename(output_path.join(out_file), &portable_path)?;
+
+  try_sign(&portable_path, settings)?;
+
+  Ok(vec![portable_path])
+}
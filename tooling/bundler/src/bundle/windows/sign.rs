@@ -4,12 +4,16 @@
 // SPDX-License-Identifier: MIT
 
 use crate::bundle::common::CommandExt;
+#[cfg(target_os = "windows")]
 use bitness::{self, Bitness};
-use log::{debug, info};
-use std::{
-  path::{Path, PathBuf},
-  process::Command,
-};
+#[cfg(target_os = "windows")]
+use log::debug;
+use log::info;
+#[cfg(target_os = "windows")]
+use std::path::PathBuf;
+use std::{path::Path, process::Command};
+use tauri_utils::config::WindowsSignConfig;
+#[cfg(target_os = "windows")]
 use winreg::{
   enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY},
   RegKey,
@@ -18,12 +22,14 @@ use winreg::{
 pub struct SignParams {
   pub product_name: String,
   pub digest_algorithm: String,
-  pub certificate_thumbprint: String,
+  pub certificate_thumbprint: Option<String>,
   pub timestamp_url: Option<String>,
   pub tsp: bool,
+  pub sign: Option<WindowsSignConfig>,
 }
 
 // sign code forked from https://github.com/forbjok/rust-codesign
+#[cfg(target_os = "windows")]
 fn locate_signtool() -> crate::Result<PathBuf> {
   const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
   const KITS_ROOT_REGVALUE_NAME: &str = r"KitsRoot10";
@@ -90,11 +96,130 @@ fn locate_signtool() -> crate::Result<PathBuf> {
   Err(crate::Error::SignToolNotFound)
 }
 
+/// Whether the configured signing backend can run on the current host.
+pub fn can_sign_on_host(params: &SignParams) -> bool {
+  cfg!(target_os = "windows")
+    || matches!(
+      params.sign,
+      Some(WindowsSignConfig::Pkcs11 { .. }) | Some(WindowsSignConfig::Custom { .. })
+    )
+}
+
 pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
-  // Convert path to string reference, as we need to pass it as a command-line parameter to signtool
+  // Convert path to string reference, as we need to pass it as a command-line parameter to the signing tool
   let path_str = path.as_ref().to_str().unwrap();
 
-  info!(action = "Signing"; "{} with identity \"{}\"", path_str, params.certificate_thumbprint);
+  let output = match &params.sign {
+    Some(WindowsSignConfig::AzureKeyVault {
+      vault_url,
+      certificate_name,
+      client_id,
+      tenant_id,
+    }) => {
+      info!(action = "Signing"; "{} with Azure Key Vault certificate \"{}\"", path_str, certificate_name);
+
+      let mut cmd = Command::new("azuresigntool");
+      cmd.arg("sign");
+      cmd.args(["-kvu", vault_url]);
+      cmd.args(["-kvc", certificate_name]);
+      let client_id = std::env::var("AZURE_CLIENT_ID")
+        .ok()
+        .or_else(|| client_id.clone());
+      if let Some(client_id) = client_id {
+        cmd.args(["-kvi", &client_id]);
+        if let Some(tenant_id) = std::env::var("AZURE_TENANT_ID")
+          .ok()
+          .or_else(|| tenant_id.clone())
+        {
+          cmd.args(["-kvt", &tenant_id]);
+        }
+        let client_secret = std::env::var("AZURE_CLIENT_SECRET").map_err(|_| {
+          anyhow::anyhow!(
+            "the AZURE_CLIENT_SECRET environment variable is required to sign with Azure Key Vault"
+          )
+        })?;
+        cmd.args(["-kvs", &client_secret]);
+      } else {
+        cmd.arg("-kvm");
+      }
+      cmd.args(["-fd", &params.digest_algorithm]);
+      cmd.args(["-d", &params.product_name]);
+      if let Some(ref timestamp_url) = params.timestamp_url {
+        if params.tsp {
+          cmd.args(["-tr", timestamp_url]);
+          cmd.args(["-td", &params.digest_algorithm]);
+        } else {
+          cmd.args(["-t", timestamp_url]);
+        }
+      }
+      cmd.arg(path_str);
+
+      cmd.output_ok()?
+    }
+    Some(WindowsSignConfig::Pkcs11 {
+      module,
+      key,
+      certificate_path,
+      engine,
+    }) => {
+      info!(action = "Signing"; "{} with PKCS#11 key \"{}\"", path_str, key);
+
+      // osslsigncode can't sign in place
+      let signed_path = path.as_ref().with_extension("signed");
+
+      let mut cmd = Command::new("osslsigncode");
+      cmd.arg("sign");
+      if let Some(engine) = engine {
+        cmd.arg("-pkcs11engine").arg(engine);
+      }
+      cmd.arg("-pkcs11module").arg(module);
+      cmd.args(["-key", key]);
+      cmd.arg("-certs").arg(certificate_path);
+      if let Ok(pin) = std::env::var("TAURI_PKCS11_PIN") {
+        cmd.args(["-pass", &pin]);
+      }
+      cmd.args(["-h", &params.digest_algorithm]);
+      cmd.args(["-n", &params.product_name]);
+      if let Some(ref timestamp_url) = params.timestamp_url {
+        cmd.args([if params.tsp { "-ts" } else { "-t" }, timestamp_url]);
+      }
+      cmd.args(["-in", path_str]);
+      cmd.arg("-out").arg(&signed_path);
+
+      let output = cmd.output_ok()?;
+      std::fs::rename(&signed_path, path.as_ref())?;
+      output
+    }
+    Some(WindowsSignConfig::Custom { command, args }) => {
+      info!(action = "Signing"; "{} with `{}`", path_str, command);
+
+      let mut cmd = Command::new(command);
+      if args.iter().any(|arg| arg.contains("%1")) {
+        cmd.args(args.iter().map(|arg| arg.replace("%1", path_str)));
+      } else {
+        cmd.args(args);
+        cmd.arg(path_str);
+      }
+
+      cmd.output_ok()?
+    }
+    None => sign_with_signtool(path_str, params)?,
+  };
+
+  let stdout = String::from_utf8_lossy(output.stdout.as_slice()).into_owned();
+  info!("{:?}", stdout);
+
+  Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn sign_with_signtool(path_str: &str, params: &SignParams) -> crate::Result<std::process::Output> {
+  let certificate_thumbprint = params
+    .certificate_thumbprint
+    .as_deref()
+    .ok_or_else(|| anyhow::anyhow!("no certificate thumbprint set"))?;
+
+  info!(action = "Signing"; "{} with identity \"{}\"", path_str, certificate_thumbprint);
 
   // Construct SignTool command
   let signtool = locate_signtool()?;
@@ -104,7 +229,7 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
   let mut cmd = Command::new(signtool);
   cmd.arg("sign");
   cmd.args(["/fd", &params.digest_algorithm]);
-  cmd.args(["/sha1", &params.certificate_thumbprint]);
+  cmd.args(["/sha1", certificate_thumbprint]);
   cmd.args(["/d", &params.product_name]);
 
   if let Some(ref timestamp_url) = params.timestamp_url {
@@ -119,10 +244,16 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
   cmd.arg(path_str);
 
   // Execute SignTool command
-  let output = cmd.output_ok()?;
-
-  let stdout = String::from_utf8_lossy(output.stdout.as_slice()).into_owned();
-  info!("{:?}", stdout);
+  cmd.output_ok().map_err(Into::into)
+}
 
-  Ok(())
+#[cfg(not(target_os = "windows"))]
+fn sign_with_signtool(_path_str: &str, params: &SignParams) -> crate::Result<std::process::Output> {
+  Err(
+    anyhow::anyhow!(
+      "signing with certificate {:?} requires signtool, which is only supported on Windows hosts",
+      params.certificate_thumbprint
+    )
+    .into(),
+  )
 }
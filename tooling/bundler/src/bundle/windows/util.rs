@@ -12,10 +12,10 @@ use log::info;
 use sha2::Digest;
 use zip::ZipArchive;
 
-#[cfg(target_os = "windows")]
-use crate::bundle::windows::sign::{sign, SignParams};
-#[cfg(target_os = "windows")]
-use crate::Settings;
+use crate::{
+  bundle::windows::sign::{can_sign_on_host, sign, SignParams},
+  Settings,
+};
 
 pub const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
 pub const WEBVIEW2_X86_INSTALLER_GUID: &str = "a17bde80-b5ab-47b5-8bbb-1cbe93fc6ec9";
@@ -75,31 +75,36 @@ fn verify(data: &Vec<u8>, hash: &str, mut hasher: impl Digest) -> crate::Result<
   }
 }
 
-#[cfg(target_os = "windows")]
+/// Signs the file if code signing is configured and supported on the current host.
 pub fn try_sign(file_path: &std::path::PathBuf, settings: &Settings) -> crate::Result<()> {
   use tauri_utils::display_path;
 
-  if let Some(certificate_thumbprint) = settings.windows().certificate_thumbprint.as_ref() {
+  let windows = settings.windows();
+  if windows.certificate_thumbprint.is_none() && windows.sign.is_none() {
+    return Ok(());
+  }
+
+  let params = SignParams {
+    product_name: settings.product_name().into(),
+    digest_algorithm: windows
+      .digest_algorithm
+      .as_ref()
+      .map(|algorithm| algorithm.to_string())
+      .unwrap_or_else(|| "sha256".to_string()),
+    certificate_thumbprint: windows.certificate_thumbprint.clone(),
+    timestamp_url: windows.timestamp_url.as_ref().map(|url| url.to_string()),
+    tsp: windows.tsp,
+    sign: windows.sign.clone(),
+  };
+
+  if can_sign_on_host(&params) {
     info!(action = "Signing"; "{}", display_path(file_path));
-    sign(
-      file_path,
-      &SignParams {
-        product_name: settings.product_name().into(),
-        digest_algorithm: settings
-          .windows()
-          .digest_algorithm
-          .as_ref()
-          .map(|algorithm| algorithm.to_string())
-          .unwrap_or_else(|| "sha256".to_string()),
-        certificate_thumbprint: certificate_thumbprint.to_string(),
-        timestamp_url: settings
-          .windows()
-          .timestamp_url
-          .as_ref()
-          .map(|url| url.to_string()),
-        tsp: settings.windows().tsp,
-      },
-    )?;
+    sign(file_path, &params)?;
+  } else {
+    info!(
+      "Code signing with signtool or Azure Key Vault is only supported on Windows hosts, skipping {}...",
+      display_path(file_path)
+    );
   }
   Ok(())
 }
@@ -0,0 +1,464 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    app_paths::tauri_dir,
+    config::{
+      fs_scopes, get as get_config, parse::read_capabilities, Capability, Config, Csp,
+      DisabledCspModificationKind,
+    },
+  },
+  OutputFormat, Result,
+};
+use anyhow::Context;
+use base64::Engine;
+use clap::Parser;
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::{
+  fmt,
+  path::{Component, Path},
+};
+
+/// Base directory variables that resolve to folders holding the user files.
+const USER_DIRECTORY_VARIABLES: &[&str] = &[
+  "$HOME",
+  "$DOCUMENT",
+  "$DESKTOP",
+  "$DOWNLOAD",
+  "$PICTURE",
+  "$VIDEO",
+  "$AUDIO",
+  "$PUBLIC",
+  "$DATA",
+  "$LOCALDATA",
+  "$CONFIG",
+  "$CACHE",
+];
+
+#[derive(Debug, Parser)]
+#[clap(about = "Audit the Tauri configuration and Rust project for insecure settings")]
+pub struct Options {
+  /// The output format. `json` prints the report as a JSON object to stdout.
+  #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+  format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+  Low,
+  Medium,
+  High,
+}
+
+impl Severity {
+  /// Points removed from the score of the report.
+  fn penalty(self) -> u32 {
+    match self {
+      Self::Low => 2,
+      Self::Medium => 8,
+      Self::High => 20,
+    }
+  }
+}
+
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Low => write!(f, "low"),
+      Self::Medium => write!(f, "medium"),
+      Self::High => write!(f, "high"),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct Finding {
+  severity: Severity,
+  message: String,
+  remediation: &'static str,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Report {
+  score: u32,
+  findings: Vec<Finding>,
+}
+
+impl Report {
+  fn push(&mut self, severity: Severity, message: impl Into<String>, remediation: &'static str) {
+    self.findings.push(Finding {
+      severity,
+      message: message.into(),
+      remediation,
+    });
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let config = get_config(None)?;
+  let config_guard = config.lock().unwrap();
+  let config = config_guard.as_ref().unwrap();
+
+  let tauri_dir = tauri_dir();
+  let capabilities = read_capabilities(&tauri_dir)?;
+  let manifest_path = tauri_dir.join("Cargo.toml");
+  let manifest: toml::Value = toml::from_str(
+    &std::fs::read_to_string(&manifest_path)
+      .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+  )
+  .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+
+  let report = audit(config, &capabilities, &manifest);
+
+  match options.format {
+    OutputFormat::Text => {
+      for finding in &report.findings {
+        let message = format!("[{}] {}", finding.severity, finding.message);
+        if finding.severity == Severity::Low {
+          info!("{message}");
+        } else {
+          warn!("{message}");
+        }
+        info!(action = "Fix"; "{}", finding.remediation);
+      }
+      info!(action = "Score"; "{}/100 ({} findings)", report.score, report.findings.len());
+    }
+    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+  }
+
+  Ok(())
+}
+
+fn audit(config: &Config, capabilities: &[Capability], manifest: &toml::Value) -> Report {
+  let mut report = Report::default();
+
+  check_csp(config, &mut report);
+  check_remote_ipc_access(config, &mut report);
+  check_capabilities(config, capabilities, &mut report);
+  check_fs_scopes(config, &mut report);
+  check_shell_scope(config, &mut report);
+  check_updater(config, &mut report);
+  check_manifest(manifest, &mut report);
+
+  report
+    .findings
+    .sort_by_key(|f| std::cmp::Reverse(f.severity));
+  report.score = 100u32.saturating_sub(report.findings.iter().map(|f| f.severity.penalty()).sum());
+  report
+}
+
+fn check_csp(config: &Config, report: &mut Report) {
+  let security = &config.tauri.security;
+  match &security.csp {
+    None => report.push(
+      Severity::High,
+      "No Content Security Policy is set, so injected scripts can run and load any resource.",
+      "Set `tauri > security > csp`, starting from `default-src 'self'`.",
+    ),
+    Some(csp) => check_csp_sources(csp, "tauri > security > csp", report),
+  }
+  for (label, csp) in &security.window_csp {
+    check_csp_sources(
+      csp,
+      &format!("the `{label}` window policy on tauri > security > windowCsp"),
+      report,
+    );
+  }
+
+  match &security.dangerous_disable_asset_csp_modification {
+    DisabledCspModificationKind::Flag(true) => report.push(
+      Severity::Medium,
+      "`dangerousDisableAssetCspModification` is enabled, so the nonces and hashes of the app scripts and styles are not injected into the CSP.",
+      "Remove `tauri > security > dangerousDisableAssetCspModification` or only list the directives you need to manage manually.",
+    ),
+    DisabledCspModificationKind::List(directives) if !directives.is_empty() => report.push(
+      Severity::Low,
+      format!(
+        "The asset CSP modification is disabled for the {} directives.",
+        directives.join(", ")
+      ),
+      "Make sure the listed directives are properly configured on `tauri > security > csp`.",
+    ),
+    _ => {}
+  }
+}
+
+fn check_csp_sources(csp: &Csp, location: &str, report: &mut Report) {
+  let policy = csp.to_string();
+  for directive in policy.split(';') {
+    let mut tokens = directive.split_whitespace();
+    let Some(name) = tokens.next() else {
+      continue;
+    };
+    for source in tokens {
+      match source {
+        "'unsafe-eval'" | "'unsafe-inline'" if name != "style-src" => report.push(
+          Severity::Medium,
+          format!("{location} allows {source} on `{name}`."),
+          "Remove the unsafe source; Tauri injects nonces and hashes for the bundled scripts.",
+        ),
+        "*" | "http:" | "https:" | "data:" if name == "script-src" || name == "default-src" => {
+          report.push(
+            Severity::Medium,
+            format!("{location} allows any `{source}` source on `{name}`."),
+            "Only list the origins the app needs to load scripts from.",
+          )
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
+fn check_remote_ipc_access(config: &Config, report: &mut Report) {
+  for scope in &config.tauri.security.dangerous_remote_domain_ipc_access {
+    let scheme = scope.scheme.as_deref().unwrap_or("any scheme");
+    report.push(
+      if scope.scheme.as_deref() == Some("https") {
+        Severity::Medium
+      } else {
+        Severity::High
+      },
+      format!(
+        "The remote domain `{}` ({scheme}) can invoke commands on the {} windows.",
+        scope.domain,
+        scope.windows.join(", ")
+      ),
+      "Remove the `tauri > security > dangerousRemoteDomainIpcAccess` entry or restrict it to the `https` scheme and the plugins the remote content needs.",
+    );
+  }
+}
+
+fn check_capabilities(config: &Config, capabilities: &[Capability], report: &mut Report) {
+  let configured = &config.tauri.security.capabilities;
+  if configured.is_empty() && capabilities.is_empty() {
+    report.push(
+      Severity::Medium,
+      "No capabilities are defined, so every window can invoke every command.",
+      "Define capabilities on `tauri > security > capabilities` or the `capabilities` folder granting each window only the commands it needs.",
+    );
+    return;
+  }
+
+  for capability in configured.iter().chain(capabilities) {
+    if capability.windows.iter().any(|w| w == "*") && !capability.plugins.is_empty() {
+      report.push(
+        Severity::Low,
+        format!(
+          "The `{}` capability grants every window access to the {} plugins.",
+          capability.identifier,
+          capability.plugins.join(", ")
+        ),
+        "List the labels of the windows that need the plugins instead of `*`.",
+      );
+    }
+  }
+}
+
+/// Whether the scope pattern matches most of the filesystem or the whole user folder.
+fn is_broad_pattern(pattern: &Path) -> bool {
+  let has_wildcard = |c: &Component<'_>| c.as_os_str().to_string_lossy().contains(['*', '?', '[']);
+  if !pattern.components().any(|c| has_wildcard(&c)) {
+    return false;
+  }
+
+  let mut normal = Vec::new();
+  let mut rooted = false;
+  for component in pattern.components().take_while(|c| !has_wildcard(c)) {
+    match component {
+      Component::Normal(c) => normal.push(c.to_string_lossy().into_owned()),
+      Component::RootDir | Component::Prefix(_) => rooted = true,
+      _ => {}
+    }
+  }
+  match normal.as_slice() {
+    [] => true,
+    [first] => rooted || USER_DIRECTORY_VARIABLES.contains(&first.as_str()),
+    _ => false,
+  }
+}
+
+fn check_fs_scopes(config: &Config, report: &mut Report) {
+  for (name, scope) in fs_scopes(config) {
+    for path in scope.allowed_paths() {
+      if is_broad_pattern(path) {
+        report.push(
+          Severity::High,
+          format!(
+            "The {name} scope allows `{}`, which exposes most of the user files.",
+            path.display()
+          ),
+          "Allow the app specific directories such as `$APPDATA/**` instead, and deny sensitive paths.",
+        );
+      }
+    }
+  }
+}
+
+fn check_shell_scope(config: &Config, report: &mut Report) {
+  let Some(shell) = config.plugins.0.get("shell") else {
+    return;
+  };
+
+  if shell.get("open") == Some(&JsonValue::Bool(true)) {
+    report.push(
+      Severity::Low,
+      "The shell plugin can open any URL or file with the default application.",
+      "Set `plugins > shell > open` to a regex matching the URLs the app opens.",
+    );
+  }
+
+  for entry in shell
+    .get("scope")
+    .and_then(JsonValue::as_array)
+    .into_iter()
+    .flatten()
+  {
+    let name = entry
+      .get("name")
+      .and_then(JsonValue::as_str)
+      .unwrap_or_default();
+    match entry.get("args") {
+      Some(JsonValue::Bool(true)) => report.push(
+        Severity::High,
+        format!("The `{name}` shell scope entry allows any argument."),
+        "List the allowed arguments, using validators for the dynamic ones.",
+      ),
+      Some(JsonValue::Array(args)) => {
+        let wildcard = args.iter().any(|arg| {
+          matches!(
+            arg.get("validator").and_then(JsonValue::as_str),
+            Some(".*" | ".+" | "^.*$" | "^.+$")
+          )
+        });
+        if wildcard {
+          report.push(
+            Severity::Medium,
+            format!("The `{name}` shell scope entry has an argument validator matching anything."),
+            "Use a validator matching only the expected values.",
+          );
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Whether the value is a base64 encoded minisign public key.
+fn is_valid_pubkey(pubkey: &str) -> bool {
+  base64::engine::general_purpose::STANDARD
+    .decode(pubkey.trim())
+    .ok()
+    .and_then(|key| String::from_utf8(key).ok())
+    .map_or(false, |key| key.starts_with("untrusted comment:"))
+}
+
+fn check_updater(config: &Config, report: &mut Report) {
+  let Some(plugin) = config.plugins.0.get("updater") else {
+    return;
+  };
+
+  let pubkey = plugin
+    .get("pubkey")
+    .and_then(JsonValue::as_str)
+    .unwrap_or_default();
+  if pubkey.trim().is_empty() {
+    report.push(
+      Severity::High,
+      "The updater doesn't pin a public key, so update signatures can't be verified.",
+      "Generate a key pair with `tauri signer generate` and set its public key on `plugins > updater > pubkey`.",
+    );
+  } else if !is_valid_pubkey(pubkey) {
+    report.push(
+      Severity::Medium,
+      "The updater public key is not a valid key generated by `tauri signer generate`.",
+      "Set the content of the `.pub` file generated by `tauri signer generate` on `plugins > updater > pubkey`.",
+    );
+  }
+
+  for endpoint in plugin
+    .get("endpoints")
+    .and_then(JsonValue::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(JsonValue::as_str)
+  {
+    if endpoint.starts_with("http://") {
+      report.push(
+        Severity::High,
+        format!("The updater endpoint `{endpoint}` is not served over HTTPS."),
+        "Serve the update manifest over HTTPS.",
+      );
+    }
+  }
+}
+
+fn check_manifest(manifest: &toml::Value, report: &mut Report) {
+  let features = manifest
+    .get("dependencies")
+    .and_then(|d| d.get("tauri"))
+    .and_then(|t| t.get("features"))
+    .and_then(toml::Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(toml::Value::as_str);
+
+  for feature in features {
+    if feature == "devtools" {
+      report.push(
+        Severity::Medium,
+        "The `devtools` feature of the tauri crate is enabled, so production builds include the web inspector.",
+        "Only enable the `devtools` feature on debug builds, for instance behind a Cargo feature of your app.",
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn broad_patterns() {
+    assert!(is_broad_pattern(Path::new("**")));
+    assert!(is_broad_pattern(Path::new("/**")));
+    assert!(is_broad_pattern(Path::new("/home/*")));
+    assert!(is_broad_pattern(Path::new("$HOME/**")));
+    assert!(!is_broad_pattern(Path::new("$HOME")));
+    assert!(!is_broad_pattern(Path::new("$APPDATA/**")));
+    assert!(!is_broad_pattern(Path::new("$HOME/notes/*.md")));
+  }
+
+  #[test]
+  fn report_score() {
+    let config: Config = serde_json::from_value(serde_json::json!({
+      "tauri": {
+        "security": {
+          "csp": "default-src 'self'; script-src 'self' 'unsafe-eval'",
+          "assetProtocol": {
+            "scope": ["$HOME/**"]
+          }
+        }
+      }
+    }))
+    .unwrap();
+    let manifest: toml::Value = toml::from_str("[dependencies]\ntauri = \"2\"").unwrap();
+
+    let report = audit(&config, &[], &manifest);
+    let severities = report
+      .findings
+      .iter()
+      .map(|f| f.severity)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      severities,
+      vec![Severity::High, Severity::Medium, Severity::Medium]
+    );
+    assert_eq!(report.score, 64);
+  }
+}
@@ -4,25 +4,37 @@
 
 use crate::{
   helpers::{
-    app_paths::{app_dir, tauri_dir},
+    app_paths::{app_dir, select_package, tauri_dir},
     command_env,
     config::{get as get_config, AppUrl, HookCommand, WindowUrl, MERGE_CONFIG_EXTENSION_NAME},
     resolve_merge_config,
     updater_signature::{read_key_from_file, secret_key as updater_secret_key, sign_file},
   },
   interface::{AppInterface, AppSettings, Interface},
-  CommandExt, Result,
+  CommandExt, OutputFormat, Result,
 };
 use anyhow::{bail, Context};
 use base64::Engine;
 use clap::{ArgAction, Parser};
 use log::{debug, error, info, warn};
+use serde::Serialize;
+use sha2::Digest;
 use std::{
+  collections::HashMap,
   env::{set_current_dir, var_os},
   path::{Path, PathBuf},
   process::Command,
+  time::Instant,
 };
 use tauri_bundler::bundle::{bundle_project, Bundle, PackageType};
+use tauri_utils::{
+  assets::{AssetsSizeReport, ASSETS_SIZE_REPORT_FILE_NAME},
+  plugin::{new_permissions, PluginPermissions, PLUGIN_PERMISSIONS_FILE_NAME},
+};
+
+/// The file where the plugin permissions of the last build are stored, relative to the Tauri directory.
+/// It should be committed so permission changes of plugin updates show up on reviews.
+const PLUGIN_PERMISSIONS_SNAPSHOT_PATH: &str = "gen/plugin-permissions.json";
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Tauri build")]
@@ -35,9 +47,15 @@ pub struct Options {
   pub debug: bool,
   /// Target triple to build against.
   ///
+  /// Multiple comma separated targets are built and bundled sequentially, and the artifacts of each target are listed in `<target-dir>/tauri-build-manifest.json`.
+  ///
   /// It must be one of the values outputted by `$rustc --print target-list` or `universal-apple-darwin` for an universal macOS application.
   ///
   /// Note that compiling an universal macOS application requires both `aarch64-apple-darwin` and `x86_64-apple-darwin` targets to be installed.
+  /// Sidecars missing an `universal-apple-darwin` binary are merged from their `aarch64-apple-darwin` and `x86_64-apple-darwin` binaries.
+  ///
+  /// MSVC Windows targets are built with `cargo-xwin` on Linux and macOS hosts when it is installed.
+  /// Only the bundles supported by the host are created when cross compiling.
   #[clap(short, long)]
   pub target: Option<String>,
   /// Space or comma separated list of features to activate
@@ -45,7 +63,8 @@ pub struct Options {
   pub features: Option<Vec<String>>,
   /// Space or comma separated list of bundles to package.
   ///
-  /// Each bundle must be one of `deb`, `appimage`, `msi`, `app` or `dmg` on MacOS and `updater` on all platforms.
+  /// Each bundle must be one of `deb`, `rpm`, `appimage`, `flatpak`, `snap` on Linux, `msi`, `nsis`, `portable` on Windows, `app` or `dmg` on MacOS and `updater` on all platforms.
+  /// Note that `flatpak`, `snap` and `portable` are only built when explicitly requested.
   /// If `none` is specified, the bundler will be skipped.
   ///
   /// Note that the `updater` bundle is not automatically added so you must specify it if the updater is enabled.
@@ -54,18 +73,199 @@ pub struct Options {
   /// JSON string or path to JSON file to merge with tauri.conf.json
   #[clap(short, long)]
   pub config: Option<String>,
+  /// The package of the cargo workspace to build, for workspaces with several Tauri apps.
+  ///
+  /// Its folder must contain the Tauri configuration file.
+  #[clap(short, long)]
+  pub package: Option<String>,
   /// Command line arguments passed to the runner
   pub args: Vec<String>,
   /// Skip prompting for values
   #[clap(long)]
   pub ci: bool,
+  /// Skip the macOS app notarization even if the notarization credentials are set
+  #[clap(long)]
+  pub skip_notarize: bool,
+  /// Create every bundle, even the ones whose inputs did not change since the previous build
+  #[clap(long)]
+  pub force_bundle: bool,
+  /// Report how long each build step took, and let cargo write its compilation timings report
+  #[clap(long)]
+  pub timings: bool,
+  /// The output format. `json` prints the bundles, their hashes and updater signatures to stdout once the build finishes.
+  #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+  pub format: OutputFormat,
+}
+
+/// The build output, printed with `--format json` and written to the build manifest when building multiple targets.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildOutput {
+  product_name: Option<String>,
+  version: Option<String>,
+  identifier: String,
+  targets: Vec<TargetArtifacts>,
+}
+
+/// The artifacts created for a target.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TargetArtifacts {
+  target: String,
+  out_dir: PathBuf,
+  bundles: Vec<BundleArtifacts>,
+  /// The duration of the build steps, set with `--timings`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  timings: Option<Vec<Timing>>,
+}
+
+/// The duration of a build step.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Timing {
+  step: String,
+  seconds: f64,
+}
+
+impl Timing {
+  fn since(step: impl Into<String>, started: Instant) -> Self {
+    Self {
+      step: step.into(),
+      seconds: started.elapsed().as_secs_f64(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleArtifacts {
+  bundle_type: String,
+  artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Artifact {
+  path: PathBuf,
+  /// The file size in bytes, not set for directories such as the macOS app bundle.
+  size: Option<u64>,
+  /// The hex encoded SHA-256 hash of the file, not set for directories.
+  sha256: Option<String>,
+  /// The updater signature of the file.
+  signature: Option<String>,
+}
+
+impl Artifact {
+  fn new(path: PathBuf, signature_paths: &[PathBuf]) -> Result<Self> {
+    let (size, sha256) = if path.is_file() {
+      let mut hasher = sha2::Sha256::new();
+      let mut file = std::fs::File::open(&path)?;
+      let size = std::io::copy(&mut file, &mut hasher)?;
+      (Some(size), Some(hex::encode(hasher.finalize())))
+    } else {
+      (None, None)
+    };
+
+    let mut signature_path = path.clone().into_os_string();
+    signature_path.push(".sig");
+    let signature_path = PathBuf::from(signature_path);
+    let signature = if signature_paths.contains(&signature_path) {
+      Some(std::fs::read_to_string(&signature_path)?)
+    } else {
+      None
+    };
+
+    Ok(Self {
+      path,
+      size,
+      sha256,
+      signature,
+    })
+  }
 }
 
-pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
+/// The file name of the manifest written to the cargo target directory when building multiple targets.
+const BUILD_MANIFEST_FILE_NAME: &str = "tauri-build-manifest.json";
+
+pub fn command(options: Options, verbosity: u8) -> Result<()> {
+  let format = options.format;
+  let targets = options
+    .target
+    .as_deref()
+    .map(|t| {
+      t.split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+
+  let mut artifacts = Vec::new();
+  if targets.len() > 1 {
+    for target in targets {
+      info!(action = "Building"; "target {}", target);
+      let mut options = options.clone();
+      options.target.replace(target.clone());
+      artifacts.push(
+        build_target(options, verbosity)
+          .with_context(|| format!("failed to build target {target}"))?,
+      );
+    }
+  } else {
+    let mut options = options;
+    options.target = targets.into_iter().next();
+    artifacts.push(build_target(options, verbosity)?);
+  }
+
+  let output = {
+    let config = get_config(None)?;
+    let config_guard = config.lock().unwrap();
+    let config_ = config_guard.as_ref().unwrap();
+    BuildOutput {
+      product_name: config_.package.product_name.clone(),
+      version: config_.package.version.clone(),
+      identifier: config_.tauri.bundle.identifier.clone(),
+      targets: artifacts,
+    }
+  };
+
+  if output.targets.len() > 1 {
+    // the out dir is `<target-dir>/<triple>/<profile>`
+    let manifest_path = output.targets[0]
+      .out_dir
+      .parent()
+      .and_then(|p| p.parent())
+      .unwrap()
+      .join(BUILD_MANIFEST_FILE_NAME);
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&output)?)
+      .with_context(|| "failed to write build manifest")?;
+    info!(action = "Finished"; "{} targets, build manifest written to {}", output.targets.len(), tauri_utils::display_path(&manifest_path));
+  }
+
+  if format == OutputFormat::Json {
+    println!("{}", serde_json::to_string(&output)?);
+  }
+
+  Ok(())
+}
+
+fn build_target(mut options: Options, verbosity: u8) -> Result<TargetArtifacts> {
   options.ci = options.ci || std::env::var("CI").is_ok();
   let ci = options.ci;
+  let debug = options.debug;
+  let target = options.target.clone();
+  let report_timings = options.timings;
+  if report_timings && !options.args.iter().any(|a| a == "--timings") {
+    options.args.insert(0, "--timings".into());
+  }
 
+  let mut timings = Vec::new();
+  let started = Instant::now();
   let mut interface = setup(&mut options, false)?;
+  timings.push(Timing::since(
+    "configuration and beforeBuildCommand",
+    started,
+  ));
 
   let config = get_config(options.config.as_deref())?;
   let config_guard = config.lock().unwrap();
@@ -77,10 +277,17 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
   let bin_path = app_settings.app_binary_path(&interface_options)?;
   let out_dir = bin_path.parent().unwrap();
 
+  let started = Instant::now();
   interface.build(interface_options)?;
+  timings.push(Timing::since("app build", started));
+
+  print_assets_size_report(out_dir);
+  check_plugin_permissions(out_dir)?;
 
   let app_settings = interface.app_settings();
 
+  let mut bundles = Vec::new();
+  let mut signed_paths = Vec::new();
   if config_.tauri.bundle.active {
     let package_types = if let Some(names) = &options.bundles {
       let mut types = vec![];
@@ -121,15 +328,20 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
     // if we have a package to bundle, let's run the `before_bundle_command`.
     if package_types.as_ref().map_or(true, |p| !p.is_empty()) {
       if let Some(before_bundle) = config_.build.before_bundle_command.clone() {
+        let started = Instant::now();
         run_hook(
           "beforeBundleCommand",
           before_bundle,
           &interface,
           options.debug,
+          HashMap::new(),
         )?;
+        timings.push(Timing::since("beforeBundleCommand", started));
       }
     }
 
+    let skip_notarize = options.skip_notarize;
+    let force_bundle = options.force_bundle;
     let mut settings = app_settings
       .get_bundler_settings(&options.into(), config_, out_dir, package_types)
       .with_context(|| "failed to build bundler settings")?;
@@ -139,8 +351,12 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
       1 => log::Level::Info,
       _ => log::Level::Trace,
     });
+    settings.set_force_bundle(force_bundle);
 
     // set env vars used by the bundler
+    if skip_notarize {
+      std::env::set_var("TAURI_SKIP_NOTARIZATION", "true");
+    }
     #[cfg(target_os = "linux")]
     {
       if config_.tauri.system_tray.is_some() {
@@ -173,9 +389,11 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
       }
     }
 
-    let bundles = bundle_project(settings)
+    let started = Instant::now();
+    bundles = bundle_project(settings)
       .map_err(|e| anyhow::anyhow!("{:#}", e))
       .with_context(|| "failed to bundle project")?;
+    timings.push(Timing::since("bundling", started));
 
     let updater_bundles: Vec<&Bundle> = bundles
       .iter()
@@ -183,6 +401,7 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
       .collect();
     // If updater is active and we bundled it
     if config_.tauri.bundle.updater.active && !updater_bundles.is_empty() {
+      let started = Instant::now();
       // if no password provided we use an empty string
       let password = var_os("TAURI_KEY_PASSWORD")
         .map(|v| v.to_str().unwrap().to_string())
@@ -210,7 +429,6 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
       let public_key = minisign::PublicKeyBox::from_string(&pub_key_decoded)?.into_public_key()?;
 
       // make sure we have our package built
-      let mut signed_paths = Vec::new();
       for elem in updater_bundles {
         // we expect to have only one path in the vec but we iter if we add
         // another type of updater package who require multiple file signature
@@ -227,13 +445,87 @@ pub fn command(mut options: Options, verbosity: u8) -> Result<()> {
       }
 
       print_signed_updater_archive(&signed_paths)?;
+      timings.push(Timing::since("updater signing", started));
     }
   }
 
-  Ok(())
+  if let Some(after_build) = config_.build.after_build_command.clone() {
+    let bundle_paths = bundles
+      .iter()
+      .flat_map(|bundle| bundle.bundle_paths.iter())
+      .chain(signed_paths.iter());
+    let mut hook_env = HashMap::new();
+    hook_env.insert("TAURI_APP_PATH", bin_path.display().to_string());
+    hook_env.insert(
+      "TAURI_BUNDLE_PATHS",
+      std::env::join_paths(bundle_paths)?
+        .to_string_lossy()
+        .into_owned(),
+    );
+    let started = Instant::now();
+    run_hook(
+      "afterBuildCommand",
+      after_build,
+      &interface,
+      debug,
+      hook_env,
+    )?;
+    timings.push(Timing::since("afterBuildCommand", started));
+  }
+
+  if report_timings {
+    print_timings(&timings, out_dir);
+  }
+
+  Ok(TargetArtifacts {
+    target: match target {
+      Some(target) => target,
+      None => tauri_utils::platform::target_triple()?,
+    },
+    out_dir: out_dir.to_path_buf(),
+    bundles: bundles
+      .into_iter()
+      .map(|bundle| {
+        Ok(BundleArtifacts {
+          bundle_type: bundle.package_type.short_name().into(),
+          artifacts: bundle
+            .bundle_paths
+            .into_iter()
+            .map(|path| Artifact::new(path, &signed_paths))
+            .collect::<Result<_>>()?,
+        })
+      })
+      .collect::<Result<_>>()?,
+    timings: report_timings.then_some(timings),
+  })
+}
+
+fn print_timings(timings: &[Timing], out_dir: &Path) {
+  let total = timings.iter().map(|t| t.seconds).sum::<f64>();
+  info!(action = "Timings"; "{:.2}s in total", total);
+  for timing in timings {
+    info!("{:>8.2}s {}", timing.seconds, timing.step);
+  }
+  // written by cargo to the target directory, the out dir is `<target-dir>/[<triple>/]<profile>`
+  if let Some(report) = out_dir
+    .ancestors()
+    .skip(1)
+    .take(2)
+    .map(|dir| dir.join("cargo-timings").join("cargo-timing.html"))
+    .find(|report| report.exists())
+  {
+    info!(
+      "Cargo compilation timings report: {}",
+      tauri_utils::display_path(&report)
+    );
+  }
 }
 
 pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
+  if let Some(package) = &options.package {
+    select_package(package)?;
+  }
+
   let (merge_config, merge_config_path) = resolve_merge_config(&options.config)?;
   options.config = merge_config;
 
@@ -282,6 +574,7 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
       before_build,
       &interface,
       options.debug,
+      HashMap::new(),
     )?;
   }
 
@@ -328,7 +621,13 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
   Ok(interface)
 }
 
-fn run_hook(name: &str, hook: HookCommand, interface: &AppInterface, debug: bool) -> Result<()> {
+fn run_hook(
+  name: &str,
+  hook: HookCommand,
+  interface: &AppInterface,
+  debug: bool,
+  hook_env: HashMap<&'static str, String>,
+) -> Result<()> {
   let (script, script_cwd) = match hook {
     HookCommand::Script(s) if s.is_empty() => (None, None),
     HookCommand::Script(s) => (Some(s), None),
@@ -340,6 +639,7 @@ fn run_hook(name: &str, hook: HookCommand, interface: &AppInterface, debug: bool
 
     let mut env = command_env(debug);
     env.extend(interface.env());
+    env.extend(hook_env);
 
     debug!("Setting environment for hook {:?}", env);
 
@@ -374,6 +674,79 @@ fn run_hook(name: &str, hook: HookCommand, interface: &AppInterface, debug: bool
   Ok(())
 }
 
+/// Reads a file written to the `OUT_DIR` of the most recent app build script run.
+fn read_build_script_output(out_dir: &Path, file_name: &str) -> Option<Vec<u8>> {
+  std::fs::read_dir(out_dir.join("build"))
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path().join("out").join(file_name))
+    .filter_map(|path| {
+      let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+      Some((modified, path))
+    })
+    .max_by_key(|(modified, _)| *modified)
+    .and_then(|(_, path)| std::fs::read(path).ok())
+}
+
+/// Prints the size of the embedded frontend assets, read from the report written by the most recent app build script run.
+fn print_assets_size_report(out_dir: &Path) {
+  let report = read_build_script_output(out_dir, ASSETS_SIZE_REPORT_FILE_NAME)
+    .and_then(|report| serde_json::from_slice::<AssetsSizeReport>(&report).ok());
+
+  if let Some(report) = report {
+    if report.files > 0 && report.size > 0 {
+      info!(
+        action = "Embedded";
+        "{} frontend assets: {:.1} KiB, {:.1} KiB embedded ({:.0}%)",
+        report.files,
+        report.size as f64 / 1024.0,
+        report.embedded_size as f64 / 1024.0,
+        report.embedded_size as f64 * 100.0 / report.size as f64
+      );
+    }
+  }
+}
+
+/// Warns about the plugin permissions that were not declared on the previous build and updates the snapshot.
+fn check_plugin_permissions(out_dir: &Path) -> Result<()> {
+  let Some(current) = read_build_script_output(out_dir, PLUGIN_PERMISSIONS_FILE_NAME)
+    .and_then(|permissions| serde_json::from_slice::<PluginPermissions>(&permissions).ok())
+  else {
+    return Ok(());
+  };
+
+  let snapshot_path = tauri_dir().join(PLUGIN_PERMISSIONS_SNAPSHOT_PATH);
+  if snapshot_path.exists() {
+    let previous: PluginPermissions =
+      serde_json::from_str(&std::fs::read_to_string(&snapshot_path)?)
+        .with_context(|| format!("failed to parse {}", snapshot_path.display()))?;
+    for (plugin, permission) in new_permissions(&previous, &current) {
+      warn!(
+        "The `{plugin}` plugin requires the new `{}` permission{}, granting the commands: {}",
+        permission.identifier,
+        if permission.optional {
+          " (optional)"
+        } else {
+          ""
+        },
+        permission.commands.join(", ")
+      );
+      if let Some(description) = &permission.description {
+        warn!("  {description}");
+      }
+    }
+  } else if current.is_empty() {
+    return Ok(());
+  }
+
+  std::fs::create_dir_all(snapshot_path.parent().unwrap())?;
+  std::fs::write(&snapshot_path, serde_json::to_string_pretty(&current)?)
+    .with_context(|| format!("failed to write {}", snapshot_path.display()))?;
+
+  Ok(())
+}
+
 fn print_signed_updater_archive(output_paths: &[PathBuf]) -> crate::Result<()> {
   use std::fmt::Write;
   if !output_paths.is_empty() {
@@ -0,0 +1,39 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::{Parser, Subcommand};
+
+mod migrate;
+mod show;
+mod validate;
+
+#[derive(Parser)]
+#[clap(
+  author,
+  version,
+  about = "Inspect, validate and migrate the Tauri configuration",
+  subcommand_required(true),
+  arg_required_else_help(true)
+)]
+pub struct Cli {
+  #[clap(subcommand)]
+  command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Show(show::Options),
+  Validate(validate::Options),
+  Migrate(migrate::Options),
+}
+
+pub fn command(cli: Cli) -> Result<()> {
+  match cli.command {
+    Commands::Show(options) => show::command(options)?,
+    Commands::Validate(options) => validate::command(options)?,
+    Commands::Migrate(options) => migrate::command(options)?,
+  }
+  Ok(())
+}
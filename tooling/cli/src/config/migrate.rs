@@ -0,0 +1,235 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{app_paths::tauri_dir, config::to_string},
+  Result,
+};
+use clap::Parser;
+use heck::ToLowerCamelCase;
+use log::{info, warn};
+use serde_json::Value as JsonValue;
+use std::fs::{read_dir, read_to_string, write};
+use tauri_utils::config::parse::{
+  is_configuration_file, parse_json5_value, parse_toml_value, ConfigFormat,
+};
+
+/// The `tauri` keys removed in v2, moved to the plugins configuration by the v1 migration.
+const V1_KEYS: &[&str] = &["allowlist", "cli", "updater"];
+
+#[derive(Debug, Parser)]
+#[clap(about = "Rewrite the deprecated keys of the configuration files")]
+pub struct Options {
+  /// Print the changes without writing the configuration files
+  #[clap(long)]
+  dry_run: bool,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
+
+  let mut up_to_date = true;
+  for entry in read_dir(tauri_dir())? {
+    let path = entry?.path();
+    if !is_configuration_file(&path) {
+      continue;
+    }
+    let format = ConfigFormat::from_path(&path).unwrap();
+
+    let raw = read_to_string(&path)?;
+    let mut config = match format {
+      ConfigFormat::Json | ConfigFormat::Json5 => parse_json5_value(&raw, &path)?,
+      ConfigFormat::Toml => parse_toml_value(&raw, &path)?,
+    };
+
+    let changes = migrate(&mut config, format, &schema)?;
+    if changes.is_empty() {
+      continue;
+    }
+    up_to_date = false;
+
+    let file_name = path.file_name().unwrap().to_string_lossy();
+    for change in &changes {
+      info!(action = "Migrate"; "{file_name}: {change}");
+    }
+    if !options.dry_run {
+      if matches!(format, ConfigFormat::Json5) {
+        warn!("{file_name} was rewritten as JSON, its comments were removed");
+      }
+      write(&path, to_string(&config, format)?)?;
+    }
+  }
+
+  if up_to_date {
+    info!("The configuration files are up to date");
+  }
+
+  Ok(())
+}
+
+/// Rewrites the deprecated keys of a configuration file, returning a description of each change.
+///
+/// - The v1 `tauri > allowlist`, `tauri > cli` and `tauri > updater` objects are moved to the plugins configuration.
+/// - The kebab-case keys of JSON and JSON5 files are renamed to camelCase, the only spelling the config schema accepts.
+///   TOML files keep their kebab-case keys.
+fn migrate(
+  config: &mut JsonValue,
+  format: ConfigFormat,
+  schema: &JsonValue,
+) -> Result<Vec<String>> {
+  let mut changes = Vec::new();
+
+  let v1_keys = config
+    .get("tauri")
+    .and_then(|tauri| tauri.as_object())
+    .map(|tauri| {
+      V1_KEYS
+        .iter()
+        .filter(|key| tauri.contains_key(**key))
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  if !v1_keys.is_empty() {
+    crate::migrate::config::migrate_config(config)?;
+    for key in v1_keys {
+      changes.push(format!(
+        "moved `tauri > {key}` to the plugins configuration"
+      ));
+    }
+  }
+
+  if !matches!(format, ConfigFormat::Toml) {
+    rename_aliases(config, schema, schema, &mut Vec::new(), &mut changes);
+  }
+
+  Ok(changes)
+}
+
+/// Renames the object keys that are not declared by the schema but whose camelCase spelling is.
+fn rename_aliases(
+  value: &mut JsonValue,
+  schema: &JsonValue,
+  root: &JsonValue,
+  path: &mut Vec<String>,
+  changes: &mut Vec<String>,
+) {
+  let schemas = subschemas(schema, root);
+
+  match value {
+    JsonValue::Object(map) => {
+      let property = |key: &str| {
+        schemas
+          .iter()
+          .find_map(|schema| schema.get("properties").and_then(|p| p.get(key)))
+      };
+      let additional_properties = schemas
+        .iter()
+        .find_map(|schema| schema.get("additionalProperties").filter(|s| s.is_object()));
+
+      for key in map.keys().cloned().collect::<Vec<_>>() {
+        let camel_case = key.to_lower_camel_case();
+        let key = if property(&key).is_none()
+          && camel_case != key
+          && property(&camel_case).is_some()
+          && !map.contains_key(&camel_case)
+        {
+          let value = map.remove(&key).unwrap();
+          map.insert(camel_case.clone(), value);
+          let parent = path.iter().map(|k| format!("{k} > ")).collect::<String>();
+          changes.push(format!("renamed `{parent}{key}` to `{parent}{camel_case}`"));
+          camel_case
+        } else {
+          key
+        };
+
+        if let Some(schema) = property(&key).or(additional_properties) {
+          path.push(key.clone());
+          rename_aliases(map.get_mut(&key).unwrap(), schema, root, path, changes);
+          path.pop();
+        }
+      }
+    }
+    JsonValue::Array(array) => {
+      if let Some(items) = schemas
+        .iter()
+        .find_map(|schema| schema.get("items").filter(|s| s.is_object()))
+      {
+        for (i, item) in array.iter_mut().enumerate() {
+          path.push(i.to_string());
+          rename_aliases(item, items, root, path, changes);
+          path.pop();
+        }
+      }
+    }
+    _ => (),
+  }
+}
+
+/// Resolves the `$ref` of the schema and flattens its `allOf`, `anyOf` and `oneOf` subschemas.
+fn subschemas<'a>(schema: &'a JsonValue, root: &'a JsonValue) -> Vec<&'a JsonValue> {
+  let schema = match schema
+    .get("$ref")
+    .and_then(|r| r.as_str())
+    .and_then(|r| r.strip_prefix("#/definitions/"))
+  {
+    Some(definition) => match root.get("definitions").and_then(|d| d.get(definition)) {
+      Some(schema) => schema,
+      None => return Vec::new(),
+    },
+    None => schema,
+  };
+
+  let mut schemas = vec![schema];
+  for key in ["allOf", "anyOf", "oneOf"] {
+    if let Some(list) = schema.get(key).and_then(|l| l.as_array()) {
+      for s in list {
+        schemas.extend(subschemas(s, root));
+      }
+    }
+  }
+  schemas
+}
+
+#[cfg(test)]
+mod tests {
+  use super::ConfigFormat;
+  use serde_json::json;
+
+  #[test]
+  fn rename_aliases() {
+    let schema: serde_json::Value =
+      serde_json::from_str(include_str!("../../schema.json")).unwrap();
+    let mut config = json!({
+      "build": { "dist-dir": "../dist" },
+      "tauri": {
+        "windows": [{ "min-width": 400, "title": "main" }],
+      },
+      "plugins": { "my-plugin": { "some-option": true } }
+    });
+
+    let changes = super::migrate(&mut config, ConfigFormat::Json, &schema).unwrap();
+    assert_eq!(
+      changes,
+      vec![
+        "renamed `build > dist-dir` to `build > distDir`",
+        "renamed `tauri > windows > 0 > min-width` to `tauri > windows > 0 > minWidth`",
+      ]
+    );
+    assert_eq!(
+      config,
+      json!({
+        "build": { "distDir": "../dist" },
+        "tauri": {
+          "windows": [{ "minWidth": 400, "title": "main" }],
+        },
+        "plugins": { "my-plugin": { "some-option": true } }
+      })
+    );
+
+    let mut config = json!({ "build": { "dist-dir": "../dist" } });
+    assert!(super::migrate(&mut config, ConfigFormat::Toml, &schema)
+      .unwrap()
+      .is_empty());
+  }
+}
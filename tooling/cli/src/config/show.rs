@@ -0,0 +1,40 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    config::{get as get_config, merge_layers, read_layers, Config},
+    resolve_merge_config,
+  },
+  Result,
+};
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Print the configuration merged with the platform and environment config files")]
+pub struct Options {
+  /// Print the resolved configuration, including the default values
+  #[clap(long)]
+  resolved: bool,
+  /// JSON string or path to JSON file to merge with tauri.conf.json
+  #[clap(short, long)]
+  config: Option<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let (merge_config, _) = resolve_merge_config(&options.config)?;
+
+  let config = if options.resolved {
+    let config = get_config(merge_config.as_deref())?;
+    let config_guard = config.lock().unwrap();
+    let config: &Config = config_guard.as_ref().unwrap();
+    serde_json::to_value(config)?
+  } else {
+    merge_layers(&read_layers(merge_config.as_deref())?)
+  };
+
+  println!("{}", serde_json::to_string_pretty(&config)?);
+
+  Ok(())
+}
@@ -0,0 +1,343 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::{
+    config::{
+      merge_layers, read_layers, validate_schema, Config, ConfigLayer, MERGE_CONFIG_EXTENSION_NAME,
+    },
+    resolve_merge_config,
+  },
+  Result,
+};
+use clap::Parser;
+use log::{error, info};
+use serde_json::Value as JsonValue;
+use std::{
+  env::{current_dir, set_current_dir},
+  fs::read_to_string,
+};
+use tauri_utils::config::parse::ConfigFormat;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Validate the configuration against the config schema")]
+pub struct Options {
+  /// JSON string or path to JSON file to merge with tauri.conf.json
+  #[clap(short, long)]
+  config: Option<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let (merge_config, merge_config_path) = resolve_merge_config(&options.config)?;
+  let layers = read_layers(merge_config.as_deref())?;
+  let config = merge_layers(&layers);
+
+  let errors = validate_schema(&config);
+  for error in &errors {
+    // point at the unexpected property instead of its parent object
+    let path = match error.unexpected.as_slice() {
+      [key] => [error.path.as_slice(), std::slice::from_ref(key)].concat(),
+      _ => error.path.clone(),
+    };
+    let location = locate_error(&layers, &path, merge_config_path.as_deref());
+    if error.path.is_empty() {
+      error!("{location}: {}", error.message);
+    } else {
+      error!("{location}: `{}` {}", error.path.join(" > "), error.message);
+    }
+  }
+  if !errors.is_empty() {
+    return Err(anyhow::anyhow!(
+      "the configuration has {} error(s)",
+      errors.len()
+    ));
+  }
+
+  // the schema can't check everything the `Config` deserializer does
+  // (which resolves `package > version` relative to the config path)
+  let current_dir = current_dir()?;
+  set_current_dir(layers[0].path.as_ref().unwrap().parent().unwrap())?;
+  let result = serde_json::from_value::<Config>(config);
+  set_current_dir(current_dir)?;
+  if let Err(e) = result {
+    return Err(anyhow::anyhow!(
+      "`{}` is not a valid configuration: {e}",
+      layers[0].name
+    ));
+  }
+
+  info!("The configuration is valid");
+  Ok(())
+}
+
+/// Describes where the invalid value is defined: the last config layer setting it,
+/// with the line and column for JSON and JSON5 files.
+fn locate_error(
+  layers: &[ConfigLayer],
+  path: &[String],
+  merge_config_path: Option<&str>,
+) -> String {
+  let layer = layers
+    .iter()
+    .rev()
+    .find(|layer| contains(&layer.value, path))
+    .unwrap_or(&layers[0]);
+
+  let (name, source) = match &layer.path {
+    Some(p) => (
+      p.display().to_string(),
+      match ConfigFormat::from_path(p) {
+        Some(ConfigFormat::Json | ConfigFormat::Json5) => read_to_string(p).ok(),
+        _ => None,
+      },
+    ),
+    None if layer.name == MERGE_CONFIG_EXTENSION_NAME => match merge_config_path {
+      Some(p) => (p.to_string(), read_to_string(p).ok()),
+      None => (layer.name.clone(), Some(layer.value.to_string())),
+    },
+    None => (layer.name.clone(), None),
+  };
+
+  match source.and_then(|source| locate(&source, path)) {
+    Some((line, column)) => format!("{name}:{line}:{column}"),
+    None => name,
+  }
+}
+
+fn contains(value: &JsonValue, path: &[String]) -> bool {
+  let mut value = value;
+  for segment in path {
+    let next = match value {
+      JsonValue::Object(map) => map.get(segment),
+      JsonValue::Array(array) => segment.parse::<usize>().ok().and_then(|i| array.get(i)),
+      _ => None,
+    };
+    match next {
+      Some(next) => value = next,
+      None => return false,
+    }
+  }
+  true
+}
+
+/// Finds the 1-based line and column of the value at `path` in a JSON or JSON5 document.
+///
+/// Object members are located by their key.
+fn locate(source: &str, path: &[String]) -> Option<(usize, usize)> {
+  let mut scanner = Scanner {
+    source: source.as_bytes(),
+    position: 0,
+  };
+  let offset = scanner.find(path)?;
+  let before = &source[..offset];
+  let line = before.matches('\n').count() + 1;
+  let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+  Some((line, column))
+}
+
+struct Scanner<'a> {
+  source: &'a [u8],
+  position: usize,
+}
+
+impl Scanner<'_> {
+  fn peek(&self) -> Option<u8> {
+    self.source.get(self.position).copied()
+  }
+
+  /// Skips whitespace and JSON5 comments.
+  fn skip_trivia(&mut self) {
+    loop {
+      match (self.peek(), self.source.get(self.position + 1)) {
+        (Some(c), _) if c.is_ascii_whitespace() => self.position += 1,
+        (Some(b'/'), Some(b'/')) => {
+          while !matches!(self.peek(), None | Some(b'\n')) {
+            self.position += 1;
+          }
+        }
+        (Some(b'/'), Some(b'*')) => {
+          self.position += 2;
+          while self.position < self.source.len()
+            && !self.source[self.position..].starts_with(b"*/")
+          {
+            self.position += 1;
+          }
+          self.position = (self.position + 2).min(self.source.len());
+        }
+        _ => break,
+      }
+    }
+  }
+
+  /// Finds the offset of the value at `path`, relative to the value at the current position.
+  fn find(&mut self, path: &[String]) -> Option<usize> {
+    self.skip_trivia();
+    let Some((segment, rest)) = path.split_first() else {
+      return Some(self.position);
+    };
+
+    match self.peek()? {
+      b'{' => {
+        self.position += 1;
+        loop {
+          self.skip_trivia();
+          if self.peek()? == b'}' {
+            return None;
+          }
+          let key_position = self.position;
+          let key = self.key()?;
+          self.skip_trivia();
+          if self.peek()? != b':' {
+            return None;
+          }
+          self.position += 1;
+          if key == *segment {
+            return if rest.is_empty() {
+              Some(key_position)
+            } else {
+              self.find(rest)
+            };
+          }
+          self.skip_value()?;
+          self.skip_trivia();
+          if self.peek()? == b',' {
+            self.position += 1;
+          }
+        }
+      }
+      b'[' => {
+        let index = segment.parse::<usize>().ok()?;
+        self.position += 1;
+        for _ in 0..index {
+          self.skip_value()?;
+          self.skip_trivia();
+          if self.peek()? != b',' {
+            return None;
+          }
+          self.position += 1;
+        }
+        self.skip_trivia();
+        if self.peek()? == b']' {
+          return None;
+        }
+        self.find(rest)
+      }
+      _ => None,
+    }
+  }
+
+  /// Reads an object key, quoted or a JSON5 identifier.
+  fn key(&mut self) -> Option<String> {
+    match self.peek()? {
+      quote @ (b'"' | b'\'') => self.string(quote),
+      _ => {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c >= 0x80)
+        {
+          self.position += 1;
+        }
+        (self.position > start)
+          .then(|| String::from_utf8_lossy(&self.source[start..self.position]).into_owned())
+      }
+    }
+  }
+
+  /// Reads a string literal, resolving the single character escape sequences.
+  fn string(&mut self, quote: u8) -> Option<String> {
+    self.position += 1;
+    let mut value = Vec::new();
+    loop {
+      let c = self.peek()?;
+      self.position += 1;
+      match c {
+        c if c == quote => return Some(String::from_utf8_lossy(&value).into_owned()),
+        b'\\' => {
+          value.push(match self.peek()? {
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            c => c,
+          });
+          self.position += 1;
+        }
+        c => value.push(c),
+      }
+    }
+  }
+
+  fn skip_value(&mut self) -> Option<()> {
+    self.skip_trivia();
+    match self.peek()? {
+      quote @ (b'"' | b'\'') => {
+        self.string(quote)?;
+      }
+      open @ (b'{' | b'[') => {
+        let close = if open == b'{' { b'}' } else { b']' };
+        self.position += 1;
+        loop {
+          self.skip_trivia();
+          match self.peek()? {
+            c if c == close => {
+              self.position += 1;
+              break;
+            }
+            b',' | b':' => self.position += 1,
+            _ => self.skip_value()?,
+          }
+        }
+      }
+      _ => {
+        // numbers, booleans, null and JSON5 identifiers
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || b"+-._$".contains(&c) || c >= 0x80)
+        {
+          self.position += 1;
+        }
+        if self.position == start {
+          return None;
+        }
+      }
+    }
+    Some(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  fn path(path: &[&str]) -> Vec<String> {
+    path.iter().map(|s| s.to_string()).collect()
+  }
+
+  #[test]
+  fn locate() {
+    let json = r#"{
+  "build": { "distDir": "../dist" },
+  "tauri": {
+    "windows": [
+      { "title": "main" },
+      { "title": 1 }
+    ]
+  }
+}"#;
+    assert_eq!(super::locate(json, &[]), Some((1, 1)));
+    assert_eq!(
+      super::locate(json, &path(&["build", "distDir"])),
+      Some((2, 14))
+    );
+    assert_eq!(
+      super::locate(json, &path(&["tauri", "windows", "1", "title"])),
+      Some((6, 9))
+    );
+    assert_eq!(super::locate(json, &path(&["tauri", "windows", "2"])), None);
+
+    let json5 = r#"{
+  // the frontend
+  build: { devPath: 'http://localhost:1420', /* served by vite */ distDir: "../dist", },
+}"#;
+    assert_eq!(
+      super::locate(json5, &path(&["build", "distDir"])),
+      Some((3, 67))
+    );
+  }
+}
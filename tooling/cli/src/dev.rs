@@ -4,9 +4,10 @@
 
 use crate::{
   helpers::{
-    app_paths::{app_dir, tauri_dir},
+    app_paths::{app_dir, select_package, tauri_dir},
     command_env,
     config::{get as get_config, reload as reload_config, AppUrl, BeforeDevCommand, WindowUrl},
+    process::ProcessTree,
     resolve_merge_config,
   },
   interface::{AppInterface, DevProcess, ExitReason, Interface},
@@ -17,24 +18,17 @@ use anyhow::{bail, Context};
 use clap::{ArgAction, Parser};
 use log::{error, info, warn};
 use once_cell::sync::OnceCell;
-use shared_child::SharedChild;
 
 use std::{
   env::set_current_dir,
   net::{IpAddr, Ipv4Addr},
   process::{exit, Command, ExitStatus, Stdio},
-  sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
-  },
+  sync::atomic::{AtomicBool, Ordering},
 };
 
-static BEFORE_DEV: OnceCell<Mutex<Arc<SharedChild>>> = OnceCell::new();
+static BEFORE_DEV: OnceCell<ProcessTree> = OnceCell::new();
 static KILL_BEFORE_DEV_FLAG: OnceCell<AtomicBool> = OnceCell::new();
 
-#[cfg(unix)]
-const KILL_CHILDREN_SCRIPT: &[u8] = include_bytes!("../scripts/kill-children.sh");
-
 pub const TAURI_DEV_WATCHER_GITIGNORE: &[u8] = include_bytes!("../tauri-dev-watcher.gitignore");
 
 #[derive(Debug, Clone, Parser)]
@@ -58,6 +52,17 @@ pub struct Options {
   /// Run the code in release mode
   #[clap(long = "release")]
   pub release_mode: bool,
+  /// The package of the cargo workspace to run, for workspaces with several Tauri apps.
+  ///
+  /// Its folder must contain the Tauri configuration file.
+  #[clap(short, long)]
+  pub package: Option<String>,
+  /// Build the app with the given cargo profile, e.g. a custom profile inheriting from `release` for performance testing
+  #[clap(long, conflicts_with = "release_mode")]
+  pub profile: Option<String>,
+  /// Do not activate the default features of the app crate
+  #[clap(long)]
+  pub no_default_features: bool,
   /// Command line arguments passed to the runner. Arguments after `--` are passed to the application.
   pub args: Vec<String>,
   /// Disable the file watcher
@@ -138,6 +143,10 @@ pub fn local_ip_address(force: bool) -> &'static IpAddr {
 }
 
 pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
+  if let Some(package) = &options.package {
+    select_package(package)?;
+  }
+
   let (merge_config, _merge_config_path) = resolve_merge_config(&options.config)?;
   options.config = merge_config;
 
@@ -242,10 +251,9 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
         command.stdout(os_pipe::dup_stdout()?);
         command.stderr(os_pipe::dup_stderr()?);
 
-        let child = SharedChild::spawn(&mut command)
+        let process_tree = ProcessTree::spawn(&mut command)
           .unwrap_or_else(|_| panic!("failed to run `{before_dev}`"));
-        let child = Arc::new(child);
-        let child_ = child.clone();
+        let child_ = process_tree.child().clone();
 
         std::thread::spawn(move || {
           let status = child_
@@ -257,7 +265,7 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
           }
         });
 
-        BEFORE_DEV.set(Mutex::new(child)).unwrap();
+        let _ = BEFORE_DEV.set(process_tree);
         KILL_BEFORE_DEV_FLAG.set(AtomicBool::default()).unwrap();
 
         let _ = ctrlc::set_handler(move || {
@@ -302,31 +310,54 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
     .build
     .dev_path
     .clone();
+  let dev_server_config = config
+    .lock()
+    .unwrap()
+    .as_ref()
+    .unwrap()
+    .build
+    .dev_server
+    .clone();
+  // the URL of the frontend dev server, which must be up before the app starts
+  let frontend_url = dev_path.clone();
   if !options.no_dev_server {
-    if let AppUrl::Url(WindowUrl::App(path)) = &dev_path {
-      use crate::helpers::web_dev_server::start_dev_server;
-      if path.exists() {
-        let path = path.canonicalize()?;
-        let ip = if mobile {
-          *local_ip_address(options.force_ip_prompt)
-        } else {
-          Ipv4Addr::new(127, 0, 0, 1).into()
-        };
-        let server_url = start_dev_server(path, ip, options.port)?;
-        let server_url = format!("http://{server_url}");
-        dev_path = AppUrl::Url(WindowUrl::External(server_url.parse().unwrap()));
-
-        // TODO: in v2, use an env var to pass the url to the app context
-        // or better separate the config passed from the cli internally and
-        // config passed by the user in `--config` into to separate env vars
-        // and the context merges, the user first, then the internal cli config
-        if let Some(c) = &options.config {
-          let mut c: tauri_utils::config::Config = serde_json::from_str(c)?;
-          c.build.dev_path = dev_path.clone();
-          options.config = Some(serde_json::to_string(&c).unwrap());
-        } else {
-          options.config = Some(format!(r#"{{ "build": {{ "devPath": "{server_url}" }} }}"#))
-        }
+    use crate::helpers::web_dev_server::{start_dev_server, DevServerTarget};
+    let target = match &dev_path {
+      AppUrl::Url(WindowUrl::App(path)) if path.exists() => {
+        Some(DevServerTarget::Dir(path.canonicalize()?))
+      }
+      AppUrl::Url(WindowUrl::External(url))
+        if dev_server_config.https || !dev_server_config.proxy.is_empty() =>
+      {
+        Some(DevServerTarget::Url(url.clone()))
+      }
+      _ => None,
+    };
+    if let Some(target) = target {
+      let ip = if mobile {
+        *local_ip_address(options.force_ip_prompt)
+      } else {
+        Ipv4Addr::new(127, 0, 0, 1).into()
+      };
+      let server_url = start_dev_server(target, ip, options.port, &dev_server_config)?;
+      if dev_server_config.https && mobile {
+        info!(
+          "Install the certificate authority {} on your device to trust the dev server",
+          crate::helpers::dev_certificate::certificate_authority_path().display()
+        );
+      }
+      dev_path = AppUrl::Url(WindowUrl::External(server_url.clone()));
+
+      // TODO: in v2, use an env var to pass the url to the app context
+      // or better separate the config passed from the cli internally and
+      // config passed by the user in `--config` into to separate env vars
+      // and the context merges, the user first, then the internal cli config
+      if let Some(c) = &options.config {
+        let mut c: tauri_utils::config::Config = serde_json::from_str(c)?;
+        c.build.dev_path = dev_path.clone();
+        options.config = Some(serde_json::to_string(&c).unwrap());
+      } else {
+        options.config = Some(format!(r#"{{ "build": {{ "devPath": "{server_url}" }} }}"#))
       }
     }
 
@@ -334,7 +365,7 @@ pub fn setup(options: &mut Options, mobile: bool) -> Result<AppInterface> {
   }
 
   if std::env::var_os("TAURI_SKIP_DEVSERVER_CHECK") != Some("true".into()) {
-    if let AppUrl::Url(WindowUrl::External(dev_server_url)) = dev_path {
+    if let AppUrl::Url(WindowUrl::External(dev_server_url)) = frontend_url {
       let host = dev_server_url
         .host()
         .unwrap_or_else(|| panic!("No host name in the URL"));
@@ -442,43 +473,11 @@ fn check_for_updates() -> Result<()> {
 }
 
 pub fn kill_before_dev_process() {
-  if let Some(child) = BEFORE_DEV.get() {
-    let child = child.lock().unwrap();
+  if let Some(process_tree) = BEFORE_DEV.get() {
     KILL_BEFORE_DEV_FLAG
       .get()
       .unwrap()
       .store(true, Ordering::Relaxed);
-    #[cfg(windows)]
-    {
-      let powershell_path = std::env::var("SYSTEMROOT").map_or_else(
-        |_| "powershell.exe".to_string(),
-        |p| format!("{p}\\System32\\WindowsPowerShell\\v1.0\\powershell.exe"),
-      );
-      let _ = Command::new(powershell_path)
-      .arg("-NoProfile")
-      .arg("-Command")
-      .arg(format!("function Kill-Tree {{ Param([int]$ppid); Get-CimInstance Win32_Process | Where-Object {{ $_.ParentProcessId -eq $ppid }} | ForEach-Object {{ Kill-Tree $_.ProcessId }}; Stop-Process -Id $ppid -ErrorAction SilentlyContinue }}; Kill-Tree {}", child.id()))
-      .status();
-    }
-    #[cfg(unix)]
-    {
-      use std::io::Write;
-      let mut kill_children_script_path = std::env::temp_dir();
-      kill_children_script_path.push("kill-children.sh");
-
-      if !kill_children_script_path.exists() {
-        if let Ok(mut file) = std::fs::File::create(&kill_children_script_path) {
-          use std::os::unix::fs::PermissionsExt;
-          let _ = file.write_all(KILL_CHILDREN_SCRIPT);
-          let mut permissions = file.metadata().unwrap().permissions();
-          permissions.set_mode(0o770);
-          let _ = file.set_permissions(permissions);
-        }
-      }
-      let _ = Command::new(&kill_children_script_path)
-        .arg(child.id().to_string())
-        .output();
-    }
-    let _ = child.kill();
+    process_tree.kill();
   }
 }
@@ -7,10 +7,12 @@ use std::{
   env::current_dir,
   ffi::OsStr,
   path::{Path, PathBuf},
+  process::Command,
 };
 
 use ignore::WalkBuilder;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Deserialize;
 
 use tauri_utils::config::parse::{
   folder_has_configuration_file, is_configuration_file, ConfigFormat,
@@ -18,6 +20,9 @@ use tauri_utils::config::parse::{
 
 const TAURI_GITIGNORE: &[u8] = include_bytes!("../../tauri.gitignore");
 
+/// The Tauri directory of the package selected with `--package`.
+static PACKAGE_TAURI_DIR: OnceCell<PathBuf> = OnceCell::new();
+
 fn lookup<F: Fn(&PathBuf) -> bool>(dir: &Path, checker: F) -> Option<PathBuf> {
   let mut default_gitignore = std::env::temp_dir();
   default_gitignore.push(".gitignore");
@@ -60,6 +65,10 @@ fn lookup<F: Fn(&PathBuf) -> bool>(dir: &Path, checker: F) -> Option<PathBuf> {
 }
 
 fn get_tauri_dir() -> PathBuf {
+  if let Some(dir) = PACKAGE_TAURI_DIR.get() {
+    return dir.clone();
+  }
+
   let cwd = current_dir().expect("failed to read cwd");
 
   if cwd.join("src-tauri/tauri.conf.json").exists()
@@ -80,6 +89,15 @@ fn get_tauri_dir() -> PathBuf {
 }
 
 fn get_app_dir() -> Option<PathBuf> {
+  // the frontend of a workspace package is usually its parent folder, but can be shared with the other apps
+  if let Some(dir) = PACKAGE_TAURI_DIR.get() {
+    return dir
+      .ancestors()
+      .skip(1)
+      .find(|d| d.join("package.json").exists())
+      .map(Path::to_path_buf);
+  }
+
   lookup(&current_dir().expect("failed to read cwd"), |path| {
     if let Some(file_name) = path.file_name() {
       file_name == OsStr::new("package.json")
@@ -99,3 +117,58 @@ pub fn app_dir() -> &'static PathBuf {
 pub fn tauri_dir() -> PathBuf {
   get_tauri_dir()
 }
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+  packages: Vec<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+  name: String,
+  manifest_path: PathBuf,
+}
+
+/// Uses the folder of the given package of the current cargo workspace as the Tauri directory,
+/// for workspaces with several Tauri apps.
+///
+/// Must be called before the Tauri directory is used.
+pub fn select_package(name: &str) -> crate::Result<()> {
+  let output = Command::new("cargo")
+    .args(["metadata", "--no-deps", "--format-version", "1"])
+    .output()?;
+  if !output.status.success() {
+    return Err(anyhow::anyhow!(
+      "cargo metadata command exited with a non zero exit code: {}",
+      String::from_utf8(output.stderr)?
+    ));
+  }
+  let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)?;
+
+  let apps = metadata
+    .packages
+    .into_iter()
+    .filter_map(|package| {
+      let dir = package.manifest_path.parent()?.to_path_buf();
+      folder_has_configuration_file(&dir).then_some((package.name, dir))
+    })
+    .collect::<Vec<_>>();
+
+  match apps.iter().find(|(package, _)| package == name) {
+    Some((_, dir)) => {
+      let _ = PACKAGE_TAURI_DIR.set(dir.clone());
+      Ok(())
+    }
+    None if apps.is_empty() => Err(anyhow::anyhow!(
+      "the cargo workspace has no package named `{name}` with a Tauri configuration file"
+    )),
+    None => Err(anyhow::anyhow!(
+      "the cargo workspace has no package named `{name}` with a Tauri configuration file, the Tauri apps are {}",
+      apps
+        .iter()
+        .map(|(package, _)| format!("`{package}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    )),
+  }
+}
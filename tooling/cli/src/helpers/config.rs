@@ -3,10 +3,10 @@
 // SPDX-License-Identifier: MIT
 
 use anyhow::Context;
-use json_patch::merge;
 use log::error;
 use once_cell::sync::Lazy;
 use serde_json::Value as JsonValue;
+use tauri_utils::config::parse::{merge, ConfigFormat};
 
 pub use tauri_utils::config::*;
 
@@ -14,18 +14,22 @@ use std::{
   collections::HashMap,
   env::{current_dir, set_current_dir, set_var, var_os},
   ffi::OsStr,
+  path::PathBuf,
   process::exit,
   sync::{Arc, Mutex},
 };
 
 pub const MERGE_CONFIG_EXTENSION_NAME: &str = "--config";
+pub const ENV_OVERRIDES_EXTENSION_NAME: &str = "TAURI_CONFIG_*";
 
 pub struct ConfigMetadata {
   /// The actual configuration, merged with any extension.
   inner: Config,
-  /// The config extensions (platform-specific config files or the config CLI argument).
+  /// The config extensions (platform-specific and environment config files, environment variable overrides or the config CLI argument).
   /// Maps the extension name to its value.
   extensions: HashMap<String, JsonValue>,
+  /// The formats of the config files that were read.
+  formats: Vec<ConfigFormat>,
 }
 
 impl std::ops::Deref for ConfigMetadata {
@@ -38,6 +42,18 @@ impl std::ops::Deref for ConfigMetadata {
 }
 
 impl ConfigMetadata {
+  /// The Cargo features required to read the config files at compile time.
+  pub fn format_features(&self) -> Vec<&'static str> {
+    let mut features = self
+      .formats
+      .iter()
+      .filter_map(|format| format.cargo_feature())
+      .collect::<Vec<_>>();
+    features.sort_unstable();
+    features.dedup();
+    features
+  }
+
   /// Checks which config is overwriting the bundle identifier.
   pub fn find_bundle_identifier_overwriter(&self) -> Option<String> {
     for (ext, config) in &self.extensions {
@@ -94,6 +110,8 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
     banner_path: config.banner_path,
     dialog_image_path: config.dialog_image_path,
     fips_compliant: var_os("TAURI_FIPS_COMPLIANT").map_or(false, |v| v == "true"),
+    install_mode: config.install_mode,
+    start_menu_folder: config.start_menu_folder,
   }
 }
 
@@ -108,6 +126,165 @@ pub fn nsis_settings(config: NsisConfig) -> tauri_bundler::NsisSettings {
     languages: config.languages,
     custom_language_files: config.custom_language_files,
     display_language_selector: config.display_language_selector,
+    start_menu_folder: config.start_menu_folder,
+  }
+}
+
+/// The asset protocol and filesystem plugin scopes, with their names.
+pub fn fs_scopes(config: &Config) -> Vec<(&'static str, FsScope)> {
+  let mut scopes = vec![(
+    "asset protocol",
+    config.tauri.security.asset_protocol.scope.clone(),
+  )];
+  if let Some(fs_scope) = config
+    .plugins
+    .0
+    .get("fs")
+    .and_then(|fs| fs.get("scope"))
+    .and_then(|scope| serde_json::from_value::<FsScope>(scope.clone()).ok())
+  {
+    scopes.push(("fs plugin", fs_scope));
+  }
+  scopes
+}
+
+/// Paths the app can access through the asset protocol and the filesystem plugin scopes.
+fn scoped_paths(config: &Config) -> Vec<String> {
+  fs_scopes(config)
+    .iter()
+    .flat_map(|(_, scope)| scope.allowed_paths())
+    .map(|path| path.to_string_lossy().into_owned())
+    .collect()
+}
+
+/// Whether the app needs network access, either via plugins or by loading remote content.
+fn uses_network(config: &Config) -> bool {
+  ["http", "updater", "websocket", "upload"]
+    .iter()
+    .any(|plugin| config.plugins.0.contains_key(*plugin))
+    || config
+      .tauri
+      .windows
+      .iter()
+      .any(|w| matches!(w.url, WindowUrl::External(_)))
+    || !config
+      .tauri
+      .security
+      .dangerous_remote_domain_ipc_access
+      .is_empty()
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+  if !list.contains(&value) {
+    list.push(value);
+  }
+}
+
+pub fn flatpak_settings(config: &Config) -> tauri_bundler::FlatpakSettings {
+  let flatpak = config.tauri.bundle.flatpak.clone();
+
+  let mut finish_args = Vec::new();
+  for path in scoped_paths(config) {
+    if let Some(filesystem) = flatpak_filesystem(&path) {
+      push_unique(&mut finish_args, format!("--filesystem={filesystem}"));
+    }
+  }
+  if uses_network(config) {
+    push_unique(&mut finish_args, "--share=network".into());
+  }
+  for arg in flatpak.finish_args {
+    push_unique(&mut finish_args, arg);
+  }
+
+  tauri_bundler::FlatpakSettings {
+    runtime: flatpak.runtime,
+    runtime_version: flatpak.runtime_version,
+    sdk: flatpak.sdk,
+    finish_args,
+  }
+}
+
+pub fn snap_settings(config: &Config) -> tauri_bundler::SnapSettings {
+  let snap = config.tauri.bundle.snap.clone();
+
+  let mut plugs = Vec::new();
+  for path in scoped_paths(config) {
+    if let Some(plug) = snap_plug(&path) {
+      push_unique(&mut plugs, plug.into());
+    }
+  }
+  if uses_network(config) {
+    push_unique(&mut plugs, "network".into());
+  }
+  for plug in snap.plugs {
+    push_unique(&mut plugs, plug);
+  }
+
+  tauri_bundler::SnapSettings {
+    confinement: snap.confinement,
+    base: snap.base,
+    grade: snap.grade,
+    plugs,
+  }
+}
+
+/// Maps a scope path to the Snap interface that grants access to it.
+///
+/// The `home` interface does not grant access to hidden files and folders,
+/// so `$CONFIG`, `$DATA` and similar scopes require a custom `personal-files` plug.
+fn snap_plug(path: &str) -> Option<&'static str> {
+  let first = path.split('/').next()?;
+  match first {
+    "$HOME" | "$DESKTOP" | "$DOCUMENT" | "$DOWNLOAD" | "$AUDIO" | "$PICTURE" | "$PUBLIC"
+    | "$TEMPLATE" | "$VIDEO" => Some("home"),
+    "" if path.starts_with("/media")
+      || path.starts_with("/mnt")
+      || path.starts_with("/run/media") =>
+    {
+      Some("removable-media")
+    }
+    _ => None,
+  }
+}
+
+/// Maps a scope path to a Flatpak `--filesystem` value.
+///
+/// Returns `None` for paths that are already accessible inside the sandbox (e.g. the app data directories).
+fn flatpak_filesystem(path: &str) -> Option<String> {
+  let mut components = path.split('/');
+  let first = components.next()?;
+  let base = match first {
+    "" => "",
+    "$HOME" => "home",
+    "$DESKTOP" => "xdg-desktop",
+    "$DOCUMENT" => "xdg-documents",
+    "$DOWNLOAD" => "xdg-download",
+    "$AUDIO" => "xdg-music",
+    "$PICTURE" => "xdg-pictures",
+    "$PUBLIC" => "xdg-public-share",
+    "$TEMPLATE" => "xdg-templates",
+    "$VIDEO" => "xdg-videos",
+    "$CONFIG" => "xdg-config",
+    "$CACHE" => "xdg-cache",
+    "$DATA" | "$LOCALDATA" => "xdg-data",
+    "$FONT" => "xdg-data/fonts",
+    "$RUNTIME" => "xdg-run",
+    // app directories, resources and temporary files are available without extra permissions
+    _ => return None,
+  };
+
+  // stop at the first component containing a glob pattern
+  let subpath = components
+    .take_while(|c| !c.contains(['*', '?', '[', '{']))
+    .filter(|c| !c.is_empty())
+    .collect::<Vec<_>>()
+    .join("/");
+
+  match (base, subpath.is_empty()) {
+    ("", true) => Some("host".into()),
+    ("", false) => Some(format!("/{subpath}")),
+    (base, true) => Some(base.into()),
+    (base, false) => Some(format!("{base}/{subpath}")),
   }
 }
 
@@ -116,49 +293,155 @@ fn config_handle() -> &'static ConfigHandle {
   &CONFING_HANDLE
 }
 
+/// A configuration source, in the order they are merged.
+pub struct ConfigLayer {
+  /// The file name of the configuration file, or the extension name (e.g. [`MERGE_CONFIG_EXTENSION_NAME`]).
+  pub name: String,
+  /// The path of the configuration file.
+  pub path: Option<PathBuf>,
+  pub value: JsonValue,
+}
+
+/// Reads the `tauri.conf.json` file and its extensions, in the order they must be merged.
+pub fn read_layers(merge_config: Option<&str>) -> crate::Result<Vec<ConfigLayer>> {
+  let tauri_dir = super::app_paths::tauri_dir();
+  let file_layer = |(value, path): (JsonValue, PathBuf)| ConfigLayer {
+    name: path.file_name().unwrap().to_string_lossy().into_owned(),
+    path: Some(path),
+    value,
+  };
+
+  let mut layers = vec![file_layer(tauri_utils::config::parse::parse_value(
+    tauri_dir.join("tauri.conf.json"),
+  )?)];
+
+  if let Some(platform_config) = tauri_utils::config::parse::read_platform(tauri_dir.clone())? {
+    layers.push(file_layer(platform_config));
+  }
+
+  if let Some(env) = tauri_utils::config::parse::env() {
+    layers.push(file_layer(tauri_utils::config::parse::read_env(
+      &tauri_dir, &env,
+    )?));
+  }
+
+  if let Some(overrides) = tauri_utils::config::parse::env_overrides() {
+    layers.push(ConfigLayer {
+      name: ENV_OVERRIDES_EXTENSION_NAME.into(),
+      path: None,
+      value: overrides,
+    });
+  }
+
+  if let Some(merge_config) = merge_config {
+    layers.push(ConfigLayer {
+      name: MERGE_CONFIG_EXTENSION_NAME.into(),
+      path: None,
+      value: serde_json::from_str(merge_config)
+        .with_context(|| "failed to parse config to merge")?,
+    });
+  }
+
+  Ok(layers)
+}
+
+/// Merges the configuration layers.
+pub fn merge_layers(layers: &[ConfigLayer]) -> JsonValue {
+  let mut config = layers[0].value.clone();
+  for layer in &layers[1..] {
+    merge(&mut config, &layer.value);
+  }
+  config
+}
+
+/// Serializes the configuration in the given format.
+pub fn to_string(config: &JsonValue, format: ConfigFormat) -> crate::Result<String> {
+  match format {
+    ConfigFormat::Json | ConfigFormat::Json5 => Ok(serde_json::to_string_pretty(config)?),
+    ConfigFormat::Toml => {
+      // TOML has no null, and tables must be written after the values, which the TOML value serializer does
+      let mut config = config.clone();
+      remove_nulls(&mut config);
+      Ok(toml::to_string_pretty(&toml::Value::try_from(&config)?)?)
+    }
+  }
+}
+
+fn remove_nulls(value: &mut JsonValue) {
+  match value {
+    JsonValue::Object(map) => {
+      map.retain(|_, value| !value.is_null());
+      map.values_mut().for_each(remove_nulls);
+    }
+    JsonValue::Array(array) => array.iter_mut().for_each(remove_nulls),
+    _ => (),
+  }
+}
+
+/// A configuration value that does not match the config JSON schema.
+pub struct SchemaError {
+  /// The path of the invalid value, as object keys and array indexes.
+  pub path: Vec<String>,
+  /// The unexpected properties of the object, for additional properties errors.
+  pub unexpected: Vec<String>,
+  pub message: String,
+}
+
+/// Validates the configuration against the config JSON schema.
+pub fn validate_schema(config: &JsonValue) -> Vec<SchemaError> {
+  let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json")).unwrap();
+  let schema = jsonschema::JSONSchema::compile(&schema).unwrap();
+  let result = schema.validate(config);
+  match result {
+    Ok(()) => Vec::new(),
+    Err(errors) => errors
+      .map(|error| SchemaError {
+        path: error.instance_path.clone().into_vec(),
+        unexpected: match &error.kind {
+          jsonschema::error::ValidationErrorKind::AdditionalProperties { unexpected } => {
+            unexpected.clone()
+          }
+          _ => Vec::new(),
+        },
+        message: error.to_string(),
+      })
+      .collect(),
+  }
+}
+
 /// Gets the static parsed config from `tauri.conf.json`.
 fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<ConfigHandle> {
   if !reload && config_handle().lock().unwrap().is_some() {
     return Ok(config_handle().clone());
   }
 
-  let tauri_dir = super::app_paths::tauri_dir();
-  let (mut config, config_path) =
-    tauri_utils::config::parse::parse_value(tauri_dir.join("tauri.conf.json"))?;
-  let config_file_name = config_path.file_name().unwrap().to_string_lossy();
-  let mut extensions = HashMap::new();
-
-  if let Some((platform_config, config_path)) =
-    tauri_utils::config::parse::read_platform(tauri_dir)?
-  {
-    merge(&mut config, &platform_config);
-    extensions.insert(
-      config_path.file_name().unwrap().to_str().unwrap().into(),
-      platform_config,
-    );
-  }
-
+  let layers = read_layers(merge_config)?;
   if let Some(merge_config) = merge_config {
     set_var("TAURI_CONFIG", merge_config);
-    let merge_config: JsonValue =
-      serde_json::from_str(merge_config).with_context(|| "failed to parse config to merge")?;
-    merge(&mut config, &merge_config);
-    extensions.insert(MERGE_CONFIG_EXTENSION_NAME.into(), merge_config);
-  };
+  }
+
+  let config_path = layers[0].path.clone().unwrap();
+  let config_file_name = &layers[0].name;
+  let config = merge_layers(&layers);
+  let formats = layers
+    .iter()
+    .filter_map(|layer| layer.path.as_deref().and_then(ConfigFormat::from_path))
+    .collect();
 
   if config_path.extension() == Some(OsStr::new("json"))
     || config_path.extension() == Some(OsStr::new("json5"))
   {
-    let schema: JsonValue = serde_json::from_str(include_str!("../../schema.json"))?;
-    let schema = jsonschema::JSONSchema::compile(&schema).unwrap();
-    let result = schema.validate(&config);
-    if let Err(errors) = result {
+    let errors = validate_schema(&config);
+    if !errors.is_empty() {
       for error in errors {
-        let path = error.instance_path.clone().into_vec().join(" > ");
+        let path = error.path.join(" > ");
         if path.is_empty() {
-          error!("`{}` error: {}", config_file_name, error);
+          error!("`{}` error: {}", config_file_name, error.message);
         } else {
-          error!("`{}` error on `{}`: {}", config_file_name, path, error);
+          error!(
+            "`{}` error on `{}`: {}",
+            config_file_name, path, error.message
+          );
         }
       }
       if !reload {
@@ -167,6 +450,12 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
     }
   }
 
+  let extensions = layers
+    .into_iter()
+    .skip(1)
+    .map(|layer| (layer.name, layer.value))
+    .collect();
+
   // the `Config` deserializer for `package > version` can resolve the version from a path relative to the config path
   // so we actually need to change the current working directory here
   let current_dir = current_dir()?;
@@ -178,6 +467,7 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
   *config_handle().lock().unwrap() = Some(ConfigMetadata {
     inner: config,
     extensions,
+    formats,
   });
 
   Ok(config_handle().clone())
@@ -190,3 +480,32 @@ pub fn get(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
 pub fn reload(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
   get_internal(merge_config, true)
 }
+
+#[cfg(test)]
+mod tests {
+  #[test]
+  fn flatpak_filesystem() {
+    assert_eq!(
+      super::flatpak_filesystem("$HOME/**").as_deref(),
+      Some("home")
+    );
+    assert_eq!(
+      super::flatpak_filesystem("$DOCUMENT/notes/*.md").as_deref(),
+      Some("xdg-documents/notes")
+    );
+    assert_eq!(
+      super::flatpak_filesystem("/mnt/data/**").as_deref(),
+      Some("/mnt/data")
+    );
+    assert_eq!(super::flatpak_filesystem("/**").as_deref(), Some("host"));
+    assert_eq!(super::flatpak_filesystem("$APPDATA/**"), None);
+  }
+
+  #[test]
+  fn snap_plug() {
+    assert_eq!(super::snap_plug("$DOWNLOAD/**"), Some("home"));
+    assert_eq!(super::snap_plug("/media/**"), Some("removable-media"));
+    assert_eq!(super::snap_plug("/etc/hosts"), None);
+    assert_eq!(super::snap_plug("$APPDATA/**"), None);
+  }
+}
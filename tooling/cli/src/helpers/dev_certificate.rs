@@ -0,0 +1,386 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Certificates for serving the `tauri dev` server over HTTPS.
+//!
+//! A certificate authority is generated on first use and stored in the local data directory,
+//! each dev server run then gets a fresh certificate for `localhost` and its IP address signed by it.
+
+#[cfg(any(target_os = "macos", windows))]
+use crate::CommandExt;
+use crate::Result;
+use anyhow::Context;
+use base64::Engine;
+use log::{info, warn};
+use ring::{
+  rand::{SecureRandom, SystemRandom},
+  signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+};
+use sha2::{Digest, Sha256};
+use std::{
+  fs::{create_dir_all, read, read_to_string, write},
+  net::{IpAddr, Ipv4Addr, Ipv6Addr},
+  path::{Path, PathBuf},
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const CA_NAME: &str = "Tauri Development CA";
+const CA_VALIDITY: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+// Apple platforms reject server certificates valid for more than 825 days
+const CERTIFICATE_VALIDITY: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// A server certificate and its PKCS#8 private key.
+pub struct DevCertificate {
+  /// The DER encoded certificate chain, the server certificate first.
+  pub chain: Vec<Vec<u8>>,
+  pub private_key: Vec<u8>,
+}
+
+/// The directory storing the certificate authority.
+pub fn certificate_authority_dir() -> PathBuf {
+  dirs_next::data_local_dir()
+    .unwrap_or_else(std::env::temp_dir)
+    .join("tauri")
+    .join("dev-certificate-authority")
+}
+
+/// The path of the PEM encoded certificate authority, to install on the devices loading the dev server.
+pub fn certificate_authority_path() -> PathBuf {
+  certificate_authority_dir().join("ca.crt")
+}
+
+/// Issues a certificate for `localhost`, the loopback addresses and `ip`.
+pub fn dev_certificate(ip: IpAddr) -> Result<DevCertificate> {
+  let rng = SystemRandom::new();
+  let (ca_certificate, ca_key) = certificate_authority(&rng)?;
+
+  let key = generate_key(&rng)?;
+  let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &key)
+    .map_err(|_| anyhow::anyhow!("failed to load the dev server private key"))?;
+
+  let mut ips = vec![
+    IpAddr::V4(Ipv4Addr::LOCALHOST),
+    IpAddr::V6(Ipv6Addr::LOCALHOST),
+  ];
+  if !ips.contains(&ip) && !ip.is_unspecified() {
+    ips.push(ip);
+  }
+  let mut alt_names = vec![der(0x82, b"localhost")];
+  for ip in ips {
+    alt_names.push(match ip {
+      IpAddr::V4(ip) => der(0x87, &ip.octets()),
+      IpAddr::V6(ip) => der(0x87, &ip.octets()),
+    });
+  }
+
+  let extensions = vec![
+    extension(&[2, 5, 29, 19], true, sequence(&[])),
+    // digitalSignature
+    extension(&[2, 5, 29, 15], true, der(0x03, &[7, 0x80])),
+    // serverAuth
+    extension(
+      &[2, 5, 29, 37],
+      false,
+      sequence(&[oid(&[1, 3, 6, 1, 5, 5, 7, 3, 1])]),
+    ),
+    extension(&[2, 5, 29, 17], false, der(0x30, &alt_names.concat())),
+    extension(
+      &[2, 5, 29, 35],
+      false,
+      sequence(&[der(0x80, &key_identifier(ca_key.public_key().as_ref()))]),
+    ),
+  ];
+
+  let certificate = certificate(
+    &rng,
+    &ca_key,
+    &name(&format!("Tauri Development Server ({ip})")),
+    key_pair.public_key().as_ref(),
+    CERTIFICATE_VALIDITY,
+    extensions,
+  )?;
+
+  Ok(DevCertificate {
+    chain: vec![certificate, ca_certificate],
+    private_key: key,
+  })
+}
+
+/// Loads the certificate authority, generating it if it doesn't exist yet.
+fn certificate_authority(rng: &SystemRandom) -> Result<(Vec<u8>, EcdsaKeyPair)> {
+  let dir = certificate_authority_dir();
+  let certificate_path = certificate_authority_path();
+  let key_path = dir.join("ca.key");
+
+  if certificate_path.exists() && key_path.exists() {
+    let certificate = from_pem(&read_to_string(&certificate_path)?)
+      .with_context(|| format!("invalid certificate {}", certificate_path.display()))?;
+    let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &read(&key_path)?)
+      .map_err(|_| anyhow::anyhow!("invalid private key {}", key_path.display()))?;
+    return Ok((certificate, key));
+  }
+
+  let pkcs8 = generate_key(rng)?;
+  let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &pkcs8)
+    .map_err(|_| anyhow::anyhow!("failed to load the certificate authority private key"))?;
+  let key_identifier = key_identifier(key.public_key().as_ref());
+  let extensions = vec![
+    // cA: true
+    extension(&[2, 5, 29, 19], true, sequence(&[der(0x01, &[0xff])])),
+    // keyCertSign and cRLSign
+    extension(&[2, 5, 29, 15], true, der(0x03, &[1, 0x06])),
+    extension(&[2, 5, 29, 14], false, der(0x04, &key_identifier)),
+  ];
+  let certificate = certificate(
+    rng,
+    &key,
+    &name(CA_NAME),
+    key.public_key().as_ref(),
+    CA_VALIDITY,
+    extensions,
+  )?;
+
+  create_dir_all(&dir)?;
+  write_private(&key_path, &pkcs8)?;
+  write(&certificate_path, to_pem(&certificate))?;
+  info!(
+    "Generated the dev server certificate authority at {}",
+    certificate_path.display()
+  );
+  trust(&certificate_path);
+
+  Ok((certificate, key))
+}
+
+/// Adds the certificate authority to the trusted certificates of the user.
+fn trust(path: &Path) {
+  #[cfg(target_os = "macos")]
+  let result = std::process::Command::new("security")
+    .args(["add-trusted-cert", "-r", "trustRoot", "-k"])
+    .arg(
+      dirs_next::home_dir()
+        .unwrap_or_default()
+        .join("Library/Keychains/login.keychain-db"),
+    )
+    .arg(path)
+    .output_ok();
+  #[cfg(windows)]
+  let result = std::process::Command::new("certutil")
+    .args(["-user", "-addstore", "Root"])
+    .arg(path)
+    .output_ok();
+  #[cfg(not(any(target_os = "macos", windows)))]
+  let result: Result<()> = Err(anyhow::anyhow!(
+    "it must be added to the system certificates manually, e.g. with `sudo cp {} /usr/local/share/ca-certificates/tauri-dev.crt && sudo update-ca-certificates` on Debian-based distributions or `sudo trust anchor {}` on Fedora and Arch Linux",
+    path.display(),
+    path.display()
+  ));
+
+  match result {
+    Ok(_) => info!("Added the dev server certificate authority to the trusted certificates"),
+    Err(e) => warn!(
+      "The dev server certificate authority {} is not trusted: {e:#}",
+      path.display()
+    ),
+  }
+}
+
+fn generate_key(rng: &SystemRandom) -> Result<Vec<u8>> {
+  EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, rng)
+    .map(|key| key.as_ref().to_vec())
+    .map_err(|_| anyhow::anyhow!("failed to generate a private key"))
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+  #[cfg(unix)]
+  {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+    std::fs::OpenOptions::new()
+      .write(true)
+      .create(true)
+      .truncate(true)
+      .mode(0o600)
+      .open(path)?
+      .write_all(contents)?;
+  }
+  #[cfg(not(unix))]
+  write(path, contents)?;
+  Ok(())
+}
+
+/// Builds and signs an X.509 v3 certificate.
+fn certificate(
+  rng: &SystemRandom,
+  issuer: &EcdsaKeyPair,
+  subject: &[u8],
+  public_key: &[u8],
+  validity: Duration,
+  extensions: Vec<Vec<u8>>,
+) -> Result<Vec<u8>> {
+  let mut serial = [0u8; 16];
+  rng
+    .fill(&mut serial)
+    .map_err(|_| anyhow::anyhow!("failed to generate a certificate serial number"))?;
+  // a positive integer without leading zeros
+  serial[0] = (serial[0] & 0x7f) | 0x40;
+
+  let now = SystemTime::now();
+  let not_before = now - Duration::from_secs(24 * 60 * 60);
+  let signature_algorithm = sequence(&[oid(&[1, 2, 840, 10045, 4, 3, 2])]);
+
+  let tbs_certificate = sequence(&[
+    // v3
+    der(0xa0, &der(0x02, &[2])),
+    der(0x02, &serial),
+    signature_algorithm.clone(),
+    name(CA_NAME),
+    sequence(&[utc_time(not_before), utc_time(now + validity)]),
+    subject.to_vec(),
+    sequence(&[
+      sequence(&[
+        oid(&[1, 2, 840, 10045, 2, 1]),
+        oid(&[1, 2, 840, 10045, 3, 1, 7]),
+      ]),
+      bit_string(public_key),
+    ]),
+    der(0xa3, &sequence(&extensions)),
+  ]);
+
+  let signature = issuer
+    .sign(rng, &tbs_certificate)
+    .map_err(|_| anyhow::anyhow!("failed to sign the certificate"))?;
+
+  Ok(sequence(&[
+    tbs_certificate,
+    signature_algorithm,
+    bit_string(signature.as_ref()),
+  ]))
+}
+
+/// A distinguished name with a single common name.
+fn name(common_name: &str) -> Vec<u8> {
+  sequence(&[der(
+    0x31,
+    &sequence(&[oid(&[2, 5, 4, 3]), der(0x0c, common_name.as_bytes())]),
+  )])
+}
+
+fn extension(id: &[u64], critical: bool, value: Vec<u8>) -> Vec<u8> {
+  let mut fields = vec![oid(id)];
+  if critical {
+    fields.push(der(0x01, &[0xff]));
+  }
+  fields.push(der(0x04, &value));
+  sequence(&fields)
+}
+
+fn key_identifier(public_key: &[u8]) -> Vec<u8> {
+  Sha256::digest(public_key)[..20].to_vec()
+}
+
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+  let mut out = vec![tag];
+  let length = content.len();
+  if length < 0x80 {
+    out.push(length as u8);
+  } else {
+    let bytes = length.to_be_bytes();
+    let bytes = &bytes[bytes.iter().position(|b| *b != 0).unwrap()..];
+    out.push(0x80 | bytes.len() as u8);
+    out.extend_from_slice(bytes);
+  }
+  out.extend_from_slice(content);
+  out
+}
+
+fn sequence(items: &[Vec<u8>]) -> Vec<u8> {
+  der(0x30, &items.concat())
+}
+
+fn bit_string(bytes: &[u8]) -> Vec<u8> {
+  der(0x03, &[&[0], bytes].concat())
+}
+
+fn oid(arcs: &[u64]) -> Vec<u8> {
+  let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+  for arc in &arcs[2..] {
+    let mut bytes = vec![(arc & 0x7f) as u8];
+    let mut arc = arc >> 7;
+    while arc > 0 {
+      bytes.push((arc & 0x7f) as u8 | 0x80);
+      arc >>= 7;
+    }
+    bytes.reverse();
+    content.extend(bytes);
+  }
+  der(0x06, &content)
+}
+
+/// Encodes the time as an ASN.1 UTCTime, valid until 2049.
+fn utc_time(time: SystemTime) -> Vec<u8> {
+  let seconds = time.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+  let (days, seconds) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+
+  // converts the days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+  let z = days + 719468;
+  let era = z.div_euclid(146097);
+  let doe = z - era * 146097;
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+
+  der(
+    0x17,
+    format!(
+      "{:02}{month:02}{day:02}{:02}{:02}{:02}Z",
+      year % 100,
+      seconds / 3600,
+      seconds % 3600 / 60,
+      seconds % 60
+    )
+    .as_bytes(),
+  )
+}
+
+fn to_pem(der: &[u8]) -> String {
+  let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+  let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+  for line in encoded.as_bytes().chunks(64) {
+    pem.push_str(std::str::from_utf8(line).unwrap());
+    pem.push('\n');
+  }
+  pem.push_str("-----END CERTIFICATE-----\n");
+  pem
+}
+
+fn from_pem(pem: &str) -> Result<Vec<u8>> {
+  let encoded = pem
+    .lines()
+    .filter(|line| !line.starts_with("-----"))
+    .collect::<String>();
+  base64::engine::general_purpose::STANDARD
+    .decode(encoded.trim())
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::{Duration, UNIX_EPOCH};
+
+  #[test]
+  fn der_encoding() {
+    assert_eq!(
+      super::oid(&[1, 2, 840, 10045, 4, 3, 2]),
+      [0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02]
+    );
+    assert_eq!(&super::der(0x04, &[0; 200])[..3], [0x04, 0x81, 200]);
+    assert_eq!(
+      super::utc_time(UNIX_EPOCH + Duration::from_secs(1_709_251_199)),
+      [&[0x17, 13][..], b"240229235959Z"].concat()
+    );
+  }
+}
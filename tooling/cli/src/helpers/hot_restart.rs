@@ -0,0 +1,101 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The server `tauri dev` uses to ask the app to save its state before restarting it,
+//! see the `hot-restart` core plugin of the `tauri` crate.
+
+use log::{debug, warn};
+use std::{
+  fs::remove_file,
+  io::{self, BufRead, BufReader, Write},
+  net::{Ipv4Addr, TcpListener, TcpStream},
+  path::PathBuf,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+const ADDRESS_ENV: &str = "TAURI_DEV_HOT_RESTART_ADDRESS";
+const STATE_ENV: &str = "TAURI_DEV_HOT_RESTART_STATE";
+/// How long the app has to run the `on_hot_restart` hook and save the state of its windows.
+const SAVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct HotRestartServer {
+  /// The connection of the running app.
+  connection: Arc<Mutex<Option<TcpStream>>>,
+  state_path: PathBuf,
+}
+
+impl HotRestartServer {
+  /// Starts the server and sets the environment variables the app reads to connect to it.
+  pub fn start() -> io::Result<Self> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let state_path =
+      std::env::temp_dir().join(format!("tauri-hot-restart-{}.json", std::process::id()));
+    let _ = remove_file(&state_path);
+    std::env::set_var(ADDRESS_ENV, listener.local_addr()?.to_string());
+    std::env::set_var(STATE_ENV, &state_path);
+
+    let connection = Arc::new(Mutex::new(None));
+    let connection_ = connection.clone();
+    std::thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        connection_.lock().unwrap().replace(stream);
+      }
+    });
+
+    Ok(Self {
+      connection,
+      state_path,
+    })
+  }
+
+  /// Asks the running app to save its state, returning once it is saved.
+  pub fn save_state(&self) {
+    let Some(mut stream) = self.connection.lock().unwrap().take() else {
+      return;
+    };
+    let saved =
+      stream.set_read_timeout(Some(SAVE_TIMEOUT)).is_ok() && writeln!(stream, "save").is_ok() && {
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).is_ok() && line.trim() == "saved"
+      };
+    if saved {
+      debug!("Saved the app state before restarting it");
+    } else {
+      warn!("The app did not save its state before restarting");
+    }
+  }
+}
+
+impl Drop for HotRestartServer {
+  fn drop(&mut self) {
+    let _ = remove_file(&self.state_path);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::{BufRead, BufReader, Write};
+
+  #[test]
+  fn save_state() {
+    let server = super::HotRestartServer::start().unwrap();
+    let address = std::env::var(super::ADDRESS_ENV).unwrap();
+
+    let app = std::thread::spawn(move || {
+      let mut stream = std::net::TcpStream::connect(address).unwrap();
+      let mut line = String::new();
+      BufReader::new(&stream).read_line(&mut line).unwrap();
+      assert_eq!(line, "save\n");
+      stream.write_all(b"saved\n").unwrap();
+    });
+    while server.connection.lock().unwrap().is_none() {
+      std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    server.save_state();
+    app.join().unwrap();
+    assert!(server.connection.lock().unwrap().is_none());
+  }
+}
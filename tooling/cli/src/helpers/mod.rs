@@ -4,9 +4,12 @@
 
 pub mod app_paths;
 pub mod config;
+pub mod dev_certificate;
 pub mod flock;
 pub mod framework;
+pub mod hot_restart;
 pub mod npm;
+pub mod process;
 pub mod template;
 pub mod updater_signature;
 pub mod web_dev_server;
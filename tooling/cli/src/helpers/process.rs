@@ -0,0 +1,173 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Spawning commands whose whole process tree can be killed,
+//! e.g. a `beforeDevCommand` running `npm run dev`, which spawns the dev server from a shell.
+
+use shared_child::SharedChild;
+use std::{io, process::Command, sync::Arc};
+
+/// A child process and its descendants.
+///
+/// On Unix the child is the leader of a new process group, on Windows it is assigned to a job object
+/// that also kills the tree when the CLI exits.
+pub struct ProcessTree {
+  child: Arc<SharedChild>,
+  #[cfg(windows)]
+  job: Option<windows::Job>,
+}
+
+impl ProcessTree {
+  /// Spawns the command.
+  pub fn spawn(command: &mut Command) -> io::Result<Self> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+
+    let child = Arc::new(SharedChild::spawn(command)?);
+
+    Ok(Self {
+      #[cfg(windows)]
+      job: windows::Job::assign(child.id()),
+      child,
+    })
+  }
+
+  /// The root process of the tree.
+  pub fn child(&self) -> &Arc<SharedChild> {
+    &self.child
+  }
+
+  /// Kills every process of the tree.
+  ///
+  /// On Unix the processes are sent a `SIGTERM` so they can clean up, and a `SIGKILL` if they are still running after two seconds.
+  pub fn kill(&self) {
+    #[cfg(unix)]
+    {
+      use std::time::{Duration, Instant};
+
+      let pgid = self.child.id() as libc::pid_t;
+      // SAFETY: the process group was created by `spawn`
+      unsafe { libc::killpg(pgid, libc::SIGTERM) };
+      let deadline = Instant::now() + Duration::from_secs(2);
+      // `killpg` with no signal checks whether a process of the group is still alive,
+      // the group leader must be reaped first since it is a zombie until then
+      while {
+        let _ = self.child.try_wait();
+        unsafe { libc::killpg(pgid, 0) == 0 }
+      } {
+        if Instant::now() > deadline {
+          unsafe { libc::killpg(pgid, libc::SIGKILL) };
+          break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+      }
+    }
+
+    #[cfg(windows)]
+    match &self.job {
+      Some(job) => job.terminate(),
+      None => windows::kill_tree(self.child.id()),
+    }
+
+    let _ = self.child.kill();
+  }
+}
+
+#[cfg(windows)]
+mod windows {
+  use std::{mem::size_of, process::Command, ptr::null_mut};
+  use winapi::um::{
+    handleapi::CloseHandle,
+    jobapi2::{
+      AssignProcessToJobObject, CreateJobObjectW, SetInformationJobObject, TerminateJobObject,
+    },
+    processthreadsapi::OpenProcess,
+    winnt::{
+      JobObjectExtendedLimitInformation, HANDLE, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+      JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, PROCESS_SET_QUOTA, PROCESS_TERMINATE,
+    },
+  };
+
+  pub struct Job(HANDLE);
+
+  // SAFETY: job object handles can be used from any thread
+  unsafe impl Send for Job {}
+  unsafe impl Sync for Job {}
+
+  impl Job {
+    /// Creates a job object killing its processes when closed and assigns the process to it.
+    ///
+    /// The processes it spawns afterwards are added to the job automatically.
+    pub fn assign(pid: u32) -> Option<Self> {
+      unsafe {
+        let job = CreateJobObjectW(null_mut(), null_mut());
+        if job.is_null() {
+          return None;
+        }
+        let job = Self(job);
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        if SetInformationJobObject(
+          job.0,
+          JobObjectExtendedLimitInformation,
+          &mut info as *mut _ as *mut _,
+          size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        ) == 0
+        {
+          return None;
+        }
+
+        let process = OpenProcess(PROCESS_SET_QUOTA | PROCESS_TERMINATE, 0, pid);
+        if process.is_null() {
+          return None;
+        }
+        let assigned = AssignProcessToJobObject(job.0, process) != 0;
+        CloseHandle(process);
+
+        assigned.then_some(job)
+      }
+    }
+
+    pub fn terminate(&self) {
+      unsafe { TerminateJobObject(self.0, 1) };
+    }
+  }
+
+  impl Drop for Job {
+    fn drop(&mut self) {
+      unsafe { CloseHandle(self.0) };
+    }
+  }
+
+  /// Kills the process and its descendants, for processes that could not be assigned to a job object.
+  pub fn kill_tree(pid: u32) {
+    let powershell_path = std::env::var("SYSTEMROOT").map_or_else(
+      |_| "powershell.exe".to_string(),
+      |p| format!("{p}\\System32\\WindowsPowerShell\\v1.0\\powershell.exe"),
+    );
+    let _ = Command::new(powershell_path)
+      .arg("-NoProfile")
+      .arg("-Command")
+      .arg(format!("function Kill-Tree {{ Param([int]$ppid); Get-CimInstance Win32_Process | Where-Object {{ $_.ParentProcessId -eq $ppid }} | ForEach-Object {{ Kill-Tree $_.ProcessId }}; Stop-Process -Id $ppid -ErrorAction SilentlyContinue }}; Kill-Tree {pid}"))
+      .status();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(unix)]
+  #[test]
+  fn kill_process_tree() {
+    let mut command = std::process::Command::new("sh");
+    command.arg("-c").arg("sleep 30 & sleep 30 & wait");
+    let tree = super::ProcessTree::spawn(&mut command).unwrap();
+    let pgid = tree.child().id() as libc::pid_t;
+
+    tree.kill();
+    tree.child().wait().unwrap();
+    // SAFETY: only checks whether a process of the group still exists
+    assert_ne!(unsafe { libc::killpg(pgid, 0) }, 0);
+  }
+}
@@ -3,40 +3,90 @@
 // SPDX-License-Identifier: MIT
 
 use axum::{
+  body::Body,
   extract::{ws::WebSocket, WebSocketUpgrade},
-  http::{header::CONTENT_TYPE, Request, StatusCode},
-  response::IntoResponse,
-  routing::get,
+  http::{
+    header::{CONTENT_TYPE, HOST, UPGRADE},
+    HeaderValue, Method, Request, StatusCode,
+  },
+  response::{IntoResponse, Response},
+  routing::{any, get},
   Router, Server,
 };
 use html5ever::{namespace_url, ns, LocalName, QualName};
+use hyper::client::{Client, HttpConnector};
 use kuchiki::{traits::TendrilSink, NodeRef};
 use notify::RecursiveMode;
 use notify_debouncer_mini::new_debouncer;
+use rustls::{Certificate, PrivateKey, ServerConfig, ServerConnection};
 use std::{
-  net::{IpAddr, SocketAddr},
+  io::{self, Read, Write},
+  net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
   path::{Path, PathBuf},
   sync::{mpsc::sync_channel, Arc},
   thread,
   time::Duration,
 };
-use tauri_utils::mime_type::MimeType;
-use tokio::sync::broadcast::{channel, Sender};
+use tauri_utils::{config::DevServerConfig, mime_type::MimeType};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpStream,
+  sync::broadcast::{channel, Sender},
+};
+use url::Url;
+
+use super::dev_certificate::dev_certificate;
 
 const AUTO_RELOAD_SCRIPT: &str = include_str!("./auto-reload.js");
 
+/// What the dev server serves for the requests that don't match a proxy rule.
+pub enum DevServerTarget {
+  /// Serves the files of the directory, reloading the page when they change.
+  Dir(PathBuf),
+  /// Proxies the requests to the frontend dev server at the URL.
+  Url(Url),
+}
+
 struct State {
-  serve_dir: PathBuf,
+  target: DevServerTarget,
+  /// The proxy rules, longest path prefix first.
+  proxy: Vec<(String, Url)>,
+  client: Client<HttpConnector>,
+  https: bool,
   address: SocketAddr,
   tx: Sender<()>,
 }
 
-pub fn start_dev_server<P: AsRef<Path>>(
-  path: P,
+/// Starts the dev server on the given IP address, returning its URL.
+pub fn start_dev_server(
+  target: DevServerTarget,
   ip: IpAddr,
   port: Option<u16>,
-) -> crate::Result<SocketAddr> {
-  let serve_dir = path.as_ref().to_path_buf();
+  config: &DevServerConfig,
+) -> crate::Result<Url> {
+  let mut proxy = config
+    .proxy
+    .iter()
+    .map(|(prefix, upstream)| (prefix.trim_end_matches('/').to_string(), upstream.clone()))
+    .collect::<Vec<_>>();
+  proxy.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+  let target_url = match &target {
+    DevServerTarget::Url(url) => Some(url),
+    DevServerTarget::Dir(_) => None,
+  };
+  for upstream in proxy.iter().map(|(_, upstream)| upstream).chain(target_url) {
+    if upstream.scheme() != "http" {
+      return Err(anyhow::anyhow!(
+        "cannot proxy requests to {upstream}: only http URLs are supported"
+      ));
+    }
+  }
+
+  let tls_config = if config.https {
+    Some(tls_config(ip)?)
+  } else {
+    None
+  };
 
   let (server_url_tx, server_url_rx) = std::sync::mpsc::channel();
 
@@ -48,28 +98,30 @@ pub fn start_dev_server<P: AsRef<Path>>(
       .block_on(async move {
         let (tx, _) = channel(1);
 
-        let tokio_tx = tx.clone();
-        let serve_dir_ = serve_dir.clone();
-        thread::spawn(move || {
-          let (tx, rx) = sync_channel(1);
-          let mut watcher = new_debouncer(Duration::from_secs(1), None, move |r| {
-            if let Ok(events) = r {
-              tx.send(events).unwrap()
-            }
-          })
-          .unwrap();
-
-          watcher
-            .watcher()
-            .watch(&serve_dir_, RecursiveMode::Recursive)
+        if let DevServerTarget::Dir(serve_dir) = &target {
+          let tokio_tx = tx.clone();
+          let serve_dir_ = serve_dir.clone();
+          thread::spawn(move || {
+            let (tx, rx) = sync_channel(1);
+            let mut watcher = new_debouncer(Duration::from_secs(1), None, move |r| {
+              if let Ok(events) = r {
+                tx.send(events).unwrap()
+              }
+            })
             .unwrap();
 
-          loop {
-            if rx.recv().is_ok() {
-              let _ = tokio_tx.send(());
+            watcher
+              .watcher()
+              .watch(&serve_dir_, RecursiveMode::Recursive)
+              .unwrap();
+
+            loop {
+              if rx.recv().is_ok() {
+                let _ = tokio_tx.send(());
+              }
             }
-          }
-        });
+          });
+        }
 
         let mut auto_port = false;
         let mut port = port.unwrap_or_else(|| {
@@ -82,47 +134,77 @@ pub fn start_dev_server<P: AsRef<Path>>(
             .unwrap()
         });
 
-        let (server, server_url) = loop {
+        let (listener, server_url) = loop {
           let server_url = SocketAddr::new(ip, port);
-          let server = Server::try_bind(&server_url);
+          let listener = TcpListener::bind(server_url);
 
           if !auto_port {
-            break (server, server_url);
+            break (listener, server_url);
           }
 
-          if server.is_ok() {
-            break (server, server_url);
+          if listener.is_ok() {
+            break (listener, server_url);
           }
 
           port += 1;
         };
 
+        // with HTTPS the listener decrypts the connections and forwards them to the HTTP server on a random port
+        let server = listener
+          .and_then(|listener| match &tls_config {
+            Some(tls_config) => {
+              let server = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+              listener.set_nonblocking(true)?;
+              tokio::spawn(terminate_tls(
+                tokio::net::TcpListener::from_std(listener)?,
+                server.local_addr()?,
+                tls_config.clone(),
+              ));
+              Ok(server)
+            }
+            None => Ok(listener),
+          })
+          .map_err(anyhow::Error::from)
+          .and_then(|listener| Server::from_tcp(listener).map_err(Into::into));
+
+        let https = tls_config.is_some();
+        let serves_dir = matches!(target, DevServerTarget::Dir(_));
         let state = Arc::new(State {
-          serve_dir,
+          target,
+          proxy,
+          client: Client::new(),
+          https,
           tx,
           address: server_url,
         });
-        let router = Router::new()
-          .fallback(
-            Router::new().nest(
-              "/",
-              get({
-                let state = state.clone();
-                move |req| handler(req, state)
-              })
-              .handle_error(|_error| async move { StatusCode::INTERNAL_SERVER_ERROR }),
-            ),
-          )
-          .route(
+        let mut router = Router::new().fallback(
+          Router::new().nest(
+            "/",
+            any({
+              let state = state.clone();
+              move |req| handler(req, state)
+            })
+            .handle_error(|_error| async move { StatusCode::INTERNAL_SERVER_ERROR }),
+          ),
+        );
+        if serves_dir {
+          router = router.route(
             "/__tauri_cli",
             get(move |ws: WebSocketUpgrade| async move {
               ws.on_upgrade(|socket| async move { ws_handler(socket, state).await })
             }),
           );
+        }
 
         match server {
           Ok(server) => {
-            server_url_tx.send(Ok(server_url)).unwrap();
+            server_url_tx
+              .send(Ok(
+                format!("{}://{server_url}", if https { "https" } else { "http" })
+                  .parse()
+                  .unwrap(),
+              ))
+              .unwrap();
             server.serve(router.into_make_service()).await.unwrap();
           }
           Err(e) => {
@@ -139,7 +221,178 @@ pub fn start_dev_server<P: AsRef<Path>>(
   server_url_rx.recv().unwrap()
 }
 
-async fn handler<T>(req: Request<T>, state: Arc<State>) -> impl IntoResponse {
+async fn handler(req: Request<Body>, state: Arc<State>) -> Response {
+  let path = req.uri().path();
+  let upstream = state
+    .proxy
+    .iter()
+    .find(|(prefix, _)| {
+      path
+        .strip_prefix(prefix.as_str())
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+    })
+    .map(|(_, upstream)| upstream);
+
+  match (upstream, &state.target) {
+    (Some(upstream), _) | (None, DevServerTarget::Url(upstream)) => {
+      proxy(req, upstream, &state).await
+    }
+    (None, DevServerTarget::Dir(serve_dir)) => {
+      if req.method() == Method::GET || req.method() == Method::HEAD {
+        serve_file(req, serve_dir, &state).into_response()
+      } else {
+        StatusCode::METHOD_NOT_ALLOWED.into_response()
+      }
+    }
+  }
+}
+
+/// Forwards the request to the upstream server, including WebSocket connections.
+async fn proxy(mut req: Request<Body>, upstream: &Url, state: &State) -> Response {
+  let mut url = upstream.clone();
+  url.set_path(&format!(
+    "{}{}",
+    upstream.path().trim_end_matches('/'),
+    req.uri().path()
+  ));
+  url.set_query(req.uri().query());
+  *req.uri_mut() = match url.as_str().parse() {
+    Ok(uri) => uri,
+    Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+  };
+
+  let authority = &url[url::Position::BeforeHost..url::Position::AfterPort];
+  let headers = req.headers_mut();
+  if let Some(host) = headers.insert(HOST, HeaderValue::from_str(authority).unwrap()) {
+    headers.insert("x-forwarded-host", host);
+  }
+  headers.insert(
+    "x-forwarded-proto",
+    HeaderValue::from_static(if state.https { "https" } else { "http" }),
+  );
+
+  let client_upgrade = req
+    .headers()
+    .contains_key(UPGRADE)
+    .then(|| hyper::upgrade::on(&mut req));
+
+  match state.client.request(req).await {
+    Ok(mut response) => {
+      if let Some(client_upgrade) = client_upgrade {
+        if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+          let upstream_upgrade = hyper::upgrade::on(&mut response);
+          tokio::spawn(async move {
+            if let (Ok(mut client), Ok(mut upstream)) =
+              (client_upgrade.await, upstream_upgrade.await)
+            {
+              let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+            }
+          });
+        }
+      }
+      response.into_response()
+    }
+    Err(e) => (
+      StatusCode::BAD_GATEWAY,
+      format!("failed to proxy the request to {url}: {e}"),
+    )
+      .into_response(),
+  }
+}
+
+fn tls_config(ip: IpAddr) -> crate::Result<Arc<ServerConfig>> {
+  let certificate = dev_certificate(ip)?;
+  let mut config = ServerConfig::builder()
+    .with_safe_defaults()
+    .with_no_client_auth()
+    .with_single_cert(
+      certificate.chain.into_iter().map(Certificate).collect(),
+      PrivateKey(certificate.private_key),
+    )?;
+  config.alpn_protocols = vec![b"http/1.1".to_vec()];
+  Ok(Arc::new(config))
+}
+
+/// Accepts the TLS connections, forwarding their decrypted traffic to the HTTP server.
+async fn terminate_tls(
+  listener: tokio::net::TcpListener,
+  server: SocketAddr,
+  config: Arc<ServerConfig>,
+) {
+  loop {
+    if let Ok((stream, _)) = listener.accept().await {
+      let config = config.clone();
+      tokio::spawn(async move {
+        let _ = forward_tls(stream, server, config).await;
+      });
+    }
+  }
+}
+
+async fn forward_tls(
+  mut client: TcpStream,
+  server: SocketAddr,
+  config: Arc<ServerConfig>,
+) -> io::Result<()> {
+  let mut connection =
+    ServerConnection::new(config).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+  let mut server = TcpStream::connect(server).await?;
+  let mut client_buffer = vec![0; 16 * 1024];
+  let mut server_buffer = vec![0; 16 * 1024];
+  let mut plaintext = Vec::new();
+
+  loop {
+    flush_tls(&mut connection, &mut client).await?;
+
+    tokio::select! {
+      read = client.read(&mut client_buffer) => {
+        let read = read?;
+        if read == 0 {
+          return Ok(());
+        }
+        let mut records = &client_buffer[..read];
+        while !records.is_empty() {
+          connection.read_tls(&mut records)?;
+          let state = match connection.process_new_packets() {
+            Ok(state) => state,
+            Err(e) => {
+              // send the alert describing the error
+              let _ = flush_tls(&mut connection, &mut client).await;
+              return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+          };
+          if state.plaintext_bytes_to_read() > 0 {
+            plaintext.resize(state.plaintext_bytes_to_read(), 0);
+            connection.reader().read_exact(&mut plaintext)?;
+            server.write_all(&plaintext).await?;
+          }
+          if state.peer_has_closed() {
+            return Ok(());
+          }
+        }
+      }
+      read = server.read(&mut server_buffer), if !connection.is_handshaking() => {
+        let read = read?;
+        if read == 0 {
+          connection.send_close_notify();
+          return flush_tls(&mut connection, &mut client).await;
+        }
+        connection.writer().write_all(&server_buffer[..read])?;
+      }
+    }
+  }
+}
+
+async fn flush_tls(connection: &mut ServerConnection, client: &mut TcpStream) -> io::Result<()> {
+  while connection.wants_write() {
+    let mut records = Vec::new();
+    connection.write_tls(&mut records)?;
+    client.write_all(&records).await?;
+  }
+  Ok(())
+}
+
+fn serve_file<T>(req: Request<T>, serve_dir: &Path, state: &State) -> impl IntoResponse {
   let uri = req.uri().to_string();
   let uri = if uri == "/" {
     &uri
@@ -147,10 +400,10 @@ async fn handler<T>(req: Request<T>, state: Arc<State>) -> impl IntoResponse {
     uri.strip_prefix('/').unwrap_or(&uri)
   };
 
-  let file = std::fs::read(state.serve_dir.join(uri))
-    .or_else(|_| std::fs::read(state.serve_dir.join(format!("{}.html", &uri))))
-    .or_else(|_| std::fs::read(state.serve_dir.join(format!("{}/index.html", &uri))))
-    .or_else(|_| std::fs::read(state.serve_dir.join("index.html")));
+  let file = std::fs::read(serve_dir.join(uri))
+    .or_else(|_| std::fs::read(serve_dir.join(format!("{}.html", &uri))))
+    .or_else(|_| std::fs::read(serve_dir.join(format!("{}/index.html", &uri))))
+    .or_else(|_| std::fs::read(serve_dir.join("index.html")));
 
   file
     .map(|mut f| {
@@ -175,7 +428,11 @@ async fn handler<T>(req: Request<T>, state: Arc<State>) -> impl IntoResponse {
             NodeRef::new_element(QualName::new(None, ns!(html), "script".into()), None);
           script_el.append(NodeRef::new_text(AUTO_RELOAD_SCRIPT.replace(
             "{{reload_url}}",
-            &format!("ws://{}/__tauri_cli", state.address),
+            &format!(
+              "{}://{}/__tauri_cli",
+              if state.https { "wss" } else { "ws" },
+              state.address
+            ),
           )));
           head.prepend(script_el);
         });
@@ -2,11 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::Result;
+use crate::{OutputFormat, Result};
 use clap::Parser;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
   fmt::{self, Display, Formatter},
   panic,
@@ -72,7 +72,8 @@ pub(crate) fn cli_upstream_version() -> Result<String> {
     .map_err(|e| anyhow::Error::new(e))
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum Status {
   Neutral = 0,
   #[default]
@@ -170,7 +171,8 @@ struct Section<'a> {
 }
 
 impl Section<'_> {
-  fn display(&mut self) {
+  /// Runs all items and returns the most severe status.
+  fn run(&mut self) -> Status {
     let mut status = Status::Neutral;
 
     for item in &mut self.items {
@@ -180,6 +182,12 @@ impl Section<'_> {
       }
     }
 
+    status
+  }
+
+  fn display(&mut self) {
+    let status = self.run();
+
     let status_str = format!("[{status}]");
     let status = match status {
       Status::Neutral => status_str.normal(),
@@ -196,6 +204,28 @@ impl Section<'_> {
       }
     }
   }
+
+  fn display_json(&mut self) -> serde_json::Value {
+    let status = self.run();
+    let items = self
+      .items
+      .iter()
+      .filter_map(|item| {
+        item.description.as_ref().map(|description| {
+          let (label, value) = description.split_once(':').unwrap_or((description, ""));
+          serde_json::json!({
+            "label": label.trim(),
+            "value": value.trim(),
+            "status": item.status,
+          })
+        })
+      })
+      .collect::<Vec<_>>();
+    serde_json::json!({
+      "status": status,
+      "items": items,
+    })
+  }
 }
 
 #[derive(Debug, Parser)]
@@ -204,10 +234,18 @@ pub struct Options {
   /// Interactive mode to apply automatic fixes.
   #[clap(long)]
   pub interactive: bool,
+  /// The output format. `json` prints the information as a JSON object to stdout.
+  #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+  pub format: OutputFormat,
 }
 
 pub fn command(options: Options) -> Result<()> {
-  let Options { interactive } = options;
+  let Options {
+    interactive,
+    format,
+  } = options;
+  // the automatic fixes are prompted, which is not possible with machine readable output
+  let interactive = interactive && format == OutputFormat::Text;
   let hook = panic::take_hook();
   panic::set_hook(Box::new(|_info| {
     // do nothing
@@ -250,9 +288,7 @@ pub fn command(options: Options) -> Result<()> {
   };
   app.items.extend(app::items(app_dir, tauri_dir.as_deref()));
 
-  environment.display();
-  packages.display();
-  app.display();
+  let mut sections = vec![environment, packages, app];
 
   // iOS
   #[cfg(target_os = "macos")]
@@ -265,10 +301,26 @@ pub fn command(options: Options) -> Result<()> {
           items: Vec::new(),
         };
         ios.items.extend(ios::items());
-        ios.display();
+        sections.push(ios);
       }
     }
   }
 
+  match format {
+    OutputFormat::Text => {
+      for section in &mut sections {
+        section.display();
+      }
+    }
+    OutputFormat::Json => {
+      colored::control::set_override(false);
+      let output = sections
+        .iter_mut()
+        .map(|section| (section.label.to_lowercase(), section.display_json()))
+        .collect::<serde_json::Map<_, _>>();
+      println!("{}", serde_json::to_string_pretty(&output)?);
+    }
+  }
+
   Ok(())
 }
@@ -13,20 +13,27 @@ use std::{
   collections::BTreeMap,
   env::current_dir,
   fmt::Display,
-  fs::{read_to_string, remove_dir_all},
-  path::PathBuf,
+  fs::{create_dir_all, read_to_string, remove_dir_all, rename, File},
+  path::{Path, PathBuf},
   str::FromStr,
 };
 
 use crate::Result;
 use anyhow::Context;
-use clap::Parser;
-use dialoguer::Input;
+use clap::{Parser, ValueEnum};
+use dialoguer::{Input, Select};
 use handlebars::{to_json, Handlebars};
+use heck::ToKebabCase;
 use include_dir::{include_dir, Dir};
-use log::warn;
+use log::{info, warn};
+use tauri_utils::config::parse::ConfigFormat;
 
 const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/app");
+static VITE_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/presets/vite");
+static NEXT_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/presets/next");
+static SVELTEKIT_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/presets/sveltekit");
+static LEPTOS_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/presets/leptos");
+static YEW_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/presets/yew");
 const TAURI_CONF_TEMPLATE: &str = include_str!("../templates/tauri.conf.json");
 
 #[derive(Debug, Parser)]
@@ -66,6 +73,120 @@ pub struct Options {
   /// A shell command to run before `tauri build` kicks in.
   #[clap(long)]
   before_build_command: Option<String>,
+  /// The format of the Tauri configuration file
+  #[clap(long, value_enum, default_value_t = ConfigFileFormat::Json)]
+  config_format: ConfigFileFormat,
+  /// Scaffold a frontend next to the Tauri project, configuring its dev server and build commands.
+  ///
+  /// Prompted for when the directory has no `package.json` or `Cargo.toml` file.
+  #[clap(long, value_enum)]
+  preset: Option<Preset>,
+}
+
+/// A frontend scaffolded by `tauri init`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Preset {
+  /// Vanilla TypeScript with Vite
+  Vite,
+  /// React with Next.js
+  Next,
+  /// Svelte with SvelteKit
+  #[value(name = "sveltekit")]
+  SvelteKit,
+  /// Rust with Leptos, built with Trunk
+  Leptos,
+  /// Rust with Yew, built with Trunk
+  Yew,
+}
+
+impl Preset {
+  const ALL: [Self; 5] = [
+    Self::Vite,
+    Self::Next,
+    Self::SvelteKit,
+    Self::Leptos,
+    Self::Yew,
+  ];
+
+  fn template(self) -> &'static Dir<'static> {
+    match self {
+      Self::Vite => &VITE_TEMPLATE_DIR,
+      Self::Next => &NEXT_TEMPLATE_DIR,
+      Self::SvelteKit => &SVELTEKIT_TEMPLATE_DIR,
+      Self::Leptos => &LEPTOS_TEMPLATE_DIR,
+      Self::Yew => &YEW_TEMPLATE_DIR,
+    }
+  }
+
+  /// Whether the frontend is a Rust crate, built to WebAssembly.
+  fn is_rust(self) -> bool {
+    matches!(self, Self::Leptos | Self::Yew)
+  }
+
+  fn dev_path(self) -> &'static str {
+    match self {
+      // the port is set in the generated `next.config.js`, `vite.config.js` or `Trunk.toml`
+      Self::Next => "http://localhost:3000",
+      _ => "http://localhost:1420",
+    }
+  }
+
+  fn dist_dir(self) -> &'static str {
+    match self {
+      Self::Next => "../out",
+      Self::SvelteKit => "../build",
+      _ => "../dist",
+    }
+  }
+
+  fn before_dev_command(self) -> &'static str {
+    if self.is_rust() {
+      "trunk serve"
+    } else {
+      "npm run dev"
+    }
+  }
+
+  fn before_build_command(self) -> &'static str {
+    if self.is_rust() {
+      "trunk build"
+    } else {
+      "npm run build"
+    }
+  }
+}
+
+impl Display for Preset {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Vite => write!(f, "Vite (TypeScript)"),
+      Self::Next => write!(f, "Next.js (React)"),
+      Self::SvelteKit => write!(f, "SvelteKit (Svelte)"),
+      Self::Leptos => write!(f, "Leptos (Rust)"),
+      Self::Yew => write!(f, "Yew (Rust)"),
+    }
+  }
+}
+
+/// The format of the configuration file created by `tauri init`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ConfigFileFormat {
+  /// `tauri.conf.json`
+  Json,
+  /// `tauri.conf.json5`, JSON with comments
+  Json5,
+  /// `Tauri.toml`
+  Toml,
+}
+
+impl From<ConfigFileFormat> for ConfigFormat {
+  fn from(format: ConfigFileFormat) -> Self {
+    match format {
+      ConfigFileFormat::Json => Self::Json,
+      ConfigFileFormat::Json5 => Self::Json5,
+      ConfigFileFormat::Toml => Self::Toml,
+    }
+  }
 }
 
 #[derive(Default)]
@@ -79,6 +200,28 @@ impl Options {
     self.ci = self.ci || std::env::var("CI").is_ok();
     let package_json_path = PathBuf::from(&self.directory).join("package.json");
 
+    if self.preset.is_none()
+      && !self.ci
+      && !package_json_path.exists()
+      && !Path::new(&self.directory).join("Cargo.toml").exists()
+    {
+      self.preset = request_preset()?;
+    }
+    if let Some(preset) = self.preset {
+      self
+        .dist_dir
+        .get_or_insert_with(|| preset.dist_dir().into());
+      self
+        .dev_path
+        .get_or_insert_with(|| preset.dev_path().into());
+      self
+        .before_dev_command
+        .get_or_insert_with(|| preset.before_dev_command().into());
+      self
+        .before_build_command
+        .get_or_insert_with(|| preset.before_build_command().into());
+    }
+
     let init_defaults = if package_json_path.exists() {
       let package_json_text = read_to_string(package_json_path)?;
       let package_json: crate::PackageJson = serde_json::from_str(&package_json_text)?;
@@ -164,21 +307,26 @@ pub fn command(mut options: Options) -> Result<()> {
       template_target_path
     );
   } else {
+    let config_format = ConfigFormat::from(options.config_format);
+    let features = config_format
+      .cargo_feature()
+      .map(|feature| format!(r#", features = ["{feature}"]"#))
+      .unwrap_or_default();
     let (tauri_dep, tauri_build_dep) = if let Some(tauri_path) = options.tauri_path {
       (
         format!(
-          r#"{{  path = {:?} }}"#,
+          r#"{{  path = {:?}{features} }}"#,
           resolve_tauri_path(&tauri_path, "core/tauri")
         ),
         format!(
-          "{{  path = {:?} }}",
+          "{{  path = {:?}{features} }}",
           resolve_tauri_path(&tauri_path, "core/tauri-build")
         ),
       )
     } else {
       (
-        format!(r#"{{ version = "{}" }}"#, metadata.tauri),
-        format!(r#"{{ version = "{}" }}"#, metadata.tauri_build),
+        format!(r#"{{ version = "{}"{features} }}"#, metadata.tauri),
+        format!(r#"{{ version = "{}"{features} }}"#, metadata.tauri_build),
       )
     };
 
@@ -268,18 +416,86 @@ pub fn command(mut options: Options) -> Result<()> {
       }
     }
 
-    data.insert(
-      "tauri_config",
-      to_json(serde_json::to_string_pretty(&config).unwrap()),
-    );
+    let tauri_config = crate::helpers::config::to_string(&config, config_format)?;
+    data.insert("tauri_config", to_json(tauri_config));
 
     template::render(&handlebars, &data, &TEMPLATE_DIR, &options.directory)
       .with_context(|| "failed to render Tauri template")?;
+
+    if !matches!(config_format, ConfigFormat::Json) {
+      let src_tauri = Path::new(&options.directory).join("src-tauri");
+      rename(
+        src_tauri.join("tauri.conf.json"),
+        src_tauri.join(config_format.into_file_name()),
+      )?;
+    }
+
+    if let Some(preset) = options.preset {
+      let app_name = data["app_name"].as_str().unwrap_or_default();
+      data.insert("package_name", to_json(package_name(app_name)));
+      data.insert("api_version", to_json(format!("^{}", metadata.js_api)));
+      data.insert(
+        "cli_version",
+        to_json(format!("^{}", env!("CARGO_PKG_VERSION"))),
+      );
+
+      let directory = Path::new(&options.directory);
+      template::render_with_generator(
+        &handlebars,
+        &data,
+        preset.template(),
+        directory,
+        &mut |file_path| {
+          let path = directory.join(file_path);
+          if path.exists() && !options.force {
+            warn!(
+              "{} already exists, skipping it. Run `init --force` to overwrite.",
+              path.display()
+            );
+            return Ok(None);
+          }
+          create_dir_all(path.parent().unwrap())?;
+          File::create(path).map(Some)
+        },
+      )
+      .with_context(|| format!("failed to render the {preset} template"))?;
+
+      if preset.is_rust() {
+        info!("Install Trunk with `cargo install --locked trunk` and the WebAssembly target with `rustup target add wasm32-unknown-unknown`, then run `cargo tauri dev` to start the app");
+      } else {
+        info!("Run `npm install` and `npm run tauri dev` to start the app");
+      }
+    }
   }
 
   Ok(())
 }
 
+/// The name of the frontend package, a kebab-case version of the app name.
+fn package_name(app_name: &str) -> String {
+  let name = app_name
+    .to_kebab_case()
+    .chars()
+    .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+    .collect::<String>();
+  if name.is_empty() {
+    "tauri-app".into()
+  } else {
+    name
+  }
+}
+
+fn request_preset() -> Result<Option<Preset>> {
+  let mut items = vec!["None, I already have a frontend".to_string()];
+  items.extend(Preset::ALL.iter().map(ToString::to_string));
+  let index = Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+    .with_prompt("Which frontend do you want to scaffold?")
+    .items(&items)
+    .default(0)
+    .interact()?;
+  Ok(index.checked_sub(1).map(|i| Preset::ALL[i]))
+}
+
 fn request_input<T>(
   prompt: &str,
   initial: Option<T>,
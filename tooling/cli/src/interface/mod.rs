@@ -10,8 +10,9 @@ use std::{
   process::ExitStatus,
 };
 
-use crate::helpers::config::Config;
+use crate::helpers::config::{Config, ConfigMetadata};
 use tauri_bundler::bundle::{PackageType, Settings, SettingsBuilder};
+use tauri_utils::assets::EXTERNAL_ASSETS_FILE_NAME;
 
 pub use rust::{manifest, MobileOptions, Options, Rust as AppInterface};
 
@@ -58,9 +59,15 @@ pub trait AppSettings {
       tauri_utils::platform::target_triple()?
     };
 
+    let mut bundle_settings = self.get_bundle_settings(config, &enabled_features)?;
+    if config.tauri.external_assets.enable {
+      // written by the codegen when building the app
+      bundle_settings.external_assets = Some(out_dir.join(EXTERNAL_ASSETS_FILE_NAME));
+    }
+
     let mut settings_builder = SettingsBuilder::new()
       .package_settings(self.get_package_settings())
-      .bundle_settings(self.get_bundle_settings(config, &enabled_features)?)
+      .bundle_settings(bundle_settings)
       .binaries(self.get_binaries(config, &target)?)
       .project_out_directory(out_dir)
       .target(target);
@@ -86,7 +93,7 @@ pub enum ExitReason {
 pub trait Interface: Sized {
   type AppSettings: AppSettings;
 
-  fn new(config: &Config, target: Option<String>) -> crate::Result<Self>;
+  fn new(config: &ConfigMetadata, target: Option<String>) -> crate::Result<Self>;
   fn app_settings(&self) -> &Self::AppSettings;
   fn env(&self) -> HashMap<&str, String>;
   fn build(&mut self, options: Options) -> crate::Result<()>;
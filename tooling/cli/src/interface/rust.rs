@@ -18,19 +18,20 @@ use anyhow::Context;
 use heck::ToKebabCase;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use log::{debug, error, info};
-use notify::RecursiveMode;
-use notify_debouncer_mini::new_debouncer;
+use notify::{PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, new_debouncer_opt, DebounceEventResult, Debouncer};
 use serde::Deserialize;
 use tauri_bundler::{
   AppCategory, BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings,
-  UpdaterSettings, WindowsSettings,
+  RpmSettings, UpdaterSettings, WindowsSettings,
 };
-use tauri_utils::config::parse::is_configuration_file;
+use tauri_utils::config::{parse::is_configuration_file, WatcherConfig};
 
 use super::{AppSettings, DevProcess, ExitReason, Interface};
 use crate::helpers::{
   app_paths::{app_dir, tauri_dir},
-  config::{nsis_settings, reload as reload_config, wix_settings, Config},
+  config::{nsis_settings, reload as reload_config, wix_settings, Config, ConfigMetadata},
+  hot_restart::HotRestartServer,
 };
 use tauri_utils::display_path;
 
@@ -68,12 +69,23 @@ impl From<crate::build::Options> for Options {
 
 impl From<crate::dev::Options> for Options {
   fn from(options: crate::dev::Options) -> Self {
+    // the runner arguments come before the `--` separating the application arguments
+    let mut args = Vec::new();
+    if let Some(profile) = options.profile {
+      args.push("--profile".into());
+      args.push(profile);
+    }
+    if options.no_default_features {
+      args.push("--no-default-features".into());
+    }
+    args.extend(options.args);
+
     Self {
       runner: options.runner,
       debug: !options.release_mode,
       target: options.target,
       features: options.features,
-      args: options.args,
+      args,
       config: options.config,
       no_watch: options.no_watch,
     }
@@ -99,13 +111,15 @@ pub struct Rust {
   app_settings: RustAppSettings,
   config_features: Vec<String>,
   product_name: Option<String>,
+  external_bin: Vec<String>,
   available_targets: Option<Vec<Target>>,
+  watcher_config: WatcherConfig,
 }
 
 impl Interface for Rust {
   type AppSettings = RustAppSettings;
 
-  fn new(config: &Config, target: Option<String>) -> crate::Result<Self> {
+  fn new(config: &ConfigMetadata, target: Option<String>) -> crate::Result<Self> {
     let manifest = {
       let (tx, rx) = sync_channel(1);
       let mut watcher = new_debouncer(Duration::from_secs(1), None, move |r| {
@@ -141,7 +155,9 @@ impl Interface for Rust {
       app_settings,
       config_features: config.build.features.clone().unwrap_or_default(),
       product_name: config.package.product_name.clone(),
+      external_bin: config.tauri.bundle.external_bin.clone().unwrap_or_default(),
       available_targets: None,
+      watcher_config: config.build.watcher.clone(),
     })
   }
 
@@ -154,6 +170,7 @@ impl Interface for Rust {
       options,
       &self.app_settings,
       self.product_name.clone(),
+      &self.external_bin,
       &mut self.available_targets,
       self.config_features.clone(),
     )?;
@@ -193,7 +210,7 @@ impl Interface for Rust {
           on_exit(status, reason)
         })
       });
-      self.run_dev_watcher(config, run)
+      self.run_dev_watcher(config, run, HotRestartServer::start().ok())
     }
   }
 
@@ -217,7 +234,7 @@ impl Interface for Rust {
     } else {
       let config = options.config.clone();
       let run = Arc::new(|_rust: &mut Rust| runner(options.clone()));
-      self.run_dev_watcher(config, run)
+      self.run_dev_watcher(config, run, None)
     }
   }
 
@@ -273,7 +290,13 @@ struct IgnoreMatcher(Vec<Gitignore>);
 impl IgnoreMatcher {
   fn is_ignore(&self, path: &Path, is_dir: bool) -> bool {
     for gitignore in &self.0 {
-      if gitignore.matched(path, is_dir).is_ignore() {
+      // match the parent directories too, so ignoring a directory ignores the files it contains
+      let matched = if path.starts_with(gitignore.path()) {
+        gitignore.matched_path_or_any_parents(path, is_dir)
+      } else {
+        gitignore.matched(path, is_dir)
+      };
+      if matched.is_ignore() {
         return true;
       }
     }
@@ -281,6 +304,43 @@ impl IgnoreMatcher {
   }
 }
 
+/// The `tauri dev` file watcher, polling the file system when `build > watcher > pollInterval` is set.
+enum DevWatcher {
+  Recommended(Debouncer<RecommendedWatcher>),
+  Poll(Debouncer<PollWatcher>),
+}
+
+impl DevWatcher {
+  fn watcher(&mut self) -> &mut dyn Watcher {
+    match self {
+      Self::Recommended(debouncer) => debouncer.watcher(),
+      Self::Poll(debouncer) => debouncer.watcher(),
+    }
+  }
+}
+
+/// The paths of the workspace members, expanding their glob patterns.
+fn workspace_members(workspace_path: &Path, workspace: &WorkspaceSettings) -> Vec<PathBuf> {
+  let expand = |patterns: &Option<Vec<String>>| {
+    patterns
+      .iter()
+      .flatten()
+      .flat_map(|pattern| {
+        let pattern = workspace_path.join(pattern);
+        match glob::glob(&pattern.to_string_lossy()) {
+          Ok(paths) => paths.flatten().collect(),
+          Err(_) => vec![pattern],
+        }
+      })
+      .collect::<Vec<_>>()
+  };
+  let exclude = expand(&workspace.exclude);
+  expand(&workspace.members)
+    .into_iter()
+    .filter(|path| path.is_dir() && !exclude.contains(path))
+    .collect()
+}
+
 fn build_ignore_matcher(dir: &Path) -> IgnoreMatcher {
   let mut matchers = Vec::new();
 
@@ -448,6 +508,7 @@ impl Rust {
     &mut self,
     config: Option<String>,
     run: Arc<F>,
+    hot_restart: Option<HotRestartServer>,
   ) -> crate::Result<()> {
     let child = run(self)?;
 
@@ -457,39 +518,56 @@ impl Rust {
     let tauri_path = tauri_dir();
     let workspace_path = get_workspace_dir()?;
 
-    let watch_folders = if tauri_path == workspace_path {
-      vec![tauri_path]
+    let mut watch_folders = if tauri_path == workspace_path {
+      vec![tauri_path.clone()]
     } else {
       let cargo_settings = CargoSettings::load(&workspace_path)?;
       cargo_settings
         .workspace
         .as_ref()
-        .map(|w| {
-          w.members
-            .clone()
-            .unwrap_or_default()
-            .into_iter()
-            .map(|p| workspace_path.join(p))
-            .collect()
-        })
-        .unwrap_or_else(|| vec![tauri_path])
+        .map(|w| workspace_members(&workspace_path, w))
+        .unwrap_or_else(|| vec![tauri_path.clone()])
     };
+    if !watch_folders.contains(&tauri_path) {
+      watch_folders.push(tauri_path.clone());
+    }
+    for path in &self.watcher_config.paths {
+      watch_folders.push(tauri_path.join(path));
+    }
 
     let watch_folders = watch_folders.iter().map(Path::new).collect::<Vec<_>>();
     let common_ancestor = common_path::common_path_all(watch_folders.clone()).unwrap();
-    let ignore_matcher = build_ignore_matcher(&common_ancestor);
+    let mut ignore_matcher = build_ignore_matcher(&common_ancestor);
+    if !self.watcher_config.ignore.is_empty() {
+      let mut ignore_builder = GitignoreBuilder::new(&tauri_path);
+      for pattern in &self.watcher_config.ignore {
+        ignore_builder
+          .add_line(None, pattern)
+          .with_context(|| format!("invalid `build > watcher > ignore` pattern `{pattern}`"))?;
+      }
+      ignore_matcher.0.push(ignore_builder.build()?);
+    }
 
-    let mut watcher = new_debouncer(Duration::from_secs(1), None, move |r| {
+    let debounce = Duration::from_millis(self.watcher_config.debounce);
+    let handler = move |r: DebounceEventResult| {
       if let Ok(events) = r {
         tx.send(events).unwrap()
       }
-    })
-    .unwrap();
+    };
+    let mut watcher = match self.watcher_config.poll_interval {
+      Some(interval) => DevWatcher::Poll(new_debouncer_opt(
+        debounce,
+        None,
+        handler,
+        notify::Config::default().with_poll_interval(Duration::from_millis(interval)),
+      )?),
+      None => DevWatcher::Recommended(new_debouncer(debounce, None, handler)?),
+    };
     for path in watch_folders {
       if !ignore_matcher.is_ignore(path, true) {
         info!("Watching {} for changes...", display_path(path));
         lookup(path, |file_type, p| {
-          if p != path {
+          if p != path && !ignore_matcher.is_ignore(&p, file_type.is_dir()) {
             debug!("Watching {} for changes...", display_path(&p));
             let _ = watcher.watcher().watch(
               &p,
@@ -534,6 +612,9 @@ impl Rust {
               // which will trigger the watcher again
               // So the app should only be started when a file other than tauri.conf.json is changed
               let mut p = process.lock().unwrap();
+              if let Some(hot_restart) = &hot_restart {
+                hot_restart.save_state();
+              }
               p.kill().with_context(|| "failed to kill app process")?;
               // wait for the process to exit
               loop {
@@ -613,6 +694,8 @@ pub struct TomlWorkspaceField {
 struct WorkspaceSettings {
   /// the workspace members.
   members: Option<Vec<String>>,
+  /// the paths excluded from the workspace members.
+  exclude: Option<Vec<String>>,
   package: Option<WorkspacePackageSettings>,
 }
 
@@ -702,6 +785,8 @@ impl AppSettings for RustAppSettings {
       features,
       config.tauri.bundle.clone(),
       config.tauri.system_tray.clone(),
+      crate::helpers::config::flatpak_settings(config),
+      crate::helpers::config::snap_settings(config),
     )
   }
 
@@ -1031,13 +1116,28 @@ pub fn get_workspace_dir() -> crate::Result<PathBuf> {
   )
 }
 
-pub fn get_profile(options: &Options) -> String {
-  options
-    .args
+/// The `--profile` passed to the runner, either as `--profile <name>` or `--profile=<name>`.
+pub fn profile_arg(args: &[String]) -> Option<&str> {
+  args
     .iter()
-    .position(|a| a == "--profile")
-    .map(|i| options.args[i + 1].clone())
-    .unwrap_or_else(|| if options.debug { "debug" } else { "release" }.into())
+    .take_while(|a| *a != "--")
+    .enumerate()
+    .find_map(|(i, a)| match a.strip_prefix("--profile") {
+      Some("") => args.get(i + 1).map(|p| p.as_str()),
+      Some(p) => p.strip_prefix('='),
+      None => None,
+    })
+}
+
+/// The name of the target directory the app is built to.
+pub fn get_profile(options: &Options) -> String {
+  match profile_arg(&options.args) {
+    // the built-in profiles do not use their name as directory
+    Some("dev" | "test") => "debug".into(),
+    Some("bench") => "release".into(),
+    Some(profile) => profile.into(),
+    None => if options.debug { "debug" } else { "release" }.into(),
+  }
 }
 
 #[allow(unused_variables)]
@@ -1046,6 +1146,8 @@ fn tauri_config_to_bundle_settings(
   features: &[String],
   config: crate::helpers::config::BundleConfig,
   system_tray_config: Option<crate::helpers::config::SystemTrayConfig>,
+  flatpak_settings: tauri_bundler::FlatpakSettings,
+  snap_settings: tauri_bundler::SnapSettings,
 ) -> crate::Result<BundleSettings> {
   let enabled_features = manifest.all_enabled_features(features);
 
@@ -1065,6 +1167,8 @@ fn tauri_config_to_bundle_settings(
   let mut resources = config.resources.unwrap_or_default();
   #[allow(unused_mut)]
   let mut depends = config.deb.depends.unwrap_or_default();
+  #[allow(unused_mut)]
+  let mut rpm_depends = config.rpm.depends.unwrap_or_default();
 
   #[cfg(target_os = "linux")]
   {
@@ -1072,14 +1176,18 @@ fn tauri_config_to_bundle_settings(
       let tray = std::env::var("TAURI_TRAY").unwrap_or_else(|_| "ayatana".to_string());
       if tray == "ayatana" {
         depends.push("libayatana-appindicator3-1".into());
+        rpm_depends.push("libayatana-appindicator-gtk3".into());
       } else {
         depends.push("libappindicator3-1".into());
+        rpm_depends.push("libappindicator-gtk3".into());
       }
     }
 
     // provides `libwebkit2gtk-4.1.so.37` and all `4.0` versions have the -37 package name
     depends.push("libwebkit2gtk-4.1-0".to_string());
     depends.push("libgtk-3-0".to_string());
+    rpm_depends.push("webkit2gtk4.1".to_string());
+    rpm_depends.push("gtk3".to_string());
   }
 
   #[cfg(windows)]
@@ -1122,6 +1230,7 @@ fn tauri_config_to_bundle_settings(
     } else {
       Some(resources)
     },
+    compress_resources: config.compress_resources,
     copyright: config.copyright,
     category: match config.category {
       Some(category) => Some(AppCategory::from_str(&category).map_err(|e| match e {
@@ -1142,6 +1251,21 @@ fn tauri_config_to_bundle_settings(
       files: config.deb.files,
       desktop_template: config.deb.desktop_template,
     },
+    rpm: RpmSettings {
+      depends: if rpm_depends.is_empty() {
+        None
+      } else {
+        Some(rpm_depends)
+      },
+      release: config.rpm.release,
+      license: config.rpm.license,
+      files: config.rpm.files,
+      signing_key: std::env::var("TAURI_RPM_SIGNING_KEY")
+        .ok()
+        .or(config.rpm.signing_key),
+    },
+    flatpak: flatpak_settings,
+    snap: snap_settings,
     macos: MacOsSettings {
       frameworks: config.macos.frameworks,
       minimum_system_version: config.macos.minimum_system_version,
@@ -1158,6 +1282,7 @@ fn tauri_config_to_bundle_settings(
           None
         }
       },
+      local_network: config.local_network,
     },
     windows: WindowsSettings {
       timestamp_url: config.windows.timestamp_url,
@@ -1174,12 +1299,75 @@ fn tauri_config_to_bundle_settings(
       webview_install_mode: config.windows.webview_install_mode,
       webview_fixed_runtime_path: config.windows.webview_fixed_runtime_path,
       allow_downgrades: config.windows.allow_downgrades,
+      sign: config.windows.sign,
     },
     updater: Some(UpdaterSettings {
       active: config.updater.active,
       pubkey: config.updater.pubkey,
       msiexec_args: Some(config.updater.windows.install_mode.msiexec_args()),
     }),
+    localizations: config.localizations,
     ..Default::default()
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{Options, WorkspaceSettings};
+
+  #[test]
+  fn get_profile() {
+    let options = |debug: bool, args: &[&str]| Options {
+      debug,
+      args: args.iter().map(|a| a.to_string()).collect(),
+      ..Default::default()
+    };
+    assert_eq!(super::get_profile(&options(true, &[])), "debug");
+    assert_eq!(super::get_profile(&options(false, &[])), "release");
+    assert_eq!(
+      super::get_profile(&options(true, &["--profile", "perf"])),
+      "perf"
+    );
+    assert_eq!(
+      super::get_profile(&options(true, &["--profile=perf"])),
+      "perf"
+    );
+    assert_eq!(
+      super::get_profile(&options(false, &["--profile", "dev"])),
+      "debug"
+    );
+    assert_eq!(
+      super::get_profile(&options(true, &["--", "--profile", "perf"])),
+      "debug"
+    );
+  }
+
+  #[test]
+  fn workspace_members() {
+    let workspace = std::env::temp_dir().join(format!("tauri-workspace-{}", std::process::id()));
+    for member in ["src-tauri", "crates/a", "crates/b", "crates/excluded"] {
+      std::fs::create_dir_all(workspace.join(member)).unwrap();
+    }
+    std::fs::write(workspace.join("crates/README.md"), "").unwrap();
+
+    let mut members = super::workspace_members(
+      &workspace,
+      &WorkspaceSettings {
+        members: Some(vec!["src-tauri".into(), "crates/*".into()]),
+        exclude: Some(vec!["crates/excluded".into()]),
+        package: None,
+      },
+    );
+    members.sort();
+    assert_eq!(
+      members,
+      vec![
+        workspace.join("crates/a"),
+        workspace.join("crates/b"),
+        workspace.join("src-tauri")
+      ]
+    );
+
+    std::fs::remove_dir_all(workspace).unwrap();
+  }
+}
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::{get_profile, AppSettings, DevProcess, ExitReason, Options, RustAppSettings, Target};
+use super::{
+  get_profile, profile_arg, AppSettings, DevProcess, ExitReason, Options, RustAppSettings, Target,
+};
 use crate::CommandExt;
 use tauri_utils::display_path;
 
 use anyhow::Context;
 use heck::ToKebabCase;
+use log::{info, warn};
 use shared_child::SharedChild;
 use std::{
   fs::rename,
@@ -20,6 +23,9 @@ use std::{
   },
 };
 
+/// The targets merged into an `universal-apple-darwin` binary.
+const UNIVERSAL_MACOS_TARGETS: [&str; 2] = ["aarch64-apple-darwin", "x86_64-apple-darwin"];
+
 pub struct DevChild {
   manually_killed_app: Arc<AtomicBool>,
   build_child: Option<Arc<SharedChild>>,
@@ -135,6 +141,7 @@ pub fn build(
   options: Options,
   app_settings: &RustAppSettings,
   product_name: Option<String>,
+  external_bin: &[String],
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
 ) -> crate::Result<()> {
@@ -162,7 +169,7 @@ pub fn build(
       .arg("-create")
       .arg("-output")
       .arg(out_dir.join(bin_name));
-    for triple in ["aarch64-apple-darwin", "x86_64-apple-darwin"] {
+    for triple in UNIVERSAL_MACOS_TARGETS {
       let mut options = options.clone();
       options.target.replace(triple.into());
 
@@ -182,6 +189,8 @@ pub fn build(
         "Result of `lipo` command was unsuccessful: {lipo_status}. (Is `lipo` installed?)"
       )));
     }
+
+    lipo_sidecars(external_bin)?;
   } else {
     build_production_app(options, available_targets, config_features)
       .with_context(|| "failed to build app")?;
@@ -192,6 +201,41 @@ pub fn build(
   Ok(())
 }
 
+/// Creates the missing `universal-apple-darwin` sidecars by merging their `aarch64-apple-darwin` and `x86_64-apple-darwin` binaries.
+fn lipo_sidecars(external_bin: &[String]) -> crate::Result<()> {
+  for bin in external_bin {
+    let universal_path = PathBuf::from(format!("{bin}-universal-apple-darwin"));
+    if universal_path.exists() {
+      continue;
+    }
+
+    let arch_paths = UNIVERSAL_MACOS_TARGETS
+      .iter()
+      .map(|triple| PathBuf::from(format!("{bin}-{triple}")))
+      .collect::<Vec<_>>();
+    if !arch_paths.iter().all(|path| path.exists()) {
+      // let the bundler report the missing sidecar
+      continue;
+    }
+
+    info!(action = "Merging"; "{} sidecars", display_path(&universal_path));
+
+    let lipo_status = Command::new("lipo")
+      .arg("-create")
+      .arg("-output")
+      .arg(&universal_path)
+      .args(&arch_paths)
+      .output_ok()?
+      .status;
+    if !lipo_status.success() {
+      return Err(anyhow::anyhow!(format!(
+        "Result of `lipo` command was unsuccessful: {lipo_status}. (Is `lipo` installed?)"
+      )));
+    }
+  }
+  Ok(())
+}
+
 fn build_dev_app<F: FnOnce(ExitStatus, ExitReason) + Send + 'static>(
   options: Options,
   available_targets: &mut Option<Vec<Target>>,
@@ -314,6 +358,24 @@ fn build_command(
   available_targets: &mut Option<Vec<Target>>,
   config_features: Vec<String>,
 ) -> crate::Result<Command> {
+  // cargo-xwin provides the MSVC CRT and Windows SDK needed to build MSVC targets on other hosts
+  let use_xwin = options.runner.is_none()
+    && !cfg!(windows)
+    && options
+      .target
+      .as_ref()
+      .map_or(false, |t| t.ends_with("windows-msvc"));
+  let use_xwin = use_xwin
+    && {
+      let installed = Command::new("cargo")
+        .args(["xwin", "--version"])
+        .output()
+        .map_or(false, |o| o.status.success());
+      if !installed {
+        warn!("Building a Windows MSVC target on a non-Windows host requires `cargo-xwin`, install it with `cargo install --locked cargo-xwin` or set a custom `--runner`");
+      }
+      installed
+    };
   let runner = options.runner.unwrap_or_else(|| "cargo".into());
 
   if let Some(target) = &options.target {
@@ -337,7 +399,7 @@ fn build_command(
     args.push(features.join(","));
   }
 
-  if !options.debug && !args.contains(&"--profile".to_string()) {
+  if !options.debug && profile_arg(&args).is_none() {
     args.push("--release".into());
   }
 
@@ -347,6 +409,9 @@ fn build_command(
   }
 
   let mut build_cmd = Command::new(runner);
+  if use_xwin {
+    build_cmd.arg("xwin");
+  }
   build_cmd.arg("build");
   build_cmd.args(args);
 
@@ -4,7 +4,7 @@
 
 use crate::helpers::{
   app_paths::tauri_dir,
-  config::{Config, PatternKind},
+  config::{ConfigMetadata, PatternKind},
 };
 
 use anyhow::Context;
@@ -20,6 +20,9 @@ use std::{
   path::Path,
 };
 
+/// The Cargo features that enable the JSON5 and TOML config formats.
+const CONFIG_FORMAT_FEATURES: &[&str] = &["config-json5", "config-toml"];
+
 #[derive(Default)]
 pub struct Manifest {
   pub inner: Document,
@@ -265,31 +268,42 @@ fn inject_features(
   Ok(persist)
 }
 
-pub fn rewrite_manifest(config: &Config) -> crate::Result<Manifest> {
+pub fn rewrite_manifest(config: &ConfigMetadata) -> crate::Result<Manifest> {
   let manifest_path = tauri_dir().join("Cargo.toml");
   let mut manifest = read_manifest(&manifest_path)?;
 
   let mut dependencies = Vec::new();
+  let format_features = config.format_features();
 
   // tauri-build
-  let mut tauri_build_features = HashSet::new();
+  let mut tauri_build_features = HashSet::from_iter(format_features.iter().map(|f| f.to_string()));
   if let PatternKind::Isolation { .. } = config.tauri.pattern {
     tauri_build_features.insert("isolation".to_string());
   }
   dependencies.push(DependencyAllowlist {
     name: "tauri-build".into(),
     kind: DependencyKind::Build,
-    all_cli_managed_features: vec!["isolation"],
+    all_cli_managed_features: [&["isolation"], CONFIG_FORMAT_FEATURES].concat(),
     features: tauri_build_features,
   });
 
   // tauri
-  let tauri_features =
-    HashSet::from_iter(config.tauri.features().into_iter().map(|f| f.to_string()));
+  let tauri_features = HashSet::from_iter(
+    config
+      .tauri
+      .features()
+      .into_iter()
+      .chain(format_features)
+      .map(|f| f.to_string()),
+  );
   dependencies.push(DependencyAllowlist {
     name: "tauri".into(),
     kind: DependencyKind::Normal,
-    all_cli_managed_features: crate::helpers::config::TauriConfig::all_features(),
+    all_cli_managed_features: [
+      crate::helpers::config::TauriConfig::all_features().as_slice(),
+      CONFIG_FORMAT_FEATURES,
+    ]
+    .concat(),
     features: tauri_features,
   });
 
@@ -4,8 +4,10 @@
 
 pub use anyhow::Result;
 
+mod audit;
 mod build;
 mod completions;
+mod config;
 mod dev;
 mod helpers;
 mod icon;
@@ -15,6 +17,7 @@ mod interface;
 mod migrate;
 mod mobile;
 mod plugin;
+mod scopes;
 mod signer;
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
@@ -53,11 +56,23 @@ impl Display for RunMode {
   }
 }
 
+/// The output format of a command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+  /// Human readable logs.
+  #[default]
+  Text,
+  /// Machine readable JSON printed to stdout.
+  Json,
+}
+
 #[derive(Deserialize)]
 pub struct VersionMetadata {
   tauri: String,
   #[serde(rename = "tauri-build")]
   tauri_build: String,
+  #[serde(rename = "@tauri-apps/api")]
+  js_api: String,
 }
 
 #[derive(Deserialize)]
@@ -88,6 +103,7 @@ pub(crate) struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
+  Audit(audit::Options),
   Build(build::Options),
   Dev(dev::Options),
   Icon(icon::Options),
@@ -95,12 +111,13 @@ enum Commands {
   Init(init::Options),
   Plugin(plugin::Cli),
   Signer(signer::Cli),
+  Scopes(scopes::Cli),
+  Config(config::Cli),
   Completions(completions::Options),
   Android(mobile::android::Cli),
   #[cfg(target_os = "macos")]
   Ios(mobile::ios::Cli),
-  /// Migrate from v1 to v2
-  Migrate,
+  Migrate(migrate::Options),
 }
 
 fn format_error<I: CommandFactory>(err: clap::Error) -> clap::Error {
@@ -194,6 +211,7 @@ where
   }
 
   match cli.command {
+    Commands::Audit(options) => audit::command(options)?,
     Commands::Build(options) => build::command(options, cli.verbose)?,
     Commands::Dev(options) => dev::command(options)?,
     Commands::Icon(options) => icon::command(options)?,
@@ -201,11 +219,13 @@ where
     Commands::Init(options) => init::command(options)?,
     Commands::Plugin(cli) => plugin::command(cli)?,
     Commands::Signer(cli) => signer::command(cli)?,
+    Commands::Scopes(cli) => scopes::command(cli)?,
+    Commands::Config(cli) => config::command(cli)?,
     Commands::Completions(options) => completions::command(options, cli_)?,
     Commands::Android(c) => mobile::android::command(c, cli.verbose)?,
     #[cfg(target_os = "macos")]
     Commands::Ios(c) => mobile::ios::command(c, cli.verbose)?,
-    Commands::Migrate => migrate::command()?,
+    Commands::Migrate(options) => migrate::command(options)?,
   }
 
   Ok(())
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use super::Migration;
 use crate::Result;
 
 use serde_json::{Map, Value};
 
-use std::{fs::write, path::Path};
+use std::{fs::read_to_string, path::Path};
 
 macro_rules! move_allowlist_object {
   ($plugins: ident, $value: expr, $plugin: literal, $field: literal) => {{
@@ -21,25 +22,36 @@ macro_rules! move_allowlist_object {
   }};
 }
 
-pub fn migrate(tauri_dir: &Path) -> Result<()> {
+pub fn migrate(tauri_dir: &Path) -> Result<Option<Migration>> {
   if let Ok((mut config, config_path)) =
     tauri_utils_v1::config::parse::parse_value(tauri_dir.join("tauri.conf.json"))
   {
+    let original_config = config.clone();
     migrate_config(&mut config)?;
-    write(config_path, serde_json::to_string_pretty(&config)?)?;
+    if config != original_config {
+      let original = read_to_string(&config_path)?;
+      let mut migrated = serde_json::to_string_pretty(&config)?;
+      if original.ends_with('\n') {
+        migrated.push('\n');
+      }
+      return Ok(Some(Migration {
+        path: config_path,
+        original,
+        migrated,
+      }));
+    }
   }
 
-  Ok(())
+  Ok(None)
 }
 
-fn migrate_config(config: &mut Value) -> Result<()> {
+pub(crate) fn migrate_config(config: &mut Value) -> Result<()> {
   if let Some(config) = config.as_object_mut() {
     let mut plugins = config
-      .entry("plugins")
-      .or_insert_with(|| Value::Object(Default::default()))
-      .as_object_mut()
-      .unwrap()
-      .clone();
+      .get("plugins")
+      .and_then(|p| p.as_object())
+      .cloned()
+      .unwrap_or_default();
 
     if let Some(tauri_config) = config.get_mut("tauri").and_then(|c| c.as_object_mut()) {
       // allowlist
@@ -58,7 +70,9 @@ fn migrate_config(config: &mut Value) -> Result<()> {
       }
     }
 
-    config.insert("plugins".into(), plugins.into());
+    if !plugins.is_empty() || config.contains_key("plugins") {
+      config.insert("plugins".into(), plugins.into());
+    }
   }
 
   Ok(())
@@ -0,0 +1,144 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::Path;
+
+/// The number of unchanged lines shown around the changes.
+const CONTEXT: usize = 3;
+
+enum Line<'a> {
+  Unchanged(&'a str),
+  Removed(&'a str),
+  Added(&'a str),
+}
+
+/// Diffs the lines using their longest common subsequence.
+fn diff_lines<'a>(original: &[&'a str], migrated: &[&'a str]) -> Vec<Line<'a>> {
+  // lcs[i][j] is the length of the longest common subsequence of original[i..] and migrated[j..]
+  let mut lcs = vec![vec![0usize; migrated.len() + 1]; original.len() + 1];
+  for i in (0..original.len()).rev() {
+    for j in (0..migrated.len()).rev() {
+      lcs[i][j] = if original[i] == migrated[j] {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
+    }
+  }
+
+  let (mut i, mut j) = (0, 0);
+  let mut lines = Vec::new();
+  while i < original.len() || j < migrated.len() {
+    if i < original.len() && j < migrated.len() && original[i] == migrated[j] {
+      lines.push(Line::Unchanged(original[i]));
+      i += 1;
+      j += 1;
+    } else if i < original.len() && (j == migrated.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+      lines.push(Line::Removed(original[i]));
+      i += 1;
+    } else {
+      lines.push(Line::Added(migrated[j]));
+      j += 1;
+    }
+  }
+  lines
+}
+
+/// Formats the changes of a file in the unified diff format.
+pub fn unified_diff(path: &Path, original: &str, migrated: &str) -> String {
+  let lines = diff_lines(
+    &original.lines().collect::<Vec<_>>(),
+    &migrated.lines().collect::<Vec<_>>(),
+  );
+
+  // the changes with their context, merged when they overlap
+  let mut hunks: Vec<(usize, usize)> = Vec::new();
+  for (i, _) in lines
+    .iter()
+    .enumerate()
+    .filter(|(_, line)| !matches!(line, Line::Unchanged(_)))
+  {
+    let start = i.saturating_sub(CONTEXT);
+    let end = (i + CONTEXT + 1).min(lines.len());
+    match hunks.last_mut() {
+      Some(hunk) if start <= hunk.1 => hunk.1 = end,
+      _ => hunks.push((start, end)),
+    }
+  }
+
+  // the original and migrated line numbers each line starts at
+  let mut positions = Vec::with_capacity(lines.len());
+  let (mut original_line, mut migrated_line) = (0, 0);
+  for line in &lines {
+    positions.push((original_line, migrated_line));
+    match line {
+      Line::Unchanged(_) => {
+        original_line += 1;
+        migrated_line += 1;
+      }
+      Line::Removed(_) => original_line += 1,
+      Line::Added(_) => migrated_line += 1,
+    }
+  }
+
+  let mut diff = format!("--- {0}\n+++ {0}\n", path.display());
+  for (start, end) in hunks {
+    let hunk = &lines[start..end];
+    let removed = hunk
+      .iter()
+      .filter(|line| !matches!(line, Line::Added(_)))
+      .count();
+    let added = hunk
+      .iter()
+      .filter(|line| !matches!(line, Line::Removed(_)))
+      .count();
+    // empty ranges start at the line before the hunk
+    let (original_start, migrated_start) = positions[start];
+    diff.push_str(&format!(
+      "@@ -{},{removed} +{},{added} @@\n",
+      original_start + usize::from(removed > 0),
+      migrated_start + usize::from(added > 0),
+    ));
+    for line in hunk {
+      let (prefix, line) = match line {
+        Line::Unchanged(line) => (' ', line),
+        Line::Removed(line) => ('-', line),
+        Line::Added(line) => ('+', line),
+      };
+      diff.push(prefix);
+      diff.push_str(line);
+      diff.push('\n');
+    }
+  }
+  diff
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  #[test]
+  fn unified_diff() {
+    let original = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    let migrated = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+    assert_eq!(
+      super::unified_diff(Path::new("file.txt"), original, migrated),
+      "--- file.txt
++++ file.txt
+@@ -1,5 +1,5 @@
+ a
+-b
++B
+ c
+ d
+ e
+@@ -8,3 +8,4 @@
+ h
+ i
+ j
++k
+"
+    );
+  }
+}
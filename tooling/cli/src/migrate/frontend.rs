@@ -0,0 +1,74 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::Migration;
+use crate::{Result, VersionMetadata};
+
+use regex::Regex;
+
+use std::{fs::read_to_string, path::Path};
+
+/// Updates the versions of the Tauri packages in the `package.json` file.
+pub fn migrate(app_dir: &Path) -> Result<Option<Migration>> {
+  let path = app_dir.join("package.json");
+  if !path.exists() {
+    return Ok(None);
+  }
+
+  let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../../metadata.json"))?;
+  let original = read_to_string(&path)?;
+  let migrated = migrate_package_json(&original, &metadata.js_api, env!("CARGO_PKG_VERSION"));
+
+  Ok((migrated != original).then_some(Migration {
+    path,
+    original,
+    migrated,
+  }))
+}
+
+/// Rewrites the dependency versions in place, keeping the formatting of the file.
+fn migrate_package_json(package_json: &str, api_version: &str, cli_version: &str) -> String {
+  let mut package_json = package_json.to_string();
+  for (package, version) in [
+    ("@tauri-apps/api", api_version),
+    ("@tauri-apps/cli", cli_version),
+  ] {
+    let dependency =
+      Regex::new(&format!(r#"("{}"\s*:\s*")[^"]*""#, regex::escape(package))).unwrap();
+    package_json = dependency
+      .replace_all(&package_json, format!(r#"${{1}}^{version}""#))
+      .into_owned();
+  }
+  package_json
+}
+
+#[cfg(test)]
+mod tests {
+  #[test]
+  fn migrate_package_json() {
+    let package_json = r#"{
+  "scripts": { "tauri": "tauri" },
+  "dependencies": {
+    "@tauri-apps/api": "^1.4.0"
+  },
+  "devDependencies": {
+    "@tauri-apps/cli" : "1.4.0"
+  }
+}
+"#;
+    assert_eq!(
+      super::migrate_package_json(package_json, "2.0.0-alpha.4", "2.0.0-alpha.9"),
+      r#"{
+  "scripts": { "tauri": "tauri" },
+  "dependencies": {
+    "@tauri-apps/api": "^2.0.0-alpha.4"
+  },
+  "devDependencies": {
+    "@tauri-apps/cli" : "^2.0.0-alpha.9"
+  }
+}
+"#
+    );
+  }
+}
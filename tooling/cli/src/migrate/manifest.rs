@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use super::Migration;
 use crate::{interface::rust::manifest::read_manifest, Result};
 
 use anyhow::Context;
@@ -9,31 +10,30 @@ use itertools::Itertools;
 use tauri_utils_v1::config::Allowlist;
 use toml_edit::{Document, Entry, Item, Table, TableLike, Value};
 
-use std::{fs::File, io::Write, path::Path};
+use std::{fs::read_to_string, path::Path};
 
 const CRATE_TYPES: &[&str] = &["staticlib", "cdylib", "rlib"];
 
-pub fn migrate(tauri_dir: &Path) -> Result<()> {
+pub fn migrate(tauri_dir: &Path) -> Result<Option<Migration>> {
   let manifest_path = tauri_dir.join("Cargo.toml");
+  let original = read_to_string(&manifest_path).with_context(|| "failed to read Cargo.toml")?;
   let mut manifest = read_manifest(&manifest_path)?;
   migrate_manifest(&mut manifest)?;
 
-  let mut manifest_file =
-    File::create(&manifest_path).with_context(|| "failed to open Cargo.toml for rewrite")?;
-  manifest_file.write_all(
-    manifest
-      .to_string()
-      // apply some formatting fixes
-      .replace(r#"" ,features =["#, r#"", features = ["#)
-      .replace(r#"" , features"#, r#"", features"#)
-      .replace("]}", "] }")
-      .replace("={", "= {")
-      .replace("=[", "= [")
-      .as_bytes(),
-  )?;
-  manifest_file.flush()?;
-
-  Ok(())
+  let migrated = manifest
+    .to_string()
+    // apply some formatting fixes
+    .replace(r#"" ,features =["#, r#"", features = ["#)
+    .replace(r#"" , features"#, r#"", features"#)
+    .replace("]}", "] }")
+    .replace("={", "= {")
+    .replace("=[", "= [");
+
+  Ok((migrated != original).then_some(Migration {
+    path: manifest_path,
+    original,
+    migrated,
+  }))
 }
 
 fn migrate_manifest(manifest: &mut Document) -> Result<()> {
@@ -45,7 +45,22 @@ fn migrate_manifest(manifest: &mut Document) -> Result<()> {
     .expect("manifest dependencies isn't a table");
 
   let version = dependency_version();
-  migrate_dependency(dependencies, "tauri", version, &features_to_remove());
+  migrate_dependency(
+    dependencies,
+    "tauri",
+    version.clone(),
+    &features_to_remove(),
+  );
+
+  if let Some(build_dependencies) = manifest
+    .as_table_mut()
+    .get_mut("build-dependencies")
+    .and_then(|d| d.as_table_mut())
+  {
+    if build_dependencies.contains_key("tauri-build") {
+      migrate_dependency(build_dependencies, "tauri-build", version, &[]);
+    }
+  }
 
   let lib = manifest
     .as_table_mut()
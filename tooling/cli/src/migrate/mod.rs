@@ -2,16 +2,80 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{helpers::app_paths::tauri_dir, Result};
+use crate::{
+  helpers::app_paths::{app_dir, tauri_dir},
+  Result,
+};
 
-mod config;
+use clap::Parser;
+use log::{info, warn};
+
+use std::{fs::write, path::PathBuf};
+
+pub(crate) mod config;
+mod diff;
+mod frontend;
 mod manifest;
+mod usages;
 
-pub fn command() -> Result<()> {
+#[derive(Debug, Parser)]
+#[clap(about = "Migrate from v1 to v2")]
+pub struct Options {
+  /// Print the report without writing the migrated files
+  #[clap(long)]
+  dry_run: bool,
+}
+
+/// A file rewritten by the migration.
+pub struct Migration {
+  path: PathBuf,
+  original: String,
+  migrated: String,
+}
+
+/// Migrates the configuration, the Cargo manifest and the `package.json` file,
+/// printing their diff and the API usages that must be migrated manually.
+pub fn command(options: Options) -> Result<()> {
   let tauri_dir = tauri_dir();
+  let app_dir = app_dir();
+
+  let mut migrations = Vec::new();
+  migrations.extend(config::migrate(&tauri_dir)?);
+  migrations.extend(manifest::migrate(&tauri_dir)?);
+  migrations.extend(frontend::migrate(app_dir)?);
+
+  for migration in &migrations {
+    print!(
+      "{}",
+      diff::unified_diff(&migration.path, &migration.original, &migration.migrated)
+    );
+    if !options.dry_run {
+      write(&migration.path, &migration.migrated)?;
+    }
+  }
+
+  let usages = usages::find(&tauri_dir, app_dir);
+  for usage in &usages {
+    warn!(
+      "{}:{}: {}\n    {}",
+      usage.path.display(),
+      usage.line,
+      usage.message,
+      usage.code
+    );
+  }
 
-  config::migrate(&tauri_dir)?;
-  manifest::migrate(&tauri_dir)?;
+  match (migrations.len(), options.dry_run) {
+    (0, _) => info!("The project files are up to date"),
+    (count, true) => info!("{count} file(s) would be migrated"),
+    (count, false) => info!("Migrated {count} file(s)"),
+  }
+  if !usages.is_empty() {
+    warn!(
+      "Found {} usage(s) of APIs that changed in v2, they must be migrated manually",
+      usages.len()
+    );
+  }
 
   Ok(())
 }
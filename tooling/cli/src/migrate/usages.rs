@@ -0,0 +1,215 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use ignore::WalkBuilder;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use std::{
+  fs::read_to_string,
+  path::{Path, PathBuf},
+};
+
+/// The Rust APIs removed in v2, with how to migrate them.
+///
+/// Only the first matching pattern of a line is reported, so the specific patterns come first.
+const RUST_APIS: &[(&str, &str)] = &[
+  (
+    "api::process::Command",
+    "`tauri::api::process::Command` moved to the `tauri-plugin-shell` crate",
+  ),
+  (
+    "api::process",
+    "`tauri::api::process` moved to `tauri::process` and the `tauri-plugin-process` crate",
+  ),
+  (
+    "api::dialog",
+    "`tauri::api::dialog` moved to the `tauri-plugin-dialog` crate",
+  ),
+  (
+    "api::shell",
+    "`tauri::api::shell` moved to the `tauri-plugin-shell` crate",
+  ),
+  (
+    "api::http",
+    "`tauri::api::http` moved to the `tauri-plugin-http` crate",
+  ),
+  (
+    "api::notification",
+    "`tauri::api::notification` moved to the `tauri-plugin-notification` crate",
+  ),
+  (
+    "api::cli",
+    "`tauri::api::cli` moved to the `tauri-plugin-cli` crate",
+  ),
+  (
+    "get_cli_matches",
+    "`get_cli_matches` moved to the `tauri-plugin-cli` crate",
+  ),
+  (
+    "api::clipboard",
+    "`tauri::api::clipboard` moved to the `tauri-plugin-clipboard-manager` crate",
+  ),
+  (
+    "ClipboardManager",
+    "`ClipboardManager` moved to the `tauri-plugin-clipboard-manager` crate",
+  ),
+  (
+    "clipboard_manager()",
+    "`clipboard_manager()` moved to the `tauri-plugin-clipboard-manager` crate",
+  ),
+  (
+    "GlobalShortcutManager",
+    "`GlobalShortcutManager` moved to the `tauri-plugin-global-shortcut` crate",
+  ),
+  (
+    "global_shortcut_manager()",
+    "`global_shortcut_manager()` moved to the `tauri-plugin-global-shortcut` crate",
+  ),
+  (
+    "api::path",
+    "`tauri::api::path` was replaced by the path resolver, e.g. `app.path().app_data_dir()`",
+  ),
+  (
+    "path_resolver()",
+    "`path_resolver()` was replaced by `path()`",
+  ),
+  (
+    "tauri::updater",
+    "`tauri::updater` moved to the `tauri-plugin-updater` crate",
+  ),
+  (
+    "fs_scope()",
+    "`fs_scope()` moved to the `tauri-plugin-fs` crate",
+  ),
+  (
+    "shell_scope()",
+    "`shell_scope()` moved to the `tauri-plugin-shell` crate",
+  ),
+];
+
+/// The `@tauri-apps/api` modules moved to plugins in v2, with their plugin name.
+const JS_MODULES: &[(&str, &str)] = &[
+  ("app", "app"),
+  ("cli", "cli"),
+  ("clipboard", "clipboard-manager"),
+  ("dialog", "dialog"),
+  ("fs", "fs"),
+  ("globalShortcut", "global-shortcut"),
+  ("http", "http"),
+  ("notification", "notification"),
+  ("os", "os"),
+  ("process", "process"),
+  ("shell", "shell"),
+  ("updater", "updater"),
+  ("window", "window"),
+];
+
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx", "mts", "vue", "svelte"];
+
+/// A usage of an API that can't be migrated automatically.
+pub struct Usage {
+  pub path: PathBuf,
+  /// The 1-based line number.
+  pub line: usize,
+  pub code: String,
+  pub message: String,
+}
+
+/// Finds the usages of the removed Rust APIs in the Tauri directory
+/// and of the `@tauri-apps/api` modules moved to plugins in the frontend.
+pub fn find(tauri_dir: &Path, app_dir: &Path) -> Vec<Usage> {
+  let mut usages = Vec::new();
+  for path in source_files(tauri_dir, &["rs"]) {
+    usages.extend(find_in_file(&path, rust_api_usage));
+  }
+  for path in source_files(app_dir, JS_EXTENSIONS) {
+    usages.extend(find_in_file(&path, js_api_usage));
+  }
+  usages
+}
+
+/// The source files of the directory, skipping the ignored and build folders.
+fn source_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+  let mut files = WalkBuilder::new(dir)
+    .require_git(false)
+    .filter_entry(|entry| {
+      !matches!(
+        entry.file_name().to_str(),
+        Some("node_modules" | "target" | "gen")
+      )
+    })
+    .build()
+    .flatten()
+    .map(|entry| entry.into_path())
+    .filter(|path| {
+      path.is_file()
+        && path
+          .extension()
+          .and_then(|e| e.to_str())
+          .map_or(false, |e| extensions.contains(&e))
+    })
+    .collect::<Vec<_>>();
+  files.sort();
+  files
+}
+
+fn find_in_file(path: &Path, usage: fn(&str) -> Option<String>) -> Vec<Usage> {
+  let Ok(source) = read_to_string(path) else {
+    return Vec::new();
+  };
+  source
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim_start().starts_with("//"))
+    .filter_map(|(i, line)| {
+      usage(line).map(|message| Usage {
+        path: path.to_path_buf(),
+        line: i + 1,
+        code: line.trim().to_string(),
+        message,
+      })
+    })
+    .collect()
+}
+
+fn rust_api_usage(line: &str) -> Option<String> {
+  RUST_APIS
+    .iter()
+    .find(|(pattern, _)| line.contains(pattern))
+    .map(|(_, message)| message.to_string())
+}
+
+fn js_api_usage(line: &str) -> Option<String> {
+  static IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"@tauri-apps/api/(\w+)['"]"#).unwrap());
+  let module = IMPORT.captures(line)?.get(1)?.as_str();
+  JS_MODULES
+    .iter()
+    .find(|(m, _)| *m == module)
+    .map(|(module, plugin)| {
+      format!("`@tauri-apps/api/{module}` moved to the `@tauri-apps/plugin-{plugin}` package")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  #[test]
+  fn api_usage() {
+    assert_eq!(
+      super::rust_api_usage("use tauri::api::process::Command;").as_deref(),
+      Some("`tauri::api::process::Command` moved to the `tauri-plugin-shell` crate")
+    );
+    assert_eq!(
+      super::rust_api_usage("let dir = app.path_resolver().app_data_dir();").as_deref(),
+      Some("`path_resolver()` was replaced by `path()`")
+    );
+    assert!(super::rust_api_usage("use tauri::Manager;").is_none());
+
+    assert_eq!(
+      super::js_api_usage("import { readTextFile } from '@tauri-apps/api/fs'").as_deref(),
+      Some("`@tauri-apps/api/fs` moved to the `@tauri-apps/plugin-fs` package")
+    );
+    assert!(super::js_api_usage("import { invoke } from '@tauri-apps/api/tauri'").is_none());
+  }
+}
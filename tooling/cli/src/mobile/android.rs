@@ -34,6 +34,7 @@ use super::{
 };
 use crate::{helpers::config::Config as TauriConfig, Result};
 
+mod add_module;
 mod android_studio_script;
 mod build;
 mod dev;
@@ -73,6 +74,7 @@ enum Commands {
   Build(build::Options),
   #[clap(hide(true))]
   AndroidStudioScript(android_studio_script::Options),
+  AddModule(add_module::Options),
 }
 
 pub fn command(cli: Cli, verbosity: u8) -> Result<()> {
@@ -88,6 +90,7 @@ pub fn command(cli: Cli, verbosity: u8) -> Result<()> {
     Commands::Dev(options) => dev::command(options, noise_level)?,
     Commands::Build(options) => build::command(options, noise_level)?,
     Commands::AndroidStudioScript(options) => android_studio_script::command(options)?,
+    Commands::AddModule(options) => add_module::command(options)?,
   }
 
   Ok(())
@@ -0,0 +1,128 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{ensure_init, get_app, get_config, MobileTarget};
+use crate::{
+  helpers::{config::get as get_tauri_config, template},
+  mobile::init::handlebars,
+  Result,
+};
+
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use include_dir::{include_dir, Dir};
+
+use std::{
+  fs::{create_dir_all, read_to_string, write, File},
+  path::Path,
+};
+
+const WIDGET_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/mobile/android-widget");
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Module {
+  /// A Glance app widget displaying the data written with `tauri::widgets`
+  Widget,
+}
+
+impl Module {
+  fn name(&self) -> &'static str {
+    match self {
+      Self::Widget => "widget",
+    }
+  }
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Adds a module to the Android Studio project")]
+pub struct Options {
+  /// The kind of module
+  #[clap(value_enum)]
+  module: Module,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let tauri_config = get_tauri_config(None)?;
+
+  let (config, _metadata) = {
+    let tauri_config_guard = tauri_config.lock().unwrap();
+    let tauri_config_ = tauri_config_guard.as_ref().unwrap();
+    get_config(&get_app(tauri_config_), tauri_config_, &Default::default())
+  };
+  ensure_init(config.project_dir(), MobileTarget::Android)?;
+
+  let project_dir = config.project_dir();
+  let name = options.module.name();
+  let dest = project_dir.join(name);
+  if dest.exists() {
+    anyhow::bail!("{name} module already exists at {}", dest.display());
+  }
+
+  let (handlebars, mut map) = handlebars(config.app());
+  map.insert("android", &config);
+
+  let template_dir = match options.module {
+    Module::Widget => &WIDGET_TEMPLATE_DIR,
+  };
+  // the Kotlin sources are in the package of the module
+  let package_path = format!(
+    "java/{}/{}/{name}",
+    config.app().reverse_domain().replace('.', "/"),
+    config.app().name_snake()
+  );
+  let mut created_dirs = Vec::new();
+  template::render_with_generator(&handlebars, map.inner(), template_dir, &dest, &mut |path| {
+    let path = match (
+      path.extension().and_then(|e| e.to_str()),
+      path.strip_prefix("src/main"),
+    ) {
+      (Some("kt"), Ok(file)) => dest.join("src/main").join(&package_path).join(file),
+      _ => dest.join(path),
+    };
+    let parent = path.parent().unwrap().to_path_buf();
+    if !created_dirs.contains(&parent) {
+      create_dir_all(&parent)?;
+      created_dirs.push(parent);
+    }
+    File::create(path).map(Some)
+  })
+  .with_context(|| "failed to process template")?;
+
+  // `tauri-build` includes the module on each build, but Android Studio syncs the project before building it
+  include_module(&project_dir, name)?;
+
+  log::info!(action = "Created"; "{name} module at {}", dest.display());
+  Ok(())
+}
+
+/// Includes the module in the Gradle settings, and adds it to the dependencies of the app.
+fn include_module(project_dir: &Path, name: &str) -> Result<()> {
+  let settings_path = project_dir.join("tauri.settings.gradle");
+  let mut settings = read_to_string(&settings_path).unwrap_or_default();
+  let include = format!("include ':{name}'");
+  if !settings.contains(&include) {
+    if !settings.is_empty() && !settings.ends_with('\n') {
+      settings.push('\n');
+    }
+    settings.push_str(&include);
+    settings.push('\n');
+    write(&settings_path, settings)
+      .with_context(|| format!("failed to write {}", settings_path.display()))?;
+  }
+
+  let build_path = project_dir.join("app").join("tauri.build.gradle.kts");
+  let mut build = read_to_string(&build_path)
+    .with_context(|| format!("failed to read {}", build_path.display()))?;
+  let dependency = format!(r#"implementation(project(":{name}"))"#);
+  if !build.contains(&dependency) {
+    let end = build
+      .rfind('}')
+      .with_context(|| format!("{} has no dependencies block", build_path.display()))?;
+    build.insert_str(end, &format!("  {dependency}\n"));
+    write(&build_path, build)
+      .with_context(|| format!("failed to write {}", build_path.display()))?;
+  }
+
+  Ok(())
+}
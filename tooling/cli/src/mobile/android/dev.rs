@@ -92,6 +92,9 @@ impl From<Options> for DevOptions {
       port: options.port,
       force_ip_prompt: options.force_ip_prompt,
       release_mode: options.release_mode,
+      profile: None,
+      no_default_features: false,
+      package: None,
     }
   }
 }
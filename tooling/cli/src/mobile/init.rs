@@ -18,6 +18,7 @@ use tauri_mobile::{
     cli::{Report, TextWrapper},
   },
 };
+use tauri_utils::config::{Color, SystemBarStyle};
 
 use std::{
   env::{current_dir, var, var_os},
@@ -161,6 +162,34 @@ pub fn exec(
         let (config, metadata) =
           super::android::get_config(&app, tauri_config_, &Default::default());
         map.insert("android", &config);
+        map.insert("android-push", tauri_config_.tauri.bundle.push.enable);
+        map.insert(
+          "android-in-app-purchases",
+          tauri_config_.tauri.bundle.in_app_purchases.enable,
+        );
+        map.insert(
+          "android-background-tasks",
+          !tauri_config_
+            .tauri
+            .bundle
+            .background_tasks
+            .identifiers
+            .is_empty(),
+        );
+        let system_bars = &tauri_config_.tauri.bundle.system_bars;
+        if let Some(color) = &system_bars.status_bar_color {
+          map.insert("android-status-bar-color", android_color(color));
+        }
+        if let Some(light) = android_light_system_bar(system_bars.status_bar_style) {
+          map.insert("android-light-status-bar", light);
+        }
+        if let Some(color) = &system_bars.navigation_bar_color {
+          map.insert("android-navigation-bar-color", android_color(color));
+        }
+        if let Some(light) = android_light_system_bar(system_bars.navigation_bar_style) {
+          map.insert("android-light-navigation-bar", light);
+        }
+        map.insert("android-edge-to-edge", system_bars.edge_to_edge);
         super::android::project::gen(
           &config,
           &metadata,
@@ -188,6 +217,79 @@ pub fn exec(
     Target::Ios => {
       let (config, metadata) = super::ios::get_config(&app, tauri_config_, &Default::default());
       map.insert("apple", &config);
+      // JSON strings are valid YAML scalars, escaping the user text in the `Info.plist` properties
+      let local_network = &tauri_config_.tauri.bundle.local_network;
+      if let Some(usage_description) = &local_network.usage_description {
+        map.insert(
+          "ios-local-network-usage-description",
+          serde_json::to_string(usage_description)?,
+        );
+      }
+      if !local_network.bonjour_services.is_empty() {
+        map.insert(
+          "ios-bonjour-services",
+          serde_json::to_string(&local_network.bonjour_services)?,
+        );
+      }
+      let push = &tauri_config_.tauri.bundle.push;
+      let mut background_modes = Vec::new();
+      if push.enable {
+        map.insert("ios-aps-environment", push.aps_environment.as_str());
+        background_modes.push("remote-notification");
+      }
+      let background_tasks = &tauri_config_.tauri.bundle.background_tasks;
+      if !background_tasks.identifiers.is_empty() {
+        map.insert(
+          "ios-background-task-identifiers",
+          serde_json::to_string(&background_tasks.identifiers)?,
+        );
+        background_modes.extend(["fetch", "processing"]);
+      }
+      if !background_modes.is_empty() {
+        map.insert(
+          "ios-background-modes",
+          serde_json::to_string(&background_modes)?,
+        );
+      }
+      map.insert(
+        "ios-in-app-purchases",
+        tauri_config_.tauri.bundle.in_app_purchases.enable,
+      );
+      let shortcuts = &tauri_config_.tauri.bundle.shortcuts;
+      if !shortcuts.is_empty() {
+        let items = shortcuts
+          .iter()
+          .map(|shortcut| {
+            let mut item = serde_json::json!({
+              "UIApplicationShortcutItemType": shortcut.id,
+              "UIApplicationShortcutItemTitle": shortcut.title,
+            });
+            if let Some(description) = &shortcut.description {
+              item["UIApplicationShortcutItemSubtitle"] = description.as_str().into();
+            }
+            if let Some(icon) = &shortcut.icon {
+              item["UIApplicationShortcutItemIconSymbolName"] = icon.as_str().into();
+            }
+            item
+          })
+          .collect::<Vec<_>>();
+        map.insert("ios-shortcut-items", serde_json::to_string(&items)?);
+      }
+      if super::ios::widget_extension_dir(&config).is_dir() {
+        map.insert("ios-widget", true);
+        map.insert(
+          "ios-app-group",
+          super::ios::widgets_app_group(&tauri_config_.tauri.bundle.identifier),
+        );
+      }
+      map.insert(
+        "ios-status-bar-style",
+        match tauri_config_.tauri.bundle.system_bars.status_bar_style {
+          SystemBarStyle::Default => "UIStatusBarStyleDefault",
+          SystemBarStyle::LightContent => "UIStatusBarStyleLightContent",
+          SystemBarStyle::DarkContent => "UIStatusBarStyleDarkContent",
+        },
+      );
       super::ios::project::gen(
         &config,
         &metadata,
@@ -209,7 +311,7 @@ pub fn exec(
   Ok(app)
 }
 
-fn handlebars(app: &App) -> (Handlebars<'static>, JsonMap) {
+pub(super) fn handlebars(app: &App) -> (Handlebars<'static>, JsonMap) {
   let mut h = Handlebars::new();
   h.register_escape_fn(handlebars::no_escape);
 
@@ -391,6 +493,22 @@ fn prefix_path(
     .map_err(Into::into)
 }
 
+/// The `#AARRGGBB` Android color resource of the color.
+fn android_color(color: &Color) -> String {
+  let Color(r, g, b, a) = color;
+  format!("#{a:02X}{r:02X}{g:02X}{b:02X}")
+}
+
+/// The value of the `windowLightStatusBar` and `windowLightNavigationBar` theme attributes,
+/// or `None` to keep the default of the theme.
+fn android_light_system_bar(style: SystemBarStyle) -> Option<&'static str> {
+  match style {
+    SystemBarStyle::Default => None,
+    SystemBarStyle::LightContent => Some("false"),
+    SystemBarStyle::DarkContent => Some("true"),
+  }
+}
+
 fn unprefix_path(
   helper: &Helper,
   _: &Handlebars,
@@ -30,8 +30,9 @@ use super::{
 };
 use crate::{helpers::config::Config as TauriConfig, Result};
 
-use std::{process::exit, thread::sleep, time::Duration};
+use std::{path::PathBuf, process::exit, thread::sleep, time::Duration};
 
+mod add_extension;
 mod build;
 mod dev;
 mod open;
@@ -77,6 +78,7 @@ enum Commands {
   Build(build::Options),
   #[clap(hide(true))]
   XcodeScript(xcode_script::Options),
+  AddExtension(add_extension::Options),
 }
 
 pub fn command(cli: Cli, verbosity: u8) -> Result<()> {
@@ -92,6 +94,7 @@ pub fn command(cli: Cli, verbosity: u8) -> Result<()> {
     Commands::Dev(options) => dev::command(options, noise_level)?,
     Commands::Build(options) => build::command(options, noise_level)?,
     Commands::XcodeScript(options) => xcode_script::command(options)?,
+    Commands::AddExtension(options) => add_extension::command(options)?,
   }
 
   Ok(())
@@ -143,6 +146,18 @@ pub fn get_config(
   (config, metadata)
 }
 
+/// The directory of the widget extension generated with `tauri ios add-extension widget`.
+pub fn widget_extension_dir(config: &AppleConfig) -> PathBuf {
+  config
+    .project_dir()
+    .join(format!("{}_Widget", config.app().name()))
+}
+
+/// The App Group shared by the app and its widget extension, where `tauri::widgets` stores the data.
+pub fn widgets_app_group(identifier: &str) -> String {
+  format!("group.{identifier}")
+}
+
 fn ios_deploy_device_prompt<'a>(env: &'_ Env, target: Option<&str>) -> Result<Device<'a>> {
   let device_list = ios_deploy::device_list(env)
     .map_err(|cause| anyhow::anyhow!("Failed to detect connected iOS devices: {cause}"))?;
@@ -0,0 +1,75 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{
+  ensure_init, get_app, get_config, init_command, widget_extension_dir, widgets_app_group,
+  MobileTarget,
+};
+use crate::{
+  helpers::{config::get as get_tauri_config, template},
+  mobile::init::handlebars,
+  Result,
+};
+
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use include_dir::{include_dir, Dir};
+
+use std::fs::remove_file;
+
+const WIDGET_TEMPLATE_DIR: Dir<'_> = include_dir!("templates/mobile/ios-widget");
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Extension {
+  /// A WidgetKit extension displaying the data written with `tauri::widgets`
+  Widget,
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Adds an app extension to the Xcode project")]
+pub struct Options {
+  /// The kind of extension
+  #[clap(value_enum)]
+  extension: Extension,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let tauri_config = get_tauri_config(None)?;
+
+  let (config, app_group) = {
+    let tauri_config_guard = tauri_config.lock().unwrap();
+    let tauri_config_ = tauri_config_guard.as_ref().unwrap();
+    let (config, _metadata) =
+      get_config(&get_app(tauri_config_), tauri_config_, &Default::default());
+    (
+      config,
+      widgets_app_group(&tauri_config_.tauri.bundle.identifier),
+    )
+  };
+  ensure_init(config.project_dir(), MobileTarget::Ios)?;
+
+  match options.extension {
+    Extension::Widget => {
+      let dest = widget_extension_dir(&config);
+      if dest.exists() {
+        anyhow::bail!("widget extension already exists at {}", dest.display());
+      }
+
+      let (handlebars, mut map) = handlebars(config.app());
+      map.insert("app-group", &app_group);
+      template::render(&handlebars, map.inner(), &WIDGET_TEMPLATE_DIR, &dest)
+        .with_context(|| "failed to process template")?;
+
+      log::info!(action = "Created"; "widget extension at {}, sharing its data with the app in the `{app_group}` App Group", dest.display());
+    }
+  }
+
+  // the project spec only includes the extensions existing when it is generated
+  log::info!("Regenerating project.yml with the extension target");
+  let spec = config.project_dir().join("project.yml");
+  if spec.exists() {
+    remove_file(&spec).with_context(|| format!("failed to remove {}", spec.display()))?;
+  }
+  init_command(MobileTarget::Ios, true, false, true)
+}
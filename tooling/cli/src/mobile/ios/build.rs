@@ -68,8 +68,13 @@ impl From<Options> for BuildOptions {
       features: options.features,
       bundles: None,
       config: options.config,
+      package: None,
       args: Vec::new(),
       ci: false,
+      skip_notarize: false,
+      force_bundle: false,
+      timings: false,
+      format: Default::default(),
     }
   }
 }
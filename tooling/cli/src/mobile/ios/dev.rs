@@ -79,6 +79,9 @@ impl From<Options> for DevOptions {
       no_dev_server: options.no_dev_server,
       port: options.port,
       force_ip_prompt: options.force_ip_prompt,
+      profile: None,
+      no_default_features: false,
+      package: None,
     }
   }
 }
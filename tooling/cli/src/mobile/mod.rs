@@ -5,9 +5,7 @@
 use crate::{
   helpers::{
     app_paths::tauri_dir,
-    config::{
-      get as get_config, reload as reload_config, AppUrl, Config as TauriConfig, WindowUrl,
-    },
+    config::{get as get_config, reload as reload_config, AppUrl, ConfigMetadata, WindowUrl},
   },
   interface::{AppInterface, AppSettings, DevProcess, Interface, Options as InterfaceOptions},
 };
@@ -262,7 +260,7 @@ fn read_options(identifier: &str) -> CliOptions {
   options
 }
 
-pub fn get_app(config: &TauriConfig) -> App {
+pub fn get_app(config: &ConfigMetadata) -> App {
   let mut s = config.tauri.bundle.identifier.rsplit('.');
   let app_name = s.next().unwrap_or("app").to_string();
   let mut domain = String::new();
@@ -10,6 +10,7 @@ mod add;
 mod android;
 mod init;
 mod ios;
+mod new;
 
 #[derive(Parser)]
 #[clap(
@@ -26,6 +27,7 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
+  New(new::Options),
   Init(init::Options),
   Add(add::Options),
   Android(android::Cli),
@@ -34,6 +36,7 @@ enum Commands {
 
 pub fn command(cli: Cli) -> Result<()> {
   match cli.command {
+    Commands::New(options) => new::command(options)?,
     Commands::Init(options) => init::command(options)?,
     Commands::Add(options) => add::command(options)?,
     Commands::Android(cli) => android::command(cli)?,
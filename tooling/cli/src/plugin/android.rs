@@ -63,6 +63,7 @@ pub fn command(cli: Cli) -> Result<()> {
 
       let mut data = BTreeMap::new();
       super::init::plugin_name_data(&mut data, &options.plugin_name);
+      super::init::mobile_data(&mut data, true, out_dir.join("ios").exists());
 
       let mut created_dirs = Vec::new();
       template::render_with_generator(
@@ -97,11 +98,14 @@ tauri-build = "{}"
 "#,
         metadata.tauri_build
       );
-      let build_file = super::init::TEMPLATE_DIR
-        .get_file("build.rs")
-        .unwrap()
-        .contents_utf8()
-        .unwrap();
+      let build_file = handlebars.render_template(
+        super::init::TEMPLATE_DIR
+          .get_file("build.rs")
+          .unwrap()
+          .contents_utf8()
+          .unwrap(),
+        &data,
+      )?;
       let init_fn = format!(
         r#"
 pub fn init<R: Runtime>() -> TauriPlugin<R> {{
@@ -11,7 +11,7 @@ use anyhow::Context;
 use clap::Parser;
 use dialoguer::Input;
 use handlebars::{to_json, Handlebars};
-use heck::{AsKebabCase, ToKebabCase, ToPascalCase, ToSnakeCase};
+use heck::{ToKebabCase, ToPascalCase, ToSnakeCase};
 use include_dir::{include_dir, Dir};
 use log::warn;
 use std::{
@@ -19,7 +19,7 @@ use std::{
   env::current_dir,
   ffi::OsStr,
   fmt::Display,
-  fs::{create_dir_all, remove_dir_all, File, OpenOptions},
+  fs::{create_dir_all, File, OpenOptions},
   path::{Component, Path, PathBuf},
   str::FromStr,
 };
@@ -27,27 +27,37 @@ use std::{
 pub const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/plugin");
 
 #[derive(Debug, Parser)]
-#[clap(about = "Initializes a Tauri plugin project")]
+#[clap(about = "Initializes a Tauri plugin project in an existing directory")]
 pub struct Options {
-  /// Name of your Tauri plugin
+  /// Name of your Tauri plugin.
+  /// If not specified, it will be inferred from the target directory name.
   #[clap(short = 'n', long = "name")]
-  plugin_name: String,
+  pub(crate) plugin_name: Option<String>,
   /// Initializes a Tauri plugin without the TypeScript API
   #[clap(long)]
-  no_api: bool,
+  pub(crate) no_api: bool,
   /// Initializes a Tauri core plugin (internal usage)
   #[clap(long, hide(true))]
-  tauri: bool,
+  pub(crate) tauri: bool,
   /// Set target directory for init
   #[clap(short, long)]
   #[clap(default_value_t = current_dir().expect("failed to read cwd").display().to_string())]
-  directory: String,
+  pub(crate) directory: String,
   /// Path of the Tauri project to use (relative to the cwd)
   #[clap(short, long)]
-  tauri_path: Option<PathBuf>,
+  pub(crate) tauri_path: Option<PathBuf>,
   /// Author name
   #[clap(short, long)]
-  author: Option<String>,
+  pub(crate) author: Option<String>,
+  /// Initializes the Android project of the plugin
+  #[clap(long)]
+  pub(crate) android: bool,
+  /// Initializes the iOS project of the plugin
+  #[clap(long)]
+  pub(crate) ios: bool,
+  /// Initializes both the Android and iOS projects of the plugin
+  #[clap(long)]
+  pub(crate) mobile: bool,
 }
 
 impl Options {
@@ -59,134 +69,170 @@ impl Options {
         "You".into()
       });
     }
+    if self.mobile {
+      self.android = true;
+      self.ios = true;
+    }
   }
 }
 
 pub fn command(mut options: Options) -> Result<()> {
   options.load();
-  let template_target_path = PathBuf::from(options.directory).join(format!(
-    "tauri-plugin-{}",
-    AsKebabCase(&options.plugin_name)
-  ));
+  let template_target_path = PathBuf::from(&options.directory);
+  let plugin_name = match options.plugin_name.take() {
+    Some(name) => name,
+    None => infer_plugin_name(&template_target_path)?,
+  };
   let metadata = crates_metadata()?;
-  if template_target_path.exists() {
-    warn!("Plugin dir ({:?}) not empty.", template_target_path);
+
+  let (tauri_dep, tauri_example_dep, tauri_build_dep) = if let Some(tauri_path) = options.tauri_path
+  {
+    // the plugin and its example live in different directories, so use an absolute path
+    let tauri_path = current_dir()?.join(tauri_path);
+    (
+      format!(
+        r#"{{  path = {:?} }}"#,
+        resolve_tauri_path(&tauri_path, "core/tauri")
+      ),
+      format!(
+        r#"{{  path = {:?} }}"#,
+        resolve_tauri_path(&tauri_path, "core/tauri")
+      ),
+      format!(
+        "{{  path = {:?} }}",
+        resolve_tauri_path(&tauri_path, "core/tauri-build")
+      ),
+    )
   } else {
-    let (tauri_dep, tauri_example_dep, tauri_build_dep) =
-      if let Some(tauri_path) = options.tauri_path {
-        (
-          format!(
-            r#"{{  path = {:?} }}"#,
-            resolve_tauri_path(&tauri_path, "core/tauri")
-          ),
-          format!(
-            r#"{{  path = {:?} }}"#,
-            resolve_tauri_path(&tauri_path, "core/tauri")
-          ),
-          format!(
-            "{{  path = {:?} }}",
-            resolve_tauri_path(&tauri_path, "core/tauri-build")
-          ),
-        )
-      } else {
-        (
-          format!(r#"{{ version = "{}" }}"#, metadata.tauri),
-          format!(r#"{{ version = "{}" }}"#, metadata.tauri),
-          format!(r#"{{ version = "{}" }}"#, metadata.tauri_build),
-        )
-      };
+    (
+      format!(r#"{{ version = "{}" }}"#, metadata.tauri),
+      format!(r#"{{ version = "{}" }}"#, metadata.tauri),
+      format!(r#"{{ version = "{}" }}"#, metadata.tauri_build),
+    )
+  };
 
-    let _ = remove_dir_all(&template_target_path);
-    let mut handlebars = Handlebars::new();
-    handlebars.register_escape_fn(handlebars::no_escape);
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
 
-    let mut data = BTreeMap::new();
-    plugin_name_data(&mut data, &options.plugin_name);
-    data.insert("tauri_dep", to_json(tauri_dep));
-    data.insert("tauri_example_dep", to_json(tauri_example_dep));
-    data.insert("tauri_build_dep", to_json(tauri_build_dep));
-    data.insert("author", to_json(options.author));
+  let mut data = BTreeMap::new();
+  plugin_name_data(&mut data, &plugin_name);
+  mobile_data(&mut data, options.android, options.ios);
+  data.insert("tauri_dep", to_json(tauri_dep));
+  data.insert("tauri_example_dep", to_json(tauri_example_dep));
+  data.insert("tauri_build_dep", to_json(tauri_build_dep));
+  data.insert("author", to_json(options.author));
+  data.insert("no_api", to_json(options.no_api));
 
-    if options.tauri {
-      data.insert(
-        "license_header",
-        to_json(
-          "// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
-             // SPDX-License-Identifier: Apache-2.0
-             // SPDX-License-Identifier: MIT\n\n"
-            .replace("  ", "")
-            .replace(" //", "//"),
-        ),
-      );
-    }
+  if options.tauri {
+    data.insert(
+      "license_header",
+      to_json(
+        "// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+           // SPDX-License-Identifier: Apache-2.0
+           // SPDX-License-Identifier: MIT\n\n"
+          .replace("  ", "")
+          .replace(" //", "//"),
+      ),
+    );
+  }
 
+  let plugin_id = if options.android {
     let plugin_id = request_input(
       "What should be the Android Package ID for your plugin?",
-      Some(format!("com.plugin.{}", options.plugin_name)),
+      Some(format!("com.plugin.{}", plugin_name)),
       false,
       false,
     )?
     .unwrap();
-
     data.insert("android_package_id", to_json(&plugin_id));
+    plugin_id
+  } else {
+    String::new()
+  };
 
-    let mut created_dirs = Vec::new();
-    template::render_with_generator(
-      &handlebars,
-      &data,
-      &TEMPLATE_DIR,
-      &template_target_path,
-      &mut |mut path| {
-        let mut components = path.components();
-        let root = components.next().unwrap();
+  let mut created_dirs = Vec::new();
+  template::render_with_generator(
+    &handlebars,
+    &data,
+    &TEMPLATE_DIR,
+    &template_target_path,
+    &mut |mut path| {
+      let mut components = path.components();
+      let root = components.next().unwrap();
 
-        if let Component::Normal(component) = root {
-          match component.to_str().unwrap() {
-            "__example-api" => {
-              if options.no_api {
-                return Ok(None);
-              } else {
-                path = Path::new("examples").join(components.collect::<PathBuf>());
-              }
+      if let Component::Normal(component) = root {
+        match component.to_str().unwrap() {
+          "__example-api" => {
+            if options.no_api {
+              return Ok(None);
+            } else {
+              path = Path::new("examples").join(components.collect::<PathBuf>());
             }
-            "__example-basic" => {
-              if options.no_api {
-                path = Path::new("examples").join(components.collect::<PathBuf>());
-              } else {
-                return Ok(None);
-              }
-            }
-            "android" => {
-              return generate_android_out_file(
-                &path,
-                &template_target_path,
-                &plugin_id.replace('.', "/"),
-                &mut created_dirs,
-              );
+          }
+          "__example-basic" => {
+            if options.no_api {
+              path = Path::new("examples").join(components.collect::<PathBuf>());
+            } else {
+              return Ok(None);
             }
-            "webview-dist" | "webview-src" | "package.json" => {
-              if options.no_api {
-                return Ok(None);
-              }
+          }
+          "android" => {
+            if !options.android {
+              return Ok(None);
             }
-            _ => (),
+            return generate_android_out_file(
+              &path,
+              &template_target_path,
+              &plugin_id.replace('.', "/"),
+              &mut created_dirs,
+            );
+          }
+          "ios" if !options.ios => return Ok(None),
+          "src" if !(options.android || options.ios) && path.ends_with("mobile.rs") => {
+            return Ok(None)
           }
+          "webview-dist" | "webview-src" | "package.json" if options.no_api => return Ok(None),
+          _ => (),
         }
+      }
+
+      let path = template_target_path.join(path);
+      if path.exists() {
+        warn!("Skipping {}: file already exists.", path.display());
+        return Ok(None);
+      }
+      let parent = path.parent().unwrap().to_path_buf();
+      if !created_dirs.contains(&parent) {
+        create_dir_all(&parent)?;
+        created_dirs.push(parent);
+      }
+      File::create(path).map(Some)
+    },
+  )
+  .with_context(|| "failed to render plugin template")?;
 
-        let path = template_target_path.join(path);
-        let parent = path.parent().unwrap().to_path_buf();
-        if !created_dirs.contains(&parent) {
-          create_dir_all(&parent)?;
-          created_dirs.push(parent);
-        }
-        File::create(path).map(Some)
-      },
-    )
-    .with_context(|| "failed to render plugin Android template")?;
-  }
   Ok(())
 }
 
+/// Infers the plugin name from its directory, e.g. `tauri-plugin-store` is the `store` plugin.
+fn infer_plugin_name(directory: &Path) -> Result<String> {
+  let directory = if directory.is_absolute() {
+    directory.to_path_buf()
+  } else {
+    current_dir()?.join(directory)
+  };
+  let name = directory
+    .file_name()
+    .map(|name| name.to_string_lossy().into_owned())
+    .context("failed to infer the plugin name from the target directory, use the --name option")?;
+  Ok(
+    name
+      .strip_prefix("tauri-plugin-")
+      .map(ToString::to_string)
+      .unwrap_or(name),
+  )
+}
+
 pub fn plugin_name_data(data: &mut BTreeMap<&'static str, serde_json::Value>, plugin_name: &str) {
   data.insert("plugin_name_original", to_json(plugin_name));
   data.insert("plugin_name", to_json(plugin_name.to_kebab_case()));
@@ -200,6 +246,21 @@ pub fn plugin_name_data(data: &mut BTreeMap<&'static str, serde_json::Value>, pl
   );
 }
 
+/// Inserts the template data that enables the mobile projects and their Rust bindings.
+pub fn mobile_data(data: &mut BTreeMap<&'static str, serde_json::Value>, android: bool, ios: bool) {
+  let (mobile_cfg, desktop_cfg) = match (android, ios) {
+    (true, true) => ("mobile", "desktop"),
+    (true, false) => (r#"target_os = "android""#, r#"not(target_os = "android")"#),
+    (false, true) => (r#"target_os = "ios""#, r#"not(target_os = "ios")"#),
+    (false, false) => ("", ""),
+  };
+  data.insert("android", to_json(android));
+  data.insert("ios", to_json(ios));
+  data.insert("mobile", to_json(android || ios));
+  data.insert("mobile_cfg", to_json(mobile_cfg));
+  data.insert("desktop_cfg", to_json(desktop_cfg));
+}
+
 pub fn crates_metadata() -> Result<VersionMetadata> {
   serde_json::from_str::<VersionMetadata>(include_str!("../../metadata.json")).map_err(Into::into)
 }
@@ -56,6 +56,7 @@ pub fn command(cli: Cli) -> Result<()> {
 
       let mut data = BTreeMap::new();
       super::init::plugin_name_data(&mut data, &options.plugin_name);
+      super::init::mobile_data(&mut data, out_dir.join("android").exists(), true);
 
       let mut created_dirs = Vec::new();
       template::render_with_generator(
@@ -91,11 +92,14 @@ tauri-build = "{}"
 "#,
         metadata.tauri_build
       );
-      let build_file = super::init::TEMPLATE_DIR
-        .get_file("build.rs")
-        .unwrap()
-        .contents_utf8()
-        .unwrap();
+      let build_file = handlebars.render_template(
+        super::init::TEMPLATE_DIR
+          .get_file("build.rs")
+          .unwrap()
+          .contents_utf8()
+          .unwrap(),
+        &data,
+      )?;
       let init_fn = format!(
         r#"
 #[cfg(target_os = "ios")]
@@ -0,0 +1,72 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::Parser;
+use heck::AsKebabCase;
+use std::{env::current_dir, fs::create_dir_all, path::PathBuf};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Creates a new Tauri plugin project")]
+pub struct Options {
+  /// Name of your Tauri plugin
+  plugin_name: String,
+  /// Initializes a Tauri plugin without the TypeScript API
+  #[clap(long)]
+  no_api: bool,
+  /// Initializes a Tauri core plugin (internal usage)
+  #[clap(long, hide(true))]
+  tauri: bool,
+  /// Set target directory for the plugin, defaults to `tauri-plugin-<name>` in the cwd
+  #[clap(short, long)]
+  directory: Option<String>,
+  /// Path of the Tauri project to use (relative to the cwd)
+  #[clap(short, long)]
+  tauri_path: Option<PathBuf>,
+  /// Author name
+  #[clap(short, long)]
+  author: Option<String>,
+  /// Initializes the Android project of the plugin
+  #[clap(long)]
+  android: bool,
+  /// Initializes the iOS project of the plugin
+  #[clap(long)]
+  ios: bool,
+  /// Initializes both the Android and iOS projects of the plugin
+  #[clap(long)]
+  mobile: bool,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let directory = match options.directory {
+    Some(directory) => PathBuf::from(directory),
+    None => current_dir()?.join(format!(
+      "tauri-plugin-{}",
+      AsKebabCase(&options.plugin_name)
+    )),
+  };
+
+  if directory
+    .read_dir()
+    .map_or(false, |mut d| d.next().is_some())
+  {
+    return Err(anyhow::anyhow!(
+      "plugin directory {} already exists and is not empty",
+      directory.display()
+    ));
+  }
+  create_dir_all(&directory)?;
+
+  super::init::command(super::init::Options {
+    plugin_name: Some(options.plugin_name),
+    no_api: options.no_api,
+    tauri: options.tauri,
+    directory: directory.display().to_string(),
+    tauri_path: options.tauri_path,
+    author: options.author,
+    android: options.android,
+    ios: options.ios,
+    mobile: options.mobile,
+  })
+}
@@ -0,0 +1,33 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::{Parser, Subcommand};
+
+mod test;
+
+#[derive(Parser)]
+#[clap(
+  author,
+  version,
+  about = "Inspect the filesystem scopes of the app",
+  subcommand_required(true),
+  arg_required_else_help(true)
+)]
+pub struct Cli {
+  #[clap(subcommand)]
+  command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Test(test::Options),
+}
+
+pub fn command(cli: Cli) -> Result<()> {
+  match cli.command {
+    Commands::Test(options) => test::command(options)?,
+  }
+  Ok(())
+}
@@ -0,0 +1,194 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::config::{fs_scopes, get as get_config, FsScope},
+  Result,
+};
+use clap::Parser;
+use log::{info, warn};
+use std::path::{Component, Path, PathBuf};
+use tauri_utils::scope::{check, MatchOptions, Pattern, ScopePattern, ScopePolicy, Verdict};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Check why a path is allowed or denied by the asset protocol and fs plugin scopes")]
+pub struct Options {
+  /// The path to check
+  path: PathBuf,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let config = get_config(None)?;
+  let config_guard = config.lock().unwrap();
+  let config = config_guard.as_ref().unwrap();
+  let identifier = &config.tauri.bundle.identifier;
+
+  let path = if options.path.is_relative() {
+    std::env::current_dir()?.join(&options.path)
+  } else {
+    options.path
+  };
+
+  for (name, scope) in fs_scopes(config) {
+    let allowed = patterns(&scope, scope.allowed_paths(), identifier);
+    let forbidden = scope
+      .forbidden_paths()
+      .map(|paths| patterns(&scope, paths, identifier))
+      .unwrap_or_default();
+
+    let verdict = check(&path, &allowed, &forbidden, &policy(&scope));
+    match verdict {
+      Verdict::Allowed(pattern) => {
+        info!(action = "Allowed"; "{} by the {name} scope pattern `{pattern}`", path.display())
+      }
+      Verdict::Forbidden(pattern) => warn!(
+        "{} is forbidden by the {name} scope pattern `{pattern}`",
+        path.display()
+      ),
+      Verdict::NotAllowed => warn!(
+        "{} doesn't match any allowed pattern of the {name} scope",
+        path.display()
+      ),
+      Verdict::Symlink(link) => warn!(
+        "{} is denied by the {name} scope because {} is a symbolic link",
+        path.display(),
+        link.display()
+      ),
+    }
+  }
+
+  Ok(())
+}
+
+/// Mirrors the scope matching options used by the app.
+fn policy(scope: &FsScope) -> ScopePolicy {
+  let require_literal_leading_dot = match scope {
+    FsScope::Scope {
+      require_literal_leading_dot: Some(require),
+      ..
+    } => *require,
+    // dotfiles are not supposed to be exposed by default on unix
+    _ => cfg!(windows),
+  };
+  ScopePolicy {
+    match_options: MatchOptions {
+      require_literal_separator: true,
+      require_literal_leading_dot,
+      ..Default::default()
+    },
+    precedence: scope.precedence(),
+    symlinks: scope.symlinks(),
+  }
+}
+
+fn patterns(scope: &FsScope, paths: &[PathBuf], identifier: &str) -> Vec<ScopePattern> {
+  let mut patterns = Vec::new();
+  for path in paths {
+    let Some(resolved) = resolve(path, identifier) else {
+      warn!(
+        "Skipping the scope pattern `{}`: its base directory can't be resolved outside the app",
+        path.display()
+      );
+      continue;
+    };
+    let options = scope.pattern_options(path).cloned().unwrap_or_default();
+    match Pattern::new(&resolved.to_string_lossy()) {
+      Ok(pattern) => patterns.push(
+        ScopePattern::from(pattern)
+          .case_sensitive(options.case_sensitive)
+          .max_depth(options.max_depth),
+      ),
+      Err(e) => warn!(
+        "Skipping the invalid scope pattern `{}`: {e}",
+        path.display()
+      ),
+    }
+  }
+  patterns
+}
+
+/// Resolves the base directory variable of a scope pattern like the app path resolver does.
+fn resolve(path: &Path, identifier: &str) -> Option<PathBuf> {
+  let mut resolved = PathBuf::new();
+  let mut components = path.components();
+  match components.next() {
+    Some(Component::Normal(first)) => {
+      match first.to_str().and_then(|f| base_directory(f, identifier)) {
+        Some(dir) => resolved.push(dir?),
+        None => resolved.push(first),
+      }
+    }
+    Some(component) => resolved.push(component),
+    None => (),
+  }
+  for component in components {
+    if let Component::ParentDir = component {
+      continue;
+    }
+    resolved.push(component);
+  }
+  Some(resolved)
+}
+
+/// Returns `None` if the value is not a base directory variable,
+/// and `Some(None)` if the directory can't be resolved.
+fn base_directory(variable: &str, identifier: &str) -> Option<Option<PathBuf>> {
+  let dir = match variable {
+    "$AUDIO" => dirs_next::audio_dir(),
+    "$CACHE" => dirs_next::cache_dir(),
+    "$CONFIG" => dirs_next::config_dir(),
+    "$DATA" => dirs_next::data_dir(),
+    "$LOCALDATA" => dirs_next::data_local_dir(),
+    "$DOCUMENT" => dirs_next::document_dir(),
+    "$DOWNLOAD" => dirs_next::download_dir(),
+    "$PICTURE" => dirs_next::picture_dir(),
+    "$PUBLIC" => dirs_next::public_dir(),
+    "$VIDEO" => dirs_next::video_dir(),
+    "$TEMP" => Some(std::env::temp_dir()),
+    "$APPCONFIG" => dirs_next::config_dir().map(|dir| dir.join(identifier)),
+    "$APPDATA" => dirs_next::data_dir().map(|dir| dir.join(identifier)),
+    "$APPLOCALDATA" => dirs_next::data_local_dir().map(|dir| dir.join(identifier)),
+    "$APPCACHE" => dirs_next::cache_dir().map(|dir| dir.join(identifier)),
+    #[cfg(target_os = "macos")]
+    "$APPLOG" => dirs_next::home_dir().map(|dir| dir.join("Library/Logs").join(identifier)),
+    #[cfg(not(target_os = "macos"))]
+    "$APPLOG" => dirs_next::data_local_dir().map(|dir| dir.join(identifier).join("logs")),
+    "$DESKTOP" => dirs_next::desktop_dir(),
+    "$EXE" => dirs_next::executable_dir(),
+    "$FONT" => dirs_next::font_dir(),
+    "$HOME" => dirs_next::home_dir(),
+    "$RUNTIME" => dirs_next::runtime_dir(),
+    "$TEMPLATE" => dirs_next::template_dir(),
+    // the resource directory depends on where the app is installed
+    "$RESOURCE" => None,
+    _ => return None,
+  };
+  Some(dir)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::resolve;
+  use std::path::{Path, PathBuf};
+
+  #[test]
+  fn resolves_base_directories() {
+    assert_eq!(
+      resolve(Path::new("$TEMP/../app/**"), "com.tauri.dev"),
+      Some(std::env::temp_dir().join("app/**"))
+    );
+    assert_eq!(
+      resolve(Path::new("/mnt/data/*"), "com.tauri.dev"),
+      Some(PathBuf::from("/mnt/data/*"))
+    );
+    assert_eq!(
+      resolve(Path::new("$UNKNOWN/*"), "com.tauri.dev"),
+      Some(PathBuf::from("$UNKNOWN/*"))
+    );
+    assert_eq!(
+      resolve(Path::new("$RESOURCE/assets/*"), "com.tauri.dev"),
+      None
+    );
+  }
+}
@@ -0,0 +1,25 @@
+plugins {
+    id("com.android.library")
+    id("org.jetbrains.kotlin.android")
+}
+
+android {
+    namespace = "{{reverse-domain app.domain}}.{{snake-case app.name}}.widget"
+    compileSdk = 33
+    defaultConfig {
+        minSdk = {{android.min-sdk-version}}
+    }
+    buildFeatures {
+        compose = true
+    }
+    composeOptions {
+        kotlinCompilerExtensionVersion = "1.2.0-rc02"
+    }
+    kotlinOptions {
+        jvmTarget = "1.8"
+    }
+}
+
+dependencies {
+    implementation("androidx.glance:glance-appwidget:1.0.0-alpha05")
+}
@@ -0,0 +1,42 @@
+package {{reverse-domain app.domain}}.{{snake-case app.name}}.widget
+
+import android.content.Context
+import androidx.compose.runtime.Composable
+import androidx.compose.ui.unit.dp
+import androidx.glance.GlanceModifier
+import androidx.glance.LocalContext
+import androidx.glance.appwidget.GlanceAppWidget
+import androidx.glance.appwidget.GlanceAppWidgetReceiver
+import androidx.glance.layout.Alignment
+import androidx.glance.layout.Column
+import androidx.glance.layout.fillMaxSize
+import androidx.glance.layout.padding
+import androidx.glance.text.Text
+import org.json.JSONObject
+
+/**
+ * Reads the data written by the app with `tauri::widgets::set_data`, serialized as JSON.
+ */
+fun widgetData(context: Context, key: String): String? {
+  return context.getSharedPreferences("tauri_widgets", Context.MODE_PRIVATE).getString(key, null)
+}
+
+class AppWidget : GlanceAppWidget() {
+  @Composable
+  override fun Content() {
+    // e.g. `tauri::widgets::set_data(&app, "widget", &json!({ "title": "3 tasks left" }))`
+    val title = widgetData(LocalContext.current, "widget")?.let { JSONObject(it).optString("title") }
+    Column(
+      modifier = GlanceModifier.fillMaxSize().padding(16.dp),
+      verticalAlignment = Alignment.CenterVertically,
+      horizontalAlignment = Alignment.CenterHorizontally
+    ) {
+      Text(text = title ?: "{{app.stylized-name}}")
+    }
+  }
+}
+
+// updates the widgets when the app calls `tauri::widgets::reload`
+class AppWidgetReceiver : GlanceAppWidgetReceiver() {
+  override val glanceAppWidget: GlanceAppWidget = AppWidget()
+}
@@ -4,6 +4,8 @@ plugins {
     id("rust")
     {{~#each android-app-plugins}}
     id("{{this}}"){{/each}}
+    {{~#if android-push}}
+    id("com.google.gms.google-services"){{/if}}
 }
 
 android {
@@ -55,6 +57,12 @@ dependencies {
     implementation("androidx.webkit:webkit:1.6.1")
     implementation("androidx.appcompat:appcompat:1.6.1")
     implementation("com.google.android.material:material:1.8.0")
+    {{~#if android-push}}
+    implementation("com.google.firebase:firebase-messaging:23.1.2"){{/if}}
+    {{~#if android-in-app-purchases}}
+    implementation("com.android.billingclient:billing:5.2.0"){{/if}}
+    {{~#if android-background-tasks}}
+    implementation("androidx.work:work-runtime-ktx:2.8.1"){{/if}}
     testImplementation("junit:junit:4.13.2")
     androidTestImplementation("androidx.test.ext:junit:1.1.4")
     androidTestImplementation("androidx.test.espresso:espresso-core:3.5.0")
@@ -1,3 +1,15 @@
 package {{reverse-domain app.domain}}.{{snake-case app.name}}
+{{#if android-edge-to-edge}}
+import android.os.Bundle
+import androidx.core.view.WindowCompat
 
+class MainActivity : TauriActivity() {
+  override fun onCreate(savedInstanceState: Bundle?) {
+    // lay out the webview behind the system bars
+    WindowCompat.setDecorFitsSystemWindows(window, false)
+    super.onCreate(savedInstanceState)
+  }
+}
+{{else}}
 class MainActivity : TauriActivity()
+{{/if}}
@@ -8,6 +8,8 @@ buildscript {
         classpath("org.jetbrains.kotlin:kotlin-gradle-plugin:1.6.21")
         {{~#each android-project-dependencies}}
         classpath("{{this}}"){{/each}}
+        {{~#if android-push}}
+        classpath("com.google.gms:google-services:4.3.15"){{/if}}
     }
 }
 
@@ -0,0 +1,59 @@
+import SwiftUI
+import WidgetKit
+
+/// The App Group shared with the app, where `tauri::widgets::set_data` writes the data.
+let appGroup = "{{app-group}}"
+
+/// Reads the data written by the app with `tauri::widgets::set_data`.
+func widgetData<T: Decodable>(_ key: String, as type: T.Type) -> T? {
+	guard let json = UserDefaults(suiteName: appGroup)?.string(forKey: key) else {
+		return nil
+	}
+	return try? JSONDecoder().decode(type, from: Data(json.utf8))
+}
+
+/// The data of the widget, e.g. `tauri::widgets::set_data(&app, "widget", &json!({ "title": "3 tasks left" }))`.
+struct WidgetData: Decodable {
+	var title: String
+}
+
+struct Entry: TimelineEntry {
+	let date: Date
+	let data: WidgetData?
+}
+
+struct Provider: TimelineProvider {
+	func placeholder(in context: Context) -> Entry {
+		Entry(date: Date(), data: nil)
+	}
+
+	func getSnapshot(in context: Context, completion: @escaping (Entry) -> Void) {
+		completion(Entry(date: Date(), data: widgetData("widget", as: WidgetData.self)))
+	}
+
+	func getTimeline(in context: Context, completion: @escaping (Timeline<Entry>) -> Void) {
+		// the timeline is reloaded when the app calls `tauri::widgets::reload`
+		let entry = Entry(date: Date(), data: widgetData("widget", as: WidgetData.self))
+		completion(Timeline(entries: [entry], policy: .never))
+	}
+}
+
+struct WidgetView: View {
+	var entry: Entry
+
+	var body: some View {
+		Text(entry.data?.title ?? "{{app.stylized-name}}")
+			.padding()
+	}
+}
+
+@main
+struct AppWidget: Widget {
+	var body: some WidgetConfiguration {
+		StaticConfiguration(kind: "{{app.name}}_Widget", provider: Provider()) { entry in
+			WidgetView(entry: entry)
+		}
+		.configurationDisplayName("{{app.stylized-name}}")
+		.supportedFamilies([.systemSmall, .systemMedium])
+	}
+}
@@ -2,8 +2,12 @@ use std::process::exit;
 
 fn main() {
   if let Err(error) = tauri_build::mobile::PluginBuilder::new()
+{{#if android}}
     .android_path("android")
+{{/if}}
+{{#if ios}}
     .ios_path("ios")
+{{/if}}
     .run()
   {
     println!("{error:#}");
@@ -10,10 +10,14 @@ use std::{collections::HashMap, sync::Mutex};
 
 pub use models::*;
 
-#[cfg(desktop)]
+{{#if mobile}}
+#[cfg({{ desktop_cfg }})]
 mod desktop;
-#[cfg(mobile)]
+#[cfg({{ mobile_cfg }})]
 mod mobile;
+{{else}}
+mod desktop;
+{{/if}}
 
 mod commands;
 mod error;
@@ -21,15 +25,33 @@ mod models;
 
 pub use error::{Error, Result};
 
-#[cfg(desktop)]
+{{#if mobile}}
+#[cfg({{ desktop_cfg }})]
 use desktop::{{ plugin_name_pascal_case }};
-#[cfg(mobile)]
+#[cfg({{ mobile_cfg }})]
 use mobile::{{ plugin_name_pascal_case }};
+{{else}}
+use desktop::{{ plugin_name_pascal_case }};
+{{/if}}
 
 #[derive(Default)]
 struct MyState(Mutex<HashMap<String, String>>);
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`] and [`tauri::Window`] to access the {{ plugin_name }} APIs.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri_plugin_{{ plugin_name_snake_case }}::{PingRequest, {{ plugin_name_pascal_case }}Ext};
+///
+/// fn ping<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri_plugin_{{ plugin_name_snake_case }}::Result<()> {
+///   let response = app.{{ plugin_name_snake_case }}().ping(PingRequest {
+///     value: Some("ping".into()),
+///   })?;
+///   assert_eq!(response.value.as_deref(), Some("ping"));
+///   Ok(())
+/// }
+/// ```
 pub trait {{ plugin_name_pascal_case }}Ext<R: Runtime> {
   fn {{ plugin_name_snake_case }}(&self) -> &{{ plugin_name_pascal_case }}<R>;
 }
@@ -41,14 +63,26 @@ impl<R: Runtime, T: Manager<R>> crate::{{ plugin_name_pascal_case }}Ext<R> for T
 }
 
 /// Initializes the plugin.
+///
+/// # Examples
+///
+/// ```rust
+/// fn app<R: tauri::Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+///   builder.plugin(tauri_plugin_{{ plugin_name_snake_case }}::init())
+/// }
+/// ```
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("{{ plugin_name }}")
     .invoke_handler(tauri::generate_handler![commands::execute])
     .setup(|app, api| {
-      #[cfg(mobile)]
+{{#if mobile}}
+      #[cfg({{ mobile_cfg }})]
       let {{ plugin_name_snake_case }} = mobile::init(app, api)?;
-      #[cfg(desktop)]
+      #[cfg({{ desktop_cfg }})]
+      let {{ plugin_name_snake_case }} = desktop::init(app, api)?;
+{{else}}
       let {{ plugin_name_snake_case }} = desktop::init(app, api)?;
+{{/if}}
       app.manage({{ plugin_name_snake_case }});
 
       // manage state so it is accessible by the commands
@@ -3,6 +3,15 @@
 {{/if}}
 import { invoke } from '@tauri-apps/api/tauri'
 
-export async function execute() {
+/**
+ * Runs the `execute` command of the {{ plugin_name }} plugin.
+ *
+ * @example
+ * ```typescript
+ * import { execute } from 'tauri-plugin-{{ plugin_name }}-api'
+ * await execute()
+ * ```
+ */
+export async function execute(): Promise<void> {
   await invoke('plugin:{{ plugin_name }}|execute')
 }
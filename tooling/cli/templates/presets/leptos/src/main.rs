@@ -0,0 +1,15 @@
+use leptos::*;
+
+#[component]
+fn App(cx: Scope) -> impl IntoView {
+  view! { cx,
+    <main>
+      <h1>"Welcome to {{ app_name }}"</h1>
+      <p>"Edit src/main.rs and save to reload."</p>
+    </main>
+  }
+}
+
+fn main() {
+  mount_to_body(|cx| view! { cx, <App/> })
+}
@@ -0,0 +1,9 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  // Tauri serves the static export in the `out` folder, it can't run a Node.js server
+  output: 'export',
+  // the image optimization API requires a server
+  images: {
+    unoptimized: true
+  }
+}
@@ -0,0 +1,9 @@
+export default function Home() {
+  // the Tauri APIs use `window`, call them from client components
+  return (
+    <main>
+      <h1>Welcome to {{ app_name }}</h1>
+      <p>Edit src/app/page.jsx and save to reload.</p>
+    </main>
+  )
+}
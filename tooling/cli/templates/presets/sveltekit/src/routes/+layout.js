@@ -0,0 +1,3 @@
+// Tauri has no server, every page is prerendered and rendered on the client
+export const prerender = true
+export const ssr = false
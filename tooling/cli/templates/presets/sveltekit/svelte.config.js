@@ -0,0 +1,11 @@
+import adapter from '@sveltejs/adapter-static'
+import { vitePreprocess } from '@sveltejs/kit/vite'
+
+/** @type {import('@sveltejs/kit').Config} */
+export default {
+  preprocess: vitePreprocess(),
+  kit: {
+    // Tauri serves the prerendered pages in the `build` folder, it can't run a Node.js server
+    adapter: adapter()
+  }
+}
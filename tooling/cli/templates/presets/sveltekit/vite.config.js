@@ -0,0 +1,15 @@
+import { sveltekit } from '@sveltejs/kit/vite'
+import { defineConfig } from 'vite'
+
+export default defineConfig({
+  plugins: [sveltekit()],
+  // prevent vite from obscuring the Rust errors
+  clearScreen: false,
+  server: {
+    // the port of the `devPath`, Tauri expects a fixed port
+    port: 1420,
+    strictPort: true
+  },
+  // expose the `TAURI_PLATFORM`, `TAURI_DEBUG` and other variables set by the Tauri CLI
+  envPrefix: ['VITE_', 'TAURI_']
+})
@@ -0,0 +1,5 @@
+// use `invoke` from `@tauri-apps/api/tauri` to call the Rust commands
+const greeting = document.querySelector('#greeting')
+if (greeting) {
+  greeting.textContent = 'Edit src/main.ts and save to reload.'
+}
@@ -0,0 +1,19 @@
+import { defineConfig } from 'vite'
+
+export default defineConfig({
+  // prevent vite from obscuring the Rust errors
+  clearScreen: false,
+  server: {
+    // the port of the `devPath`, Tauri expects a fixed port
+    port: 1420,
+    strictPort: true
+  },
+  // expose the `TAURI_PLATFORM`, `TAURI_DEBUG` and other variables set by the Tauri CLI
+  envPrefix: ['VITE_', 'TAURI_'],
+  build: {
+    // Tauri uses Chromium on Windows and WebKit on macOS and Linux
+    target: process.env.TAURI_PLATFORM == 'windows' ? 'chrome105' : 'safari13',
+    minify: !process.env.TAURI_DEBUG ? 'esbuild' : false,
+    sourcemap: !!process.env.TAURI_DEBUG
+  }
+})
@@ -0,0 +1,15 @@
+use yew::prelude::*;
+
+#[function_component(App)]
+fn app() -> Html {
+  html! {
+    <main>
+      <h1>{ "Welcome to {{ app_name }}" }</h1>
+      <p>{ "Edit src/main.rs and save to reload." }</p>
+    </main>
+  }
+}
+
+fn main() {
+  yew::Renderer::<App>::new().render();
+}